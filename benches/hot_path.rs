@@ -0,0 +1,76 @@
+//! Benchmarks for the price-update → basis-recalc hot path
+//!
+//! Exercises `SharedState` directly, as the event processor does on every tick.
+//!
+//! Run with: cargo bench --bench hot_path
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sol_basis_bot::state::SharedState;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+fn primed_state() -> SharedState {
+    let state = SharedState::new();
+    state.update_spot_price(150.0);
+    state.update_perp_mark_price(150.3);
+    state.update_funding_rate(0.0001);
+    state
+}
+
+/// One tick: apply the price, recompute basis, publish the snapshot, record history
+fn bench_price_tick(c: &mut Criterion) {
+    let state = primed_state();
+    let mut price = 150.0;
+
+    c.bench_function("spot_price_tick", |b| {
+        b.iter(|| {
+            price += 0.0001;
+            state.update_spot_price(black_box(price));
+        })
+    });
+
+    c.bench_function("perp_price_tick", |b| {
+        b.iter(|| {
+            price += 0.0001;
+            state.update_perp_mark_price(black_box(price));
+        })
+    });
+}
+
+/// Readers on the hot path: the full snapshot versus a single atomic
+fn bench_reads(c: &mut Criterion) {
+    let state = primed_state();
+
+    c.bench_function("market_snapshot", |b| b.iter(|| black_box(state.snapshot())));
+    c.bench_function("basis_spread_load", |b| b.iter(|| black_box(state.get_basis_spread())));
+}
+
+/// Ticks while another thread keeps reading snapshots
+fn bench_contended_tick(c: &mut Criterion) {
+    let state = Arc::new(primed_state());
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let reader = {
+        let (state, stop) = (state.clone(), stop.clone());
+        thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                black_box(state.snapshot());
+            }
+        })
+    };
+
+    let mut price = 150.0;
+    c.bench_function("spot_price_tick_contended", |b| {
+        b.iter(|| {
+            price += 0.0001;
+            state.update_spot_price(black_box(price));
+        })
+    });
+
+    stop.store(true, Ordering::Relaxed);
+    reader.join().unwrap();
+}
+
+criterion_group!(benches, bench_price_tick, bench_reads, bench_contended_tick);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+//! Benchmarks for event-to-state latency
+//!
+//! Publishes market data on the `EventBus` and applies it with
+//! `SharedState::apply_market_event`, the call the bot's event processor
+//! makes for every event it receives.
+//!
+//! Run with: cargo bench --bench latency
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sol_basis_bot::network::{Event, EventBus};
+use sol_basis_bot::state::SharedState;
+use sol_basis_bot::utils::types::{PriceSource, PriceUpdate};
+
+fn price_event(seq: u64) -> Event {
+    let price = 150.0 + (seq % 1_000) as f64 * 0.0001;
+    let update = |source, price| PriceUpdate { source, price, confidence: None, timestamp: 0 };
+    match seq % 3 {
+        0 => Event::SpotPriceUpdate(update(PriceSource::Pyth, price)),
+        1 => Event::PerpMarkPriceUpdate(update(PriceSource::DriftMark, price * 1.002)),
+        _ => Event::FundingRateUpdate { rate: 0.0001, timestamp: 0 },
+    }
+}
+
+/// Applying an event already received
+fn bench_apply(c: &mut Criterion) {
+    let state = SharedState::new();
+    let mut seq = 0u64;
+
+    c.bench_function("apply_market_event", |b| {
+        b.iter(|| {
+            seq += 1;
+            black_box(state.apply_market_event(black_box(&price_event(seq))));
+        })
+    });
+}
+
+/// Publish, receive and apply, with other subscribers holding the bus
+fn bench_publish_to_state(c: &mut Criterion) {
+    let state = SharedState::new();
+    let bus = EventBus::new(2048);
+    let sender = bus.sender();
+    let mut rx = bus.subscribe();
+    let mut others: Vec<_> = (0..2).map(|_| bus.subscribe()).collect();
+    let mut seq = 0u64;
+
+    c.bench_function("publish_to_state", |b| {
+        b.iter(|| {
+            seq += 1;
+            let _ = sender.send(price_event(seq));
+            let event = rx.try_recv().unwrap();
+            black_box(state.apply_market_event(&event));
+            for other in &mut others {
+                let _ = other.try_recv();
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_apply, bench_publish_to_state);
+criterion_main!(benches);
@@ -0,0 +1,9 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // gRPC stubs are only needed (and protoc only required) with the grpc feature
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/control.proto");
+        tonic_build::compile_protos("proto/control.proto")?;
+    }
+    Ok(())
+}
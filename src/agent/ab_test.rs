@@ -0,0 +1,174 @@
+//! A/B Shadow Evaluation
+//!
+//! A named strategy with `shadow: true` runs an alternative parameter set
+//! on paper next to the primary agent, on the same market data, recording
+//! its hypothetical trades under `shadow/<name>/`. The shadow is compared
+//! with the primary over trades opened since the shadow first ran:
+//! - Per arm: trades, win rate, net and average P&L, profit factor and
+//!   worst trade
+//! - Deltas: shadow minus primary
+//!
+//! The first run is kept in a `since` file in the shadow's store, so the
+//! window survives restarts. Once the shadow has proven itself, the
+//! `promote-shadow` command writes its overrides as a config profile.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::agentic::TradeOutcome;
+
+/// One arm of the comparison
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ArmMetrics {
+    pub trades: u32,
+    /// Win rate (0-1)
+    pub win_rate: f64,
+    pub net_pnl: f64,
+    pub avg_pnl: f64,
+    /// Gross profit over gross loss
+    pub profit_factor: f64,
+    pub worst_trade: f64,
+}
+
+impl ArmMetrics {
+    /// Metrics over the trades opened at or after `since`
+    pub fn compute(trades: &[TradeOutcome], since: i64) -> Self {
+        let trades: Vec<&TradeOutcome> = trades.iter().filter(|t| t.open_time >= since).collect();
+        if trades.is_empty() {
+            return Self::default();
+        }
+        let gross_profit: f64 = trades.iter().filter(|t| t.total_pnl > 0.0).map(|t| t.total_pnl).sum();
+        let gross_loss: f64 = trades.iter().filter(|t| t.total_pnl < 0.0).map(|t| -t.total_pnl).sum();
+        let net_pnl = gross_profit - gross_loss;
+        Self {
+            trades: trades.len() as u32,
+            win_rate: trades.iter().filter(|t| t.is_winner).count() as f64 / trades.len() as f64,
+            net_pnl,
+            avg_pnl: net_pnl / trades.len() as f64,
+            profit_factor: if gross_loss > 0.0 {
+                gross_profit / gross_loss
+            } else if gross_profit > 0.0 {
+                f64::INFINITY
+            } else {
+                0.0
+            },
+            worst_trade: trades.iter().map(|t| t.total_pnl).fold(f64::INFINITY, f64::min),
+        }
+    }
+}
+
+/// Shadow against primary over the same window
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowComparison {
+    pub strategy: String,
+    /// Window start: the shadow's first run (ms)
+    pub since: i64,
+    pub primary: ArmMetrics,
+    pub shadow: ArmMetrics,
+    /// Shadow minus primary net P&L (USD)
+    pub net_pnl_delta: f64,
+    /// Shadow minus primary win rate
+    pub win_rate_delta: f64,
+    /// The shadow's overrides, as they would be promoted
+    pub overrides: serde_yaml::Value,
+}
+
+impl ShadowComparison {
+    pub fn compute(
+        strategy: &str,
+        overrides: serde_yaml::Value,
+        since: i64,
+        primary: &[TradeOutcome],
+        shadow: &[TradeOutcome],
+    ) -> Self {
+        let primary = ArmMetrics::compute(primary, since);
+        let shadow = ArmMetrics::compute(shadow, since);
+        Self {
+            strategy: strategy.to_string(),
+            since,
+            net_pnl_delta: shadow.net_pnl - primary.net_pnl,
+            win_rate_delta: shadow.win_rate - primary.win_rate,
+            primary,
+            shadow,
+            overrides,
+        }
+    }
+
+    /// Human-readable report
+    pub fn summary(&self) -> String {
+        let since = chrono::DateTime::from_timestamp_millis(self.since).map(|t| t.to_rfc3339()).unwrap_or_default();
+        let arm = |name: &str, m: &ArmMetrics| {
+            format!(
+                "  {:<8} {:>4} trades  win {:>5.1}%  net ${:>10.2}  avg ${:>8.2}  PF {:>5.2}\n",
+                name,
+                m.trades,
+                m.win_rate * 100.0,
+                m.net_pnl,
+                m.avg_pnl,
+                m.profit_factor
+            )
+        };
+        format!(
+            "Shadow {} vs primary since {}\n{}{}  delta    net ${:+.2}  win {:+.1}pp\n",
+            self.strategy,
+            since,
+            arm("primary", &self.primary),
+            arm("shadow", &self.shadow),
+            self.net_pnl_delta,
+            self.win_rate_delta * 100.0
+        )
+    }
+}
+
+/// `since` file in the shadow store at `db_path`
+fn since_path(db_path: &str) -> PathBuf {
+    Path::new(db_path).with_file_name("since")
+}
+
+/// When the shadow storing at `db_path` first ran (ms), None if it never did
+pub fn read_since(db_path: &str) -> Result<Option<i64>> {
+    match std::fs::read_to_string(since_path(db_path)) {
+        Ok(content) => Ok(Some(content.trim().parse().context("Invalid shadow since file")?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).context("Failed to read shadow since file"),
+    }
+}
+
+/// The shadow's first run, recorded as `now` if this is it
+pub fn mark_since(db_path: &str, now: i64) -> Result<i64> {
+    if let Some(since) = read_since(db_path)? {
+        return Ok(since);
+    }
+    let path = since_path(db_path);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create shadow store")?;
+    }
+    std::fs::write(&path, now.to_string()).context("Failed to write shadow since file")?;
+    Ok(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(open_time: i64, pnl: f64) -> TradeOutcome {
+        TradeOutcome { open_time, total_pnl: pnl, is_winner: pnl > 0.0, ..Default::default() }
+    }
+
+    #[test]
+    fn test_comparison_over_shadow_window() {
+        // The primary's trade before the shadow started is left out
+        let primary = vec![trade(500, 100.0), trade(1_000, 20.0), trade(2_000, -10.0)];
+        let shadow = vec![trade(1_000, 25.0), trade(2_000, 5.0)];
+
+        let comparison = ShadowComparison::compute("tight-stops", serde_yaml::Value::Null, 1_000, &primary, &shadow);
+        assert_eq!(comparison.primary.trades, 2);
+        assert!((comparison.primary.net_pnl - 10.0).abs() < 1e-9);
+        assert!((comparison.primary.profit_factor - 2.0).abs() < 1e-9);
+        assert_eq!(comparison.shadow.profit_factor, f64::INFINITY);
+        assert!((comparison.net_pnl_delta - 20.0).abs() < 1e-9);
+        assert!((comparison.win_rate_delta - 0.5).abs() < 1e-9);
+        assert_eq!(comparison.primary.worst_trade, -10.0);
+    }
+}
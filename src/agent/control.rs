@@ -0,0 +1,178 @@
+//! Operator Controls
+//!
+//! Commands from outside the agent loop (gRPC, control API):
+//! - Operator pause: holds the agent paused until explicitly resumed
+//! - Live hold: pauses the agent like an operator pause until live trading
+//!   is confirmed (see `live_interlock`); `resume` does not lift it
+//! - Size override: fixed trade size in place of adaptive sizing
+//! - Close all: close the open position on the next loop iteration
+//! - Manual commands: open a position of a given size, close a specific
+//!   trade, or move the hedge; one at a time, run by the agent loop through
+//!   the same risk checks and execution path as its own trades
+
+use anyhow::{bail, ensure, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::info;
+
+/// Operator trade command, executed by the agent loop
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ManualCommand {
+    /// Open a basis position of `size_sol` (needs the agent idle)
+    Open { size_sol: f64 },
+    /// Close the open position if it belongs to `trade_id`
+    Close { trade_id: String },
+    /// Trade the perp leg by `perp_delta_sol` (positive reduces the short)
+    AdjustHedge { perp_delta_sol: f64 },
+}
+
+/// Pause reason while live trading awaits confirmation
+const LIVE_HOLD_REASON: &str = "Awaiting live trading confirmation";
+
+/// Operator command state shared with the agent loop
+#[derive(Debug, Default)]
+pub struct AgentControl {
+    /// Operator pause reason (None = not paused by the operator)
+    pause_reason: RwLock<Option<String>>,
+    /// Fixed trade size (SOL), overriding adaptive sizing
+    size_override: RwLock<Option<f64>>,
+    /// Close requested, not yet acted on
+    close_requested: AtomicBool,
+    /// Manual command submitted, not yet acted on
+    manual: RwLock<Option<ManualCommand>>,
+    /// Live trading not yet confirmed
+    live_hold: AtomicBool,
+}
+
+impl AgentControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pause trading until `resume` is called
+    pub fn pause(&self, reason: &str) {
+        info!("Operator pause: {}", reason);
+        *self.pause_reason.write() = Some(reason.to_string());
+    }
+
+    /// Lift an operator pause
+    pub fn resume(&self) {
+        info!("Operator resume");
+        *self.pause_reason.write() = None;
+    }
+
+    /// Whether the operator has paused trading, or live trading is held
+    pub fn is_paused(&self) -> bool {
+        self.pause_reason.read().is_some() || self.is_live_held()
+    }
+
+    /// Operator pause reason, if paused
+    pub fn pause_reason(&self) -> Option<String> {
+        self.pause_reason.read().clone().or_else(|| self.is_live_held().then(|| LIVE_HOLD_REASON.to_string()))
+    }
+
+    /// Hold trading until `release_live`
+    pub fn hold_for_live(&self) {
+        self.live_hold.store(true, Ordering::SeqCst);
+    }
+
+    /// Lift the live hold, once live trading is confirmed
+    pub fn release_live(&self) {
+        self.live_hold.store(false, Ordering::SeqCst);
+    }
+
+    /// Whether live trading awaits confirmation
+    pub fn is_live_held(&self) -> bool {
+        self.live_hold.load(Ordering::SeqCst)
+    }
+
+    /// Set (or clear with None) a fixed trade size
+    pub fn set_size(&self, size_sol: Option<f64>) {
+        info!("Operator size override: {:?}", size_sol);
+        *self.size_override.write() = size_sol;
+    }
+
+    /// Fixed trade size, if set
+    pub fn size_override(&self) -> Option<f64> {
+        *self.size_override.read()
+    }
+
+    /// Ask the agent to close all positions
+    pub fn request_close_all(&self) {
+        info!("Operator close-all requested");
+        self.close_requested.store(true, Ordering::SeqCst);
+    }
+
+    /// Take a pending close request, clearing it
+    pub fn take_close_request(&self) -> bool {
+        self.close_requested.swap(false, Ordering::SeqCst)
+    }
+
+    /// Queue a manual command; fails if one is already waiting
+    pub fn submit(&self, command: ManualCommand) -> Result<()> {
+        match &command {
+            ManualCommand::Open { size_sol } => {
+                ensure!(size_sol.is_finite() && *size_sol > 0.0, "size_sol must be positive");
+            }
+            ManualCommand::Close { trade_id } => ensure!(!trade_id.is_empty(), "trade_id is required"),
+            ManualCommand::AdjustHedge { perp_delta_sol } => {
+                ensure!(
+                    perp_delta_sol.is_finite() && *perp_delta_sol != 0.0,
+                    "perp_delta_sol must be non-zero"
+                );
+            }
+        }
+
+        let mut pending = self.manual.write();
+        if let Some(existing) = pending.as_ref() {
+            bail!("Manual command already pending: {:?}", existing);
+        }
+        info!("Operator manual command: {:?}", command);
+        *pending = Some(command);
+        Ok(())
+    }
+
+    /// Manual command waiting to run, if any
+    pub fn pending_manual(&self) -> Option<ManualCommand> {
+        self.manual.read().clone()
+    }
+
+    /// Take the pending manual command, clearing it
+    pub fn take_manual(&self) -> Option<ManualCommand> {
+        self.manual.write().take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_close_request_is_taken_once() {
+        let control = AgentControl::new();
+        control.request_close_all();
+        assert!(control.take_close_request());
+        assert!(!control.take_close_request());
+
+        control.pause("maintenance");
+        assert_eq!(control.pause_reason().as_deref(), Some("maintenance"));
+        control.resume();
+        assert!(!control.is_paused());
+    }
+
+    #[test]
+    fn test_manual_commands_queue_one_at_a_time() {
+        let control = AgentControl::new();
+        assert!(control.submit(ManualCommand::Open { size_sol: 0.0 }).is_err());
+
+        control.submit(ManualCommand::Open { size_sol: 5.0 }).unwrap();
+        assert!(control.submit(ManualCommand::AdjustHedge { perp_delta_sol: 0.5 }).is_err());
+        assert_eq!(control.take_manual(), Some(ManualCommand::Open { size_sol: 5.0 }));
+        assert!(control.take_manual().is_none());
+
+        control.submit(ManualCommand::Close { trade_id: "t1".to_string() }).unwrap();
+        assert!(control.pending_manual().is_some());
+    }
+}
@@ -0,0 +1,126 @@
+//! Entry Laddering
+//!
+//! Instead of one all-in entry, a position can be built in tranches as the
+//! basis gets richer against its own history:
+//! - Each level adds its fraction of the sized position once the basis
+//!   reaches the level's percentile (from the basis engine)
+//! - The tranches already reached go in with the open signal; the rest are
+//!   added while the position is monitored
+//! - Tranches still waiting when the position closes are dropped
+//!
+//! The tranches make up one position: one trade id, blended entry prices
+//! and one recorded outcome.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::LadderLevel;
+
+/// One planned share of a laddered position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Tranche {
+    /// Basis percentile that triggers the tranche (0-100)
+    pub percentile: f64,
+    /// Planned size (SOL)
+    pub size_sol: f64,
+    /// When the tranche was entered (ms)
+    pub filled_at: Option<i64>,
+    /// Basis when the tranche was entered (%)
+    pub entry_basis: Option<f64>,
+}
+
+/// Tranches of one position, by ascending percentile
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntryLadder {
+    pub tranches: Vec<Tranche>,
+}
+
+impl EntryLadder {
+    /// Split `size_sol` over the configured levels
+    pub fn plan(levels: &[LadderLevel], size_sol: f64) -> Self {
+        let tranches = levels
+            .iter()
+            .map(|level| Tranche {
+                percentile: level.percentile,
+                size_sol: size_sol * level.fraction,
+                filled_at: None,
+                entry_basis: None,
+            })
+            .collect();
+        Self { tranches }
+    }
+
+    /// Enter every waiting tranche the basis `richness` has reached; returns their total size
+    pub fn fill_due(&mut self, richness: f64, basis: f64, now: i64) -> f64 {
+        let mut size = 0.0;
+        for tranche in self.tranches.iter_mut().filter(|t| t.filled_at.is_none() && richness >= t.percentile) {
+            tranche.filled_at = Some(now);
+            tranche.entry_basis = Some(basis);
+            size += tranche.size_sol;
+        }
+        size
+    }
+
+    /// Size entered so far (SOL)
+    pub fn filled_size(&self) -> f64 {
+        self.tranches.iter().filter(|t| t.filled_at.is_some()).map(|t| t.size_sol).sum()
+    }
+
+    /// Whether every tranche has been entered
+    pub fn is_complete(&self) -> bool {
+        self.tranches.iter().all(|t| t.filled_at.is_some())
+    }
+
+    /// Percentile of the next waiting tranche
+    pub fn next_level(&self) -> Option<f64> {
+        self.tranches.iter().find(|t| t.filled_at.is_none()).map(|t| t.percentile)
+    }
+
+    /// One-line summary for logs
+    pub fn summary(&self) -> String {
+        let tranches: Vec<String> = self
+            .tranches
+            .iter()
+            .map(|t| format!("{:.2} SOL @ p{:.0}{}", t.size_sol, t.percentile, if t.filled_at.is_some() { " (in)" } else { "" }))
+            .collect();
+        tranches.join(", ")
+    }
+}
+
+/// How rich the basis is for a trade in its direction (0-100): the
+/// percentile for a positive basis, its complement for a negative one
+pub fn richness(basis: f64, percentile: f64) -> f64 {
+    if basis >= 0.0 {
+        percentile
+    } else {
+        100.0 - percentile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels() -> Vec<LadderLevel> {
+        vec![
+            LadderLevel { percentile: 70.0, fraction: 0.25 },
+            LadderLevel { percentile: 85.0, fraction: 0.5 },
+            LadderLevel { percentile: 95.0, fraction: 0.25 },
+        ]
+    }
+
+    #[test]
+    fn test_tranches_fill_as_basis_richens() {
+        let mut ladder = EntryLadder::plan(&levels(), 100.0);
+        assert_eq!(ladder.fill_due(60.0, 0.2, 0), 0.0);
+        assert_eq!(ladder.fill_due(72.0, 0.3, 1), 25.0);
+        assert_eq!(ladder.next_level(), Some(85.0));
+
+        // A jump past several levels enters them together, once
+        assert_eq!(ladder.fill_due(97.0, 0.6, 2), 75.0);
+        assert_eq!(ladder.fill_due(99.0, 0.7, 3), 0.0);
+        assert!(ladder.is_complete());
+        assert_eq!(ladder.filled_size(), 100.0);
+
+        assert_eq!(richness(-0.3, 10.0), 90.0);
+    }
+}
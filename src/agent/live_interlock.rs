@@ -0,0 +1,147 @@
+//! Live Trading Interlock
+//!
+//! A bot started for live trading (`paper_trading: false`) never trades on
+//! the config flag alone. A second operator must confirm through
+//! `POST /live/confirm`, presenting `api.live_confirmation.confirm_token`,
+//! a token separate from the API's bearer token:
+//! - Until then the agent is held as under an operator pause, which an
+//!   operator resume does not lift
+//! - The hold, every rejected attempt and the confirmation are logged and
+//!   raise an alert
+//!
+//! A confirmation lasts for the process; after a restart live trading needs
+//! a new one.
+
+use anyhow::{bail, Result};
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use super::AgentControl;
+use crate::network::Event;
+use crate::telemetry::Alert;
+
+/// Who confirmed live trading, and when
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LiveConfirmation {
+    pub confirmed_by: String,
+    /// ms
+    pub confirmed_at: i64,
+}
+
+/// Interlock state, for the control API
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveStatus {
+    pub awaiting_confirmation: bool,
+    /// When the hold started (ms)
+    pub held_since: i64,
+    pub confirmation: Option<LiveConfirmation>,
+}
+
+/// Holds a live agent until a second operator confirms
+pub struct LiveInterlock {
+    confirm_token: String,
+    control: Arc<AgentControl>,
+    event_tx: broadcast::Sender<Event>,
+    held_since: i64,
+    confirmation: RwLock<Option<LiveConfirmation>>,
+}
+
+/// Compare tokens in time independent of where they differ
+pub fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided.bytes().zip(expected.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+impl LiveInterlock {
+    /// Hold `control` until live trading is confirmed with `confirm_token`
+    pub fn arm(confirm_token: &str, control: Arc<AgentControl>, event_tx: broadcast::Sender<Event>) -> Self {
+        control.hold_for_live();
+        warn!("Live trading is held until confirmed via POST /live/confirm");
+        let _ = event_tx.send(Event::Alert(Alert::warning(
+            "Live trading awaiting confirmation",
+            "The bot started in live mode and holds until a second operator confirms via POST /live/confirm",
+        )));
+        Self {
+            confirm_token: confirm_token.to_string(),
+            control,
+            event_tx,
+            held_since: chrono::Utc::now().timestamp_millis(),
+            confirmation: RwLock::new(None),
+        }
+    }
+
+    pub fn status(&self) -> LiveStatus {
+        LiveStatus {
+            awaiting_confirmation: self.control.is_live_held(),
+            held_since: self.held_since,
+            confirmation: self.confirmation.read().clone(),
+        }
+    }
+
+    /// Release the hold if `token` is the confirmation token; a repeated
+    /// confirmation returns the first
+    pub fn confirm(&self, token: &str, operator: &str) -> Result<LiveConfirmation> {
+        if !tokens_match(token, &self.confirm_token) {
+            warn!("Rejected live trading confirmation from {}", operator);
+            let _ = self.event_tx.send(Event::Alert(Alert::warning(
+                "Live trading confirmation rejected",
+                format!("Invalid confirmation token from {}", operator),
+            )));
+            bail!("Invalid live confirmation token");
+        }
+        let mut confirmation = self.confirmation.write();
+        if let Some(existing) = confirmation.as_ref() {
+            return Ok(existing.clone());
+        }
+        let confirmed = LiveConfirmation {
+            confirmed_by: operator.to_string(),
+            confirmed_at: chrono::Utc::now().timestamp_millis(),
+        };
+        *confirmation = Some(confirmed.clone());
+        self.control.release_live();
+        warn!("Live trading confirmed by {}", operator);
+        let _ = self.event_tx.send(Event::Alert(Alert::warning(
+            "Live trading confirmed",
+            format!("{} confirmed live trading; the bot now trades real funds", operator),
+        )));
+        Ok(confirmed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hold_until_confirmed() {
+        let control = Arc::new(AgentControl::new());
+        let (event_tx, mut events) = broadcast::channel(8);
+        let interlock = LiveInterlock::arm("second-key", control.clone(), event_tx);
+        assert!(control.is_paused());
+
+        // An operator resume does not get around the interlock
+        control.resume();
+        assert_eq!(control.pause_reason().as_deref(), Some("Awaiting live trading confirmation"));
+
+        assert!(interlock.confirm("second-kez", "bob").is_err());
+        assert!(control.is_live_held());
+
+        let confirmed = interlock.confirm("second-key", "bob").unwrap();
+        assert_eq!(confirmed.confirmed_by, "bob");
+        assert!(!control.is_paused());
+        assert_eq!(interlock.confirm("second-key", "carol").unwrap(), confirmed);
+        assert!(!interlock.status().awaiting_confirmation);
+
+        // Armed, rejected, confirmed
+        let alerts: Vec<String> = std::iter::from_fn(|| events.try_recv().ok())
+            .filter_map(|e| match e {
+                Event::Alert(alert) => Some(alert.title),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(alerts.len(), 3);
+    }
+}
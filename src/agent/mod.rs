@@ -7,30 +7,86 @@
 //! - Rebalancing logic
 //! - **NEW** Performance-based learning
 //! - **NEW** Adaptive position sizing
-//! - **NEW** Funding reversal detection
+//! - **NEW** Funding reversal detection, with an optional hedge tilt on High
+//!   severity
+//! - Passive (post-only) perp entry with maker rebate capture
+//! - Entry laddering: positions built in tranches at rising basis percentiles
+//! - Leg reconciliation after every open and close
+//! - Operator controls (pause, resume, size override, close all)
+//! - Manual commands: open a given size, close a trade, move the hedge
+//! - Blocked open signals reported for skipped-trade tracking
+//! - Per-trade tracing spans (signal → sizing → risk check → execute → record)
+//! - Open-path latency budget with slow-path alerts
+//! - Dry-run mode: real transactions built and simulated next to paper fills
+//! - Trading windows: no entries during funding, weekend or hourly blackouts
+//! - Post-trade review: anomalous closed trades are queued for an operator
+//! - State timeouts: stuck Opening/Closing/Rebalancing states move to Error
+//!   and recover by retrying, unwinding or pausing
+//! - Execution phase tracking within those states (quote → build → submit
+//!   → confirm → verify), reported on timeouts and `/status`
+//! - Named strategies: further agents with their own config overrides,
+//!   performance store and positions, run next to the primary one
+//! - A/B shadow evaluation: a named strategy compared against the primary
+//!   agent's trades before its parameters are promoted
+//! - Temporary operator overrides of risk limits, with expiry and an audit log
+//! - Live trading interlock: a live bot holds until a second operator confirms
 
 pub mod state_machine;
 pub mod risk_manager;
 pub mod rebalancer;
+pub mod control;
+pub mod ladder;
+pub mod supervisor;
+pub mod panic_guard;
+pub mod strategies;
+pub mod ab_test;
+pub mod risk_overrides;
+pub mod live_interlock;
 
-pub use state_machine::{AgentStateMachine, AgentState, StateTransition};
-pub use risk_manager::RiskManager;
+pub use state_machine::{AgentStateMachine, AgentState, AgentStatus, ExecutionPhase, ExecutionProgress, StateTransition};
+pub use risk_manager::{RiskManager, RiskCheckResult, RiskMetrics, RiskSeverity, RiskViolation};
 pub use rebalancer::Rebalancer;
+pub use control::{AgentControl, ManualCommand};
+pub use ladder::{EntryLadder, Tranche};
+pub use supervisor::Supervisor;
+pub use panic_guard::PanicGuard;
+pub use strategies::{Strategy, StrategyStatus};
+pub use ab_test::{ArmMetrics, ShadowComparison};
+pub use risk_overrides::{OverrideAction, RiskLimit, RiskOverride, RiskOverrideEvent};
+pub use live_interlock::{LiveConfirmation, LiveInterlock, LiveStatus};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
-use tracing::{info, warn, error, debug};
+use tracing::{info, info_span, warn, error, debug, Instrument};
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, HighReversalAction, TimeoutRecovery};
+use crate::engines::basis_engine::BasisAnalysis;
+use crate::engines::signal_engine::FullTradeSignal;
+use crate::engines::{TradingWindow, WindowStatus};
 use crate::agentic::{
-    PerformanceDb, TradeOutcome, PerformanceMetrics,
-    AdaptiveSizer, SizingRecommendation,
-    ReversalDetector, ReversalSeverity,
+    PerformanceDb, TradeOutcome, PerformanceMetrics, EntryTimePerformance,
+    AdaptiveSizer, SizingRecommendation, SizingReport, StepKind, CalibrationReport, ConfidenceCalibrator,
+    ReversalDetector, ReversalSeverity, HedgeTilt, RegimeAnalysis,
+    CapitalAllocator, FrequencyGovernor, SkippedTradeTracker, ThresholdLearner, TradeReviewer,
 };
+use crate::execution::TradeExecutor;
+use crate::execution::journal::{ExecutionJournal, JournalEntry, JournalKind};
+use crate::execution::orders::{passive_limit_price, OrderStatus, OrderTracker, OrderVenue};
+use crate::execution::reconcile::{
+    LegAction, LegExecutor, LegFills, LegPrices, LegReconciler, LegSlippage, ReconcileOutcome, VenueLegs,
+};
+use crate::execution::shadow::{ShadowExecutor, ShadowReport};
+use crate::execution::tx_builder::{OrderSide, OrderType};
 use crate::network::event_bus::Event;
 use crate::position::PositionManager;
-use crate::state::SharedState;
+use crate::state::{MarketSnapshot, SharedState};
+use crate::telemetry::{trade_span, Alert, LatencyStage, LatencyTracker};
+use crate::utils::types::{CloseReason, ExpectedValue, OrderBook, SignalType, SkipReason, TradeSignal};
+
+/// Signals older than this were evaluated against stale market data
+const SIGNAL_MAX_AGE_MS: i64 = 15_000;
 
 /// Trading agent that coordinates all components with agentic capabilities
 pub struct TradingAgent {
@@ -56,10 +112,41 @@ pub struct TradingAgent {
     performance_db: Arc<PerformanceDb>,
     /// Adaptive position sizer
     adaptive_sizer: Arc<AdaptiveSizer>,
+    /// Shrinks signal confidence that has proven over-confident
+    calibrator: Arc<ConfidenceCalibrator>,
     /// Funding reversal detector
     reversal_detector: Arc<ReversalDetector>,
+    /// Post-trade review queue
+    trade_reviewer: Arc<TradeReviewer>,
+    /// Hypothetical trades for blocked signals
+    skipped_trades: Arc<SkippedTradeTracker>,
+    /// Entry thresholds learned from closed trades
+    threshold_learner: Arc<ThresholdLearner>,
+    /// Entry thresholds raised while realized edge deteriorates
+    frequency_governor: Arc<FrequencyGovernor>,
     /// Current trade context (for recording outcomes)
     current_trade_context: Arc<RwLock<Option<TradeContext>>>,
+    /// Resting perp orders (passive entry)
+    order_tracker: Arc<OrderTracker>,
+    /// Completes or unwinds one-legged fills
+    leg_reconciler: Arc<LegReconciler>,
+    /// Legs reconciliation reads and corrects: the paper positions, or the
+    /// venues when live
+    legs: Arc<dyn LegExecutor>,
+    /// Execution journal
+    journal: Arc<ExecutionJournal>,
+    /// Latest signal from the signal engine
+    signals: Arc<RwLock<Option<FullTradeSignal>>>,
+    /// Latest basis engine analysis (percentiles for entry laddering)
+    basis_analysis: Arc<RwLock<Option<BasisAnalysis>>>,
+    /// Performance by entry time, published to the signal engine
+    entry_times: Arc<RwLock<Option<EntryTimePerformance>>>,
+    /// Average hold of closed trades, published to the signal engine
+    hold_times: Arc<RwLock<Option<f64>>>,
+    /// Operator commands
+    control: Arc<AgentControl>,
+    /// Dry-run executor, when shadow mode is enabled
+    shadow: Option<Arc<ShadowExecutor>>,
 }
 
 /// Context for current open trade (used to record outcome on close)
@@ -74,6 +161,55 @@ pub struct TradeContext {
     pub entry_funding_apr: f64,
     pub entry_confidence: f64,
     pub accumulated_funding: f64,
+    pub fees_paid: f64,
+    /// Slippage measured on the legs' fills at open and close (USD)
+    pub slippage: LegSlippage,
+    pub perp_order_type: OrderType,
+    /// Root tracing span for the trade's lifecycle
+    pub span: tracing::Span,
+    /// Open-path stage timings, from signal detection
+    pub latency: LatencyTracker,
+    /// Resting passive perp order, while awaiting fill
+    pub perp_order_id: Option<String>,
+    /// How the entry was sized
+    pub sizing: SizingReport,
+    /// Size the open in progress executes (the whole trade unless laddered)
+    pub tranche_size: f64,
+    /// Entry tranches, when laddering
+    pub ladder: Option<EntryLadder>,
+    /// Expected value estimated by the signal engine
+    pub expected_value: Option<ExpectedValue>,
+    /// Hedge tilt on a High funding reversal, once filled
+    pub hedge_tilt: Option<HedgeTilt>,
+}
+
+impl TradeContext {
+    /// Add the ladder tranches the basis has reached since entry, blending
+    /// the entry basis; returns their size. Entry prices are blended once
+    /// the tranche executes (`price_tranche`).
+    pub fn add_due_tranches(&mut self, percentile: f64, market: &MarketSnapshot, now: i64) -> Option<f64> {
+        let ladder = self.ladder.as_mut()?;
+        let size = ladder.fill_due(ladder::richness(self.entry_basis, percentile), market.basis_spread, now);
+        if size <= 0.0 {
+            return None;
+        }
+        let total = self.size + size;
+        self.entry_basis = (self.entry_basis * self.size + market.basis_spread * size) / total;
+        self.size = total;
+        self.tranche_size = size;
+        Some(size)
+    }
+    
+    /// Blend the tranche just executed into the entry prices, at the prices
+    /// its legs traded at
+    pub fn price_tranche(&mut self, prices: LegPrices) -> Result<()> {
+        let spot = prices.spot.context("No fill price for the spot leg")?;
+        let perp = prices.perp.context("No fill price for the perp leg")?;
+        let held = self.size - self.tranche_size;
+        self.entry_spot = (self.entry_spot * held + spot * self.tranche_size) / self.size;
+        self.entry_perp = (self.entry_perp * held + perp * self.tranche_size) / self.size;
+        Ok(())
+    }
 }
 
 impl TradingAgent {
@@ -83,9 +219,14 @@ impl TradingAgent {
         state: Arc<SharedState>,
         position_manager: Arc<PositionManager>,
         event_tx: broadcast::Sender<Event>,
+        signals: Arc<RwLock<Option<FullTradeSignal>>>,
+        regime: Arc<RwLock<Option<RegimeAnalysis>>>,
     ) -> Result<Self> {
-        let state_machine = Arc::new(RwLock::new(AgentStateMachine::new()));
-        let risk_manager = Arc::new(RiskManager::new(config.clone(), state.clone()));
+        // Every transition is sent to the performance store's audit log
+        let (transition_tx, mut transition_rx) = tokio::sync::mpsc::unbounded_channel();
+        let state_machine = Arc::new(RwLock::new(
+            AgentStateMachine::new().with_audit(state.clone(), transition_tx),
+        ));
         let rebalancer = Arc::new(Rebalancer::new(
             config.clone(),
             state.clone(),
@@ -93,19 +234,53 @@ impl TradingAgent {
         ));
         
         // Initialize agentic components
-        let db_path = "data/performance.json";
+        let db_path = &config.agentic.performance_db_path;
         
         // Ensure data directory exists
-        tokio::fs::create_dir_all("data").await.ok();
+        if let Some(dir) = std::path::Path::new(db_path).parent() {
+            tokio::fs::create_dir_all(dir).await.ok();
+        }
         
         let performance_db = Arc::new(
             PerformanceDb::new(db_path).await?
+                .with_metric_windows(&config.agentic.metric_windows_days).await
         );
         
-        let adaptive_sizer = Arc::new(AdaptiveSizer::new(
-            config.clone(),
-            performance_db.clone(),
-        ));
+        let transition_db = performance_db.clone();
+        tokio::spawn(async move {
+            while let Some(transition) = transition_rx.recv().await {
+                if let Err(e) = transition_db.record_transition(&transition).await {
+                    warn!("Failed to persist state transition: {}", e);
+                }
+            }
+        });
+        
+        // Drawdown is measured from the persisted high-water mark
+        let risk_manager = Arc::new(
+            RiskManager::new(config.clone(), state.clone())
+                .with_store(performance_db.clone())
+                .with_events(event_tx.clone()),
+        );
+        if let Err(e) = risk_manager.restore().await {
+            warn!("Failed to restore risk state, starting fresh: {}", e);
+        }
+        
+        let adaptive_sizer = Arc::new(
+            AdaptiveSizer::new(config.clone(), state.clone(), performance_db.clone())
+                .with_regime(regime)
+                .with_allocator(Arc::new(CapitalAllocator::new(
+                    config.clone(),
+                    performance_db.clone(),
+                    vec![config.perp_market()],
+                ))),
+        );
+        
+        if let Err(e) = adaptive_sizer.restore_compounding().await {
+            warn!("Failed to restore compounding state, starting from the configured size: {}", e);
+        }
+        
+        let calibrator = Arc::new(ConfidenceCalibrator::new(&config.agentic, performance_db.clone()));
+        calibrator.recalculate().await;
         
         let reversal_detector = Arc::new(ReversalDetector::new(
             config.clone(),
@@ -113,6 +288,18 @@ impl TradingAgent {
             event_tx.clone(),
         ));
         
+        let trade_reviewer = Arc::new(TradeReviewer::new(config.clone(), performance_db.clone()));
+        trade_reviewer.rebuild().await;
+        
+        let skipped_trades = Arc::new(SkippedTradeTracker::new(&config, performance_db.clone()));
+        
+        let threshold_learner = Arc::new(ThresholdLearner::new(config.clone(), state.clone(), performance_db.clone()));
+        if let Err(e) = threshold_learner.restore().await {
+            warn!("Failed to restore learned entry thresholds, using the configured ones: {}", e);
+        }
+        let frequency_governor = Arc::new(FrequencyGovernor::new(config.clone(), state.clone(), performance_db.clone()));
+        frequency_governor.restore().await;
+        
         info!("Trading agent initialized with agentic features");
         
         // Log current performance metrics
@@ -124,6 +311,13 @@ impl TradingAgent {
             );
         }
         
+        let leg_reconciler = Arc::new(LegReconciler::new(&config.execution));
+        // Paper legs until a live executor is attached
+        let legs: Arc<dyn LegExecutor> = position_manager.clone();
+        // The journal lives with the performance store ("data/" by default)
+        let journal_path = std::path::Path::new(db_path)
+            .with_file_name("execution_journal.jsonl");
+        
         Ok(Self {
             config,
             state,
@@ -135,11 +329,75 @@ impl TradingAgent {
             running: Arc::new(RwLock::new(false)),
             performance_db,
             adaptive_sizer,
+            calibrator,
             reversal_detector,
+            trade_reviewer,
+            skipped_trades,
+            threshold_learner,
+            frequency_governor,
             current_trade_context: Arc::new(RwLock::new(None)),
+            order_tracker: Arc::new(OrderTracker::new()),
+            leg_reconciler,
+            legs,
+            signals,
+            basis_analysis: Arc::new(RwLock::new(None)),
+            entry_times: Arc::new(RwLock::new(None)),
+            hold_times: Arc::new(RwLock::new(None)),
+            journal: Arc::new(ExecutionJournal::new(&journal_path.to_string_lossy())),
+            control: Arc::new(AgentControl::new()),
+            shadow: None,
         })
     }
     
+    /// Place resting perp orders on `venue` and follow them there
+    pub fn with_order_venue(mut self, venue: Arc<dyn OrderVenue>) -> Self {
+        self.order_tracker = Arc::new(OrderTracker::new().with_venue(venue));
+        self
+    }
+    
+    /// Send live rebalance orders through an executor (the execution
+    /// manager, or a mock in tests)
+    pub fn with_execution(mut self, execution: Arc<dyn TradeExecutor>) -> Self {
+        if !self.config.paper_trading {
+            self.legs = Arc::new(VenueLegs::new(execution.clone(), self.position_manager.clone()));
+        }
+        self.rebalancer = Arc::new(
+            Rebalancer::new(self.config.clone(), self.state.clone(), self.position_manager.clone())
+                .with_execution(execution),
+        );
+        self
+    }
+    
+    /// Read basis percentiles from the basis engine, for entry laddering
+    pub fn with_basis(mut self, basis_analysis: Arc<RwLock<Option<BasisAnalysis>>>) -> Self {
+        self.basis_analysis = basis_analysis;
+        self
+    }
+    
+    /// Publish performance by entry time to the signal engine
+    pub fn with_entry_times(mut self, entry_times: Arc<RwLock<Option<EntryTimePerformance>>>) -> Self {
+        self.entry_times = entry_times;
+        self
+    }
+    
+    /// Publish the average hold of closed trades to the signal engine
+    pub fn with_hold_times(mut self, hold_times: Arc<RwLock<Option<f64>>>) -> Self {
+        self.hold_times = hold_times;
+        self
+    }
+    
+    /// Share operator controls with other command sources (the control API)
+    pub fn with_control(mut self, control: Arc<AgentControl>) -> Self {
+        self.control = control;
+        self
+    }
+    
+    /// Build and simulate real transactions for every paper open and close
+    pub fn with_shadow(mut self, shadow: Arc<ShadowExecutor>) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+    
     /// Start the trading agent
     pub async fn start(&self) -> Result<()> {
         *self.running.write().await = true;
@@ -148,6 +406,9 @@ impl TradingAgent {
         // Start reversal detector
         self.reversal_detector.start().await?;
         
+        // Follow blocked signals as hypothetical trades
+        self.skipped_trades.clone().spawn(self.event_tx.subscribe());
+        
         let running = self.running.clone();
         let state = self.state.clone();
         let config = self.config.clone();
@@ -158,43 +419,205 @@ impl TradingAgent {
         let event_tx = self.event_tx.clone();
         let performance_db = self.performance_db.clone();
         let adaptive_sizer = self.adaptive_sizer.clone();
+        let calibrator = self.calibrator.clone();
         let reversal_detector = self.reversal_detector.clone();
+        let trade_reviewer = self.trade_reviewer.clone();
+        let threshold_learner = self.threshold_learner.clone();
+        let frequency_governor = self.frequency_governor.clone();
         let current_trade_context = self.current_trade_context.clone();
+        let order_tracker = self.order_tracker.clone();
+        let leg_reconciler = self.leg_reconciler.clone();
+        let legs = self.legs.clone();
+        let journal = self.journal.clone();
+        let signals = self.signals.clone();
+        let basis_analysis = self.basis_analysis.clone();
+        let entry_times = self.entry_times.clone();
+        let hold_times = self.hold_times.clone();
+        let control = self.control.clone();
+        let shadow = self.shadow.clone();
+        let trading_window = TradingWindow::new(&config);
         
         // Main agent loop
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+            let mut interval = tokio::time::interval(config.engines.agent_tick());
+            // Timestamp of the last signal acted on, so each is used once
+            let mut last_signal_ts = 0i64;
+            // State that timed out, awaiting recovery from Error
+            let mut timed_out: Option<AgentState> = None;
+            let mut timeout_retries = 0u32;
+            // Operator commands waiting for their state: open size (SOL), perp delta (SOL)
+            let mut manual_open: Option<f64> = None;
+            let mut manual_hedge: Option<f64> = None;
+            // Reversal tilt behind the pending hedge move, until it fills
+            let mut pending_tilt: Option<HedgeTilt> = None;
+            // What sent the agent to Closing, for the trade outcome
+            let mut close_reason = CloseReason::Unknown;
+            *entry_times.write().await = Some(performance_db.get_performance_by_entry_time().await);
+            *hold_times.write().await = performance_db.average_hold_hours(config.signals.expected_value.min_trades).await;
             
             while *running.read().await {
                 interval.tick().await;
+                state.agent_heartbeat.store(chrono::Utc::now().timestamp_millis(), Ordering::Relaxed);
                 
                 // Check risk conditions first
                 let risk_check = risk_manager.check_all().await;
+                adaptive_sizer.observe_drawdown(state.drawdown_pct.load());
                 
-                if risk_check.should_pause {
+                // Stop loss and max drawdown exit the open position
+                if risk_check.should_close() {
                     let mut sm = state_machine.write().await;
-                    if sm.current_state() != AgentState::Paused {
-                        warn!("Risk check triggered pause: {:?}", risk_check.reasons);
-                        sm.transition_to(AgentState::Paused);
-                        let _ = event_tx.send(Event::SystemPause {
-                            reason: risk_check.reasons.join("; "),
-                        });
+                    if sm.current_state() == AgentState::Monitoring {
+                        warn!("Risk check requires closing: {}", risk_check.summary());
+                        let stop = risk_check.violations.iter().any(|v| matches!(v, RiskViolation::StopLoss { .. }));
+                        close_reason = if stop { CloseReason::StopLoss } else { CloseReason::RiskPause };
+                        sm.transition_to_with_reason(AgentState::Closing, Some(risk_check.summary()));
+                    }
+                }
+                
+                // Pausing rules stop new trades; a close in progress finishes first
+                if risk_check.should_pause() {
+                    let mut sm = state_machine.write().await;
+                    let current = sm.current_state();
+                    if current != AgentState::Closing {
+                        if current != AgentState::Paused {
+                            warn!("Risk check triggered pause: {}", risk_check.summary());
+                            if current == AgentState::AwaitingFill {
+                                Self::cancel_resting_entry(&order_tracker, &current_trade_context).await;
+                            }
+                            sm.transition_to(AgentState::Paused);
+                            let _ = event_tx.send(Event::SystemPause {
+                                reason: risk_check.summary(),
+                            });
+                        }
+                        Self::skip_pending_signal(
+                            &signals,
+                            &mut last_signal_ts,
+                            &event_tx,
+                            &state,
+                            SkipReason::RiskPause,
+                            &risk_check.summary(),
+                        ).await;
+                        continue;
+                    }
+                }
+                
+                // Operator close-all, also honoured while paused
+                if control.take_close_request() {
+                    let has_position = state.spot_position.read().is_some()
+                        || state.perp_position.read().is_some();
+                    let mut sm = state_machine.write().await;
+                    let current = sm.current_state();
+                    if current == AgentState::Monitoring || (current == AgentState::Paused && has_position) {
+                        warn!("Operator requested close of all positions");
+                        close_reason = CloseReason::ManualClose;
+                        sm.transition_to(AgentState::Closing);
+                        continue;
+                    }
+                }
+                
+                // Operator manual commands; opens and hedge moves run from their state below
+                if let Some(command) = control.take_manual() {
+                    let current = state_machine.read().await.current_state();
+                    match command {
+                        ManualCommand::Open { size_sol } if current == AgentState::Idle && !control.is_paused() => {
+                            manual_open = Some(size_sol);
+                        }
+                        ManualCommand::Close { trade_id } => {
+                            let open_id = current_trade_context.read().await.as_ref().map(|c| c.id.clone());
+                            if open_id.as_deref() != Some(trade_id.as_str()) {
+                                warn!("Manual close ignored: trade {} is not open", trade_id);
+                            } else if matches!(current, AgentState::Monitoring | AgentState::Paused) {
+                                warn!("Operator requested close of trade {}", trade_id);
+                                close_reason = CloseReason::ManualClose;
+                                state_machine.write().await.transition_to_with_reason(
+                                    AgentState::Closing,
+                                    Some("Operator manual close".to_string()),
+                                );
+                                continue;
+                            } else {
+                                warn!("Manual close of {} ignored in state {:?}", trade_id, current);
+                            }
+                        }
+                        ManualCommand::AdjustHedge { perp_delta_sol } if current == AgentState::Monitoring => {
+                            manual_hedge = Some(perp_delta_sol);
+                            state_machine.write().await.transition_to_with_reason(
+                                AgentState::Rebalancing,
+                                Some("Operator hedge adjustment".to_string()),
+                            );
+                            continue;
+                        }
+                        command => warn!("Manual command {:?} ignored in state {:?}", command, current),
+                    }
+                }
+                
+                // Operator pause holds until an explicit resume; a close in
+                // progress is allowed to finish first
+                if let Some(reason) = control.pause_reason() {
+                    let mut sm = state_machine.write().await;
+                    if sm.current_state() != AgentState::Closing {
+                        Self::skip_pending_signal(
+                            &signals,
+                            &mut last_signal_ts,
+                            &event_tx,
+                            &state,
+                            SkipReason::OperatorPause,
+                            &reason,
+                        ).await;
+                    }
+                    match sm.current_state() {
+                        AgentState::Closing => {}
+                        AgentState::Paused => continue,
+                        current => {
+                            warn!("Operator paused trading: {}", reason);
+                            if current == AgentState::AwaitingFill {
+                                Self::cancel_resting_entry(&order_tracker, &current_trade_context).await;
+                            }
+                            sm.transition_to_with_reason(AgentState::Paused, Some(reason.clone()));
+                            let _ = event_tx.send(Event::SystemPause { reason });
+                            continue;
+                        }
                     }
-                    continue;
                 }
                 
                 // Check for funding reversal (agentic feature)
                 if let Some(severity) = reversal_detector.get_reversal_severity().await {
                     match severity {
-                        ReversalSeverity::Critical => {
+                        ReversalSeverity::Critical if config.agentic.force_close_on_critical_reversal => {
                             // Force close on critical reversal
                             let mut sm = state_machine.write().await;
                             if sm.current_state() == AgentState::Monitoring {
                                 warn!("Critical funding reversal - forcing position close");
+                                close_reason = CloseReason::ReversalCritical;
                                 sm.transition_to(AgentState::Closing);
                                 continue;
                             }
                         }
+                        ReversalSeverity::High if config.agentic.high_reversal_action == HighReversalAction::Tilt => {
+                            // Buy back part of the short, once per trade
+                            let mut sm = state_machine.write().await;
+                            let ctx = current_trade_context.read().await;
+                            let untilted = ctx.as_ref().filter(|c| c.hedge_tilt.is_none());
+                            if let (AgentState::Monitoring, Some(ctx)) = (sm.current_state(), untilted) {
+                                let market = state.snapshot();
+                                let delta = ctx.size * config.agentic.reversal_tilt_pct / 100.0;
+                                warn!("High funding reversal - tilting hedge: buying back {:.4} SOL of the perp short", delta);
+                                pending_tilt = Some(HedgeTilt::new(
+                                    chrono::Utc::now().timestamp_millis(),
+                                    severity,
+                                    market.funding_apr,
+                                    delta,
+                                    market.perp_mark_price,
+                                ));
+                                // The rebalancer holds the tilted ratio from here
+                                state.hedge_tilt_pct.store(config.agentic.reversal_tilt_pct);
+                                manual_hedge = Some(delta);
+                                sm.transition_to_with_reason(
+                                    AgentState::Rebalancing,
+                                    Some("Funding reversal hedge tilt".to_string()),
+                                );
+                                continue;
+                            }
+                        }
                         ReversalSeverity::High => {
                             // Emit warning but don't force close
                             debug!("High severity funding reversal detected");
@@ -205,21 +628,93 @@ impl TradingAgent {
                 
                 // Get current state
                 let current_state = state_machine.read().await.current_state();
+                if matches!(current_state, AgentState::Idle | AgentState::Monitoring) {
+                    timeout_retries = 0;
+                }
+                
+                // States whose work spans ticks (or that just came back from a hang)
+                if state_machine.read().await.is_timed_out(&config.state_timeouts) {
+                    Self::on_state_timeout(&state_machine, &event_tx, current_state).await;
+                    timed_out = Some(current_state);
+                    continue;
+                }
                 
                 match current_state {
                     AgentState::Idle => {
                         // Check for trade opportunities
-                        if let Some(signal) = Self::check_for_signals(&state, &config).await {
-                            info!("Trade signal detected: {:?}", signal);
+                        let now = chrono::Utc::now().timestamp_millis();
+                        let entry = match manual_open.take() {
+                            Some(size) => Some((Self::manual_signal(&state, size, now), Some(size))),
+                            None => Self::check_for_signals(&signals, last_signal_ts, now).await
+                                .map(|s| (s.signal, None)),
+                        };
+                        if let Some((signal, manual_size)) = entry {
+                            if manual_size.is_none() {
+                                last_signal_ts = signal.timestamp;
+                            }
+                            
+                            // The window may have closed since the signal was generated
+                            let window = trading_window.status();
+                            if !window.open {
+                                let reason = window.reason.unwrap_or_default();
+                                info!("Skipping entry signal: {}", reason);
+                                if manual_size.is_none() {
+                                    Self::skip_signal(&event_tx, &state, SkipReason::TradingWindow, &signal, signal.size, reason);
+                                }
+                                continue;
+                            }
+                            
+                            let trade_id = uuid::Uuid::new_v4().to_string();
+                            let span = trade_span(&trade_id);
+                            // Timed from the market data behind the signal, not this tick
+                            let mut latency = LatencyTracker::since(signal.market_timestamp);
+                            info_span!(
+                                parent: &span,
+                                "signal",
+                                confidence = signal.confidence,
+                                age_ms = now - signal.timestamp
+                            ).in_scope(|| {
+                                info!(
+                                    "Trade signal detected: {} (confidence {:.0}%)",
+                                    signal.reason,
+                                    signal.confidence * 100.0
+                                );
+                            });
                             
                             // Get adaptive sizing recommendation
-                            let basis = state.get_basis_spread();
-                            let funding_apr = state.funding_apr.load();
-                            let sizing = adaptive_sizer.get_recommended_size(
-                                basis,
-                                funding_apr,
-                                0.8, // Signal confidence
+                            let market = state.snapshot();
+                            let basis = market.basis_spread;
+                            let funding_apr = market.funding_apr;
+                            let confidence = calibrator.adjust(signal.confidence).await;
+                            if confidence < signal.confidence {
+                                debug!(
+                                    "Calibrated confidence {:.0}% -> {:.0}%",
+                                    signal.confidence * 100.0,
+                                    confidence * 100.0
+                                );
+                            }
+                            let mut sizing = latency.time(
+                                LatencyStage::Sizing,
+                                adaptive_sizer.get_recommended_size(
+                                    basis,
+                                    funding_apr,
+                                    confidence,
+                                ).instrument(info_span!(parent: &span, "sizing")),
                             ).await;
+                            let ceiling = adaptive_sizer.operator_ceiling().await;
+                            if let Some(size) = manual_size {
+                                let size = size.min(ceiling);
+                                let detail = format!("Operator manual open: {:.4} SOL", size);
+                                sizing.report.push(StepKind::Cap, "operator_manual", size, detail.clone());
+                                sizing.adjustments.push(detail);
+                                sizing.size_sol = size;
+                            } else if let Some(size) = control.size_override() {
+                                let size = size.min(ceiling);
+                                let detail = format!("Operator size override: {:.4} SOL", size);
+                                sizing.report.push(StepKind::Cap, "operator_override", size, detail.clone());
+                                sizing.adjustments.push(detail);
+                                sizing.size_sol = size;
+                            }
                             
                             info!(
                                 "Adaptive sizing: {:.2} SOL ({:.1}% of max) | Kelly: {:.1}% | Adjustments: {:?}",
@@ -228,64 +723,411 @@ impl TradingAgent {
                                 sizing.kelly_fraction * 100.0,
                                 sizing.adjustments
                             );
+                            info!(parent: &span, "Sizing derivation: {}", sizing.report.summary());
+                            if let Ok(json) = serde_json::to_string(&sizing.report) {
+                                debug!(parent: &span, "Sizing report: {}", json);
+                            }
+                            
+                            // Re-check risk: sizing may have taken a while
+                            let risk = latency.time(
+                                LatencyStage::RiskCheck,
+                                risk_manager.check_all().instrument(info_span!(parent: &span, "risk_check")),
+                            ).await;
+                            if risk.blocks_entries() {
+                                warn!(parent: &span, "Pre-trade risk check failed: {}", risk.summary());
+                                if manual_size.is_none() {
+                                    Self::skip_signal(&event_tx, &state, SkipReason::RiskCheck, &signal, sizing.size_sol, risk.summary());
+                                }
+                                continue;
+                            }
+                            if sizing.size_sol <= 0.0 {
+                                warn!(parent: &span, "No capital to size the trade: {}", sizing.report.summary());
+                                if manual_size.is_none() {
+                                    Self::skip_signal(&event_tx, &state, SkipReason::Capital, &signal, signal.size, sizing.report.summary());
+                                }
+                                continue;
+                            }
+                            
+                            // A laddered entry starts with the tranches the basis has reached
+                            let mut tranche_size = sizing.size_sol;
+                            let mut ladder = None;
+                            let ladder_config = &config.trading.entry_ladder;
+                            if ladder_config.enabled && manual_size.is_none() {
+                                let mut plan = EntryLadder::plan(&ladder_config.levels, sizing.size_sol);
+                                let percentile = basis_analysis.read().await.as_ref().map(|b| b.percentile);
+                                tranche_size = percentile
+                                    .map(|p| plan.fill_due(ladder::richness(basis, p), basis, now))
+                                    .unwrap_or(0.0);
+                                if tranche_size <= 0.0 {
+                                    let detail = match percentile {
+                                        Some(p) => format!(
+                                            "Basis at p{:.0}, first ladder level p{:.0}",
+                                            ladder::richness(basis, p),
+                                            plan.next_level().unwrap_or(100.0)
+                                        ),
+                                        None => "No basis percentile yet for the entry ladder".to_string(),
+                                    };
+                                    info!(parent: &span, "Skipping entry signal: {}", detail);
+                                    Self::skip_signal(&event_tx, &state, SkipReason::Ladder, &signal, sizing.size_sol, detail);
+                                    continue;
+                                }
+                                info!(parent: &span, "Entry ladder: {}", plan.summary());
+                                ladder = Some(plan);
+                            }
+                            
+                            let perp_order_type = Self::choose_perp_order_type(
+                                &config,
+                                state.perp_book().as_ref(),
+                                tranche_size,
+                                basis < 0.0,
+                            );
                             
                             // Store trade context for later recording
-                            let trade_id = uuid::Uuid::new_v4().to_string();
                             *current_trade_context.write().await = Some(TradeContext {
-                                id: trade_id,
+                                id: trade_id.clone(),
                                 open_time: chrono::Utc::now().timestamp_millis(),
-                                size: sizing.size_sol,
-                                entry_spot: state.spot_price.load(),
-                                entry_perp: state.perp_mark_price.load(),
+                                size: tranche_size,
+                                // Signal-time quotes until the open is priced from its fills
+                                entry_spot: market.spot_price,
+                                entry_perp: market.perp_mark_price,
                                 entry_basis: basis,
                                 entry_funding_apr: funding_apr,
-                                entry_confidence: sizing.confidence,
+                                // Raw signal confidence, so calibration measures the signal engine
+                                entry_confidence: signal.confidence,
                                 accumulated_funding: 0.0,
+                                fees_paid: 0.0,
+                                slippage: LegSlippage::default(),
+                                perp_order_type,
+                                span,
+                                latency,
+                                perp_order_id: None,
+                                sizing: sizing.report.clone(),
+                                tranche_size,
+                                ladder,
+                                expected_value: signal.expected_value,
+                                hedge_tilt: None,
                             });
                             
                             let mut sm = state_machine.write().await;
+                            sm.set_trade(Some(trade_id));
                             sm.transition_to(AgentState::Opening);
                         }
                     }
                     
                     AgentState::Opening => {
-                        // Get the adaptive size from context
-                        let size = current_trade_context.read().await
+                        // Get the adaptive size from context; a later ladder
+                        // tranche adds to the position already held
+                        let context = current_trade_context.read().await
                             .as_ref()
-                            .map(|c| c.size)
-                            .unwrap_or(100.0);
+                            .map(|c| (c.size, c.tranche_size, c.span.clone()));
+                        let Some((size, tranche, span)) = context else {
+                            error!("Opening without a trade context, nothing to execute");
+                            let mut sm = state_machine.write().await;
+                            sm.transition_to(AgentState::Idle);
+                            continue;
+                        };
+                        let adding = tranche < size;
                         
-                        // Execute opening trade
-                        if config.paper_trading {
-                            debug!("Paper trading: simulating open with size {:.2} SOL", size);
-                            position_manager.simulate_open(
-                                state.spot_price.load(),
+                        let passive = &config.execution.passive_entry;
+                        if passive.enabled && !adding {
+                            // Rest a post-only short on the perp; the spot leg
+                            // waits until it fills
+                            let market = state.snapshot();
+                            let (bid, ask) = if market.perp_best_bid > 0.0 && market.perp_best_ask > 0.0 {
+                                (market.perp_best_bid, market.perp_best_ask)
+                            } else {
+                                (market.perp_mark_price, market.perp_mark_price)
+                            };
+                            let price = passive_limit_price(OrderSide::Short, bid, ask, passive.limit_offset_bps);
+                            let timeout_ms = passive.fill_timeout_secs as i64 * 1000;
+                            // Without a venue the tracker only simulates, which live trading must not do
+                            let submitted = if config.paper_trading || order_tracker.has_venue() {
+                                order_tracker.submit(OrderSide::Short, price, size, true, timeout_ms).await
+                            } else {
+                                Err(anyhow::anyhow!("no order venue configured for live trading"))
+                            };
+                            let order = match submitted {
+                                Ok(order) => order,
+                                Err(e) => {
+                                    warn!(parent: &span, "Passive entry order failed: {}", e);
+                                    current_trade_context.write().await.take();
+                                    let mut sm = state_machine.write().await;
+                                    sm.transition_to(AgentState::Idle);
+                                    continue;
+                                }
+                            };
+                            
+                            info!(
+                                parent: &span,
+                                "Passive entry: post-only short {:.2} SOL @ ${:.4}, timeout {}s",
+                                size, price, passive.fill_timeout_secs
+                            );
+                            if let Some(ctx) = current_trade_context.write().await.as_mut() {
+                                ctx.perp_order_id = Some(order.id);
+                                ctx.perp_order_type = OrderType::Limit;
+                            }
+                            
+                            let mut sm = state_machine.write().await;
+                            sm.transition_to(AgentState::AwaitingFill);
+                            continue;
+                        }
+                        
+                        // Execute opening trade within the state's time budget
+                        let deadline = Self::state_deadline(&state_machine, &config).await;
+                        let progress = state_machine.read().await.progress();
+                        let work = async {
+                            let execute_start = std::time::Instant::now();
+                            if config.paper_trading && adding {
+                                debug!("Paper trading: simulating ladder tranche of {:.2} SOL", tranche);
+                                position_manager.simulate_add(
+                                    state.spot_price.load(),
+                                    tranche,
+                                ).instrument(info_span!(parent: &span, "execute", size = tranche)).await;
+                            } else if config.paper_trading {
+                                debug!("Paper trading: simulating open with size {:.2} SOL", size);
+                                position_manager.simulate_open(
+                                    state.spot_price.load(),
+                                    size,
+                                ).instrument(info_span!(parent: &span, "execute", size)).await;
+                            }
+                            if let Some(shadow) = &shadow {
+                                let market = state.snapshot();
+                                let (trade_id, order_type) = current_trade_context.read().await
+                                    .as_ref()
+                                    .map(|c| (c.id.clone(), c.perp_order_type))
+                                    .unwrap_or((String::new(), OrderType::Market));
+                                let report = shadow.dry_run_open(
+                                    tranche,
+                                    market.spot_price,
+                                    market.perp_mark_price,
+                                    order_type,
+                                ).instrument(info_span!(parent: &span, "dry_run")).await;
+                                Self::record_shadow(&journal, &trade_id, report).await;
+                            }
+                        
+                            let execute_ms = execute_start.elapsed().as_secs_f64() * 1000.0;
+                        
+                            // Paper fills at the market now; live legs are priced
+                            // from their executions
+                            let paper_prices = config.paper_trading.then(|| {
+                                let market = state.snapshot();
+                                LegPrices::new(market.spot_price, market.perp_mark_price)
+                            });
+                            if let Some(ctx) = current_trade_context.write().await.as_mut() {
+                                if let Some(prices) = paper_prices {
+                                    ctx.fees_paid += Self::paper_fees(&config, prices, tranche);
+                                }
+                                ctx.latency.record(LatencyStage::Execute, execute_ms);
+                            }
+                        
+                            let reconcile_start = std::time::Instant::now();
+                            progress.enter(ExecutionPhase::Verify);
+                            let next = Self::after_open(
+                                &leg_reconciler,
+                                &legs,
+                                &journal,
+                                &event_tx,
+                                &current_trade_context,
                                 size,
-                            ).await;
+                                paper_prices.unwrap_or_default(),
+                            ).instrument(info_span!(parent: &span, "reconcile")).await;
+                            (next, reconcile_start)
+                        };
+                        let Ok((next, reconcile_start)) = tokio::time::timeout_at(deadline, progress.track(work)).await else {
+                            Self::on_state_timeout(&state_machine, &event_tx, AgentState::Opening).await;
+                            timed_out = Some(AgentState::Opening);
+                            continue;
+                        };
+                        
+                        // Signal-to-position latency against the budget
+                        if let Some(ctx) = current_trade_context.write().await.as_mut() {
+                            ctx.latency.record(LatencyStage::Reconcile, reconcile_start.elapsed().as_secs_f64() * 1000.0);
+                            let budget = config.execution.open_latency_budget_ms;
+                            if let Some(alert) = ctx.latency.check_budget(budget, &ctx.id) {
+                                warn!(parent: &span, "{}", alert.message);
+                                let _ = event_tx.send(Event::Alert(alert));
+                            } else {
+                                debug!(parent: &span, "Open path {:.0}ms ({})", ctx.latency.elapsed_ms(), ctx.latency.breakdown());
+                            }
                         }
+                        let mut sm = state_machine.write().await;
+                        sm.transition_to(next);
+                    }
+                    
+                    AgentState::AwaitingFill => {
+                        let order_id = current_trade_context.read().await
+                            .as_ref()
+                            .and_then(|c| c.perp_order_id.clone());
+                        let Some(order) = order_id.and_then(|id| order_tracker.get(&id)) else {
+                            warn!("Awaiting fill without a tracked order, returning to idle");
+                            current_trade_context.write().await.take();
+                            let mut sm = state_machine.write().await;
+                            sm.transition_to(AgentState::Idle);
+                            continue;
+                        };
                         
+                        let market = state.snapshot();
+                        let now = chrono::Utc::now().timestamp_millis();
+                        let passive = &config.execution.passive_entry;
+                        
+                        // Live orders: fills and closes as the venue reports them
+                        if let Err(e) = order_tracker.sync().await {
+                            warn!("Order sync failed: {}", e);
+                        }
+                        // Paper fills when the book trades through the resting price
+                        if config.paper_trading
+                            && order.status.is_live()
+                            && order.is_crossed(market.perp_best_bid, market.perp_best_ask)
+                        {
+                            order_tracker.record_fill(&order.id, order.remaining(), order.price.unwrap_or(market.perp_mark_price));
+                        }
+                        order_tracker.expire_orders(now).await;
+                        for fill in order_tracker.take_fills() {
+                            position_manager.apply_perp_fill(&fill).await;
+                        }
+                        
+                        let mut order = order_tracker.get(&order.id).unwrap_or(order);
+                        if order.status.is_live() {
+                            // Follow a book that has moved away from the resting price
+                            let (bid, ask) = (market.perp_best_bid, market.perp_best_ask);
+                            let current = order.price.unwrap_or_default();
+                            if passive.reprice_bps > 0.0 && bid > 0.0 && ask > 0.0 && current > 0.0 {
+                                let price = passive_limit_price(order.side, bid, ask, passive.limit_offset_bps);
+                                if ((price - current) / current).abs() * 10_000.0 >= passive.reprice_bps {
+                                    match order_tracker.replace_order(&order.id, price).await {
+                                        Ok(Some(replacement)) => {
+                                            if let Some(ctx) = current_trade_context.write().await.as_mut() {
+                                                ctx.perp_order_id = Some(replacement.id.clone());
+                                            }
+                                            order = replacement;
+                                        }
+                                        Ok(None) => {}
+                                        Err(e) => warn!("Re-pricing passive entry failed: {}", e),
+                                    }
+                                }
+                            }
+                            if order.status.is_live() {
+                                continue;
+                            }
+                        }
+                        
+                        if order.filled <= 0.0 {
+                            info!("Passive entry not filled within {}s, cancelled", config.execution.passive_entry.fill_timeout_secs);
+                            current_trade_context.write().await.take();
+                            order_tracker.prune();
+                            let mut sm = state_machine.write().await;
+                            sm.transition_to(AgentState::Idle);
+                            continue;
+                        }
+                        
+                        // Perp leg filled (fully, or partially before expiry):
+                        // execute the spot leg for the filled size
+                        let filled = order.filled;
+                        let Some(fill_price) = order.avg_fill_price.or(order.price) else {
+                            let reason = format!("Passive entry {} filled {:.2} SOL without a fill price", order.id, filled);
+                            error!("{}", reason);
+                            let _ = event_tx.send(Event::SystemPause { reason: reason.clone() });
+                            let mut sm = state_machine.write().await;
+                            sm.transition_to_with_reason(AgentState::Paused, Some(reason));
+                            continue;
+                        };
+                        if order.status != OrderStatus::Filled {
+                            info!("Passive entry partially filled: {:.2} of {:.2} SOL", filled, order.size);
+                        }
+                        
+                        if config.paper_trading {
+                            debug!("Paper trading: simulating spot leg with size {:.2} SOL", filled);
+                            position_manager.open_spot_leg(market.spot_price, filled).await;
+                        }
+                        
+                        if let Some(ctx) = current_trade_context.write().await.as_mut() {
+                            let maker_fee = fill_price * filled * config.execution.perp_maker_fee_bps / 10_000.0;
+                            ctx.size = filled;
+                            ctx.tranche_size = filled;
+                            ctx.fees_paid += maker_fee;
+                            if config.paper_trading {
+                                ctx.fees_paid += market.spot_price * filled * config.execution.spot_fee_bps() / 10_000.0;
+                            }
+                            ctx.perp_order_id = None;
+                            info!(parent: &ctx.span, "Passive entry filled @ ${:.4}, maker fee ${:.4}", fill_price, maker_fee);
+                        }
+                        order_tracker.prune();
+                        
+                        let next = Self::after_open(
+                            &leg_reconciler,
+                            &legs,
+                            &journal,
+                            &event_tx,
+                            &current_trade_context,
+                            filled,
+                            LegPrices {
+                                spot: config.paper_trading.then_some(market.spot_price),
+                                perp: Some(fill_price),
+                            },
+                        ).await;
                         let mut sm = state_machine.write().await;
-                        sm.transition_to(AgentState::Monitoring);
+                        sm.transition_to(next);
                     }
                     
                     AgentState::Monitoring => {
                         // Update accumulated funding in context
+                        let market = state.snapshot();
                         if let Some(ctx) = current_trade_context.write().await.as_mut() {
-                            let funding_rate = state.current_funding_rate.load();
                             // Estimate funding accrual (simplified)
-                            ctx.accumulated_funding += funding_rate * ctx.size * state.spot_price.load();
+                            ctx.accumulated_funding += market.funding_rate * ctx.size * market.spot_price;
+                            if let Some(tilt) = ctx.hedge_tilt.as_mut() {
+                                tilt.accrue_funding(market.funding_rate, market.spot_price);
+                            }
                         }
                         
                         // Check for close condition
-                        let basis = state.get_basis_spread();
+                        let basis = market.basis_spread;
                         
                         if basis.abs() < config.trading.basis_close_threshold_pct {
                             info!("Basis converged to {:.4}%, closing position", basis);
+                            close_reason = CloseReason::BasisConverged;
                             let mut sm = state_machine.write().await;
                             sm.transition_to(AgentState::Closing);
                             continue;
                         }
                         
+                        // Held too long: the capital is better freed (0 = no limit)
+                        let max_hold_ms = config.trading.max_hold_time_hours as i64 * 3_600_000;
+                        let open_time = current_trade_context.read().await.as_ref().map(|c| c.open_time);
+                        if let Some(open_time) = open_time.filter(|_| max_hold_ms > 0) {
+                            if chrono::Utc::now().timestamp_millis() - open_time >= max_hold_ms {
+                                info!("Position held {}h, closing", config.trading.max_hold_time_hours);
+                                close_reason = CloseReason::MaxHold;
+                                state_machine.write().await.transition_to_with_reason(
+                                    AgentState::Closing,
+                                    Some("Max hold time".to_string()),
+                                );
+                                continue;
+                            }
+                        }
+                        
+                        // Laddered entry: add the tranches the basis has since reached
+                        if !risk_check.blocks_entries() && trading_window.status().open {
+                            let percentile = basis_analysis.read().await.as_ref().map(|b| b.percentile);
+                            let now = chrono::Utc::now().timestamp_millis();
+                            let mut context = current_trade_context.write().await;
+                            if let (Some(ctx), Some(percentile)) = (context.as_mut(), percentile) {
+                                if let Some(added) = ctx.add_due_tranches(percentile, &market, now) {
+                                    info!(
+                                        parent: &ctx.span,
+                                        "Ladder tranche: adding {:.2} SOL at basis {:.4}% (position {:.2} SOL)",
+                                        added, basis, ctx.size
+                                    );
+                                    drop(context);
+                                    state_machine.write().await.transition_to_with_reason(
+                                        AgentState::Opening,
+                                        Some("Entry ladder tranche".to_string()),
+                                    );
+                                    continue;
+                                }
+                            }
+                        }
+                        
                         // Check for rebalance
                         if rebalancer.needs_rebalance().await {
                             info!("Hedge drift detected, rebalancing");
@@ -295,27 +1137,102 @@ impl TradingAgent {
                     }
                     
                     AgentState::Closing => {
-                        let exit_spot = state.spot_price.load();
-                        let exit_perp = state.perp_mark_price.load();
-                        let exit_basis = state.get_basis_spread();
+                        let market = state.snapshot();
+                        let exit_basis = market.basis_spread;
                         
-                        // Execute closing trade
-                        let pnl = if config.paper_trading {
-                            debug!("Paper trading: simulating close");
-                            position_manager.simulate_close(exit_spot).await
+                        let (trade_id, span) = current_trade_context.read().await
+                            .as_ref()
+                            .map(|c| (c.id.clone(), c.span.clone()))
+                            .unwrap_or_else(|| (String::new(), tracing::Span::none()));
+                        
+                        // Execute closing trade within the state's time budget
+                        let deadline = Self::state_deadline(&state_machine, &config).await;
+                        let progress = state_machine.read().await.progress();
+                        let work = async {
+                            // Live legs are closed by reconciling them to flat
+                            if config.paper_trading {
+                                debug!("Paper trading: simulating close");
+                                position_manager.simulate_close(market.spot_price)
+                                    .instrument(info_span!(parent: &span, "close"))
+                                    .await;
+                            }
+                            if let Some(shadow) = &shadow {
+                                let size = current_trade_context.read().await.as_ref().map(|c| c.size).unwrap_or(0.0);
+                                let report = shadow.dry_run_close(size, market.perp_mark_price)
+                                    .instrument(info_span!(parent: &span, "dry_run"))
+                                    .await;
+                                Self::record_shadow(&journal, &trade_id, report).await;
+                            }
+                            
+                            // Both legs must be flat before the trade is recorded
+                            progress.enter(ExecutionPhase::Verify);
+                            Self::reconcile_legs(
+                                &leg_reconciler,
+                                &legs,
+                                &journal,
+                                &event_tx,
+                                &trade_id,
+                                "close",
+                                LegFills::default(),
+                            ).instrument(info_span!(parent: &span, "reconcile")).await
+                        };
+                        let Ok(outcome) = tokio::time::timeout_at(deadline, progress.track(work)).await else {
+                            Self::on_state_timeout(&state_machine, &event_tx, AgentState::Closing).await;
+                            timed_out = Some(AgentState::Closing);
+                            continue;
+                        };
+                        if !outcome.is_balanced() {
+                            let reason = "Unresolved leg imbalance after close".to_string();
+                            error!("{}", reason);
+                            let mut sm = state_machine.write().await;
+                            sm.transition_to_with_reason(AgentState::Paused, Some(reason.clone()));
+                            let _ = event_tx.send(Event::SystemPause { reason });
+                            continue;
+                        }
+                        
+                        // Exit at the quotes the closing fills traded against;
+                        // paper closes fill at the market
+                        let paper_prices = if config.paper_trading {
+                            LegPrices::new(market.spot_price, market.perp_mark_price)
                         } else {
-                            0.0 // Would get from actual execution
+                            LegPrices::default()
+                        };
+                        let exit = outcome.volume.quotes().or(paper_prices);
+                        let (Some(exit_spot), Some(exit_perp)) = (exit.spot, exit.perp) else {
+                            let reason = "No fill price for a closed leg; trade not recorded".to_string();
+                            error!("{}", reason);
+                            let mut sm = state_machine.write().await;
+                            sm.transition_to_with_reason(AgentState::Paused, Some(reason.clone()));
+                            let _ = event_tx.send(Event::SystemPause { reason });
+                            continue;
                         };
                         
                         // Record trade outcome (agentic learning)
-                        if let Some(ctx) = current_trade_context.write().await.take() {
+                        state.hedge_tilt_pct.store(0.0);
+                        let mut trade_pnl = 0.0;
+                        if let Some(mut ctx) = current_trade_context.write().await.take() {
                             let close_time = chrono::Utc::now().timestamp_millis();
                             let hold_hours = (close_time - ctx.open_time) as f64 / 3600000.0;
+                            ctx.slippage.add(outcome.slippage);
+                            ctx.fees_paid += outcome.volume.fees_usd;
+                            if config.paper_trading {
+                                ctx.fees_paid += Self::paper_fees(&config, exit, ctx.size);
+                            }
                             
-                            // Calculate component P&Ls
-                            let spot_pnl = (exit_spot - ctx.entry_spot) * ctx.size;
-                            let perp_pnl = (ctx.entry_perp - exit_perp) * ctx.size; // Short position
-                            let total_pnl = spot_pnl + perp_pnl + ctx.accumulated_funding;
+                            // Entry and exit prices are the quotes the legs filled
+                            // against; measured slippage comes off the leg it was
+                            // paid on, leaving each leg's P&L at its fill prices
+                            let spot_pnl = (exit_spot - ctx.entry_spot) * ctx.size - ctx.slippage.spot;
+                            let mut perp_pnl = (ctx.entry_perp - exit_perp) * ctx.size - ctx.slippage.perp; // Short position
+                            // A tilt bought back part of the short early and gave up its funding
+                            if let Some(tilt) = ctx.hedge_tilt.as_mut() {
+                                tilt.close(exit_perp);
+                                perp_pnl += tilt.price_pnl;
+                                ctx.accumulated_funding -= tilt.funding_forgone;
+                                info!("Hedge tilt impact: ${:.2} versus the full hedge", tilt.pnl_impact());
+                            }
+                            let total_pnl = spot_pnl + perp_pnl + ctx.accumulated_funding - ctx.fees_paid;
+                            trade_pnl = total_pnl;
                             let notional = ctx.entry_spot * ctx.size;
                             let roi_pct = if notional > 0.0 { total_pnl / notional * 100.0 } else { 0.0 };
                             
@@ -338,16 +1255,40 @@ impl TradingAgent {
                                 roi_pct,
                                 hold_hours,
                                 is_winner: total_pnl > 0.0,
-                                close_reason: "basis_converged".to_string(),
+                                close_reason,
                                 entry_confidence: ctx.entry_confidence,
+                                fees_paid: ctx.fees_paid,
+                                slippage_cost: ctx.slippage.total(),
+                                market: config.perp_market(),
+                                sizing: Some(ctx.sizing),
+                                expected_value: ctx.expected_value,
+                                hedge_tilt: ctx.hedge_tilt,
                             };
                             
-                            if let Err(e) = performance_db.record_trade(outcome).await {
+                            risk_manager.record_trade(&outcome).await;
+                            adaptive_sizer.compound(total_pnl, exit_spot).await;
+                            let record = performance_db.record_trade(outcome.clone())
+                                .instrument(info_span!(parent: &span, "record"));
+                            if let Err(e) = record.await {
                                 error!("Failed to record trade outcome: {}", e);
                             }
+                            *entry_times.write().await = Some(performance_db.get_performance_by_entry_time().await);
+                            *hold_times.write().await = performance_db.average_hold_hours(config.signals.expected_value.min_trades).await;
+                            if let Err(e) = threshold_learner.observe(&outcome.id).await {
+                                warn!("Failed to adjust entry thresholds: {}", e);
+                            }
+                            frequency_governor.observe().await;
+                            
+                            // Queue anomalous trades for an operator
+                            if trade_reviewer.review(&outcome).await.is_some() {
+                                if let Err(e) = trade_reviewer.export_report(&config.agentic.review_report_path).await {
+                                    warn!("Failed to export trade review report: {}", e);
+                                }
+                            }
                             
-                            // Recalculate adaptive sizing
+                            // Recalculate adaptive sizing and confidence calibration
                             adaptive_sizer.recalculate().await;
+                            calibrator.recalculate().await;
                         }
                         
                         let mut sm = state_machine.write().await;
@@ -355,7 +1296,11 @@ impl TradingAgent {
                         
                         // Log P&L
                         let realized_pnl = position_manager.get_realized_pnl().await;
-                        info!("Position closed. Trade P&L: ${:.2} | Total realized: ${:.2}", pnl, realized_pnl);
+                        info!(
+                            "Position closed ({}). Trade P&L: ${:.2} | Total realized: ${:.2}",
+                            close_reason, trade_pnl, realized_pnl
+                        );
+                        close_reason = CloseReason::Unknown;
                         
                         // Log updated performance metrics
                         let metrics = performance_db.get_metrics().await;
@@ -369,9 +1314,47 @@ impl TradingAgent {
                     }
                     
                     AgentState::Rebalancing => {
-                        // Execute rebalance
-                        if let Err(e) = rebalancer.execute_rebalance().await {
-                            error!("Rebalance failed: {}", e);
+                        // Execute rebalance; unfilled legs are retried on the next pass
+                        let deadline = Self::state_deadline(&state_machine, &config).await;
+                        let progress = state_machine.read().await.progress();
+                        let work = async {
+                            match manual_hedge.take() {
+                                Some(delta) => rebalancer.execute_adjustment(delta).await,
+                                None => rebalancer.execute_rebalance().await,
+                            }
+                        };
+                        let Ok(rebalance) = tokio::time::timeout_at(deadline, progress.track(work)).await else {
+                            // An unconfirmed tilt is dropped; the rebalancer restores the full hedge
+                            if pending_tilt.take().is_some() {
+                                state.hedge_tilt_pct.store(0.0);
+                            }
+                            Self::on_state_timeout(&state_machine, &event_tx, AgentState::Rebalancing).await;
+                            timed_out = Some(AgentState::Rebalancing);
+                            continue;
+                        };
+                        let tilt = pending_tilt.take();
+                        let tilted = rebalance.as_ref().map(|r| r.perp_traded).unwrap_or(0.0);
+                        match rebalance {
+                            Ok(result) if result.cost_usd > 0.0 => {
+                                if let Some(ctx) = current_trade_context.write().await.as_mut() {
+                                    ctx.fees_paid += result.cost_usd;
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(e) => error!("Rebalance failed: {}", e),
+                        }
+                        if let Some(mut tilt) = tilt {
+                            if tilted > 0.0 {
+                                // Held at what actually filled
+                                tilt.perp_delta_sol = tilted;
+                                if let Some(ctx) = current_trade_context.write().await.as_mut() {
+                                    state.hedge_tilt_pct.store(tilted / ctx.size * 100.0);
+                                    ctx.hedge_tilt = Some(tilt);
+                                }
+                            } else {
+                                warn!("Hedge tilt did not fill; keeping the full hedge");
+                                state.hedge_tilt_pct.store(0.0);
+                            }
                         }
                         
                         let mut sm = state_machine.write().await;
@@ -385,8 +1368,11 @@ impl TradingAgent {
                             let reversal_active = reversal_detector.is_reversal_active().await;
                             if !reversal_active {
                                 info!("Risk conditions cleared, resuming");
+                                let has_position = state.spot_position.read().is_some()
+                                    || state.perp_position.read().is_some();
                                 let mut sm = state_machine.write().await;
-                                sm.transition_to(AgentState::Idle);
+                                let target = Self::resume_state(sm.previous_state(), has_position);
+                                sm.transition_to(target);
                                 let _ = event_tx.send(Event::SystemResume);
                             } else {
                                 debug!("Waiting for funding reversal to clear before resuming");
@@ -395,10 +1381,55 @@ impl TradingAgent {
                     }
                     
                     AgentState::Error => {
-                        // Wait for manual intervention or timeout
-                        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                        let Some(stuck) = timed_out.take() else {
+                            // Wait for manual intervention or timeout
+                            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                            let mut sm = state_machine.write().await;
+                            sm.transition_to(AgentState::Idle);
+                            continue;
+                        };
+                        
+                        let timeouts = &config.state_timeouts;
+                        let has_position = state.spot_position.read().is_some()
+                            || state.perp_position.read().is_some();
                         let mut sm = state_machine.write().await;
-                        sm.transition_to(AgentState::Idle);
+                        match timeouts.recovery {
+                            TimeoutRecovery::Retry if timeout_retries < timeouts.max_retries => {
+                                timeout_retries += 1;
+                                // A stuck rebalance is re-evaluated from monitoring
+                                let target = if stuck == AgentState::Rebalancing { AgentState::Monitoring } else { stuck };
+                                warn!("Retrying {} after timeout ({}/{})", stuck, timeout_retries, timeouts.max_retries);
+                                sm.transition_to_with_reason(target, Some("Retry after timeout".to_string()));
+                            }
+                            TimeoutRecovery::Unwind => {
+                                if has_position {
+                                    warn!("Unwinding position after {} timeout", stuck);
+                                    close_reason = if stuck == AgentState::Rebalancing {
+                                        CloseReason::Rebalance
+                                    } else {
+                                        CloseReason::Timeout
+                                    };
+                                    sm.transition_to_with_reason(AgentState::Closing, Some("Unwind after timeout".to_string()));
+                                } else {
+                                    // Nothing was filled: drop the trade and any resting order
+                                    if let Some(ctx) = current_trade_context.write().await.take() {
+                                        if let Some(id) = ctx.perp_order_id {
+                                            if let Err(e) = order_tracker.cancel_order(&id).await {
+                                                warn!("Cancelling order {} failed: {}", id, e);
+                                            }
+                                        }
+                                    }
+                                    sm.transition_to_with_reason(AgentState::Idle, Some("Unwound after timeout".to_string()));
+                                }
+                            }
+                            _ => {
+                                // Pause, or retries exhausted: hold for an operator
+                                let reason = format!("{} timed out", stuck);
+                                control.pause(&reason);
+                                sm.transition_to_with_reason(AgentState::Paused, Some(reason.clone()));
+                                let _ = event_tx.send(Event::SystemPause { reason });
+                            }
+                        }
                     }
                 }
             }
@@ -409,26 +1440,289 @@ impl TradingAgent {
         Ok(())
     }
     
-    /// Check for trade signals
-    async fn check_for_signals(state: &Arc<SharedState>, config: &Arc<AppConfig>) -> Option<String> {
-        let basis = state.get_basis_spread();
-        let funding_apr = state.funding_apr.load();
+    /// Take a fresh, not yet acted on open signal from the signal engine
+    async fn check_for_signals(
+        signals: &RwLock<Option<FullTradeSignal>>,
+        last_seen: i64,
+        now: i64,
+    ) -> Option<FullTradeSignal> {
+        let signal = signals.read().await.clone()?;
         
-        // Check minimum thresholds
-        if basis.abs() >= config.trading.min_basis_spread_pct 
-            && funding_apr.abs() >= config.trading.min_funding_apr_pct 
+        if signal.signal.signal_type != SignalType::OpenBasis
+            || signal.signal.timestamp <= last_seen
+            || now - signal.signal.timestamp > SIGNAL_MAX_AGE_MS
         {
-            // Check alignment
-            let aligned = (basis > 0.0 && funding_apr > 0.0) || (basis < 0.0 && funding_apr < 0.0);
-            if aligned {
-                return Some(format!(
-                    "Basis: {:.4}%, Funding APR: {:.2}%",
-                    basis, funding_apr
-                ));
+            return None;
+        }
+        
+        Some(signal)
+    }
+    
+    /// Take a fresh open signal that a pause blocked and report it as skipped
+    async fn skip_pending_signal(
+        signals: &RwLock<Option<FullTradeSignal>>,
+        last_seen: &mut i64,
+        event_tx: &broadcast::Sender<Event>,
+        state: &SharedState,
+        reason: SkipReason,
+        detail: &str,
+    ) {
+        let now = chrono::Utc::now().timestamp_millis();
+        if let Some(signal) = Self::check_for_signals(signals, *last_seen, now).await {
+            *last_seen = signal.signal.timestamp;
+            Self::skip_signal(event_tx, state, reason, &signal.signal, signal.signal.size, detail.to_string());
+        }
+    }
+    
+    /// Report an open signal blocked by a filter, for skipped-trade tracking
+    fn skip_signal(
+        event_tx: &broadcast::Sender<Event>,
+        state: &SharedState,
+        reason: SkipReason,
+        signal: &TradeSignal,
+        size: f64,
+        detail: String,
+    ) {
+        debug!("Open signal skipped ({:?}): {}", reason, detail);
+        let _ = event_tx.send(Event::SignalSkipped {
+            reason,
+            detail,
+            size,
+            basis_spread: signal.basis_spread,
+            funding_apr: signal.funding_apr,
+            spot_price: state.snapshot().spot_price,
+            confidence: signal.confidence,
+            timestamp: signal.timestamp,
+        });
+    }
+    
+    /// Stand-in signal for an operator manual open at current market levels.
+    /// Zero confidence keeps manual trades out of confidence calibration
+    fn manual_signal(state: &SharedState, size_sol: f64, now: i64) -> TradeSignal {
+        let market = state.snapshot();
+        TradeSignal {
+            signal_type: SignalType::OpenBasis,
+            size: size_sol,
+            basis_spread: market.basis_spread,
+            funding_apr: market.funding_apr,
+            expected_profit: 0.0,
+            confidence: 0.0,
+            timestamp: now,
+            market_timestamp: now,
+            reason: format!("Operator manual open of {:.4} SOL", size_sol),
+            expected_value: None,
+        }
+    }
+    
+    /// Deadline for work in the current state, from its remaining time budget
+    async fn state_deadline(
+        state_machine: &RwLock<AgentStateMachine>,
+        config: &AppConfig,
+    ) -> tokio::time::Instant {
+        let remaining = state_machine.read().await
+            .time_remaining(&config.state_timeouts)
+            .unwrap_or(std::time::Duration::from_secs(365 * 24 * 3600));
+        tokio::time::Instant::now() + remaining
+    }
+    
+    /// Move a stuck state to Error and alert; recovery runs from Error
+    async fn on_state_timeout(
+        state_machine: &RwLock<AgentStateMachine>,
+        event_tx: &broadcast::Sender<Event>,
+        stuck: AgentState,
+    ) {
+        let mut sm = state_machine.write().await;
+        let mut reason = format!("{} exceeded its time limit after {}s", stuck, sm.time_in_state().as_secs());
+        if let Some((phase, in_phase)) = sm.progress().current() {
+            reason.push_str(&format!(", stuck in {} for {}s", phase, in_phase.as_secs()));
+        }
+        error!("{}", reason);
+        sm.transition_to_with_reason(AgentState::Error, Some(reason.clone()));
+        let _ = event_tx.send(Event::Alert(Alert::critical("Agent state timeout", reason)));
+    }
+    
+    /// Cancel a passive entry order resting on the book; fills it already
+    /// took are handled when the agent resumes into `AwaitingFill`
+    async fn cancel_resting_entry(order_tracker: &OrderTracker, current_trade_context: &RwLock<Option<TradeContext>>) {
+        let order_id = current_trade_context.read().await.as_ref().and_then(|c| c.perp_order_id.clone());
+        if let Some(id) = order_id {
+            match order_tracker.cancel_order(&id).await {
+                Ok(_) => info!("Cancelled resting entry order {} on pause", id),
+                Err(e) => warn!("Cancelling resting entry order {} failed: {}", id, e),
+            }
+        }
+    }
+    
+    /// Where a pause resumes: a passive entry goes back to collecting the
+    /// fills its order took, an open position back to monitoring, and
+    /// anything else to idle
+    fn resume_state(previous: Option<AgentState>, has_position: bool) -> AgentState {
+        match previous {
+            Some(AgentState::AwaitingFill) => AgentState::AwaitingFill,
+            _ if has_position => AgentState::Monitoring,
+            _ => AgentState::Idle,
+        }
+    }
+    
+    /// Fees on a paper fill of both legs at `prices`
+    fn paper_fees(config: &AppConfig, prices: LegPrices, size: f64) -> f64 {
+        let spot = prices.spot.unwrap_or(0.0) * config.execution.spot_fee_bps();
+        let perp = prices.perp.unwrap_or(0.0) * config.execution.perp_taker_fee_bps;
+        (spot + perp) * size / 10_000.0
+    }
+    
+    /// Reconcile legs after an open and pick the next state.
+    ///
+    /// The tranche is priced at the quotes its executions filled against,
+    /// falling back to `known` for legs filled before reconciliation.
+    /// Unwound opens return to idle with no trade; unresolved imbalances
+    /// and legs without a fill price pause.
+    async fn after_open(
+        reconciler: &LegReconciler,
+        legs: &Arc<dyn LegExecutor>,
+        journal: &ExecutionJournal,
+        event_tx: &broadcast::Sender<Event>,
+        current_trade_context: &RwLock<Option<TradeContext>>,
+        size: f64,
+        known: LegPrices,
+    ) -> AgentState {
+        let trade_id = current_trade_context.read().await
+            .as_ref()
+            .map(|c| c.id.clone())
+            .unwrap_or_default();
+        let outcome = Self::reconcile_legs(
+            reconciler,
+            legs,
+            journal,
+            event_tx,
+            &trade_id,
+            "open",
+            LegFills::new(size, -size),
+        ).await;
+        if let Some(ctx) = current_trade_context.write().await.as_mut() {
+            ctx.slippage.add(outcome.slippage);
+            ctx.fees_paid += outcome.volume.fees_usd;
+        }
+        
+        match outcome.action {
+            LegAction::None | LegAction::Completed => {
+                let prices = outcome.volume.quotes().or(known);
+                if let Some(ctx) = current_trade_context.write().await.as_mut() {
+                    if let Err(e) = ctx.price_tranche(prices) {
+                        let reason = format!("Cannot price the open: {}", e);
+                        error!("{}", reason);
+                        let _ = event_tx.send(Event::SystemPause { reason });
+                        return AgentState::Paused;
+                    }
+                }
+                AgentState::Monitoring
+            }
+            LegAction::Unwound => {
+                let mut context = current_trade_context.write().await;
+                // A failed ladder tranche leaves the tranches already held
+                if let Some(ctx) = context.as_mut().filter(|c| c.size > c.tranche_size) {
+                    ctx.size -= ctx.tranche_size;
+                    ctx.tranche_size = 0.0;
+                    warn!("Ladder tranche unwound after one-legged fill, keeping {:.4} SOL", ctx.size);
+                    return AgentState::Monitoring;
+                }
+                warn!("Open unwound after one-legged fill, no position taken");
+                context.take();
+                AgentState::Idle
+            }
+            LegAction::Failed => {
+                let reason = "Unresolved leg imbalance after open".to_string();
+                error!("{}", reason);
+                let _ = event_tx.send(Event::SystemPause { reason });
+                AgentState::Paused
+            }
+        }
+    }
+    
+    /// Compare filled legs against the target, complete or unwind as needed,
+    /// and journal any imbalance
+    async fn reconcile_legs(
+        reconciler: &LegReconciler,
+        legs: &Arc<dyn LegExecutor>,
+        journal: &ExecutionJournal,
+        event_tx: &broadcast::Sender<Event>,
+        trade_id: &str,
+        phase: &str,
+        target: LegFills,
+    ) -> ReconcileOutcome {
+        let outcome = match legs.leg_fills().await {
+            Ok(fills) => reconciler.reconcile(legs.as_ref(), target, fills).await,
+            Err(e) => {
+                error!("Failed to read the legs for reconciliation: {:#}", e);
+                ReconcileOutcome::unverified(target)
+            }
+        };
+        if !outcome.was_imbalanced() {
+            return outcome;
+        }
+        
+        let resolution = format!("{:?}", outcome.action).to_lowercase();
+        let _ = event_tx.send(Event::LegImbalance {
+            trade_id: trade_id.to_string(),
+            phase: phase.to_string(),
+            spot_filled: outcome.before.spot,
+            perp_filled: outcome.before.perp,
+            resolution,
+        });
+        
+        let entry = JournalEntry::new(
+            JournalKind::LegReconciliation,
+            trade_id,
+            serde_json::json!({ "phase": phase, "outcome": &outcome }),
+        );
+        if let Err(e) = journal.append(&entry).await {
+            error!("Failed to journal leg reconciliation: {}", e);
+        }
+        
+        outcome
+    }
+    
+    /// Log and journal a dry-run report
+    async fn record_shadow(journal: &ExecutionJournal, trade_id: &str, report: Result<ShadowReport>) {
+        let report = match report {
+            Ok(report) => report,
+            Err(e) => {
+                warn!("Dry-run failed before simulation: {:#}", e);
+                return;
             }
+        };
+        report.log();
+        
+        let entry = JournalEntry::new(JournalKind::ShadowExecution, trade_id, serde_json::json!(&report));
+        if let Err(e) = journal.append(&entry).await {
+            error!("Failed to journal dry-run report: {}", e);
         }
+    }
+    
+    /// Choose the perp entry order type from order book depth.
+    ///
+    /// Takes the book with a market order unless the estimated taker cost at
+    /// this size exceeds the configured maximum, in which case a limit order
+    /// is preferred. Without a book there is nothing to judge, so market.
+    fn choose_perp_order_type(
+        config: &AppConfig,
+        book: Option<&OrderBook>,
+        size: f64,
+        is_buy: bool,
+    ) -> OrderType {
+        let cost = match book {
+            Some(book) => book.taker_cost_pct(size, is_buy),
+            None => return OrderType::Market,
+        };
         
-        None
+        match cost {
+            Some(cost) if cost <= config.trading.max_perp_taker_cost_pct => OrderType::Market,
+            // Too expensive, or not enough visible depth to fill at all
+            _ => {
+                debug!("Perp taker cost {:?}% at {:.2} SOL, using limit order", cost, size);
+                OrderType::Limit
+            }
+        }
     }
     
     /// Stop the trading agent
@@ -443,6 +1737,26 @@ impl TradingAgent {
         self.state_machine.read().await.current_state()
     }
     
+    /// Current state with its execution phase and trade
+    pub async fn status(&self) -> AgentStatus {
+        self.state_machine.read().await.status()
+    }
+    
+    /// Whether the trading window currently allows entries
+    pub fn trading_window(&self) -> WindowStatus {
+        TradingWindow::new(&self.config).status()
+    }
+    
+    /// Shared state the agent trades on
+    pub fn shared_state(&self) -> &Arc<SharedState> {
+        &self.state
+    }
+    
+    /// Latest signal from the signal engine, if any
+    pub async fn latest_signal(&self) -> Option<FullTradeSignal> {
+        self.signals.read().await.clone()
+    }
+    
     /// Get risk manager
     pub fn risk_manager(&self) -> &Arc<RiskManager> {
         &self.risk_manager
@@ -458,6 +1772,16 @@ impl TradingAgent {
         &self.performance_db
     }
     
+    /// Persisted state transitions between two timestamps (ms), for post-mortems
+    pub async fn get_transitions(&self, start: i64, end: i64) -> Result<Vec<StateTransition>> {
+        self.performance_db.get_transitions(start, end).await
+    }
+    
+    /// Get order tracker
+    pub fn order_tracker(&self) -> &Arc<OrderTracker> {
+        &self.order_tracker
+    }
+    
     /// Get adaptive sizer
     pub fn adaptive_sizer(&self) -> &Arc<AdaptiveSizer> {
         &self.adaptive_sizer
@@ -468,15 +1792,45 @@ impl TradingAgent {
         &self.reversal_detector
     }
     
+    /// Get post-trade review queue
+    pub fn trade_reviewer(&self) -> &Arc<TradeReviewer> {
+        &self.trade_reviewer
+    }
+    
+    /// Get the skipped-trade tracker
+    pub fn skipped_trades(&self) -> &Arc<SkippedTradeTracker> {
+        &self.skipped_trades
+    }
+    
+    /// Get the entry threshold learner
+    pub fn threshold_learner(&self) -> &Arc<ThresholdLearner> {
+        &self.threshold_learner
+    }
+    
+    /// Get the trade-frequency governor
+    pub fn frequency_governor(&self) -> &Arc<FrequencyGovernor> {
+        &self.frequency_governor
+    }
+    
+    /// Get operator controls
+    pub fn control(&self) -> &Arc<AgentControl> {
+        &self.control
+    }
+    
     /// Get performance metrics
     pub async fn get_performance_metrics(&self) -> PerformanceMetrics {
         self.performance_db.get_metrics().await
     }
     
+    /// Predicted versus realized win rate by entry confidence
+    pub async fn get_calibration(&self) -> CalibrationReport {
+        self.calibrator.report().await
+    }
+    
     /// Get adaptive sizing recommendation
     pub async fn get_sizing_recommendation(&self, confidence: f64) -> SizingRecommendation {
-        let basis = self.state.get_basis_spread();
-        let funding_apr = self.state.funding_apr.load();
+        let market = self.state.snapshot();
+        let (basis, funding_apr) = (market.basis_spread, market.funding_apr);
         self.adaptive_sizer.get_recommended_size(basis, funding_apr, confidence).await
     }
     
@@ -484,14 +1838,39 @@ impl TradingAgent {
     pub async fn emergency_stop(&self) {
         warn!("Emergency stop triggered");
         let mut sm = self.state_machine.write().await;
+        if sm.current_state() == AgentState::AwaitingFill {
+            Self::cancel_resting_entry(&self.order_tracker, &self.current_trade_context).await;
+        }
         sm.transition_to(AgentState::Paused);
         let _ = self.event_tx.send(Event::SystemPause {
             reason: "Emergency stop".to_string(),
         });
     }
     
+    /// Close both legs from outside the agent loop, which must be stopped
+    pub async fn flatten(&self) -> ReconcileOutcome {
+        let trade_id = self.current_trade_context.read().await.as_ref().map(|c| c.id.clone()).unwrap_or_default();
+        if self.config.paper_trading {
+            self.position_manager.simulate_close(self.state.snapshot().spot_price).await;
+        }
+        Self::reconcile_legs(
+            &self.leg_reconciler,
+            &self.legs,
+            &self.journal,
+            &self.event_tx,
+            &trade_id,
+            "flatten",
+            LegFills::default(),
+        ).await
+    }
+    
     /// Export trade history to CSV
     pub async fn export_trades(&self, path: &str) -> Result<()> {
         self.performance_db.export_csv(path).await
     }
+    
+    /// Export the trade review queue as a markdown report
+    pub async fn export_review_report(&self, path: &str) -> Result<()> {
+        self.trade_reviewer.export_report(path).await
+    }
 }
@@ -0,0 +1,240 @@
+//! Panic Guard
+//!
+//! Tokio catches a panic inside a spawned task and only ends that task,
+//! which can leave a hedged position with nothing managing it. The guard
+//! installs a panic hook that reports a panic on any thread to a handler
+//! task, which brings the bot to a safe state and exits:
+//! - Emergency stop: the agent is paused and its loop stopped
+//! - Open legs are flattened (with `flatten_positions`), within
+//!   `flatten_timeout_secs`
+//! - A critical alert is sent straight to the alert channels
+//! - Risk counters and engine history are persisted
+//! - The process exits with `EXIT_CODE`, for the process manager to restart
+//!
+//! Panics reported before the handler starts wait in the channel. The
+//! handler needs panics to unwind (the release profile's `panic = "unwind"`):
+//! under abort the process ends as soon as the hook returns.
+
+use std::any::Any;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::config::PanicConfig;
+use crate::execution::LegAction;
+use crate::state::{warm_start, SharedState};
+use crate::telemetry::{Alert, AlertManager};
+
+use super::TradingAgent;
+
+/// Exit status after a panic (EX_SOFTWARE)
+pub const EXIT_CODE: i32 = 70;
+/// Time allowed for alerting and persistence on top of the flatten timeout
+const SETTLE_SECS: u64 = 15;
+
+/// A panic, as reported by the hook
+#[derive(Debug, Clone)]
+pub struct PanicReport {
+    pub thread: String,
+    pub location: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for PanicReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "thread '{}' panicked", self.thread)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Panic message from its payload
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+/// Report of a panic on the current thread, as the hook sends it
+fn report(location: Option<String>, payload: &(dyn Any + Send)) -> PanicReport {
+    PanicReport {
+        thread: std::thread::current().name().unwrap_or("unnamed").to_string(),
+        location,
+        message: payload_message(payload),
+    }
+}
+
+/// Chain a hook reporting every panic after the default output
+pub fn install() -> mpsc::UnboundedReceiver<PanicReport> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        previous(info);
+        let _ = tx.send(report(info.location().map(|l| l.to_string()), info.payload()));
+    }));
+    rx
+}
+
+/// Brings the bot to a safe state on the first panic
+pub struct PanicGuard {
+    config: PanicConfig,
+    agent: Arc<TradingAgent>,
+    alert_manager: Arc<AlertManager>,
+    state: Arc<SharedState>,
+    history_path: Option<PathBuf>,
+}
+
+impl PanicGuard {
+    pub fn new(
+        config: &PanicConfig,
+        agent: Arc<TradingAgent>,
+        alert_manager: Arc<AlertManager>,
+        state: Arc<SharedState>,
+    ) -> Self {
+        Self { config: config.clone(), agent, alert_manager, state, history_path: None }
+    }
+
+    /// Also save engine history for a warm restart
+    pub fn with_history(mut self, path: Option<PathBuf>) -> Self {
+        self.history_path = path;
+        self
+    }
+
+    /// Wait for a panic, reach a safe state and exit
+    pub fn spawn(self, panics: mpsc::UnboundedReceiver<PanicReport>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if self.handle(panics).await.is_some() {
+                std::process::exit(EXIT_CODE);
+            }
+        })
+    }
+
+    /// Wait for the first panic and reach a safe state; None if the hook
+    /// went away first
+    pub async fn handle(self, mut panics: mpsc::UnboundedReceiver<PanicReport>) -> Option<PanicReport> {
+        let budget = Duration::from_secs(self.config.flatten_timeout_secs + SETTLE_SECS);
+        let report = panics.recv().await?;
+        error!("Panic, entering safe state: {}", report);
+
+        // In its own task, so a second panic here still ends in an exit
+        let guard = Arc::new(self);
+        let handled = report.clone();
+        let work = tokio::spawn(async move { guard.safe_state(&handled).await });
+        match tokio::time::timeout(budget, work).await {
+            Ok(Ok(())) => info!("Safe state reached, exiting"),
+            Ok(Err(e)) => error!("Safe state handling failed: {}", e),
+            Err(_) => error!("Safe state handling timed out after {:?}", budget),
+        }
+        Some(report)
+    }
+
+    async fn safe_state(&self, report: &PanicReport) {
+        self.agent.control().pause(&format!("Panic: {}", report.message));
+        self.agent.emergency_stop().await;
+        self.agent.stop().await;
+
+        let positions = if !self.agent.position_manager().has_position().await {
+            "no open position".to_string()
+        } else if !self.config.flatten_positions {
+            "position left open (flatten_positions is off)".to_string()
+        } else {
+            let timeout = Duration::from_secs(self.config.flatten_timeout_secs);
+            match tokio::time::timeout(timeout, self.agent.flatten()).await {
+                Ok(outcome) if outcome.action != LegAction::Failed => "position flattened".to_string(),
+                Ok(outcome) => format!(
+                    "flatten failed, spot {:.4} / perp {:.4} SOL still open",
+                    outcome.after.spot, outcome.after.perp
+                ),
+                Err(_) => format!("flatten timed out after {}s, position may be open", timeout.as_secs()),
+            }
+        };
+        warn!("Safe state: {}", positions);
+
+        self.alert_manager
+            .send(Alert::critical(
+                "Bot panicked and is exiting",
+                format!("{}; trading stopped, {}", report, positions),
+            ))
+            .await;
+
+        self.agent.risk_manager().flush().await;
+        if let Some(path) = &self.history_path {
+            if let Err(e) = warm_start::save(&self.state, path).await {
+                warn!("Failed to save engine history: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_from_payload() {
+        let payload = std::panic::catch_unwind(|| panic!("leg {} missing", "perp")).unwrap_err();
+        let report = PanicReport {
+            thread: "tokio-runtime-worker".to_string(),
+            location: Some("src/agent/mod.rs:10:5".to_string()),
+            message: payload_message(payload.as_ref()),
+        };
+        assert_eq!(
+            report.to_string(),
+            "thread 'tokio-runtime-worker' panicked at src/agent/mod.rs:10:5: leg perp missing"
+        );
+        assert_eq!(payload_message(&42u32), "non-string panic payload");
+    }
+
+    /// Drives the hook's report by hand: installing the process-wide hook
+    /// here would take over panics in every other test
+    #[tokio::test]
+    async fn test_panic_in_spawned_task_reaches_the_handler() {
+        use crate::config::AppConfig;
+        use crate::network::EventBus;
+        use crate::position::PositionManager;
+        use tokio::sync::RwLock;
+
+        let dir = std::env::temp_dir().join(format!("panic-guard-{}", uuid::Uuid::new_v4()));
+        let mut config = AppConfig::default_for_test();
+        config.agentic.performance_db_path = dir.join("performance.json").to_string_lossy().into_owned();
+        let config = Arc::new(config);
+        let state = Arc::new(SharedState::new());
+        let agent = Arc::new(
+            TradingAgent::new(
+                config.clone(),
+                state.clone(),
+                Arc::new(PositionManager::new(state.clone())),
+                EventBus::new(64).sender(),
+                Arc::new(RwLock::new(None)),
+                Arc::new(RwLock::new(None)),
+            )
+            .await
+            .unwrap(),
+        );
+        let alerts = Arc::new(AlertManager::new(&config.telemetry));
+        let guard = PanicGuard::new(&config.supervisor.panic, agent.clone(), alerts, state);
+
+        let (tx, panics) = mpsc::unbounded_channel();
+        let task = tokio::spawn(async { panic!("leg task died") });
+        let payload = task.await.unwrap_err().into_panic();
+        tx.send(report(None, payload.as_ref())).unwrap();
+
+        let report = tokio::time::timeout(Duration::from_secs(30), guard.handle(panics))
+            .await
+            .expect("handler did not finish")
+            .expect("panic was not reported");
+        assert_eq!(report.message, "leg task died");
+        assert!(agent.control().is_paused());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
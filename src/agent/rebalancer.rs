@@ -3,16 +3,18 @@
 //! Handles hedge rebalancing:
 //! - Monitors hedge drift
 //! - Calculates rebalance amounts
-//! - Executes rebalance trades
+//! - Executes rebalance trades (live through the execution manager)
+//! - Charges rebalance costs to position P&L
 //! - Rate limiting
+//! - Operator hedge adjustments through the same execution path
 
-use anyhow::Result;
+use anyhow::{ensure, Context, Result};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
-use tokio::sync::RwLock;
 use tracing::{info, warn, debug};
 
 use crate::config::AppConfig;
+use crate::execution::{SubmissionPath, TradeExecutor};
 use crate::position::PositionManager;
 use crate::state::SharedState;
 
@@ -38,10 +40,14 @@ pub struct RebalanceResult {
     pub spot_traded: f64,
     /// Perp size traded
     pub perp_traded: f64,
-    /// Transaction signature
+    /// Transaction signature(s), comma separated
     pub signature: Option<String>,
+    /// How each filled leg was submitted, in signature order
+    pub paths: Vec<SubmissionPath>,
     /// Error message if failed
     pub error: Option<String>,
+    /// Fees and slippage charged to P&L (USD)
+    pub cost_usd: f64,
 }
 
 /// Rebalancer
@@ -58,6 +64,8 @@ pub struct Rebalancer {
     rebalance_count: AtomicU64,
     /// Hour of count reset
     count_reset_hour: AtomicI64,
+    /// Live execution path (unused when paper trading)
+    execution: Option<Arc<dyn TradeExecutor>>,
 }
 
 impl Rebalancer {
@@ -74,9 +82,16 @@ impl Rebalancer {
             last_rebalance: AtomicI64::new(0),
             rebalance_count: AtomicU64::new(0),
             count_reset_hour: AtomicI64::new(0),
+            execution: None,
         }
     }
     
+    /// Place live rebalance orders through an executor
+    pub fn with_execution(mut self, execution: Arc<dyn TradeExecutor>) -> Self {
+        self.execution = Some(execution);
+        self
+    }
+    
     /// Check if rebalancing is needed
     pub async fn needs_rebalance(&self) -> bool {
         let decision = self.evaluate().await;
@@ -114,10 +129,10 @@ impl Rebalancer {
         let perp_size = positions.perp_size;
         
         // Calculate adjustment needed to restore 1:1 hedge
-        // Drift > 0 means spot > perp (need to increase perp or decrease spot)
-        // Drift < 0 means perp > spot (need to increase spot or decrease perp)
+        // Drift > 0 means spot > perp (need to increase the short or decrease spot)
+        // Drift < 0 means perp > spot (need to increase spot or decrease the short)
         let (spot_adjustment, perp_adjustment) = if hedge_drift > 0.0 {
-            // Reduce spot or increase perp
+            // Reduce spot or increase the short
             let adjustment = spot_size * (hedge_drift / 100.0);
             if adjustment < self.config.rebalance.min_rebalance_size_sol {
                 return RebalanceDecision {
@@ -127,9 +142,9 @@ impl Rebalancer {
                     reason: format!("Adjustment {:.4} below minimum", adjustment),
                 };
             }
-            (-adjustment / 2.0, adjustment / 2.0) // Split adjustment
+            (-adjustment / 2.0, -adjustment / 2.0) // Split adjustment
         } else {
-            // Increase spot or reduce perp
+            // Increase spot or reduce the short
             let adjustment = perp_size * (-hedge_drift / 100.0);
             if adjustment < self.config.rebalance.min_rebalance_size_sol {
                 return RebalanceDecision {
@@ -139,7 +154,7 @@ impl Rebalancer {
                     reason: format!("Adjustment {:.4} below minimum", adjustment),
                 };
             }
-            (adjustment / 2.0, -adjustment / 2.0) // Split adjustment
+            (adjustment / 2.0, adjustment / 2.0) // Split adjustment
         };
         
         RebalanceDecision {
@@ -156,14 +171,39 @@ impl Rebalancer {
     /// Execute rebalancing
     pub async fn execute_rebalance(&self) -> Result<RebalanceResult> {
         let decision = self.evaluate().await;
+        self.execute(decision).await
+    }
+    
+    /// Trade the perp leg by `perp_delta` SOL (positive reduces the short) at
+    /// the operator's request, regardless of drift and rate limits
+    pub async fn execute_adjustment(&self, perp_delta: f64) -> Result<RebalanceResult> {
+        let positions = self.position_manager.get_positions().await;
+        ensure!(positions.perp_size > 0.0, "No perp hedge to adjust");
+        ensure!(
+            perp_delta.abs() <= self.config.trading.max_position_size_sol,
+            "Hedge adjustment {:.4} SOL exceeds max position size",
+            perp_delta
+        );
         
+        self.execute(RebalanceDecision {
+            should_rebalance: true,
+            spot_adjustment: 0.0,
+            perp_adjustment: perp_delta,
+            reason: format!("Operator hedge adjustment {:+.4} SOL", perp_delta),
+        }).await
+    }
+    
+    /// Fill a rebalance decision, paper or live
+    async fn execute(&self, decision: RebalanceDecision) -> Result<RebalanceResult> {
         if !decision.should_rebalance {
             return Ok(RebalanceResult {
                 success: false,
                 spot_traded: 0.0,
                 perp_traded: 0.0,
                 signature: None,
+                paths: Vec::new(),
                 error: Some(decision.reason),
+                cost_usd: 0.0,
             });
         }
         
@@ -175,11 +215,13 @@ impl Rebalancer {
         // Record rebalance attempt
         self.record_rebalance();
         
-        // In paper trading mode, just update positions
+        // In paper trading mode, fill at reference prices with estimated costs
         if self.config.paper_trading {
-            self.position_manager.adjust_positions(
+            let cost_usd = self.estimate_cost(decision.spot_adjustment, decision.perp_adjustment);
+            self.position_manager.adjust_positions_with_cost(
                 decision.spot_adjustment,
                 decision.perp_adjustment,
+                cost_usd,
             ).await;
             
             // Update hedge drift in state
@@ -190,28 +232,96 @@ impl Rebalancer {
                 spot_traded: decision.spot_adjustment,
                 perp_traded: decision.perp_adjustment,
                 signature: Some("paper_trade".to_string()),
+                paths: Vec::new(),
                 error: None,
+                cost_usd,
             });
         }
         
-        // Real execution would go here
-        // For now, simulate success
-        self.position_manager.adjust_positions(
-            decision.spot_adjustment,
-            decision.perp_adjustment,
-        ).await;
+        let execution = self.execution.as_ref()
+            .context("Live rebalancing needs an execution manager")?;
+        
+        // Each leg narrows the drift on its own. A failed leg is left for the
+        // next rebalance; only what actually filled is applied to positions
+        let mut signatures = Vec::new();
+        let mut paths = Vec::new();
+        let mut errors = Vec::new();
+        let mut cost_usd = 0.0;
+        
+        let mut spot_traded = 0.0;
+        if decision.spot_adjustment != 0.0 {
+            match execution.trade_spot(decision.spot_adjustment).await {
+                Ok(fill) => {
+                    spot_traded = fill.filled;
+                    cost_usd += fill.cost_usd;
+                    signatures.push(fill.signature);
+                    paths.push(fill.path);
+                }
+                Err(e) => errors.push(format!("spot leg failed: {:#}", e)),
+            }
+        }
+        
+        let mut perp_traded = 0.0;
+        if decision.perp_adjustment != 0.0 {
+            // Buying back part of the short must never flip it long
+            let reduce_only = decision.perp_adjustment > 0.0;
+            match execution.trade_perp(decision.perp_adjustment, reduce_only).await {
+                Ok(fill) => {
+                    perp_traded = fill.filled;
+                    cost_usd += fill.cost_usd;
+                    signatures.push(fill.signature);
+                    paths.push(fill.path);
+                }
+                Err(e) => errors.push(format!("perp leg failed: {:#}", e)),
+            }
+        }
+        
+        if spot_traded != 0.0 || perp_traded != 0.0 {
+            self.position_manager.adjust_positions_with_cost(spot_traded, perp_traded, cost_usd).await;
+            self.update_hedge_drift().await;
+        }
         
-        self.update_hedge_drift().await;
+        let error = if errors.is_empty() {
+            info!(
+                "Rebalance filled: spot {:.4}, perp {:.4}, cost ${:.4} (via {:?})",
+                spot_traded, perp_traded, cost_usd, paths
+            );
+            None
+        } else {
+            let error = errors.join("; ");
+            warn!("Rebalance incomplete: {}", error);
+            Some(error)
+        };
         
         Ok(RebalanceResult {
-            success: true,
-            spot_traded: decision.spot_adjustment,
-            perp_traded: decision.perp_adjustment,
-            signature: None,
-            error: None,
+            success: errors.is_empty(),
+            spot_traded,
+            perp_traded,
+            signature: (!signatures.is_empty()).then(|| signatures.join(",")),
+            paths,
+            error,
+            cost_usd,
         })
     }
     
+    /// Estimated fees and slippage of a paper rebalance (USD)
+    fn estimate_cost(&self, spot_delta: f64, perp_delta: f64) -> f64 {
+        let market = self.state.snapshot();
+        
+        // Spot: effective price at depth against the reference price
+        let fill = if spot_delta > 0.0 { market.spot_buy_price } else { market.spot_sell_price };
+        let spot_cost = if fill > 0.0 && market.spot_price > 0.0 {
+            ((fill - market.spot_price) * spot_delta).max(0.0)
+        } else {
+            0.0
+        };
+        
+        let perp_cost = market.perp_mark_price * perp_delta.abs()
+            * self.config.execution.perp_taker_fee_bps / 10_000.0;
+        
+        spot_cost + perp_cost
+    }
+    
     /// Check if rebalancing is allowed (rate limiting)
     fn can_rebalance(&self) -> bool {
         let now = chrono::Utc::now();
@@ -251,14 +361,14 @@ impl Rebalancer {
         self.rebalance_count.fetch_add(1, Ordering::SeqCst);
     }
     
-    /// Update hedge drift in state
+    /// Update hedge drift in state, measured from any reversal tilt
     async fn update_hedge_drift(&self) {
         let positions = self.position_manager.get_positions().await;
         let spot_size = positions.spot_size;
         let perp_size = positions.perp_size;
         
         let drift = if spot_size > 0.0 {
-            ((spot_size - perp_size) / spot_size) * 100.0
+            ((spot_size - perp_size) / spot_size) * 100.0 - self.state.hedge_tilt_pct.load()
         } else {
             0.0
         };
@@ -302,4 +412,28 @@ mod tests {
         };
         assert!(decision.should_rebalance);
     }
+
+    #[tokio::test]
+    async fn test_paper_rebalance_restores_hedge_and_charges_cost() {
+        let config = Arc::new(AppConfig::default_for_test());
+        let state = Arc::new(SharedState::new());
+        state.update_spot_price(150.0);
+        state.update_perp_mark_price(150.0);
+
+        // 100 SOL spot against an 80 SOL short: 20% drift
+        let positions = Arc::new(PositionManager::new(state.clone()));
+        positions.simulate_open(150.0, 100.0).await;
+        positions.adjust_positions(0.0, 20.0).await;
+        state.hedge_drift.store(20.0);
+
+        let rebalancer = Rebalancer::new(config, state, positions.clone());
+        let decision = rebalancer.evaluate().await;
+        assert_eq!((decision.spot_adjustment, decision.perp_adjustment), (-10.0, -10.0));
+
+        let result = rebalancer.execute_rebalance().await.unwrap();
+        assert!(result.success);
+        assert!(result.cost_usd > 0.0);
+        assert!(positions.leg_fills().await.net_delta().abs() < 1e-9);
+        assert!((positions.get_realized_pnl().await + result.cost_usd).abs() < 1e-9);
+    }
 }
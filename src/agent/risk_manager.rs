@@ -6,28 +6,223 @@
 //! - Position size limits
 //! - Daily loss limits
 //! - Circuit breakers
+//!
+//! The high-water mark is persisted next to the performance database and
+//! restored on startup, so drawdown protection survives restarts. Daily P&L
+//! is kept in the daily ledger, with days starting at midnight in
+//! `risk.daily_timezone`, so the daily loss limit survives them too.
+//!
+//! Each failed check is a typed `RiskViolation`; whether it pauses trading,
+//! closes the position, or only warns is decided per rule.
+//!
+//! The daily loss, drawdown, stop loss and hedge drift limits can be
+//! overridden for a while by an operator (see `risk_overrides`).
 
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use tokio::sync::{broadcast, RwLock};
 use tracing::{info, warn, error, debug};
 
-use crate::config::AppConfig;
-use crate::state::SharedState;
+use super::risk_overrides::{self, OverrideAction, RiskLimit, RiskOverride, RiskOverrideEvent};
+
+use crate::agentic::daily_ledger::trading_day;
+use crate::agentic::{DailyLedger, DailyLedgerEntry, PerformanceDb, RiskState, TradeOutcome};
+use crate::config::{AppConfig, VolatilityAction};
+use crate::network::Event;
+use crate::state::{AtomicF64, SharedState};
+use crate::utils::types::GasLevel;
+
+/// Minimum time between high-water mark writes (seconds)
+const PERSIST_INTERVAL_SECS: i64 = 10;
+
+/// Errors since the daily reset before trading pauses
+const MAX_ERRORS: u64 = 10;
+
+/// How serious a violation is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskSeverity {
+    /// Informational, no action taken
+    Warning,
+    /// Trading must stop until it clears
+    High,
+    /// Capital at risk; exit as well as stop
+    Critical,
+}
+
+/// A failed risk check, with the values that failed it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum RiskViolation {
+    /// Drawdown from the equity peak reached the limit
+    DrawdownExceeded { drawdown_pct: f64, limit_pct: f64 },
+    /// Drawdown is within 20% of the limit
+    DrawdownWarning { drawdown_pct: f64, limit_pct: f64 },
+    /// Position loss reached the stop
+    StopLoss { loss_pct: f64, limit_pct: f64 },
+    /// Spot and perp legs have drifted apart
+    HedgeDrift { drift_pct: f64, limit_pct: f64 },
+    /// RPC connection lost
+    RpcDown,
+    /// Realized losses today reached the limit
+    DailyLossLimit { pnl_usd: f64, limit_usd: f64 },
+    /// Too many errors since the daily reset
+    ErrorRate { errors: u64, limit: u64 },
+    /// Wallet SOL for fees is below the warning threshold
+    LowGas { balance_sol: f64, threshold_sol: f64 },
+    /// Wallet SOL for fees is below the critical threshold
+    GasCritical { balance_sol: f64, threshold_sol: f64 },
+    /// Spot oracles disagree by more than the limit
+    OracleDivergence { divergence_pct: f64, limit_pct: f64 },
+    /// Perp mark strays from its index: a stressed or manipulated perp market
+    MarkIndexDivergence { divergence_pct: f64, limit_pct: f64 },
+    /// Pyth is stale; spot is priced from the secondary sources
+    OracleContingency,
+    /// Realized volatility is above its trigger percentile; entries stop
+    /// with `block_entries`, otherwise the sizer scales them down
+    HighVolatility { vol_pct: f64, threshold_pct: f64, block_entries: bool },
+}
+
+impl RiskViolation {
+    pub fn severity(&self) -> RiskSeverity {
+        match self {
+            Self::HighVolatility { block_entries, .. } if *block_entries => RiskSeverity::High,
+            Self::DrawdownWarning { .. } | Self::LowGas { .. } | Self::HighVolatility { .. } => RiskSeverity::Warning,
+            Self::StopLoss { .. }
+            | Self::HedgeDrift { .. }
+            | Self::RpcDown
+            | Self::ErrorRate { .. }
+            | Self::GasCritical { .. }
+            | Self::OracleDivergence { .. }
+            | Self::MarkIndexDivergence { .. }
+            | Self::OracleContingency => RiskSeverity::High,
+            Self::DrawdownExceeded { .. } | Self::DailyLossLimit { .. } => RiskSeverity::Critical,
+        }
+    }
+
+    /// Whether new trades must stop
+    pub fn pauses(&self) -> bool {
+        match self {
+            Self::DrawdownExceeded { .. }
+            | Self::HedgeDrift { .. }
+            | Self::RpcDown
+            | Self::DailyLossLimit { .. }
+            | Self::ErrorRate { .. } => true,
+            // A stop loss exits the trade; the strategy itself is still sound
+            Self::StopLoss { .. } | Self::DrawdownWarning { .. } => false,
+            // Pausing would also hold back closes, which still need to go through
+            Self::LowGas { .. } | Self::GasCritical { .. } => false,
+            // The spot price is in doubt for entries; exits on it are still safer than holding
+            Self::OracleDivergence { .. } | Self::MarkIndexDivergence { .. } | Self::OracleContingency => false,
+            // Open trades are hedged; only new risk is held back
+            Self::HighVolatility { .. } => false,
+        }
+    }
+
+    /// Whether new entries must stop, even if trading is not paused
+    pub fn blocks_entries(&self) -> bool {
+        self.pauses()
+            || matches!(
+                self,
+                Self::GasCritical { .. }
+                    | Self::OracleDivergence { .. }
+                    | Self::MarkIndexDivergence { .. }
+                    | Self::OracleContingency
+                    | Self::HighVolatility { block_entries: true, .. }
+            )
+    }
+
+    /// Whether an open position must be closed
+    pub fn closes(&self) -> bool {
+        matches!(self, Self::DrawdownExceeded { .. } | Self::StopLoss { .. })
+    }
+
+    /// Contribution to the risk score
+    fn score(&self) -> f64 {
+        match self {
+            Self::DrawdownExceeded { .. } => 50.0,
+            Self::DrawdownWarning { .. } => 25.0,
+            Self::StopLoss { .. } => 30.0,
+            Self::HedgeDrift { .. } => 20.0,
+            Self::RpcDown => 25.0,
+            Self::DailyLossLimit { .. } => 40.0,
+            Self::ErrorRate { .. } => 15.0,
+            Self::LowGas { .. } => 5.0,
+            Self::GasCritical { .. } => 20.0,
+            Self::OracleDivergence { .. } => 15.0,
+            Self::MarkIndexDivergence { .. } => 15.0,
+            Self::OracleContingency => 15.0,
+            Self::HighVolatility { .. } => 10.0,
+        }
+    }
+}
+
+impl fmt::Display for RiskViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DrawdownExceeded { drawdown_pct, .. } => write!(f, "Max drawdown exceeded: {:.2}%", drawdown_pct),
+            Self::DrawdownWarning { drawdown_pct, .. } => write!(f, "Drawdown warning: {:.2}%", drawdown_pct),
+            Self::StopLoss { loss_pct, .. } => write!(f, "Stop loss triggered: {:.2}%", loss_pct),
+            Self::HedgeDrift { drift_pct, .. } => write!(f, "Excessive hedge drift: {:.2}%", drift_pct),
+            Self::RpcDown => write!(f, "RPC disconnected"),
+            Self::DailyLossLimit { pnl_usd, .. } => write!(f, "Daily loss limit: ${:.2}", pnl_usd),
+            Self::ErrorRate { errors, .. } => write!(f, "High error count: {}", errors),
+            Self::LowGas { balance_sol, .. } => write!(f, "Wallet gas low: {:.4} SOL", balance_sol),
+            Self::GasCritical { balance_sol, .. } => write!(f, "Wallet gas critical: {:.4} SOL", balance_sol),
+            Self::OracleDivergence { divergence_pct, .. } => write!(f, "Spot oracles diverge: {:.2}%", divergence_pct),
+            Self::MarkIndexDivergence { divergence_pct, .. } => write!(f, "Perp mark off index: {:+.2}%", divergence_pct),
+            Self::OracleContingency => write!(f, "Pyth stale, spot from secondary oracles"),
+            Self::HighVolatility { vol_pct, threshold_pct, .. } => {
+                write!(f, "Realized volatility {:.1}% above {:.1}%", vol_pct, threshold_pct)
+            }
+        }
+    }
+}
 
 /// Risk check result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct RiskCheckResult {
-    /// Should pause trading
-    pub should_pause: bool,
-    /// Should close positions
-    pub should_close: bool,
-    /// Reasons for the decision
-    pub reasons: Vec<String>,
+    /// Failed checks
+    pub violations: Vec<RiskViolation>,
     /// Risk score (0-100, higher = more risky)
     pub risk_score: f64,
 }
 
+impl RiskCheckResult {
+    fn from_violations(violations: Vec<RiskViolation>) -> Self {
+        let risk_score = violations.iter().map(RiskViolation::score).sum::<f64>().min(100.0);
+        Self { violations, risk_score }
+    }
+
+    /// Should pause trading
+    pub fn should_pause(&self) -> bool {
+        self.violations.iter().any(RiskViolation::pauses)
+    }
+
+    /// Should skip new entries
+    pub fn blocks_entries(&self) -> bool {
+        self.violations.iter().any(RiskViolation::blocks_entries)
+    }
+
+    /// Should close positions
+    pub fn should_close(&self) -> bool {
+        self.violations.iter().any(RiskViolation::closes)
+    }
+
+    /// Most severe violation, if any
+    pub fn max_severity(&self) -> Option<RiskSeverity> {
+        self.violations.iter().map(RiskViolation::severity).max()
+    }
+
+    /// Violations joined for logs and events
+    pub fn summary(&self) -> String {
+        self.violations.iter().map(|v| v.to_string()).collect::<Vec<_>>().join("; ")
+    }
+}
+
 /// Risk metrics
 #[derive(Debug, Clone)]
 pub struct RiskMetrics {
@@ -55,16 +250,26 @@ pub struct RiskManager {
     state: Arc<SharedState>,
     /// Peak equity (high water mark)
     peak_equity: AtomicU64,
-    /// Daily P&L tracking
-    daily_pnl: AtomicI64,
-    /// Trade count today
-    trades_today: AtomicU64,
+    /// Realized P&L per trading day
+    ledger: parking_lot::RwLock<DailyLedger>,
     /// Last reset timestamp
     last_reset: AtomicI64,
     /// Is paused
     paused: RwLock<bool>,
     /// Pause reason
     pause_reason: RwLock<Option<String>>,
+    /// Realized P&L from before this run (USD)
+    realized_base: AtomicF64,
+    /// Where risk counters are persisted
+    store: Option<Arc<PerformanceDb>>,
+    /// Counters changed since the last write
+    dirty: AtomicBool,
+    /// Last write (unix seconds)
+    last_persist: AtomicI64,
+    /// Operator overrides in force
+    overrides: parking_lot::RwLock<Vec<RiskOverride>>,
+    /// Alerts on override changes
+    event_tx: Option<broadcast::Sender<Event>>,
 }
 
 impl RiskManager {
@@ -74,34 +279,207 @@ impl RiskManager {
             config,
             state,
             peak_equity: AtomicU64::new(0),
-            daily_pnl: AtomicI64::new(0),
-            trades_today: AtomicU64::new(0),
+            ledger: parking_lot::RwLock::new(DailyLedger::default()),
             last_reset: AtomicI64::new(chrono::Utc::now().timestamp()),
             paused: RwLock::new(false),
             pause_reason: RwLock::new(None),
+            realized_base: AtomicF64::new(0.0),
+            store: None,
+            dirty: AtomicBool::new(false),
+            last_persist: AtomicI64::new(0),
+            overrides: parking_lot::RwLock::new(Vec::new()),
+            event_tx: None,
+        }
+    }
+    
+    /// Persist the high-water mark and daily ledger in the performance store
+    pub fn with_store(mut self, store: Arc<PerformanceDb>) -> Self {
+        self.store = Some(store);
+        self
+    }
+    
+    /// Alert on risk limit overrides being set, cleared or expiring
+    pub fn with_events(mut self, event_tx: broadcast::Sender<Event>) -> Self {
+        self.event_tx = Some(event_tx);
+        self
+    }
+    
+    /// Restore the persisted high-water mark, daily ledger and active limit
+    /// overrides. A missing ledger is rebuilt from the closed trades.
+    pub async fn restore(&self) -> Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let ledger = match store.load_daily_ledger().await? {
+            Some(ledger) => ledger,
+            None => {
+                let ledger = DailyLedger::rebuild(&store.get_all_trades().await, self.config.risk.daily_timezone);
+                store.save_daily_ledger(&ledger).await?;
+                ledger
+            }
+        };
+        *self.ledger.write() = ledger;
+        
+        let active = risk_overrides::replay(&store.get_risk_overrides().await?, chrono::Utc::now().timestamp_millis());
+        for o in &active {
+            warn!("Restored risk limit override: {} {} until {} ({})", o.limit, o.value, o.expires_at, o.reason);
+        }
+        *self.overrides.write() = active;
+        
+        let Some(saved) = store.load_risk_state().await? else {
+            return Ok(());
+        };
+        self.peak_equity.store((saved.peak_equity * 1_000_000.0) as u64, Ordering::SeqCst);
+        self.realized_base.store(saved.realized_pnl);
+        
+        let today = self.today_entry();
+        info!(
+            "Restored risk state: peak equity ${:.2}, daily P&L ${:.2} over {} trades",
+            saved.peak_equity,
+            today.as_ref().map_or(0.0, |e| e.realized_pnl),
+            today.as_ref().map_or(0, |e| e.trades)
+        );
+        Ok(())
+    }
+    
+    /// Current trading day, in `risk.daily_timezone`
+    fn today(&self) -> chrono::NaiveDate {
+        trading_day(self.config.risk.daily_timezone, chrono::Utc::now().timestamp_millis())
+    }
+    
+    /// Today's ledger row, None until a trade closes today
+    pub fn today_entry(&self) -> Option<DailyLedgerEntry> {
+        self.ledger.read().day(self.today()).cloned()
+    }
+    
+    /// The daily ledger, oldest day first
+    pub fn daily_ledger(&self) -> DailyLedger {
+        self.ledger.read().clone()
+    }
+    
+    /// Current counters, as persisted
+    pub fn risk_state(&self) -> RiskState {
+        let today = self.today_entry();
+        RiskState {
+            peak_equity: self.peak_equity.load(Ordering::SeqCst) as f64 / 1_000_000.0,
+            realized_pnl: self.realized_base.load() + self.state.realized_pnl.load(),
+            daily_pnl: today.as_ref().map_or(0.0, |e| e.realized_pnl),
+            trades_today: today.as_ref().map_or(0, |e| e.trades as u64),
+            last_reset: self.last_reset.load(Ordering::SeqCst),
+        }
+    }
+    
+    /// Limit in force: the operator's override, else the configured value
+    pub fn limit(&self, limit: RiskLimit) -> f64 {
+        self.overrides
+            .read()
+            .iter()
+            .find(|o| o.limit == limit)
+            .map_or_else(|| limit.configured(&self.config.risk), |o| o.value)
+    }
+    
+    /// Overrides in force
+    pub fn overrides(&self) -> Vec<RiskOverride> {
+        self.overrides.read().clone()
+    }
+    
+    /// Override `limit` with `value` for `duration_mins`, replacing any
+    /// override of it in force. Fails outside `risk.overrides` or if the
+    /// override cannot be logged.
+    pub async fn set_override(&self, limit: RiskLimit, value: f64, duration_mins: u64, reason: &str) -> Result<RiskOverride> {
+        risk_overrides::check(&self.config.risk, limit, value, duration_mins)?;
+        let now = chrono::Utc::now().timestamp_millis();
+        let details = RiskOverride {
+            limit,
+            value,
+            configured: limit.configured(&self.config.risk),
+            reason: reason.to_string(),
+            set_at: now,
+            expires_at: now + duration_mins as i64 * 60_000,
+        };
+        self.audit(OverrideAction::Set, details.clone()).await?;
+        let mut overrides = self.overrides.write();
+        overrides.retain(|o| o.limit != limit);
+        overrides.push(details.clone());
+        Ok(details)
+    }
+    
+    /// Return `limit` to its configured value, None if it was not overridden
+    pub async fn clear_override(&self, limit: RiskLimit) -> Result<Option<RiskOverride>> {
+        let Some(cleared) = self.overrides.read().iter().find(|o| o.limit == limit).cloned() else {
+            return Ok(None);
+        };
+        self.audit(OverrideAction::Cleared, cleared.clone()).await?;
+        self.overrides.write().retain(|o| o.limit != limit);
+        Ok(Some(cleared))
+    }
+    
+    /// Drop overrides past their expiry
+    async fn expire_overrides(&self) {
+        let now = chrono::Utc::now().timestamp_millis();
+        let expired: Vec<RiskOverride> = {
+            let mut overrides = self.overrides.write();
+            let (expired, active): (Vec<_>, Vec<_>) = overrides.drain(..).partition(|o| o.expires_at <= now);
+            *overrides = active;
+            expired
+        };
+        for o in expired {
+            if let Err(e) = self.audit(OverrideAction::Expired, o).await {
+                error!("Failed to log risk override expiry: {}", e);
+            }
+        }
+    }
+    
+    /// Log an override change and alert on it
+    async fn audit(&self, action: OverrideAction, details: RiskOverride) -> Result<()> {
+        let event = RiskOverrideEvent { timestamp: chrono::Utc::now().timestamp_millis(), action, details };
+        if let Some(store) = &self.store {
+            store.record_risk_override(&event).await?;
+        }
+        let alert = event.alert();
+        warn!("{}: {}", alert.title, alert.message);
+        if let Some(event_tx) = &self.event_tx {
+            let _ = event_tx.send(Event::Alert(alert));
+        }
+        Ok(())
+    }
+    
+    /// Write changed counters, at most every few seconds unless forced
+    async fn persist(&self, force: bool) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let now = chrono::Utc::now().timestamp();
+        if !self.dirty.load(Ordering::SeqCst)
+            || (!force && now - self.last_persist.load(Ordering::SeqCst) < PERSIST_INTERVAL_SECS)
+        {
+            return;
+        }
+        
+        self.dirty.store(false, Ordering::SeqCst);
+        self.last_persist.store(now, Ordering::SeqCst);
+        if let Err(e) = store.save_risk_state(&self.risk_state()).await {
+            self.dirty.store(true, Ordering::SeqCst);
+            error!("Failed to persist risk state: {}", e);
         }
     }
     
     /// Perform all risk checks
     pub async fn check_all(&self) -> RiskCheckResult {
-        let mut reasons = Vec::new();
-        let mut should_pause = false;
-        let mut should_close = false;
-        let mut risk_score = 0.0;
+        let mut violations = Vec::new();
         
-        // Check daily reset
+        // Check daily reset and lapsed overrides
         self.check_daily_reset().await;
+        self.expire_overrides().await;
         
         // 1. Check drawdown
         let drawdown = self.calculate_drawdown().await;
-        if drawdown >= self.config.risk.max_drawdown_pct {
-            should_pause = true;
-            should_close = true;
-            reasons.push(format!("Max drawdown exceeded: {:.2}%", drawdown));
-            risk_score += 50.0;
-        } else if drawdown >= self.config.risk.max_drawdown_pct * 0.8 {
-            reasons.push(format!("Drawdown warning: {:.2}%", drawdown));
-            risk_score += 25.0;
+        self.state.drawdown_pct.store(drawdown);
+        let limit_pct = self.limit(RiskLimit::MaxDrawdown);
+        if drawdown >= limit_pct {
+            violations.push(RiskViolation::DrawdownExceeded { drawdown_pct: drawdown, limit_pct });
+        } else if drawdown >= limit_pct * 0.8 {
+            violations.push(RiskViolation::DrawdownWarning { drawdown_pct: drawdown, limit_pct });
         }
         
         // 2. Check position stop loss
@@ -109,56 +487,92 @@ impl RiskManager {
         let position_value = self.get_position_value().await;
         if position_value > 0.0 {
             let loss_pct = (-unrealized_pnl / position_value) * 100.0;
-            if loss_pct >= self.config.risk.stop_loss_pct {
-                should_close = true;
-                reasons.push(format!("Stop loss triggered: {:.2}%", loss_pct));
-                risk_score += 30.0;
+            let stop_pct = self.limit(RiskLimit::StopLoss);
+            if loss_pct >= stop_pct {
+                violations.push(RiskViolation::StopLoss { loss_pct, limit_pct: stop_pct });
             }
         }
         
+        // Spot from the secondary oracles is noisier: widen the limits measured on it
+        let contingency = *self.state.oracle_contingency.read();
+        let scale = self.config.protocols.oracles.contingency.scale(contingency);
+        
         // 3. Check hedge drift
         let hedge_drift = self.state.hedge_drift.load().abs();
-        if hedge_drift >= self.config.risk.hedge_drift_threshold_pct * 2.0 {
-            should_pause = true;
-            reasons.push(format!("Excessive hedge drift: {:.2}%", hedge_drift));
-            risk_score += 20.0;
+        let drift_limit = self.limit(RiskLimit::HedgeDrift) * 2.0 * scale;
+        if hedge_drift >= drift_limit {
+            violations.push(RiskViolation::HedgeDrift { drift_pct: hedge_drift, limit_pct: drift_limit });
         }
         
         // 4. Check error rate
         let error_count = self.state.error_count.load(Ordering::SeqCst);
-        if error_count > 10 {
-            should_pause = true;
-            reasons.push(format!("High error count: {}", error_count));
-            risk_score += 15.0;
+        if error_count > MAX_ERRORS {
+            violations.push(RiskViolation::ErrorRate { errors: error_count, limit: MAX_ERRORS });
         }
         
         // 5. Check connection status
         if !*self.state.rpc_connected.read() {
-            should_pause = true;
-            reasons.push("RPC disconnected".to_string());
-            risk_score += 25.0;
+            violations.push(RiskViolation::RpcDown);
+        }
+
+        // 6. Check wallet gas
+        if let Some(gas) = *self.state.gas.read() {
+            let wallet = &self.config.wallet;
+            match gas.level {
+                GasLevel::Critical => violations.push(RiskViolation::GasCritical {
+                    balance_sol: gas.sol_balance,
+                    threshold_sol: wallet.gas_critical_sol,
+                }),
+                GasLevel::Low => violations.push(RiskViolation::LowGas {
+                    balance_sol: gas.sol_balance,
+                    threshold_sol: wallet.gas_warn_sol,
+                }),
+                GasLevel::Ok => {}
+            }
         }
         
-        // 6. Check daily loss limit (implied from max_funding_reversal_loss)
-        let daily_pnl = self.daily_pnl.load(Ordering::SeqCst) as f64 / 1_000_000.0;
-        if daily_pnl < -self.config.risk.max_funding_reversal_loss {
-            should_pause = true;
-            reasons.push(format!("Daily loss limit: ${:.2}", daily_pnl));
-            risk_score += 40.0;
+        // 7. Check daily loss limit (implied from max_funding_reversal_loss)
+        let daily_pnl = self.today_entry().map_or(0.0, |e| e.realized_pnl);
+        let loss_limit = self.limit(RiskLimit::DailyLoss);
+        if daily_pnl < -loss_limit {
+            violations.push(RiskViolation::DailyLossLimit { pnl_usd: daily_pnl, limit_usd: loss_limit });
+        }
+
+        // 8. Check spot oracle agreement
+        let divergence = self.state.oracle_divergence_pct.load();
+        let divergence_limit = self.config.risk.max_oracle_divergence_pct * scale;
+        if divergence > divergence_limit {
+            violations.push(RiskViolation::OracleDivergence { divergence_pct: divergence, limit_pct: divergence_limit });
+        }
+        if contingency {
+            violations.push(RiskViolation::OracleContingency);
+        }
+
+        // 9. Check perp mark against its index
+        let mark_limit = self.config.risk.max_mark_index_divergence_pct;
+        if let Some(divergence) = self.state.snapshot().mark_index_divergence_pct().filter(|d| d.abs() > mark_limit) {
+            violations.push(RiskViolation::MarkIndexDivergence { divergence_pct: divergence, limit_pct: mark_limit });
+        }
+
+        // 10. Check realized volatility
+        if let Some(vol) = (*self.state.volatility.read()).filter(|v| v.elevated) {
+            violations.push(RiskViolation::HighVolatility {
+                vol_pct: vol.realized_vol_pct,
+                threshold_pct: vol.threshold_pct.unwrap_or(0.0),
+                block_entries: self.config.risk.volatility.action == VolatilityAction::Pause,
+            });
         }
         
+        let result = RiskCheckResult::from_violations(violations);
+        self.persist(false).await;
+        
         // Update pause state
-        if should_pause {
+        if result.should_pause() {
             *self.paused.write().await = true;
-            *self.pause_reason.write().await = Some(reasons.join("; "));
+            *self.pause_reason.write().await = Some(result.summary());
         }
         
-        RiskCheckResult {
-            should_pause,
-            should_close,
-            reasons,
-            risk_score: risk_score.min(100.0),
-        }
+        result
     }
     
     /// Calculate current drawdown
@@ -172,6 +586,7 @@ impl RiskManager {
                 (current_equity * 1_000_000.0) as u64,
                 Ordering::SeqCst,
             );
+            self.dirty.store(true, Ordering::SeqCst);
             0.0
         } else if peak > 0.0 {
             ((peak - current_equity) / peak) * 100.0
@@ -187,7 +602,7 @@ impl RiskManager {
         
         // Assume starting capital of 10000 for now
         // In production, would track actual balance
-        10000.0 + realized + unrealized
+        10000.0 + self.realized_base.load() + realized + unrealized
     }
     
     /// Get position value
@@ -197,28 +612,42 @@ impl RiskManager {
         spot_price * 100.0 // Assume 100 SOL position
     }
     
-    /// Check and perform daily reset
+    /// Check and perform daily reset. Daily P&L needs none: a new day reads
+    /// a new ledger row.
     async fn check_daily_reset(&self) {
         let now = chrono::Utc::now();
         let last_reset = self.last_reset.load(Ordering::SeqCst);
-        let last_date = chrono::DateTime::from_timestamp(last_reset, 0)
-            .map(|dt| dt.date_naive())
-            .unwrap_or_default();
+        let last_date = trading_day(self.config.risk.daily_timezone, last_reset * 1000);
         
-        if now.date_naive() > last_date {
+        if self.today() > last_date {
             info!("Daily reset triggered");
-            self.daily_pnl.store(0, Ordering::SeqCst);
-            self.trades_today.store(0, Ordering::SeqCst);
             self.last_reset.store(now.timestamp(), Ordering::SeqCst);
             self.state.error_count.store(0, Ordering::SeqCst);
+            self.dirty.store(true, Ordering::SeqCst);
+            self.persist(true).await;
         }
     }
     
-    /// Record a trade
-    pub fn record_trade(&self, pnl: f64) {
-        self.trades_today.fetch_add(1, Ordering::SeqCst);
-        let pnl_micro = (pnl * 1_000_000.0) as i64;
-        self.daily_pnl.fetch_add(pnl_micro, Ordering::SeqCst);
+    /// Write the counters now
+    pub async fn flush(&self) {
+        self.dirty.store(true, Ordering::SeqCst);
+        self.persist(true).await;
+    }
+    
+    /// Record a closed trade against its day in the ledger
+    pub async fn record_trade(&self, trade: &TradeOutcome) {
+        let ledger = {
+            let mut ledger = self.ledger.write();
+            ledger.record(self.config.risk.daily_timezone, trade);
+            ledger.clone()
+        };
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_daily_ledger(&ledger).await {
+                error!("Failed to persist daily ledger: {}", e);
+            }
+        }
+        self.dirty.store(true, Ordering::SeqCst);
+        self.persist(true).await;
     }
     
     /// Check if can resume trading
@@ -226,7 +655,7 @@ impl RiskManager {
         // Check if conditions have improved
         let check = self.check_all().await;
         
-        if !check.should_pause && check.risk_score < 30.0 {
+        if !check.should_pause() && check.risk_score < 30.0 {
             *self.paused.write().await = false;
             *self.pause_reason.write().await = None;
             true
@@ -255,13 +684,14 @@ impl RiskManager {
             0.0
         };
         
+        let today = self.today_entry();
         RiskMetrics {
             drawdown_pct: drawdown,
             peak_equity: peak,
             current_equity,
             unrealized_pnl: self.state.unrealized_pnl.load(),
-            realized_pnl_today: self.daily_pnl.load(Ordering::SeqCst) as f64 / 1_000_000.0,
-            trades_today: self.trades_today.load(Ordering::SeqCst) as u32,
+            realized_pnl_today: today.as_ref().map_or(0.0, |e| e.realized_pnl),
+            trades_today: today.as_ref().map_or(0, |e| e.trades),
             error_count: self.state.error_count.load(Ordering::SeqCst),
         }
     }
@@ -287,12 +717,81 @@ mod tests {
 
     #[test]
     fn test_risk_check_result() {
-        let result = RiskCheckResult {
-            should_pause: false,
-            should_close: false,
-            reasons: vec![],
-            risk_score: 0.0,
+        let result = RiskCheckResult::default();
+        assert!(!result.should_pause());
+        assert_eq!(result.max_severity(), None);
+    }
+
+    #[tokio::test]
+    async fn test_high_water_mark_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("risk_state_{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let db_path = dir.join("performance.json");
+        let store = Arc::new(PerformanceDb::new(db_path.to_str().unwrap()).await.unwrap());
+        let config = Arc::new(AppConfig::default_for_test());
+
+        let state = Arc::new(SharedState::new());
+        state.realized_pnl.store(500.0);
+        let risk = RiskManager::new(config.clone(), state).with_store(store.clone());
+        risk.check_all().await;
+        let close_time = chrono::Utc::now().timestamp_millis();
+        risk.record_trade(&TradeOutcome { close_time, total_pnl: -120.0, ..Default::default() }).await;
+
+        // Fresh process: nothing realized yet this run
+        let restarted = RiskManager::new(config, Arc::new(SharedState::new())).with_store(store);
+        restarted.restore().await.unwrap();
+        let saved = restarted.risk_state();
+        assert_eq!(saved.peak_equity, 10_500.0);
+        assert_eq!(saved.realized_pnl, 500.0);
+        assert_eq!(saved.daily_pnl, -120.0);
+        assert_eq!(saved.trades_today, 1);
+        assert!(tokio::fs::try_exists(dir.join("daily_ledger.json")).await.unwrap());
+
+        tokio::fs::remove_dir_all(&dir).await.ok();
+    }
+
+    #[test]
+    fn test_rule_specific_actions() {
+        let stop = RiskCheckResult::from_violations(vec![
+            RiskViolation::StopLoss { loss_pct: 6.0, limit_pct: 5.0 },
+        ]);
+        assert!(stop.should_close());
+        assert!(!stop.should_pause());
+
+        let drift = RiskCheckResult::from_violations(vec![
+            RiskViolation::HedgeDrift { drift_pct: 4.5, limit_pct: 4.0 },
+            RiskViolation::DrawdownWarning { drawdown_pct: 8.5, limit_pct: 10.0 },
+        ]);
+        assert!(drift.should_pause());
+        assert!(!drift.should_close());
+        assert_eq!(drift.max_severity(), Some(RiskSeverity::High));
+        assert_eq!(drift.risk_score, 45.0);
+        assert_eq!(drift.summary(), "Excessive hedge drift: 4.50%; Drawdown warning: 8.50%");
+
+        let gas = RiskCheckResult::from_violations(vec![
+            RiskViolation::GasCritical { balance_sol: 0.01, threshold_sol: 0.02 },
+        ]);
+        assert!(gas.blocks_entries());
+        assert!(!gas.should_pause());
+        assert!(!gas.should_close());
+
+        let oracles = RiskCheckResult::from_violations(vec![
+            RiskViolation::OracleDivergence { divergence_pct: 1.5, limit_pct: 1.0 },
+            RiskViolation::MarkIndexDivergence { divergence_pct: -0.8, limit_pct: 0.5 },
+        ]);
+        assert!(oracles.blocks_entries());
+        assert!(!oracles.should_pause());
+
+        let contingency = RiskCheckResult::from_violations(vec![RiskViolation::OracleContingency]);
+        assert!(contingency.blocks_entries());
+        assert!(!contingency.should_pause());
+        assert!(!contingency.should_close());
+
+        let volatile = |block_entries| {
+            RiskCheckResult::from_violations(vec![RiskViolation::HighVolatility { vol_pct: 140.0, threshold_pct: 95.0, block_entries }])
         };
-        assert!(!result.should_pause);
+        assert!(!volatile(false).blocks_entries());
+        assert!(volatile(true).blocks_entries());
+        assert!(!volatile(true).should_pause());
     }
 }
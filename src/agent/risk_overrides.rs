@@ -0,0 +1,186 @@
+//! Risk Limit Overrides
+//!
+//! An operator can temporarily replace a risk limit through the control API
+//! (`POST /risk/overrides`), e.g. a higher daily loss limit for two hours,
+//! rather than editing the config and restarting or staying locked out:
+//! - Every override has a duration, capped at `risk.overrides.max_duration_mins`
+//! - A limit can be loosened to at most `risk.overrides.max_factor` times its
+//!   configured value; tightening is always allowed
+//! - Setting, clearing and expiry are appended to `risk_overrides.jsonl` next
+//!   to the performance database, and each raises an alert
+//!
+//! The risk manager checks against the overridden limits and expires them on
+//! its next check after `expires_at`. Overrides still active are restored
+//! from the log on restart.
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::config::RiskConfig;
+use crate::telemetry::Alert;
+
+/// A limit that can be overridden
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskLimit {
+    /// Realized loss per day (USD), `max_funding_reversal_loss`
+    DailyLoss,
+    /// Drawdown from the equity peak (%), `max_drawdown_pct`
+    MaxDrawdown,
+    /// Position loss (%), `stop_loss_pct`
+    StopLoss,
+    /// Spot/perp drift (%), `hedge_drift_threshold_pct`
+    HedgeDrift,
+}
+
+impl RiskLimit {
+    /// Value in the config
+    pub fn configured(&self, risk: &RiskConfig) -> f64 {
+        match self {
+            Self::DailyLoss => risk.max_funding_reversal_loss,
+            Self::MaxDrawdown => risk.max_drawdown_pct,
+            Self::StopLoss => risk.stop_loss_pct,
+            Self::HedgeDrift => risk.hedge_drift_threshold_pct,
+        }
+    }
+}
+
+impl fmt::Display for RiskLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::DailyLoss => "daily_loss",
+            Self::MaxDrawdown => "max_drawdown",
+            Self::StopLoss => "stop_loss",
+            Self::HedgeDrift => "hedge_drift",
+        })
+    }
+}
+
+/// An override in force
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskOverride {
+    pub limit: RiskLimit,
+    /// Limit in force while the override lasts
+    pub value: f64,
+    /// Limit in the config, in force again after expiry
+    pub configured: f64,
+    /// Operator's reason, for the audit log
+    pub reason: String,
+    /// When it was set (ms)
+    pub set_at: i64,
+    /// When it lapses (ms)
+    pub expires_at: i64,
+}
+
+/// What happened to an override
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideAction {
+    Set,
+    /// Removed by the operator before expiry
+    Cleared,
+    Expired,
+}
+
+/// One audit log entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskOverrideEvent {
+    /// When it happened (ms)
+    pub timestamp: i64,
+    pub action: OverrideAction,
+    #[serde(flatten)]
+    pub details: RiskOverride,
+}
+
+impl RiskOverrideEvent {
+    /// Alert for the operators
+    pub fn alert(&self) -> Alert {
+        let o = &self.details;
+        let expires = chrono::DateTime::from_timestamp_millis(o.expires_at)
+            .map(|t| t.format("%H:%M UTC").to_string())
+            .unwrap_or_default();
+        match self.action {
+            OverrideAction::Set => Alert::warning(
+                "Risk limit overridden",
+                format!("{} {} → {} until {}: {}", o.limit, o.configured, o.value, expires, o.reason),
+            ),
+            OverrideAction::Cleared => {
+                Alert::info("Risk limit override cleared", format!("{} back to {}", o.limit, o.configured))
+            }
+            OverrideAction::Expired => {
+                Alert::info("Risk limit override expired", format!("{} back to {}", o.limit, o.configured))
+            }
+        }
+    }
+}
+
+/// Check a requested override against the configured bounds
+pub fn check(risk: &RiskConfig, limit: RiskLimit, value: f64, duration_mins: u64) -> Result<()> {
+    let bounds = &risk.overrides;
+    ensure!(value.is_finite() && value > 0.0, "Override of {} must be positive", limit);
+    ensure!(
+        duration_mins > 0 && duration_mins <= bounds.max_duration_mins,
+        "Override duration must be within 1-{} minutes",
+        bounds.max_duration_mins
+    );
+    let max = limit.configured(risk) * bounds.max_factor;
+    ensure!(
+        value <= max,
+        "{} can be raised to at most {} ({}x its configured value)",
+        limit,
+        max,
+        bounds.max_factor
+    );
+    Ok(())
+}
+
+/// Overrides still in force at `now` after the logged events, oldest first
+pub fn replay(log: &[RiskOverrideEvent], now: i64) -> Vec<RiskOverride> {
+    let mut active: Vec<RiskOverride> = Vec::new();
+    for event in log {
+        active.retain(|o| o.limit != event.details.limit);
+        if event.action == OverrideAction::Set {
+            active.push(event.details.clone());
+        }
+    }
+    active.retain(|o| o.expires_at > now);
+    active
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    fn event(action: OverrideAction, limit: RiskLimit, value: f64, expires_at: i64) -> RiskOverrideEvent {
+        RiskOverrideEvent {
+            timestamp: 0,
+            action,
+            details: RiskOverride { limit, value, configured: 0.0, reason: String::new(), set_at: 0, expires_at },
+        }
+    }
+
+    #[test]
+    fn test_check_bounds_and_replay() {
+        let risk = AppConfig::default_for_test().risk;
+        assert!(check(&risk, RiskLimit::DailyLoss, 1_000.0, 120).is_ok());
+        assert!(check(&risk, RiskLimit::DailyLoss, 100.0, 120).is_ok());
+        assert!(check(&risk, RiskLimit::DailyLoss, 1_000.1, 120).is_err());
+        assert!(check(&risk, RiskLimit::DailyLoss, 1_000.0, 0).is_err());
+        assert!(check(&risk, RiskLimit::MaxDrawdown, 6.0, 241).is_err());
+
+        let log = vec![
+            event(OverrideAction::Set, RiskLimit::DailyLoss, 800.0, 5_000),
+            event(OverrideAction::Set, RiskLimit::StopLoss, 3.0, 5_000),
+            event(OverrideAction::Set, RiskLimit::MaxDrawdown, 7.0, 1_000),
+            event(OverrideAction::Cleared, RiskLimit::StopLoss, 3.0, 5_000),
+            event(OverrideAction::Set, RiskLimit::DailyLoss, 900.0, 6_000),
+        ];
+        // Stop loss cleared, drawdown lapsed, daily loss replaced
+        let active = replay(&log, 2_000);
+        assert_eq!(active.len(), 1);
+        assert_eq!((active[0].limit, active[0].value), (RiskLimit::DailyLoss, 900.0));
+        assert!(replay(&log, 6_000).is_empty());
+    }
+}
@@ -3,22 +3,44 @@
 //! Manages the trading lifecycle states:
 //! - Idle: Waiting for opportunities
 //! - Opening: Executing entry trade
+//! - AwaitingFill: Passive perp order resting, spot leg pending
 //! - Monitoring: Watching position
 //! - Closing: Executing exit trade
 //! - Rebalancing: Adjusting hedge
 //! - Paused: Risk-triggered halt
 //! - Error: Recovery state
+//!
+//! Active states have a maximum duration (`state_timeouts`); a state that
+//! runs past it is treated as stuck.
+//!
+//! While Opening, Closing or Rebalancing executes, its `ExecutionProgress`
+//! tracks the phase reached (quote, build, submit, confirm, verify), so a
+//! stuck entry shows where it is stuck. The phase is cleared on transition.
+//!
+//! With an audit sink attached, every transition is also sent out (with the
+//! trade in progress and market prices) to be persisted for post-mortems.
 
-use std::time::Instant;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 use tracing::{info, debug, warn};
 
+use crate::config::StateTimeoutConfig;
+use crate::state::SharedState;
+use crate::telemetry::{observe_stages, LatencyStage};
+
 /// Agent states
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AgentState {
     /// Idle - waiting for trade opportunities
     Idle,
     /// Opening a new position
     Opening,
+    /// Passive perp entry order resting, waiting for fill before the spot leg
+    AwaitingFill,
     /// Monitoring an active position
     Monitoring,
     /// Closing a position
@@ -36,6 +58,7 @@ impl std::fmt::Display for AgentState {
         match self {
             AgentState::Idle => write!(f, "Idle"),
             AgentState::Opening => write!(f, "Opening"),
+            AgentState::AwaitingFill => write!(f, "AwaitingFill"),
             AgentState::Monitoring => write!(f, "Monitoring"),
             AgentState::Closing => write!(f, "Closing"),
             AgentState::Rebalancing => write!(f, "Rebalancing"),
@@ -45,13 +68,134 @@ impl std::fmt::Display for AgentState {
     }
 }
 
+impl AgentState {
+    /// Maximum time allowed in this state, if limited
+    pub fn time_limit(&self, timeouts: &StateTimeoutConfig) -> Option<Duration> {
+        let secs = match self {
+            AgentState::Opening => timeouts.opening_secs,
+            AgentState::AwaitingFill => timeouts.awaiting_fill_secs,
+            AgentState::Closing => timeouts.closing_secs,
+            AgentState::Rebalancing => timeouts.rebalancing_secs,
+            _ => 0,
+        };
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+}
+
+/// Step of an execution within an active state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionPhase {
+    /// Fetching a swap quote
+    Quote,
+    /// Building the transaction (blockhash or nonce)
+    Build,
+    /// Simulating and sending it
+    Submit,
+    /// Waiting for confirmation
+    Confirm,
+    /// Reconciling the legs against the intended position
+    Verify,
+}
+
+impl ExecutionPhase {
+    /// Phase an execution stage belongs to
+    pub fn from_stage(stage: LatencyStage) -> Option<Self> {
+        match stage {
+            LatencyStage::Quote => Some(Self::Quote),
+            LatencyStage::Blockhash => Some(Self::Build),
+            LatencyStage::Simulate | LatencyStage::Submit => Some(Self::Submit),
+            LatencyStage::Confirm => Some(Self::Confirm),
+            LatencyStage::Reconcile => Some(Self::Verify),
+            LatencyStage::Sizing | LatencyStage::RiskCheck | LatencyStage::Execute => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ExecutionPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecutionPhase::Quote => write!(f, "quote"),
+            ExecutionPhase::Build => write!(f, "build"),
+            ExecutionPhase::Submit => write!(f, "submit"),
+            ExecutionPhase::Confirm => write!(f, "confirm"),
+            ExecutionPhase::Verify => write!(f, "verify"),
+        }
+    }
+}
+
+/// Phase reached by the execution in progress, shared with the work itself
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionProgress(Arc<Mutex<Option<(ExecutionPhase, Instant)>>>);
+
+impl ExecutionProgress {
+    /// Record reaching `phase`
+    pub fn enter(&self, phase: ExecutionPhase) {
+        let mut current = self.0.lock();
+        if current.map(|(p, _)| p) != Some(phase) {
+            info!("Execution phase: {}", phase);
+            *current = Some((phase, Instant::now()));
+        }
+    }
+    
+    /// Current phase and time spent in it
+    pub fn current(&self) -> Option<(ExecutionPhase, Duration)> {
+        self.0.lock().map(|(phase, since)| (phase, since.elapsed()))
+    }
+    
+    fn clear(&self) {
+        *self.0.lock() = None;
+    }
+    
+    /// Await `work`, following it through the execution stages it enters
+    pub async fn track<F: Future>(&self, work: F) -> F::Output {
+        let progress = self.clone();
+        observe_stages(
+            move |stage| {
+                if let Some(phase) = ExecutionPhase::from_stage(stage) {
+                    progress.enter(phase);
+                }
+            },
+            work,
+        )
+        .await
+    }
+}
+
+/// Where the agent is, for status reporting
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentStatus {
+    pub state: AgentState,
+    pub secs_in_state: u64,
+    /// Execution phase, while an active state executes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<ExecutionPhase>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secs_in_phase: Option<u64>,
+    /// Trade in progress, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<String>,
+}
+
 /// State transition record
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateTransition {
     pub from: AgentState,
     pub to: AgentState,
     pub timestamp: i64,
     pub reason: Option<String>,
+    /// Trade in progress, if any
+    #[serde(default)]
+    pub trade_id: Option<String>,
+    /// Market at the transition (0 when no market state is attached)
+    #[serde(default)]
+    pub spot_price: f64,
+    #[serde(default)]
+    pub perp_mark_price: f64,
+    #[serde(default)]
+    pub basis_spread_pct: f64,
+    #[serde(default)]
+    pub funding_apr_pct: f64,
 }
 
 /// Agent state machine
@@ -66,6 +210,14 @@ pub struct AgentStateMachine {
     history: Vec<StateTransition>,
     /// Max history size
     max_history: usize,
+    /// Trade in progress, attached to transitions until the next Idle
+    trade_id: Option<String>,
+    /// Market state sampled into each transition
+    market: Option<Arc<SharedState>>,
+    /// Receiver of every transition, for persistence
+    audit: Option<mpsc::UnboundedSender<StateTransition>>,
+    /// Execution phase within the current state
+    progress: ExecutionProgress,
 }
 
 impl AgentStateMachine {
@@ -77,9 +229,25 @@ impl AgentStateMachine {
             state_entered_at: Instant::now(),
             history: Vec::new(),
             max_history: 100,
+            trade_id: None,
+            market: None,
+            audit: None,
+            progress: ExecutionProgress::default(),
         }
     }
     
+    /// Send every transition, with market prices, to an audit sink
+    pub fn with_audit(mut self, market: Arc<SharedState>, sink: mpsc::UnboundedSender<StateTransition>) -> Self {
+        self.market = Some(market);
+        self.audit = Some(sink);
+        self
+    }
+    
+    /// Attach a trade to subsequent transitions (cleared on return to Idle)
+    pub fn set_trade(&mut self, trade_id: Option<String>) {
+        self.trade_id = trade_id;
+    }
+    
     /// Get current state
     pub fn current_state(&self) -> AgentState {
         self.current
@@ -95,6 +263,35 @@ impl AgentStateMachine {
         self.state_entered_at.elapsed()
     }
     
+    /// Execution phase tracking of the current state
+    pub fn progress(&self) -> ExecutionProgress {
+        self.progress.clone()
+    }
+    
+    /// State, execution phase and trade, for status reporting
+    pub fn status(&self) -> AgentStatus {
+        let phase = self.progress.current();
+        AgentStatus {
+            state: self.current,
+            secs_in_state: self.time_in_state().as_secs(),
+            phase: phase.map(|(p, _)| p),
+            secs_in_phase: phase.map(|(_, d)| d.as_secs()),
+            trade_id: self.trade_id.clone(),
+        }
+    }
+    
+    /// Time left before the current state times out; None if unlimited
+    pub fn time_remaining(&self, timeouts: &StateTimeoutConfig) -> Option<Duration> {
+        self.current
+            .time_limit(timeouts)
+            .map(|limit| limit.saturating_sub(self.time_in_state()))
+    }
+    
+    /// Whether the current state has run past its limit
+    pub fn is_timed_out(&self, timeouts: &StateTimeoutConfig) -> bool {
+        self.time_remaining(timeouts) == Some(Duration::ZERO)
+    }
+    
     /// Check if transition is valid
     pub fn can_transition_to(&self, target: AgentState) -> bool {
         use AgentState::*;
@@ -107,13 +304,21 @@ impl AgentStateMachine {
             
             // From Opening
             (Opening, Monitoring) => true,
+            (Opening, AwaitingFill) => true,
             (Opening, Idle) => true, // Failed to open
             (Opening, Paused) => true,
             (Opening, Error) => true,
             
+            // From AwaitingFill
+            (AwaitingFill, Monitoring) => true, // Filled, spot leg executed
+            (AwaitingFill, Idle) => true, // Timed out and cancelled
+            (AwaitingFill, Paused) => true,
+            (AwaitingFill, Error) => true,
+            
             // From Monitoring
             (Monitoring, Closing) => true,
             (Monitoring, Rebalancing) => true,
+            (Monitoring, Opening) => true, // Next entry ladder tranche
             (Monitoring, Paused) => true,
             (Monitoring, Error) => true,
             
@@ -131,11 +336,17 @@ impl AgentStateMachine {
             (Paused, Idle) => true,
             (Paused, Monitoring) => true, // Resume to monitoring if position exists
             (Paused, Closing) => true, // Force close
+            (Paused, AwaitingFill) => true, // Resume a passive entry cancelled on pause
             (Paused, Error) => true,
             
             // From Error
             (Error, Idle) => true,
             (Error, Paused) => true,
+            // Timeout recovery: retry the stuck state, or unwind
+            (Error, Opening) => true,
+            (Error, AwaitingFill) => true,
+            (Error, Monitoring) => true,
+            (Error, Closing) => true,
             
             // Same state
             (a, b) if a == b => false,
@@ -160,11 +371,17 @@ impl AgentStateMachine {
             return false;
         }
         
+        let market = self.market.as_ref().map(|s| s.snapshot());
         let transition = StateTransition {
             from: self.current,
             to: target,
             timestamp: chrono::Utc::now().timestamp_millis(),
             reason: reason.clone(),
+            trade_id: self.trade_id.clone(),
+            spot_price: market.as_ref().map_or(0.0, |m| m.spot_price),
+            perp_mark_price: market.as_ref().map_or(0.0, |m| m.perp_mark_price),
+            basis_spread_pct: market.as_ref().map_or(0.0, |m| m.basis_spread),
+            funding_apr_pct: market.as_ref().map_or(0.0, |m| m.funding_apr),
         };
         
         info!(
@@ -177,6 +394,15 @@ impl AgentStateMachine {
         self.previous = Some(self.current);
         self.current = target;
         self.state_entered_at = Instant::now();
+        self.progress.clear();
+        
+        if target == AgentState::Idle {
+            self.trade_id = None;
+        }
+        
+        if let Some(audit) = &self.audit {
+            let _ = audit.send(transition.clone());
+        }
         
         // Add to history
         self.history.push(transition);
@@ -196,7 +422,11 @@ impl AgentStateMachine {
     pub fn is_active(&self) -> bool {
         matches!(
             self.current,
-            AgentState::Opening | AgentState::Monitoring | AgentState::Closing | AgentState::Rebalancing
+            AgentState::Opening
+                | AgentState::AwaitingFill
+                | AgentState::Monitoring
+                | AgentState::Closing
+                | AgentState::Rebalancing
         )
     }
     
@@ -252,6 +482,72 @@ mod tests {
         assert!(sm.transition_to(AgentState::Idle));
         
         assert_eq!(sm.history().len(), 4);
+        
+        // Laddered entries add tranches from monitoring
+        assert!(sm.transition_to(AgentState::Opening));
+        assert!(sm.transition_to(AgentState::Monitoring));
+        assert!(sm.transition_to(AgentState::Opening));
+    }
+    
+    #[test]
+    fn test_passive_entry_states() {
+        let mut sm = AgentStateMachine::new();
+        assert!(sm.transition_to(AgentState::Opening));
+        assert!(sm.transition_to(AgentState::AwaitingFill));
+        assert!(sm.is_active());
+        
+        // Unfilled orders are cancelled back to idle
+        assert!(sm.transition_to(AgentState::Idle));
+        assert!(!sm.transition_to(AgentState::AwaitingFill));
+    }
+    
+    #[test]
+    fn test_audit_sink() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut sm = AgentStateMachine::new().with_audit(Arc::new(SharedState::new()), tx);
+        sm.set_trade(Some("t1".to_string()));
+        sm.transition_to(AgentState::Opening);
+        sm.transition_to(AgentState::Idle);
+        sm.transition_to(AgentState::Paused);
+        
+        let trades: Vec<Option<String>> = std::iter::from_fn(|| rx.try_recv().ok()).map(|t| t.trade_id).collect();
+        assert_eq!(trades, vec![Some("t1".to_string()), Some("t1".to_string()), None]);
+    }
+    
+    #[test]
+    fn test_state_timeouts() {
+        let timeouts = StateTimeoutConfig { opening_secs: 0, ..Default::default() };
+        let mut sm = AgentStateMachine::new();
+        assert_eq!(sm.time_remaining(&timeouts), None);
+        
+        sm.transition_to(AgentState::Opening);
+        assert!(!sm.is_timed_out(&timeouts));
+        
+        sm.transition_to(AgentState::Monitoring);
+        sm.transition_to(AgentState::Closing);
+        sm.state_entered_at -= Duration::from_secs(timeouts.closing_secs + 1);
+        assert!(sm.is_timed_out(&timeouts));
+        
+        // Recovery may unwind through Closing again
+        assert!(sm.transition_to(AgentState::Error));
+        assert!(sm.transition_to(AgentState::Closing));
+    }
+    
+    #[test]
+    fn test_execution_phase() {
+        let mut sm = AgentStateMachine::new();
+        sm.transition_to(AgentState::Opening);
+        assert_eq!(sm.status().phase, None);
+        
+        let progress = sm.progress();
+        progress.enter(ExecutionPhase::Quote);
+        progress.enter(ExecutionPhase::from_stage(LatencyStage::Simulate).unwrap());
+        assert_eq!(sm.status().phase, Some(ExecutionPhase::Submit));
+        assert_eq!(ExecutionPhase::from_stage(LatencyStage::Sizing), None);
+        
+        // A new state starts without a phase
+        sm.transition_to(AgentState::Monitoring);
+        assert!(progress.current().is_none());
     }
     
     #[test]
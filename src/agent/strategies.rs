@@ -0,0 +1,219 @@
+//! Named Strategies
+//!
+//! Runs the instances under `strategies` in the config next to the primary
+//! agent, in the same process. Each is a full trading agent with:
+//! - Its config: the base config with the strategy's overrides merged in
+//! - Its own performance store and execution journal, under
+//!   `strategies/<name>/` next to the primary store
+//! - Its own shared state and position manager, so positions, P&L, risk
+//!   state and the agent state are tracked per strategy
+//! - Its own operator controls, served under `/strategies/<name>/`
+//! - Per-strategy gauges, labelled `strategy`
+//!
+//! Price feeds, engines, signals and the regime are shared. Market readings
+//! are mirrored from the primary state into each strategy's state every
+//! agent tick. Overrides therefore change what the agent decides with
+//! (sizing, risk limits, entry and exit handling), not the signals it sees.
+//! Named strategies trade on paper; the wallet belongs to the primary agent.
+//! A strategy marked `shadow` is an A/B arm of the primary (see `ab_test`).
+
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+use tracing::info;
+
+use super::ab_test::{self, ShadowComparison};
+use super::{AgentStatus, TradingAgent};
+use crate::agentic::{PerformanceDb, RegimeAnalysis};
+use crate::config::{AppConfig, StrategyConfig};
+use crate::engines::EngineManager;
+use crate::network::Event;
+use crate::position::PositionManager;
+use crate::state::SharedState;
+use crate::telemetry::record_strategy;
+
+/// A named strategy instance
+pub struct Strategy {
+    pub name: String,
+    pub config: Arc<AppConfig>,
+    /// The strategy's own state, fed from the primary state
+    pub state: Arc<SharedState>,
+    pub agent: Arc<TradingAgent>,
+    /// A/B shadow of the primary agent: takes no manual commands
+    pub shadow: bool,
+    /// Overrides over the base config, as a shadow would be promoted
+    overrides: serde_yaml::Value,
+    /// Start of a shadow's comparison window (ms)
+    since: Option<i64>,
+    /// Mirrors market readings and records gauges
+    mirror: parking_lot::Mutex<Option<JoinHandle<()>>>,
+}
+
+/// A strategy's state, for the control API
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyStatus {
+    pub name: String,
+    pub agent: AgentStatus,
+    pub paused: bool,
+    pub shadow: bool,
+    /// Spot leg (SOL)
+    pub position_sol: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub total_trades: u32,
+    pub performance_db_path: String,
+}
+
+impl Strategy {
+    /// Build the instance for `strategy` on top of the base config, reading
+    /// signals, basis and regime from the shared engines
+    pub async fn new(
+        base: &AppConfig,
+        strategy: &StrategyConfig,
+        event_tx: broadcast::Sender<Event>,
+        engines: &EngineManager,
+        regime: Arc<RwLock<Option<RegimeAnalysis>>>,
+    ) -> Result<Self> {
+        let config = Arc::new(base.for_strategy(strategy)?);
+        let state = Arc::new(SharedState::with_sampling(
+            config.protocols.drift.funding_interval,
+            config.engines.basis_interval(),
+            config.engines.funding_interval(),
+        ));
+        let position_manager = Arc::new(PositionManager::new(state.clone()));
+        let agent = TradingAgent::new(
+            config.clone(),
+            state.clone(),
+            position_manager,
+            event_tx,
+            engines.signal.signal_handle(),
+            regime,
+        )
+        .await?
+        .with_basis(engines.basis.analysis_handle())
+        .with_entry_times(engines.signal.entry_time_handle())
+        .with_hold_times(engines.signal.hold_time_handle());
+
+        let since = strategy
+            .shadow
+            .then(|| ab_test::mark_since(&config.agentic.performance_db_path, chrono::Utc::now().timestamp_millis()))
+            .transpose()?;
+        info!("Strategy {} ready (performance store {})", strategy.name, config.agentic.performance_db_path);
+        Ok(Self {
+            name: strategy.name.clone(),
+            config,
+            state,
+            agent: Arc::new(agent),
+            shadow: strategy.shadow,
+            overrides: strategy.overrides.clone(),
+            since,
+            mirror: parking_lot::Mutex::new(None),
+        })
+    }
+
+    /// Start mirroring the primary state and run the agent
+    pub async fn start(&self, primary: Arc<SharedState>) -> Result<()> {
+        let state = self.state.clone();
+        let name = self.name.clone();
+        let position_manager = self.agent.position_manager().clone();
+        let tick = self.config.engines.agent_tick();
+        // First readings land before the agent's first tick
+        let mut since = state.mirror_market(&primary, 0);
+        *self.mirror.lock() = Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick);
+            loop {
+                interval.tick().await;
+                since = state.mirror_market(&primary, since);
+                position_manager.update_pnl().await;
+                let positions = position_manager.get_positions().await;
+                record_strategy(&name, positions.spot_size, positions.realized_pnl, positions.unrealized_pnl);
+            }
+        }));
+        self.agent.start().await?;
+        info!("Strategy {} started", self.name);
+        Ok(())
+    }
+
+    pub async fn stop(&self) {
+        self.agent.stop().await;
+        if let Some(task) = self.mirror.lock().take() {
+            task.abort();
+        }
+        info!("Strategy {} stopped", self.name);
+    }
+
+    pub async fn status(&self) -> StrategyStatus {
+        let positions = self.agent.position_manager().get_positions().await;
+        StrategyStatus {
+            name: self.name.clone(),
+            agent: self.agent.status().await,
+            paused: self.agent.control().is_paused(),
+            shadow: self.shadow,
+            position_sol: positions.spot_size,
+            realized_pnl: positions.realized_pnl,
+            unrealized_pnl: positions.unrealized_pnl,
+            total_trades: self.agent.performance_db().get_metrics().await.total_trades,
+            performance_db_path: self.config.agentic.performance_db_path.clone(),
+        }
+    }
+
+    /// A shadow's trades against the primary's, None unless a shadow
+    pub async fn compare(&self, primary: &PerformanceDb) -> Option<ShadowComparison> {
+        let since = self.since?;
+        Some(ShadowComparison::compute(
+            &self.name,
+            self.overrides.clone(),
+            since,
+            &primary.get_all_trades().await,
+            &self.agent.performance_db().get_all_trades().await,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy_config_isolated() {
+        let mut base = AppConfig::default_for_test();
+        base.agentic.performance_db_path = "data/performance.json".to_string();
+        base.paper_trading = false;
+        let strategy: StrategyConfig = serde_yaml::from_str(
+            "name: sol-conservative\noverrides:\n  trading:\n    max_position_size_sol: 5.0\n",
+        )
+        .unwrap();
+
+        let config = base.for_strategy(&strategy).unwrap();
+        assert_eq!(config.trading.max_position_size_sol, 5.0);
+        assert_eq!(config.trading.min_basis_spread_pct, base.trading.min_basis_spread_pct);
+        assert_eq!(
+            std::path::Path::new(&config.agentic.performance_db_path),
+            std::path::Path::new("data/strategies/sol-conservative/performance.json")
+        );
+        assert!(config.paper_trading);
+
+        // Feeds are shared, so the market cannot change
+        let other_market: StrategyConfig =
+            serde_yaml::from_str("name: eth\noverrides:\n  market: ETH\n").unwrap();
+        assert!(base.for_strategy(&other_market).is_err());
+    }
+
+    #[test]
+    fn test_mirror_market_leaves_positions() {
+        let primary = SharedState::new();
+        primary.update_spot_price(150.0);
+        primary.update_perp_mark_price(150.3);
+        let strategy = SharedState::new();
+        strategy.realized_pnl.store(12.0);
+
+        let since = strategy.mirror_market(&primary, 0);
+        assert_eq!(strategy.snapshot().perp_mark_price, 150.3);
+        assert!((strategy.basis_spread.load() - 0.2).abs() < 1e-9);
+        assert_eq!(strategy.realized_pnl.load(), 12.0);
+        assert_eq!(strategy.basis_history.since(0).len(), primary.basis_history.since(0).len());
+        assert_eq!(since, primary.spot_history.since(0).last().unwrap().timestamp);
+    }
+}
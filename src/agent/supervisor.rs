@@ -0,0 +1,240 @@
+//! Task Supervisor
+//!
+//! Engines, feeds and the agent loop run in spawned tasks; a panic inside
+//! one ends that task silently and leaves the bot half-alive. The
+//! supervisor watches each task's heartbeat:
+//! - A task silent for longer than its stall limit is restarted (if it can
+//!   be) and its restarts are counted
+//! - A critical task that needs more than `max_restarts` restarts within
+//!   `restart_window_secs`, or that cannot be restarted, escalates: trading
+//!   is paused through the operator pause and a critical alert is sent
+//!
+//! Restarts are logged as warnings and exported as the
+//! `sol_basis_bot_task_restarts_total` metric.
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::config::SupervisorConfig;
+use crate::network::Event;
+use crate::telemetry::{record_task_restart, Alert};
+
+use super::AgentControl;
+
+type Restart = Box<dyn Fn() -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+/// A watched task
+struct Supervised {
+    name: &'static str,
+    critical: bool,
+    /// Last heartbeat (ms, 0 before the first)
+    last_beat: Box<dyn Fn() -> i64 + Send + Sync>,
+    stall_ms: i64,
+    restart: Option<Restart>,
+    /// Restart times within the window (ms)
+    restarts: VecDeque<i64>,
+    total_restarts: u64,
+    /// Heartbeat at the last restart, so one stall is acted on once
+    restarted_at_beat: Option<i64>,
+}
+
+/// What the supervisor does about a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Healthy,
+    Restart,
+    Escalate,
+}
+
+/// Heartbeat-based watchdog over the bot's tasks
+pub struct Supervisor {
+    config: SupervisorConfig,
+    tasks: Vec<Supervised>,
+    control: Arc<AgentControl>,
+    event_tx: broadcast::Sender<Event>,
+    /// Set once trading has been paused, so escalation happens once
+    escalated: bool,
+}
+
+impl Supervisor {
+    pub fn new(config: &SupervisorConfig, control: Arc<AgentControl>, event_tx: broadcast::Sender<Event>) -> Self {
+        Self { config: config.clone(), tasks: Vec::new(), control, event_tx, escalated: false }
+    }
+
+    /// Watch a task that restarts with `restart`
+    pub fn watch<F>(
+        self,
+        name: &'static str,
+        critical: bool,
+        stall: Duration,
+        last_beat: impl Fn() -> i64 + Send + Sync + 'static,
+        restart: impl Fn() -> F + Send + Sync + 'static,
+    ) -> Self
+    where
+        F: std::future::Future<Output = Result<()>> + Send + 'static,
+    {
+        let restart: Restart = Box::new(move || Box::pin(restart()));
+        self.add(name, critical, stall, Box::new(last_beat), Some(restart))
+    }
+
+    /// Watch a task that cannot be restarted; a stall escalates if critical
+    pub fn watch_only(
+        self,
+        name: &'static str,
+        critical: bool,
+        stall: Duration,
+        last_beat: impl Fn() -> i64 + Send + Sync + 'static,
+    ) -> Self {
+        self.add(name, critical, stall, Box::new(last_beat), None)
+    }
+
+    fn add(
+        mut self,
+        name: &'static str,
+        critical: bool,
+        stall: Duration,
+        last_beat: Box<dyn Fn() -> i64 + Send + Sync>,
+        restart: Option<Restart>,
+    ) -> Self {
+        self.tasks.push(Supervised {
+            name,
+            critical,
+            last_beat,
+            stall_ms: stall.as_millis() as i64,
+            restart,
+            restarts: VecDeque::new(),
+            total_restarts: 0,
+            restarted_at_beat: None,
+        });
+        self
+    }
+
+    /// Decide on one task at `now` (ms), counting a restart if one is due
+    fn judge(task: &mut Supervised, config: &SupervisorConfig, now: i64) -> Verdict {
+        let beat = (task.last_beat)();
+        // Tasks that have not beaten yet are still starting
+        if beat == 0 || now - beat <= task.stall_ms || task.restarted_at_beat == Some(beat) {
+            return Verdict::Healthy;
+        }
+        if task.restart.is_none() {
+            task.restarted_at_beat = Some(beat);
+            return if task.critical { Verdict::Escalate } else { Verdict::Healthy };
+        }
+
+        let window_start = now - config.restart_window_secs as i64 * 1000;
+        while task.restarts.front().is_some_and(|t| *t < window_start) {
+            task.restarts.pop_front();
+        }
+        task.restarts.push_back(now);
+        task.total_restarts += 1;
+        task.restarted_at_beat = Some(beat);
+        if task.critical && task.restarts.len() as u32 > config.max_restarts {
+            Verdict::Escalate
+        } else {
+            Verdict::Restart
+        }
+    }
+
+    /// Check every task once
+    pub async fn check(&mut self, now: i64) {
+        for i in 0..self.tasks.len() {
+            let verdict = Self::judge(&mut self.tasks[i], &self.config, now);
+            let task = &self.tasks[i];
+            let silent_secs = (now - (task.last_beat)()) / 1000;
+            match verdict {
+                Verdict::Healthy => {}
+                Verdict::Restart => {
+                    warn!("{} silent for {}s, restarting (restart {})", task.name, silent_secs, task.total_restarts);
+                    record_task_restart(task.name);
+                    if let Some(restart) = &task.restart {
+                        if let Err(e) = restart().await {
+                            error!("Failed to restart {}: {:#}", task.name, e);
+                        }
+                    }
+                }
+                Verdict::Escalate => {
+                    if let Some(restart) = &task.restart {
+                        record_task_restart(task.name);
+                        if let Err(e) = restart().await {
+                            error!("Failed to restart {}: {:#}", task.name, e);
+                        }
+                    }
+                    let (name, restarts) = (task.name, task.restarts.len());
+                    if !self.escalated {
+                        self.escalate(name, silent_secs, restarts);
+                    }
+                }
+            }
+        }
+    }
+
+    fn escalate(&mut self, name: &str, silent_secs: i64, restarts: usize) {
+        let reason = if restarts > 0 {
+            format!("{} keeps dying ({} restarts in {}s)", name, restarts, self.config.restart_window_secs)
+        } else {
+            format!("{} stopped {}s ago and cannot be restarted", name, silent_secs)
+        };
+        error!("Task supervisor pausing trading: {}", reason);
+        self.control.pause(&reason);
+        self.escalated = true;
+        let _ = self.event_tx.send(Event::Alert(Alert::critical(
+            "Critical task failing",
+            format!("{}; trading paused until an operator resumes", reason),
+        )));
+    }
+
+    /// Check on `check_interval_secs` until the task is aborted
+    pub fn spawn(mut self) -> JoinHandle<()> {
+        let period = Duration::from_secs(self.config.check_interval_secs.max(1));
+        info!("Supervising {} tasks every {:?}", self.tasks.len(), period);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                // A resumed operator pause re-arms escalation
+                if self.escalated && !self.control.is_paused() {
+                    self.escalated = false;
+                }
+                self.check(chrono::Utc::now().timestamp_millis()).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    #[test]
+    fn test_restarts_then_escalates() {
+        let config = SupervisorConfig { max_restarts: 2, restart_window_secs: 600, ..SupervisorConfig::default() };
+        let beat = Arc::new(AtomicI64::new(1_000));
+        let reader = beat.clone();
+        let supervisor = Supervisor::new(&config, Arc::new(AgentControl::new()), broadcast::channel(8).0).watch(
+            "engine",
+            true,
+            Duration::from_secs(10),
+            move || reader.load(Ordering::Relaxed),
+            || async { Ok(()) },
+        );
+        let mut task = supervisor.tasks.into_iter().next().unwrap();
+
+        assert_eq!(Supervisor::judge(&mut task, &config, 5_000), Verdict::Healthy);
+        assert_eq!(Supervisor::judge(&mut task, &config, 20_000), Verdict::Restart);
+        // The same stall is acted on once
+        assert_eq!(Supervisor::judge(&mut task, &config, 25_000), Verdict::Healthy);
+
+        beat.store(30_000, Ordering::Relaxed);
+        assert_eq!(Supervisor::judge(&mut task, &config, 45_000), Verdict::Restart);
+        beat.store(50_000, Ordering::Relaxed);
+        assert_eq!(Supervisor::judge(&mut task, &config, 65_000), Verdict::Escalate);
+        assert_eq!(task.total_restarts, 3);
+    }
+}
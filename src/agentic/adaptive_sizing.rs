@@ -0,0 +1,656 @@
+//! Adaptive Position Sizing
+//!
+//! Kelly-criterion sizing adjusted by recent performance:
+//! - Kelly fraction from win rate and win/loss ratio, weighting rolling
+//!   windows (shorter first) more heavily than lifetime history
+//! - Half-Kelly and max-fraction safety caps
+//! - Monte Carlo risk-of-ruin cap on the Kelly fraction
+//! - Streak-based scaling (down on losses, conservatively up on wins)
+//! - Scaling by the basis regime (smaller while the basis trends)
+//! - Scaling by realized volatility (smaller while it is elevated)
+//! - Throttling by drawdown along the configured curve
+//! - Capping by the capital the wallet can actually deploy and the
+//!   portfolio allocator assigns to this market
+//! - A max position size grown by compounded realized P&L, if enabled
+//! - A full derivation of every recommendation (`explain`) for auditing
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::config::{AppConfig, VolatilityAction};
+use crate::execution::balances::AvailableCapital;
+use crate::state::SharedState;
+use crate::telemetry::record_drawdown_throttle;
+
+use super::capital_allocator::CapitalAllocator;
+use super::compounding::CompoundingState;
+use super::drawdown_throttle::DrawdownThrottle;
+use super::performance_db::{PerformanceDb, PerformanceMetrics};
+use super::regime_detector::{MarketRegime, RegimeAnalysis};
+use super::sizing_report::{KellyDerivation, SizingReport, StepKind};
+
+/// Conservative Kelly fraction used before enough trades are recorded
+const DEFAULT_KELLY_FRACTION: f64 = 0.10;
+
+/// Maximum times the Kelly fraction is halved to meet risk-of-ruin limits
+const MAX_RUIN_HALVINGS: u32 = 6;
+
+/// Trades a rolling window needs before it counts toward the sizing edge
+const MIN_WINDOW_TRADES: u32 = 5;
+
+/// Sizing recommendation
+#[derive(Debug, Clone)]
+pub struct SizingRecommendation {
+    /// Recommended size in SOL
+    pub size_sol: f64,
+    /// Size as percentage of max position size
+    pub size_pct_of_max: f64,
+    /// Kelly fraction used (0-1)
+    pub kelly_fraction: f64,
+    /// Signal confidence used for sizing (0-1)
+    pub confidence: f64,
+    /// Adjustments applied, in order
+    pub adjustments: Vec<String>,
+    /// Full derivation of the size
+    pub report: SizingReport,
+}
+
+/// Adaptive position sizer
+pub struct AdaptiveSizer {
+    /// Configuration
+    config: Arc<AppConfig>,
+    /// Shared state
+    state: Arc<SharedState>,
+    /// Performance database
+    performance_db: Arc<PerformanceDb>,
+    /// Cached Kelly derivation (None until first calculation)
+    kelly: RwLock<Option<KellyDerivation>>,
+    /// Latest wallet / collateral snapshot
+    available_capital: RwLock<Option<AvailableCapital>>,
+    /// Latest basis regime analysis (None if regime detection is not wired)
+    regime: Option<Arc<RwLock<Option<RegimeAnalysis>>>>,
+    /// Portfolio capital allocator (None = no allocation cap)
+    allocator: Option<Arc<CapitalAllocator>>,
+    /// Realized P&L reinvested into the max position size
+    compounding: RwLock<CompoundingState>,
+    /// Size cut by drawdown
+    throttle: parking_lot::Mutex<DrawdownThrottle>,
+}
+
+impl AdaptiveSizer {
+    /// Create a new adaptive sizer
+    pub fn new(
+        config: Arc<AppConfig>,
+        state: Arc<SharedState>,
+        performance_db: Arc<PerformanceDb>,
+    ) -> Self {
+        Self {
+            config,
+            state,
+            performance_db,
+            kelly: RwLock::new(None),
+            available_capital: RwLock::new(None),
+            regime: None,
+            allocator: None,
+            compounding: RwLock::new(CompoundingState::default()),
+            throttle: parking_lot::Mutex::new(DrawdownThrottle::new()),
+        }
+    }
+    
+    /// Take the latest drawdown reading for the drawdown throttle
+    pub fn observe_drawdown(&self, drawdown_pct: f64) {
+        let config = &self.config.risk.drawdown_throttle;
+        if !config.enabled {
+            return;
+        }
+        let mut throttle = self.throttle.lock();
+        let before = throttle.multiplier;
+        let multiplier = throttle.observe(config, drawdown_pct);
+        if multiplier < before {
+            warn!("Drawdown {:.2}%: size throttled to {:.0}%", drawdown_pct, multiplier * 100.0);
+        } else if multiplier > before {
+            info!("Drawdown back to {:.2}%: size throttle at {:.0}%", drawdown_pct, multiplier * 100.0);
+        }
+        record_drawdown_throttle(multiplier);
+    }
+    
+    /// Drawdown throttle state
+    pub fn drawdown_throttle(&self) -> DrawdownThrottle {
+        self.throttle.lock().clone()
+    }
+
+    /// Scale sizes by the basis regime reported through `regime`
+    pub fn with_regime(mut self, regime: Arc<RwLock<Option<RegimeAnalysis>>>) -> Self {
+        self.regime = Some(regime);
+        self
+    }
+
+    /// Cap sizes by the capital the allocator assigns to this market
+    pub fn with_allocator(mut self, allocator: Arc<CapitalAllocator>) -> Self {
+        self.allocator = Some(allocator);
+        self
+    }
+
+    /// Load the compounded max position size persisted by an earlier run
+    pub async fn restore_compounding(&self) -> anyhow::Result<()> {
+        if let Some(state) = self.performance_db.load_compounding().await? {
+            *self.compounding.write().await = state;
+            if self.config.agentic.enable_compounding {
+                info!(
+                    "Compounded max position: {:.2} SOL ({} trades, ${:.2} compounded)",
+                    self.max_position_size().await,
+                    state.trades,
+                    state.compounded_pnl_usd
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Compound a closed trade's realized P&L into the max position size
+    pub async fn compound(&self, pnl_usd: f64, exit_spot: f64) {
+        if !self.config.agentic.enable_compounding {
+            return;
+        }
+        let base = self.config.trading.max_position_size_sol;
+        let state = {
+            let mut state = self.compounding.write().await;
+            state.apply(pnl_usd, exit_spot, base, &self.config.agentic, chrono::Utc::now().timestamp_millis());
+            *state
+        };
+        info!(
+            "Compounded ${:.2}: max position {:.2} SOL (configured {:.2})",
+            pnl_usd,
+            state.max_size_sol(base),
+            base
+        );
+        if let Err(e) = self.performance_db.save_compounding(&state).await {
+            warn!("Failed to persist compounding state: {}", e);
+        }
+    }
+
+    /// Compounding progress so far
+    pub async fn compounding(&self) -> CompoundingState {
+        *self.compounding.read().await
+    }
+
+    /// Max position size: the configured one, grown by compounding if enabled
+    pub async fn max_position_size(&self) -> f64 {
+        let base = self.config.trading.max_position_size_sol;
+        if !self.config.agentic.enable_compounding {
+            return base;
+        }
+        self.compounding.read().await.max_size_sol(base)
+    }
+
+    /// Recalculate the Kelly fraction from the performance database
+    pub async fn recalculate(&self) -> f64 {
+        let metrics = self.performance_db.get_metrics().await;
+        let mut derivation = self.kelly_from_metrics(&metrics);
+        if metrics.total_trades >= self.config.agentic.min_trades_for_adaptation {
+            self.cap_by_risk_of_ruin(&mut derivation).await;
+        }
+        *self.kelly.write().await = Some(derivation);
+
+        info!(
+            "Adaptive sizing recalculated: Kelly {:.1}% ({} trades, {:.1}% win rate)",
+            derivation.kelly * 100.0,
+            metrics.total_trades,
+            metrics.win_rate * 100.0
+        );
+
+        derivation.kelly
+    }
+
+    /// Derive the (capped) Kelly fraction for a set of metrics
+    fn kelly_from_metrics(&self, metrics: &PerformanceMetrics) -> KellyDerivation {
+        let agentic = &self.config.agentic;
+        let mut derivation = KellyDerivation {
+            trades: metrics.total_trades,
+            lifetime_win_rate: metrics.win_rate,
+            lifetime_avg_win: metrics.avg_win,
+            lifetime_avg_loss: metrics.avg_loss,
+            recent_window_weight: agentic.recent_window_weight,
+            ..Default::default()
+        };
+
+        if !agentic.enable_adaptive_sizing
+            || metrics.total_trades < agentic.min_trades_for_adaptation
+            || metrics.avg_loss <= 0.0
+        {
+            let kelly = DEFAULT_KELLY_FRACTION.min(agentic.max_kelly_fraction);
+            derivation.capped_kelly = kelly;
+            derivation.kelly = kelly;
+            derivation.default_used = true;
+            return derivation;
+        }
+
+        // Kelly: f = W - (1 - W) / R, R = avg win / avg loss
+        let (win_rate, avg_win, avg_loss) = blended_edge(metrics, agentic.recent_window_weight);
+        let win_loss_ratio = avg_win / avg_loss;
+        let raw_kelly = win_rate - (1.0 - win_rate) / win_loss_ratio;
+        let mut kelly = raw_kelly;
+
+        if agentic.use_half_kelly {
+            kelly *= 0.5;
+        }
+        let kelly = kelly.max(0.0).min(agentic.max_kelly_fraction);
+
+        derivation.win_rate = win_rate;
+        derivation.avg_win = avg_win;
+        derivation.avg_loss = avg_loss;
+        derivation.raw_kelly = raw_kelly;
+        derivation.half_kelly = agentic.use_half_kelly;
+        derivation.capped_kelly = kelly;
+        derivation.kelly = kelly;
+        derivation
+    }
+
+    /// Halve the Kelly fraction until simulated risk of ruin and drawdown
+    /// breach probability are within the configured limits
+    async fn cap_by_risk_of_ruin(&self, derivation: &mut KellyDerivation) {
+        let agentic = &self.config.agentic;
+        let kelly = derivation.kelly;
+
+        for _ in 0..=MAX_RUIN_HALVINGS {
+            let Some(estimate) = self.performance_db.estimate_risk_of_ruin(derivation.kelly, &self.config).await else {
+                return;
+            };
+            if estimate.risk_of_ruin_pct <= agentic.max_risk_of_ruin_pct
+                && estimate.drawdown_breach_pct <= agentic.max_drawdown_breach_pct
+            {
+                break;
+            }
+            derivation.kelly *= 0.5;
+            derivation.ruin_halvings += 1;
+        }
+
+        if derivation.kelly < kelly {
+            warn!(
+                "Kelly fraction cut from {:.1}% to {:.1}% by risk-of-ruin limits",
+                kelly * 100.0, derivation.kelly * 100.0
+            );
+        }
+    }
+
+    /// Get the current Kelly derivation, calculating it on first use
+    async fn current_kelly(&self) -> KellyDerivation {
+        if let Some(derivation) = *self.kelly.read().await {
+            return derivation;
+        }
+        self.recalculate().await;
+        self.kelly.read().await.unwrap_or_default()
+    }
+
+    /// Update the capital snapshot used to cap recommendations
+    pub async fn update_available_capital(&self, capital: AvailableCapital) {
+        *self.available_capital.write().await = Some(capital);
+    }
+
+    /// Get the latest capital snapshot
+    pub async fn available_capital(&self) -> Option<AvailableCapital> {
+        self.available_capital.read().await.clone()
+    }
+
+    /// Get recommended position size for current conditions
+    pub async fn get_recommended_size(
+        &self,
+        basis_spread: f64,
+        funding_apr: f64,
+        confidence: f64,
+    ) -> SizingRecommendation {
+        let report = self.explain(basis_spread, funding_apr, confidence).await;
+        debug!("Sizing: {}", report.summary());
+
+        SizingRecommendation {
+            size_sol: report.size_sol,
+            size_pct_of_max: if report.max_size_sol > 0.0 { report.size_sol / report.max_size_sol * 100.0 } else { 0.0 },
+            kelly_fraction: report.kelly.kelly,
+            confidence,
+            adjustments: report.adjustments(),
+            report,
+        }
+    }
+
+    /// Size for current conditions with its full derivation: Kelly inputs,
+    /// each multiplier applied and each cap hit
+    pub async fn explain(
+        &self,
+        basis_spread: f64,
+        funding_apr: f64,
+        confidence: f64,
+    ) -> SizingReport {
+        let max_size = self.max_position_size().await;
+        let min_basis = self.config.trading.min_basis_spread_pct;
+        let min_multiplier = self.config.agentic.min_position_multiplier;
+        let kelly = self.current_kelly().await;
+
+        // Base size is 20% of max
+        let base_size = max_size * 0.2;
+        let mut report = SizingReport {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            basis_spread,
+            funding_apr,
+            confidence,
+            max_size_sol: max_size,
+            base_size_sol: base_size,
+            kelly,
+            steps: Vec::new(),
+            size_sol: base_size,
+        };
+
+        // Scale with basis strength
+        if min_basis > 0.0 {
+            let spread_multiple = (basis_spread.abs() / min_basis).clamp(0.5, 3.0);
+            report.push(
+                StepKind::Multiplier,
+                "basis",
+                report.size_sol * spread_multiple,
+                format!("Strong basis ({:.2}%): {:.1}x multiplier", basis_spread.abs(), spread_multiple),
+            );
+        }
+
+        // Scale by Kelly relative to the cap
+        let kelly_multiplier = (kelly.kelly / self.config.agentic.max_kelly_fraction)
+            .clamp(min_multiplier, 1.0);
+        report.push(
+            StepKind::Multiplier,
+            "kelly",
+            report.size_sol * kelly_multiplier,
+            format!("Kelly {:.1}%: {:.2}x multiplier", kelly.kelly * 100.0, kelly_multiplier),
+        );
+
+        // Scale by recent streak
+        if self.config.agentic.enable_adaptive_sizing {
+            let metrics = self.performance_db.get_metrics().await;
+            let streak = metrics.current_streak;
+            if streak <= -2 {
+                let multiplier = (1.0 + 0.1 * streak as f64).max(min_multiplier);
+                report.push(
+                    StepKind::Multiplier,
+                    "losing_streak",
+                    report.size_sol * multiplier,
+                    format!("Losing streak ({}): {:.2}x", streak, multiplier),
+                );
+            } else if streak >= 3 {
+                report.push(
+                    StepKind::Multiplier,
+                    "winning_streak",
+                    report.size_sol * 1.1,
+                    format!("Winning streak ({}): 1.10x", streak),
+                );
+            }
+        }
+
+        // Weak funding tempers conviction
+        let min_funding = self.config.trading.min_funding_apr_pct;
+        if min_funding > 0.0 && funding_apr.abs() < min_funding {
+            let multiplier = (funding_apr.abs() / min_funding).max(min_multiplier);
+            report.push(
+                StepKind::Multiplier,
+                "weak_funding",
+                report.size_sol * multiplier,
+                format!("Funding APR {:.1}% below target: {:.2}x", funding_apr, multiplier),
+            );
+        }
+
+        // Trending basis makes convergence unreliable
+        if let Some(regime) = self.regime.as_ref().filter(|_| self.config.agentic.enable_regime_detection) {
+            let regime = regime.read().await
+                .as_ref()
+                .map(|r| r.regime)
+                .unwrap_or(MarketRegime::Unknown);
+            let multiplier = regime.size_multiplier();
+            if multiplier < 1.0 {
+                report.push(
+                    StepKind::Multiplier,
+                    "regime",
+                    report.size_sol * multiplier,
+                    format!("Basis regime {}: {:.2}x", regime.as_str(), multiplier),
+                );
+            }
+        }
+
+        self.apply_risk_scaling(&mut report);
+
+        let confidence_multiplier = confidence.clamp(0.0, 1.0);
+        report.push(
+            StepKind::Multiplier,
+            "confidence",
+            report.size_sol * confidence_multiplier,
+            format!("Confidence {:.0}%: {:.2}x", confidence * 100.0, confidence_multiplier),
+        );
+        if report.size_sol > max_size {
+            report.push(
+                StepKind::Cap,
+                "max_position",
+                max_size,
+                format!("Capped to max position: {:.4} SOL (was {:.4})", max_size, report.size_sol),
+            );
+        }
+
+        self.apply_capital_caps(&mut report).await;
+        report
+    }
+
+    /// Largest size an operator may open or override to: the max position
+    /// after volatility and drawdown scaling, within the capital caps
+    pub async fn operator_ceiling(&self) -> f64 {
+        let max_size = self.max_position_size().await;
+        let mut report = SizingReport {
+            max_size_sol: max_size,
+            base_size_sol: max_size,
+            size_sol: max_size,
+            ..Default::default()
+        };
+        self.apply_risk_scaling(&mut report);
+        self.apply_capital_caps(&mut report).await;
+        report.size_sol
+    }
+
+    /// Scale the size down for elevated volatility and drawdown
+    fn apply_risk_scaling(&self, report: &mut SizingReport) {
+        // Violent markets make the legs fill apart
+        let volatility = &self.config.risk.volatility;
+        if volatility.action == VolatilityAction::Halve {
+            if let Some(vol) = (*self.state.volatility.read()).filter(|v| v.elevated) {
+                report.push(
+                    StepKind::Multiplier,
+                    "volatility",
+                    report.size_sol * volatility.size_multiplier,
+                    format!("Realized volatility {:.1}% elevated: {:.2}x", vol.realized_vol_pct, volatility.size_multiplier),
+                );
+            }
+        }
+
+        // Cut size as drawdown nears the limit, until it has recovered
+        let throttle = self.drawdown_throttle();
+        if self.config.risk.drawdown_throttle.enabled && throttle.is_throttled() {
+            report.push(
+                StepKind::Multiplier,
+                "drawdown",
+                report.size_sol * throttle.multiplier,
+                format!(
+                    "Drawdown throttle (worst {:.2}%, now {:.2}%): {:.2}x",
+                    throttle.worst_pct, throttle.drawdown_pct, throttle.multiplier
+                ),
+            );
+        }
+    }
+
+    /// Clamp the size to the wallet, collateral and allocation
+    async fn apply_capital_caps(&self, report: &mut SizingReport) {
+        // Never recommend more than the wallet can fund
+        let capital = self.available_capital.read().await.clone();
+        if let Some(capital) = capital.as_ref() {
+            self.apply_capital_limits(capital, report);
+        }
+
+        // Nor more than this market's share of portfolio capital
+        if let (Some(allocator), Some(capital)) = (&self.allocator, capital.as_ref()) {
+            self.apply_allocation(allocator, capital, report).await;
+        }
+    }
+
+    /// Clamp the size to this market's allocation, rebalancing if it is due
+    async fn apply_allocation(
+        &self,
+        allocator: &CapitalAllocator,
+        capital: &AvailableCapital,
+        report: &mut SizingReport,
+    ) {
+        let spot_price = self.state.snapshot().spot_price;
+        if spot_price <= 0.0 {
+            return;
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        if allocator.needs_rebalance(now).await {
+            let total = capital.usdc_balance
+                + capital.sol_balance * spot_price
+                + capital.drift_free_collateral;
+            allocator.rebalance(total, now).await;
+        }
+
+        let market = self.config.perp_market();
+        let Some(allocation_usd) = allocator.allocation_usd(&market).await else {
+            return;
+        };
+        let max_size = allocation_usd / spot_price;
+        if report.size_sol > max_size {
+            report.push(
+                StepKind::Cap,
+                "allocation",
+                max_size,
+                format!("Capped to {} allocation: {:.4} SOL (was {:.4})", market, max_size, report.size_sol),
+            );
+        }
+    }
+
+    /// Clamp the size to what wallet balances and Drift collateral support
+    fn apply_capital_limits(&self, capital: &AvailableCapital, report: &mut SizingReport) {
+        if !capital.has_gas() {
+            report.push(
+                StepKind::Cap,
+                "gas_reserve",
+                0.0,
+                format!(
+                    "SOL balance {:.4} below gas reserve {:.4}: size 0",
+                    capital.sol_balance, capital.gas_reserve_sol
+                ),
+            );
+            return;
+        }
+
+        let market = self.state.snapshot();
+
+        let max_spot = capital.max_spot_size(market.spot_price);
+        if report.size_sol > max_spot {
+            report.push(
+                StepKind::Cap,
+                "wallet_balance",
+                max_spot,
+                format!("Capped to wallet balance: {:.4} SOL (was {:.4})", max_spot, report.size_sol),
+            );
+        }
+
+        let max_perp = capital.max_perp_size(market.perp_mark_price, self.config.trading.max_leverage);
+        if report.size_sol > max_perp {
+            report.push(
+                StepKind::Cap,
+                "perp_collateral",
+                max_perp,
+                format!("Capped to Drift free collateral: {:.4} SOL (was {:.4})", max_perp, report.size_sol),
+            );
+        }
+    }
+}
+
+/// Win rate, average win and average loss for Kelly, blending lifetime
+/// metrics with the rolling windows.
+///
+/// The windows share `recent_weight` of the blend in inverse proportion to
+/// their length, so the shortest counts most. Windows with fewer than
+/// `MIN_WINDOW_TRADES` trades or without a loss are left out.
+fn blended_edge(metrics: &PerformanceMetrics, recent_weight: f64) -> (f64, f64, f64) {
+    let lifetime = (metrics.win_rate, metrics.avg_win, metrics.avg_loss);
+    let windows: Vec<_> = metrics.windows.iter()
+        .filter(|w| w.trades >= MIN_WINDOW_TRADES && w.avg_loss > 0.0)
+        .collect();
+    if windows.is_empty() || recent_weight <= 0.0 {
+        return lifetime;
+    }
+
+    let total: f64 = windows.iter().map(|w| 1.0 / w.days as f64).sum();
+    let lifetime_weight = 1.0 - recent_weight;
+    windows.iter().fold(
+        (lifetime.0 * lifetime_weight, lifetime.1 * lifetime_weight, lifetime.2 * lifetime_weight),
+        |(win_rate, avg_win, avg_loss), w| {
+            let weight = recent_weight / w.days as f64 / total;
+            (win_rate + w.win_rate * weight, avg_win + w.avg_win * weight, avg_loss + w.avg_loss * weight)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agentic::performance_db::WindowMetrics;
+
+    #[tokio::test]
+    async fn test_size_capped_by_capital() {
+        let state = Arc::new(SharedState::new());
+        state.update_spot_price(100.0);
+        state.update_perp_mark_price(100.0);
+
+        let dir = std::env::temp_dir().join(format!("sizing-{}", uuid::Uuid::new_v4()));
+        let db = Arc::new(PerformanceDb::new(dir.join("perf.json").to_str().unwrap()).await.unwrap());
+        let sizer = AdaptiveSizer::new(Arc::new(AppConfig::default_for_test()), state, db);
+
+        sizer.update_available_capital(AvailableCapital {
+            sol_balance: 1.0,
+            usdc_balance: 500.0,
+            drift_free_collateral: 10_000.0,
+            gas_reserve_sol: 0.05,
+            timestamp: 0,
+        }).await;
+
+        let rec = sizer.get_recommended_size(0.3, 20.0, 1.0).await;
+        assert!(rec.size_sol <= 5.95 + 1e-9);
+        assert!(rec.adjustments.iter().any(|a| a.contains("wallet balance")));
+        assert!(rec.report.caps_hit().any(|s| s.name == "wallet_balance"));
+        assert!(rec.report.kelly.default_used);
+        assert_eq!(rec.report.size_sol, rec.size_sol);
+
+        // Operator sizes answer to the same wallet cap
+        assert!(sizer.operator_ceiling().await <= 5.95 + 1e-9);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_recent_windows_outweigh_lifetime() {
+        let window = |days, trades, win_rate| WindowMetrics {
+            days,
+            trades,
+            win_rate,
+            avg_win: 10.0,
+            avg_loss: 10.0,
+            ..Default::default()
+        };
+        let metrics = PerformanceMetrics {
+            win_rate: 0.8,
+            avg_win: 10.0,
+            avg_loss: 10.0,
+            windows: vec![window(7, 10, 0.4), window(30, 30, 0.6), window(90, 2, 0.0)],
+            ..Default::default()
+        };
+
+        // 90d is too thin; 7d takes 30/37 of the recent share
+        let (win_rate, _, _) = blended_edge(&metrics, 0.5);
+        let expected = 0.5 * 0.8 + 0.5 * (0.4 * 30.0 + 0.6 * 7.0) / 37.0;
+        assert!((win_rate - expected).abs() < 1e-9);
+        assert_eq!(blended_edge(&metrics, 0.0).0, 0.8);
+    }
+}
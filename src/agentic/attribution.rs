@@ -0,0 +1,123 @@
+//! P&L Attribution
+//!
+//! Decomposes trade P&L into its sources:
+//! - Convergence: basis narrowing between entry and exit, at quoted prices
+//! - Funding carry collected on the short perp
+//! - Directional: residual exposure from an imperfect hedge
+//! - Execution slippage and fees
+//!
+//! A basis trade that makes money mostly from the directional bucket is
+//! not doing what it is supposed to, even if it is profitable.
+
+use serde::{Deserialize, Serialize};
+
+use super::performance_db::TradeOutcome;
+
+/// P&L broken down by source (USD, positive = profit)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PnlAttribution {
+    /// P&L from basis convergence
+    pub convergence: f64,
+    /// Funding carry collected
+    pub funding: f64,
+    /// P&L from unhedged price exposure
+    pub directional: f64,
+    /// Execution slippage (always <= 0)
+    pub slippage: f64,
+    /// Fees paid (always <= 0)
+    pub fees: f64,
+}
+
+impl PnlAttribution {
+    /// Attribute a single trade.
+    ///
+    /// Entry and exit prices are the quotes the legs traded against, while
+    /// leg P&Ls are realized at fill prices. Convergence and directional are
+    /// measured at the quotes and slippage is its own line, so
+    /// `convergence + funding + directional - fees - slippage_cost` equals
+    /// the trade's P&L.
+    pub fn from_trade(trade: &TradeOutcome) -> Self {
+        let entry_basis_usd = trade.entry_perp - trade.entry_spot;
+        let exit_basis_usd = trade.exit_perp - trade.exit_spot;
+        let basis_pnl = (entry_basis_usd - exit_basis_usd) * trade.size;
+        // Both legs at the quotes, before execution cost
+        let quoted_legs_pnl = trade.spot_pnl + trade.perp_pnl + trade.slippage_cost;
+
+        Self {
+            convergence: basis_pnl,
+            funding: trade.funding_collected,
+            directional: quoted_legs_pnl - basis_pnl,
+            slippage: -trade.slippage_cost,
+            fees: -trade.fees_paid,
+        }
+    }
+
+    /// Aggregate attribution across trades
+    pub fn aggregate<'a>(trades: impl IntoIterator<Item = &'a TradeOutcome>) -> Self {
+        trades.into_iter().fold(Self::default(), |mut acc, t| {
+            let a = Self::from_trade(t);
+            acc.convergence += a.convergence;
+            acc.funding += a.funding;
+            acc.directional += a.directional;
+            acc.slippage += a.slippage;
+            acc.fees += a.fees;
+            acc
+        })
+    }
+
+    /// Sum of all components
+    pub fn total(&self) -> f64 {
+        self.convergence + self.funding + self.directional + self.slippage + self.fees
+    }
+
+    /// Sum of absolute component sizes
+    fn gross(&self) -> f64 {
+        self.convergence.abs() + self.funding.abs() + self.directional.abs()
+            + self.slippage.abs() + self.fees.abs()
+    }
+
+    /// Share of gross P&L coming from funding carry (0-1)
+    pub fn carry_share(&self) -> f64 {
+        let gross = self.gross();
+        if gross > 0.0 { self.funding.abs() / gross } else { 0.0 }
+    }
+
+    /// Share of gross P&L coming from directional exposure (0-1)
+    pub fn directional_share(&self) -> f64 {
+        let gross = self.gross();
+        if gross > 0.0 { self.directional.abs() / gross } else { 0.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribution_sums_to_total() {
+        // Long 10 spot at 150 -> 152, short 9 perp at 150.30 -> 152.05 (under-hedged)
+        let trade = TradeOutcome {
+            size: 10.0,
+            entry_spot: 150.0,
+            entry_perp: 150.30,
+            exit_spot: 152.0,
+            exit_perp: 152.05,
+            // 0.40 of slippage paid on the spot fills
+            spot_pnl: (152.0 - 150.0) * 10.0 - 0.4,
+            perp_pnl: (150.30 - 152.05) * 9.0,
+            funding_collected: 1.5,
+            slippage_cost: 0.4,
+            fees_paid: 0.6,
+            total_pnl: 19.6 - 15.75 + 1.5 - 0.6,
+            ..Default::default()
+        };
+
+        let a = PnlAttribution::from_trade(&trade);
+        assert!((a.convergence - 2.5).abs() < 1e-9);
+        assert!((a.directional - 1.75).abs() < 1e-9);
+        assert!((a.slippage + 0.4).abs() < 1e-9);
+        let identity = a.convergence + a.funding + a.directional - trade.fees_paid - trade.slippage_cost;
+        assert!((identity - trade.total_pnl).abs() < 1e-9);
+        assert!((a.total() - trade.total_pnl).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,159 @@
+//! Confidence Calibration
+//!
+//! Signal confidence is only useful if a 70% signal wins about 70% of the
+//! time. Closed trades are bucketed by entry confidence and each bucket's
+//! realized win rate is compared with the confidence it predicted:
+//! - Over-confidence: trade-weighted predicted minus realized win rate
+//! - Shrink factor: realized over predicted, applied to new confidence
+//!   inputs once enough trades exist (never raised above 1)
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::config::AgenticConfig;
+
+use super::performance_db::{PerformanceDb, TradeOutcome};
+
+/// Trades with entry confidence in `[lower, upper)`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub trades: u32,
+    /// Mean entry confidence (0-1)
+    pub predicted: f64,
+    /// Share of winners (0-1)
+    pub realized_win_rate: f64,
+}
+
+/// Predicted versus realized win rates
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    /// Buckets over [0, 1], lowest first; empty buckets included
+    pub buckets: Vec<CalibrationBucket>,
+    /// Trades with a recorded entry confidence
+    pub trades: u32,
+    /// Mean entry confidence over all trades (0-1)
+    pub predicted: f64,
+    /// Win rate over all trades (0-1)
+    pub realized_win_rate: f64,
+    /// Predicted minus realized win rate; positive is over-confident
+    pub overconfidence: f64,
+    /// Multiplier applied to confidence inputs (1 = no shrink)
+    pub shrink_factor: f64,
+}
+
+/// Bucket trades by entry confidence and derive the shrink factor
+pub fn calibrate(trades: &[TradeOutcome], config: &AgenticConfig) -> CalibrationReport {
+    let count = config.calibration_buckets.max(1);
+    let width = 1.0 / count as f64;
+    let mut buckets: Vec<CalibrationBucket> = (0..count)
+        .map(|i| CalibrationBucket { lower: i as f64 * width, upper: (i + 1) as f64 * width, ..Default::default() })
+        .collect();
+
+    let scored: Vec<&TradeOutcome> = trades.iter().filter(|t| t.entry_confidence > 0.0).collect();
+    let mut wins = vec![0u32; count];
+    let mut confidence_sum = vec![0.0; count];
+    for trade in &scored {
+        let confidence = trade.entry_confidence.clamp(0.0, 1.0);
+        let index = ((confidence / width) as usize).min(count - 1);
+        buckets[index].trades += 1;
+        confidence_sum[index] += confidence;
+        wins[index] += trade.is_winner as u32;
+    }
+    for (i, bucket) in buckets.iter_mut().enumerate() {
+        if bucket.trades > 0 {
+            bucket.predicted = confidence_sum[i] / bucket.trades as f64;
+            bucket.realized_win_rate = wins[i] as f64 / bucket.trades as f64;
+        }
+    }
+
+    let mut report = CalibrationReport { buckets, trades: scored.len() as u32, shrink_factor: 1.0, ..Default::default() };
+    if scored.is_empty() {
+        return report;
+    }
+    let n = scored.len() as f64;
+    report.predicted = confidence_sum.iter().sum::<f64>() / n;
+    report.realized_win_rate = wins.iter().sum::<u32>() as f64 / n;
+    report.overconfidence = report.predicted - report.realized_win_rate;
+    if report.trades >= config.calibration_min_trades && report.overconfidence > 0.0 {
+        report.shrink_factor = (report.realized_win_rate / report.predicted).clamp(config.min_confidence_shrink, 1.0);
+    }
+    report
+}
+
+/// Keeps the calibration current and shrinks confidence inputs
+pub struct ConfidenceCalibrator {
+    config: AgenticConfig,
+    performance_db: Arc<PerformanceDb>,
+    report: RwLock<CalibrationReport>,
+}
+
+impl ConfidenceCalibrator {
+    pub fn new(config: &AgenticConfig, performance_db: Arc<PerformanceDb>) -> Self {
+        Self {
+            config: config.clone(),
+            performance_db,
+            report: RwLock::new(CalibrationReport { shrink_factor: 1.0, ..Default::default() }),
+        }
+    }
+
+    /// Rebuild the calibration from all closed trades
+    pub async fn recalculate(&self) -> CalibrationReport {
+        let report = calibrate(&self.performance_db.get_all_trades().await, &self.config);
+        if report.shrink_factor < 1.0 {
+            info!(
+                "Signals over-confident: predicted {:.0}%, realized {:.0}% over {} trades; confidence x{:.2}",
+                report.predicted * 100.0,
+                report.realized_win_rate * 100.0,
+                report.trades,
+                report.shrink_factor
+            );
+        }
+        *self.report.write().await = report.clone();
+        report
+    }
+
+    /// Latest calibration
+    pub async fn report(&self) -> CalibrationReport {
+        self.report.read().await.clone()
+    }
+
+    /// Confidence after correcting for measured over-confidence
+    pub async fn adjust(&self, confidence: f64) -> f64 {
+        if !self.config.enable_confidence_calibration {
+            return confidence;
+        }
+        confidence * self.report.read().await.shrink_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(confidence: f64, is_winner: bool) -> TradeOutcome {
+        TradeOutcome { entry_confidence: confidence, is_winner, ..Default::default() }
+    }
+
+    #[test]
+    fn test_overconfident_signals_shrink() {
+        let config = AgenticConfig { calibration_min_trades: 10, ..AgenticConfig::default() };
+        // 0.9-confidence signals winning half the time
+        let trades: Vec<_> = (0..20).map(|i| trade(0.9, i % 2 == 0)).collect();
+        let report = calibrate(&trades, &config);
+
+        let top = report.buckets.last().unwrap();
+        assert_eq!(top.trades, 20);
+        assert!((top.realized_win_rate - 0.5).abs() < 1e-9);
+        assert!((report.overconfidence - 0.4).abs() < 1e-9);
+        assert!((report.shrink_factor - 0.5 / 0.9).abs() < 1e-9);
+
+        // Too few trades, or well calibrated: no shrink
+        assert_eq!(calibrate(&trades[..5], &config).shrink_factor, 1.0);
+        let calibrated: Vec<_> = (0..20).map(|i| trade(0.5, i % 2 == 0)).collect();
+        assert_eq!(calibrate(&calibrated, &config).shrink_factor, 1.0);
+    }
+}
@@ -0,0 +1,180 @@
+//! Capital Allocation
+//!
+//! Portfolio-level split of capital across markets/strategies:
+//! - Weights proportional to each market's realized Sharpe ratio
+//! - Equal weights when no market has a positive Sharpe ratio
+//! - Neutral prior for markets without enough trade history
+//! - Per-market and aggregate exposure caps from the risk config
+//! - Allocations refreshed once per rebalance interval (daily by default)
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::config::AppConfig;
+
+use super::performance_db::{MarketPerformance, PerformanceDb};
+
+/// Sharpe assumed for markets without enough trades
+const PRIOR_SHARPE: f64 = 1.0;
+
+/// Capital assigned to one market
+#[derive(Debug, Clone, Serialize)]
+pub struct Allocation {
+    /// Market / strategy label
+    pub market: String,
+    /// Sharpe ratio used for weighting
+    pub sharpe: f64,
+    /// Share of total capital (0-1)
+    pub weight: f64,
+    /// Capital assigned (USD)
+    pub capital_usd: f64,
+}
+
+/// Portfolio capital allocator
+pub struct CapitalAllocator {
+    /// Configuration
+    config: Arc<AppConfig>,
+    /// Performance database
+    performance_db: Arc<PerformanceDb>,
+    /// Markets competing for capital
+    markets: Vec<String>,
+    /// Current allocations by market
+    allocations: RwLock<HashMap<String, Allocation>>,
+    /// Last rebalance time (ms, 0 = never)
+    last_rebalance: RwLock<i64>,
+}
+
+impl CapitalAllocator {
+    /// Create an allocator for a set of markets
+    pub fn new(config: Arc<AppConfig>, performance_db: Arc<PerformanceDb>, markets: Vec<String>) -> Self {
+        Self {
+            config,
+            performance_db,
+            markets,
+            allocations: RwLock::new(HashMap::new()),
+            last_rebalance: RwLock::new(0),
+        }
+    }
+
+    /// Whether the rebalance interval has elapsed
+    pub async fn needs_rebalance(&self, now: i64) -> bool {
+        let interval_ms = self.config.risk.allocation_rebalance_hours as i64 * 3_600_000;
+        now - *self.last_rebalance.read().await >= interval_ms
+    }
+
+    /// Recompute allocations from `total_capital_usd`
+    pub async fn rebalance(&self, total_capital_usd: f64, now: i64) -> Vec<Allocation> {
+        let performance = self.performance_db.get_performance_by_market().await;
+        let allocations = self.allocate(total_capital_usd, &performance);
+
+        for a in &allocations {
+            info!(
+                "Allocation {}: {:.1}% (${:.0}, Sharpe {:.2})",
+                a.market, a.weight * 100.0, a.capital_usd, a.sharpe
+            );
+        }
+
+        *self.allocations.write().await = allocations
+            .iter()
+            .map(|a| (a.market.clone(), a.clone()))
+            .collect();
+        *self.last_rebalance.write().await = now;
+        allocations
+    }
+
+    /// Split capital by Sharpe, then apply per-market and aggregate caps.
+    ///
+    /// Capital trimmed by the per-market cap is left unallocated rather
+    /// than pushed onto other markets. Without a positive Sharpe anywhere
+    /// the markets share capital equally instead of getting none.
+    fn allocate(&self, total_capital_usd: f64, performance: &[MarketPerformance]) -> Vec<Allocation> {
+        let risk = &self.config.risk;
+        let min_trades = self.config.agentic.min_trades_for_adaptation;
+
+        let sharpes: Vec<f64> = self.markets
+            .iter()
+            .map(|market| {
+                performance
+                    .iter()
+                    .find(|p| &p.market == market && p.trades >= min_trades)
+                    .map(|p| p.sharpe_ratio)
+                    .unwrap_or(PRIOR_SHARPE)
+            })
+            .collect();
+
+        let total_score: f64 = sharpes.iter().map(|s| s.max(0.0)).sum();
+        let deployable = total_capital_usd.max(0.0).min(risk.max_total_exposure_usd);
+        let max_weight = risk.max_market_allocation_pct / 100.0;
+
+        self.markets
+            .iter()
+            .zip(sharpes)
+            .map(|(market, sharpe)| {
+                let raw = if total_score > 0.0 {
+                    sharpe.max(0.0) / total_score
+                } else {
+                    1.0 / self.markets.len() as f64
+                };
+                let weight = raw.min(max_weight);
+                Allocation {
+                    market: market.clone(),
+                    sharpe,
+                    weight,
+                    capital_usd: deployable * weight,
+                }
+            })
+            .collect()
+    }
+
+    /// Capital currently assigned to a market (None before the first rebalance)
+    pub async fn allocation_usd(&self, market: &str) -> Option<f64> {
+        self.allocations.read().await.get(market).map(|a| a.capital_usd)
+    }
+
+    /// All current allocations
+    pub async fn allocations(&self) -> Vec<Allocation> {
+        self.allocations.read().await.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_allocation_weights_and_caps() {
+        let mut config = AppConfig::default_for_test();
+        config.risk.max_total_exposure_usd = 10_000.0;
+        config.risk.max_market_allocation_pct = 60.0;
+        config.agentic.min_trades_for_adaptation = 10;
+
+        let dir = std::env::temp_dir().join(format!("allocator-{}", uuid::Uuid::new_v4()));
+        let db = Arc::new(PerformanceDb::new(dir.join("perf.json").to_str().unwrap()).await.unwrap());
+        let markets = vec!["SOL-PERP".to_string(), "ETH-PERP".to_string(), "BTC-PERP".to_string()];
+        let allocator = CapitalAllocator::new(Arc::new(config), db, markets);
+
+        let performance = vec![
+            MarketPerformance { market: "SOL-PERP".to_string(), trades: 20, sharpe_ratio: 3.0, net_pnl: 0.0 },
+            MarketPerformance { market: "ETH-PERP".to_string(), trades: 20, sharpe_ratio: -0.5, net_pnl: 0.0 },
+            MarketPerformance { market: "BTC-PERP".to_string(), trades: 2, sharpe_ratio: 9.0, net_pnl: 0.0 },
+        ];
+        let allocations = allocator.allocate(50_000.0, &performance);
+
+        // SOL 3 / (3 + 1 prior) = 75%, capped at 60% of the 10k exposure limit
+        assert!((allocations[0].capital_usd - 6_000.0).abs() < 1e-6);
+        // Negative Sharpe gets nothing; thin history falls back to the prior
+        assert_eq!(allocations[1].capital_usd, 0.0);
+        assert!((allocations[2].weight - 0.25).abs() < 1e-9);
+
+        // No positive Sharpe anywhere: equal weights, not an idle book
+        let losing: Vec<_> = performance
+            .iter()
+            .map(|p| MarketPerformance { trades: 20, sharpe_ratio: -1.0, ..p.clone() })
+            .collect();
+        let allocations = allocator.allocate(9_000.0, &losing);
+        assert!(allocations.iter().all(|a| (a.capital_usd - 3_000.0).abs() < 1e-6));
+    }
+}
@@ -0,0 +1,76 @@
+//! Profit Compounding
+//!
+//! Grows `trading.max_position_size_sol` with realized P&L so winners are
+//! compounded without config edits:
+//! - Each closed trade reinvests `compounding_fraction` of its P&L, turned
+//!   into SOL at the exit price (losses shrink the size the same way)
+//! - The compounded size stays between `min_compounding_multiplier` and
+//!   `max_compounding_multiplier` times the configured size
+//!
+//! The reinvested amount is kept in `compounding.json` next to the
+//! performance database, so the grown size survives restarts.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::AgenticConfig;
+
+/// Realized P&L reinvested into the position size
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompoundingState {
+    /// Size added to (or, if negative, taken from) the configured max (SOL)
+    pub reinvested_sol: f64,
+    /// Realized P&L compounded so far (USD)
+    pub compounded_pnl_usd: f64,
+    /// Closed trades compounded
+    pub trades: u32,
+    /// Last update (ms)
+    pub updated_at: i64,
+}
+
+impl CompoundingState {
+    /// Reinvest a closed trade's share of P&L, keeping the size within its caps
+    pub fn apply(&mut self, pnl_usd: f64, exit_spot: f64, base_max_sol: f64, config: &AgenticConfig, now: i64) {
+        if exit_spot <= 0.0 {
+            return;
+        }
+        let lower = base_max_sol * (config.min_compounding_multiplier - 1.0);
+        let upper = base_max_sol * (config.max_compounding_multiplier - 1.0);
+        self.reinvested_sol =
+            (self.reinvested_sol + pnl_usd * config.compounding_fraction / exit_spot).clamp(lower, upper);
+        self.compounded_pnl_usd += pnl_usd;
+        self.trades += 1;
+        self.updated_at = now;
+    }
+
+    /// Max position size after compounding (SOL)
+    pub fn max_size_sol(&self, base_max_sol: f64) -> f64 {
+        (base_max_sol + self.reinvested_sol).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profits_grow_size_within_caps() {
+        let config = AgenticConfig {
+            compounding_fraction: 0.5,
+            max_compounding_multiplier: 1.5,
+            min_compounding_multiplier: 0.8,
+            ..AgenticConfig::default()
+        };
+        let mut state = CompoundingState::default();
+
+        // $2,000 at $100: half of 20 SOL reinvested
+        state.apply(2_000.0, 100.0, 100.0, &config, 1);
+        assert_eq!(state.max_size_sol(100.0), 110.0);
+
+        // Growth and shrinkage stop at the caps
+        state.apply(20_000.0, 100.0, 100.0, &config, 2);
+        assert_eq!(state.max_size_sol(100.0), 150.0);
+        state.apply(-50_000.0, 100.0, 100.0, &config, 3);
+        assert_eq!(state.max_size_sol(100.0), 80.0);
+        assert_eq!(state.trades, 3);
+    }
+}
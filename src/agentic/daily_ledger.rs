@@ -0,0 +1,145 @@
+//! Daily P&L Ledger
+//!
+//! One row per trading day, persisted as `daily_ledger.json` next to the
+//! performance database:
+//! - Realized P&L, fees and funding of the trades closed that day
+//! - Trade count
+//!
+//! Days run from midnight to midnight in `risk.daily_timezone`, and a trade
+//! counts on the day it closed. The risk manager checks the daily loss limit
+//! against today's row, so the limit holds across restarts. A missing ledger
+//! is rebuilt from the closed trades.
+
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use super::performance_db::TradeOutcome;
+
+/// Totals of one trading day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyLedgerEntry {
+    /// Local date in the ledger's timezone
+    pub date: NaiveDate,
+    /// Net realized P&L (USD)
+    pub realized_pnl: f64,
+    /// Fees paid (USD)
+    pub fees: f64,
+    /// Funding collected (USD)
+    pub funding: f64,
+    pub trades: u32,
+}
+
+impl DailyLedgerEntry {
+    fn new(date: NaiveDate) -> Self {
+        Self { date, realized_pnl: 0.0, fees: 0.0, funding: 0.0, trades: 0 }
+    }
+}
+
+/// Trading day of `timestamp_ms` in `tz`
+pub fn trading_day(tz: Tz, timestamp_ms: i64) -> NaiveDate {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms)
+        .unwrap_or_default()
+        .with_timezone(&tz)
+        .date_naive()
+}
+
+/// Daily rows, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DailyLedger {
+    entries: Vec<DailyLedgerEntry>,
+}
+
+impl DailyLedger {
+    /// Ledger of `trades`, by close day in `tz`
+    pub fn rebuild<'a>(trades: impl IntoIterator<Item = &'a TradeOutcome>, tz: Tz) -> Self {
+        let mut ledger = Self::default();
+        for trade in trades {
+            ledger.record(tz, trade);
+        }
+        ledger
+    }
+
+    /// Add a closed trade to its day
+    pub fn record(&mut self, tz: Tz, trade: &TradeOutcome) -> &DailyLedgerEntry {
+        let date = trading_day(tz, trade.close_time);
+        let index = match self.entries.binary_search_by_key(&date, |e| e.date) {
+            Ok(index) => index,
+            Err(index) => {
+                self.entries.insert(index, DailyLedgerEntry::new(date));
+                index
+            }
+        };
+        let entry = &mut self.entries[index];
+        entry.realized_pnl += trade.total_pnl;
+        entry.fees += trade.fees_paid;
+        entry.funding += trade.funding_collected;
+        entry.trades += 1;
+        entry
+    }
+
+    /// Row of `date`, if anything closed that day
+    pub fn day(&self, date: NaiveDate) -> Option<&DailyLedgerEntry> {
+        self.entries.binary_search_by_key(&date, |e| e.date).ok().map(|i| &self.entries[i])
+    }
+
+    /// The last `days` rows, oldest first
+    pub fn recent(&self, days: usize) -> &[DailyLedgerEntry] {
+        &self.entries[self.entries.len().saturating_sub(days)..]
+    }
+
+    /// Human-readable report of the last `days` rows
+    pub fn summary(&self, days: usize) -> String {
+        let mut out = format!(
+            "{:<10}  {:>6}  {:>12}  {:>10}  {:>10}\n",
+            "date", "trades", "realized", "fees", "funding"
+        );
+        for entry in self.recent(days) {
+            out.push_str(&format!(
+                "{}  {:>6}  {:>12.2}  {:>10.2}  {:>10.2}\n",
+                entry.date, entry.trades, entry.realized_pnl, entry.fees, entry.funding
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(close: &str, pnl: f64) -> TradeOutcome {
+        let close_time = chrono::DateTime::parse_from_rfc3339(close).unwrap().timestamp_millis();
+        TradeOutcome { close_time, total_pnl: pnl, fees_paid: 1.0, funding_collected: 2.5, ..Default::default() }
+    }
+
+    #[test]
+    fn test_days_follow_timezone() {
+        let trades = vec![
+            trade("2026-03-14T15:00:00Z", -40.0),
+            // Still the 14th in New York (UTC-4)
+            trade("2026-03-15T03:00:00Z", -25.0),
+            trade("2026-03-15T05:00:00Z", 10.0),
+        ];
+        let day = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+        let utc = DailyLedger::rebuild(&trades, chrono_tz::UTC);
+        assert_eq!(utc.day(day("2026-03-14")).unwrap().trades, 1);
+        assert_eq!(utc.day(day("2026-03-15")).unwrap().realized_pnl, -15.0);
+
+        let new_york = DailyLedger::rebuild(&trades, chrono_tz::America::New_York);
+        let first = new_york.day(day("2026-03-14")).unwrap();
+        assert_eq!(first.trades, 2);
+        assert_eq!(first.realized_pnl, -65.0);
+        assert_eq!(first.fees, 2.0);
+        assert_eq!(first.funding, 5.0);
+        assert_eq!(new_york.recent(1), &[DailyLedgerEntry {
+            date: day("2026-03-15"),
+            realized_pnl: 10.0,
+            fees: 1.0,
+            funding: 2.5,
+            trades: 1,
+        }]);
+    }
+}
@@ -0,0 +1,118 @@
+//! Drawdown Throttle
+//!
+//! Scales the target size down as drawdown approaches `max_drawdown_pct`,
+//! instead of trading full size right up to the pause
+//! (`risk.drawdown_throttle`):
+//! - The curve maps drawdown to a share of size, read linearly between its
+//!   points or as steps
+//! - The throttle holds the size of the worst drawdown seen; it is released
+//!   to the curve only once the drawdown has retraced `recovery_fraction`
+//!   of that worst, so size does not bounce back on the first uptick
+//!
+//! The risk manager publishes the drawdown every agent tick; the agent feeds
+//! it here and the adaptive sizer applies the multiplier.
+
+use serde::Serialize;
+
+use crate::config::{DrawdownThrottleConfig, ThrottleMode, ThrottlePoint};
+
+/// Share of size (0-1) at `drawdown_pct` on the curve
+pub fn curve_multiplier(points: &[ThrottlePoint], mode: ThrottleMode, drawdown_pct: f64) -> f64 {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return 1.0;
+    };
+    let size_pct = match mode {
+        ThrottleMode::Steps => points
+            .iter()
+            .take_while(|p| p.drawdown_pct <= drawdown_pct)
+            .last()
+            .map_or(100.0, |p| p.size_pct),
+        ThrottleMode::Linear => {
+            // The curve starts from full size at no drawdown
+            let origin = (first.drawdown_pct > 0.0).then_some(ThrottlePoint { drawdown_pct: 0.0, size_pct: 100.0 });
+            let curve: Vec<ThrottlePoint> = origin.into_iter().chain(points.iter().copied()).collect();
+            curve
+                .windows(2)
+                .find(|w| drawdown_pct <= w[1].drawdown_pct)
+                .map(|w| {
+                    let t = ((drawdown_pct - w[0].drawdown_pct) / (w[1].drawdown_pct - w[0].drawdown_pct)).max(0.0);
+                    w[0].size_pct + t * (w[1].size_pct - w[0].size_pct)
+                })
+                .unwrap_or(last.size_pct)
+        }
+    };
+    size_pct / 100.0
+}
+
+/// Throttle state, with the worst drawdown it is holding
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DrawdownThrottle {
+    /// Latest drawdown (%)
+    pub drawdown_pct: f64,
+    /// Worst drawdown since the throttle was last released (%)
+    pub worst_pct: f64,
+    /// Size multiplier in force (0-1)
+    pub multiplier: f64,
+}
+
+impl DrawdownThrottle {
+    pub fn new() -> Self {
+        Self { multiplier: 1.0, ..Default::default() }
+    }
+
+    /// Take a drawdown reading; returns the multiplier in force
+    pub fn observe(&mut self, config: &DrawdownThrottleConfig, drawdown_pct: f64) -> f64 {
+        self.drawdown_pct = drawdown_pct;
+        let recovered = drawdown_pct <= self.worst_pct * (1.0 - config.recovery_fraction);
+        if drawdown_pct > self.worst_pct || recovered {
+            self.worst_pct = drawdown_pct;
+        }
+        self.multiplier = curve_multiplier(&config.points, config.mode, self.worst_pct);
+        self.multiplier
+    }
+
+    /// Whether size is cut
+    pub fn is_throttled(&self) -> bool {
+        self.multiplier < 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curve_linear_and_steps() {
+        let config = DrawdownThrottleConfig::default();
+        let linear = |dd| curve_multiplier(&config.points, ThrottleMode::Linear, dd);
+        assert_eq!(linear(1.0), 1.0);
+        assert_eq!(linear(2.0), 1.0);
+        assert!((linear(2.5) - 0.75).abs() < 1e-9);
+        assert!((linear(3.5) - 0.375).abs() < 1e-9);
+        assert_eq!(linear(4.8), 0.25);
+
+        let steps = |dd| curve_multiplier(&config.points, ThrottleMode::Steps, dd);
+        assert_eq!(steps(2.9), 1.0);
+        assert_eq!(steps(3.0), 0.5);
+        assert_eq!(steps(3.9), 0.5);
+        assert_eq!(steps(4.5), 0.25);
+    }
+
+    #[test]
+    fn test_full_size_only_after_recovery() {
+        let config = DrawdownThrottleConfig { mode: ThrottleMode::Steps, ..Default::default() };
+        let mut throttle = DrawdownThrottle::new();
+        assert_eq!(throttle.observe(&config, 1.0), 1.0);
+        assert_eq!(throttle.observe(&config, 4.0), 0.25);
+
+        // Retracing less than half of the 4% keeps the worst size
+        assert_eq!(throttle.observe(&config, 3.0), 0.25);
+        assert_eq!(throttle.observe(&config, 2.1), 0.25);
+        assert!(throttle.is_throttled());
+
+        // Back to 2%: released to the curve, which is full size there
+        assert_eq!(throttle.observe(&config, 2.0), 1.0);
+        assert_eq!(throttle.worst_pct, 2.0);
+        assert!(!throttle.is_throttled());
+    }
+}
@@ -0,0 +1,179 @@
+//! Daily Equity Curve
+//!
+//! Risk-adjusted returns computed per trade assume a trade rate and ignore
+//! how long capital sat in each trade. Instead, equity is rebuilt per UTC
+//! day from trade close times and marked to market on the last day:
+//! - Sharpe and Sortino from daily returns, annualized over 365 days
+//! - Max drawdown from the daily equity peak
+//! - Calmar: annualized daily return over max drawdown
+//!
+//! Days without closes carry the previous equity forward (a zero return).
+
+use serde::{Deserialize, Serialize};
+
+use super::performance_db::TradeOutcome;
+
+/// Milliseconds per day
+const DAY_MS: i64 = 86_400_000;
+
+/// Crypto markets trade every day
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Longest curve built (days); older history is dropped
+const MAX_DAYS: i64 = 10 * 365;
+
+/// Unrealized P&L of open positions at a point in time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenMark {
+    /// Mark time (ms)
+    pub timestamp: i64,
+    /// Unrealized P&L (USD)
+    pub unrealized_pnl: f64,
+}
+
+/// Equity at the end of one UTC day
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DailyEquity {
+    /// Start of the day (ms)
+    pub day: i64,
+    /// Equity at the end of the day (USD)
+    pub equity: f64,
+}
+
+/// Risk-adjusted statistics of an equity curve
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CurveStats {
+    /// Annualized Sharpe ratio of daily returns
+    pub sharpe_ratio: f64,
+    /// Annualized Sortino ratio of daily returns (0 without losing days)
+    pub sortino_ratio: f64,
+    /// Annualized return over max drawdown (0 without a drawdown)
+    pub calmar_ratio: f64,
+    /// Largest fall from an equity peak (%)
+    pub max_drawdown_pct: f64,
+    /// Days on the curve
+    pub days: u32,
+}
+
+/// Build end-of-day equity from `base` capital, realized P&L on the day each
+/// trade closed, and the open positions' mark on the final day.
+///
+/// The curve starts the day before the first trade opened, at `base`.
+pub fn build<'a>(
+    trades: impl IntoIterator<Item = &'a TradeOutcome>,
+    base: f64,
+    mark: Option<OpenMark>,
+) -> Vec<DailyEquity> {
+    let mut first_open = None::<i64>;
+    let mut closes: Vec<(i64, f64)> = Vec::new();
+    for trade in trades {
+        let open_day = trade.open_time.div_euclid(DAY_MS);
+        first_open = Some(first_open.map_or(open_day, |d| d.min(open_day)));
+        closes.push((trade.close_time.div_euclid(DAY_MS), trade.total_pnl));
+    }
+    let Some(first) = first_open else {
+        return Vec::new();
+    };
+    closes.sort_by_key(|(day, _)| *day);
+
+    let last_close = closes.last().map(|(day, _)| *day).unwrap_or(first);
+    let last = mark.map_or(last_close, |m| m.timestamp.div_euclid(DAY_MS).max(last_close));
+    let start = (first - 1).max(last - MAX_DAYS);
+
+    let mut curve = Vec::with_capacity((last - start + 1) as usize);
+    let mut realized = 0.0_f64;
+    let mut pending = closes.iter().peekable();
+    for day in start..=last {
+        while let Some((_, pnl)) = pending.next_if(|(d, _)| *d <= day) {
+            realized += pnl;
+        }
+        let open = if day == last { mark.map_or(0.0, |m| m.unrealized_pnl) } else { 0.0 };
+        curve.push(DailyEquity { day: day * DAY_MS, equity: base + realized + open });
+    }
+    curve
+}
+
+/// Sharpe, Sortino, Calmar and max drawdown of a daily equity curve
+pub fn stats(curve: &[DailyEquity]) -> CurveStats {
+    let returns: Vec<f64> = curve
+        .windows(2)
+        .filter(|w| w[0].equity > 0.0)
+        .map(|w| w[1].equity / w[0].equity - 1.0)
+        .collect();
+    let max_drawdown_pct = max_drawdown_pct(curve);
+    let mut stats = CurveStats { max_drawdown_pct, days: curve.len() as u32, ..Default::default() };
+    if returns.len() < 2 {
+        return stats;
+    }
+
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let std_dev = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let downside = (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / n).sqrt();
+    let annualizer = DAYS_PER_YEAR.sqrt();
+
+    if std_dev > 0.0 {
+        stats.sharpe_ratio = mean / std_dev * annualizer;
+    }
+    if downside > 0.0 {
+        stats.sortino_ratio = mean / downside * annualizer;
+    }
+    if max_drawdown_pct > 0.0 {
+        stats.calmar_ratio = mean * DAYS_PER_YEAR * 100.0 / max_drawdown_pct;
+    }
+    stats
+}
+
+/// Largest fall from a running equity peak (%)
+fn max_drawdown_pct(curve: &[DailyEquity]) -> f64 {
+    let mut peak = f64::MIN;
+    let mut max_dd = 0.0_f64;
+    for point in curve {
+        peak = peak.max(point.equity);
+        if peak > 0.0 {
+            max_dd = max_dd.max((peak - point.equity) / peak * 100.0);
+        }
+    }
+    max_dd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(close_day: i64, pnl: f64) -> TradeOutcome {
+        TradeOutcome {
+            open_time: close_day * DAY_MS,
+            close_time: close_day * DAY_MS + 3_600_000,
+            total_pnl: pnl,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_curve_is_daily_and_marked_to_market() {
+        let trades = vec![trade(10, 100.0), trade(13, -50.0)];
+        let mark = OpenMark { timestamp: 15 * DAY_MS + 1, unrealized_pnl: 25.0 };
+        let curve = build(&trades, 10_000.0, Some(mark));
+
+        // Day 9 (base) through day 15, idle days carried forward
+        assert_eq!(curve.len(), 7);
+        assert_eq!(curve[0].equity, 10_000.0);
+        assert_eq!(curve[1].equity, 10_100.0);
+        assert_eq!(curve[3].equity, 10_100.0);
+        assert_eq!(curve[4].equity, 10_050.0);
+        assert_eq!(curve[6].equity, 10_075.0);
+
+        let stats = stats(&curve);
+        assert!(stats.sharpe_ratio > 0.0);
+        assert!(stats.sortino_ratio > stats.sharpe_ratio);
+        assert!((stats.max_drawdown_pct - 50.0 / 10_100.0 * 100.0).abs() < 1e-9);
+        assert!(stats.calmar_ratio > 0.0);
+    }
+
+    #[test]
+    fn test_stats_need_two_returns() {
+        assert_eq!(stats(&build(&[trade(3, 10.0)], 1_000.0, None)).sharpe_ratio, 0.0);
+        assert!(build(&[], 1_000.0, None).is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+//! Trade-Frequency Governor
+//!
+//! Over-trading shows up as a falling realized edge: the thresholds let in
+//! setups that no longer pay after fees. After every close the governor
+//! (`signals.frequency_governor`) compares the mean net ROI of the last
+//! `recent_trades` with the last `baseline_trades`:
+//! - Deteriorating (recent edge `decay_margin_pct` below the baseline, or
+//!   below `min_edge_pct`): one level up, up to `max_level`
+//! - Recovered (recent edge at or above the baseline and `min_edge_pct`):
+//!   one level down
+//!
+//! Each level multiplies the effective basis and funding entry thresholds by
+//! another `1 + step_pct`, within the threshold learner's bounds. Only the
+//! last `baseline_trades` closes are read per step, and the level is rebuilt
+//! on startup by replaying the last `baseline_trades` closes, so it needs no
+//! storage of its own.
+
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::performance_db::{PerformanceDb, TradeOutcome};
+use crate::config::{AppConfig, FrequencyGovernorConfig};
+use crate::state::SharedState;
+use crate::telemetry::record_frequency_governor;
+use crate::utils::types::GovernorStatus;
+
+/// Mean net ROI of the last `n` trades (%)
+fn edge(trades: &[TradeOutcome], n: usize) -> f64 {
+    let recent = &trades[trades.len().saturating_sub(n)..];
+    recent.iter().map(|t| t.roi_pct).sum::<f64>() / recent.len() as f64
+}
+
+/// Governor after the close of the last of `trades` (oldest first)
+pub fn step(config: &FrequencyGovernorConfig, status: GovernorStatus, trades: &[TradeOutcome]) -> GovernorStatus {
+    if trades.len() < config.baseline_trades {
+        return status;
+    }
+    let recent = edge(trades, config.recent_trades);
+    let baseline = edge(trades, config.baseline_trades);
+    let level = if recent < baseline - config.decay_margin_pct || recent < config.min_edge_pct {
+        (status.level + 1).min(config.max_level)
+    } else if recent >= baseline {
+        status.level.saturating_sub(1)
+    } else {
+        status.level
+    };
+    GovernorStatus {
+        level,
+        multiplier: (1.0 + config.step_pct / 100.0).powi(level as i32),
+        recent_edge_pct: Some(recent),
+        baseline_edge_pct: Some(baseline),
+    }
+}
+
+/// Governor after every close in `trades`
+pub fn replay(config: &FrequencyGovernorConfig, trades: &[TradeOutcome]) -> GovernorStatus {
+    (1..=trades.len()).fold(GovernorStatus::default(), |status, n| step(config, status, &trades[..n]))
+}
+
+/// Keeps the governor reading in the shared state for the signal engine
+pub struct FrequencyGovernor {
+    config: Arc<AppConfig>,
+    state: Arc<SharedState>,
+    performance_db: Arc<PerformanceDb>,
+}
+
+impl FrequencyGovernor {
+    pub fn new(config: Arc<AppConfig>, state: Arc<SharedState>, performance_db: Arc<PerformanceDb>) -> Self {
+        Self { config, state, performance_db }
+    }
+
+    /// Latest reading, None while disabled
+    pub fn status(&self) -> Option<GovernorStatus> {
+        *self.state.frequency_governor.read()
+    }
+
+    /// The last `n` closed trades, oldest first
+    async fn last_trades(&self, n: usize) -> Vec<TradeOutcome> {
+        let mut trades = self.performance_db.get_recent_trades(n).await;
+        trades.reverse();
+        trades
+    }
+
+    /// Rebuild the level by replaying the last `baseline_trades` closes
+    pub async fn restore(&self) {
+        let governor = &self.config.signals.frequency_governor;
+        if !governor.enabled {
+            return;
+        }
+        // Each replayed close needs its own baseline window behind it
+        let window = (2 * governor.baseline_trades).saturating_sub(1);
+        let status = replay(governor, &self.last_trades(window).await);
+        if status.level > 0 {
+            info!("Trade-frequency governor at level {} ({:.2}x entry thresholds)", status.level, status.multiplier);
+        }
+        record_frequency_governor(status.level, status.multiplier);
+        *self.state.frequency_governor.write() = Some(status);
+    }
+
+    /// Step the governor after a close
+    pub async fn observe(&self) -> GovernorStatus {
+        let governor = &self.config.signals.frequency_governor;
+        let before = self.state.frequency_governor.read().unwrap_or_default();
+        if !governor.enabled {
+            return before;
+        }
+        let status = step(governor, before, &self.last_trades(governor.baseline_trades).await);
+        if status.level > before.level {
+            warn!(
+                "Realized edge deteriorating ({:.2}% vs {:.2}% baseline): entry thresholds {:.2}x",
+                status.recent_edge_pct.unwrap_or(0.0),
+                status.baseline_edge_pct.unwrap_or(0.0),
+                status.multiplier
+            );
+        } else if status.level < before.level {
+            info!(
+                "Realized edge recovering ({:.2}% vs {:.2}% baseline): entry thresholds {:.2}x",
+                status.recent_edge_pct.unwrap_or(0.0),
+                status.baseline_edge_pct.unwrap_or(0.0),
+                status.multiplier
+            );
+        }
+        record_frequency_governor(status.level, status.multiplier);
+        *self.state.frequency_governor.write() = Some(status);
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trades(rois: &[f64]) -> Vec<TradeOutcome> {
+        rois.iter().map(|&roi_pct| TradeOutcome { roi_pct, ..Default::default() }).collect()
+    }
+
+    #[test]
+    fn test_governor_tightens_on_decay_and_relaxes_on_recovery() {
+        let config = FrequencyGovernorConfig { enabled: true, recent_trades: 2, baseline_trades: 6, ..Default::default() };
+
+        // Not enough history for a baseline
+        assert_eq!(replay(&config, &trades(&[0.5; 5])).level, 0);
+
+        // Edge falls from 0.5% to 0.1%, then goes negative: two levels up
+        let mut history = vec![0.5; 6];
+        history.extend([0.1, -0.2, -0.2]);
+        let status = replay(&config, &trades(&history));
+        assert_eq!(status.level, 2);
+        assert!((status.multiplier - 1.21).abs() < 1e-9);
+        assert!((status.recent_edge_pct.unwrap() + 0.2).abs() < 1e-9);
+
+        // A strong trade lifts the recent edge over the baseline: one level down
+        history.push(0.9);
+        let status = replay(&config, &trades(&history));
+        assert_eq!(status.level, 1);
+        assert!(status.recent_edge_pct.unwrap() >= status.baseline_edge_pct.unwrap());
+    }
+}
@@ -0,0 +1,122 @@
+//! Reversal Hedge Tilt
+//!
+//! With `agentic.high_reversal_action: tilt`, a High funding reversal buys
+//! back `reversal_tilt_pct` of the perp short instead of only warning. The
+//! short pays funding once it flips negative, so a smaller short pays less,
+//! at the price of a net long exposure until the trade closes.
+//!
+//! Each tilt is kept on the trade with what it did to P&L against holding
+//! the full hedge:
+//! - Funding the bought-back short would have collected (positive) or paid
+//!   (negative), accrued like the trade's own funding
+//! - Price P&L of the bought-back short: long exposure from the tilt to the
+//!   exit
+//!
+//! Closed trades carry their tilt, so the policy can be judged by
+//! `PerformanceDb::get_hedge_tilt_performance`.
+
+use serde::{Deserialize, Serialize};
+
+use super::reversal_detector::ReversalSeverity;
+
+/// A hedge tilt on an open trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HedgeTilt {
+    /// When the tilt filled (ms)
+    pub timestamp: i64,
+    /// Reversal severity that triggered it
+    pub severity: ReversalSeverity,
+    /// Funding APR at the tilt (%)
+    pub funding_apr: f64,
+    /// Perp short bought back (SOL)
+    pub perp_delta_sol: f64,
+    /// Perp mark at the tilt
+    pub perp_price: f64,
+    /// Funding the bought-back short would have collected since (USD)
+    pub funding_forgone: f64,
+    /// Price P&L of the bought-back short to the exit (USD); set on close
+    pub price_pnl: f64,
+}
+
+impl HedgeTilt {
+    pub fn new(timestamp: i64, severity: ReversalSeverity, funding_apr: f64, perp_delta_sol: f64, perp_price: f64) -> Self {
+        Self {
+            timestamp,
+            severity,
+            funding_apr,
+            perp_delta_sol,
+            perp_price,
+            funding_forgone: 0.0,
+            price_pnl: 0.0,
+        }
+    }
+
+    /// Accrue funding on the bought-back short, as the trade accrues its own
+    pub fn accrue_funding(&mut self, funding_rate: f64, price: f64) {
+        self.funding_forgone += funding_rate * self.perp_delta_sol * price;
+    }
+
+    /// Settle the long exposure at the exit perp price
+    pub fn close(&mut self, exit_perp: f64) {
+        self.price_pnl = (exit_perp - self.perp_price) * self.perp_delta_sol;
+    }
+
+    /// P&L of tilting versus holding the full hedge (USD, positive = the tilt helped)
+    pub fn pnl_impact(&self) -> f64 {
+        self.price_pnl - self.funding_forgone
+    }
+}
+
+/// How tilts on closed trades worked out
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HedgeTiltPerformance {
+    /// Closed trades that were tilted
+    pub tilts: u32,
+    /// Tilts that beat holding the full hedge
+    pub helped: u32,
+    /// Funding not paid (negative) or forgone (positive), summed (USD)
+    pub funding_forgone: f64,
+    /// Price P&L of the tilts, summed (USD)
+    pub price_pnl: f64,
+    /// Net P&L impact, summed (USD)
+    pub net_impact: f64,
+}
+
+impl HedgeTiltPerformance {
+    pub fn from_tilts<'a>(tilts: impl IntoIterator<Item = &'a HedgeTilt>) -> Self {
+        tilts.into_iter().fold(Self::default(), |mut perf, tilt| {
+            perf.tilts += 1;
+            perf.helped += (tilt.pnl_impact() > 0.0) as u32;
+            perf.funding_forgone += tilt.funding_forgone;
+            perf.price_pnl += tilt.price_pnl;
+            perf.net_impact += tilt.pnl_impact();
+            perf
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tilt_pnl_impact() {
+        // Buy back 2.5 SOL of the short at $150
+        let mut tilt = HedgeTilt::new(0, ReversalSeverity::High, 12.0, 2.5, 150.0);
+        // Funding flips negative: the short would have paid it
+        tilt.accrue_funding(-0.0002, 150.0);
+        tilt.accrue_funding(-0.0002, 150.0);
+        assert!((tilt.funding_forgone + 0.15).abs() < 1e-9);
+
+        // Price falls $2 before the exit: the long exposure loses $5
+        tilt.close(148.0);
+        assert!((tilt.price_pnl + 5.0).abs() < 1e-9);
+        assert!((tilt.pnl_impact() + 4.85).abs() < 1e-9);
+
+        let mut helped = HedgeTilt::new(0, ReversalSeverity::High, 12.0, 1.0, 150.0);
+        helped.close(151.0);
+        let perf = HedgeTiltPerformance::from_tilts([&tilt, &helped]);
+        assert_eq!((perf.tilts, perf.helped), (2, 1));
+        assert!((perf.net_impact + 3.85).abs() < 1e-9);
+    }
+}
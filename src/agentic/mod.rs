@@ -2,13 +2,68 @@
 //!
 //! Self-learning and adaptive features:
 //! - Performance database (SQLite trade logging)
-//! - Adaptive position sizing (Kelly criterion)
-//! - Funding reversal detection
+//! - Daily equity curve and risk-adjusted returns
+//! - P&L attribution (carry vs convergence vs directional vs costs)
+//! - Adaptive position sizing (Kelly criterion, capped by available capital)
+//!   with an auditable derivation of each size
+//! - Compounding of realized P&L into the max position size
+//! - Confidence calibration (predicted vs realized win rate)
+//! - Monte Carlo risk of ruin at the current Kelly fraction
+//! - Idle-capital and spot-leg yield accrual
+//! - Capital allocation across markets by realized Sharpe
+//! - Funding reversal detection, and hedge tilts on High reversals
+//! - Basis regime detection (mean-reverting vs trending)
+//! - Realized volatility monitoring, for de-risking in violent markets
+//! - Post-trade review queue for anomalous closed trades
+//! - Hypothetical trades for blocked signals, to measure filter costs
+//! - Session report on shutdown
+//! - Drawdown throttle: size cut along a curve as drawdown nears its limit
+//! - Online entry threshold learning within hard bounds, with an audit log
+//!   and rollback
+//! - Trade-frequency governor: entry thresholds raised while realized edge
+//!   deteriorates
+//! - Persisted daily P&L ledger, with day boundaries in a configured timezone
 
 pub mod performance_db;
+pub mod attribution;
+pub mod equity_curve;
 pub mod adaptive_sizing;
+pub mod calibration;
+pub mod sizing_report;
+pub mod compounding;
+pub mod risk_of_ruin;
+pub mod idle_yield;
+pub mod capital_allocator;
 pub mod reversal_detector;
+pub mod hedge_tilt;
+pub mod regime_detector;
+pub mod volatility_monitor;
+pub mod trade_review;
+pub mod skipped_trades;
+pub mod session_report;
+pub mod threshold_learner;
+pub mod drawdown_throttle;
+pub mod frequency_governor;
+pub mod daily_ledger;
 
-pub use performance_db::{PerformanceDb, TradeOutcome, PerformanceMetrics};
+pub use performance_db::{PerformanceDb, TradeOutcome, PerformanceMetrics, MarketPerformance, CloseReasonPerformance, EntryTimePerformance, RiskState, WindowMetrics, DEFAULT_MARKET};
+pub use attribution::PnlAttribution;
+pub use equity_curve::{CurveStats, DailyEquity, OpenMark};
 pub use adaptive_sizing::{AdaptiveSizer, SizingRecommendation};
+pub use calibration::{CalibrationBucket, CalibrationReport, ConfidenceCalibrator};
+pub use sizing_report::{KellyDerivation, SizingReport, SizingStep, StepKind};
+pub use compounding::CompoundingState;
+pub use risk_of_ruin::RiskOfRuinEstimate;
+pub use idle_yield::YieldAccrual;
+pub use capital_allocator::{Allocation, CapitalAllocator};
 pub use reversal_detector::{ReversalDetector, ReversalAlert, ReversalSeverity};
+pub use hedge_tilt::{HedgeTilt, HedgeTiltPerformance};
+pub use regime_detector::{RegimeDetector, RegimeAnalysis, MarketRegime};
+pub use volatility_monitor::VolatilityMonitor;
+pub use trade_review::{ReviewFlag, ReviewItem, TradeReviewer};
+pub use skipped_trades::{SkipSummary, SkippedTrade, SkippedTradeTracker};
+pub use session_report::{SessionData, SessionEvent, SessionLog};
+pub use drawdown_throttle::DrawdownThrottle;
+pub use frequency_governor::FrequencyGovernor;
+pub use daily_ledger::{DailyLedger, DailyLedgerEntry};
+pub use threshold_learner::{ThresholdAction, ThresholdAdjustment, ThresholdLearner};
@@ -2,17 +2,49 @@
 //!
 //! SQLite-backed trade logging and metrics calculation:
 //! - Stores all trade outcomes persistently
-//! - Calculates win rate, Sharpe ratio, profit factor
-//! - Tracks performance by market conditions
+//! - Calculates win rate, profit factor, and Sharpe, Sortino and Calmar
+//!   ratios from a daily equity curve
+//! - Tracks performance by market conditions and by close reason
+//! - Tracks performance by UTC entry hour and weekday, fed back to the
+//!   signal engine as an entry-time preference
+//! - Attributes P&L to convergence, funding, directional exposure and costs
+//! - Estimates risk of ruin by bootstrapping trade returns
+//! - Accrues idle-capital and spot-leg yield, for returns on capital
+//! - Persists risk counters (high-water mark, daily P&L) and the daily P&L
+//!   ledger across restarts
+//! - Keeps an append-only audit log of agent state transitions
+//! - Logs resolved hypothetical trades for blocked signals
+//! - Logs profit sweeps to the cold wallet
+//! - Logs learned entry threshold adjustments and rollbacks
+//! - Logs operator risk limit overrides, their clearing and expiry
 //! - Enables learning from historical performance
 
 use anyhow::{Context, Result};
+use chrono::{Datelike, Timelike};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
+use crate::agent::risk_overrides::RiskOverrideEvent;
+use crate::agent::state_machine::StateTransition;
+use crate::config::{AppConfig, TreasuryConfig};
+use crate::execution::{InventoryReport, SweepRecord};
+use crate::utils::types::{CloseReason, ExpectedValue};
+
+use super::attribution::PnlAttribution;
+use super::compounding::CompoundingState;
+use super::daily_ledger::DailyLedger;
+use super::equity_curve::{self, CurveStats, DailyEquity, OpenMark};
+use super::hedge_tilt::{HedgeTilt, HedgeTiltPerformance};
+use super::idle_yield::YieldAccrual;
+use super::sizing_report::SizingReport;
+use super::skipped_trades::SkippedTrade;
+use super::threshold_learner::ThresholdAdjustment;
+use super::risk_of_ruin::{self, RiskOfRuinEstimate, RuinParams, HORIZON_DAYS, MAX_PATHS, MAX_TRADES_PER_PATH};
+
 /// Trade outcome record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeOutcome {
@@ -44,7 +76,7 @@ pub struct TradeOutcome {
     pub spot_pnl: f64,
     /// Perp P&L
     pub perp_pnl: f64,
-    /// Total P&L (spot + perp + funding)
+    /// Total P&L (spot + perp + funding - fees)
     pub total_pnl: f64,
     /// Return on capital (%)
     pub roi_pct: f64,
@@ -52,10 +84,54 @@ pub struct TradeOutcome {
     pub hold_hours: f64,
     /// Whether trade was profitable
     pub is_winner: bool,
-    /// Close reason
-    pub close_reason: String,
+    /// What triggered the close
+    pub close_reason: CloseReason,
     /// Confidence score at entry
     pub entry_confidence: f64,
+    /// Fees paid across both legs (USD)
+    #[serde(default)]
+    pub fees_paid: f64,
+    /// Slippage versus quoted prices across both legs (USD, positive = cost)
+    #[serde(default)]
+    pub slippage_cost: f64,
+    /// Market / strategy the trade belongs to
+    #[serde(default = "default_market")]
+    pub market: String,
+    /// How the entry was sized
+    #[serde(default)]
+    pub sizing: Option<SizingReport>,
+    /// Expected value estimated at entry, to validate against the outcome
+    #[serde(default)]
+    pub expected_value: Option<ExpectedValue>,
+    /// Hedge tilt on a High funding reversal, with its P&L impact
+    #[serde(default)]
+    pub hedge_tilt: Option<HedgeTilt>,
+}
+
+/// Market label for trades recorded before markets were tracked
+pub const DEFAULT_MARKET: &str = "SOL-PERP";
+
+fn default_market() -> String { DEFAULT_MARKET.to_string() }
+
+/// Milliseconds per day
+const DAY_MS: i64 = 86_400_000;
+
+/// Minimum time between yield accrual writes (ms)
+const YIELD_PERSIST_INTERVAL_MS: i64 = 60_000;
+
+/// Net P&L over the capital it was earned on (today's capital less that P&L)
+fn return_on_capital_pct(net_pnl: f64, capital_usd: f64) -> f64 {
+    let base = capital_usd - net_pnl;
+    if capital_usd > 0.0 && base > 0.0 { net_pnl / base * 100.0 } else { 0.0 }
+}
+
+/// Fill the capital and yield metrics from `metrics.capital_usd` and the accrual
+fn apply_capital(metrics: &mut PerformanceMetrics, accrual: &YieldAccrual) {
+    metrics.return_on_capital_pct = return_on_capital_pct(metrics.net_pnl, metrics.capital_usd);
+    metrics.idle_yield_usd = accrual.idle_yield_usd;
+    metrics.spot_yield_usd = accrual.spot_yield_usd;
+    metrics.annualized_return_pct = accrual.annualized_return_pct(metrics.net_pnl);
+    metrics.hold_apr_pct = accrual.idle_apr_pct;
 }
 
 /// Performance metrics
@@ -85,9 +161,15 @@ pub struct PerformanceMetrics {
     pub expectancy: f64,
     /// Average hold time (hours)
     pub avg_hold_hours: f64,
-    /// Sharpe ratio (annualized)
+    /// Sharpe ratio of daily equity returns (annualized)
     pub sharpe_ratio: f64,
-    /// Maximum drawdown (%)
+    /// Sortino ratio of daily equity returns (annualized)
+    #[serde(default)]
+    pub sortino_ratio: f64,
+    /// Annualized daily return over max drawdown
+    #[serde(default)]
+    pub calmar_ratio: f64,
+    /// Maximum drawdown of daily equity (%)
     pub max_drawdown_pct: f64,
     /// Average ROI per trade (%)
     pub avg_roi_pct: f64,
@@ -101,6 +183,102 @@ pub struct PerformanceMetrics {
     pub longest_win_streak: u32,
     /// Longest loss streak
     pub longest_loss_streak: u32,
+    /// Aggregate P&L attribution across all trades
+    #[serde(default)]
+    pub attribution: PnlAttribution,
+    /// Monte Carlo risk estimate at the last sizing recalculation
+    #[serde(default)]
+    pub risk_of_ruin: Option<RiskOfRuinEstimate>,
+    /// Total capital at the last inventory report (USD)
+    #[serde(default)]
+    pub capital_usd: f64,
+    /// Net P&L over the capital it was earned on (%)
+    #[serde(default)]
+    pub return_on_capital_pct: f64,
+    /// Yield accrued on idle capital (USD)
+    #[serde(default)]
+    pub idle_yield_usd: f64,
+    /// Yield accrued on the spot leg (USD)
+    #[serde(default)]
+    pub spot_yield_usd: f64,
+    /// Trade P&L plus yield, annualized on the capital employed (%)
+    #[serde(default)]
+    pub annualized_return_pct: f64,
+    /// Return from simply holding the capital at the idle yield (APR %)
+    #[serde(default)]
+    pub hold_apr_pct: f64,
+    /// Metrics over the configured rolling windows, shortest first
+    #[serde(default)]
+    pub windows: Vec<WindowMetrics>,
+}
+
+/// Metrics over trades closed in the last `days`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowMetrics {
+    /// Window length (days)
+    pub days: u32,
+    pub trades: u32,
+    /// Win rate (0-1)
+    pub win_rate: f64,
+    /// Gross profit over gross loss
+    pub profit_factor: f64,
+    /// Sharpe ratio of daily equity returns in the window (annualized)
+    pub sharpe_ratio: f64,
+    pub avg_win: f64,
+    pub avg_loss: f64,
+    pub net_pnl: f64,
+}
+
+impl WindowMetrics {
+    /// Metrics over trades closed in the `days` before `end`, with equity
+    /// starting from `base` before the first trade
+    fn compute(trades: &[TradeOutcome], days: u32, end: i64, base: f64) -> Self {
+        let start = end - days as i64 * DAY_MS;
+        let earlier: f64 = trades.iter().filter(|t| t.close_time <= start).map(|t| t.total_pnl).sum();
+        let recent: Vec<&TradeOutcome> = trades.iter().filter(|t| t.close_time > start).collect();
+        if recent.is_empty() {
+            return Self { days, ..Default::default() };
+        }
+
+        let winners = recent.iter().filter(|t| t.is_winner).count();
+        let gross_profit: f64 = recent.iter().filter(|t| t.total_pnl > 0.0).map(|t| t.total_pnl).sum();
+        let gross_loss: f64 = recent.iter().filter(|t| t.total_pnl < 0.0).map(|t| -t.total_pnl).sum();
+        let losers = recent.iter().filter(|t| t.total_pnl < 0.0).count();
+        let profitable = recent.iter().filter(|t| t.total_pnl > 0.0).count();
+        let curve = equity_curve::build(recent.iter().copied(), base + earlier, None);
+
+        Self {
+            days,
+            trades: recent.len() as u32,
+            win_rate: winners as f64 / recent.len() as f64,
+            profit_factor: if gross_loss > 0.0 {
+                gross_profit / gross_loss
+            } else if gross_profit > 0.0 {
+                f64::INFINITY
+            } else {
+                0.0
+            },
+            sharpe_ratio: equity_curve::stats(&curve).sharpe_ratio,
+            avg_win: if profitable > 0 { gross_profit / profitable as f64 } else { 0.0 },
+            avg_loss: if losers > 0 { gross_loss / losers as f64 } else { 0.0 },
+            net_pnl: gross_profit - gross_loss,
+        }
+    }
+}
+
+/// Risk counters that must survive restarts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RiskState {
+    /// Equity high-water mark (USD)
+    pub peak_equity: f64,
+    /// Cumulative realized P&L the equity is measured from (USD)
+    pub realized_pnl: f64,
+    /// Realized P&L since `last_reset` (USD)
+    pub daily_pnl: f64,
+    /// Trades closed since `last_reset`
+    pub trades_today: u64,
+    /// Last daily reset (unix seconds)
+    pub last_reset: i64,
 }
 
 /// Performance database using simple file storage
@@ -108,6 +286,32 @@ pub struct PerformanceMetrics {
 pub struct PerformanceDb {
     /// Database file path
     db_path: String,
+    /// Risk state file, next to the database
+    risk_state_path: String,
+    /// State transition log (JSON lines), next to the database
+    transitions_path: String,
+    /// Resolved skipped trades (JSON lines), next to the database
+    skipped_path: String,
+    /// Yield accrual file, next to the database
+    yield_path: String,
+    /// Compounded position size, next to the database
+    compounding_path: String,
+    /// Profit sweep log (JSON lines), next to the database
+    sweeps_path: String,
+    /// Entry threshold adjustment log (JSON lines), next to the database
+    thresholds_path: String,
+    /// Daily P&L ledger, next to the database
+    ledger_path: String,
+    /// Risk limit override log (JSON lines), next to the database
+    overrides_path: String,
+    /// Idle and spot yield accrued so far
+    yield_accrual: Arc<RwLock<YieldAccrual>>,
+    /// Last yield accrual write (ms)
+    yield_saved_at: AtomicI64,
+    /// Latest mark of open positions, for the equity curve
+    open_mark: RwLock<Option<OpenMark>>,
+    /// Rolling metric windows (days)
+    window_days: Vec<u32>,
     /// In-memory trades cache
     trades: Arc<RwLock<Vec<TradeOutcome>>>,
     /// Cached metrics
@@ -125,8 +329,65 @@ impl PerformanceDb {
             Vec::new()
         };
         
+        let risk_state_path = Path::new(db_path)
+            .with_file_name("risk_state.json")
+            .to_string_lossy()
+            .into_owned();
+        let transitions_path = Path::new(db_path)
+            .with_file_name("transitions.jsonl")
+            .to_string_lossy()
+            .into_owned();
+        let skipped_path = Path::new(db_path)
+            .with_file_name("skipped_trades.jsonl")
+            .to_string_lossy()
+            .into_owned();
+        let yield_path = Path::new(db_path)
+            .with_file_name("idle_yield.json")
+            .to_string_lossy()
+            .into_owned();
+        let compounding_path = Path::new(db_path)
+            .with_file_name("compounding.json")
+            .to_string_lossy()
+            .into_owned();
+        let sweeps_path = Path::new(db_path)
+            .with_file_name("sweeps.jsonl")
+            .to_string_lossy()
+            .into_owned();
+        let thresholds_path = Path::new(db_path)
+            .with_file_name("threshold_adjustments.jsonl")
+            .to_string_lossy()
+            .into_owned();
+        let ledger_path = Path::new(db_path)
+            .with_file_name("daily_ledger.json")
+            .to_string_lossy()
+            .into_owned();
+        let overrides_path = Path::new(db_path)
+            .with_file_name("risk_overrides.jsonl")
+            .to_string_lossy()
+            .into_owned();
+        let yield_accrual = match tokio::fs::read_to_string(&yield_path).await {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable yield accrual: {}", e);
+                YieldAccrual::default()
+            }),
+            Err(_) => YieldAccrual::default(),
+        };
+        
         let db = Self {
             db_path: db_path.to_string(),
+            risk_state_path,
+            transitions_path,
+            skipped_path,
+            yield_path,
+            compounding_path,
+            sweeps_path,
+            thresholds_path,
+            ledger_path,
+            overrides_path,
+            yield_accrual: Arc::new(RwLock::new(yield_accrual)),
+            yield_saved_at: AtomicI64::new(0),
+            open_mark: RwLock::new(None),
+            window_days: Vec::new(),
             trades: Arc::new(RwLock::new(trades)),
             metrics: Arc::new(RwLock::new(PerformanceMetrics::default())),
         };
@@ -139,6 +400,14 @@ impl PerformanceDb {
         Ok(db)
     }
     
+    /// Also report metrics over rolling windows of these lengths (days)
+    pub async fn with_metric_windows(mut self, days: &[u32]) -> Self {
+        self.window_days = days.to_vec();
+        self.window_days.sort_unstable();
+        self.recalculate_metrics().await;
+        self
+    }
+    
     /// Record a trade outcome
     pub async fn record_trade(&self, trade: TradeOutcome) -> Result<()> {
         {
@@ -173,12 +442,238 @@ impl PerformanceDb {
         Ok(())
     }
     
+    /// Load persisted risk counters, if any
+    pub async fn load_risk_state(&self) -> Result<Option<RiskState>> {
+        if !Path::new(&self.risk_state_path).exists() {
+            return Ok(None);
+        }
+        let content = tokio::fs::read_to_string(&self.risk_state_path).await
+            .context("Failed to read risk state")?;
+        let state = serde_json::from_str(&content).context("Failed to parse risk state")?;
+        Ok(Some(state))
+    }
+    
+    /// Persist risk counters
+    pub async fn save_risk_state(&self, state: &RiskState) -> Result<()> {
+        let content = serde_json::to_string_pretty(state)
+            .context("Failed to serialize risk state")?;
+        
+        // Write then rename, so a crash never leaves a truncated file
+        let tmp = format!("{}.tmp", self.risk_state_path);
+        tokio::fs::write(&tmp, content).await
+            .context("Failed to write risk state")?;
+        tokio::fs::rename(&tmp, &self.risk_state_path).await
+            .context("Failed to replace risk state")?;
+        Ok(())
+    }
+    
+    /// Load the daily P&L ledger, if one was written
+    pub async fn load_daily_ledger(&self) -> Result<Option<DailyLedger>> {
+        if !Path::new(&self.ledger_path).exists() {
+            return Ok(None);
+        }
+        let content = tokio::fs::read_to_string(&self.ledger_path).await
+            .context("Failed to read daily ledger")?;
+        let ledger = serde_json::from_str(&content).context("Failed to parse daily ledger")?;
+        Ok(Some(ledger))
+    }
+    
+    /// Persist the daily P&L ledger
+    pub async fn save_daily_ledger(&self, ledger: &DailyLedger) -> Result<()> {
+        let content = serde_json::to_string_pretty(ledger)
+            .context("Failed to serialize daily ledger")?;
+        let tmp = format!("{}.tmp", self.ledger_path);
+        tokio::fs::write(&tmp, content).await
+            .context("Failed to write daily ledger")?;
+        tokio::fs::rename(&tmp, &self.ledger_path).await
+            .context("Failed to replace daily ledger")?;
+        Ok(())
+    }
+    
+    /// Load the compounded position size, if any
+    pub async fn load_compounding(&self) -> Result<Option<CompoundingState>> {
+        if !Path::new(&self.compounding_path).exists() {
+            return Ok(None);
+        }
+        let content = tokio::fs::read_to_string(&self.compounding_path).await
+            .context("Failed to read compounding state")?;
+        let state = serde_json::from_str(&content).context("Failed to parse compounding state")?;
+        Ok(Some(state))
+    }
+    
+    /// Persist the compounded position size
+    pub async fn save_compounding(&self, state: &CompoundingState) -> Result<()> {
+        let content = serde_json::to_string_pretty(state)
+            .context("Failed to serialize compounding state")?;
+        let tmp = format!("{}.tmp", self.compounding_path);
+        tokio::fs::write(&tmp, content).await
+            .context("Failed to write compounding state")?;
+        tokio::fs::rename(&tmp, &self.compounding_path).await
+            .context("Failed to replace compounding state")?;
+        Ok(())
+    }
+    
+    /// Append a profit sweep to the sweep log
+    pub async fn record_sweep(&self, sweep: &SweepRecord) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+        
+        let mut line = serde_json::to_string(sweep)
+            .context("Failed to serialize profit sweep")?;
+        line.push('\n');
+        
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.sweeps_path)
+            .await
+            .context("Failed to open sweep log")?;
+        file.write_all(line.as_bytes()).await
+            .context("Failed to write sweep log")?;
+        Ok(())
+    }
+    
+    /// Append an entry threshold adjustment to its log
+    pub async fn record_threshold_adjustment(&self, adjustment: &ThresholdAdjustment) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+        
+        let mut line = serde_json::to_string(adjustment)
+            .context("Failed to serialize threshold adjustment")?;
+        line.push('\n');
+        
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.thresholds_path)
+            .await
+            .context("Failed to open threshold adjustment log")?;
+        file.write_all(line.as_bytes()).await
+            .context("Failed to write threshold adjustment log")?;
+        Ok(())
+    }
+    
+    /// All logged threshold adjustments, oldest first
+    pub async fn get_threshold_adjustments(&self) -> Result<Vec<ThresholdAdjustment>> {
+        let content = match tokio::fs::read_to_string(&self.thresholds_path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context("Failed to read threshold adjustment log"),
+        };
+        
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<ThresholdAdjustment>(line).ok())
+            .collect())
+    }
+    
+    /// Append a risk limit override event to its log
+    pub async fn record_risk_override(&self, event: &RiskOverrideEvent) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+        
+        let mut line = serde_json::to_string(event)
+            .context("Failed to serialize risk override")?;
+        line.push('\n');
+        
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.overrides_path)
+            .await
+            .context("Failed to open risk override log")?;
+        file.write_all(line.as_bytes()).await
+            .context("Failed to write risk override log")?;
+        Ok(())
+    }
+    
+    /// All logged risk override events, oldest first
+    pub async fn get_risk_overrides(&self) -> Result<Vec<RiskOverrideEvent>> {
+        let content = match tokio::fs::read_to_string(&self.overrides_path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context("Failed to read risk override log"),
+        };
+        
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<RiskOverrideEvent>(line).ok())
+            .collect())
+    }
+    
+    /// Append a state transition to the audit log
+    pub async fn record_transition(&self, transition: &StateTransition) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+        
+        let mut line = serde_json::to_string(transition)
+            .context("Failed to serialize state transition")?;
+        line.push('\n');
+        
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.transitions_path)
+            .await
+            .context("Failed to open transition log")?;
+        file.write_all(line.as_bytes()).await
+            .context("Failed to write transition log")?;
+        Ok(())
+    }
+    
+    /// Logged state transitions with `start <= timestamp <= end` (ms), oldest first
+    pub async fn get_transitions(&self, start: i64, end: i64) -> Result<Vec<StateTransition>> {
+        let content = match tokio::fs::read_to_string(&self.transitions_path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context("Failed to read transition log"),
+        };
+        
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<StateTransition>(line).ok())
+            .filter(|t| t.timestamp >= start && t.timestamp <= end)
+            .collect())
+    }
+    
+    /// Append a resolved skipped trade to its log
+    pub async fn record_skipped_trade(&self, trade: &SkippedTrade) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+        
+        let mut line = serde_json::to_string(trade)
+            .context("Failed to serialize skipped trade")?;
+        line.push('\n');
+        
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.skipped_path)
+            .await
+            .context("Failed to open skipped trade log")?;
+        file.write_all(line.as_bytes()).await
+            .context("Failed to write skipped trade log")?;
+        Ok(())
+    }
+    
+    /// All logged skipped trades, oldest first
+    pub async fn get_skipped_trades(&self) -> Result<Vec<SkippedTrade>> {
+        let content = match tokio::fs::read_to_string(&self.skipped_path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context("Failed to read skipped trade log"),
+        };
+        
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<SkippedTrade>(line).ok())
+            .collect())
+    }
+    
     /// Recalculate all metrics from trades
     async fn recalculate_metrics(&self) {
         let trades = self.trades.read().await;
         
         if trades.is_empty() {
-            *self.metrics.write().await = PerformanceMetrics::default();
+            let capital_usd = self.metrics.read().await.capital_usd;
+            let mut metrics = PerformanceMetrics { capital_usd, ..Default::default() };
+            apply_capital(&mut metrics, &*self.yield_accrual.read().await);
+            *self.metrics.write().await = metrics;
             return;
         }
         
@@ -236,17 +731,34 @@ impl PerformanceDb {
         let best_trade = trades.iter().map(|t| t.total_pnl).fold(f64::NEG_INFINITY, f64::max);
         let worst_trade = trades.iter().map(|t| t.total_pnl).fold(f64::INFINITY, f64::min);
         
-        // Calculate Sharpe ratio
-        let returns: Vec<f64> = trades.iter().map(|t| t.roi_pct / 100.0).collect();
-        let sharpe_ratio = Self::calculate_sharpe(&returns);
+        // Kept until the sizer re-runs the simulation
+        let (risk_of_ruin, capital_usd) = {
+            let metrics = self.metrics.read().await;
+            (metrics.risk_of_ruin, metrics.capital_usd)
+        };
+        
+        // Sharpe, Sortino, Calmar and drawdown from the daily equity curve
+        let base = Self::equity_base(&trades, net_pnl, capital_usd);
+        let curve = equity_curve::build(trades.iter(), base, *self.open_mark.read().await);
+        let CurveStats { sharpe_ratio, sortino_ratio, calmar_ratio, max_drawdown_pct, .. } =
+            equity_curve::stats(&curve);
         
-        // Calculate max drawdown
-        let max_drawdown_pct = Self::calculate_max_drawdown(&trades);
+        // Rolling windows end at the latest close or mark, not the wall clock
+        let end = trades.iter().map(|t| t.close_time)
+            .chain(self.open_mark.read().await.map(|m| m.timestamp))
+            .max()
+            .unwrap_or(0);
+        let windows = self.window_days.iter()
+            .map(|days| WindowMetrics::compute(&trades, *days, end, base))
+            .collect();
         
         // Calculate streaks
         let (current_streak, longest_win, longest_loss) = Self::calculate_streaks(&trades);
         
-        *self.metrics.write().await = PerformanceMetrics {
+        // Where the P&L actually came from
+        let attribution = PnlAttribution::aggregate(trades.iter());
+        
+        let mut metrics = PerformanceMetrics {
             total_trades,
             winning_trades,
             losing_trades,
@@ -260,6 +772,8 @@ impl PerformanceDb {
             expectancy,
             avg_hold_hours,
             sharpe_ratio,
+            sortino_ratio,
+            calmar_ratio,
             max_drawdown_pct,
             avg_roi_pct,
             best_trade,
@@ -267,56 +781,127 @@ impl PerformanceDb {
             current_streak,
             longest_win_streak: longest_win,
             longest_loss_streak: longest_loss,
+            attribution,
+            risk_of_ruin,
+            capital_usd,
+            windows,
+            ..Default::default()
         };
+        apply_capital(&mut metrics, &*self.yield_accrual.read().await);
+        *self.metrics.write().await = metrics;
     }
     
-    /// Calculate Sharpe ratio (annualized)
-    fn calculate_sharpe(returns: &[f64]) -> f64 {
-        if returns.len() < 2 {
-            return 0.0;
+    /// Take the current capital from the treasury's inventory report and
+    /// accrue idle and spot yield since the last report
+    pub async fn record_inventory(&self, inventory: &InventoryReport, config: &TreasuryConfig) {
+        let accrual = {
+            let mut accrual = self.yield_accrual.write().await;
+            accrual.accrue(
+                inventory.timestamp,
+                inventory.idle_usd,
+                inventory.deployed_usd,
+                config.idle_yield_apr_pct,
+                config.spot_yield_apr_pct,
+            );
+            *accrual
+        };
+        {
+            let mut metrics = self.metrics.write().await;
+            metrics.capital_usd = inventory.total_capital_usd;
+            apply_capital(&mut metrics, &accrual);
         }
         
-        let mean: f64 = returns.iter().sum::<f64>() / returns.len() as f64;
-        let variance: f64 = returns.iter()
-            .map(|r| (r - mean).powi(2))
-            .sum::<f64>() / returns.len() as f64;
-        let std_dev = variance.sqrt();
-        
-        if std_dev == 0.0 {
-            return 0.0;
+        if inventory.timestamp - self.yield_saved_at.load(Ordering::Relaxed) >= YIELD_PERSIST_INTERVAL_MS {
+            self.yield_saved_at.store(inventory.timestamp, Ordering::Relaxed);
+            if let Err(e) = self.save_yield(&accrual).await {
+                warn!("Failed to persist yield accrual: {}", e);
+            }
         }
-        
-        // Annualize assuming ~100 trades per year
-        let trades_per_year = 100.0;
-        (mean / std_dev) * trades_per_year.sqrt()
     }
     
-    /// Calculate maximum drawdown
-    fn calculate_max_drawdown(trades: &[TradeOutcome]) -> f64 {
-        if trades.is_empty() {
-            return 0.0;
+    /// Mark open positions to market, so the equity curve's last day
+    /// includes their unrealized P&L
+    pub async fn mark_to_market(&self, unrealized_pnl: f64, timestamp: i64) {
+        *self.open_mark.write().await = Some(OpenMark { timestamp, unrealized_pnl });
+        self.recalculate_metrics().await;
+    }
+    
+    /// End-of-day equity since the first trade
+    pub async fn get_daily_equity(&self) -> Vec<DailyEquity> {
+        let trades = self.trades.read().await;
+        let net_pnl = trades.iter().map(|t| t.total_pnl).sum();
+        let base = Self::equity_base(&trades, net_pnl, self.metrics.read().await.capital_usd);
+        equity_curve::build(trades.iter(), base, *self.open_mark.read().await)
+    }
+    
+    /// Capital the equity curve starts from: today's capital less the P&L
+    /// earned on it, or the largest position when capital is unknown
+    fn equity_base(trades: &[TradeOutcome], net_pnl: f64, capital_usd: f64) -> f64 {
+        let base = capital_usd - net_pnl;
+        if capital_usd > 0.0 && base > 0.0 {
+            base
+        } else {
+            trades.iter().map(|t| t.size * t.entry_spot).fold(0.0, f64::max)
         }
-        
-        let mut peak = 0.0_f64;
-        let mut max_dd = 0.0_f64;
-        let mut cumulative = 0.0;
-        
-        for trade in trades {
-            cumulative += trade.total_pnl;
-            if cumulative > peak {
-                peak = cumulative;
-            }
-            let drawdown = if peak > 0.0 {
-                (peak - cumulative) / peak * 100.0
-            } else {
-                0.0
+    }
+    
+    /// Persist the yield accrual
+    async fn save_yield(&self, accrual: &YieldAccrual) -> Result<()> {
+        let content = serde_json::to_string_pretty(accrual)
+            .context("Failed to serialize yield accrual")?;
+        let tmp = format!("{}.tmp", self.yield_path);
+        tokio::fs::write(&tmp, content).await
+            .context("Failed to write yield accrual")?;
+        tokio::fs::rename(&tmp, &self.yield_path).await
+            .context("Failed to replace yield accrual")?;
+        Ok(())
+    }
+    
+    /// Estimate risk of ruin over the next 30 days at a Kelly fraction.
+    ///
+    /// The trade rate is taken from history, so the horizon covers as many
+    /// trades as the bot would make in 30 days at its observed pace. Paths
+    /// and horizon are capped at `MAX_PATHS` and `MAX_TRADES_PER_PATH`, and
+    /// the simulation runs on the blocking pool from a copy of the returns.
+    pub async fn estimate_risk_of_ruin(
+        &self,
+        kelly_fraction: f64,
+        config: &AppConfig,
+    ) -> Option<RiskOfRuinEstimate> {
+        let (returns, params) = {
+            let trades = self.trades.read().await;
+            let first = trades.iter().map(|t| t.open_time).min()?;
+            let last = trades.iter().map(|t| t.close_time).max()?;
+            let days = ((last - first) as f64 / 86_400_000.0).max(1.0);
+            let trades_per_day = trades.len() as f64 / days;
+            
+            let returns: Vec<f64> = trades.iter().map(|t| t.roi_pct / 100.0).collect();
+            let params = RuinParams {
+                paths: config.agentic.monte_carlo_paths.min(MAX_PATHS),
+                trades_per_path: ((trades_per_day * HORIZON_DAYS).ceil() as usize).min(MAX_TRADES_PER_PATH),
+                ruin_threshold_pct: config.agentic.ruin_threshold_pct,
+                max_drawdown_pct: config.risk.max_drawdown_pct,
             };
-            if drawdown > max_dd {
-                max_dd = drawdown;
-            }
-        }
+            (returns, params)
+        };
+        let estimate = tokio::task::spawn_blocking(move || {
+            risk_of_ruin::simulate(&returns, kelly_fraction, &params, &mut rand::thread_rng())
+        })
+        .await
+        .ok()??;
+        
+        debug!(
+            "Risk of ruin at Kelly {:.1}%: ruin {:.2}%, drawdown breach {:.2}%, 30d P&L p5/p50/p95 {:.1}/{:.1}/{:.1}%",
+            kelly_fraction * 100.0,
+            estimate.risk_of_ruin_pct,
+            estimate.drawdown_breach_pct,
+            estimate.pnl_30d_p5_pct,
+            estimate.pnl_30d_p50_pct,
+            estimate.pnl_30d_p95_pct
+        );
         
-        max_dd
+        self.metrics.write().await.risk_of_ruin = Some(estimate);
+        Some(estimate)
     }
     
     /// Calculate win/loss streaks
@@ -408,6 +993,30 @@ impl PerformanceDb {
             .collect()
     }
     
+    /// Trade count, Sharpe and net P&L per market
+    pub async fn get_performance_by_market(&self) -> Vec<MarketPerformance> {
+        let trades = self.trades.read().await;
+        let mut by_market: std::collections::BTreeMap<&str, Vec<&TradeOutcome>> = Default::default();
+        for trade in trades.iter() {
+            by_market.entry(trade.market.as_str()).or_default().push(trade);
+        }
+        
+        by_market
+            .into_iter()
+            .map(|(market, trades)| {
+                let net_pnl = trades.iter().map(|t| t.total_pnl).sum();
+                let capital = trades.iter().map(|t| t.size * t.entry_spot).fold(0.0, f64::max);
+                let curve = equity_curve::build(trades.iter().copied(), capital, None);
+                MarketPerformance {
+                    market: market.to_string(),
+                    trades: trades.len() as u32,
+                    sharpe_ratio: equity_curve::stats(&curve).sharpe_ratio,
+                    net_pnl,
+                }
+            })
+            .collect()
+    }
+    
     /// Get performance by funding level
     pub async fn get_performance_by_funding(&self) -> FundingPerformance {
         let trades = self.trades.read().await;
@@ -432,6 +1041,45 @@ impl PerformanceDb {
         }
     }
     
+    /// Trade count, win rate and P&L per close reason
+    pub async fn get_performance_by_close_reason(&self) -> Vec<CloseReasonPerformance> {
+        let trades = self.trades.read().await;
+        let mut by_reason: std::collections::BTreeMap<CloseReason, Vec<&TradeOutcome>> = Default::default();
+        for trade in trades.iter() {
+            by_reason.entry(trade.close_reason).or_default().push(trade);
+        }
+        
+        by_reason
+            .into_iter()
+            .map(|(reason, trades)| CloseReasonPerformance {
+                reason,
+                trades: trades.len() as u32,
+                win_rate: Self::win_rate_of(&trades),
+                avg_pnl: Self::avg_pnl_of(&trades),
+                net_pnl: trades.iter().map(|t| t.total_pnl).sum(),
+                avg_hold_hours: trades.iter().map(|t| t.hold_hours).sum::<f64>() / trades.len() as f64,
+            })
+            .collect()
+    }
+    
+    /// P&L impact of reversal hedge tilts versus holding the full hedge
+    pub async fn get_hedge_tilt_performance(&self) -> HedgeTiltPerformance {
+        let trades = self.trades.read().await;
+        HedgeTiltPerformance::from_tilts(trades.iter().filter_map(|t| t.hedge_tilt.as_ref()))
+    }
+    
+    /// Win rate and average P&L by UTC entry hour and weekday
+    pub async fn get_performance_by_entry_time(&self) -> EntryTimePerformance {
+        EntryTimePerformance::from_trades(&self.trades.read().await)
+    }
+    
+    /// Average hold of closed trades (hours), once `min_trades` have closed
+    pub async fn average_hold_hours(&self, min_trades: u32) -> Option<f64> {
+        let trades = self.trades.read().await;
+        (!trades.is_empty() && trades.len() >= min_trades as usize)
+            .then(|| trades.iter().map(|t| t.hold_hours).sum::<f64>() / trades.len() as f64)
+    }
+    
     fn win_rate_of(trades: &[&TradeOutcome]) -> f64 {
         if trades.is_empty() {
             return 0.0;
@@ -453,16 +1101,19 @@ impl PerformanceDb {
         let mut csv = String::from(
             "id,open_time,close_time,size,entry_spot,entry_perp,exit_spot,exit_perp,\
              entry_basis,exit_basis,entry_funding_apr,funding_collected,spot_pnl,perp_pnl,\
-             total_pnl,roi_pct,hold_hours,is_winner,close_reason,entry_confidence\n"
+             total_pnl,roi_pct,hold_hours,is_winner,close_reason,entry_confidence,\
+             fees_paid,slippage_cost,market,expected_value\n"
         );
         
         for t in trades.iter() {
             csv.push_str(&format!(
-                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
                 t.id, t.open_time, t.close_time, t.size, t.entry_spot, t.entry_perp,
                 t.exit_spot, t.exit_perp, t.entry_basis, t.exit_basis, t.entry_funding_apr,
                 t.funding_collected, t.spot_pnl, t.perp_pnl, t.total_pnl, t.roi_pct,
-                t.hold_hours, t.is_winner, t.close_reason, t.entry_confidence
+                t.hold_hours, t.is_winner, t.close_reason, t.entry_confidence,
+                t.fees_paid, t.slippage_cost, t.market,
+                t.expected_value.map(|ev| ev.ev.to_string()).unwrap_or_default()
             ));
         }
         
@@ -472,6 +1123,99 @@ impl PerformanceDb {
     }
 }
 
+/// Per-market performance summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketPerformance {
+    pub market: String,
+    pub trades: u32,
+    pub sharpe_ratio: f64,
+    pub net_pnl: f64,
+}
+
+/// Performance of the trades closed for one reason
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseReasonPerformance {
+    pub reason: CloseReason,
+    pub trades: u32,
+    pub win_rate: f64,
+    pub avg_pnl: f64,
+    pub net_pnl: f64,
+    pub avg_hold_hours: f64,
+}
+
+/// Performance of the trades entered in one hour or weekday
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeBucketPerformance {
+    /// UTC hour (0-23) or weekday (0 = Monday)
+    pub bucket: u32,
+    pub trades: u32,
+    pub win_rate: f64,
+    pub avg_pnl: f64,
+}
+
+/// Performance by when trades were entered
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntryTimePerformance {
+    pub trades: u32,
+    pub win_rate: f64,
+    /// 24 UTC hours
+    pub by_hour: Vec<TimeBucketPerformance>,
+    /// 7 weekdays, Monday first
+    pub by_weekday: Vec<TimeBucketPerformance>,
+}
+
+impl EntryTimePerformance {
+    pub fn from_trades(trades: &[TradeOutcome]) -> Self {
+        let mut hours: Vec<Vec<&TradeOutcome>> = vec![Vec::new(); 24];
+        let mut weekdays: Vec<Vec<&TradeOutcome>> = vec![Vec::new(); 7];
+        for trade in trades {
+            let opened = chrono::DateTime::from_timestamp_millis(trade.open_time).unwrap_or_default();
+            hours[opened.hour() as usize].push(trade);
+            weekdays[opened.weekday().num_days_from_monday() as usize].push(trade);
+        }
+        let buckets = |groups: Vec<Vec<&TradeOutcome>>| {
+            groups
+                .into_iter()
+                .enumerate()
+                .map(|(bucket, trades)| TimeBucketPerformance {
+                    bucket: bucket as u32,
+                    trades: trades.len() as u32,
+                    win_rate: PerformanceDb::win_rate_of(&trades),
+                    avg_pnl: PerformanceDb::avg_pnl_of(&trades),
+                })
+                .collect()
+        };
+        let all: Vec<&TradeOutcome> = trades.iter().collect();
+        Self {
+            trades: trades.len() as u32,
+            win_rate: PerformanceDb::win_rate_of(&all),
+            by_hour: buckets(hours),
+            by_weekday: buckets(weekdays),
+        }
+    }
+
+    /// Win rate edge of entering at `timestamp` over all trades (-1 to 1):
+    /// the mean of its hour's and weekday's edges, counting only buckets
+    /// with at least `min_trades` trades
+    pub fn preference(&self, timestamp: i64, min_trades: u32) -> f64 {
+        let now = chrono::DateTime::from_timestamp_millis(timestamp).unwrap_or_default();
+        let edges: Vec<f64> = [
+            self.by_hour.get(now.hour() as usize),
+            self.by_weekday.get(now.weekday().num_days_from_monday() as usize),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|b| b.trades >= min_trades.max(1))
+        .map(|b| b.win_rate - self.win_rate)
+        .collect();
+        if edges.is_empty() {
+            0.0
+        } else {
+            edges.iter().sum::<f64>() / edges.len() as f64
+        }
+    }
+}
+
 /// Performance breakdown by funding level
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FundingPerformance {
@@ -488,30 +1232,92 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_sharpe_calculation() {
-        let returns = vec![0.01, 0.02, -0.005, 0.015, 0.01];
-        let sharpe = PerformanceDb::calculate_sharpe(&returns);
-        assert!(sharpe > 0.0);
+    fn test_equity_base() {
+        let trades = vec![
+            TradeOutcome { size: 10.0, entry_spot: 150.0, total_pnl: 100.0, ..Default::default() },
+            TradeOutcome { size: 20.0, entry_spot: 140.0, total_pnl: -50.0, ..Default::default() },
+        ];
+        // Capital known: the curve starts from it less the P&L since
+        assert_eq!(PerformanceDb::equity_base(&trades, 50.0, 10_050.0), 10_000.0);
+        // Capital unknown: the largest position stands in
+        assert_eq!(PerformanceDb::equity_base(&trades, 50.0, 0.0), 2_800.0);
     }
     
     #[test]
-    fn test_max_drawdown() {
-        let trades = vec![
-            TradeOutcome {
-                id: "1".to_string(),
-                total_pnl: 100.0,
-                is_winner: true,
-                ..Default::default()
-            },
-            TradeOutcome {
-                id: "2".to_string(),
-                total_pnl: -50.0,
-                is_winner: false,
-                ..Default::default()
-            },
-        ];
-        let dd = PerformanceDb::calculate_max_drawdown(&trades);
-        assert!(dd > 0.0);
+    fn test_window_metrics() {
+        let day = DAY_MS;
+        let trade = |close_day: i64, pnl: f64| TradeOutcome {
+            open_time: close_day * day - 3_600_000,
+            close_time: close_day * day,
+            total_pnl: pnl,
+            is_winner: pnl > 0.0,
+            ..Default::default()
+        };
+        let trades = vec![trade(1, -100.0), trade(25, 30.0), trade(27, -10.0), trade(29, 20.0)];
+        
+        let week = WindowMetrics::compute(&trades, 7, 30 * day, 10_000.0);
+        assert_eq!(week.trades, 3);
+        assert!((week.win_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(week.profit_factor, 5.0);
+        assert_eq!(week.avg_loss, 10.0);
+        assert_eq!(week.net_pnl, 40.0);
+        
+        let month = WindowMetrics::compute(&trades, 30, 30 * day, 10_000.0);
+        assert_eq!(month.trades, 4);
+        assert!(month.net_pnl < 0.0);
+    }
+    
+    #[tokio::test]
+    async fn test_performance_by_close_reason() {
+        let dir = std::env::temp_dir().join(format!("close-reasons-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        // Reasons from before the taxonomy still load
+        let stored = r#"[{"id":"old","open_time":0,"close_time":0,"size":1.0,"entry_spot":100.0,"entry_perp":100.5,
+            "exit_spot":100.0,"exit_perp":100.0,"entry_basis":0.5,"exit_basis":0.0,"entry_funding_apr":10.0,
+            "funding_collected":0.0,"spot_pnl":0.0,"perp_pnl":0.0,"total_pnl":5.0,"roi_pct":0.0,"hold_hours":2.0,
+            "is_winner":true,"close_reason":"","entry_confidence":0.5}]"#;
+        let path = dir.join("perf.json");
+        tokio::fs::write(&path, stored).await.unwrap();
+        let db = PerformanceDb::new(path.to_str().unwrap()).await.unwrap();
+        
+        for (reason, pnl) in [(CloseReason::StopLoss, -20.0), (CloseReason::BasisConverged, 10.0), (CloseReason::StopLoss, -10.0)] {
+            let trade = TradeOutcome { close_reason: reason, total_pnl: pnl, is_winner: pnl > 0.0, hold_hours: 4.0, ..Default::default() };
+            db.record_trade(trade).await.unwrap();
+        }
+        
+        let by_reason = db.get_performance_by_close_reason().await;
+        let reasons: Vec<_> = by_reason.iter().map(|r| (r.reason, r.trades)).collect();
+        assert_eq!(
+            reasons,
+            vec![(CloseReason::BasisConverged, 1), (CloseReason::StopLoss, 2), (CloseReason::Unknown, 1)]
+        );
+        assert_eq!(by_reason[1].net_pnl, -30.0);
+        assert_eq!(by_reason[1].win_rate, 0.0);
+    }
+    
+    #[test]
+    fn test_entry_time_preference() {
+        // 2024-01-01 was a Monday
+        let monday = 1_704_067_200_000;
+        let hour = 3_600_000;
+        let trade = |open_time: i64, pnl: f64| TradeOutcome { open_time, total_pnl: pnl, is_winner: pnl > 0.0, ..Default::default() };
+        let mut trades = Vec::new();
+        for day in 0..4 {
+            // Winners at 08:00, losers at 20:00
+            trades.push(trade(monday + day * 24 * hour + 8 * hour, 10.0));
+            trades.push(trade(monday + day * 24 * hour + 20 * hour, -5.0));
+        }
+        
+        let profile = EntryTimePerformance::from_trades(&trades);
+        assert_eq!(profile.by_hour[8].trades, 4);
+        assert_eq!(profile.by_hour[20].avg_pnl, -5.0);
+        assert_eq!(profile.by_weekday[0].trades, 2);
+        assert_eq!(profile.win_rate, 0.5);
+        
+        // Weekdays have too few trades to count; the hour alone decides
+        assert!((profile.preference(monday + 8 * hour, 3) - 0.5).abs() < 1e-9);
+        assert!((profile.preference(monday + 20 * hour, 3) + 0.5).abs() < 1e-9);
+        assert_eq!(profile.preference(monday + 12 * hour, 3), 0.0);
     }
 }
 
@@ -536,8 +1342,14 @@ impl Default for TradeOutcome {
             roi_pct: 0.0,
             hold_hours: 0.0,
             is_winner: false,
-            close_reason: String::new(),
+            close_reason: CloseReason::Unknown,
             entry_confidence: 0.0,
+            fees_paid: 0.0,
+            slippage_cost: 0.0,
+            market: default_market(),
+            sizing: None,
+            expected_value: None,
+            hedge_tilt: None,
         }
     }
 }
@@ -0,0 +1,250 @@
+//! Basis Regime Detection
+//!
+//! Classifies the basis as mean-reverting (carry-friendly) or trending
+//! using a variance-ratio test over the shared basis history:
+//! - VR(q) = Var(q-step changes) / (q * Var(1-step changes))
+//! - VR < 1: changes partly undo each other, the basis reverts
+//! - VR ≈ 1: random walk
+//! - VR > 1: changes persist, the basis trends
+//!
+//! A basis trade is a convergence bet, so entries are gated and sizes
+//! scaled down when the basis trends.
+
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, info};
+
+use crate::config::AppConfig;
+use crate::network::event_bus::Event;
+use crate::state::SharedState;
+
+/// Basis history needed before a regime is reported
+const MIN_HISTORY_SPAN: Duration = Duration::from_secs(20 * 60);
+
+/// Market regime of the basis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MarketRegime {
+    /// Basis reverts to its mean: carry-friendly
+    MeanReverting,
+    /// No detectable structure
+    RandomWalk,
+    /// Basis moves persist: convergence is unreliable
+    Trending,
+    /// Not enough history
+    Unknown,
+}
+
+impl MarketRegime {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MeanReverting => "MEAN_REVERTING",
+            Self::RandomWalk => "RANDOM_WALK",
+            Self::Trending => "TRENDING",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// Whether new basis trades may be entered
+    pub fn is_carry_friendly(&self) -> bool {
+        !matches!(self, Self::Trending)
+    }
+
+    /// Position size multiplier for this regime; no history, no scaling
+    pub fn size_multiplier(&self) -> f64 {
+        match self {
+            Self::MeanReverting | Self::Unknown => 1.0,
+            Self::RandomWalk => 0.75,
+            Self::Trending => 0.25,
+        }
+    }
+}
+
+/// Regime classification result
+#[derive(Debug, Clone, Serialize)]
+pub struct RegimeAnalysis {
+    pub regime: MarketRegime,
+    /// Variance ratio at the configured lag (None if not enough history)
+    pub variance_ratio: Option<f64>,
+    /// Basis samples used
+    pub samples: usize,
+    pub timestamp: i64,
+}
+
+/// Regime detector
+pub struct RegimeDetector {
+    /// Configuration
+    config: Arc<AppConfig>,
+    /// Shared state
+    state: Arc<SharedState>,
+    /// Event sender
+    event_tx: broadcast::Sender<Event>,
+    /// Is running
+    running: Arc<RwLock<bool>>,
+    /// Last analysis
+    last_analysis: Arc<RwLock<Option<RegimeAnalysis>>>,
+}
+
+impl RegimeDetector {
+    /// Create a new regime detector
+    pub fn new(
+        config: Arc<AppConfig>,
+        state: Arc<SharedState>,
+        event_tx: broadcast::Sender<Event>,
+    ) -> Self {
+        Self {
+            config,
+            state,
+            event_tx,
+            running: Arc::new(RwLock::new(false)),
+            last_analysis: Arc::new(RwLock::new(None)),
+        }
+    }
+    
+    /// Start the regime detector
+    pub async fn start(&self) -> anyhow::Result<()> {
+        if !self.config.agentic.enable_regime_detection {
+            info!("Regime detection disabled");
+            return Ok(());
+        }
+        
+        *self.running.write().await = true;
+        info!("Regime detector starting");
+        
+        let running = self.running.clone();
+        let state = self.state.clone();
+        let config = self.config.clone();
+        let event_tx = self.event_tx.clone();
+        let last_analysis = self.last_analysis.clone();
+        
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.engines.regime_interval());
+            
+            while *running.read().await {
+                interval.tick().await;
+                
+                let analysis = Self::analyze(&state, &config, chrono::Utc::now().timestamp_millis());
+                debug!(
+                    "Basis regime: {} (VR {:?}, {} samples)",
+                    analysis.regime.as_str(), analysis.variance_ratio, analysis.samples
+                );
+                
+                let previous = last_analysis.read().await.as_ref().map(|a| a.regime);
+                if let Some(from) = previous.filter(|r| *r != analysis.regime) {
+                    info!("Basis regime changed: {} -> {}", from.as_str(), analysis.regime.as_str());
+                    let _ = event_tx.send(Event::RegimeChanged {
+                        from,
+                        to: analysis.regime,
+                        variance_ratio: analysis.variance_ratio.unwrap_or(1.0),
+                    });
+                }
+                
+                *last_analysis.write().await = Some(analysis);
+            }
+            
+            info!("Regime detector stopped");
+        });
+        
+        Ok(())
+    }
+    
+    /// Classify the basis over the configured window
+    fn analyze(state: &SharedState, config: &AppConfig, timestamp: i64) -> RegimeAnalysis {
+        let agentic = &config.agentic;
+        let window_ms = (agentic.regime_window_hours * 3_600_000.0) as i64;
+        let values: Vec<f64> = state.basis_history
+            .since(timestamp - window_ms)
+            .iter()
+            .map(|s| s.value)
+            .collect();
+        
+        let variance_ratio = if values.len() >= config.engines.basis_samples(MIN_HISTORY_SPAN) {
+            variance_ratio(&values, agentic.regime_lag)
+        } else {
+            None
+        };
+        
+        let regime = match variance_ratio {
+            None => MarketRegime::Unknown,
+            Some(vr) if vr >= agentic.trending_variance_ratio => MarketRegime::Trending,
+            Some(vr) if vr <= agentic.mean_reverting_variance_ratio => MarketRegime::MeanReverting,
+            Some(_) => MarketRegime::RandomWalk,
+        };
+        
+        RegimeAnalysis {
+            regime,
+            variance_ratio,
+            samples: values.len(),
+            timestamp,
+        }
+    }
+    
+    /// Stop the regime detector
+    pub async fn stop(&self) {
+        *self.running.write().await = false;
+        info!("Regime detector stopping");
+    }
+    
+    /// Get last analysis
+    pub async fn get_last_analysis(&self) -> Option<RegimeAnalysis> {
+        self.last_analysis.read().await.clone()
+    }
+    
+    /// Shared handle to the latest analysis, for the signal engine and sizer
+    pub fn regime_handle(&self) -> Arc<RwLock<Option<RegimeAnalysis>>> {
+        self.last_analysis.clone()
+    }
+    
+    /// Current regime (Unknown until the first analysis)
+    pub async fn current_regime(&self) -> MarketRegime {
+        self.last_analysis.read().await
+            .as_ref()
+            .map(|a| a.regime)
+            .unwrap_or(MarketRegime::Unknown)
+    }
+}
+
+/// Lo-MacKinlay variance ratio of a level series at lag `q`
+pub fn variance_ratio(levels: &[f64], q: usize) -> Option<f64> {
+    if q < 2 || levels.len() <= q + 1 {
+        return None;
+    }
+    
+    let diffs = |lag: usize| -> Vec<f64> {
+        levels.windows(lag + 1).map(|w| w[lag] - w[0]).collect()
+    };
+    let variance = |xs: &[f64]| -> f64 {
+        let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+        xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / xs.len() as f64
+    };
+    
+    let var_1 = variance(&diffs(1));
+    if var_1 <= f64::EPSILON {
+        return None;
+    }
+    Some(variance(&diffs(q)) / (q as f64 * var_1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variance_ratio_classifies_trend_and_reversion() {
+        // Alternating around a mean: every move is undone
+        let reverting: Vec<f64> = (0..200).map(|i| if i % 2 == 0 { 0.2 } else { 0.3 }).collect();
+        assert!(variance_ratio(&reverting, 10).unwrap() < 0.5);
+
+        // Long runs of same-signed moves: moves persist
+        let mut trending = vec![0.0];
+        for i in 1..200 {
+            let step = if (i / 20) % 2 == 0 { 0.01 } else { -0.01 };
+            trending.push(trending[i - 1] + step);
+        }
+        assert!(variance_ratio(&trending, 10).unwrap() > 2.0);
+
+        // Without history sizes are left alone
+        assert_eq!(MarketRegime::Unknown.size_multiplier(), 1.0);
+    }
+}
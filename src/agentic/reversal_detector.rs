@@ -6,17 +6,33 @@
 //! - Severity classification
 //! - Alert generation with actionable recommendations
 
-use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, info, warn};
 
 use crate::config::AppConfig;
 use crate::network::event_bus::Event;
 use crate::state::SharedState;
+use crate::utils::types::FundingSnapshot;
+
+/// Funding history window used for reversal analysis (ms)
+const ANALYSIS_WINDOW_MS: i64 = 4 * 60 * 60 * 1000;
+
+/// How long a reversal stays active after its last alert (ms)
+pub const REVERSAL_ACTIVE_MS: i64 = 30 * 60 * 1000;
+
+/// History needed before any analysis
+const MIN_HISTORY_SPAN: Duration = Duration::from_secs(5 * 60);
+/// History needed before acceleration is estimated
+const ACCELERATION_SPAN: Duration = Duration::from_secs(10 * 60);
+/// History at which the data contributes full confidence
+const FULL_CONFIDENCE_SPAN: Duration = Duration::from_secs(50 * 60);
 
 /// Reversal severity levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReversalSeverity {
     /// Minor shift - monitor closely
     Low,
@@ -77,14 +93,6 @@ pub struct ReversalAlert {
     pub confidence: f64,
 }
 
-/// Funding rate sample for history tracking
-#[derive(Debug, Clone)]
-struct FundingSample {
-    timestamp: i64,
-    rate: f64,
-    apr: f64,
-}
-
 /// Reversal detector
 pub struct ReversalDetector {
     /// Configuration
@@ -95,8 +103,6 @@ pub struct ReversalDetector {
     event_tx: broadcast::Sender<Event>,
     /// Is running
     running: Arc<RwLock<bool>>,
-    /// Funding history (for velocity calculation)
-    history: Arc<RwLock<VecDeque<FundingSample>>>,
     /// Last calculated velocity
     last_velocity: Arc<RwLock<f64>>,
     /// Last alert
@@ -121,7 +127,6 @@ impl ReversalDetector {
             state,
             event_tx,
             running: Arc::new(RwLock::new(false)),
-            history: Arc::new(RwLock::new(VecDeque::with_capacity(480))), // 4 hours at 30s
             last_velocity: Arc::new(RwLock::new(0.0)),
             last_alert: Arc::new(RwLock::new(None)),
             alert_history: Arc::new(RwLock::new(Vec::new())),
@@ -139,7 +144,6 @@ impl ReversalDetector {
         let state = self.state.clone();
         let config = self.config.clone();
         let event_tx = self.event_tx.clone();
-        let history = self.history.clone();
         let last_velocity = self.last_velocity.clone();
         let last_alert = self.last_alert.clone();
         let alert_history = self.alert_history.clone();
@@ -147,43 +151,28 @@ impl ReversalDetector {
         let last_alert_time = self.last_alert_time.clone();
         
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+            let mut interval = tokio::time::interval(config.engines.reversal_interval());
             
             while *running.read().await {
                 interval.tick().await;
                 
-                let current_rate = state.current_funding_rate.load();
-                let current_apr = state.funding_apr.load();
+                let market = state.snapshot();
+                let current_rate = market.funding_rate;
+                let current_apr = market.funding_apr;
                 let timestamp = chrono::Utc::now().timestamp_millis();
                 
                 if current_rate.abs() < 0.000001 {
                     continue; // Skip if no funding data
                 }
                 
-                // Add to history
-                {
-                    let mut hist = history.write().await;
-                    hist.push_back(FundingSample {
-                        timestamp,
-                        rate: current_rate,
-                        apr: current_apr,
-                    });
-                    
-                    // Keep last 4 hours
-                    let cutoff = timestamp - (4 * 60 * 60 * 1000);
-                    while hist.front().map(|s| s.timestamp < cutoff).unwrap_or(false) {
-                        hist.pop_front();
-                    }
-                }
-                
                 // Analyze for reversal
                 let analysis = Self::analyze_reversal(
-                    &history,
+                    &state,
                     current_rate,
                     current_apr,
                     &config,
                     timestamp,
-                ).await;
+                );
                 
                 // Store velocity
                 *last_velocity.write().await = analysis.velocity;
@@ -208,6 +197,7 @@ impl ReversalDetector {
                         );
                         
                         // Store alert
+                        state.funding_reversal_at.store(alert.timestamp, Ordering::Relaxed);
                         *last_alert.write().await = Some(alert.clone());
                         *last_alert_time.write().await = timestamp;
                         
@@ -221,10 +211,11 @@ impl ReversalDetector {
                         }
                         
                         // Emit event
-                        let _ = event_tx.send(Event::TradeSignal {
-                            signal_type: format!("funding_reversal_{}", alert.severity.as_str().to_lowercase()),
-                            size: 0.0,
-                            reason: alert.recommendation.clone(),
+                        let _ = event_tx.send(Event::FundingReversal {
+                            severity: alert.severity,
+                            apr_pct: alert.current_apr,
+                            time_to_zero_hours: alert.time_to_zero_hours,
+                            recommendation: alert.recommendation.clone(),
                         });
                     }
                 }
@@ -237,16 +228,18 @@ impl ReversalDetector {
     }
     
     /// Analyze funding for reversal signals
-    async fn analyze_reversal(
-        history: &Arc<RwLock<VecDeque<FundingSample>>>,
+    fn analyze_reversal(
+        state: &SharedState,
         current_rate: f64,
         current_apr: f64,
         config: &AppConfig,
         timestamp: i64,
     ) -> ReversalAnalysis {
-        let hist = history.read().await;
+        let hist = state.get_funding_series(timestamp - ANALYSIS_WINDOW_MS, timestamp);
         
-        if hist.len() < 10 {
+        // Sample-count thresholds cover fixed spans at the configured sampling
+        let engines = &config.engines;
+        if hist.len() < engines.funding_samples(MIN_HISTORY_SPAN) {
             return ReversalAnalysis {
                 velocity: 0.0,
                 acceleration: 0.0,
@@ -258,7 +251,7 @@ impl ReversalDetector {
         let velocity = Self::calculate_velocity(&hist);
         
         // Calculate acceleration (change in velocity)
-        let acceleration = Self::calculate_acceleration(&hist);
+        let acceleration = Self::calculate_acceleration(&hist, engines.funding_samples(ACCELERATION_SPAN));
         
         // Check for reversal conditions
         let is_positive = current_rate > 0.0;
@@ -272,20 +265,22 @@ impl ReversalDetector {
             };
         }
         
-        // Calculate reversal metrics
-        let velocity_magnitude = velocity.abs();
-        let acceleration_magnitude = acceleration.abs();
+        // Calculate reversal metrics, normalized to hourly rates so the
+        // severity thresholds hold for any funding interval
+        let interval = config.protocols.drift.funding_interval;
+        let velocity_magnitude = interval.hourly_rate(velocity.abs());
+        let acceleration_magnitude = interval.hourly_rate(acceleration.abs());
         
         // Predict time to zero crossing
         let time_to_zero = if velocity_magnitude > 0.0001 {
-            Some(current_rate.abs() / velocity_magnitude)
+            Some(interval.hourly_rate(current_rate.abs()) / velocity_magnitude)
         } else {
             None
         };
         
-        // Predict future funding
-        let predicted_1h = current_apr + (velocity * 1.0 * 24.0 * 365.0 * 100.0);
-        let predicted_8h = current_apr + (velocity * 8.0 * 24.0 * 365.0 * 100.0);
+        // Predict future funding (velocity is in per-period rate units per hour)
+        let predicted_1h = current_apr + interval.annualize(velocity * 1.0);
+        let predicted_8h = current_apr + interval.annualize(velocity * 8.0);
         
         // Determine severity
         let severity = Self::determine_severity(
@@ -333,7 +328,12 @@ impl ReversalDetector {
         );
         
         // Calculate confidence
-        let confidence = Self::calculate_confidence(&hist, velocity_magnitude, acceleration_magnitude);
+        let confidence = Self::calculate_confidence(
+            &hist,
+            engines.funding_samples(FULL_CONFIDENCE_SPAN),
+            velocity_magnitude,
+            acceleration_magnitude,
+        );
         
         let alert = ReversalAlert {
             timestamp,
@@ -358,49 +358,52 @@ impl ReversalDetector {
     }
     
     /// Calculate velocity (rate of change per hour)
-    fn calculate_velocity(history: &VecDeque<FundingSample>) -> f64 {
+    fn calculate_velocity(history: &[FundingSnapshot]) -> f64 {
         if history.len() < 2 {
             return 0.0;
         }
         
         // Use last 30 minutes of data for velocity
-        let cutoff = history.back().map(|s| s.timestamp - 30 * 60 * 1000).unwrap_or(0);
+        let cutoff = history.last().map(|s| s.timestamp - 30 * 60 * 1000).unwrap_or(0);
         let recent: Vec<_> = history.iter().filter(|s| s.timestamp >= cutoff).collect();
         
         if recent.len() < 2 {
             return 0.0;
         }
         
-        // Linear regression for more stable velocity
+        // Linear regression against time (hours) for more stable velocity
+        let t0 = recent[0].timestamp;
+        let hours = |s: &FundingSnapshot| (s.timestamp - t0) as f64 / 3600000.0;
         let n = recent.len() as f64;
-        let sum_x: f64 = recent.iter().enumerate().map(|(i, _)| i as f64).sum();
+        let sum_x: f64 = recent.iter().map(|s| hours(s)).sum();
         let sum_y: f64 = recent.iter().map(|s| s.rate).sum();
-        let sum_xy: f64 = recent.iter().enumerate().map(|(i, s)| i as f64 * s.rate).sum();
-        let sum_xx: f64 = recent.iter().enumerate().map(|(i, _)| (i as f64).powi(2)).sum();
+        let sum_xy: f64 = recent.iter().map(|s| hours(s) * s.rate).sum();
+        let sum_xx: f64 = recent.iter().map(|s| hours(s).powi(2)).sum();
         
-        let slope = (n * sum_xy - sum_x * sum_y) / (n * sum_xx - sum_x.powi(2));
+        let denominator = n * sum_xx - sum_x.powi(2);
+        if denominator == 0.0 {
+            return 0.0;
+        }
         
-        // Convert to per-hour (samples are 30s apart)
-        slope * 120.0 // 120 samples per hour
+        (n * sum_xy - sum_x * sum_y) / denominator
     }
     
     /// Calculate acceleration (change in velocity)
-    fn calculate_acceleration(history: &VecDeque<FundingSample>) -> f64 {
-        if history.len() < 20 {
+    fn calculate_acceleration(history: &[FundingSnapshot], min_samples: usize) -> f64 {
+        if history.len() < min_samples.max(4) {
             return 0.0;
         }
         
         // Split into two halves and compare velocities
         let mid = history.len() / 2;
+        let (first_half, second_half) = history.split_at(mid);
         
-        let first_half: VecDeque<_> = history.iter().take(mid).cloned().collect();
-        let second_half: VecDeque<_> = history.iter().skip(mid).cloned().collect();
+        let v1 = Self::calculate_velocity(first_half);
+        let v2 = Self::calculate_velocity(second_half);
         
-        let v1 = Self::calculate_velocity(&first_half);
-        let v2 = Self::calculate_velocity(&second_half);
-        
-        // Time between midpoints (rough estimate)
-        let time_hours = (history.len() as f64 * 30.0) / 3600.0 / 2.0;
+        // Time between the midpoints of each half
+        let midpoint = |h: &[FundingSnapshot]| (h[0].timestamp + h[h.len() - 1].timestamp) as f64 / 2.0;
+        let time_hours = (midpoint(second_half) - midpoint(first_half)) / 3600000.0;
         
         if time_hours > 0.0 {
             (v2 - v1) / time_hours
@@ -492,14 +495,15 @@ impl ReversalDetector {
     
     /// Calculate confidence in prediction
     fn calculate_confidence(
-        history: &VecDeque<FundingSample>,
+        history: &[FundingSnapshot],
+        full_samples: usize,
         velocity_magnitude: f64,
         acceleration_magnitude: f64,
     ) -> f64 {
         let mut confidence = 0.5; // Base confidence
         
         // More data = higher confidence
-        let data_factor = (history.len() as f64 / 100.0).min(1.0);
+        let data_factor = (history.len() as f64 / full_samples as f64).min(1.0);
         confidence += data_factor * 0.2;
         
         // Strong, consistent velocity = higher confidence
@@ -541,8 +545,7 @@ impl ReversalDetector {
         self.last_alert.read().await.as_ref()
             .map(|a| {
                 let now = chrono::Utc::now().timestamp_millis();
-                // Consider reversal active if alert within last 30 minutes
-                now - a.timestamp < 30 * 60 * 1000
+                now - a.timestamp < REVERSAL_ACTIVE_MS
             })
             .unwrap_or(false)
     }
@@ -558,17 +561,18 @@ impl ReversalDetector {
     
     /// Manual check for reversal (for testing)
     pub async fn check_now(&self) -> Option<ReversalAlert> {
-        let current_rate = self.state.current_funding_rate.load();
-        let current_apr = self.state.funding_apr.load();
+        let market = self.state.snapshot();
+        let current_rate = market.funding_rate;
+        let current_apr = market.funding_apr;
         let timestamp = chrono::Utc::now().timestamp_millis();
         
         let analysis = Self::analyze_reversal(
-            &self.history,
+            &self.state,
             current_rate,
             current_apr,
             &self.config,
             timestamp,
-        ).await;
+        );
         
         analysis.alert
     }
@@ -594,12 +598,12 @@ mod tests {
 
     #[test]
     fn test_velocity_calculation() {
-        let mut history = VecDeque::new();
+        let mut history = Vec::new();
         let now = 1000000;
         
         // Add samples with decreasing rate
         for i in 0..20 {
-            history.push_back(FundingSample {
+            history.push(FundingSnapshot {
                 timestamp: now + i * 30000,
                 rate: 0.001 - (i as f64 * 0.00005),
                 apr: 0.0,
@@ -0,0 +1,144 @@
+//! Monte Carlo Risk of Ruin
+//!
+//! Bootstraps historical per-trade returns into simulated equity paths:
+//! - Risk of ruin: share of paths losing the ruin threshold of equity
+//! - Probability of breaching the max drawdown limit
+//! - Distribution of P&L over a 30-day horizon
+//!
+//! Each simulated trade commits the Kelly fraction of current equity and
+//! earns a return drawn (with replacement) from the trade history.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Simulation horizon (days)
+pub const HORIZON_DAYS: f64 = 30.0;
+
+/// Most paths simulated, whatever the config asks for
+pub const MAX_PATHS: usize = 20_000;
+
+/// Most trades per path, bounding the horizon of a fast-trading history
+pub const MAX_TRADES_PER_PATH: usize = 5_000;
+
+/// Simulation parameters
+#[derive(Debug, Clone, Copy)]
+pub struct RuinParams {
+    /// Number of simulated paths
+    pub paths: usize,
+    /// Trades per path (horizon length)
+    pub trades_per_path: usize,
+    /// Equity loss counted as ruin (%)
+    pub ruin_threshold_pct: f64,
+    /// Drawdown limit from the risk config (%)
+    pub max_drawdown_pct: f64,
+}
+
+/// Risk-of-ruin estimate at a given Kelly fraction
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RiskOfRuinEstimate {
+    /// Kelly fraction simulated (0-1)
+    pub kelly_fraction: f64,
+    /// Paths simulated
+    pub paths: usize,
+    /// Trades per path
+    pub trades_per_path: usize,
+    /// Probability of losing the ruin threshold (%)
+    pub risk_of_ruin_pct: f64,
+    /// Probability of breaching the max drawdown limit (%)
+    pub drawdown_breach_pct: f64,
+    /// 30-day P&L, 5th percentile (% of equity)
+    pub pnl_30d_p5_pct: f64,
+    /// 30-day P&L, median (% of equity)
+    pub pnl_30d_p50_pct: f64,
+    /// 30-day P&L, 95th percentile (% of equity)
+    pub pnl_30d_p95_pct: f64,
+}
+
+/// Run the bootstrap simulation.
+///
+/// `returns` are per-trade returns on deployed capital (0.01 = 1%).
+/// Returns None without history to resample.
+pub fn simulate<R: Rng>(
+    returns: &[f64],
+    kelly_fraction: f64,
+    params: &RuinParams,
+    rng: &mut R,
+) -> Option<RiskOfRuinEstimate> {
+    if returns.is_empty() || params.paths == 0 || params.trades_per_path == 0 {
+        return None;
+    }
+
+    let ruin_level = 1.0 - params.ruin_threshold_pct / 100.0;
+    let mut ruined = 0usize;
+    let mut breached = 0usize;
+    let mut final_pnl = Vec::with_capacity(params.paths);
+
+    for _ in 0..params.paths {
+        let mut equity = 1.0_f64;
+        let mut peak = 1.0_f64;
+        let mut path_ruined = false;
+        let mut path_breached = false;
+
+        for _ in 0..params.trades_per_path {
+            let r = returns[rng.gen_range(0..returns.len())];
+            equity = (equity * (1.0 + kelly_fraction * r)).max(0.0);
+            peak = peak.max(equity);
+
+            if (peak - equity) / peak * 100.0 >= params.max_drawdown_pct {
+                path_breached = true;
+            }
+            if equity <= ruin_level {
+                path_ruined = true;
+                break;
+            }
+        }
+
+        ruined += path_ruined as usize;
+        breached += path_breached as usize;
+        final_pnl.push((equity - 1.0) * 100.0);
+    }
+
+    final_pnl.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let percentile = |pct: f64| final_pnl[((final_pnl.len() - 1) as f64 * pct).round() as usize];
+    let paths = params.paths as f64;
+
+    Some(RiskOfRuinEstimate {
+        kelly_fraction,
+        paths: params.paths,
+        trades_per_path: params.trades_per_path,
+        risk_of_ruin_pct: ruined as f64 / paths * 100.0,
+        drawdown_breach_pct: breached as f64 / paths * 100.0,
+        pnl_30d_p5_pct: percentile(0.05),
+        pnl_30d_p50_pct: percentile(0.50),
+        pnl_30d_p95_pct: percentile(0.95),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_ruin_grows_with_fraction() {
+        // Positive expectancy but occasional 30% losses
+        let returns = [0.05, 0.04, 0.06, -0.30, 0.05];
+        let params = RuinParams {
+            paths: 2000,
+            trades_per_path: 60,
+            ruin_threshold_pct: 50.0,
+            max_drawdown_pct: 10.0,
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let small = simulate(&returns, 0.1, &params, &mut rng).unwrap();
+        let large = simulate(&returns, 1.0, &params, &mut rng).unwrap();
+
+        assert_eq!(small.risk_of_ruin_pct, 0.0);
+        assert!(large.risk_of_ruin_pct > small.risk_of_ruin_pct);
+        assert!(large.drawdown_breach_pct > small.drawdown_breach_pct);
+        assert!(small.pnl_30d_p5_pct <= small.pnl_30d_p50_pct);
+        assert!(small.pnl_30d_p50_pct <= small.pnl_30d_p95_pct);
+    }
+}
@@ -0,0 +1,374 @@
+//! Session Report
+//!
+//! Written to `report.dir` on shutdown, as HTML (equity curve drawn inline)
+//! or markdown:
+//! - Realized and unrealized P&L, and any positions left open
+//! - Daily equity curve
+//! - Trades closed during the session
+//! - Signals fired by type versus entries taken, and opens skipped per filter
+//! - Risk events: pauses, hedge drift, leg imbalances, degraded APIs and
+//!   warning or worse alerts
+//! - Funding reversal alerts
+//! - Latency per execution stage
+//!
+//! `SessionLog` follows the event bus for signals and alerts from startup;
+//! trades, equity and positions are read at shutdown.
+
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::PathBuf;
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::config::{ReportFormat, SessionReportConfig};
+use crate::network::Event;
+use crate::telemetry::{AlertLevel, StageStats};
+use crate::utils::types::{Position, SignalType, SkipReason};
+
+use super::equity_curve::DailyEquity;
+use super::performance_db::TradeOutcome;
+
+/// Risk events and reversal alerts kept for the report
+const MAX_EVENTS: usize = 200;
+
+/// A noteworthy event during the session
+#[derive(Debug, Clone)]
+pub struct SessionEvent {
+    /// Receive time (ms)
+    pub timestamp: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    fired: HashMap<SignalType, u32>,
+    skipped: BTreeMap<SkipReason, u32>,
+    risk_events: VecDeque<SessionEvent>,
+    risk_event_count: usize,
+    reversals: VecDeque<SessionEvent>,
+}
+
+/// State read at shutdown
+#[derive(Debug, Clone, Default)]
+pub struct SessionData {
+    /// Trades closed during the session
+    pub trades: Vec<TradeOutcome>,
+    pub equity: Vec<DailyEquity>,
+    /// Spot and perp legs still open
+    pub positions: Vec<Position>,
+    pub unrealized_pnl: f64,
+    pub latency: Vec<StageStats>,
+}
+
+/// Signals and alerts seen since startup
+pub struct SessionLog {
+    started_at: i64,
+    tally: Mutex<Tally>,
+}
+
+impl Default for SessionLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn push(events: &mut VecDeque<SessionEvent>, description: String) {
+    if events.len() == MAX_EVENTS {
+        events.pop_front();
+    }
+    events.push_back(SessionEvent { timestamp: chrono::Utc::now().timestamp_millis(), description });
+}
+
+fn format_time(ms: i64, format: &str) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .map(|t| t.format(format).to_string())
+        .unwrap_or_default()
+}
+
+fn time(ms: i64) -> String {
+    format_time(ms, "%Y-%m-%d %H:%M:%S")
+}
+
+impl SessionLog {
+    pub fn new() -> Self {
+        Self { started_at: chrono::Utc::now().timestamp_millis(), tally: Mutex::new(Tally::default()) }
+    }
+
+    /// Session start (ms)
+    pub fn started_at(&self) -> i64 {
+        self.started_at
+    }
+
+    /// Count a signal, or remember a risk event or reversal alert
+    pub fn record(&self, event: &Event) {
+        let mut tally = self.tally.lock();
+        let risk = match event {
+            Event::SignalGenerated(signal) => {
+                *tally.fired.entry(signal.signal_type).or_default() += 1;
+                None
+            }
+            Event::SignalSkipped { reason, .. } => {
+                *tally.skipped.entry(*reason).or_default() += 1;
+                None
+            }
+            Event::FundingReversal { severity, apr_pct, recommendation, .. } => {
+                let description = format!("{} at {:.2}% APR: {}", severity.as_str(), apr_pct, recommendation);
+                push(&mut tally.reversals, description);
+                None
+            }
+            Event::SystemPause { reason } => Some(format!("Paused: {}", reason)),
+            Event::SystemResume => Some("Resumed".to_string()),
+            Event::HedgeDriftAlert { drift_pct, threshold_pct } => {
+                Some(format!("Hedge drift {:.2}% (limit {:.2}%)", drift_pct, threshold_pct))
+            }
+            Event::LegImbalance { trade_id, phase, spot_filled, perp_filled, resolution } => Some(format!(
+                "Leg imbalance on {} of {}: spot {:.4} / perp {:.4}, {}",
+                phase, trade_id, spot_filled, perp_filled, resolution
+            )),
+            Event::DependencyDegraded { dependency, failures, .. } => {
+                Some(format!("{} degraded after {} failures", dependency.as_str(), failures))
+            }
+            Event::Alert(alert) if alert.level >= AlertLevel::Warning => {
+                Some(format!("{}: {}", alert.title, alert.message))
+            }
+            _ => None,
+        };
+        if let Some(description) = risk {
+            tally.risk_event_count += 1;
+            push(&mut tally.risk_events, description);
+        }
+    }
+
+    /// Record events until the bus closes
+    pub async fn run(&self, mut rx: broadcast::Receiver<Event>) {
+        loop {
+            match rx.recv().await {
+                Ok(event) => self.record(&event),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    }
+
+    /// Entries taken: trades opened this session, closed or still open
+    fn entries_taken(&self, data: &SessionData) -> usize {
+        let closed = data.trades.iter().filter(|t| t.open_time >= self.started_at).count();
+        let open = data.positions.iter().any(|p| p.opened_at >= self.started_at);
+        closed + open as usize
+    }
+
+    /// Markdown report
+    pub fn markdown(&self, data: &SessionData, ended_at: i64) -> String {
+        let tally = self.tally.lock();
+        let realized: f64 = data.trades.iter().map(|t| t.total_pnl).sum();
+        let mut out = format!(
+            "# Session Report\n\n{} to {} (UTC)\n\n\
+             | | |\n|---|---|\n\
+             | Trades closed | {} |\n| Realized P&L | ${:.2} |\n| Unrealized P&L | ${:.2} |\n\
+             | Open signals fired | {} |\n| Entries taken | {} |\n\n",
+            time(self.started_at),
+            time(ended_at),
+            data.trades.len(),
+            realized,
+            data.unrealized_pnl,
+            tally.fired.get(&SignalType::OpenBasis).copied().unwrap_or(0),
+            self.entries_taken(data),
+        );
+
+        out.push_str("## Open Positions\n\n");
+        if data.positions.is_empty() {
+            out.push_str("None, flat at shutdown.\n\n");
+        } else {
+            out.push_str("| Leg | Side | Size | Entry | Mark | Unrealized |\n|---|---|---|---|---|---|\n");
+            for p in &data.positions {
+                out.push_str(&format!(
+                    "| {:?} | {:?} | {:.4} | {:.4} | {:.4} | ${:.2} |\n",
+                    p.position_type, p.side, p.size, p.entry_price, p.mark_price, p.unrealized_pnl
+                ));
+            }
+            out.push('\n');
+        }
+
+        out.push_str("## Equity\n\n| Day | Equity |\n|---|---|\n");
+        for point in &data.equity {
+            out.push_str(&format!("| {} | ${:.2} |\n", format_time(point.day, "%Y-%m-%d"), point.equity));
+        }
+
+        out.push_str("\n## Trades\n\n");
+        if data.trades.is_empty() {
+            out.push_str("None closed.\n");
+        } else {
+            out.push_str("| Trade | Opened | Closed | Size | Basis in/out | P&L | Reason |\n|---|---|---|---|---|---|---|\n");
+        }
+        for t in &data.trades {
+            out.push_str(&format!(
+                "| {} | {} | {} | {:.2} | {:.3}% / {:.3}% | ${:.2} | {} |\n",
+                t.id, time(t.open_time), time(t.close_time), t.size, t.entry_basis, t.exit_basis, t.total_pnl, t.close_reason
+            ));
+        }
+
+        out.push_str("\n## Signals\n\n| Signal | Fired |\n|---|---|\n");
+        for signal in [SignalType::OpenBasis, SignalType::CloseBasis, SignalType::Rebalance] {
+            out.push_str(&format!("| {:?} | {} |\n", signal, tally.fired.get(&signal).copied().unwrap_or(0)));
+        }
+        out.push_str("\n| Open skipped by | Count |\n|---|---|\n");
+        for (reason, count) in &tally.skipped {
+            out.push_str(&format!("| {:?} | {} |\n", reason, count));
+        }
+
+        out.push_str(&format!("\n## Risk Events ({})\n\n", tally.risk_event_count));
+        for event in &tally.risk_events {
+            out.push_str(&format!("- {} {}\n", time(event.timestamp), event.description));
+        }
+
+        out.push_str("\n## Reversal Alerts\n\n");
+        for event in &tally.reversals {
+            out.push_str(&format!("- {} {}\n", time(event.timestamp), event.description));
+        }
+
+        out.push_str("\n## Latency\n\n| Stage | Count | Mean | p50 | p95 | Max |\n|---|---|---|---|---|---|\n");
+        for s in &data.latency {
+            out.push_str(&format!(
+                "| {} | {} | {:.0}ms | {:.0}ms | {:.0}ms | {:.0}ms |\n",
+                s.stage, s.count, s.mean_ms, s.p50_ms, s.p95_ms, s.max_ms
+            ));
+        }
+        out
+    }
+
+    /// HTML report: the markdown tables as HTML, with the equity curve drawn
+    pub fn html(&self, data: &SessionData, ended_at: i64) -> String {
+        let mut body = String::new();
+        let mut in_table = false;
+        for line in self.markdown(data, ended_at).lines() {
+            if line.starts_with("|---") {
+                continue;
+            }
+            if let Some(row) = line.strip_prefix('|') {
+                if !in_table {
+                    body.push_str("<table>");
+                    in_table = true;
+                }
+                let cells: String = row
+                    .trim_end_matches('|')
+                    .split(" | ")
+                    .map(|cell| format!("<td>{}</td>", escape(cell.trim())))
+                    .collect();
+                body.push_str(&format!("<tr>{}</tr>\n", cells));
+                continue;
+            }
+            if in_table {
+                body.push_str("</table>\n");
+                in_table = false;
+            }
+            if let Some(title) = line.strip_prefix("## ") {
+                body.push_str(&format!("<h2>{}</h2>\n", escape(title)));
+                if title == "Equity" {
+                    body.push_str(&equity_svg(&data.equity));
+                }
+            } else if let Some(title) = line.strip_prefix("# ") {
+                body.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+            } else if let Some(item) = line.strip_prefix("- ") {
+                body.push_str(&format!("<div>{}</div>\n", escape(item)));
+            } else if !line.is_empty() {
+                body.push_str(&format!("<p>{}</p>\n", escape(line)));
+            }
+        }
+        if in_table {
+            body.push_str("</table>\n");
+        }
+        format!(
+            "<!doctype html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Session Report</title>\n\
+             <style>body {{ font: 14px/1.4 system-ui, sans-serif; margin: 2rem; }} \
+             table {{ border-collapse: collapse; margin-bottom: 1rem; font-variant-numeric: tabular-nums; }} \
+             td {{ padding: 0.2rem 0.6rem; border-bottom: 1px solid #ddd; }}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            body
+        )
+    }
+
+    /// Write the report to `config.dir`; returns its path
+    pub async fn write(&self, config: &SessionReportConfig, data: &SessionData) -> Result<PathBuf> {
+        let ended_at = chrono::Utc::now().timestamp_millis();
+        let (contents, extension) = match config.format {
+            ReportFormat::Html => (self.html(data, ended_at), "html"),
+            ReportFormat::Markdown => (self.markdown(data, ended_at), "md"),
+        };
+        let stamp = format_time(self.started_at, "%Y%m%d-%H%M%S");
+        tokio::fs::create_dir_all(&config.dir).await.context("Failed to create report directory")?;
+        let path = PathBuf::from(&config.dir).join(format!("session-{}.{}", stamp, extension));
+        tokio::fs::write(&path, contents).await.with_context(|| format!("Failed to write {:?}", path))?;
+        info!("Session report written to {:?}", path);
+        Ok(path)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Equity curve as an inline SVG line
+fn equity_svg(points: &[DailyEquity]) -> String {
+    if points.len() < 2 {
+        return String::new();
+    }
+    let min = points.iter().map(|p| p.equity).fold(f64::INFINITY, f64::min);
+    let max = points.iter().map(|p| p.equity).fold(f64::NEG_INFINITY, f64::max);
+    let span = if max > min { max - min } else { 1.0 };
+    let path: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let x = i as f64 / (points.len() - 1) as f64 * 600.0;
+            let y = 190.0 - (p.equity - min) / span * 180.0;
+            format!("{}{:.1},{:.1}", if i == 0 { "M" } else { "L" }, x, y)
+        })
+        .collect();
+    format!(
+        "<svg viewBox=\"0 0 600 200\" width=\"600\" height=\"200\"><path d=\"{}\" fill=\"none\" stroke=\"#4c8dff\" stroke-width=\"2\"/></svg>\n",
+        path.join("")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::types::TradeSignal;
+
+    #[test]
+    fn test_session_report() {
+        let log = SessionLog::new();
+        let signal = TradeSignal {
+            signal_type: SignalType::OpenBasis,
+            size: 10.0,
+            basis_spread: 0.5,
+            funding_apr: 20.0,
+            expected_profit: 5.0,
+            confidence: 0.8,
+            timestamp: 0,
+            market_timestamp: 0,
+            reason: String::new(),
+            expected_value: None,
+        };
+        log.record(&Event::SignalGenerated(signal.clone()));
+        log.record(&Event::SignalGenerated(signal));
+        log.record(&Event::SystemPause { reason: "Daily loss limit".to_string() });
+        log.record(&Event::FundingRateUpdate { rate: 0.0, timestamp: 0 });
+
+        let data = SessionData {
+            trades: vec![TradeOutcome { id: "t1".to_string(), open_time: log.started_at(), total_pnl: 12.5, ..Default::default() }],
+            equity: vec![DailyEquity { day: 0, equity: 10_000.0 }, DailyEquity { day: 86_400_000, equity: 10_012.5 }],
+            ..Default::default()
+        };
+        let report = log.markdown(&data, log.started_at());
+        assert!(report.contains("| Open signals fired | 2 |"));
+        assert!(report.contains("| Entries taken | 1 |"));
+        assert!(report.contains("| Realized P&L | $12.50 |"));
+        assert!(report.contains("## Risk Events (1)"));
+        assert!(report.contains("None, flat at shutdown."));
+
+        let html = log.html(&data, log.started_at());
+        assert!(html.contains("<svg"));
+        assert!(html.contains("<td>Realized P&amp;L</td>"));
+    }
+}
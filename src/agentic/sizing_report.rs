@@ -0,0 +1,144 @@
+//! Sizing Explainability
+//!
+//! Every size the adaptive sizer recommends comes with the derivation that
+//! produced it, so an operator can audit a trade after the fact:
+//! - The Kelly inputs (lifetime and blended win/loss stats) and each cut
+//!   applied to the fraction
+//! - Each multiplier applied to the base size, in order
+//! - Each cap that bound the size (wallet, collateral, allocation, operator)
+//!
+//! The report is logged at entry and stored with the `TradeOutcome`.
+
+use serde::{Deserialize, Serialize};
+
+/// How the Kelly fraction was derived
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct KellyDerivation {
+    /// Closed trades behind the statistics
+    pub trades: u32,
+    /// Lifetime win rate (0-1)
+    pub lifetime_win_rate: f64,
+    /// Lifetime average win (USD)
+    pub lifetime_avg_win: f64,
+    /// Lifetime average loss (USD)
+    pub lifetime_avg_loss: f64,
+    /// Share of the edge taken from rolling windows (0-1)
+    pub recent_window_weight: f64,
+    /// Win rate used, after blending in rolling windows (0-1)
+    pub win_rate: f64,
+    /// Average win used (USD)
+    pub avg_win: f64,
+    /// Average loss used (USD)
+    pub avg_loss: f64,
+    /// W - (1 - W) / R before any safety cut
+    pub raw_kelly: f64,
+    /// Whether the fraction was halved (half-Kelly)
+    pub half_kelly: bool,
+    /// After half-Kelly, the zero floor and `max_kelly_fraction`
+    pub capped_kelly: f64,
+    /// Times the fraction was halved to meet risk-of-ruin limits
+    pub ruin_halvings: u32,
+    /// Fraction used for sizing
+    pub kelly: f64,
+    /// The conservative default was used (too few trades or adaptation off)
+    pub default_used: bool,
+}
+
+/// Whether a step scaled the size or bounded it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepKind {
+    Multiplier,
+    Cap,
+}
+
+/// One change to the size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SizingStep {
+    pub kind: StepKind,
+    /// Short identifier, e.g. `kelly` or `wallet_balance`
+    pub name: String,
+    /// Size after the step over size before it
+    pub factor: f64,
+    /// Size after the step (SOL)
+    pub size_sol: f64,
+    /// Human-readable explanation
+    pub detail: String,
+}
+
+/// Full derivation of one sizing recommendation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SizingReport {
+    /// When the size was computed (ms)
+    pub timestamp: i64,
+    /// Basis spread the size was computed for (%)
+    pub basis_spread: f64,
+    /// Funding APR the size was computed for (%)
+    pub funding_apr: f64,
+    /// Signal confidence (0-1)
+    pub confidence: f64,
+    /// `trading.max_position_size_sol`, after compounding
+    pub max_size_sol: f64,
+    /// Size before any step (SOL)
+    pub base_size_sol: f64,
+    pub kelly: KellyDerivation,
+    /// Steps in the order they were applied
+    pub steps: Vec<SizingStep>,
+    /// Final size (SOL)
+    pub size_sol: f64,
+}
+
+impl SizingReport {
+    /// Record a step taking the size from its current value to `size_sol`
+    pub fn push(&mut self, kind: StepKind, name: &str, size_sol: f64, detail: String) {
+        let factor = if self.size_sol > 0.0 { size_sol / self.size_sol } else { 0.0 };
+        self.steps.push(SizingStep { kind, name: name.to_string(), factor, size_sol, detail });
+        self.size_sol = size_sol;
+    }
+
+    /// Caps that bound the size
+    pub fn caps_hit(&self) -> impl Iterator<Item = &SizingStep> {
+        self.steps.iter().filter(|s| s.kind == StepKind::Cap)
+    }
+
+    /// Step explanations, in order
+    pub fn adjustments(&self) -> Vec<String> {
+        self.steps.iter().map(|s| s.detail.clone()).collect()
+    }
+
+    /// One-line summary for logs
+    pub fn summary(&self) -> String {
+        let steps: Vec<String> = self.steps.iter()
+            .map(|s| format!("{} {:.2}x", s.name, s.factor))
+            .collect();
+        format!(
+            "{:.4} SOL from base {:.4} | Kelly {:.1}% (raw {:.1}%, W {:.0}%, R {:.2}, {} trades) | {}",
+            self.size_sol,
+            self.base_size_sol,
+            self.kelly.kelly * 100.0,
+            self.kelly.raw_kelly * 100.0,
+            self.kelly.win_rate * 100.0,
+            if self.kelly.avg_loss > 0.0 { self.kelly.avg_win / self.kelly.avg_loss } else { 0.0 },
+            self.kelly.trades,
+            steps.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_record_factors_and_caps() {
+        let mut report = SizingReport { base_size_sol: 10.0, size_sol: 10.0, ..Default::default() };
+        report.push(StepKind::Multiplier, "kelly", 5.0, "Kelly 12.5%: 0.50x multiplier".to_string());
+        report.push(StepKind::Cap, "wallet_balance", 4.0, "Capped to wallet balance".to_string());
+
+        assert_eq!(report.size_sol, 4.0);
+        assert_eq!(report.steps[0].factor, 0.5);
+        assert_eq!(report.steps[1].factor, 0.8);
+        assert_eq!(report.caps_hit().map(|s| s.name.as_str()).collect::<Vec<_>>(), vec!["wallet_balance"]);
+        assert_eq!(report.adjustments().len(), 2);
+    }
+}
@@ -0,0 +1,272 @@
+//! Skipped-Trade Tracking
+//!
+//! Every filter between a signal and an entry has a cost: the trades it
+//! stops. Each blocked open signal is followed as the trade it would have
+//! been, so filters can be tuned on what they actually saved or forwent:
+//! - Max favorable and adverse excursion of the basis after the signal
+//! - Resolved when the basis converges (where the trade would have closed)
+//!   or after `agentic.skipped_trade_horizon_mins`
+//! - Summarized per skip reason
+//!
+//! One hypothetical trade is followed per reason at a time, so a filter that
+//! blocks for hours counts once. Resolved trades are appended to
+//! `skipped_trades.jsonl` next to the performance database.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::config::AppConfig;
+use crate::network::event_bus::Event;
+use crate::utils::types::SkipReason;
+
+use super::performance_db::PerformanceDb;
+
+/// A blocked open signal, followed as if it had been traded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedTrade {
+    pub reason: SkipReason,
+    pub detail: String,
+    /// Signal time (ms)
+    pub timestamp: i64,
+    pub size_sol: f64,
+    pub confidence: f64,
+    /// Basis at the signal (%)
+    pub entry_basis: f64,
+    pub entry_funding_apr: f64,
+    pub entry_spot: f64,
+    /// Best hypothetical P&L seen (% of notional)
+    pub max_favorable_pct: f64,
+    /// Worst hypothetical P&L seen (% of notional, at most 0)
+    pub max_adverse_pct: f64,
+    /// Hypothetical P&L at the last update (% of notional)
+    pub pnl_pct: f64,
+    /// When the hypothetical trade was resolved (ms)
+    pub resolved_at: Option<i64>,
+}
+
+impl SkippedTrade {
+    /// Hypothetical P&L at `basis`: the basis captured in the trade's direction (%)
+    pub fn pnl_at(&self, basis: f64) -> f64 {
+        self.entry_basis.signum() * (self.entry_basis - basis)
+    }
+
+    /// Follow the basis; resolves once it converges or the horizon passes
+    pub fn update(&mut self, basis: f64, now: i64, close_threshold_pct: f64, horizon_ms: i64) {
+        if self.resolved_at.is_some() {
+            return;
+        }
+        self.pnl_pct = self.pnl_at(basis);
+        self.max_favorable_pct = self.max_favorable_pct.max(self.pnl_pct);
+        self.max_adverse_pct = self.max_adverse_pct.min(self.pnl_pct);
+        if basis.abs() <= close_threshold_pct || now - self.timestamp >= horizon_ms {
+            self.resolved_at = Some(now);
+        }
+    }
+
+    /// Hypothetical P&L (USD)
+    pub fn pnl_usd(&self) -> f64 {
+        self.pnl_pct / 100.0 * self.size_sol * self.entry_spot
+    }
+}
+
+/// What one filter cost over its resolved skips
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SkipSummary {
+    pub reason: SkipReason,
+    pub count: u32,
+    pub avg_favorable_pct: f64,
+    pub avg_adverse_pct: f64,
+    /// Average hypothetical P&L at resolution (%)
+    pub avg_pnl_pct: f64,
+    /// Hypothetical P&L forgone (USD); negative is a loss avoided
+    pub forgone_usd: f64,
+}
+
+/// Per-reason summary of resolved skipped trades
+pub fn summarize(trades: &[SkippedTrade]) -> Vec<SkipSummary> {
+    let mut by_reason: BTreeMap<SkipReason, Vec<&SkippedTrade>> = BTreeMap::new();
+    for trade in trades.iter().filter(|t| t.resolved_at.is_some()) {
+        by_reason.entry(trade.reason).or_default().push(trade);
+    }
+    by_reason
+        .into_values()
+        .map(|trades| {
+            let n = trades.len() as f64;
+            SkipSummary {
+                reason: trades[0].reason,
+                count: trades.len() as u32,
+                avg_favorable_pct: trades.iter().map(|t| t.max_favorable_pct).sum::<f64>() / n,
+                avg_adverse_pct: trades.iter().map(|t| t.max_adverse_pct).sum::<f64>() / n,
+                avg_pnl_pct: trades.iter().map(|t| t.pnl_pct).sum::<f64>() / n,
+                forgone_usd: trades.iter().map(|t| t.pnl_usd()).sum(),
+            }
+        })
+        .collect()
+}
+
+/// Follows skipped trades from `SignalSkipped` and basis events
+pub struct SkippedTradeTracker {
+    enabled: bool,
+    close_threshold_pct: f64,
+    horizon_ms: i64,
+    performance_db: Arc<PerformanceDb>,
+    open: RwLock<Vec<SkippedTrade>>,
+}
+
+impl SkippedTradeTracker {
+    pub fn new(config: &AppConfig, performance_db: Arc<PerformanceDb>) -> Self {
+        Self {
+            enabled: config.agentic.track_skipped_trades,
+            close_threshold_pct: config.trading.basis_close_threshold_pct,
+            horizon_ms: config.agentic.skipped_trade_horizon_mins as i64 * 60_000,
+            performance_db,
+            open: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Start following a skipped trade; false if one is already followed for its reason
+    pub async fn record(&self, trade: SkippedTrade) -> bool {
+        let mut open = self.open.write().await;
+        if !self.enabled || open.iter().any(|t| t.reason == trade.reason) {
+            return false;
+        }
+        debug!("Following skipped trade ({:?}): {}", trade.reason, trade.detail);
+        open.push(trade);
+        true
+    }
+
+    /// Update open skipped trades with the latest basis, persisting resolved ones
+    pub async fn update(&self, basis: f64, now: i64) {
+        let resolved: Vec<SkippedTrade> = {
+            let mut open = self.open.write().await;
+            for trade in open.iter_mut() {
+                trade.update(basis, now, self.close_threshold_pct, self.horizon_ms);
+            }
+            let (resolved, still_open) = std::mem::take(&mut *open)
+                .into_iter()
+                .partition(|t| t.resolved_at.is_some());
+            *open = still_open;
+            resolved
+        };
+
+        for trade in resolved {
+            info!(
+                "Skipped trade resolved ({:?}): {:+.3}% (best {:+.3}%, worst {:+.3}%), ${:.2}",
+                trade.reason, trade.pnl_pct, trade.max_favorable_pct, trade.max_adverse_pct, trade.pnl_usd()
+            );
+            if let Err(e) = self.performance_db.record_skipped_trade(&trade).await {
+                warn!("Failed to persist skipped trade: {}", e);
+            }
+        }
+    }
+
+    /// Skipped trades still being followed
+    pub async fn open_trades(&self) -> Vec<SkippedTrade> {
+        self.open.read().await.clone()
+    }
+
+    /// Per-reason cost of filters over all resolved skipped trades
+    pub async fn summary(&self) -> Vec<SkipSummary> {
+        match self.performance_db.get_skipped_trades().await {
+            Ok(trades) => summarize(&trades),
+            Err(e) => {
+                warn!("Failed to read skipped trades: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Record skips and follow the basis until the task is aborted
+    pub fn spawn(self: Arc<Self>, mut event_rx: broadcast::Receiver<Event>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match event_rx.recv().await {
+                    Ok(Event::SignalSkipped {
+                        reason,
+                        detail,
+                        size,
+                        basis_spread,
+                        funding_apr,
+                        spot_price,
+                        confidence,
+                        timestamp,
+                    }) => {
+                        self.record(SkippedTrade {
+                            reason,
+                            detail,
+                            timestamp,
+                            size_sol: size,
+                            confidence,
+                            entry_basis: basis_spread,
+                            entry_funding_apr: funding_apr,
+                            entry_spot: spot_price,
+                            max_favorable_pct: 0.0,
+                            max_adverse_pct: 0.0,
+                            pnl_pct: 0.0,
+                            resolved_at: None,
+                        }).await;
+                    }
+                    Ok(Event::BasisSpreadUpdate { spread, timestamp, .. }) => {
+                        self.update(spread, timestamp).await;
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("Skipped-trade tracker lagged by {} events", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skipped(reason: SkipReason, entry_basis: f64) -> SkippedTrade {
+        SkippedTrade {
+            reason,
+            detail: String::new(),
+            timestamp: 0,
+            size_sol: 10.0,
+            confidence: 0.8,
+            entry_basis,
+            entry_funding_apr: 20.0,
+            entry_spot: 100.0,
+            max_favorable_pct: 0.0,
+            max_adverse_pct: 0.0,
+            pnl_pct: 0.0,
+            resolved_at: None,
+        }
+    }
+
+    #[test]
+    fn test_excursions_and_resolution() {
+        let mut trade = skipped(SkipReason::Cooldown, 0.5);
+        trade.update(0.7, 60_000, 0.05, 3_600_000);
+        trade.update(0.3, 120_000, 0.05, 3_600_000);
+        assert!((trade.max_adverse_pct + 0.2).abs() < 1e-9);
+        assert!((trade.max_favorable_pct - 0.2).abs() < 1e-9);
+        assert!(trade.resolved_at.is_none());
+
+        // Converged: resolved where the trade would have closed
+        trade.update(0.04, 180_000, 0.05, 3_600_000);
+        assert_eq!(trade.resolved_at, Some(180_000));
+        assert!((trade.pnl_usd() - 0.46 / 100.0 * 1_000.0).abs() < 1e-9);
+
+        // A negative basis is traded the other way round
+        let mut reverse = skipped(SkipReason::Reversal, -0.5);
+        reverse.update(-0.8, 3_600_000, 0.05, 3_600_000);
+        assert!((reverse.pnl_pct + 0.3).abs() < 1e-9);
+        assert!(reverse.resolved_at.is_some());
+
+        let summary = summarize(&[trade, reverse]);
+        assert_eq!(summary.len(), 2);
+        assert!(summary.iter().all(|s| s.count == 1));
+    }
+}
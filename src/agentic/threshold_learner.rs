@@ -0,0 +1,280 @@
+//! Entry Threshold Learner
+//!
+//! A bounded online learner for the entry thresholds
+//! (`agentic.threshold_learning`). Only trades opened under the thresholds
+//! in force are scored, and nothing changes until `min_trades` of them have
+//! closed. Their average net P&L per trade, fees included, over the last
+//! `window_trades` is the reward of those thresholds:
+//! - Below `tighten_below_ev_usd`: raise both thresholds by `step_pct`,
+//!   trading less often on richer setups
+//! - Above `loosen_above_ev_usd`: lower both by `step_pct`, taking more of
+//!   the setups that are paying
+//! - In between, or already at the bound: hold
+//!
+//! Thresholds never leave their configured floors and ceilings. Every
+//! adjustment, rollbacks included, is appended to
+//! `threshold_adjustments.jsonl` next to the performance database, and the
+//! latest one is restored on restart.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use tracing::info;
+
+use super::performance_db::{PerformanceDb, TradeOutcome};
+use crate::config::{AppConfig, ThresholdLearningConfig};
+use crate::state::SharedState;
+use crate::telemetry::record_entry_thresholds;
+use crate::utils::types::EntryThresholds;
+
+/// What an adjustment did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdAction {
+    Tighten,
+    Loosen,
+    /// Back to the configured thresholds
+    Rollback,
+}
+
+/// One logged change of the entry thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdAdjustment {
+    /// When it was made (ms)
+    pub timestamp: i64,
+    pub action: ThresholdAction,
+    /// Close that triggered it; None for a rollback
+    pub trade_id: Option<String>,
+    /// Average net P&L of the trades it was based on (USD per trade)
+    pub mean_pnl_usd: Option<f64>,
+    /// Trades the average was taken over
+    pub trades: usize,
+    pub from: EntryThresholds,
+    pub to: EntryThresholds,
+}
+
+/// Thresholds from the config
+pub fn configured(config: &AppConfig) -> EntryThresholds {
+    EntryThresholds {
+        min_basis_spread_pct: config.trading.min_basis_spread_pct,
+        min_funding_apr_pct: config.trading.min_funding_apr_pct,
+    }
+}
+
+/// `thresholds` scaled by the frequency governor's `multiplier`, held
+/// within the learner's floors and ceilings while it is enabled
+pub fn governed(config: &AppConfig, thresholds: EntryThresholds, multiplier: f64) -> EntryThresholds {
+    let learning = &config.agentic.threshold_learning;
+    let basis = thresholds.min_basis_spread_pct * multiplier;
+    let funding = thresholds.min_funding_apr_pct * multiplier;
+    if !learning.enabled {
+        return EntryThresholds { min_basis_spread_pct: basis, min_funding_apr_pct: funding };
+    }
+    EntryThresholds {
+        min_basis_spread_pct: basis.clamp(learning.basis_floor_pct, learning.basis_ceiling_pct),
+        min_funding_apr_pct: funding.clamp(learning.funding_floor_apr_pct, learning.funding_ceiling_apr_pct),
+    }
+}
+
+/// Next thresholds after `scored` trades (opened under `current`, oldest
+/// first), None to hold
+pub fn decide(
+    learning: &ThresholdLearningConfig,
+    current: EntryThresholds,
+    scored: &[TradeOutcome],
+) -> Option<(ThresholdAction, f64, EntryThresholds)> {
+    if scored.is_empty() || scored.len() < learning.min_trades {
+        return None;
+    }
+    let window = &scored[scored.len().saturating_sub(learning.window_trades)..];
+    let mean_pnl = window.iter().map(|t| t.total_pnl).sum::<f64>() / window.len() as f64;
+    let (action, factor) = if mean_pnl < learning.tighten_below_ev_usd {
+        (ThresholdAction::Tighten, 1.0 + learning.step_pct / 100.0)
+    } else if mean_pnl > learning.loosen_above_ev_usd {
+        (ThresholdAction::Loosen, 1.0 - learning.step_pct / 100.0)
+    } else {
+        return None;
+    };
+    let next = EntryThresholds {
+        min_basis_spread_pct: (current.min_basis_spread_pct * factor)
+            .clamp(learning.basis_floor_pct, learning.basis_ceiling_pct),
+        min_funding_apr_pct: (current.min_funding_apr_pct * factor)
+            .clamp(learning.funding_floor_apr_pct, learning.funding_ceiling_apr_pct),
+    };
+    (next != current).then_some((action, mean_pnl, next))
+}
+
+/// Learns the entry thresholds the signal engine reads from the shared state
+pub struct ThresholdLearner {
+    config: Arc<AppConfig>,
+    state: Arc<SharedState>,
+    performance_db: Arc<PerformanceDb>,
+    /// Last adjustment (ms); trades opened before it ran under other
+    /// thresholds and are not scored
+    since: AtomicI64,
+}
+
+impl ThresholdLearner {
+    pub fn new(config: Arc<AppConfig>, state: Arc<SharedState>, performance_db: Arc<PerformanceDb>) -> Self {
+        Self { config, state, performance_db, since: AtomicI64::new(0) }
+    }
+
+    /// Whether closes adjust the thresholds
+    pub fn enabled(&self) -> bool {
+        self.config.agentic.threshold_learning.enabled
+    }
+
+    /// Thresholds in use
+    pub fn current(&self) -> EntryThresholds {
+        self.state.entry_thresholds.read().unwrap_or_else(|| configured(&self.config))
+    }
+
+    /// Restore the latest learned thresholds from the log
+    pub async fn restore(&self) -> Result<()> {
+        let log = self.performance_db.get_threshold_adjustments().await?;
+        if let Some(last) = log.last() {
+            self.since.store(last.timestamp, Ordering::Relaxed);
+        }
+        if !self.enabled() {
+            return Ok(());
+        }
+        if let Some(last) = log.last().filter(|a| a.action != ThresholdAction::Rollback) {
+            info!(
+                "Restored learned entry thresholds: basis {:.3}%, funding {:.1}% APR",
+                last.to.min_basis_spread_pct, last.to.min_funding_apr_pct
+            );
+            *self.state.entry_thresholds.write() = Some(last.to);
+        }
+        let current = self.current();
+        record_entry_thresholds(current.min_basis_spread_pct, current.min_funding_apr_pct);
+        Ok(())
+    }
+
+    /// Learn from the close of `trade_id`
+    pub async fn observe(&self, trade_id: &str) -> Result<Option<ThresholdAdjustment>> {
+        let learning = &self.config.agentic.threshold_learning;
+        if !self.enabled() {
+            return Ok(None);
+        }
+        // Score the thresholds in force on the trades they opened
+        let since = self.since.load(Ordering::Relaxed);
+        let trades: Vec<TradeOutcome> = self
+            .performance_db
+            .get_all_trades()
+            .await
+            .into_iter()
+            .filter(|t| t.open_time > since)
+            .collect();
+        let from = self.current();
+        let Some((action, mean_pnl, to)) = decide(learning, from, &trades) else {
+            return Ok(None);
+        };
+
+        let adjustment = ThresholdAdjustment {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            action,
+            trade_id: Some(trade_id.to_string()),
+            mean_pnl_usd: Some(mean_pnl),
+            trades: trades.len().min(learning.window_trades),
+            from,
+            to,
+        };
+        self.performance_db.record_threshold_adjustment(&adjustment).await?;
+        *self.state.entry_thresholds.write() = Some(to);
+        self.since.store(adjustment.timestamp, Ordering::Relaxed);
+        record_entry_thresholds(to.min_basis_spread_pct, to.min_funding_apr_pct);
+        info!(
+            "Entry thresholds {:?} on ${:.2} average P&L: basis {:.3}% → {:.3}%, funding {:.1}% → {:.1}% APR",
+            action,
+            mean_pnl,
+            from.min_basis_spread_pct,
+            to.min_basis_spread_pct,
+            from.min_funding_apr_pct,
+            to.min_funding_apr_pct
+        );
+        Ok(Some(adjustment))
+    }
+
+    /// Return to the configured thresholds and restart learning from here
+    pub async fn rollback(&self) -> Result<ThresholdAdjustment> {
+        let adjustment = ThresholdAdjustment {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            action: ThresholdAction::Rollback,
+            trade_id: None,
+            mean_pnl_usd: None,
+            trades: 0,
+            from: self.current(),
+            to: configured(&self.config),
+        };
+        self.performance_db.record_threshold_adjustment(&adjustment).await?;
+        *self.state.entry_thresholds.write() = None;
+        self.since.store(adjustment.timestamp, Ordering::Relaxed);
+        record_entry_thresholds(adjustment.to.min_basis_spread_pct, adjustment.to.min_funding_apr_pct);
+        info!(
+            "Entry thresholds rolled back to basis {:.3}%, funding {:.1}% APR",
+            adjustment.to.min_basis_spread_pct, adjustment.to.min_funding_apr_pct
+        );
+        Ok(adjustment)
+    }
+
+    /// Logged adjustments, oldest first
+    pub async fn history(&self) -> Result<Vec<ThresholdAdjustment>> {
+        self.performance_db.get_threshold_adjustments().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trades(pnls: &[f64]) -> Vec<TradeOutcome> {
+        pnls.iter().map(|&total_pnl| TradeOutcome { total_pnl, ..Default::default() }).collect()
+    }
+
+    #[test]
+    fn test_decide_within_bounds() {
+        let learning = ThresholdLearningConfig {
+            enabled: true,
+            min_trades: 3,
+            window_trades: 3,
+            tighten_below_ev_usd: 0.0,
+            loosen_above_ev_usd: 10.0,
+            ..Default::default()
+        };
+        let current = EntryThresholds { min_basis_spread_pct: 0.2, min_funding_apr_pct: 15.0 };
+
+        // Too few trades, and a mean inside the hold band
+        assert!(decide(&learning, current, &trades(&[-10.0, -10.0])).is_none());
+        assert!(decide(&learning, current, &trades(&[2.0, 3.0, 1.0])).is_none());
+
+        // Only the last three count: losing trades, net of fees, tighten by 5%
+        let (action, mean, next) = decide(&learning, current, &trades(&[30.0, -5.0, -2.0, -2.0])).unwrap();
+        assert_eq!(action, ThresholdAction::Tighten);
+        assert!((mean + 3.0).abs() < 1e-9);
+        assert!((next.min_basis_spread_pct - 0.21).abs() < 1e-9);
+        assert!((next.min_funding_apr_pct - 15.75).abs() < 1e-9);
+
+        // Paying trades loosen, but never below the floors; at the floors it holds
+        let floor = EntryThresholds { min_basis_spread_pct: 0.102, min_funding_apr_pct: 5.1 };
+        let (action, _, next) = decide(&learning, floor, &trades(&[12.0, 12.0, 12.0])).unwrap();
+        assert_eq!(action, ThresholdAction::Loosen);
+        assert_eq!(next, EntryThresholds { min_basis_spread_pct: 0.1, min_funding_apr_pct: 5.0 });
+        assert!(decide(&learning, next, &trades(&[12.0, 12.0, 12.0])).is_none());
+    }
+
+    #[test]
+    fn test_governor_stays_within_the_learner_bounds() {
+        let mut config = AppConfig::default_for_test();
+        config.agentic.threshold_learning.enabled = true;
+        let learning = config.agentic.threshold_learning.clone();
+        let near_ceiling = EntryThresholds {
+            min_basis_spread_pct: learning.basis_ceiling_pct * 0.9,
+            min_funding_apr_pct: learning.funding_ceiling_apr_pct * 0.9,
+        };
+
+        let governed = governed(&config, near_ceiling, 1.21);
+        assert_eq!(governed.min_basis_spread_pct, learning.basis_ceiling_pct);
+        assert_eq!(governed.min_funding_apr_pct, learning.funding_ceiling_apr_pct);
+    }
+}
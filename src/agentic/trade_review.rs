@@ -0,0 +1,297 @@
+//! Post-Trade Review Queue
+//!
+//! Flags closed trades that deserve an operator's attention:
+//! - Outsized losses: more than `review_loss_sigma` standard deviations
+//!   below the mean P&L of earlier trades
+//! - Extreme slippage: slippage cost above `review_slippage_bps` of notional
+//! - Funding misses: funding collected far from what the entry APR predicted
+//!
+//! Flagged trades wait in a queue, worst first, until acknowledged. The queue
+//! is rebuilt from the performance database on startup and exported as a
+//! markdown report.
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::utils::types::CloseReason;
+
+use super::performance_db::{PerformanceDb, TradeOutcome};
+
+/// Earlier trades needed before losses are judged against the distribution
+const MIN_HISTORY: usize = 5;
+/// Expected funding below this (USD) is too small to judge a miss
+const MIN_EXPECTED_FUNDING: f64 = 1.0;
+const HOURS_PER_YEAR: f64 = 8760.0;
+
+/// Why a trade was flagged
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReviewFlag {
+    /// Loss far below the mean of earlier trades
+    OutsizedLoss { pnl: f64, sigmas: f64 },
+    /// Slippage cost as a share of entry notional
+    ExtremeSlippage { cost: f64, bps: f64 },
+    /// Funding collected versus predicted from the entry APR
+    FundingMiss { expected: f64, collected: f64 },
+}
+
+impl ReviewFlag {
+    fn describe(&self) -> String {
+        match self {
+            ReviewFlag::OutsizedLoss { pnl, sigmas } => format!("loss ${:.2} ({:.1}σ)", pnl, sigmas),
+            ReviewFlag::ExtremeSlippage { cost, bps } => format!("slippage ${:.2} ({:.1} bps)", cost, bps),
+            ReviewFlag::FundingMiss { expected, collected } => {
+                format!("funding ${:.2} vs ${:.2} expected", collected, expected)
+            }
+        }
+    }
+}
+
+/// A flagged trade awaiting review
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewItem {
+    pub trade_id: String,
+    pub close_time: i64,
+    pub total_pnl: f64,
+    pub close_reason: CloseReason,
+    pub flags: Vec<ReviewFlag>,
+    /// Acknowledged by an operator
+    pub reviewed: bool,
+}
+
+/// Flags anomalous closed trades into a review queue
+pub struct TradeReviewer {
+    config: Arc<AppConfig>,
+    performance_db: Arc<PerformanceDb>,
+    queue: RwLock<Vec<ReviewItem>>,
+}
+
+impl TradeReviewer {
+    pub fn new(config: Arc<AppConfig>, performance_db: Arc<PerformanceDb>) -> Self {
+        Self {
+            config,
+            performance_db,
+            queue: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Re-review the stored trade history, each trade against the ones before it
+    pub async fn rebuild(&self) {
+        let trades = self.performance_db.get_all_trades().await;
+        let items: Vec<ReviewItem> = trades
+            .iter()
+            .enumerate()
+            .filter_map(|(i, trade)| self.flag(trade, &trades[..i]))
+            .collect();
+        if !items.is_empty() {
+            info!("Trade review queue: {} flagged trades", items.len());
+        }
+        *self.queue.write() = items;
+    }
+
+    /// Review a trade that was just recorded; returns the queued item if flagged
+    pub async fn review(&self, trade: &TradeOutcome) -> Option<ReviewItem> {
+        let history: Vec<TradeOutcome> = self.performance_db.get_all_trades().await
+            .into_iter()
+            .filter(|t| t.id != trade.id)
+            .collect();
+        let item = self.flag(trade, &history)?;
+
+        let reasons: Vec<String> = item.flags.iter().map(ReviewFlag::describe).collect();
+        warn!("Trade {} flagged for review: {}", item.trade_id, reasons.join(", "));
+        self.queue.write().push(item.clone());
+        Some(item)
+    }
+
+    /// Flags raised by a trade, given the trades closed before it
+    fn flag(&self, trade: &TradeOutcome, history: &[TradeOutcome]) -> Option<ReviewItem> {
+        let agentic = &self.config.agentic;
+        let mut flags = Vec::new();
+
+        if trade.total_pnl < 0.0 && history.len() >= MIN_HISTORY {
+            let n = history.len() as f64;
+            let mean = history.iter().map(|t| t.total_pnl).sum::<f64>() / n;
+            let variance = history.iter().map(|t| (t.total_pnl - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            let std_dev = variance.sqrt();
+            if std_dev > 0.0 {
+                let sigmas = (mean - trade.total_pnl) / std_dev;
+                if sigmas > agentic.review_loss_sigma {
+                    flags.push(ReviewFlag::OutsizedLoss { pnl: trade.total_pnl, sigmas });
+                }
+            }
+        }
+
+        let notional = trade.entry_spot * trade.size;
+        if notional > 0.0 {
+            let bps = trade.slippage_cost / notional * 10_000.0;
+            if bps > agentic.review_slippage_bps {
+                flags.push(ReviewFlag::ExtremeSlippage { cost: trade.slippage_cost, bps });
+            }
+        }
+
+        let expected = notional * trade.entry_funding_apr / 100.0 * trade.hold_hours / HOURS_PER_YEAR;
+        if expected.abs() >= MIN_EXPECTED_FUNDING {
+            let deviation_pct = (trade.funding_collected - expected).abs() / expected.abs() * 100.0;
+            if deviation_pct > agentic.review_funding_deviation_pct {
+                flags.push(ReviewFlag::FundingMiss { expected, collected: trade.funding_collected });
+            }
+        }
+
+        (!flags.is_empty()).then(|| ReviewItem {
+            trade_id: trade.id.clone(),
+            close_time: trade.close_time,
+            total_pnl: trade.total_pnl,
+            close_reason: trade.close_reason,
+            flags,
+            reviewed: false,
+        })
+    }
+
+    /// Unreviewed items, worst P&L first
+    pub fn pending(&self) -> Vec<ReviewItem> {
+        let mut items: Vec<ReviewItem> = self.queue.read().iter().filter(|i| !i.reviewed).cloned().collect();
+        items.sort_by(|a, b| a.total_pnl.total_cmp(&b.total_pnl));
+        items
+    }
+
+    /// Mark a trade as reviewed; false if it is not in the queue
+    pub fn acknowledge(&self, trade_id: &str) -> bool {
+        let mut queue = self.queue.write();
+        match queue.iter_mut().find(|i| i.trade_id == trade_id) {
+            Some(item) => {
+                item.reviewed = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Markdown report of the pending queue
+    pub fn report(&self) -> String {
+        let items = self.pending();
+        let mut report = format!("# Trade Review\n\n{} trades flagged for review.\n\n", items.len());
+        if items.is_empty() {
+            return report;
+        }
+        report.push_str("| Trade | Closed (UTC) | P&L | Close reason | Flags |\n|---|---|---|---|---|\n");
+        for item in &items {
+            let closed = chrono::DateTime::from_timestamp_millis(item.close_time)
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            let flags: Vec<String> = item.flags.iter().map(ReviewFlag::describe).collect();
+            report.push_str(&format!(
+                "| {} | {} | ${:.2} | {} | {} |\n",
+                item.trade_id, closed, item.total_pnl, item.close_reason, flags.join("; ")
+            ));
+        }
+        report
+    }
+
+    /// Write the report to a file
+    pub async fn export_report(&self, path: &str) -> Result<()> {
+        if let Some(dir) = std::path::Path::new(path).parent() {
+            tokio::fs::create_dir_all(dir).await.ok();
+        }
+        tokio::fs::write(path, self.report()).await?;
+        info!("Exported trade review report to {}", path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(id: &str, total_pnl: f64) -> TradeOutcome {
+        TradeOutcome {
+            id: id.to_string(),
+            open_time: 0,
+            close_time: 0,
+            size: 10.0,
+            entry_spot: 100.0,
+            entry_perp: 100.5,
+            exit_spot: 100.0,
+            exit_perp: 100.0,
+            entry_basis: 0.5,
+            exit_basis: 0.0,
+            entry_funding_apr: 0.0,
+            funding_collected: 0.0,
+            spot_pnl: 0.0,
+            perp_pnl: 0.0,
+            total_pnl,
+            roi_pct: 0.0,
+            hold_hours: 24.0,
+            is_winner: total_pnl > 0.0,
+            close_reason: CloseReason::BasisConverged,
+            entry_confidence: 0.8,
+            fees_paid: 0.0,
+            slippage_cost: 0.0,
+            market: crate::agentic::DEFAULT_MARKET.to_string(),
+            sizing: None,
+            expected_value: None,
+            hedge_tilt: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flags_outliers() {
+        let dir = std::env::temp_dir().join(format!("review-{}", uuid::Uuid::new_v4()));
+        let db = PerformanceDb::new(dir.join("perf.json").to_str().unwrap()).await.unwrap();
+        let reviewer = TradeReviewer::new(Arc::new(AppConfig::default_for_test()), Arc::new(db));
+        let history: Vec<TradeOutcome> = (0..10).map(|i| trade(&i.to_string(), 5.0 + (i % 3) as f64)).collect();
+
+        assert!(reviewer.flag(&trade("ok", 4.0), &history).is_none());
+
+        let item = reviewer.flag(&trade("loss", -50.0), &history).unwrap();
+        assert!(matches!(item.flags[0], ReviewFlag::OutsizedLoss { .. }));
+
+        // 30 bps of slippage, and $6.85 of funding expected at 20% APR over 300h
+        let mut costly = trade("costly", 1.0);
+        costly.slippage_cost = 3.0;
+        costly.entry_funding_apr = 20.0;
+        costly.hold_hours = 300.0;
+        let item = reviewer.flag(&costly, &history).unwrap();
+        assert_eq!(item.flags.len(), 2);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_flags_slippage_measured_on_the_legs() {
+        use crate::execution::{LegFills, LegReconciler, MockExecution, VenueLegs};
+        use crate::position::PositionManager;
+        use crate::state::SharedState;
+
+        let dir = std::env::temp_dir().join(format!("review-{}", uuid::Uuid::new_v4()));
+        let db = PerformanceDb::new(dir.join("perf.json").to_str().unwrap()).await.unwrap();
+        let config = AppConfig::default_for_test();
+        let mut execution_config = config.execution.clone();
+        execution_config.retry_delay_ms = 0;
+        let reconciler = LegReconciler::new(&execution_config);
+        let reviewer = TradeReviewer::new(Arc::new(config), Arc::new(db));
+
+        // Open and close 10 SOL with every fill slipping 10 bps
+        let state = Arc::new(SharedState::new());
+        state.update_spot_price(100.0);
+        state.update_perp_mark_price(100.5);
+        let execution = Arc::new(MockExecution::new(state.clone()).with_slippage_bps(10.0));
+        let legs = VenueLegs::new(execution, Arc::new(PositionManager::new(state)));
+        let open = reconciler.reconcile(&legs, LegFills::new(10.0, -10.0), legs.leg_fills().await.unwrap()).await;
+        let close = reconciler.reconcile(&legs, LegFills::default(), legs.leg_fills().await.unwrap()).await;
+
+        let mut slipped = trade("slipped", 1.0);
+        slipped.slippage_cost = open.slippage.total() + close.slippage.total();
+        let item = reviewer.flag(&slipped, &[]).unwrap();
+        assert_eq!(item.flags.len(), 1);
+        assert!(matches!(
+            item.flags[0],
+            ReviewFlag::ExtremeSlippage { cost, bps } if (cost - 4.01).abs() < 1e-9 && (bps - 40.1).abs() < 1e-6
+        ));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
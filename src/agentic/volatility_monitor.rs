@@ -0,0 +1,191 @@
+//! Realized Volatility Monitor
+//!
+//! Measures realized volatility of spot returns over a short window
+//! (`risk.volatility.window_mins`, 5 by default) and ranks each reading
+//! against the monitor's own history:
+//! - Realized vol: root sum of squared log returns of the sampled spot
+//!   price, annualized (%)
+//! - Elevated: above the `trigger_percentile` of readings over
+//!   `history_hours`, once `min_history` readings exist
+//!
+//! Basis trades suffer most in violent moves: legs fill apart and the basis
+//! gaps. While volatility is elevated the risk manager halves the target
+//! size or blocks entries, per `risk.volatility.action`.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::config::AppConfig;
+use crate::state::{Sample, SharedState, TimeSeries};
+use crate::telemetry::record_realized_volatility;
+use crate::utils::types::VolatilityStatus;
+
+/// Milliseconds per year, for annualizing
+const YEAR_MS: f64 = 365.0 * 86_400_000.0;
+
+/// Annualized realized volatility of a price series (%), None with fewer
+/// than two positive prices
+pub fn realized_vol_pct(prices: &[Sample]) -> Option<f64> {
+    let prices: Vec<&Sample> = prices.iter().filter(|s| s.value > 0.0).collect();
+    let (first, last) = (prices.first()?, prices.last()?);
+    let span_ms = (last.timestamp - first.timestamp) as f64;
+    if prices.len() < 2 || span_ms <= 0.0 {
+        return None;
+    }
+    let sum_sq: f64 = prices.windows(2).map(|w| (w[1].value / w[0].value).ln().powi(2)).sum();
+    Some((sum_sq * YEAR_MS / span_ms).sqrt() * 100.0)
+}
+
+/// Realized volatility monitor
+pub struct VolatilityMonitor {
+    config: Arc<AppConfig>,
+    state: Arc<SharedState>,
+    running: Arc<RwLock<bool>>,
+    /// Past readings, one per interval
+    history: Arc<TimeSeries>,
+}
+
+impl VolatilityMonitor {
+    pub fn new(config: Arc<AppConfig>, state: Arc<SharedState>) -> Self {
+        let vol = &config.risk.volatility;
+        let history = TimeSeries::new(
+            Duration::from_secs(vol.interval_secs),
+            Duration::from_secs(vol.history_hours * 3600),
+        );
+        Self {
+            config,
+            state,
+            running: Arc::new(RwLock::new(false)),
+            history: Arc::new(history),
+        }
+    }
+
+    /// Start measuring
+    pub async fn start(&self) -> anyhow::Result<()> {
+        if !self.config.risk.volatility.enabled {
+            info!("Volatility monitor disabled");
+            return Ok(());
+        }
+
+        *self.running.write().await = true;
+        info!("Volatility monitor starting");
+
+        let running = self.running.clone();
+        let state = self.state.clone();
+        let config = self.config.clone();
+        let history = self.history.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(config.risk.volatility.interval_secs));
+
+            while *running.read().await {
+                interval.tick().await;
+
+                let now = chrono::Utc::now().timestamp_millis();
+                let Some(status) = Self::measure(&state, &config, &history, now) else {
+                    continue;
+                };
+                debug!(
+                    "Realized vol {:.1}% (threshold {:?}, percentile {:?})",
+                    status.realized_vol_pct, status.threshold_pct, status.percentile
+                );
+                record_realized_volatility(status.realized_vol_pct, status.elevated);
+
+                let was_elevated = state.volatility.read().is_some_and(|s| s.elevated);
+                if status.elevated && !was_elevated {
+                    warn!(
+                        "Realized volatility elevated: {:.1}% above {:.1}% ({}th percentile)",
+                        status.realized_vol_pct,
+                        status.threshold_pct.unwrap_or(0.0),
+                        config.risk.volatility.trigger_percentile
+                    );
+                } else if !status.elevated && was_elevated {
+                    info!("Realized volatility back to normal: {:.1}%", status.realized_vol_pct);
+                }
+                *state.volatility.write() = Some(status);
+            }
+
+            info!("Volatility monitor stopped");
+        });
+
+        Ok(())
+    }
+
+    /// Measure the latest window and rank it against earlier readings
+    fn measure(state: &SharedState, config: &AppConfig, history: &TimeSeries, now: i64) -> Option<VolatilityStatus> {
+        let vol = &config.risk.volatility;
+        let window_ms = vol.window_mins as i64 * 60_000;
+        let realized_vol_pct = realized_vol_pct(&state.spot_history.since(now - window_ms))?;
+
+        let history_start = now - vol.history_hours as i64 * 3_600_000;
+        let threshold_pct = (history.range(history_start, now).len() >= vol.min_history)
+            .then(|| history.percentile(history_start, now, vol.trigger_percentile))
+            .flatten();
+        let percentile = history.percentile_rank(history_start, now, realized_vol_pct);
+        history.record(now, realized_vol_pct);
+
+        Some(VolatilityStatus {
+            realized_vol_pct,
+            threshold_pct,
+            percentile,
+            elevated: threshold_pct.is_some_and(|t| realized_vol_pct > t),
+            timestamp: now,
+        })
+    }
+
+    /// Stop measuring
+    pub async fn stop(&self) {
+        *self.running.write().await = false;
+        info!("Volatility monitor stopping");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices(moves: &[f64]) -> Vec<Sample> {
+        let mut price = 150.0;
+        let mut samples = vec![Sample { timestamp: 0, value: price }];
+        for (i, m) in moves.iter().enumerate() {
+            price *= 1.0 + m;
+            samples.push(Sample { timestamp: (i as i64 + 1) * 10_000, value: price });
+        }
+        samples
+    }
+
+    #[test]
+    fn test_realized_vol() {
+        assert_eq!(realized_vol_pct(&prices(&[])), None);
+        assert_eq!(realized_vol_pct(&prices(&[0.0; 30])), Some(0.0));
+
+        let calm = realized_vol_pct(&prices(&[0.0005, -0.0005].repeat(15))).unwrap();
+        let violent = realized_vol_pct(&prices(&[0.005, -0.005].repeat(15))).unwrap();
+        assert!((violent / calm - 10.0).abs() < 0.01);
+        // 0.05% moves every 10s, 3.15M of them a year: 0.05% × √3.15M ≈ 88.8%
+        assert!((calm - 88.8).abs() < 0.5, "{}", calm);
+    }
+
+    #[test]
+    fn test_elevated_above_own_percentile() {
+        let mut config = AppConfig::default_for_test();
+        config.risk.volatility.min_history = 10;
+        config.risk.volatility.window_mins = 5;
+        let state = SharedState::new();
+        let history = TimeSeries::new(Duration::from_secs(60), Duration::from_secs(86_400));
+        for i in 0..20 {
+            history.record(i * 60_000, 40.0 + i as f64);
+        }
+        let now = 20 * 60_000;
+        for (i, sample) in prices(&[0.005, -0.005].repeat(15)).iter().enumerate() {
+            state.spot_history.record(now - 300_000 + i as i64 * 10_000, sample.value);
+        }
+
+        let status = VolatilityMonitor::measure(&state, &config, &history, now).unwrap();
+        assert!(status.elevated);
+        assert_eq!(status.percentile, Some(100.0));
+        assert!(status.threshold_pct.unwrap() < 60.0);
+    }
+}
@@ -0,0 +1,243 @@
+//! Web Dashboard
+//!
+//! `GET /dashboard` serves a single page, embedded in the binary, that
+//! polls the JSON endpoints below and follows `/events` for live updates:
+//! - `GET /equity` - daily equity curve
+//! - `GET /positions` - open spot and perp legs with P&L
+//! - `GET /trades?limit=50` - most recent closed trades
+//! - `GET /history?topic=signal` - recent signal or alert (`topic=alert`)
+//!   events, including reversal alerts, kept since the API started
+//! - `GET /risk` - agent state, pause, connectivity and daily risk state
+//! - `GET /daily?days=30` - daily ledger: realized P&L, fees, funding and
+//!   trades per day
+//!
+//! The page itself holds no data; it passes `?token=` from its own URL on
+//! to the API, so it works behind the same bearer token.
+
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Html,
+    Json,
+};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tokio::sync::broadcast;
+
+use crate::agent::AgentState;
+use crate::agentic::{DailyEquity, DailyLedgerEntry, RiskState, TradeOutcome};
+use crate::network::Event;
+use crate::utils::types::Position;
+
+use super::ApiState;
+
+/// Embedded dashboard page
+const PAGE: &str = include_str!("dashboard.html");
+/// Events kept per topic
+const HISTORY_LEN: usize = 200;
+/// Most trades returned by `/trades`
+const MAX_TRADES: usize = 500;
+/// Most days returned by `/daily`
+const MAX_DAYS: usize = 366;
+
+/// One remembered event
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEntry {
+    /// Receive time (ms)
+    pub timestamp: i64,
+    pub event: Event,
+}
+
+/// Recent signal and alert events, for pages opened after they were sent
+#[derive(Default)]
+pub struct EventHistory {
+    signals: Mutex<VecDeque<HistoryEntry>>,
+    alerts: Mutex<VecDeque<HistoryEntry>>,
+}
+
+impl EventHistory {
+    /// Remember an event if it is a signal or an alert
+    pub fn record(&self, event: &Event) {
+        let buffer = match event.topic() {
+            "signal" => &self.signals,
+            "alert" => &self.alerts,
+            _ => return,
+        };
+        let mut buffer = buffer.lock();
+        if buffer.len() == HISTORY_LEN {
+            buffer.pop_front();
+        }
+        buffer.push_back(HistoryEntry { timestamp: chrono::Utc::now().timestamp_millis(), event: event.clone() });
+    }
+
+    /// Events of a topic, newest first
+    pub fn recent(&self, topic: &str) -> Option<Vec<HistoryEntry>> {
+        let buffer = match topic {
+            "signal" => &self.signals,
+            "alert" => &self.alerts,
+            _ => return None,
+        };
+        Some(buffer.lock().iter().rev().cloned().collect())
+    }
+
+    /// Record events until the bus closes
+    pub async fn run(&self, mut rx: broadcast::Receiver<Event>) {
+        loop {
+            match rx.recv().await {
+                Ok(event) => self.record(&event),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    }
+}
+
+/// `GET /trades` query
+#[derive(Debug, Deserialize)]
+pub(super) struct TradesQuery {
+    limit: Option<usize>,
+}
+
+/// `GET /daily` query
+#[derive(Debug, Deserialize)]
+pub(super) struct DailyQuery {
+    days: Option<usize>,
+}
+
+/// `GET /history` query
+#[derive(Debug, Deserialize)]
+pub(super) struct HistoryQuery {
+    topic: String,
+}
+
+/// `GET /positions` response
+#[derive(Debug, Serialize)]
+pub(super) struct PositionsResponse {
+    spot: Option<Position>,
+    perp: Option<Position>,
+    unrealized_pnl: f64,
+    realized_pnl: f64,
+    funding_received: f64,
+    /// Perp size versus spot size, off a perfect hedge (%)
+    hedge_drift_pct: f64,
+}
+
+/// `GET /risk` response
+#[derive(Debug, Serialize)]
+pub(super) struct RiskResponse {
+    agent_state: AgentState,
+    paused: bool,
+    pause_reason: Option<String>,
+    rpc_connected: bool,
+    ws_connected: bool,
+    /// Pending critical alerts
+    pending_alerts: usize,
+    /// Daily P&L and drawdown tracking, once a trade has closed
+    risk: Option<RiskState>,
+    timestamp: i64,
+}
+
+pub(super) async fn page(State(state): State<ApiState>) -> Result<Html<&'static str>, StatusCode> {
+    if state.dashboard_enabled {
+        Ok(Html(PAGE))
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
+
+pub(super) async fn equity(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<DailyEquity>>, StatusCode> {
+    state.authorize(&headers)?;
+    let agent = state.agent.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(Json(agent.performance_db().get_daily_equity().await))
+}
+
+pub(super) async fn positions(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<PositionsResponse>, StatusCode> {
+    state.authorize(&headers)?;
+    let shared = state.shared.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(Json(PositionsResponse {
+        spot: shared.spot_position.read().clone(),
+        perp: shared.perp_position.read().clone(),
+        unrealized_pnl: shared.unrealized_pnl.load(),
+        realized_pnl: shared.realized_pnl.load(),
+        funding_received: shared.total_funding_received.load(),
+        hedge_drift_pct: shared.hedge_drift.load(),
+    }))
+}
+
+pub(super) async fn trades(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<TradesQuery>,
+) -> Result<Json<Vec<TradeOutcome>>, StatusCode> {
+    state.authorize(&headers)?;
+    let agent = state.agent.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let limit = query.limit.unwrap_or(50).min(MAX_TRADES);
+    Ok(Json(agent.performance_db().get_recent_trades(limit).await))
+}
+
+pub(super) async fn daily(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<DailyQuery>,
+) -> Result<Json<Vec<DailyLedgerEntry>>, StatusCode> {
+    state.authorize(&headers)?;
+    let agent = state.agent.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let days = query.days.unwrap_or(30).min(MAX_DAYS);
+    Ok(Json(agent.risk_manager().daily_ledger().recent(days).to_vec()))
+}
+
+pub(super) async fn history(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<Vec<HistoryEntry>>, StatusCode> {
+    state.authorize(&headers)?;
+    state.history.recent(&query.topic).map(Json).ok_or(StatusCode::BAD_REQUEST)
+}
+
+pub(super) async fn risk(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<RiskResponse>, StatusCode> {
+    state.authorize(&headers)?;
+    let shared = state.shared.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let agent = state.agent.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(Json(RiskResponse {
+        agent_state: agent.current_state().await,
+        paused: *shared.is_paused.read(),
+        pause_reason: shared.pause_reason.read().clone(),
+        rpc_connected: *shared.rpc_connected.read(),
+        ws_connected: *shared.ws_connected.read(),
+        pending_alerts: state.alert_manager.pending_alerts().len(),
+        risk: agent.performance_db().load_risk_state().await.unwrap_or_default(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_keeps_recent_signals_and_alerts() {
+        let history = EventHistory::default();
+        history.record(&Event::FundingRateUpdate { rate: 0.0, timestamp: 0 });
+        for i in 0..HISTORY_LEN + 5 {
+            history.record(&Event::HedgeDriftAlert { drift_pct: i as f64, threshold_pct: 2.0 });
+        }
+        history.record(&Event::SystemResume);
+
+        let signals = history.recent("signal").unwrap();
+        assert_eq!(signals.len(), HISTORY_LEN);
+        assert!(matches!(&signals[0].event, Event::HedgeDriftAlert { drift_pct, .. } if *drift_pct == (HISTORY_LEN + 4) as f64));
+        assert!(history.recent("alert").unwrap().is_empty());
+        assert!(history.recent("price").is_none());
+    }
+}
@@ -0,0 +1,197 @@
+//! gRPC Control Service
+//!
+//! `BotControl` service (proto/control.proto) for external supervisors
+//! running a fleet of bots. Backed by the same TradingAgent and
+//! SharedState the binary runs on; the bearer token from the API config
+//! is checked on every call.
+
+use anyhow::{Context, Result};
+use std::pin::Pin;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tonic::{Request, Response, Status};
+use tracing::info;
+
+use crate::agent::live_interlock::tokens_match;
+use crate::agent::TradingAgent;
+use crate::config::ApiConfig;
+use crate::network::Event;
+use crate::state::SharedState;
+
+pub mod proto {
+    tonic::include_proto!("solbasis.control.v1");
+}
+
+use proto::bot_control_server::{BotControl, BotControlServer};
+use proto::{
+    CloseAllRequest, ControlReply, EventMessage, PauseRequest, ResumeRequest, SetSizeRequest,
+    StatusReply, StatusRequest, StreamEventsRequest, Transition, TransitionsReply, TransitionsRequest,
+};
+
+/// gRPC service implementation
+pub struct GrpcControl {
+    agent: Arc<TradingAgent>,
+    state: Arc<SharedState>,
+    event_tx: broadcast::Sender<Event>,
+}
+
+impl GrpcControl {
+    pub fn new(agent: Arc<TradingAgent>, state: Arc<SharedState>, event_tx: broadcast::Sender<Event>) -> Self {
+        Self { agent, state, event_tx }
+    }
+
+    /// Serve on the configured gRPC address until the task is aborted
+    pub async fn serve(self, config: &ApiConfig) -> Result<()> {
+        let addr = config.grpc_bind_address.parse()
+            .with_context(|| format!("Invalid gRPC bind address {}", config.grpc_bind_address))?;
+        config.check_exposure(&config.grpc_bind_address)?;
+        let token = config.token().map(str::to_string);
+
+        let service = BotControlServer::with_interceptor(self, move |request: Request<()>| {
+            authorize(token.as_deref(), &request)?;
+            Ok(request)
+        });
+
+        info!("gRPC control service listening on {}", addr);
+        tonic::transport::Server::builder()
+            .add_service(service)
+            .serve(addr)
+            .await
+            .context("gRPC control service failed")
+    }
+}
+
+/// Check the bearer token, if one is configured (only allowed on a
+/// loopback address)
+fn authorize(expected: Option<&str>, request: &Request<()>) -> Result<(), Status> {
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+
+    let provided = request
+        .metadata()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    if provided.is_some_and(|token| tokens_match(token, expected)) {
+        Ok(())
+    } else {
+        Err(Status::unauthenticated("invalid or missing bearer token"))
+    }
+}
+
+fn reply(message: impl Into<String>) -> Response<ControlReply> {
+    Response::new(ControlReply { ok: true, message: message.into() })
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<EventMessage, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl BotControl for GrpcControl {
+    async fn get_status(&self, _request: Request<StatusRequest>) -> Result<Response<StatusReply>, Status> {
+        let market = self.state.snapshot();
+        let control = self.agent.control();
+        let window = self.agent.trading_window();
+
+        Ok(Response::new(StatusReply {
+            agent_state: self.agent.current_state().await.to_string(),
+            operator_paused: control.is_paused(),
+            pause_reason: control.pause_reason().unwrap_or_default(),
+            spot_price: market.spot_price,
+            perp_mark_price: market.perp_mark_price,
+            basis_spread_pct: market.basis_spread,
+            funding_apr_pct: market.funding_apr,
+            realized_pnl: self.state.realized_pnl.load(),
+            unrealized_pnl: self.state.unrealized_pnl.load(),
+            trade_count: self.state.trade_count.load(Ordering::SeqCst),
+            size_override_sol: control.size_override(),
+            trading_window_open: window.open,
+            trading_window_reason: window.reason.unwrap_or_default(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }))
+    }
+
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        request: Request<StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let types = request.into_inner().types;
+
+        // Lagged receivers skip the missed events rather than ending the stream
+        let stream = BroadcastStream::new(self.event_tx.subscribe()).filter_map(move |event| {
+            let event = event.ok()?;
+            if !types.is_empty() && !types.iter().any(|t| t == event.kind()) {
+                return None;
+            }
+            let json = serde_json::to_string(&event).ok()?;
+            Some(Ok(EventMessage {
+                r#type: event.kind().to_string(),
+                json,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            }))
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn pause(&self, request: Request<PauseRequest>) -> Result<Response<ControlReply>, Status> {
+        let reason = request.into_inner().reason;
+        let reason = if reason.is_empty() { "Paused via gRPC".to_string() } else { reason };
+        self.agent.control().pause(&reason);
+        Ok(reply(format!("paused: {}", reason)))
+    }
+
+    async fn resume(&self, _request: Request<ResumeRequest>) -> Result<Response<ControlReply>, Status> {
+        self.agent.control().resume();
+        Ok(reply("resumed"))
+    }
+
+    async fn set_size(&self, request: Request<SetSizeRequest>) -> Result<Response<ControlReply>, Status> {
+        let size = request.into_inner().size_sol;
+        if let Some(size) = size {
+            if !size.is_finite() || size <= 0.0 {
+                return Err(Status::invalid_argument("size_sol must be positive"));
+            }
+        }
+        self.agent.control().set_size(size);
+        Ok(reply(match size {
+            Some(size) => format!("size fixed at {:.4} SOL", size),
+            None => "adaptive sizing restored".to_string(),
+        }))
+    }
+
+    async fn close_all(&self, _request: Request<CloseAllRequest>) -> Result<Response<ControlReply>, Status> {
+        self.agent.control().request_close_all();
+        Ok(reply("close requested"))
+    }
+
+    async fn get_transitions(
+        &self,
+        request: Request<TransitionsRequest>,
+    ) -> Result<Response<TransitionsReply>, Status> {
+        let request = request.into_inner();
+        let to = if request.to == 0 { i64::MAX } else { request.to };
+        let transitions = self.agent.get_transitions(request.from, to).await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .into_iter()
+            .filter(|t| request.trade_id.is_empty() || t.trade_id.as_deref() == Some(request.trade_id.as_str()))
+            .map(|t| Transition {
+                from_state: t.from.to_string(),
+                to_state: t.to.to_string(),
+                timestamp: t.timestamp,
+                reason: t.reason.unwrap_or_default(),
+                trade_id: t.trade_id.unwrap_or_default(),
+                spot_price: t.spot_price,
+                perp_mark_price: t.perp_mark_price,
+                basis_spread_pct: t.basis_spread_pct,
+                funding_apr_pct: t.funding_apr_pct,
+            })
+            .collect();
+        Ok(Response::new(TransitionsReply { transitions }))
+    }
+}
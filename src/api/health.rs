@@ -0,0 +1,182 @@
+//! Health Probes
+//!
+//! Unauthenticated endpoints for Kubernetes probes and systemd watchdogs,
+//! answering 200 when healthy and 503 otherwise, with the checks as JSON:
+//! - `GET /healthz` (liveness) - the agent loop is ticking within
+//!   `max_agent_lag_ms`, and prices have not been silent for
+//!   `max_feed_outage_secs`; failing it means the process is wedged and
+//!   should be restarted
+//! - `GET /readyz` (readiness) - liveness, plus spot and perp prices within
+//!   `max_price_age_ms`, RPC connected (with `require_rpc`), and the agent
+//!   started and not in one of `unready_states`
+//!
+//! Before the agent's first tick and the first price, liveness passes so a
+//! starting bot is not killed; readiness fails until both arrive.
+
+use axum::{extract::State, http::StatusCode, Json};
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+
+use crate::agent::AgentState;
+use crate::config::HealthConfig;
+
+use super::ApiState;
+
+/// Inputs to the probes, read at one instant
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProbeInputs {
+    pub now: i64,
+    /// Last agent loop tick (ms, 0 before the first)
+    pub agent_heartbeat: i64,
+    pub agent_state: Option<AgentState>,
+    /// Last spot and perp price updates (ms, 0 before the first)
+    pub spot_updated_at: i64,
+    pub perp_updated_at: i64,
+    pub rpc_connected: bool,
+}
+
+/// One probe check
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+/// Probe result
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub healthy: bool,
+    pub checks: Vec<HealthCheck>,
+    pub timestamp: i64,
+}
+
+impl HealthReport {
+    fn new(checks: Vec<HealthCheck>, timestamp: i64) -> Self {
+        Self { healthy: checks.iter().all(|c| c.ok), checks, timestamp }
+    }
+}
+
+fn check(name: &'static str, ok: bool, detail: String) -> HealthCheck {
+    HealthCheck { name, ok, detail }
+}
+
+/// Liveness checks: the process is making progress
+pub fn liveness(inputs: &ProbeInputs, config: &HealthConfig) -> Vec<HealthCheck> {
+    let mut checks = Vec::new();
+    if inputs.agent_heartbeat == 0 {
+        checks.push(check("agent_loop", true, "starting".to_string()));
+    } else {
+        let lag = inputs.now - inputs.agent_heartbeat;
+        checks.push(check("agent_loop", lag <= config.max_agent_lag_ms as i64, format!("last tick {}ms ago", lag)));
+    }
+
+    let last_price = inputs.spot_updated_at.min(inputs.perp_updated_at);
+    if config.max_feed_outage_secs > 0 && last_price > 0 {
+        let silent = (inputs.now - last_price) / 1000;
+        checks.push(check(
+            "feeds",
+            silent <= config.max_feed_outage_secs as i64,
+            format!("oldest price {}s old", silent),
+        ));
+    }
+    checks
+}
+
+/// Readiness checks: liveness plus fit to trade
+pub fn readiness(inputs: &ProbeInputs, config: &HealthConfig) -> Vec<HealthCheck> {
+    let mut checks = liveness(inputs, config);
+
+    let fresh = |updated_at: i64| updated_at > 0 && inputs.now - updated_at <= config.max_price_age_ms as i64;
+    let age = |updated_at: i64| {
+        if updated_at > 0 {
+            format!("{}ms", inputs.now - updated_at)
+        } else {
+            "none yet".to_string()
+        }
+    };
+    checks.push(check(
+        "prices",
+        fresh(inputs.spot_updated_at) && fresh(inputs.perp_updated_at),
+        format!("spot {}, perp {}", age(inputs.spot_updated_at), age(inputs.perp_updated_at)),
+    ));
+
+    if config.require_rpc {
+        checks.push(check("rpc", inputs.rpc_connected, if inputs.rpc_connected { "connected" } else { "disconnected" }.to_string()));
+    }
+
+    let agent = match inputs.agent_state {
+        Some(state) if inputs.agent_heartbeat > 0 => {
+            check("agent_state", !config.unready_states.contains(&state), state.to_string())
+        }
+        _ => check("agent_state", false, "not started".to_string()),
+    };
+    checks.push(agent);
+    checks
+}
+
+async fn inputs(state: &ApiState) -> ProbeInputs {
+    let now = chrono::Utc::now().timestamp_millis();
+    let agent_state = match &state.agent {
+        Some(agent) => Some(agent.current_state().await),
+        None => None,
+    };
+    let Some(shared) = &state.shared else {
+        return ProbeInputs { now, agent_state, ..ProbeInputs::default() };
+    };
+    let market = shared.snapshot();
+    ProbeInputs {
+        now,
+        agent_heartbeat: shared.agent_heartbeat.load(Ordering::Relaxed),
+        agent_state,
+        spot_updated_at: market.spot_updated_at,
+        perp_updated_at: market.perp_updated_at,
+        rpc_connected: *shared.rpc_connected.read(),
+    }
+}
+
+fn respond(report: HealthReport) -> (StatusCode, Json<HealthReport>) {
+    let status = if report.healthy { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(report))
+}
+
+pub(super) async fn healthz(State(state): State<ApiState>) -> (StatusCode, Json<HealthReport>) {
+    let inputs = inputs(&state).await;
+    respond(HealthReport::new(liveness(&inputs, &state.health), inputs.now))
+}
+
+pub(super) async fn readyz(State(state): State<ApiState>) -> (StatusCode, Json<HealthReport>) {
+    let inputs = inputs(&state).await;
+    respond(HealthReport::new(readiness(&inputs, &state.health), inputs.now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_liveness_and_readiness() {
+        let config = HealthConfig::default();
+        let now = 1_000_000_000;
+        let starting = ProbeInputs { now, ..ProbeInputs::default() };
+        assert!(HealthReport::new(liveness(&starting, &config), now).healthy);
+        assert!(!HealthReport::new(readiness(&starting, &config), now).healthy);
+
+        let running = ProbeInputs {
+            now,
+            agent_heartbeat: now - 1_000,
+            agent_state: Some(AgentState::Idle),
+            spot_updated_at: now - 500,
+            perp_updated_at: now - 2_000,
+            rpc_connected: true,
+        };
+        assert!(HealthReport::new(readiness(&running, &config), now).healthy);
+
+        // Stale prices and the Error state only fail readiness; a stalled loop fails liveness
+        let degraded = ProbeInputs { perp_updated_at: now - 60_000, agent_state: Some(AgentState::Error), ..running };
+        assert!(HealthReport::new(liveness(&degraded, &config), now).healthy);
+        assert_eq!(readiness(&degraded, &config).iter().filter(|c| !c.ok).count(), 2);
+        let wedged = ProbeInputs { agent_heartbeat: now - 120_000, ..running };
+        assert!(!HealthReport::new(liveness(&wedged, &config), now).healthy);
+    }
+}
@@ -0,0 +1,608 @@
+//! Control API
+//!
+//! Small HTTP API for operating the bot while it runs:
+//! - `GET  /status` - agent state and execution phase, trading window
+//!   status, wallet gas and spot inventory
+//! - `GET  /alerts` - critical alerts awaiting acknowledgement
+//! - `POST /alerts/:id/ack` - acknowledge an alert and stop its escalation
+//! - `GET  /events` - live event stream (SSE), filterable by topic
+//! - `POST /trades/open` - open a basis position of `{"size_sol": ..}`
+//! - `POST /trades/:id/close` - close the open trade with this id
+//! - `POST /hedge/adjust` - trade the perp leg by `{"perp_delta_sol": ..}`
+//! - `GET  /commands` - manual command waiting for the agent, if any
+//! - `GET  /thresholds` - entry thresholds in use, their adjustment log and
+//!   the trade-frequency governor
+//! - `POST /thresholds/rollback` - back to the configured entry thresholds
+//! - `GET  /risk/overrides` - risk limit overrides in force
+//! - `POST /risk/overrides` - override a limit for a while: `{"limit":
+//!   "daily_loss", "value": .., "duration_mins": .., "reason": ..}`
+//! - `DELETE /risk/overrides/:limit` - back to the configured limit
+//! - `GET  /live` - live trading interlock state
+//! - `POST /live/confirm` - release a live bot's hold: `{"token": ..,
+//!   "operator": ..}` with `api.live_confirmation.confirm_token`
+//! - `GET  /strategies`, `GET /strategies/:name` - named strategy status
+//! - `POST /strategies/:name/pause`, `/resume` - pause or resume one strategy
+//! - `POST /strategies/:name/trades/open`, `/trades/:id/close` - manual
+//!   commands for one strategy (not for a shadow)
+//! - `GET  /strategies/:name/compare` - a shadow's trades against the
+//!   primary agent's
+//! - `GET  /dashboard` - web dashboard, backed by the read-only endpoints
+//!   in `dashboard`
+//! - `GET  /healthz`, `GET /readyz` - liveness and readiness probes (see
+//!   `health`), served without the bearer token
+//!
+//! Manual commands are queued one at a time (409 while one is pending) and
+//! run by the agent loop through its usual risk checks and execution path.
+//!
+//! With the `grpc` feature, a `BotControl` gRPC service (see `grpc`) exposes
+//! status, event streaming and operator commands for external supervisors.
+
+pub mod dashboard;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod health;
+pub mod stream;
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    routing::{delete, get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::agent::{
+    AgentControl, AgentStatus, LiveConfirmation, LiveInterlock, LiveStatus, ManualCommand, RiskLimit, RiskOverride,
+    ShadowComparison, Strategy, StrategyStatus, TradingAgent,
+};
+use crate::agent::live_interlock::tokens_match;
+use crate::config::{ApiConfig, HealthConfig};
+use crate::engines::{TradingWindow, WindowStatus};
+use crate::execution::{InventoryReport, Treasury};
+use crate::network::Event;
+use crate::state::SharedState;
+use crate::telemetry::{AlertManager, PendingAlert};
+use crate::agentic::ThresholdAdjustment;
+use crate::utils::types::{EntryThresholds, GasStatus, GovernorStatus};
+
+/// Shared handler state
+#[derive(Clone)]
+struct ApiState {
+    alert_manager: Arc<AlertManager>,
+    event_tx: broadcast::Sender<Event>,
+    trading_window: TradingWindow,
+    /// Bot state, for the wallet gas reading
+    shared: Option<Arc<SharedState>>,
+    /// Spot inventory, for idle capital
+    treasury: Option<Arc<Treasury>>,
+    /// Operator controls, for manual trade commands
+    control: Option<Arc<AgentControl>>,
+    /// Agent state, trade history and risk state, for the dashboard
+    agent: Option<Arc<TradingAgent>>,
+    /// Named strategies run next to the primary agent
+    strategies: Arc<Vec<Arc<Strategy>>>,
+    /// Hold on a live bot, until confirmed
+    live: Option<Arc<LiveInterlock>>,
+    /// Recent signals and alerts, for the dashboard
+    history: Arc<dashboard::EventHistory>,
+    dashboard_enabled: bool,
+    /// Probe failure criteria
+    health: HealthConfig,
+    auth_token: Option<String>,
+}
+
+/// `POST /trades/open` body
+#[derive(Debug, Deserialize)]
+struct OpenRequest {
+    size_sol: f64,
+}
+
+/// `POST /hedge/adjust` body
+#[derive(Debug, Deserialize)]
+struct HedgeRequest {
+    perp_delta_sol: f64,
+}
+
+/// `POST /risk/overrides` body
+#[derive(Debug, Deserialize)]
+struct OverrideRequest {
+    limit: RiskLimit,
+    value: f64,
+    duration_mins: u64,
+    reason: String,
+}
+
+/// `POST /live/confirm` body
+#[derive(Debug, Deserialize)]
+struct LiveConfirmRequest {
+    token: String,
+    /// Who confirms, for the log and alert
+    operator: String,
+}
+
+/// `GET /status` response
+#[derive(Debug, Serialize)]
+struct StatusResponse {
+    /// Agent state, with the execution phase while a trade executes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    agent: Option<AgentStatus>,
+    trading_window: WindowStatus,
+    /// Latest wallet SOL check, once the gas watchdog has run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas: Option<GasStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inventory: Option<InventoryReport>,
+    timestamp: i64,
+}
+
+impl ApiState {
+    /// Named strategy by name
+    fn strategy(&self, name: &str) -> Option<Arc<Strategy>> {
+        self.strategies.iter().find(|s| s.name == name).cloned()
+    }
+
+    /// Check the bearer token, if one is configured (only allowed on a
+    /// loopback address)
+    fn authorize(&self, headers: &HeaderMap) -> Result<(), StatusCode> {
+        self.authorize_with(headers, None)
+    }
+
+    /// Check the bearer token, also accepting it from a query parameter
+    fn authorize_with(&self, headers: &HeaderMap, query_token: Option<&str>) -> Result<(), StatusCode> {
+        let Some(expected) = self.auth_token.as_deref().filter(|t| !t.is_empty()) else {
+            return Ok(());
+        };
+
+        let provided = headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .or(query_token);
+
+        if provided.is_some_and(|token| tokens_match(token, expected)) {
+            Ok(())
+        } else {
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
+}
+
+/// Control API server
+pub struct ControlApi {
+    /// Configuration
+    config: ApiConfig,
+    /// Handler state
+    state: ApiState,
+}
+
+impl ControlApi {
+    /// Create a new control API
+    pub fn new(
+        config: &ApiConfig,
+        alert_manager: Arc<AlertManager>,
+        event_tx: broadcast::Sender<Event>,
+        trading_window: TradingWindow,
+    ) -> Self {
+        Self {
+            config: config.clone(),
+            state: ApiState {
+                alert_manager,
+                event_tx,
+                trading_window,
+                shared: None,
+                treasury: None,
+                control: None,
+                agent: None,
+                strategies: Arc::new(Vec::new()),
+                live: None,
+                history: Arc::new(dashboard::EventHistory::default()),
+                dashboard_enabled: config.dashboard_enabled,
+                health: config.health.clone(),
+                auth_token: config.auth_token.clone(),
+            },
+        }
+    }
+
+    /// Report readings from the bot's shared state on `/status`
+    pub fn with_shared_state(mut self, shared: Arc<SharedState>) -> Self {
+        self.state.shared = Some(shared);
+        self
+    }
+
+    /// Report spot inventory and idle capital on `/status`
+    pub fn with_treasury(mut self, treasury: Arc<Treasury>) -> Self {
+        self.state.treasury = Some(treasury);
+        self
+    }
+
+    /// Accept manual trade commands for the agent
+    pub fn with_control(mut self, control: Arc<AgentControl>) -> Self {
+        self.state.control = Some(control);
+        self
+    }
+
+    /// Serve the agent's state, trades and equity curve to the dashboard
+    pub fn with_agent(mut self, agent: Arc<TradingAgent>) -> Self {
+        self.state.agent = Some(agent);
+        self
+    }
+
+    /// Serve status and controls of the named strategies
+    pub fn with_strategies(mut self, strategies: Vec<Arc<Strategy>>) -> Self {
+        self.state.strategies = Arc::new(strategies);
+        self
+    }
+
+    /// Accept the confirmation that releases a live bot
+    pub fn with_live_interlock(mut self, live: Arc<LiveInterlock>) -> Self {
+        self.state.live = Some(live);
+        self
+    }
+
+    /// Build the router
+    fn router(&self) -> Router {
+        Router::new()
+            .route("/status", get(status))
+            .route("/alerts", get(list_alerts))
+            .route("/alerts/:id/ack", post(ack_alert))
+            .route("/events", get(stream::stream_events))
+            .route("/trades/open", post(open_trade))
+            .route("/trades/:id/close", post(close_trade))
+            .route("/hedge/adjust", post(adjust_hedge))
+            .route("/commands", get(pending_command))
+            .route("/thresholds", get(entry_thresholds))
+            .route("/thresholds/rollback", post(rollback_thresholds))
+            .route("/risk/overrides", get(list_overrides).post(set_override))
+            .route("/risk/overrides/:limit", delete(clear_override))
+            .route("/live", get(live_status))
+            .route("/live/confirm", post(confirm_live))
+            .route("/strategies", get(list_strategies))
+            .route("/strategies/:name", get(strategy_status))
+            .route("/strategies/:name/pause", post(pause_strategy))
+            .route("/strategies/:name/resume", post(resume_strategy))
+            .route("/strategies/:name/compare", get(compare_strategy))
+            .route("/strategies/:name/trades/open", post(open_strategy_trade))
+            .route("/strategies/:name/trades/:id/close", post(close_strategy_trade))
+            .route("/dashboard", get(dashboard::page))
+            .route("/equity", get(dashboard::equity))
+            .route("/positions", get(dashboard::positions))
+            .route("/trades", get(dashboard::trades))
+            .route("/history", get(dashboard::history))
+            .route("/risk", get(dashboard::risk))
+            .route("/daily", get(dashboard::daily))
+            .route("/healthz", get(health::healthz))
+            .route("/readyz", get(health::readyz))
+            .with_state(self.state.clone())
+    }
+
+    /// Serve until the task is aborted
+    pub async fn serve(self) -> Result<()> {
+        self.config.check_exposure(&self.config.bind_address)?;
+        let listener = tokio::net::TcpListener::bind(&self.config.bind_address)
+            .await
+            .with_context(|| format!("Failed to bind control API on {}", self.config.bind_address))?;
+
+        info!("Control API listening on {}", self.config.bind_address);
+        let history = self.state.history.clone();
+        let events = self.state.event_tx.subscribe();
+        tokio::select! {
+            result = axum::serve(listener, self.router()) => result.context("Control API server failed"),
+            _ = history.run(events) => Ok(()),
+        }
+    }
+}
+
+async fn status(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<StatusResponse>, StatusCode> {
+    state.authorize(&headers)?;
+    let agent = match &state.agent {
+        Some(agent) => Some(agent.status().await),
+        None => None,
+    };
+    Ok(Json(StatusResponse {
+        agent,
+        trading_window: state.trading_window.status(),
+        gas: state.shared.as_ref().and_then(|s| *s.gas.read()),
+        inventory: state.treasury.as_ref().map(|t| t.current()),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    }))
+}
+
+async fn list_alerts(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<PendingAlert>>, StatusCode> {
+    state.authorize(&headers)?;
+    Ok(Json(state.alert_manager.pending_alerts()))
+}
+
+async fn ack_alert(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> StatusCode {
+    if let Err(status) = state.authorize(&headers) {
+        return status;
+    }
+
+    if state.alert_manager.acknowledge(&id) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+async fn open_trade(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<OpenRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    submit(&state, &headers, ManualCommand::Open { size_sol: request.size_sol })
+}
+
+async fn close_trade(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(trade_id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    submit(&state, &headers, ManualCommand::Close { trade_id })
+}
+
+async fn adjust_hedge(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<HedgeRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    submit(&state, &headers, ManualCommand::AdjustHedge { perp_delta_sol: request.perp_delta_sol })
+}
+
+async fn pending_command(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<Option<ManualCommand>>, StatusCode> {
+    state.authorize(&headers)?;
+    let control = state.control.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(Json(control.pending_manual()))
+}
+
+/// `GET /thresholds` response
+#[derive(Debug, Serialize)]
+struct ThresholdsResponse {
+    current: EntryThresholds,
+    learning: bool,
+    adjustments: Vec<ThresholdAdjustment>,
+    /// None while the governor is disabled
+    governor: Option<GovernorStatus>,
+}
+
+async fn entry_thresholds(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<ThresholdsResponse>, (StatusCode, String)> {
+    state.authorize(&headers).map_err(|status| (status, String::new()))?;
+    let agent = state.agent.as_ref().ok_or((StatusCode::SERVICE_UNAVAILABLE, String::new()))?;
+    let learner = agent.threshold_learner();
+    let adjustments = learner.history().await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(ThresholdsResponse {
+        current: learner.current(),
+        learning: learner.enabled(),
+        adjustments,
+        governor: agent.frequency_governor().status(),
+    }))
+}
+
+async fn rollback_thresholds(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<ThresholdAdjustment>, (StatusCode, String)> {
+    state.authorize(&headers).map_err(|status| (status, String::new()))?;
+    let agent = state.agent.as_ref().ok_or((StatusCode::SERVICE_UNAVAILABLE, String::new()))?;
+    let rollback = agent
+        .threshold_learner()
+        .rollback()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(rollback))
+}
+
+async fn list_overrides(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<RiskOverride>>, StatusCode> {
+    state.authorize(&headers)?;
+    let agent = state.agent.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(Json(agent.risk_manager().overrides()))
+}
+
+async fn set_override(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<OverrideRequest>,
+) -> Result<Json<RiskOverride>, (StatusCode, String)> {
+    state.authorize(&headers).map_err(|status| (status, String::new()))?;
+    let agent = state.agent.as_ref().ok_or((StatusCode::SERVICE_UNAVAILABLE, String::new()))?;
+    if request.reason.trim().is_empty() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "An override needs a reason".to_string()));
+    }
+    agent
+        .risk_manager()
+        .set_override(request.limit, request.value, request.duration_mins, request.reason.trim())
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))
+}
+
+async fn clear_override(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(limit): Path<RiskLimit>,
+) -> Result<Json<RiskOverride>, (StatusCode, String)> {
+    state.authorize(&headers).map_err(|status| (status, String::new()))?;
+    let agent = state.agent.as_ref().ok_or((StatusCode::SERVICE_UNAVAILABLE, String::new()))?;
+    agent
+        .risk_manager()
+        .clear_override(limit)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, format!("{} is not overridden", limit)))
+}
+
+async fn live_status(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<LiveStatus>, (StatusCode, String)> {
+    state.authorize(&headers).map_err(|status| (status, String::new()))?;
+    let live = state.live.as_ref().ok_or((StatusCode::NOT_FOUND, "Live trading needs no confirmation".to_string()))?;
+    Ok(Json(live.status()))
+}
+
+async fn confirm_live(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<LiveConfirmRequest>,
+) -> Result<Json<LiveConfirmation>, (StatusCode, String)> {
+    state.authorize(&headers).map_err(|status| (status, String::new()))?;
+    let live = state.live.as_ref().ok_or((StatusCode::NOT_FOUND, "Live trading needs no confirmation".to_string()))?;
+    if request.operator.trim().is_empty() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, "The confirming operator must be named".to_string()));
+    }
+    live.confirm(&request.token, request.operator.trim())
+        .map(Json)
+        .map_err(|e| (StatusCode::FORBIDDEN, e.to_string()))
+}
+
+async fn list_strategies(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<StrategyStatus>>, StatusCode> {
+    state.authorize(&headers)?;
+    let mut statuses = Vec::with_capacity(state.strategies.len());
+    for strategy in state.strategies.iter() {
+        statuses.push(strategy.status().await);
+    }
+    Ok(Json(statuses))
+}
+
+async fn strategy_status(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> Result<Json<StrategyStatus>, StatusCode> {
+    state.authorize(&headers)?;
+    let strategy = state.strategy(&name).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(strategy.status().await))
+}
+
+async fn compare_strategy(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> Result<Json<ShadowComparison>, (StatusCode, String)> {
+    state.authorize(&headers).map_err(|status| (status, String::new()))?;
+    let strategy = state.strategy(&name).ok_or((StatusCode::NOT_FOUND, format!("No strategy {}", name)))?;
+    let agent = state.agent.as_ref().ok_or((StatusCode::SERVICE_UNAVAILABLE, "No primary agent".to_string()))?;
+    let comparison = strategy
+        .compare(agent.performance_db())
+        .await
+        .ok_or((StatusCode::CONFLICT, format!("Strategy {} is not a shadow", name)))?;
+    Ok(Json(comparison))
+}
+
+async fn pause_strategy(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> StatusCode {
+    if let Err(status) = state.authorize(&headers) {
+        return status;
+    }
+    match state.strategy(&name) {
+        Some(strategy) => {
+            strategy.agent.control().pause("Paused via control API");
+            StatusCode::NO_CONTENT
+        }
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+async fn resume_strategy(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> StatusCode {
+    if let Err(status) = state.authorize(&headers) {
+        return status;
+    }
+    match state.strategy(&name) {
+        Some(strategy) => {
+            strategy.agent.control().resume();
+            StatusCode::NO_CONTENT
+        }
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+async fn open_strategy_trade(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    Json(request): Json<OpenRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state.authorize(&headers).map_err(|status| (status, String::new()))?;
+    let strategy = state.strategy(&name).ok_or((StatusCode::NOT_FOUND, format!("No strategy {}", name)))?;
+    shadow_guard(&strategy)?;
+    submit_to(&state, &headers, Some(strategy.agent.control()), ManualCommand::Open { size_sol: request.size_sol })
+}
+
+async fn close_strategy_trade(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path((name, trade_id)): Path<(String, String)>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state.authorize(&headers).map_err(|status| (status, String::new()))?;
+    let strategy = state.strategy(&name).ok_or((StatusCode::NOT_FOUND, format!("No strategy {}", name)))?;
+    shadow_guard(&strategy)?;
+    submit_to(&state, &headers, Some(strategy.agent.control()), ManualCommand::Close { trade_id })
+}
+
+/// Shadows only trade their own signals, to keep the A/B comparison clean
+fn shadow_guard(strategy: &Strategy) -> Result<(), (StatusCode, String)> {
+    if strategy.shadow {
+        return Err((StatusCode::CONFLICT, format!("Strategy {} is a shadow and takes no manual commands", strategy.name)));
+    }
+    Ok(())
+}
+
+/// Queue a manual command for the agent loop
+fn submit(
+    state: &ApiState,
+    headers: &HeaderMap,
+    command: ManualCommand,
+) -> Result<StatusCode, (StatusCode, String)> {
+    submit_to(state, headers, state.control.as_ref(), command)
+}
+
+/// Queue a manual command for the agent behind `control`
+fn submit_to(
+    state: &ApiState,
+    headers: &HeaderMap,
+    control: Option<&Arc<AgentControl>>,
+    command: ManualCommand,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state.authorize(headers).map_err(|status| (status, String::new()))?;
+    let control = control
+        .ok_or((StatusCode::SERVICE_UNAVAILABLE, "Agent controls unavailable".to_string()))?;
+    if control.pending_manual().is_some() {
+        return Err((StatusCode::CONFLICT, "A manual command is already pending".to_string()));
+    }
+    control.submit(command)
+        .map(|()| StatusCode::ACCEPTED)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))
+}
@@ -0,0 +1,122 @@
+//! Event Stream
+//!
+//! `GET /events` republishes event bus events as Server-Sent Events so
+//! dashboards can follow the bot in real time:
+//! - Each SSE message is named after the event type, with the event as JSON
+//! - `?topics=signal,position` limits the stream to topics
+//!   (connection, price, funding, basis, signal, position, alert, system)
+//! - `?types=TradeSignal` limits it to individual event types
+//! - `?token=...` authenticates clients that cannot set headers (EventSource)
+
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event as SseEvent, KeepAlive, Sse},
+};
+use futures::stream::{self, Stream};
+use serde::Deserialize;
+use std::convert::Infallible;
+use tokio::sync::broadcast;
+use tracing::debug;
+
+use crate::network::Event;
+
+use super::ApiState;
+
+/// Query parameters for `/events`
+#[derive(Debug, Default, Deserialize)]
+pub struct StreamQuery {
+    /// Comma-separated topics
+    pub topics: Option<String>,
+    /// Comma-separated event types
+    pub types: Option<String>,
+    /// Auth token, alternative to the Authorization header
+    pub token: Option<String>,
+}
+
+/// Which events a subscriber receives
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    topics: Vec<String>,
+    types: Vec<String>,
+}
+
+impl EventFilter {
+    pub fn from_query(query: &StreamQuery) -> Self {
+        let split = |s: &Option<String>| -> Vec<String> {
+            s.as_deref()
+                .unwrap_or("")
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        };
+        Self {
+            topics: split(&query.topics),
+            types: split(&query.types),
+        }
+    }
+
+    /// Whether an event passes the filter (no filters = everything)
+    pub fn matches(&self, event: &Event) -> bool {
+        if self.topics.is_empty() && self.types.is_empty() {
+            return true;
+        }
+        self.topics.iter().any(|t| t.eq_ignore_ascii_case(event.topic()))
+            || self.types.iter().any(|t| t == event.kind())
+    }
+}
+
+pub(super) async fn stream_events(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<StreamQuery>,
+) -> Result<Sse<impl Stream<Item = Result<SseEvent, Infallible>>>, StatusCode> {
+    state.authorize_with(&headers, query.token.as_deref())?;
+
+    let filter = EventFilter::from_query(&query);
+    let rx = state.event_tx.subscribe();
+
+    let events = stream::unfold((rx, filter), |(mut rx, filter)| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) if filter.matches(&event) => {
+                    let sse = SseEvent::default()
+                        .event(event.kind())
+                        .json_data(&event)
+                        .unwrap_or_else(|_| SseEvent::default().comment("unserializable event"));
+                    return Some((Ok(sse), (rx, filter)));
+                }
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    debug!("Event stream client lagged by {} events", n);
+                    let sse = SseEvent::default().event("Lagged").data(n.to_string());
+                    return Some((Ok(sse), (rx, filter)));
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_by_topic_and_type() {
+        let filter = EventFilter::from_query(&StreamQuery {
+            topics: Some("position, alert".to_string()),
+            types: Some("HedgeDriftAlert".to_string()),
+            token: None,
+        });
+
+        assert!(filter.matches(&Event::PositionClosed { position_id: "1".to_string(), pnl: 0.0 }));
+        assert!(filter.matches(&Event::HedgeDriftAlert { drift_pct: 3.0, threshold_pct: 2.0 }));
+        assert!(!filter.matches(&Event::FundingElevated { apr_pct: 30.0, threshold_pct: 10.0 }));
+        assert!(!filter.matches(&Event::FundingRateUpdate { rate: 0.0, timestamp: 0 }));
+        assert!(EventFilter::default().matches(&Event::SystemResume));
+    }
+}
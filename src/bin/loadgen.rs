@@ -0,0 +1,216 @@
+//! Event Pipeline Load Generator
+//!
+//! Pumps synthetic spot/perp price updates through the EventBus into
+//! SharedState with `apply_market_event`, as the bot's event processor
+//! does, optionally with the calculation engines running, and reports:
+//! - Throughput (events/sec sent and applied)
+//! - Consumer lag: events dropped by lagging subscribers and peak backlog
+//! - Per-event latency from publish to state update (p50/p90/p99/max)
+//!
+//! Exits non-zero when a threshold is exceeded, so it can gate CI runs:
+//!
+//! ```bash
+//! cargo run --release --bin loadgen -- --rate 20000 --secs 10 --max-p99-us 500
+//! ```
+
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock};
+use tokio::time::MissedTickBehavior;
+
+use sol_basis_bot::config::AppConfig;
+use sol_basis_bot::engines::EngineManager;
+use sol_basis_bot::network::{Event, EventBus};
+use sol_basis_bot::state::SharedState;
+use sol_basis_bot::utils::types::{PriceSource, PriceUpdate};
+
+/// Event pipeline load generator
+#[derive(Parser, Debug)]
+#[command(about)]
+struct Args {
+    /// Price updates per second
+    #[arg(long, default_value_t = 10_000)]
+    rate: u64,
+
+    /// Run duration (seconds)
+    #[arg(long, default_value_t = 10)]
+    secs: u64,
+
+    /// Event bus capacity (the bot uses 2048)
+    #[arg(long, default_value_t = 2048)]
+    capacity: usize,
+
+    /// Extra subscribers that only drain the bus (alerts, API streams, ...)
+    #[arg(long, default_value_t = 2)]
+    subscribers: usize,
+
+    /// Also run the funding, basis and signal engines, using this config
+    #[arg(long)]
+    engines: Option<PathBuf>,
+
+    /// Fail if p99 publish-to-state latency exceeds this (µs)
+    #[arg(long)]
+    max_p99_us: Option<u64>,
+
+    /// Fail if subscribers drop more than this many events
+    #[arg(long, default_value_t = 0)]
+    max_dropped: u64,
+}
+
+/// What one subscriber saw
+#[derive(Default)]
+struct ConsumerStats {
+    dropped: u64,
+    max_backlog: usize,
+    /// Publish-to-state latency per event (µs); only the state consumer records it
+    latencies_us: Vec<u64>,
+}
+
+/// Heartbeat timestamp marking the end of the run (engines keep the bus open)
+const END_OF_RUN: i64 = i64::MIN;
+
+fn now_us() -> i64 {
+    chrono::Utc::now().timestamp_micros()
+}
+
+/// Synthetic tick: a slow sine around $150 with the perp at a small premium.
+/// The timestamp carries the publish time in µs, for latency measurement.
+fn tick(seq: u64) -> Event {
+    let spot = 150.0 + (seq as f64 / 5_000.0).sin();
+    let update = |source, price| PriceUpdate { source, price, confidence: None, timestamp: now_us() };
+    if seq % 2 == 0 {
+        Event::SpotPriceUpdate(update(PriceSource::Pyth, spot))
+    } else {
+        Event::PerpMarkPriceUpdate(update(PriceSource::DriftMark, spot * 1.002))
+    }
+}
+
+/// Drain the bus until the end-of-run marker, optionally applying price updates to state
+async fn consume(mut rx: broadcast::Receiver<Event>, state: Option<Arc<SharedState>>, expected: u64) -> ConsumerStats {
+    let mut stats = ConsumerStats {
+        latencies_us: Vec::with_capacity(if state.is_some() { expected as usize } else { 0 }),
+        ..Default::default()
+    };
+    loop {
+        stats.max_backlog = stats.max_backlog.max(rx.len());
+        let event = match rx.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                stats.dropped += n;
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        if matches!(event, Event::Heartbeat { timestamp: END_OF_RUN }) {
+            break;
+        }
+
+        let Some(state) = &state else { continue };
+        let sent_us = match &event {
+            Event::SpotPriceUpdate(update) | Event::PerpMarkPriceUpdate(update) => update.timestamp,
+            _ => continue,
+        };
+        state.apply_market_event(&event);
+        stats.latencies_us.push((now_us() - sent_us).max(0) as u64);
+    }
+    stats
+}
+
+fn percentile(sorted: &[u64], pct: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((pct / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank]
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    anyhow::ensure!(args.rate > 0 && args.secs > 0, "rate and secs must be positive");
+
+    let state = Arc::new(SharedState::new());
+    let bus = EventBus::new(args.capacity);
+    let total = args.rate * args.secs;
+
+    let engines = match &args.engines {
+        Some(path) => {
+            let config = Arc::new(AppConfig::load(path)?);
+            let engines = EngineManager::new(config, state.clone(), bus.sender(), Arc::new(RwLock::new(None)));
+            engines.start().await?;
+            Some(engines)
+        }
+        None => None,
+    };
+
+    let state_consumer = tokio::spawn(consume(bus.subscribe(), Some(state.clone()), total));
+    let drains: Vec<_> = (0..args.subscribers)
+        .map(|_| tokio::spawn(consume(bus.subscribe(), None, total)))
+        .collect();
+
+    // Publish in 1ms batches, paced against elapsed time
+    let sender = bus.sender();
+    let started = Instant::now();
+    let mut ticker = tokio::time::interval(Duration::from_millis(1));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Burst);
+    let mut sent = 0u64;
+    while sent < total {
+        ticker.tick().await;
+        let due = (started.elapsed().as_secs_f64() * args.rate as f64) as u64;
+        while sent < due.min(total) {
+            let _ = sender.send(tick(sent));
+            sent += 1;
+        }
+    }
+    let publish_secs = started.elapsed().as_secs_f64();
+    let _ = sender.send(Event::Heartbeat { timestamp: END_OF_RUN });
+
+    let mut state_stats = state_consumer.await?;
+    let applied_secs = started.elapsed().as_secs_f64();
+    let mut dropped = state_stats.dropped;
+    let mut max_backlog = state_stats.max_backlog;
+    for drain in drains {
+        let stats = drain.await?;
+        dropped += stats.dropped;
+        max_backlog = max_backlog.max(stats.max_backlog);
+    }
+    if let Some(engines) = engines {
+        engines.stop().await;
+    }
+
+    state_stats.latencies_us.sort_unstable();
+    let latencies = &state_stats.latencies_us;
+    let p99 = percentile(latencies, 99.0);
+
+    println!("Event pipeline load test");
+    println!("  sent:        {} events in {:.2}s ({:.0}/s)", sent, publish_secs, sent as f64 / publish_secs);
+    let applied = state_stats.latencies_us.len();
+    println!("  applied:     {} events ({:.0}/s)", applied, applied as f64 / applied_secs);
+    println!("  dropped:     {} (across {} subscribers)", dropped, args.subscribers + 1);
+    println!("  max backlog: {} / {}", max_backlog, args.capacity);
+    println!(
+        "  latency µs:  p50 {} | p90 {} | p99 {} | max {}",
+        percentile(latencies, 50.0),
+        percentile(latencies, 90.0),
+        p99,
+        latencies.last().copied().unwrap_or(0),
+    );
+    println!("  final basis: {:.4}%", state.get_basis_spread());
+
+    let mut failures = Vec::new();
+    if dropped > args.max_dropped {
+        failures.push(format!("{} events dropped (max {})", dropped, args.max_dropped));
+    }
+    if let Some(max) = args.max_p99_us {
+        if p99 > max {
+            failures.push(format!("p99 latency {}µs (max {}µs)", p99, max));
+        }
+    }
+    if !failures.is_empty() {
+        anyhow::bail!("Load test failed: {}", failures.join(", "));
+    }
+    Ok(())
+}
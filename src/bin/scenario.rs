@@ -0,0 +1,73 @@
+//! Stress Scenario Runner
+//!
+//! Plays scenario files (or the canned library in `scenarios/`) through a
+//! paper-trading agent with mock feeds and execution, prints each run's
+//! state timeline, and exits non-zero if any expectation is unmet:
+//!
+//! ```bash
+//! cargo run --bin scenario                      # all canned scenarios
+//! cargo run --bin scenario -- --canned rpc_outage
+//! cargo run --bin scenario -- my_scenario.yaml --json
+//! ```
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+
+use sol_basis_bot::config::AppConfig;
+use sol_basis_bot::scenario::{self, Scenario};
+
+/// Stress scenario runner
+#[derive(Parser, Debug)]
+#[command(about)]
+struct Args {
+    /// Scenario files; runs the canned library when none are given
+    files: Vec<PathBuf>,
+
+    /// Run these canned scenarios by name
+    #[arg(long)]
+    canned: Vec<String>,
+
+    /// Base configuration
+    #[arg(short, long, default_value = "config.yaml")]
+    config: PathBuf,
+
+    /// Print reports as JSON lines
+    #[arg(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let config = AppConfig::load(&args.config)?;
+
+    let mut scenarios = Vec::new();
+    for path in &args.files {
+        scenarios.push(Scenario::load(path)?);
+    }
+    for name in &args.canned {
+        scenarios.push(Scenario::canned_named(name).with_context(|| format!("No canned scenario {}", name))?);
+    }
+    if scenarios.is_empty() {
+        scenarios = Scenario::canned();
+    }
+
+    let mut failed = Vec::new();
+    for scenario in &scenarios {
+        let report = scenario::run(scenario, &config).await?;
+        if args.json {
+            println!("{}", serde_json::to_string(&report)?);
+        } else {
+            print!("{}", report.summary());
+        }
+        if !report.passed() {
+            failed.push(report.name);
+        }
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!("{} of {} scenarios failed: {}", failed.len(), scenarios.len(), failed.join(", "));
+    }
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! Layered Configuration
+//!
+//! The effective config is merged from, lowest precedence first:
+//! - The base file (`config.yaml`)
+//! - An optional overlay file, e.g. a profile like `config.devnet.yaml`;
+//!   mappings merge key by key, anything else replaces the base value
+//! - Environment overrides: `SOLBOT__TRADING__MIN_BASIS_SPREAD_PCT=0.2`,
+//!   with the value parsed as YAML
+//!
+//! CLI flags are applied on top by the binary.
+
+use anyhow::{Context, Result};
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+
+/// Prefix of environment overrides; path segments are separated by `__`
+pub const ENV_PREFIX: &str = "SOLBOT__";
+
+/// Keys whose values are hidden when the config is printed
+const SECRET_KEYS: &[&str] = &["api_key", "api_secret", "auth_token", "confirm_token", "private_key"];
+
+/// Read a YAML file as an untyped value
+pub fn read_yaml(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {:?}", path))?;
+    serde_yaml::from_str(&content).with_context(|| format!("Failed to parse config file: {:?}", path))
+}
+
+/// Overlay file for a profile: `config.yaml` + `devnet` → `config.devnet.yaml`
+pub fn profile_path(base: &Path, profile: &str) -> PathBuf {
+    let stem = base.file_stem().and_then(|s| s.to_str()).unwrap_or("config");
+    let ext = base.extension().and_then(|s| s.to_str()).unwrap_or("yaml");
+    base.with_file_name(format!("{}.{}.{}", stem, profile, ext))
+}
+
+/// Deep-merge `overlay` into `base`
+pub fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Apply `SOLBOT__`-prefixed variables; returns the dotted keys that were set
+pub fn apply_env(config: &mut Value, vars: impl IntoIterator<Item = (String, String)>) -> Result<Vec<String>> {
+    let mut applied = Vec::new();
+    for (name, raw) in vars {
+        let Some(path) = name.strip_prefix(ENV_PREFIX) else { continue };
+        let keys: Vec<String> = path.split("__").map(str::to_lowercase).collect();
+        anyhow::ensure!(keys.iter().all(|k| !k.is_empty()), "Malformed config override {}", name);
+
+        let value: Value = serde_yaml::from_str(&raw).unwrap_or(Value::String(raw));
+        set_path(config, &keys, value).with_context(|| format!("Invalid config override {}", name))?;
+        applied.push(keys.join("."));
+    }
+    applied.sort();
+    Ok(applied)
+}
+
+fn set_path(config: &mut Value, keys: &[String], value: Value) -> Result<()> {
+    let (last, parents) = keys.split_last().context("Empty config path")?;
+    let mut node = config;
+    for key in parents {
+        if node.is_null() {
+            *node = Value::Mapping(Mapping::new());
+        }
+        let map = node.as_mapping_mut().with_context(|| format!("{} is not a section", key))?;
+        node = map.entry(Value::String(key.clone())).or_insert(Value::Null);
+    }
+    if node.is_null() {
+        *node = Value::Mapping(Mapping::new());
+    }
+    node.as_mapping_mut()
+        .context("Parent of the overridden key is not a section")?
+        .insert(Value::String(last.clone()), value);
+    Ok(())
+}
+
+/// Replace set secret values with a placeholder
+pub fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Mapping(map) => {
+            for (key, value) in map.iter_mut() {
+                let secret = key.as_str().map_or(false, |k| SECRET_KEYS.contains(&k));
+                if secret && !value.is_null() {
+                    *value = Value::String("<redacted>".to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        Value::Sequence(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_precedence() {
+        let mut config: Value = serde_yaml::from_str(
+            "trading:\n  min_basis_spread_pct: 0.1\n  max_position_size_sol: 10\nrpc:\n  primary_url: a\n",
+        ).unwrap();
+        let overlay: Value = serde_yaml::from_str("trading:\n  min_basis_spread_pct: 0.15\n").unwrap();
+        merge(&mut config, overlay);
+
+        let applied = apply_env(&mut config, vec![
+            ("SOLBOT__TRADING__MIN_BASIS_SPREAD_PCT".to_string(), "0.2".to_string()),
+            ("SOLBOT__API__AUTH_TOKEN".to_string(), "secret".to_string()),
+            ("PATH".to_string(), "/usr/bin".to_string()),
+        ]).unwrap();
+        assert_eq!(applied, vec!["api.auth_token", "trading.min_basis_spread_pct"]);
+
+        assert_eq!(config["trading"]["min_basis_spread_pct"].as_f64(), Some(0.2));
+        assert_eq!(config["trading"]["max_position_size_sol"].as_u64(), Some(10));
+        assert_eq!(config["rpc"]["primary_url"].as_str(), Some("a"));
+
+        redact_secrets(&mut config);
+        assert_eq!(config["api"]["auth_token"].as_str(), Some("<redacted>"));
+    }
+}
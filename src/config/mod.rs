@@ -2,17 +2,37 @@
 //!
 //! Handles loading and validation of the application configuration.
 
+pub mod layers;
+pub mod secrets;
+
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::time::Duration;
 use tracing::info;
 
+use crate::telemetry::AlertLevel;
+use crate::utils::types::FundingInterval;
+
 /// Main application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub rpc: RpcConfig,
+    /// Timeouts, retries and cool-off for outbound HTTP APIs
+    #[serde(default)]
+    pub http: HttpConfig,
+    /// Local clock check against RPC block time
+    #[serde(default)]
+    pub clock: ClockConfig,
     pub wallet: WalletConfig,
     pub trading: TradingConfig,
+    #[serde(default)]
+    pub signals: SignalConfig,
+    #[serde(default)]
+    pub trading_windows: TradingWindowConfig,
+    #[serde(default)]
+    pub state_timeouts: StateTimeoutConfig,
     pub risk: RiskConfig,
     pub rebalance: RebalanceConfig,
     pub execution: ExecutionConfig,
@@ -21,29 +41,237 @@ pub struct AppConfig {
     #[serde(default)]
     pub agentic: AgenticConfig,
     #[serde(default)]
+    pub api: ApiConfig,
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+    #[serde(default)]
     pub paper_trading: bool,
+    /// Build and simulate real transactions alongside paper fills, never submitting
+    #[serde(default)]
+    pub dry_run: bool,
     #[serde(default)]
     pub devnet: bool,
+    /// Endpoints and test helpers used when `devnet` is set
+    #[serde(default)]
+    pub devnet_profile: DevnetConfig,
+    /// Fault injection into RPC and WebSocket calls (needs the `chaos` feature)
+    #[serde(default)]
+    pub chaos: ChaosConfig,
+    /// Spot inventory accounting across trades
+    #[serde(default)]
+    pub treasury: TreasuryConfig,
+    /// Sampling intervals of the engines, detectors and agent loop
+    #[serde(default)]
+    pub engines: EnginesConfig,
+    /// Heartbeat watchdog that restarts stalled tasks
+    #[serde(default)]
+    pub supervisor: SupervisorConfig,
+    /// Session report written on shutdown
+    #[serde(default)]
+    pub report: SessionReportConfig,
+    /// Asset traded; a key of `markets`
+    #[serde(default = "default_market")]
+    pub market: String,
+    /// Per-asset perp market, oracle feed and spot token
+    #[serde(default = "default_markets")]
+    pub markets: BTreeMap<String, MarketConfig>,
+    /// Named strategy instances run next to the primary one
+    #[serde(default)]
+    pub strategies: Vec<StrategyConfig>,
 }
 
 impl AppConfig {
     pub fn load(path: &Path) -> Result<Self> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file: {:?}", path))?;
+        let mut value = layers::read_yaml(path)?;
+        secrets::resolve(&mut value)?;
         
-        let config: Self = serde_yaml::from_str(&content)
+        let mut config: Self = serde_yaml::from_value(value)
             .with_context(|| "Failed to parse config file")?;
+        config.apply_market()?;
         
         config.validate()?;
         info!("Configuration loaded from {:?}", path);
         Ok(config)
     }
     
-    fn validate(&self) -> Result<()> {
+    /// Load the base file, an optional overlay, then `SOLBOT__` environment
+    /// overrides (see `layers` for precedence)
+    pub fn load_layered(base: &Path, overlay: Option<&Path>) -> Result<Self> {
+        let mut value = layers::read_yaml(base)?;
+        if let Some(overlay) = overlay {
+            layers::merge(&mut value, layers::read_yaml(overlay)?);
+        }
+        let overrides = layers::apply_env(&mut value, std::env::vars())?;
+        secrets::resolve(&mut value)?;
+        
+        let mut config: Self = serde_yaml::from_value(value)
+            .with_context(|| "Failed to parse layered config")?;
+        config.apply_market()?;
+        config.validate()?;
+        info!(
+            "Configuration loaded from {:?} (overlay: {:?}, env overrides: {:?})",
+            base, overlay, overrides
+        );
+        Ok(config)
+    }
+    
+    /// Point the Drift market, Pyth feed and spot mint at the `market` entry
+    /// of the registry; devnet overrides come after
+    pub fn apply_market(&mut self) -> Result<()> {
+        let market = self.markets.get(&self.market)
+            .with_context(|| format!("market {:?} is not in markets", self.market))?
+            .clone();
+        self.protocols.drift.market_index = market.drift_market_index;
+        self.protocols.pyth.sol_usd_feed = market.pyth_feed;
+        self.protocols.jupiter.sol_mint = market.spot_mint;
+        self.protocols.jupiter.spot_decimals = market.decimals;
+        Ok(())
+    }
+    
+    /// Effective config of a named strategy: this config with the
+    /// strategy's overrides merged in, on paper, with its performance store
+    /// under `strategies/<name>/` (`shadow/<name>/` for a shadow) next to the
+    /// primary one unless the overrides place it
+    pub fn for_strategy(&self, strategy: &StrategyConfig) -> Result<Self> {
+        let mut value = serde_yaml::to_value(self).context("Failed to serialize config")?;
+        layers::merge(&mut value, strategy.overrides.clone());
+        let mut config: Self = serde_yaml::from_value(value)
+            .with_context(|| format!("Failed to apply overrides of strategy {}", strategy.name))?;
+        anyhow::ensure!(
+            config.market == self.market,
+            "strategy {} cannot change market: price feeds are shared",
+            strategy.name
+        );
+        if config.agentic.performance_db_path == self.agentic.performance_db_path {
+            let base = Path::new(&self.agentic.performance_db_path);
+            let file = base.file_name().unwrap_or(std::ffi::OsStr::new("performance.json"));
+            config.agentic.performance_db_path = base
+                .with_file_name(if strategy.shadow { "shadow" } else { "strategies" })
+                .join(&strategy.name)
+                .join(file)
+                .to_string_lossy()
+                .into_owned();
+        }
+        config.paper_trading = true;
+        config.strategies.clear();
+        config.validate()?;
+        Ok(config)
+    }
+    
+    /// Name of the traded perp market, e.g. "SOL-PERP"
+    pub fn perp_market(&self) -> String {
+        format!("{}-PERP", self.market)
+    }
+    
+    /// Point endpoints, oracle feeds and mints at devnet when `devnet` is set.
+    ///
+    /// Mainnet-only venues (Jito, CEX, Hyperliquid, Jupiter Perps) are disabled.
+    pub fn apply_devnet(&mut self) {
+        if !self.devnet {
+            return;
+        }
+        let profile = self.devnet_profile.clone();
+        self.rpc.primary_url = profile.rpc_url;
+        self.rpc.fallback_urls.clear();
+        self.rpc.ws_url = profile.ws_url;
+        self.protocols.pyth.sol_usd_feed = profile.pyth_sol_usd_feed;
+        self.protocols.drift.api_url = profile.drift_api_url;
+        self.protocols.drift.dlob_url = profile.dlob_url;
+        self.protocols.jupiter.usdc_mint = profile.usdc_mint;
+        self.execution.use_jito = false;
+        self.execution.cex.enabled = false;
+        self.protocols.hyperliquid.enabled = false;
+        self.protocols.jupiter_perps.enabled = false;
+        self.protocols.orca.enabled = false;
+        self.execution.pre_trade.cex_reference = false;
+        self.execution.perp_routing = PerpRouting::Drift;
+        info!("Devnet profile applied (RPC {})", self.rpc.primary_url);
+    }
+    
+    /// Effective configuration as YAML, with secrets redacted
+    pub fn to_redacted_yaml(&self) -> Result<String> {
+        let mut value = serde_yaml::to_value(self).context("Failed to serialize config")?;
+        layers::redact_secrets(&mut value);
+        serde_yaml::to_string(&value).context("Failed to serialize config")
+    }
+    
+    pub fn validate(&self) -> Result<()> {
         anyhow::ensure!(
             self.trading.min_basis_spread_pct > 0.0,
             "min_basis_spread_pct must be positive"
         );
+        anyhow::ensure!(
+            self.trading.basis_close_threshold_pct < self.trading.min_basis_spread_pct,
+            "basis_close_threshold_pct must be below min_basis_spread_pct (hysteresis band)"
+        );
+        anyhow::ensure!(
+            self.signals.confirmations_required > 0,
+            "signals.confirmations_required must be at least 1"
+        );
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.signals.entry_time.weight),
+            "signals.entry_time.weight must be between 0 and 1"
+        );
+        anyhow::ensure!(
+            self.signals.expected_value.default_hold_hours > 0.0 && self.signals.expected_value.spot_cost_bps >= 0.0,
+            "signals.expected_value.default_hold_hours must be positive and spot_cost_bps non-negative"
+        );
+        anyhow::ensure!(
+            self.signals.reentry.min_basis_improvement_pct >= 0.0
+                && self.signals.reentry.min_funding_improvement_pct >= 0.0,
+            "signals.reentry improvement deltas must be non-negative"
+        );
+        let governor = &self.signals.frequency_governor;
+        anyhow::ensure!(
+            governor.recent_trades > 0 && governor.recent_trades < governor.baseline_trades,
+            "signals.frequency_governor.recent_trades must be positive and below baseline_trades"
+        );
+        anyhow::ensure!(
+            governor.decay_margin_pct >= 0.0 && governor.step_pct > 0.0,
+            "signals.frequency_governor.decay_margin_pct must be non-negative and step_pct positive"
+        );
+        anyhow::ensure!(
+            self.protocols.oracles.usdc_peg.band_pct > 0.0,
+            "protocols.oracles.usdc_peg.band_pct must be positive"
+        );
+        anyhow::ensure!(
+            self.markets.contains_key(&self.market),
+            "market {:?} is not in markets",
+            self.market
+        );
+        for (symbol, market) in &self.markets {
+            anyhow::ensure!(
+                !market.pyth_feed.is_empty() && !market.spot_mint.is_empty() && market.decimals <= 12,
+                "markets.{} needs a pyth_feed, a spot_mint and at most 12 decimals",
+                symbol
+            );
+        }
+        let mut names = std::collections::HashSet::new();
+        for strategy in &self.strategies {
+            anyhow::ensure!(
+                !strategy.name.is_empty()
+                    && strategy.name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'),
+                "strategy name {:?} must be lowercase letters, digits and dashes",
+                strategy.name
+            );
+            anyhow::ensure!(names.insert(&strategy.name), "strategy {} is defined twice", strategy.name);
+            anyhow::ensure!(
+                strategy.overrides.is_null() || strategy.overrides.is_mapping(),
+                "strategies.{}.overrides must be a mapping",
+                strategy.name
+            );
+        }
+        for (factor, weight) in self.signals.scoring.weights() {
+            anyhow::ensure!(
+                (0.0..=1.0).contains(&weight),
+                "signals.scoring.{} must be between 0 and 1",
+                factor
+            );
+        }
+        anyhow::ensure!(
+            self.trading_windows.blackout_hours_utc.iter().all(|h| *h < 24),
+            "trading_windows.blackout_hours_utc must be between 0 and 23"
+        );
         anyhow::ensure!(
             self.trading.max_leverage > 0.0 && self.trading.max_leverage <= 10.0,
             "max_leverage must be between 0 and 10"
@@ -52,6 +280,23 @@ impl AppConfig {
             self.trading.slippage_tolerance_pct > 0.0 && self.trading.slippage_tolerance_pct <= 5.0,
             "slippage_tolerance_pct must be between 0 and 5"
         );
+        let adaptive = &self.trading.adaptive_slippage;
+        anyhow::ensure!(
+            adaptive.min_tolerance_pct > 0.0 && adaptive.min_tolerance_pct <= adaptive.max_tolerance_pct,
+            "adaptive_slippage needs 0 < min_tolerance_pct <= max_tolerance_pct"
+        );
+        anyhow::ensure!(
+            adaptive.max_tolerance_pct <= 5.0,
+            "adaptive_slippage.max_tolerance_pct must be at most 5"
+        );
+        anyhow::ensure!(
+            adaptive.bucket_bounds_sol.windows(2).all(|w| w[0] < w[1]),
+            "adaptive_slippage.bucket_bounds_sol must be ascending"
+        );
+        anyhow::ensure!(
+            adaptive.window >= adaptive.min_samples && adaptive.min_samples >= 2,
+            "adaptive_slippage needs 2 <= min_samples <= window"
+        );
         anyhow::ensure!(
             self.risk.max_drawdown_pct > 0.0 && self.risk.max_drawdown_pct <= 100.0,
             "max_drawdown_pct must be between 0 and 100"
@@ -64,6 +309,374 @@ impl AppConfig {
             self.agentic.max_kelly_fraction > 0.0 && self.agentic.max_kelly_fraction <= 1.0,
             "max_kelly_fraction must be between 0 and 1"
         );
+        anyhow::ensure!(
+            self.agentic.metric_windows_days.iter().all(|d| *d > 0),
+            "agentic.metric_windows_days must be positive"
+        );
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.agentic.recent_window_weight),
+            "agentic.recent_window_weight must be between 0 and 1"
+        );
+        anyhow::ensure!(
+            self.agentic.calibration_buckets > 0,
+            "agentic.calibration_buckets must be at least 1"
+        );
+        anyhow::ensure!(
+            self.agentic.min_confidence_shrink > 0.0 && self.agentic.min_confidence_shrink <= 1.0,
+            "agentic.min_confidence_shrink must be between 0 and 1"
+        );
+        anyhow::ensure!(
+            self.agentic.skipped_trade_horizon_mins > 0,
+            "agentic.skipped_trade_horizon_mins must be positive"
+        );
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.agentic.compounding_fraction),
+            "agentic.compounding_fraction must be between 0 and 1"
+        );
+        anyhow::ensure!(
+            self.agentic.min_compounding_multiplier > 0.0
+                && self.agentic.min_compounding_multiplier <= 1.0
+                && self.agentic.max_compounding_multiplier >= 1.0,
+            "agentic compounding multipliers must satisfy 0 < min <= 1 <= max"
+        );
+        anyhow::ensure!(
+            self.agentic.reversal_tilt_pct > 0.0 && self.agentic.reversal_tilt_pct < 100.0,
+            "agentic.reversal_tilt_pct must be between 0 and 100"
+        );
+        let ladder = &self.trading.entry_ladder;
+        if ladder.enabled {
+            anyhow::ensure!(!ladder.levels.is_empty(), "trading.entry_ladder.levels must not be empty");
+            anyhow::ensure!(
+                ladder.levels.iter().all(|l| (0.0..=100.0).contains(&l.percentile) && l.fraction > 0.0)
+                    && ladder.levels.windows(2).all(|w| w[0].percentile < w[1].percentile),
+                "trading.entry_ladder.levels need ascending percentiles in 0-100 and positive fractions"
+            );
+            let total: f64 = ladder.levels.iter().map(|l| l.fraction).sum();
+            anyhow::ensure!(
+                (total - 1.0).abs() < 1e-6,
+                "trading.entry_ladder.levels fractions must sum to 1 (got {})",
+                total
+            );
+        }
+        anyhow::ensure!(
+            self.risk.max_oracle_divergence_pct > 0.0,
+            "risk.max_oracle_divergence_pct must be positive"
+        );
+        anyhow::ensure!(
+            self.risk.max_mark_index_divergence_pct > 0.0,
+            "risk.max_mark_index_divergence_pct must be positive"
+        );
+        let vol = &self.risk.volatility;
+        anyhow::ensure!(
+            vol.window_mins > 0 && vol.interval_secs > 0 && vol.history_hours > 0,
+            "risk.volatility.window_mins, interval_secs and history_hours must be positive"
+        );
+        anyhow::ensure!(
+            (0.0..=100.0).contains(&vol.trigger_percentile),
+            "risk.volatility.trigger_percentile must be within 0-100"
+        );
+        anyhow::ensure!(
+            vol.size_multiplier > 0.0 && vol.size_multiplier <= 1.0,
+            "risk.volatility.size_multiplier must be within (0, 1]"
+        );
+        let throttle = &self.risk.drawdown_throttle;
+        if throttle.enabled {
+            anyhow::ensure!(!throttle.points.is_empty(), "risk.drawdown_throttle.points must not be empty");
+            anyhow::ensure!(
+                throttle.points.iter().all(|p| p.drawdown_pct >= 0.0
+                    && p.drawdown_pct < self.risk.max_drawdown_pct
+                    && p.size_pct > 0.0
+                    && p.size_pct <= 100.0),
+                "risk.drawdown_throttle points need drawdown_pct below max_drawdown_pct and size_pct within (0, 100]"
+            );
+            anyhow::ensure!(
+                throttle.points.windows(2).all(|w| w[0].drawdown_pct < w[1].drawdown_pct && w[0].size_pct >= w[1].size_pct),
+                "risk.drawdown_throttle.points must rise in drawdown_pct and not rise in size_pct"
+            );
+            anyhow::ensure!(
+                throttle.recovery_fraction > 0.0 && throttle.recovery_fraction <= 1.0,
+                "risk.drawdown_throttle.recovery_fraction must be within (0, 1]"
+            );
+        }
+        anyhow::ensure!(
+            self.risk.overrides.max_duration_mins > 0 && self.risk.overrides.max_factor >= 1.0,
+            "risk.overrides.max_duration_mins must be positive and max_factor at least 1"
+        );
+        if self.api.enabled {
+            self.api.check_exposure(&self.api.bind_address)?;
+        }
+        if self.api.grpc_enabled {
+            self.api.check_exposure(&self.api.grpc_bind_address)?;
+        }
+        // Dry runs trade on paper whatever the file says
+        if !self.paper_trading && !self.dry_run {
+            anyhow::ensure!(self.api.enabled, "Live trading is confirmed through the control API; set api.enabled");
+            self.api.confirm_token()?;
+        }
+        let oracles = &self.protocols.oracles;
+        anyhow::ensure!(
+            oracles.outlier_pct > 0.0 && oracles.max_quote_age_ms > 0,
+            "protocols.oracles.outlier_pct and max_quote_age_ms must be positive"
+        );
+        anyhow::ensure!(
+            [oracles.pyth_weight, oracles.switchboard_weight, oracles.jupiter_weight].iter().all(|w| *w >= 0.0),
+            "protocols.oracles weights must not be negative"
+        );
+        anyhow::ensure!(
+            oracles.contingency.pyth_stale_ms >= oracles.max_quote_age_ms,
+            "protocols.oracles.contingency.pyth_stale_ms must be at least max_quote_age_ms"
+        );
+        anyhow::ensure!(
+            self.http.timeout_ms > 0 && self.http.breaker_failures > 0,
+            "http.timeout_ms and breaker_failures must be positive"
+        );
+        anyhow::ensure!(
+            self.clock.check_interval_secs > 0 && self.clock.samples > 0,
+            "clock.check_interval_secs and samples must be positive"
+        );
+        anyhow::ensure!(
+            oracles.contingency.threshold_multiplier >= 1.0,
+            "protocols.oracles.contingency.threshold_multiplier must be at least 1"
+        );
+        anyhow::ensure!(
+            !self.protocols.switchboard.enabled || !self.protocols.switchboard.sol_usd_feed.is_empty(),
+            "protocols.switchboard.sol_usd_feed is required when Switchboard is enabled"
+        );
+        anyhow::ensure!(
+            self.risk.max_market_allocation_pct > 0.0 && self.risk.max_market_allocation_pct <= 100.0,
+            "max_market_allocation_pct must be between 0 and 100"
+        );
+        anyhow::ensure!(
+            self.agentic.ruin_threshold_pct > 0.0 && self.agentic.ruin_threshold_pct <= 100.0,
+            "ruin_threshold_pct must be between 0 and 100"
+        );
+        anyhow::ensure!(
+            self.agentic.mean_reverting_variance_ratio < self.agentic.trending_variance_ratio,
+            "mean_reverting_variance_ratio must be below trending_variance_ratio"
+        );
+        anyhow::ensure!(
+            self.agentic.regime_lag >= 2,
+            "regime_lag must be at least 2"
+        );
+        anyhow::ensure!(
+            self.agentic.review_loss_sigma > 0.0
+                && self.agentic.review_slippage_bps > 0.0
+                && self.agentic.review_funding_deviation_pct > 0.0,
+            "trade review thresholds must be positive"
+        );
+        let learning = &self.agentic.threshold_learning;
+        if learning.enabled {
+            anyhow::ensure!(
+                learning.window_trades > 0 && learning.min_trades > 0,
+                "threshold_learning.window_trades and min_trades must be positive"
+            );
+            anyhow::ensure!(
+                learning.step_pct > 0.0 && learning.step_pct < 100.0,
+                "threshold_learning.step_pct must be between 0 and 100"
+            );
+            anyhow::ensure!(
+                learning.tighten_below_ev_usd <= learning.loosen_above_ev_usd,
+                "threshold_learning.tighten_below_ev_usd must not exceed loosen_above_ev_usd"
+            );
+            anyhow::ensure!(
+                learning.basis_floor_pct > self.trading.basis_close_threshold_pct,
+                "threshold_learning.basis_floor_pct must be above basis_close_threshold_pct (hysteresis band)"
+            );
+            anyhow::ensure!(
+                (learning.basis_floor_pct..=learning.basis_ceiling_pct).contains(&self.trading.min_basis_spread_pct),
+                "trading.min_basis_spread_pct must be within threshold_learning's basis floor and ceiling"
+            );
+            anyhow::ensure!(
+                learning.funding_floor_apr_pct > 0.0
+                    && (learning.funding_floor_apr_pct..=learning.funding_ceiling_apr_pct).contains(&self.trading.min_funding_apr_pct),
+                "trading.min_funding_apr_pct must be within threshold_learning's funding floor and ceiling"
+            );
+        }
+        anyhow::ensure!(
+            self.wallet.gas_reserve_sol >= 0.0,
+            "gas_reserve_sol must not be negative"
+        );
+        anyhow::ensure!(
+            self.wallet.gas_critical_sol >= 0.0 && self.wallet.gas_critical_sol <= self.wallet.gas_warn_sol,
+            "gas_critical_sol must be between 0 and gas_warn_sol"
+        );
+        anyhow::ensure!(
+            self.treasury.paper_starting_usdc >= 0.0,
+            "treasury.paper_starting_usdc must not be negative"
+        );
+        anyhow::ensure!(
+            self.treasury.idle_yield_apr_pct >= 0.0 && self.treasury.spot_yield_apr_pct >= 0.0,
+            "treasury yield APRs must not be negative"
+        );
+        let export = &self.telemetry.market_export;
+        if export.enabled {
+            anyhow::ensure!(
+                export.sample_interval_ms >= MIN_ENGINE_INTERVAL_MS,
+                "telemetry.market_export.sample_interval_ms must be at least {}",
+                MIN_ENGINE_INTERVAL_MS
+            );
+            anyhow::ensure!(
+                export.format != ExportFormat::Parquet || cfg!(feature = "parquet"),
+                "telemetry.market_export.format parquet needs a build with --features parquet"
+            );
+        }
+        let sweep = &self.treasury.profit_sweep;
+        if sweep.enabled {
+            anyhow::ensure!(
+                sweep.cold_wallet.parse::<solana_sdk::pubkey::Pubkey>().is_ok(),
+                "treasury.profit_sweep.cold_wallet must be a valid address"
+            );
+            anyhow::ensure!(
+                sweep.equity_target_usd > 0.0 && sweep.min_sweep_usd >= 0.0 && sweep.interval_secs > 0,
+                "treasury.profit_sweep needs a positive equity target and interval"
+            );
+        }
+        let engines = &self.engines;
+        anyhow::ensure!(
+            self.api.health.max_agent_lag_ms > engines.agent_tick_ms,
+            "api.health.max_agent_lag_ms must be longer than engines.agent_tick_ms"
+        );
+        anyhow::ensure!(
+            [
+                engines.basis_interval_ms,
+                engines.funding_interval_ms,
+                engines.signal_interval_ms,
+                engines.agent_tick_ms,
+                engines.reversal_interval_ms,
+                engines.regime_interval_ms,
+            ]
+            .iter()
+            .all(|ms| *ms >= MIN_ENGINE_INTERVAL_MS),
+            "engines intervals must be at least {}ms",
+            MIN_ENGINE_INTERVAL_MS
+        );
+        anyhow::ensure!(
+            engines.funding_interval_ms <= 10 * 60 * 1000,
+            "engines.funding_interval_ms must be at most 10 minutes for reversal detection"
+        );
+        anyhow::ensure!(
+            engines.history_save_secs > 0,
+            "engines.history_save_secs must be positive"
+        );
+        anyhow::ensure!(
+            engines.history_import.lookback_hours > 0,
+            "engines.history_import.lookback_hours must be positive"
+        );
+        anyhow::ensure!(
+            self.execution.passive_entry.limit_offset_bps >= 0.0,
+            "passive_entry.limit_offset_bps must not be negative"
+        );
+        let tip = &self.execution.jito_tip;
+        anyhow::ensure!(
+            tip.strategy == "fixed" || tip.strategy == "dynamic",
+            "execution.jito_tip.strategy must be \"fixed\" or \"dynamic\""
+        );
+        anyhow::ensure!(
+            [25, 50, 75, 95, 99].contains(&tip.percentile),
+            "execution.jito_tip.percentile must be 25, 50, 75, 95 or 99"
+        );
+        anyhow::ensure!(
+            tip.max_profit_share_pct > 0.0 && tip.max_profit_share_pct <= 100.0,
+            "execution.jito_tip.max_profit_share_pct must be in (0, 100]"
+        );
+        anyhow::ensure!(
+            tip.min_lamports <= tip.max_lamports,
+            "execution.jito_tip.min_lamports must not exceed max_lamports"
+        );
+        anyhow::ensure!(
+            !self.execution.cex.enabled || self.execution.cex.exchange == "binance",
+            "execution.cex.exchange must be \"binance\""
+        );
+        anyhow::ensure!(
+            self.execution.perp_routing != PerpRouting::Hyperliquid || self.protocols.hyperliquid.enabled,
+            "execution.perp_routing needs protocols.hyperliquid.enabled"
+        );
+        anyhow::ensure!(
+            self.execution.perp_routing != PerpRouting::JupiterPerps || self.protocols.jupiter_perps.enabled,
+            "execution.perp_routing needs protocols.jupiter_perps.enabled"
+        );
+        anyhow::ensure!(
+            self.execution.perp_routing != PerpRouting::BestFunding
+                || self.protocols.hyperliquid.enabled
+                || self.protocols.jupiter_perps.enabled,
+            "execution.perp_routing best_funding needs a second perp venue"
+        );
+        anyhow::ensure!(
+            self.protocols.jupiter_perps.leverage >= 1.0,
+            "protocols.jupiter_perps.leverage must be at least 1"
+        );
+        anyhow::ensure!(
+            self.protocols.orca.max_size_sol > 0.0,
+            "protocols.orca.max_size_sol must be positive"
+        );
+        anyhow::ensure!(
+            self.execution.max_quote_age_ms > 0,
+            "execution.max_quote_age_ms must be positive"
+        );
+        let pre_trade = &self.execution.pre_trade;
+        anyhow::ensure!(
+            pre_trade.max_price_age_ms > 0 && pre_trade.max_rpc_latency_ms > 0 && pre_trade.max_orders_per_minute > 0,
+            "execution.pre_trade price age, RPC latency and order rate limits must be positive"
+        );
+        anyhow::ensure!(
+            pre_trade.max_cex_deviation_pct > 0.0 && pre_trade.max_basis_pct > 0.0,
+            "execution.pre_trade deviation limits must be positive"
+        );
+        anyhow::ensure!(
+            self.execution.cex.lot_size_sol > 0.0,
+            "execution.cex.lot_size_sol must be positive"
+        );
+        anyhow::ensure!(
+            self.execution.passive_entry.fill_timeout_secs > 0,
+            "passive_entry.fill_timeout_secs must be positive"
+        );
+        anyhow::ensure!(
+            self.execution.passive_entry.reprice_bps >= 0.0,
+            "passive_entry.reprice_bps must be non-negative"
+        );
+        anyhow::ensure!(
+            self.state_timeouts.awaiting_fill_secs == 0
+                || self.state_timeouts.awaiting_fill_secs > self.execution.passive_entry.fill_timeout_secs,
+            "state_timeouts.awaiting_fill_secs must exceed passive_entry.fill_timeout_secs"
+        );
+        anyhow::ensure!(
+            self.devnet_profile.synthetic_funding.interval_secs > 0
+                && self.devnet_profile.synthetic_funding.period_secs > 0,
+            "devnet_profile.synthetic_funding interval_secs and period_secs must be positive"
+        );
+        anyhow::ensure!(
+            self.secrets.provider != SecretProvider::Vault || self.secrets.vault_url.is_some(),
+            "secrets.vault_url is required for the vault provider"
+        );
+        for (name, rate) in [
+            ("error_rate", self.chaos.error_rate),
+            ("timeout_rate", self.chaos.timeout_rate),
+            ("latency_spike_rate", self.chaos.latency_spike_rate),
+            ("ws_drop_rate", self.chaos.ws_drop_rate),
+        ] {
+            anyhow::ensure!((0.0..=1.0).contains(&rate), "chaos.{} must be between 0 and 1", name);
+        }
+        anyhow::ensure!(
+            self.chaos.error_rate + self.chaos.timeout_rate <= 1.0,
+            "chaos.error_rate and chaos.timeout_rate must sum to at most 1"
+        );
+        anyhow::ensure!(self.rpc.health_check_interval_secs > 0, "rpc.health_check_interval_secs must be positive");
+        if self.supervisor.enabled {
+            let slowest_ms = [
+                self.engines.basis_interval_ms,
+                self.engines.funding_interval_ms,
+                self.engines.signal_interval_ms,
+                self.engines.agent_tick_ms,
+            ]
+            .into_iter()
+            .max()
+            .unwrap_or(0);
+            anyhow::ensure!(
+                self.supervisor.stall_secs * 1000 > slowest_ms,
+                "supervisor.stall_secs must exceed the slowest engine interval"
+            );
+            anyhow::ensure!(self.supervisor.check_interval_secs > 0, "supervisor.check_interval_secs must be positive");
+        }
         Ok(())
     }
     
@@ -79,9 +692,18 @@ impl AppConfig {
                 request_timeout_ms: 10000,
                 max_retries: 3,
                 requests_per_second: 50,
+                health_check_interval_secs: 10,
+                max_health_failures: 3,
             },
+            http: HttpConfig::default(),
+            clock: ClockConfig::default(),
             wallet: WalletConfig {
                 keypair_path: "./wallet.json".to_string(),
+                gas_reserve_sol: 0.05,
+                balance_refresh_secs: 30,
+                gas_warn_sol: 0.1,
+                gas_critical_sol: 0.02,
+                gas_check_secs: 30,
             },
             trading: TradingConfig {
                 min_basis_spread_pct: 0.1,
@@ -92,7 +714,13 @@ impl AppConfig {
                 slippage_tolerance_pct: 0.5,
                 basis_close_threshold_pct: 0.05,
                 max_hold_time_hours: 168,
+                max_perp_taker_cost_pct: 0.05,
+                entry_ladder: EntryLadderConfig::default(),
+                adaptive_slippage: AdaptiveSlippageConfig::default(),
             },
+            signals: SignalConfig::default(),
+            trading_windows: TradingWindowConfig::default(),
+            state_timeouts: StateTimeoutConfig::default(),
             risk: RiskConfig {
                 max_drawdown_pct: 5.0,
                 stop_loss_pct: 2.0,
@@ -100,6 +728,15 @@ impl AppConfig {
                 max_funding_reversal_loss: 500.0,
                 max_open_positions: 5,
                 min_trade_interval_secs: 60,
+                max_total_exposure_usd: default_max_total_exposure_usd(),
+                max_market_allocation_pct: default_max_market_allocation_pct(),
+                allocation_rebalance_hours: default_allocation_rebalance_hours(),
+                max_oracle_divergence_pct: default_max_oracle_divergence(),
+                max_mark_index_divergence_pct: default_max_mark_index_divergence(),
+                volatility: VolatilityConfig::default(),
+                drawdown_throttle: DrawdownThrottleConfig::default(),
+                daily_timezone: default_daily_timezone(),
+                overrides: RiskOverrideConfig::default(),
             },
             rebalance: RebalanceConfig {
                 check_interval_secs: 60,
@@ -110,6 +747,8 @@ impl AppConfig {
                 use_jito: true,
                 jito_tip_lamports: 10000,
                 jito_block_engine_url: "https://mainnet.block-engine.jito.wtf".to_string(),
+                jito_landing_timeout_secs: 8,
+                jito_tip: JitoTipConfig::default(),
                 max_retries: 3,
                 retry_delay_ms: 100,
                 simulate_before_submit: true,
@@ -118,6 +757,17 @@ impl AppConfig {
                     fixed_fee: 1000,
                     max_fee: 100000,
                 },
+                perp_taker_fee_bps: 3.5,
+                perp_maker_fee_bps: -0.25,
+                passive_entry: PassiveEntryConfig::default(),
+                leg_tolerance_sol: 0.01,
+                open_latency_budget_ms: 2000,
+                max_quote_age_ms: 2000,
+                max_requotes: 2,
+                cex: CexConfig::default(),
+                perp_routing: PerpRouting::Drift,
+                durable_nonce: DurableNonceConfig::default(),
+                pre_trade: PreTradeConfig::default(),
             },
             telemetry: TelemetryConfig {
                 log_level: "info".to_string(),
@@ -128,24 +778,127 @@ impl AppConfig {
                 enable_alerts: false,
                 alert_webhook: None,
                 telegram: TelegramConfig::default(),
+                alerting: AlertingConfig::default(),
+                otlp_endpoint: None,
+                otlp_service_name: default_otlp_service_name(),
+                market_export: MarketExportConfig::default(),
             },
             protocols: ProtocolsConfig {
                 drift: DriftConfig {
                     program_id: "dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH".to_string(),
                     market_index: 0,
+                    funding_interval: FundingInterval::Hourly,
+                    api_url: default_drift_api_url(),
+                    dlob_url: "https://dlob.drift.trade".to_string(),
+                    dlob_depth: 10,
+                    dlob_poll_ms: 1000,
                 },
                 pyth: PythConfig {
                     sol_usd_feed: "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG".to_string(),
+                    usdc_usd_feed: default_usdc_usd_feed(),
                 },
                 jupiter: JupiterConfig {
                     api_url: "https://quote-api.jup.ag/v6".to_string(),
                     sol_mint: "So11111111111111111111111111111111111111112".to_string(),
+                    spot_decimals: 9,
                     usdc_mint: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+                    depth_quote_size_sol: 100.0,
+                    depth_quote_interval_ms: 5000,
                 },
+                hyperliquid: HyperliquidConfig::default(),
+                jupiter_perps: JupiterPerpsConfig::default(),
+                orca: OrcaConfig::default(),
+                switchboard: SwitchboardConfig::default(),
+                oracles: OracleAggregationConfig::default(),
             },
             agentic: AgenticConfig::default(),
+            api: ApiConfig::default(),
+            secrets: SecretsConfig::default(),
             paper_trading: true,
+            dry_run: false,
             devnet: false,
+            devnet_profile: DevnetConfig::default(),
+            chaos: ChaosConfig::default(),
+            treasury: TreasuryConfig::default(),
+            engines: EnginesConfig::default(),
+            supervisor: SupervisorConfig::default(),
+            report: SessionReportConfig::default(),
+            market: default_market(),
+            markets: default_markets(),
+            strategies: Vec::new(),
+        }
+    }
+}
+
+/// Retry policy of the HTTP API clients (see `network::http`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpConfig {
+    /// Timeout of each attempt
+    #[serde(default = "default_http_timeout")]
+    pub timeout_ms: u64,
+    /// Retries of connection errors, timeouts, 429 and 5xx responses
+    #[serde(default = "default_http_retries")]
+    pub max_retries: u32,
+    /// First retry delay, doubled on each further retry
+    #[serde(default = "default_http_backoff")]
+    pub backoff_ms: u64,
+    #[serde(default = "default_http_max_backoff")]
+    pub max_backoff_ms: u64,
+    /// Consecutive failed calls behind a breaker before it opens
+    #[serde(default = "default_breaker_failures")]
+    pub breaker_failures: u32,
+    /// Time calls behind an open breaker are held back
+    #[serde(default = "default_cool_off")]
+    pub cool_off_secs: u64,
+}
+
+fn default_http_timeout() -> u64 { 10_000 }
+fn default_http_retries() -> u32 { 2 }
+fn default_http_backoff() -> u64 { 200 }
+fn default_http_max_backoff() -> u64 { 2_000 }
+fn default_breaker_failures() -> u32 { 5 }
+fn default_cool_off() -> u64 { 30 }
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: default_http_timeout(),
+            max_retries: default_http_retries(),
+            backoff_ms: default_http_backoff(),
+            max_backoff_ms: default_http_max_backoff(),
+            breaker_failures: default_breaker_failures(),
+            cool_off_secs: default_cool_off(),
+        }
+    }
+}
+
+/// Clock skew monitor (see `utils::clock`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How often block time is sampled
+    #[serde(default = "default_clock_check_interval")]
+    pub check_interval_secs: u64,
+    /// Skew of the local clock beyond which a warning is raised
+    #[serde(default = "default_max_clock_skew")]
+    pub max_skew_ms: i64,
+    /// Recent samples the skew is the median of
+    #[serde(default = "default_clock_samples")]
+    pub samples: usize,
+}
+
+fn default_clock_check_interval() -> u64 { 60 }
+fn default_max_clock_skew() -> i64 { 2_000 }
+fn default_clock_samples() -> usize { 5 }
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_interval_secs: default_clock_check_interval(),
+            max_skew_ms: default_max_clock_skew(),
+            samples: default_clock_samples(),
         }
     }
 }
@@ -164,18 +917,47 @@ pub struct RpcConfig {
     pub max_retries: u32,
     #[serde(default = "default_requests_per_second")]
     pub requests_per_second: u32,
+    /// How often the active endpoint is health checked
+    #[serde(default = "default_health_check_interval")]
+    pub health_check_interval_secs: u64,
+    /// Consecutive failed checks (each followed by a failover) before RPC is marked down
+    #[serde(default = "default_max_health_failures")]
+    pub max_health_failures: u32,
 }
 
 fn default_connection_timeout() -> u64 { 5000 }
 fn default_request_timeout() -> u64 { 10000 }
 fn default_max_retries() -> u32 { 3 }
 fn default_requests_per_second() -> u32 { 50 }
+fn default_health_check_interval() -> u64 { 10 }
+fn default_max_health_failures() -> u32 { 3 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletConfig {
     pub keypair_path: String,
+    /// SOL kept aside for transaction fees and never sized into trades
+    #[serde(default = "default_gas_reserve_sol")]
+    pub gas_reserve_sol: f64,
+    /// How often wallet and collateral balances are refreshed
+    #[serde(default = "default_balance_refresh_secs")]
+    pub balance_refresh_secs: u64,
+    /// SOL balance below which the gas watchdog warns
+    #[serde(default = "default_gas_warn_sol")]
+    pub gas_warn_sol: f64,
+    /// SOL balance below which new entries are blocked
+    #[serde(default = "default_gas_critical_sol")]
+    pub gas_critical_sol: f64,
+    /// How often the gas watchdog checks the SOL balance
+    #[serde(default = "default_gas_check_secs")]
+    pub gas_check_secs: u64,
 }
 
+fn default_gas_reserve_sol() -> f64 { 0.05 }
+fn default_balance_refresh_secs() -> u64 { 30 }
+fn default_gas_warn_sol() -> f64 { 0.1 }
+fn default_gas_critical_sol() -> f64 { 0.02 }
+fn default_gas_check_secs() -> u64 { 30 }
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingConfig {
     pub min_basis_spread_pct: f64,
@@ -188,89 +970,1742 @@ pub struct TradingConfig {
     pub basis_close_threshold_pct: f64,
     #[serde(default = "default_max_hold_time")]
     pub max_hold_time_hours: u64,
+    /// Perp taker cost (% vs mid) above which entries use a limit order
+    #[serde(default = "default_max_perp_taker_cost")]
+    pub max_perp_taker_cost_pct: f64,
+    /// Build positions in tranches at rising basis percentiles
+    #[serde(default)]
+    pub entry_ladder: EntryLadderConfig,
+    /// Spot swap tolerance from realized slippage, per size bucket
+    #[serde(default)]
+    pub adaptive_slippage: AdaptiveSlippageConfig,
 }
 
 fn default_basis_close_threshold() -> f64 { 0.05 }
 fn default_max_hold_time() -> u64 { 168 }
+fn default_max_perp_taker_cost() -> f64 { 0.05 }
 
+/// Entry laddering: each level adds a share of the sized position once the
+/// basis reaches its historical percentile
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RiskConfig {
-    pub max_drawdown_pct: f64,
-    pub stop_loss_pct: f64,
-    pub hedge_drift_threshold_pct: f64,
-    pub max_funding_reversal_loss: f64,
-    #[serde(default = "default_max_open_positions")]
-    pub max_open_positions: u32,
-    #[serde(default = "default_min_trade_interval")]
-    pub min_trade_interval_secs: u64,
+pub struct EntryLadderConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Levels by ascending percentile; fractions sum to 1
+    #[serde(default = "default_ladder_levels")]
+    pub levels: Vec<LadderLevel>,
 }
 
-fn default_max_open_positions() -> u32 { 5 }
-fn default_min_trade_interval() -> u64 { 60 }
+/// One rung of the entry ladder
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LadderLevel {
+    /// Basis percentile vs history that triggers the tranche (0-100)
+    pub percentile: f64,
+    /// Share of the sized position (0-1)
+    pub fraction: f64,
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RebalanceConfig {
-    pub check_interval_secs: u64,
-    pub min_rebalance_size_sol: f64,
-    #[serde(default = "default_max_rebalances")]
-    pub max_rebalances_per_hour: u32,
+fn default_ladder_levels() -> Vec<LadderLevel> {
+    vec![
+        LadderLevel { percentile: 70.0, fraction: 0.25 },
+        LadderLevel { percentile: 85.0, fraction: 0.5 },
+        LadderLevel { percentile: 95.0, fraction: 0.25 },
+    ]
 }
 
-fn default_max_rebalances() -> u32 { 10 }
+impl Default for EntryLadderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            levels: default_ladder_levels(),
+        }
+    }
+}
 
+/// Adaptive slippage tolerance: once a size bucket has `min_samples`
+/// fills, spot swaps of that size use its mean realized slippage plus
+/// `stdev_multiplier` standard deviations, within the bounds
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExecutionConfig {
-    pub use_jito: bool,
-    pub jito_tip_lamports: u64,
-    #[serde(default = "default_jito_url")]
-    pub jito_block_engine_url: String,
-    pub max_retries: u32,
-    pub retry_delay_ms: u64,
-    pub simulate_before_submit: bool,
-    pub priority_fee: PriorityFeeConfig,
+pub struct AdaptiveSlippageConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_min_slippage_tolerance")]
+    pub min_tolerance_pct: f64,
+    #[serde(default = "default_max_slippage_tolerance")]
+    pub max_tolerance_pct: f64,
+    /// Upper bounds of the size buckets (SOL); larger fills share the last bucket
+    #[serde(default = "default_slippage_buckets")]
+    pub bucket_bounds_sol: Vec<f64>,
+    /// Fills kept per bucket
+    #[serde(default = "default_slippage_window")]
+    pub window: usize,
+    #[serde(default = "default_slippage_min_samples")]
+    pub min_samples: usize,
+    #[serde(default = "default_slippage_stdev_multiplier")]
+    pub stdev_multiplier: f64,
+}
+
+fn default_min_slippage_tolerance() -> f64 { 0.1 }
+fn default_max_slippage_tolerance() -> f64 { 1.0 }
+fn default_slippage_buckets() -> Vec<f64> { vec![1.0, 10.0, 50.0] }
+fn default_slippage_window() -> usize { 50 }
+fn default_slippage_min_samples() -> usize { 10 }
+fn default_slippage_stdev_multiplier() -> f64 { 3.0 }
+
+impl Default for AdaptiveSlippageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_tolerance_pct: default_min_slippage_tolerance(),
+            max_tolerance_pct: default_max_slippage_tolerance(),
+            bucket_bounds_sol: default_slippage_buckets(),
+            window: default_slippage_window(),
+            min_samples: default_slippage_min_samples(),
+            stdev_multiplier: default_slippage_stdev_multiplier(),
+        }
+    }
+}
+
+/// Signal debouncing: persistence and per-type cooldowns
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalConfig {
+    /// Consecutive evaluations a condition must hold before it is signalled
+    #[serde(default = "default_confirmations")]
+    pub confirmations_required: u32,
+    /// Minimum confidence for an open evaluation to count towards persistence
+    #[serde(default = "default_min_open_confidence")]
+    pub min_open_confidence: f64,
+    /// Minimum time between open signals
+    #[serde(default = "default_open_cooldown")]
+    pub open_cooldown_secs: u64,
+    /// Minimum time between close signals
+    #[serde(default = "default_close_cooldown")]
+    pub close_cooldown_secs: u64,
+    /// Minimum time between rebalance signals
+    #[serde(default = "default_rebalance_cooldown")]
+    pub rebalance_cooldown_secs: u64,
+    /// Entry confidence adjustment from past performance by entry time
+    #[serde(default)]
+    pub entry_time: EntryTimeConfig,
+    /// Confidence added by each entry condition
+    #[serde(default)]
+    pub scoring: ScoringConfig,
+    /// Expected value model of entries
+    #[serde(default)]
+    pub expected_value: ExpectedValueConfig,
+    /// No re-entry into the same setup as the last failed or closed entry
+    #[serde(default)]
+    pub reentry: ReentryConfig,
+    /// Entry thresholds raised while realized edge deteriorates
+    #[serde(default)]
+    pub frequency_governor: FrequencyGovernorConfig,
+}
+
+fn default_confirmations() -> u32 { 3 }
+fn default_min_open_confidence() -> f64 { 0.6 }
+fn default_open_cooldown() -> u64 { 300 }
+fn default_close_cooldown() -> u64 { 60 }
+fn default_rebalance_cooldown() -> u64 { 120 }
+
+impl Default for SignalConfig {
+    fn default() -> Self {
+        Self {
+            confirmations_required: default_confirmations(),
+            min_open_confidence: default_min_open_confidence(),
+            open_cooldown_secs: default_open_cooldown(),
+            close_cooldown_secs: default_close_cooldown(),
+            rebalance_cooldown_secs: default_rebalance_cooldown(),
+            entry_time: EntryTimeConfig::default(),
+            scoring: ScoringConfig::default(),
+            expected_value: ExpectedValueConfig::default(),
+            reentry: ReentryConfig::default(),
+            frequency_governor: FrequencyGovernorConfig::default(),
+        }
+    }
+}
+
+/// Over-trading guard: realized edge (net ROI per trade) of the last
+/// `recent_trades` is compared with the last `baseline_trades`. Each close
+/// with edge deteriorating raises the governor one level, multiplying the
+/// entry thresholds by another `step_pct`; each close with edge recovered
+/// lowers it one level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrequencyGovernorConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_governor_recent")]
+    pub recent_trades: usize,
+    #[serde(default = "default_governor_baseline")]
+    pub baseline_trades: usize,
+    /// Recent edge this far below the baseline is deteriorating (% ROI)
+    #[serde(default = "default_governor_margin")]
+    pub decay_margin_pct: f64,
+    /// Recent edge below this is deteriorating whatever the baseline (% ROI)
+    #[serde(default = "default_governor_min_edge")]
+    pub min_edge_pct: f64,
+    /// Threshold increase per level (%)
+    #[serde(default = "default_governor_step")]
+    pub step_pct: f64,
+    #[serde(default = "default_governor_max_level")]
+    pub max_level: u32,
+}
+
+fn default_governor_recent() -> usize { 10 }
+fn default_governor_baseline() -> usize { 50 }
+fn default_governor_margin() -> f64 { 0.2 }
+fn default_governor_min_edge() -> f64 { 0.05 }
+fn default_governor_step() -> f64 { 10.0 }
+fn default_governor_max_level() -> u32 { 5 }
+
+impl Default for FrequencyGovernorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            recent_trades: default_governor_recent(),
+            baseline_trades: default_governor_baseline(),
+            decay_margin_pct: default_governor_margin(),
+            min_edge_pct: default_governor_min_edge(),
+            step_pct: default_governor_step(),
+            max_level: default_governor_max_level(),
+        }
+    }
+}
+
+/// Anti-churn: market state remembered at the last failed or closed entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReentryConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How long after the entry ended the same setup is refused
+    #[serde(default = "default_reentry_cooldown")]
+    pub cooldown_secs: u64,
+    /// Basis richer than the last entry by this much is a new setup (%)
+    #[serde(default = "default_reentry_basis_delta")]
+    pub min_basis_improvement_pct: f64,
+    /// Funding APR higher than the last entry by this much is a new setup (%)
+    #[serde(default = "default_reentry_funding_delta")]
+    pub min_funding_improvement_pct: f64,
+}
+
+fn default_reentry_cooldown() -> u64 { 3600 }
+fn default_reentry_basis_delta() -> f64 { 0.05 }
+fn default_reentry_funding_delta() -> f64 { 5.0 }
+
+impl Default for ReentryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cooldown_secs: default_reentry_cooldown(),
+            min_basis_improvement_pct: default_reentry_basis_delta(),
+            min_funding_improvement_pct: default_reentry_funding_delta(),
+        }
+    }
+}
+
+/// Expected value of entries (see `engines::expected_value`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpectedValueConfig {
+    /// Hold assumed until enough trades have closed
+    #[serde(default = "default_ev_hold_hours")]
+    pub default_hold_hours: f64,
+    /// Closed trades before their average hold is used
+    #[serde(default = "default_ev_min_trades")]
+    pub min_trades: u32,
+    /// Spot swap fee and slippage per side (bps of notional)
+    #[serde(default = "default_ev_spot_cost_bps")]
+    pub spot_cost_bps: f64,
+    /// Take the hold and exit basis from the basis half-life when it can be fitted
+    #[serde(default = "default_true")]
+    pub use_half_life: bool,
+}
+
+fn default_ev_hold_hours() -> f64 { 72.0 }
+fn default_ev_min_trades() -> u32 { 5 }
+fn default_ev_spot_cost_bps() -> f64 { 10.0 }
+
+impl Default for ExpectedValueConfig {
+    fn default() -> Self {
+        Self {
+            default_hold_hours: default_ev_hold_hours(),
+            min_trades: default_ev_min_trades(),
+            spot_cost_bps: default_ev_spot_cost_bps(),
+            use_half_life: true,
+        }
+    }
+}
+
+/// Weights of the entry confidence factors; each adds its weight when its
+/// condition holds (`percentile` subtracts)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringConfig {
+    /// Basis at or above `min_basis_spread_pct`
+    #[serde(default = "default_basis_strength_weight")]
+    pub basis_strength: f64,
+    /// Funding APR at or above `min_funding_apr_pct`
+    #[serde(default = "default_funding_strength_weight")]
+    pub funding_strength: f64,
+    /// Basis and funding pointing the same way
+    #[serde(default = "default_alignment_weight")]
+    pub alignment: f64,
+    /// Basis at least one standard deviation rich versus its history
+    #[serde(default = "default_z_score_weight")]
+    pub z_score: f64,
+    /// Taken off when the basis is below its 25th percentile
+    #[serde(default = "default_percentile_weight")]
+    pub percentile: f64,
+    /// No trade within `min_trade_interval_secs`
+    #[serde(default = "default_freshness_weight")]
+    pub freshness: f64,
+}
+
+fn default_basis_strength_weight() -> f64 { 0.3 }
+fn default_funding_strength_weight() -> f64 { 0.3 }
+fn default_alignment_weight() -> f64 { 0.2 }
+fn default_z_score_weight() -> f64 { 0.1 }
+fn default_percentile_weight() -> f64 { 0.2 }
+fn default_freshness_weight() -> f64 { 0.2 }
+
+impl ScoringConfig {
+    /// Weights by factor name, for validation and logs
+    pub fn weights(&self) -> [(&'static str, f64); 6] {
+        [
+            ("basis_strength", self.basis_strength),
+            ("funding_strength", self.funding_strength),
+            ("alignment", self.alignment),
+            ("z_score", self.z_score),
+            ("percentile", self.percentile),
+            ("freshness", self.freshness),
+        ]
+    }
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            basis_strength: default_basis_strength_weight(),
+            funding_strength: default_funding_strength_weight(),
+            alignment: default_alignment_weight(),
+            z_score: default_z_score_weight(),
+            percentile: default_percentile_weight(),
+            freshness: default_freshness_weight(),
+        }
+    }
+}
+
+/// Entry-time preference learned from closed trades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryTimeConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Trades an hour or weekday needs before it counts
+    #[serde(default = "default_entry_time_min_trades")]
+    pub min_trades: u32,
+    /// Confidence added per unit of win rate edge over all trades
+    #[serde(default = "default_entry_time_weight")]
+    pub weight: f64,
+}
+
+fn default_entry_time_min_trades() -> u32 { 10 }
+fn default_entry_time_weight() -> f64 { 0.2 }
+
+impl Default for EntryTimeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_trades: default_entry_time_min_trades(),
+            weight: default_entry_time_weight(),
+        }
+    }
+}
+
+/// Time-based entry restrictions (UTC)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradingWindowConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// No entries within this many minutes before a funding settlement
+    #[serde(default = "default_funding_blackout")]
+    pub funding_blackout_mins: u64,
+    /// No entries on Saturday and Sunday
+    #[serde(default)]
+    pub weekend_blackout: bool,
+    /// Hours (0-23) with no entries
+    #[serde(default)]
+    pub blackout_hours_utc: Vec<u32>,
+}
+
+fn default_funding_blackout() -> u64 { 5 }
+
+impl Default for TradingWindowConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            funding_blackout_mins: default_funding_blackout(),
+            weekend_blackout: false,
+            blackout_hours_utc: Vec::new(),
+        }
+    }
+}
+
+/// Maximum time in each active agent state (seconds, 0 = no limit)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateTimeoutConfig {
+    #[serde(default = "default_opening_timeout")]
+    pub opening_secs: u64,
+    /// Backstop over the passive entry fill timeout
+    #[serde(default = "default_awaiting_fill_timeout")]
+    pub awaiting_fill_secs: u64,
+    #[serde(default = "default_closing_timeout")]
+    pub closing_secs: u64,
+    #[serde(default = "default_rebalancing_timeout")]
+    pub rebalancing_secs: u64,
+    /// What to do after a state times out
+    #[serde(default)]
+    pub recovery: TimeoutRecovery,
+    /// Retries before a retry policy falls back to pausing
+    #[serde(default = "default_timeout_retries")]
+    pub max_retries: u32,
+}
+
+fn default_opening_timeout() -> u64 { 120 }
+fn default_awaiting_fill_timeout() -> u64 { 900 }
+fn default_closing_timeout() -> u64 { 120 }
+fn default_rebalancing_timeout() -> u64 { 60 }
+fn default_timeout_retries() -> u32 { 1 }
+
+impl Default for StateTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            opening_secs: default_opening_timeout(),
+            awaiting_fill_secs: default_awaiting_fill_timeout(),
+            closing_secs: default_closing_timeout(),
+            rebalancing_secs: default_rebalancing_timeout(),
+            recovery: TimeoutRecovery::default(),
+            max_retries: default_timeout_retries(),
+        }
+    }
+}
+
+/// Recovery after an agent state times out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutRecovery {
+    /// Re-enter the stuck state, up to `max_retries` times
+    Retry,
+    /// Close any open legs, or return to idle if flat
+    Unwind,
+    /// Hold for an operator resume
+    #[default]
+    Pause,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskConfig {
+    pub max_drawdown_pct: f64,
+    pub stop_loss_pct: f64,
+    pub hedge_drift_threshold_pct: f64,
+    pub max_funding_reversal_loss: f64,
+    #[serde(default = "default_max_open_positions")]
+    pub max_open_positions: u32,
+    #[serde(default = "default_min_trade_interval")]
+    pub min_trade_interval_secs: u64,
+    /// Aggregate capital deployable across all markets (USD)
+    #[serde(default = "default_max_total_exposure_usd")]
+    pub max_total_exposure_usd: f64,
+    /// Largest share of capital any one market may receive (%)
+    #[serde(default = "default_max_market_allocation_pct")]
+    pub max_market_allocation_pct: f64,
+    /// How often capital allocations are recomputed (hours)
+    #[serde(default = "default_allocation_rebalance_hours")]
+    pub allocation_rebalance_hours: u64,
+    /// Largest spread between spot sources before entries stop (%)
+    #[serde(default = "default_max_oracle_divergence")]
+    pub max_oracle_divergence_pct: f64,
+    /// Largest perp mark deviation from the index before entries stop (%)
+    #[serde(default = "default_max_mark_index_divergence")]
+    pub max_mark_index_divergence_pct: f64,
+    /// De-risking while realized volatility is high
+    #[serde(default)]
+    pub volatility: VolatilityConfig,
+    /// Size reduction as drawdown approaches `max_drawdown_pct`
+    #[serde(default)]
+    pub drawdown_throttle: DrawdownThrottleConfig,
+    /// Timezone whose midnight starts a trading day, for the daily loss
+    /// limit and the daily ledger (IANA name)
+    #[serde(default = "default_daily_timezone")]
+    pub daily_timezone: chrono_tz::Tz,
+    /// Bounds on temporary limit overrides set through the control API
+    #[serde(default)]
+    pub overrides: RiskOverrideConfig,
+}
+
+/// Temporary risk limit overrides: each must expire within
+/// `max_duration_mins`, and may loosen a limit to at most `max_factor` times
+/// its configured value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskOverrideConfig {
+    #[serde(default = "default_override_max_duration")]
+    pub max_duration_mins: u64,
+    #[serde(default = "default_override_max_factor")]
+    pub max_factor: f64,
+}
+
+fn default_override_max_duration() -> u64 { 240 }
+fn default_override_max_factor() -> f64 { 2.0 }
+
+impl Default for RiskOverrideConfig {
+    fn default() -> Self {
+        Self {
+            max_duration_mins: default_override_max_duration(),
+            max_factor: default_override_max_factor(),
+        }
+    }
+}
+
+fn default_daily_timezone() -> chrono_tz::Tz { chrono_tz::UTC }
+
+/// How the throttle reads between curve points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleMode {
+    /// Interpolate between points
+    #[default]
+    Linear,
+    /// Hold each point's size until the next point is reached
+    Steps,
+}
+
+/// A point on the drawdown throttle curve
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThrottlePoint {
+    pub drawdown_pct: f64,
+    /// Share of the target size at this drawdown (%)
+    pub size_pct: f64,
+}
+
+/// Drawdown throttle: the target size follows `points` (full size before
+/// the first one, the last one's size beyond it), and once cut only
+/// recovers after the drawdown has retraced `recovery_fraction` of its worst.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrawdownThrottleConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub mode: ThrottleMode,
+    /// By ascending drawdown, with non-increasing size
+    #[serde(default = "default_throttle_points")]
+    pub points: Vec<ThrottlePoint>,
+    #[serde(default = "default_throttle_recovery")]
+    pub recovery_fraction: f64,
+}
+
+fn default_throttle_points() -> Vec<ThrottlePoint> {
+    vec![
+        ThrottlePoint { drawdown_pct: 2.0, size_pct: 100.0 },
+        ThrottlePoint { drawdown_pct: 3.0, size_pct: 50.0 },
+        ThrottlePoint { drawdown_pct: 4.0, size_pct: 25.0 },
+    ]
+}
+fn default_throttle_recovery() -> f64 { 0.5 }
+
+impl Default for DrawdownThrottleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: ThrottleMode::default(),
+            points: default_throttle_points(),
+            recovery_fraction: default_throttle_recovery(),
+        }
+    }
+}
+
+fn default_max_open_positions() -> u32 { 5 }
+fn default_min_trade_interval() -> u64 { 60 }
+fn default_max_total_exposure_usd() -> f64 { 100_000.0 }
+fn default_max_market_allocation_pct() -> f64 { 100.0 }
+fn default_allocation_rebalance_hours() -> u64 { 24 }
+fn default_max_oracle_divergence() -> f64 { 1.0 }
+fn default_max_mark_index_divergence() -> f64 { 0.5 }
+
+/// Response to elevated realized volatility
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolatilityAction {
+    /// Scale the target size by `size_multiplier`
+    #[default]
+    Halve,
+    /// Block new entries; open trades are kept
+    Pause,
+}
+
+/// Realized volatility de-risking: spot returns over `window_mins` are
+/// measured every `interval_secs` and compared with the
+/// `trigger_percentile` of the readings over `history_hours`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolatilityConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Return window each reading covers (minutes)
+    #[serde(default = "default_vol_window")]
+    pub window_mins: u64,
+    #[serde(default = "default_vol_interval")]
+    pub interval_secs: u64,
+    /// History a reading is ranked against (hours)
+    #[serde(default = "default_vol_history")]
+    pub history_hours: u64,
+    /// Percentile of past readings above which volatility is elevated
+    #[serde(default = "default_vol_percentile")]
+    pub trigger_percentile: f64,
+    /// Readings needed before the trigger applies
+    #[serde(default = "default_vol_min_history")]
+    pub min_history: usize,
+    #[serde(default)]
+    pub action: VolatilityAction,
+    /// Target size multiplier for `halve`
+    #[serde(default = "default_vol_size_multiplier")]
+    pub size_multiplier: f64,
+}
+
+fn default_vol_window() -> u64 { 5 }
+fn default_vol_interval() -> u64 { 60 }
+fn default_vol_history() -> u64 { 24 }
+fn default_vol_percentile() -> f64 { 95.0 }
+fn default_vol_min_history() -> usize { 60 }
+fn default_vol_size_multiplier() -> f64 { 0.5 }
+
+impl Default for VolatilityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_mins: default_vol_window(),
+            interval_secs: default_vol_interval(),
+            history_hours: default_vol_history(),
+            trigger_percentile: default_vol_percentile(),
+            min_history: default_vol_min_history(),
+            action: VolatilityAction::default(),
+            size_multiplier: default_vol_size_multiplier(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalanceConfig {
+    pub check_interval_secs: u64,
+    pub min_rebalance_size_sol: f64,
+    #[serde(default = "default_max_rebalances")]
+    pub max_rebalances_per_hour: u32,
+}
+
+fn default_max_rebalances() -> u32 { 10 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionConfig {
+    pub use_jito: bool,
+    pub jito_tip_lamports: u64,
+    #[serde(default = "default_jito_url")]
+    pub jito_block_engine_url: String,
+    /// How long a Jito bundle may take to land before falling back to a
+    /// regular priority-fee send
+    #[serde(default = "default_jito_landing_timeout")]
+    pub jito_landing_timeout_secs: u64,
+    /// Jito tip sizing
+    #[serde(default)]
+    pub jito_tip: JitoTipConfig,
+    pub max_retries: u32,
+    pub retry_delay_ms: u64,
+    pub simulate_before_submit: bool,
+    pub priority_fee: PriorityFeeConfig,
+    /// Perp taker fee (bps of notional)
+    #[serde(default = "default_perp_taker_fee")]
+    pub perp_taker_fee_bps: f64,
+    /// Perp maker fee (bps of notional, negative = rebate)
+    #[serde(default = "default_perp_maker_fee")]
+    pub perp_maker_fee_bps: f64,
+    /// Passive (post-only) perp entry
+    #[serde(default)]
+    pub passive_entry: PassiveEntryConfig,
+    /// Leg size mismatch tolerated before reconciling (SOL)
+    #[serde(default = "default_leg_tolerance")]
+    pub leg_tolerance_sol: f64,
+    /// Market-data-to-position budget for the open path (ms, 0 = no alarm)
+    #[serde(default = "default_open_latency_budget")]
+    pub open_latency_budget_ms: u64,
+    /// Oldest swap quote a transaction is built from (ms); older ones are re-quoted
+    #[serde(default = "default_max_quote_age")]
+    pub max_quote_age_ms: u64,
+    /// Re-quotes per swap before giving up on a stale quote
+    #[serde(default = "default_max_requotes")]
+    pub max_requotes: u32,
+    /// Fill the spot leg on a centralized exchange instead of Jupiter
+    #[serde(default)]
+    pub cex: CexConfig,
+    /// Venue for the perp leg
+    #[serde(default)]
+    pub perp_routing: PerpRouting,
+    /// Durable nonce for close transactions
+    #[serde(default)]
+    pub durable_nonce: DurableNonceConfig,
+    /// Checklist every live order must pass before submission
+    #[serde(default)]
+    pub pre_trade: PreTradeConfig,
+}
+
+impl ExecutionConfig {
+    /// Fee on a spot fill (bps of notional): the exchange's taker fee, or
+    /// none on Jupiter, whose pool fees are priced into its quotes
+    pub fn spot_fee_bps(&self) -> f64 {
+        if self.cex.enabled { self.cex.taker_fee_bps } else { 0.0 }
+    }
+}
+
+/// Where the perp leg is placed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PerpRouting {
+    #[default]
+    Drift,
+    Hyperliquid,
+    JupiterPerps,
+    /// Whichever configured venue pays shorts the most, net of borrow fees
+    BestFunding,
+}
+
+impl PerpRouting {
+    /// Fixed venue name, if routing is not dynamic
+    pub fn venue_name(&self) -> Option<&'static str> {
+        match self {
+            PerpRouting::Drift => Some("drift"),
+            PerpRouting::Hyperliquid => Some("hyperliquid"),
+            PerpRouting::JupiterPerps => Some("jupiter_perps"),
+            PerpRouting::BestFunding => None,
+        }
+    }
+}
+
+fn default_jito_url() -> String {
+    "https://mainnet.block-engine.jito.wtf".to_string()
+}
+
+fn default_jito_landing_timeout() -> u64 { 8 }
+
+/// Jito tip sizing: "fixed" tips `jito_tip_lamports`; "dynamic" tips a
+/// percentile of recently landed tips. Either way the tip is capped at a
+/// share of the trade's expected profit, then clamped to [min, max].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JitoTipConfig {
+    #[serde(default = "default_tip_strategy")]
+    pub strategy: String,
+    /// Jito tip floor endpoint (recent landed tip percentiles)
+    #[serde(default = "default_tip_floor_url")]
+    pub tip_floor_url: String,
+    /// Landed tip percentile to pay: 25, 50, 75, 95 or 99
+    #[serde(default = "default_tip_percentile")]
+    pub percentile: u8,
+    /// Most of a trade's expected profit to spend on the tip (%)
+    #[serde(default = "default_tip_profit_share")]
+    pub max_profit_share_pct: f64,
+    #[serde(default = "default_min_tip")]
+    pub min_lamports: u64,
+    #[serde(default = "default_max_tip")]
+    pub max_lamports: u64,
+    /// How long fetched tip percentiles are reused
+    #[serde(default = "default_tip_refresh")]
+    pub refresh_secs: u64,
+}
+
+fn default_tip_strategy() -> String { "fixed".to_string() }
+fn default_tip_floor_url() -> String { "https://bundles.jito.wtf/api/v1/bundles/tip_floor".to_string() }
+fn default_tip_percentile() -> u8 { 50 }
+fn default_tip_profit_share() -> f64 { 10.0 }
+fn default_min_tip() -> u64 { 1_000 }
+fn default_max_tip() -> u64 { 1_000_000 }
+fn default_tip_refresh() -> u64 { 30 }
+
+impl Default for JitoTipConfig {
+    fn default() -> Self {
+        Self {
+            strategy: default_tip_strategy(),
+            tip_floor_url: default_tip_floor_url(),
+            percentile: default_tip_percentile(),
+            max_profit_share_pct: default_tip_profit_share(),
+            min_lamports: default_min_tip(),
+            max_lamports: default_max_tip(),
+            refresh_secs: default_tip_refresh(),
+        }
+    }
+}
+fn default_perp_taker_fee() -> f64 { 3.5 }
+fn default_perp_maker_fee() -> f64 { -0.25 }
+fn default_leg_tolerance() -> f64 { 0.01 }
+fn default_open_latency_budget() -> u64 { 2000 }
+fn default_max_quote_age() -> u64 { 2000 }
+fn default_max_requotes() -> u32 { 2 }
+
+/// Passive entry: post-only perp limit order first, spot leg once filled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassiveEntryConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Distance behind the same-side touch (bps); 0 joins the touch
+    #[serde(default = "default_limit_offset")]
+    pub limit_offset_bps: f64,
+    /// Cancel the order if not filled within this time
+    #[serde(default = "default_fill_timeout")]
+    pub fill_timeout_secs: u64,
+    /// Replace the order at the new passive price once the book moves this
+    /// far from it (bps); 0 leaves it resting
+    #[serde(default)]
+    pub reprice_bps: f64,
+}
+
+/// Pre-trade checklist thresholds. Exposure, reversal and collateral
+/// checks apply only to orders adding exposure, so hedges and closes are
+/// not blocked by the state they are reducing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreTradeConfig {
+    /// Oldest price update to trade on (ms)
+    #[serde(default = "default_pre_trade_price_age")]
+    pub max_price_age_ms: u64,
+    /// Compare the oracle spot price with the CEX ticker (`execution.cex.api_url`)
+    #[serde(default = "default_true")]
+    pub cex_reference: bool,
+    /// Largest oracle spot deviation from the CEX ticker (%)
+    #[serde(default = "default_pre_trade_cex_deviation")]
+    pub max_cex_deviation_pct: f64,
+    /// Largest perp mark deviation from spot (%)
+    #[serde(default = "default_pre_trade_basis")]
+    pub max_basis_pct: f64,
+    #[serde(default = "default_pre_trade_rpc_latency")]
+    pub max_rpc_latency_ms: u64,
+    /// Live orders allowed in any 60s window
+    #[serde(default = "default_pre_trade_orders_per_minute")]
+    pub max_orders_per_minute: u32,
+}
+
+fn default_pre_trade_price_age() -> u64 { 5000 }
+fn default_pre_trade_cex_deviation() -> f64 { 0.5 }
+fn default_pre_trade_basis() -> f64 { 2.0 }
+fn default_pre_trade_rpc_latency() -> u64 { 1500 }
+fn default_pre_trade_orders_per_minute() -> u32 { 20 }
+
+impl Default for PreTradeConfig {
+    fn default() -> Self {
+        Self {
+            max_price_age_ms: default_pre_trade_price_age(),
+            cex_reference: true,
+            max_cex_deviation_pct: default_pre_trade_cex_deviation(),
+            max_basis_pct: default_pre_trade_basis(),
+            max_rpc_latency_ms: default_pre_trade_rpc_latency(),
+            max_orders_per_minute: default_pre_trade_orders_per_minute(),
+        }
+    }
+}
+
+/// Durable nonce: reduce-only and close transactions use a nonce account
+/// instead of a recent blockhash, so they cannot expire while confirming
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DurableNonceConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Nonce account keypair file
+    #[serde(default = "default_nonce_keypair_path")]
+    pub account_keypair_path: String,
+    /// Generate the keypair and create the account on chain if missing
+    #[serde(default = "default_true")]
+    pub create_if_missing: bool,
+}
+
+fn default_nonce_keypair_path() -> String { "nonce-account.json".to_string() }
+
+impl Default for DurableNonceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            account_keypair_path: default_nonce_keypair_path(),
+            create_if_missing: true,
+        }
+    }
+}
+
+fn default_limit_offset() -> f64 { 1.0 }
+fn default_fill_timeout() -> u64 { 30 }
+
+impl Default for PassiveEntryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            limit_offset_bps: default_limit_offset(),
+            fill_timeout_secs: default_fill_timeout(),
+            reprice_bps: 0.0,
+        }
+    }
+}
+
+/// Centralized exchange for the spot leg
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CexConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Exchange connector (currently "binance")
+    #[serde(default = "default_cex_exchange")]
+    pub exchange: String,
+    #[serde(default = "default_cex_api_url")]
+    pub api_url: String,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    /// Spot market traded for the spot leg
+    #[serde(default = "default_cex_symbol")]
+    pub symbol: String,
+    #[serde(default = "default_cex_base_asset")]
+    pub base_asset: String,
+    #[serde(default = "default_cex_quote_asset")]
+    pub quote_asset: String,
+    /// Order size increment (SOL)
+    #[serde(default = "default_cex_lot_size")]
+    pub lot_size_sol: f64,
+    /// Taker fee (bps), used when commission is paid in a fee token
+    #[serde(default = "default_cex_taker_fee")]
+    pub taker_fee_bps: f64,
+    /// Network for deposits and withdrawals
+    #[serde(default = "default_cex_network")]
+    pub network: String,
+    #[serde(default = "default_cex_recv_window")]
+    pub recv_window_ms: u64,
+    /// How often exchange balances and pending transfers are polled
+    #[serde(default = "default_cex_poll")]
+    pub poll_interval_secs: u64,
+}
+
+fn default_cex_exchange() -> String { "binance".to_string() }
+fn default_cex_api_url() -> String { "https://api.binance.com".to_string() }
+fn default_cex_symbol() -> String { "SOLUSDC".to_string() }
+fn default_cex_base_asset() -> String { "SOL".to_string() }
+fn default_cex_quote_asset() -> String { "USDC".to_string() }
+fn default_cex_lot_size() -> f64 { 0.001 }
+fn default_cex_taker_fee() -> f64 { 10.0 }
+fn default_cex_network() -> String { "SOL".to_string() }
+fn default_cex_recv_window() -> u64 { 5000 }
+fn default_cex_poll() -> u64 { 30 }
+
+impl Default for CexConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            exchange: default_cex_exchange(),
+            api_url: default_cex_api_url(),
+            api_key: None,
+            api_secret: None,
+            symbol: default_cex_symbol(),
+            base_asset: default_cex_base_asset(),
+            quote_asset: default_cex_quote_asset(),
+            lot_size_sol: default_cex_lot_size(),
+            taker_fee_bps: default_cex_taker_fee(),
+            network: default_cex_network(),
+            recv_window_ms: default_cex_recv_window(),
+            poll_interval_secs: default_cex_poll(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityFeeConfig {
+    pub strategy: String,
+    #[serde(default)]
+    pub fixed_fee: u64,
+    #[serde(default = "default_max_priority_fee")]
+    pub max_fee: u64,
+}
+
+fn default_max_priority_fee() -> u64 { 100000 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryConfig {
+    pub log_level: String,
+    #[serde(default)]
+    pub json_logs: bool,
+    pub log_file: Option<String>,
+    #[serde(default = "default_metrics_port")]
+    pub metrics_port: u16,
+    #[serde(default = "default_true")]
+    pub enable_metrics: bool,
+    #[serde(default)]
+    pub enable_alerts: bool,
+    pub alert_webhook: Option<String>,
+    #[serde(default)]
+    pub telegram: TelegramConfig,
+    #[serde(default)]
+    pub alerting: AlertingConfig,
+    /// OTLP collector endpoint for trace export (e.g. http://localhost:4317)
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+    /// Service name reported with exported traces
+    #[serde(default = "default_otlp_service_name")]
+    pub otlp_service_name: String,
+    /// Sampled market data written to files for offline research
+    #[serde(default)]
+    pub market_export: MarketExportConfig,
+}
+
+fn default_metrics_port() -> u16 { 9090 }
+fn default_otlp_service_name() -> String { "sol-basis-bot".to_string() }
+fn default_true() -> bool { true }
+
+/// File format of exported market data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    /// Compacted from CSV when each file rotates (needs the `parquet` feature)
+    Parquet,
+}
+
+/// How often export files roll over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportRotation {
+    Hourly,
+    #[default]
+    Daily,
+}
+
+/// Continuous market-data export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketExportConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Directory the files are written to
+    #[serde(default = "default_market_export_dir")]
+    pub directory: String,
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Interval between samples
+    #[serde(default = "default_market_export_interval_ms")]
+    pub sample_interval_ms: u64,
+    #[serde(default)]
+    pub rotation: ExportRotation,
+    /// Files older than this are deleted (0 = keep forever)
+    #[serde(default = "default_market_export_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_market_export_dir() -> String { "data/market".to_string() }
+fn default_market_export_interval_ms() -> u64 { 10_000 }
+fn default_market_export_retention_days() -> u32 { 90 }
+
+impl Default for MarketExportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: default_market_export_dir(),
+            format: ExportFormat::Csv,
+            sample_interval_ms: default_market_export_interval_ms(),
+            rotation: ExportRotation::Daily,
+            retention_days: default_market_export_retention_days(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TelegramConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    pub bot_token: Option<String>,
+    pub chat_id: Option<String>,
+}
+
+/// Alert routing, deduplication and escalation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertingConfig {
+    /// Minimum level written to the log
+    #[serde(default = "default_log_min_level")]
+    pub log_min_level: AlertLevel,
+    /// Minimum level posted to the webhook
+    #[serde(default = "default_webhook_min_level")]
+    pub webhook_min_level: AlertLevel,
+    /// Minimum level sent to Telegram
+    #[serde(default = "default_telegram_min_level")]
+    pub telegram_min_level: AlertLevel,
+    /// Identical alerts within this window are suppressed
+    #[serde(default = "default_dedup_window")]
+    pub dedup_window_secs: u64,
+    /// Re-send interval for unacknowledged critical alerts
+    #[serde(default = "default_escalation_interval")]
+    pub escalation_interval_secs: u64,
+    /// Maximum re-sends of a critical alert (0 = until acknowledged)
+    #[serde(default)]
+    pub max_escalations: u32,
+}
+
+fn default_log_min_level() -> AlertLevel { AlertLevel::Info }
+fn default_webhook_min_level() -> AlertLevel { AlertLevel::Warning }
+fn default_telegram_min_level() -> AlertLevel { AlertLevel::Error }
+fn default_dedup_window() -> u64 { 300 }
+fn default_escalation_interval() -> u64 { 120 }
+
+impl Default for AlertingConfig {
+    fn default() -> Self {
+        Self {
+            log_min_level: default_log_min_level(),
+            webhook_min_level: default_webhook_min_level(),
+            telegram_min_level: default_telegram_min_level(),
+            dedup_window_secs: default_dedup_window(),
+            escalation_interval_secs: default_escalation_interval(),
+            max_escalations: 0,
+        }
+    }
+}
+
+/// Control API configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_api_bind_address")]
+    pub bind_address: String,
+    /// Bearer token required on every request, if set
+    pub auth_token: Option<String>,
+    /// Serve the gRPC control service (requires the `grpc` feature)
+    #[serde(default)]
+    pub grpc_enabled: bool,
+    #[serde(default = "default_grpc_bind_address")]
+    pub grpc_bind_address: String,
+    /// Serve the web dashboard on `/dashboard`
+    #[serde(default = "default_true")]
+    pub dashboard_enabled: bool,
+    /// `/healthz` and `/readyz` failure criteria
+    #[serde(default)]
+    pub health: HealthConfig,
+    /// Confirmation step before a live bot trades
+    #[serde(default)]
+    pub live_confirmation: LiveConfirmationConfig,
+}
+
+/// Live trading interlock: a bot started with `paper_trading: false`
+/// holds until `POST /live/confirm` presents `confirm_token`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LiveConfirmationConfig {
+    /// Held by the confirming operator; must differ from `auth_token`
+    pub confirm_token: Option<String>,
+}
+
+fn default_api_bind_address() -> String { "127.0.0.1:8081".to_string() }
+fn default_grpc_bind_address() -> String { "127.0.0.1:50051".to_string() }
+
+impl ApiConfig {
+    /// The bearer token, unless unset or empty
+    pub fn token(&self) -> Option<&str> {
+        self.auth_token.as_deref().filter(|t| !t.is_empty())
+    }
+
+    /// Refuse to serve on `address` without a bearer token unless only this
+    /// host can reach it
+    pub fn check_exposure(&self, address: &str) -> Result<()> {
+        anyhow::ensure!(
+            self.token().is_some() || is_loopback_address(address),
+            "{} is reachable from other hosts; api.auth_token is required",
+            address
+        );
+        Ok(())
+    }
+
+    /// The live confirmation token: required, and not the bearer token
+    pub fn confirm_token(&self) -> Result<&str> {
+        let token = self
+            .live_confirmation
+            .confirm_token
+            .as_deref()
+            .filter(|t| !t.is_empty())
+            .context("api.live_confirmation.confirm_token is required for live trading")?;
+        anyhow::ensure!(
+            Some(token) != self.token(),
+            "api.live_confirmation.confirm_token must differ from api.auth_token"
+        );
+        Ok(token)
+    }
+}
+
+/// Whether `address` (host:port) only accepts connections from this host
+fn is_loopback_address(address: &str) -> bool {
+    match address.parse::<std::net::SocketAddr>() {
+        Ok(addr) => addr.ip().is_loopback(),
+        Err(_) => address.rsplit_once(':').is_some_and(|(host, _)| host == "localhost"),
+    }
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: default_api_bind_address(),
+            auth_token: None,
+            grpc_enabled: false,
+            grpc_bind_address: default_grpc_bind_address(),
+            dashboard_enabled: true,
+            health: HealthConfig::default(),
+            live_confirmation: LiveConfirmationConfig::default(),
+        }
+    }
+}
+
+/// Liveness and readiness probe criteria
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthConfig {
+    /// Agent loop silent for longer than this fails liveness (ms)
+    #[serde(default = "default_max_agent_lag_ms")]
+    pub max_agent_lag_ms: u64,
+    /// Prices older than this fail liveness, so a wedged feed is restarted (seconds, 0 = never)
+    #[serde(default = "default_max_feed_outage_secs")]
+    pub max_feed_outage_secs: u64,
+    /// Spot or perp prices older than this fail readiness (ms)
+    #[serde(default = "default_max_price_age_ms")]
+    pub max_price_age_ms: u64,
+    /// RPC disconnection fails readiness
+    #[serde(default = "default_true")]
+    pub require_rpc: bool,
+    /// Agent states that fail readiness
+    #[serde(default = "default_unready_states")]
+    pub unready_states: Vec<crate::agent::AgentState>,
+}
+
+fn default_max_agent_lag_ms() -> u64 { 30_000 }
+fn default_max_feed_outage_secs() -> u64 { 300 }
+fn default_max_price_age_ms() -> u64 { 30_000 }
+fn default_unready_states() -> Vec<crate::agent::AgentState> { vec![crate::agent::AgentState::Error] }
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_agent_lag_ms: default_max_agent_lag_ms(),
+            max_feed_outage_secs: default_max_feed_outage_secs(),
+            max_price_age_ms: default_max_price_age_ms(),
+            require_rpc: true,
+            unready_states: default_unready_states(),
+        }
+    }
+}
+
+/// Devnet endpoints and test helpers.
+///
+/// Drift uses the same program id on devnet. Jupiter does not run there, so
+/// spot swaps are mocked, and devnet funding is too thin to trade on, so a
+/// synthetic funding generator can replace it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevnetConfig {
+    #[serde(default = "default_devnet_rpc_url")]
+    pub rpc_url: String,
+    #[serde(default = "default_devnet_ws_url")]
+    pub ws_url: String,
+    #[serde(default = "default_devnet_pyth_feed")]
+    pub pyth_sol_usd_feed: String,
+    #[serde(default = "default_devnet_drift_api_url")]
+    pub drift_api_url: String,
+    #[serde(default = "default_devnet_dlob_url")]
+    pub dlob_url: String,
+    /// USDC mint used by Drift devnet
+    #[serde(default = "default_devnet_usdc_mint")]
+    pub usdc_mint: String,
+    /// Fill spot swaps at the oracle price instead of through Jupiter
+    #[serde(default = "default_true")]
+    pub mock_swaps: bool,
+    /// Simulated slippage on mock swaps (bps)
+    #[serde(default = "default_mock_swap_slippage_bps")]
+    pub mock_swap_slippage_bps: f64,
+    /// Airdrop requested when the wallet holds less than `min_sol_balance` (0 = off)
+    #[serde(default = "default_airdrop_sol")]
+    pub airdrop_sol: f64,
+    #[serde(default = "default_devnet_min_sol")]
+    pub min_sol_balance: f64,
+    #[serde(default)]
+    pub synthetic_funding: SyntheticFundingConfig,
+}
+
+fn default_devnet_rpc_url() -> String { "https://api.devnet.solana.com".to_string() }
+fn default_devnet_ws_url() -> String { "wss://api.devnet.solana.com".to_string() }
+fn default_devnet_pyth_feed() -> String { "J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix".to_string() }
+fn default_devnet_drift_api_url() -> String { "https://master.api.drift.trade".to_string() }
+fn default_devnet_dlob_url() -> String { "https://master.dlob.drift.trade".to_string() }
+fn default_devnet_usdc_mint() -> String { "8zGuJQqwhZafTah7Uc7Z4tXRnguqkn5KLFAP8oV6PHe2".to_string() }
+fn default_mock_swap_slippage_bps() -> f64 { 5.0 }
+fn default_airdrop_sol() -> f64 { 1.0 }
+fn default_devnet_min_sol() -> f64 { 0.5 }
+
+impl Default for DevnetConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: default_devnet_rpc_url(),
+            ws_url: default_devnet_ws_url(),
+            pyth_sol_usd_feed: default_devnet_pyth_feed(),
+            drift_api_url: default_devnet_drift_api_url(),
+            dlob_url: default_devnet_dlob_url(),
+            usdc_mint: default_devnet_usdc_mint(),
+            mock_swaps: true,
+            mock_swap_slippage_bps: default_mock_swap_slippage_bps(),
+            airdrop_sol: default_airdrop_sol(),
+            min_sol_balance: default_devnet_min_sol(),
+            synthetic_funding: SyntheticFundingConfig::default(),
+        }
+    }
+}
+
+/// Synthetic funding: a sine around a mean rate plus noise, so entries,
+/// holds, reversals and exits all occur within a devnet session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticFundingConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Mean rate per funding period
+    #[serde(default = "default_synthetic_mean_rate")]
+    pub mean_rate: f64,
+    /// Sine amplitude; above the mean, funding turns negative each cycle
+    #[serde(default = "default_synthetic_amplitude")]
+    pub amplitude: f64,
+    #[serde(default = "default_synthetic_period")]
+    pub period_secs: u64,
+    /// Uniform noise added to each sample (±)
+    #[serde(default = "default_synthetic_noise")]
+    pub noise: f64,
+    #[serde(default = "default_synthetic_interval")]
+    pub interval_secs: u64,
+}
+
+fn default_synthetic_mean_rate() -> f64 { 0.0001 }
+fn default_synthetic_amplitude() -> f64 { 0.00015 }
+fn default_synthetic_period() -> u64 { 7200 }
+fn default_synthetic_noise() -> f64 { 0.00001 }
+fn default_synthetic_interval() -> u64 { 15 }
+
+impl Default for SyntheticFundingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mean_rate: default_synthetic_mean_rate(),
+            amplitude: default_synthetic_amplitude(),
+            period_secs: default_synthetic_period(),
+            noise: default_synthetic_noise(),
+            interval_secs: default_synthetic_interval(),
+        }
+    }
+}
+
+/// Fault injection for resilience testing. Each RPC call independently
+/// fails, times out or is delayed with the given probabilities; WebSocket
+/// connections drop at random. Only active in builds with the `chaos` feature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChaosConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Probability an RPC call fails immediately
+    #[serde(default = "default_chaos_error_rate")]
+    pub error_rate: f64,
+    /// Probability an RPC call hangs for the request timeout, then fails
+    #[serde(default = "default_chaos_timeout_rate")]
+    pub timeout_rate: f64,
+    /// Probability an RPC call is delayed by `latency_spike_ms`
+    #[serde(default = "default_chaos_latency_spike_rate")]
+    pub latency_spike_rate: f64,
+    #[serde(default = "default_chaos_latency_spike_ms")]
+    pub latency_spike_ms: u64,
+    /// Probability a WebSocket connection drops on each received message
+    #[serde(default = "default_chaos_ws_drop_rate")]
+    pub ws_drop_rate: f64,
+    /// Fixed RNG seed for reproducible runs
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_chaos_error_rate() -> f64 { 0.05 }
+fn default_chaos_timeout_rate() -> f64 { 0.02 }
+fn default_chaos_latency_spike_rate() -> f64 { 0.05 }
+fn default_chaos_latency_spike_ms() -> u64 { 2000 }
+fn default_chaos_ws_drop_rate() -> f64 { 0.001 }
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            error_rate: default_chaos_error_rate(),
+            timeout_rate: default_chaos_timeout_rate(),
+            latency_spike_rate: default_chaos_latency_spike_rate(),
+            latency_spike_ms: default_chaos_latency_spike_ms(),
+            ws_drop_rate: default_chaos_ws_drop_rate(),
+            seed: None,
+        }
+    }
+}
+
+/// Spot inventory (treasury) accounting
+///
+/// Tracks the wallet's SOL and USDC between trades. Entries are funded from
+/// idle SOL first and the rest is bought with USDC; closes sell back into USDC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasuryConfig {
+    /// Fund entries from SOL already held before buying with USDC
+    #[serde(default = "default_true")]
+    pub use_sol_inventory: bool,
+    /// USDC the paper-trading inventory starts with
+    #[serde(default = "default_paper_starting_usdc")]
+    pub paper_starting_usdc: f64,
+    /// APR earned by idle capital, e.g. USDC lending (%)
+    #[serde(default)]
+    pub idle_yield_apr_pct: f64,
+    /// APR earned by the spot leg, e.g. when held as an LST (%)
+    #[serde(default)]
+    pub spot_yield_apr_pct: f64,
+    /// Move realized profit above an equity target to a cold wallet
+    #[serde(default)]
+    pub profit_sweep: ProfitSweepConfig,
+}
+
+fn default_paper_starting_usdc() -> f64 { 10_000.0 }
+
+impl Default for TreasuryConfig {
+    fn default() -> Self {
+        Self {
+            use_sol_inventory: true,
+            paper_starting_usdc: default_paper_starting_usdc(),
+            idle_yield_apr_pct: 0.0,
+            spot_yield_apr_pct: 0.0,
+            profit_sweep: ProfitSweepConfig::default(),
+        }
+    }
+}
+
+/// Scheduled transfer of profit out of the trading wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfitSweepConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Cold wallet receiving the swept USDC (its USDC token account must exist)
+    #[serde(default)]
+    pub cold_wallet: String,
+    /// Equity kept in the trading wallet; only USDC above it is swept (USD)
+    #[serde(default = "default_sweep_equity_target_usd")]
+    pub equity_target_usd: f64,
+    /// Smaller excesses are left for a later sweep (USD)
+    #[serde(default = "default_min_sweep_usd")]
+    pub min_sweep_usd: f64,
+    /// How often the sweep runs
+    #[serde(default = "default_sweep_interval_secs")]
+    pub interval_secs: u64,
+}
+
+fn default_sweep_equity_target_usd() -> f64 { 10_000.0 }
+fn default_min_sweep_usd() -> f64 { 100.0 }
+fn default_sweep_interval_secs() -> u64 { 86_400 }
+
+impl Default for ProfitSweepConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cold_wallet: String::new(),
+            equity_target_usd: default_sweep_equity_target_usd(),
+            min_sweep_usd: default_min_sweep_usd(),
+            interval_secs: default_sweep_interval_secs(),
+        }
+    }
+}
+
+/// Shortest sampling interval accepted for any engine (ms)
+const MIN_ENGINE_INTERVAL_MS: u64 = 100;
+
+/// Sampling intervals of the engines and detectors
+///
+/// Basis and funding history are kept at the basis and funding intervals.
+/// Detector thresholds expressed in samples are derived from these, so a
+/// faster or slower sampler covers the same span of time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnginesConfig {
+    /// Basis engine sampling (ms)
+    #[serde(default = "default_basis_interval_ms")]
+    pub basis_interval_ms: u64,
+    /// Funding engine sampling (ms)
+    #[serde(default = "default_funding_interval_ms")]
+    pub funding_interval_ms: u64,
+    /// Signal engine evaluation (ms)
+    #[serde(default = "default_signal_interval_ms")]
+    pub signal_interval_ms: u64,
+    /// Agent state machine tick (ms)
+    #[serde(default = "default_agent_tick_ms")]
+    pub agent_tick_ms: u64,
+    /// Funding reversal detector analysis (ms)
+    #[serde(default = "default_reversal_interval_ms")]
+    pub reversal_interval_ms: u64,
+    /// Basis regime detector analysis (ms)
+    #[serde(default = "default_regime_interval_ms")]
+    pub regime_interval_ms: u64,
+    /// File the basis and funding history is saved to for warm starts; empty disables
+    #[serde(default = "default_history_path")]
+    pub history_path: String,
+    /// How often the history is saved (seconds)
+    #[serde(default = "default_history_save_secs")]
+    pub history_save_secs: u64,
+    /// Saved history older than this is not reloaded (minutes)
+    #[serde(default = "default_history_max_age_mins")]
+    pub history_max_age_mins: u64,
+    /// External history loaded when there is no saved history to warm up from
+    #[serde(default)]
+    pub history_import: HistoryImportConfig,
+}
+
+fn default_basis_interval_ms() -> u64 { 10_000 }
+fn default_funding_interval_ms() -> u64 { 30_000 }
+fn default_signal_interval_ms() -> u64 { 5_000 }
+fn default_agent_tick_ms() -> u64 { 1_000 }
+fn default_reversal_interval_ms() -> u64 { 30_000 }
+fn default_regime_interval_ms() -> u64 { 60_000 }
+fn default_history_path() -> String { "data/engine_history.json".to_string() }
+fn default_history_save_secs() -> u64 { 60 }
+fn default_history_max_age_mins() -> u64 { 120 }
+
+impl Default for EnginesConfig {
+    fn default() -> Self {
+        Self {
+            basis_interval_ms: default_basis_interval_ms(),
+            funding_interval_ms: default_funding_interval_ms(),
+            signal_interval_ms: default_signal_interval_ms(),
+            agent_tick_ms: default_agent_tick_ms(),
+            reversal_interval_ms: default_reversal_interval_ms(),
+            regime_interval_ms: default_regime_interval_ms(),
+            history_path: default_history_path(),
+            history_save_secs: default_history_save_secs(),
+            history_max_age_mins: default_history_max_age_mins(),
+            history_import: HistoryImportConfig::default(),
+        }
+    }
+}
+
+/// Task supervisor: heartbeat watchdog over the engines, feeds and agent loop
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How often heartbeats are checked (seconds)
+    #[serde(default = "default_supervisor_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Engine or agent loop silence before it counts as stalled (seconds)
+    #[serde(default = "default_supervisor_stall_secs")]
+    pub stall_secs: u64,
+    /// Price feed silence before the feeds are restarted (seconds)
+    #[serde(default = "default_supervisor_feed_stall_secs")]
+    pub feed_stall_secs: u64,
+    /// Restarts of a critical task tolerated within the window before
+    /// trading is paused
+    #[serde(default = "default_supervisor_max_restarts")]
+    pub max_restarts: u32,
+    #[serde(default = "default_supervisor_restart_window_secs")]
+    pub restart_window_secs: u64,
+    /// Safe-state exit on any panic
+    #[serde(default)]
+    pub panic: PanicConfig,
+}
+
+fn default_supervisor_check_interval_secs() -> u64 { 5 }
+fn default_supervisor_stall_secs() -> u64 { 120 }
+fn default_supervisor_feed_stall_secs() -> u64 { 120 }
+fn default_supervisor_max_restarts() -> u32 { 3 }
+fn default_supervisor_restart_window_secs() -> u64 { 600 }
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_interval_secs: default_supervisor_check_interval_secs(),
+            stall_secs: default_supervisor_stall_secs(),
+            feed_stall_secs: default_supervisor_feed_stall_secs(),
+            max_restarts: default_supervisor_max_restarts(),
+            restart_window_secs: default_supervisor_restart_window_secs(),
+            panic: PanicConfig::default(),
+        }
+    }
+}
+
+/// Session report written on shutdown
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionReportConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Directory the reports are written to
+    #[serde(default = "default_report_dir")]
+    pub dir: String,
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+fn default_report_dir() -> String { "reports".to_string() }
+
+impl Default for SessionReportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: default_report_dir(),
+            format: ReportFormat::default(),
+        }
+    }
 }
 
-fn default_jito_url() -> String {
-    "https://mainnet.block-engine.jito.wtf".to_string()
+/// Session report file format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    #[default]
+    Html,
+    Markdown,
 }
 
+/// What happens when any task panics
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PriorityFeeConfig {
-    pub strategy: String,
-    #[serde(default)]
-    pub fixed_fee: u64,
-    #[serde(default = "default_max_priority_fee")]
-    pub max_fee: u64,
+pub struct PanicConfig {
+    /// Stop trading, alert, persist state and exit on a panic
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Close both legs before exiting
+    #[serde(default = "default_true")]
+    pub flatten_positions: bool,
+    /// Longest the flatten may take (seconds)
+    #[serde(default = "default_panic_flatten_timeout_secs")]
+    pub flatten_timeout_secs: u64,
 }
 
-fn default_max_priority_fee() -> u64 { 100000 }
+fn default_panic_flatten_timeout_secs() -> u64 { 60 }
+
+impl Default for PanicConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            flatten_positions: true,
+            flatten_timeout_secs: default_panic_flatten_timeout_secs(),
+        }
+    }
+}
 
+/// Historical data imported for engine warm-up
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TelemetryConfig {
-    pub log_level: String,
+pub struct HistoryImportConfig {
+    /// CSV with `timestamp` and any of `spot_price`, `perp_mark_price`,
+    /// `basis_pct`, `funding_rate` (e.g. a market export file); empty disables
     #[serde(default)]
-    pub json_logs: bool,
-    pub log_file: Option<String>,
-    #[serde(default = "default_metrics_port")]
-    pub metrics_port: u16,
-    #[serde(default = "default_true")]
-    pub enable_metrics: bool,
+    pub csv_path: String,
+    /// Fetch funding and mark/oracle TWAPs from the Drift data API
     #[serde(default)]
-    pub enable_alerts: bool,
-    pub alert_webhook: Option<String>,
+    pub drift_funding: bool,
+    /// Fetch spot candles from Pyth Benchmarks
     #[serde(default)]
-    pub telegram: TelegramConfig,
+    pub pyth_prices: bool,
+    /// History fetched from the APIs (hours)
+    #[serde(default = "default_import_lookback_hours")]
+    pub lookback_hours: u64,
 }
 
-fn default_metrics_port() -> u16 { 9090 }
-fn default_true() -> bool { true }
+fn default_import_lookback_hours() -> u64 { 8 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct TelegramConfig {
+impl Default for HistoryImportConfig {
+    fn default() -> Self {
+        Self {
+            csv_path: String::new(),
+            drift_funding: false,
+            pyth_prices: false,
+            lookback_hours: default_import_lookback_hours(),
+        }
+    }
+}
+
+impl HistoryImportConfig {
+    /// Whether any source is configured
+    pub fn is_enabled(&self) -> bool {
+        !self.csv_path.is_empty() || self.drift_funding || self.pyth_prices
+    }
+}
+
+impl EnginesConfig {
+    pub fn basis_interval(&self) -> Duration {
+        Duration::from_millis(self.basis_interval_ms)
+    }
+
+    pub fn funding_interval(&self) -> Duration {
+        Duration::from_millis(self.funding_interval_ms)
+    }
+
+    pub fn signal_interval(&self) -> Duration {
+        Duration::from_millis(self.signal_interval_ms)
+    }
+
+    pub fn agent_tick(&self) -> Duration {
+        Duration::from_millis(self.agent_tick_ms)
+    }
+
+    pub fn reversal_interval(&self) -> Duration {
+        Duration::from_millis(self.reversal_interval_ms)
+    }
+
+    pub fn regime_interval(&self) -> Duration {
+        Duration::from_millis(self.regime_interval_ms)
+    }
+
+    pub fn history_max_age(&self) -> Duration {
+        Duration::from_secs(self.history_max_age_mins * 60)
+    }
+
+    /// Funding samples recorded over `span` (at least 2)
+    pub fn funding_samples(&self, span: Duration) -> usize {
+        samples_over(span, self.funding_interval())
+    }
+
+    /// Basis samples recorded over `span` (at least 2)
+    pub fn basis_samples(&self, span: Duration) -> usize {
+        samples_over(span, self.basis_interval())
+    }
+}
+
+/// Samples taken every `interval` over `span`, at least 2
+fn samples_over(span: Duration, interval: Duration) -> usize {
+    ((span.as_millis() / interval.as_millis().max(1)) as usize).max(2)
+}
+
+/// Where `${NAME}` placeholders in config values are resolved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretProvider {
+    /// Process environment
+    #[default]
+    Env,
+    /// One file per secret in `file_dir` (Docker/Kubernetes secrets)
+    File,
+    /// Vault-compatible KV HTTP API
+    Vault,
+}
+
+/// Secrets provider configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretsConfig {
     #[serde(default)]
-    pub enabled: bool,
-    pub bot_token: Option<String>,
-    pub chat_id: Option<String>,
+    pub provider: SecretProvider,
+    #[serde(default = "default_secrets_file_dir")]
+    pub file_dir: String,
+    /// Vault address, e.g. https://vault.internal:8200
+    pub vault_url: Option<String>,
+    /// KV secret path (v2 paths include `data/`)
+    #[serde(default = "default_vault_path")]
+    pub vault_path: String,
+    /// Environment variable holding the Vault token
+    #[serde(default = "default_vault_token_env")]
+    pub vault_token_env: String,
+}
+
+fn default_secrets_file_dir() -> String { "/run/secrets".to_string() }
+fn default_vault_path() -> String { "secret/data/sol-basis-bot".to_string() }
+fn default_vault_token_env() -> String { "VAULT_TOKEN".to_string() }
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            provider: SecretProvider::Env,
+            file_dir: default_secrets_file_dir(),
+            vault_url: None,
+            vault_path: default_vault_path(),
+            vault_token_env: default_vault_token_env(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -278,24 +2713,379 @@ pub struct ProtocolsConfig {
     pub drift: DriftConfig,
     pub pyth: PythConfig,
     pub jupiter: JupiterConfig,
+    #[serde(default)]
+    pub hyperliquid: HyperliquidConfig,
+    #[serde(default)]
+    pub jupiter_perps: JupiterPerpsConfig,
+    /// Direct Orca Whirlpool swaps for small spot orders
+    #[serde(default)]
+    pub orca: OrcaConfig,
+    /// Second spot oracle, aggregated with Pyth and Jupiter
+    #[serde(default)]
+    pub switchboard: SwitchboardConfig,
+    /// How spot sources are combined into one price
+    #[serde(default)]
+    pub oracles: OracleAggregationConfig,
+}
+
+/// A tradable asset in the market registry, keyed by symbol ("SOL")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketConfig {
+    /// Drift perp market index
+    pub drift_market_index: u16,
+    /// Pyth <symbol>/USD price account
+    pub pyth_feed: String,
+    /// Spot token mint (wrapped SOL for SOL)
+    pub spot_mint: String,
+    /// Spot token decimals
+    pub decimals: u8,
+}
+
+fn default_market() -> String { "SOL".to_string() }
+
+/// A named strategy instance: config overrides on top of the base config,
+/// with its own performance store, positions, metrics and controls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyConfig {
+    /// Lowercase letters, digits and dashes, e.g. "sol-conservative"
+    pub name: String,
+    /// Config keys to override, merged like a profile overlay
+    #[serde(default)]
+    pub overrides: serde_yaml::Value,
+    /// A/B shadow of the primary agent: stored under `shadow/<name>/`, no
+    /// manual commands, compared against the primary's trades
+    #[serde(default)]
+    pub shadow: bool,
+}
+
+fn default_markets() -> BTreeMap<String, MarketConfig> {
+    BTreeMap::from([(
+        "SOL".to_string(),
+        MarketConfig {
+            drift_market_index: 0,
+            pyth_feed: "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG".to_string(),
+            spot_mint: "So11111111111111111111111111111111111111112".to_string(),
+            decimals: 9,
+        },
+    )])
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DriftConfig {
     pub program_id: String,
+    /// Perp market; set from the `markets` registry
+    #[serde(default)]
     pub market_index: u16,
+    /// Funding settlement interval ("1h", "8h" or "continuous")
+    #[serde(default)]
+    pub funding_interval: FundingInterval,
+    /// Drift data API (market stats)
+    #[serde(default = "default_drift_api_url")]
+    pub api_url: String,
+    /// DLOB server for the perp order book
+    #[serde(default = "default_dlob_url")]
+    pub dlob_url: String,
+    /// Order book levels fetched per side
+    #[serde(default = "default_dlob_depth")]
+    pub dlob_depth: usize,
+    /// Order book poll interval
+    #[serde(default = "default_dlob_poll")]
+    pub dlob_poll_ms: u64,
 }
 
+fn default_drift_api_url() -> String { "https://mainnet-beta.api.drift.trade".to_string() }
+fn default_dlob_url() -> String { "https://dlob.drift.trade".to_string() }
+fn default_dlob_depth() -> usize { 10 }
+fn default_dlob_poll() -> u64 { 1000 }
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PythConfig {
+    /// Traded asset's USD feed; set from the `markets` registry
+    #[serde(default)]
+    pub sol_usd_feed: String,
+    /// USDC/USD feed for the peg monitor (empty = USDC taken at $1)
+    #[serde(default = "default_usdc_usd_feed")]
+    pub usdc_usd_feed: String,
+}
+
+fn default_usdc_usd_feed() -> String { "Gnt27xtC473ZT2Mw5u8wZ68Z3gULkSTb5DuxJy7eJotD".to_string() }
+
+/// Switchboard on-demand SOL/USD feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwitchboardConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Crossbar gateway that simulates on-demand feeds
+    #[serde(default = "default_switchboard_crossbar_url")]
+    pub crossbar_url: String,
+    /// SOL/USD feed hash
+    #[serde(default)]
     pub sol_usd_feed: String,
+    #[serde(default = "default_switchboard_poll")]
+    pub poll_interval_ms: u64,
+}
+
+fn default_switchboard_crossbar_url() -> String { "https://crossbar.switchboard.xyz".to_string() }
+fn default_switchboard_poll() -> u64 { 1000 }
+
+impl Default for SwitchboardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            crossbar_url: default_switchboard_crossbar_url(),
+            sol_usd_feed: String::new(),
+            poll_interval_ms: default_switchboard_poll(),
+        }
+    }
+}
+
+/// Composite spot price from several sources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleAggregationConfig {
+    /// Quotes older than this are left out (ms)
+    #[serde(default = "default_max_quote_age")]
+    pub max_quote_age_ms: i64,
+    /// Quotes further than this from the median are rejected (%); needs 3+ sources
+    #[serde(default = "default_outlier_pct")]
+    pub outlier_pct: f64,
+    /// Weights of each source in the composite
+    #[serde(default = "default_one")]
+    pub pyth_weight: f64,
+    #[serde(default = "default_one")]
+    pub switchboard_weight: f64,
+    #[serde(default = "default_jupiter_weight")]
+    pub jupiter_weight: f64,
+    /// What to do when Pyth stops publishing
+    #[serde(default)]
+    pub contingency: OracleContingencyConfig,
+    /// What to do when USDC trades off its peg
+    #[serde(default)]
+    pub usdc_peg: UsdcPegConfig,
+}
+
+fn default_max_quote_age() -> i64 { 5000 }
+fn default_outlier_pct() -> f64 { 0.5 }
+fn default_one() -> f64 { 1.0 }
+fn default_jupiter_weight() -> f64 { 0.5 }
+
+impl Default for OracleAggregationConfig {
+    fn default() -> Self {
+        Self {
+            max_quote_age_ms: default_max_quote_age(),
+            outlier_pct: default_outlier_pct(),
+            pyth_weight: default_one(),
+            switchboard_weight: default_one(),
+            jupiter_weight: default_jupiter_weight(),
+            contingency: OracleContingencyConfig::default(),
+            usdc_peg: UsdcPegConfig::default(),
+        }
+    }
+}
+
+/// Contingency mode while Pyth is stale: spot priced from the other
+/// sources, thresholds widened, entries blocked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleContingencyConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Pyth silent (by publish time) for longer than this enters contingency (ms);
+    /// it ends once Pyth is within `max_quote_age_ms` again
+    #[serde(default = "default_pyth_stale")]
+    pub pyth_stale_ms: i64,
+    /// Noise-sensitive thresholds (entry, rebalance, hedge drift, oracle
+    /// divergence) are multiplied by this while in contingency
+    #[serde(default = "default_contingency_multiplier")]
+    pub threshold_multiplier: f64,
+}
+
+fn default_pyth_stale() -> i64 { 10_000 }
+fn default_contingency_multiplier() -> f64 { 1.5 }
+
+impl Default for OracleContingencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pyth_stale_ms: default_pyth_stale(),
+            threshold_multiplier: default_contingency_multiplier(),
+        }
+    }
+}
+
+/// USDC depeg guard: entries paused while USDC/USD is outside the band
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsdcPegConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Largest tolerated distance of USDC/USD from $1 (%); back within half
+    /// of it ends the depeg
+    #[serde(default = "default_usdc_band")]
+    pub band_pct: f64,
+}
+
+fn default_usdc_band() -> f64 { 0.5 }
+
+impl Default for UsdcPegConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            band_pct: default_usdc_band(),
+        }
+    }
+}
+
+impl OracleContingencyConfig {
+    /// Threshold scale for the current mode
+    pub fn scale(&self, in_contingency: bool) -> f64 {
+        if in_contingency { self.threshold_multiplier } else { 1.0 }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JupiterConfig {
     pub api_url: String,
+    /// Spot token mint; set from the `markets` registry
+    #[serde(default)]
     pub sol_mint: String,
+    /// Spot token decimals; set from the `markets` registry
+    #[serde(default = "default_spot_decimals")]
+    pub spot_decimals: u8,
     pub usdc_mint: String,
+    /// Typical trade size quoted for depth-aware pricing (SOL, 0 = disabled)
+    #[serde(default = "default_depth_quote_size")]
+    pub depth_quote_size_sol: f64,
+    /// How often depth quotes are refreshed
+    #[serde(default = "default_depth_quote_interval")]
+    pub depth_quote_interval_ms: u64,
+}
+
+fn default_spot_decimals() -> u8 { 9 }
+fn default_depth_quote_size() -> f64 { 100.0 }
+fn default_depth_quote_interval() -> u64 { 5000 }
+
+/// Hyperliquid perp venue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HyperliquidConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_hl_api_url")]
+    pub api_url: String,
+    /// Perp traded for the short leg
+    #[serde(default = "default_hl_coin")]
+    pub coin: String,
+    /// API wallet private key (hex) used to sign orders
+    pub private_key: Option<String>,
+    /// Main account address, when the API wallet trades on its behalf
+    pub account_address: Option<String>,
+    /// Distance through the mark for IOC "market" orders (%)
+    #[serde(default = "default_hl_market_slippage")]
+    pub market_slippage_pct: f64,
+}
+
+fn default_hl_api_url() -> String { "https://api.hyperliquid.xyz".to_string() }
+fn default_hl_coin() -> String { "SOL".to_string() }
+fn default_hl_market_slippage() -> f64 { 1.0 }
+
+impl Default for HyperliquidConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_url: default_hl_api_url(),
+            coin: default_hl_coin(),
+            private_key: None,
+            account_address: None,
+            market_slippage_pct: default_hl_market_slippage(),
+        }
+    }
+}
+
+/// Jupiter Perps (JLP pool) venue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JupiterPerpsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_jup_perps_program")]
+    pub program_id: String,
+    #[serde(default = "default_jup_perps_pool")]
+    pub pool: String,
+    /// SOL custody (the traded market)
+    #[serde(default = "default_jup_perps_custody")]
+    pub custody: String,
+    /// USDC custody backing shorts; its borrow rate is what shorts pay
+    #[serde(default = "default_jup_perps_collateral_custody")]
+    pub collateral_custody: String,
+    /// Position size over posted collateral
+    #[serde(default = "default_jup_perps_leverage")]
+    pub leverage: f64,
+    /// Worst acceptable fill through the oracle price (%)
+    #[serde(default = "default_hl_market_slippage")]
+    pub market_slippage_pct: f64,
+    /// How long to wait for a keeper to fill a request before reporting it unfilled
+    #[serde(default = "default_jup_perps_fill_timeout")]
+    pub fill_timeout_secs: u64,
+}
+
+fn default_jup_perps_program() -> String { "PERPHjGBqRHArX4DySjwM6UJHiR3sWAatqfdBS2qQJu".to_string() }
+fn default_jup_perps_pool() -> String { "5BUwFW4nRbftYTDMbgxykoFWqWHPzahFSNAaaaJtVKsq".to_string() }
+fn default_jup_perps_custody() -> String { "7xS2gz2bTp3fwCC7knJvUWTEU9Tycczu6VhJYKgi1wdz".to_string() }
+fn default_jup_perps_collateral_custody() -> String { "G18jKKXQwBbrHeiK3C9MRXhkHsLHf7XgCSisykV46EZa".to_string() }
+fn default_jup_perps_leverage() -> f64 { 2.0 }
+fn default_jup_perps_fill_timeout() -> u64 { 30 }
+
+impl Default for JupiterPerpsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            program_id: default_jup_perps_program(),
+            pool: default_jup_perps_pool(),
+            custody: default_jup_perps_custody(),
+            collateral_custody: default_jup_perps_collateral_custody(),
+            leverage: default_jup_perps_leverage(),
+            market_slippage_pct: default_hl_market_slippage(),
+            fill_timeout_secs: default_jup_perps_fill_timeout(),
+        }
+    }
+}
+
+/// Orca Whirlpool spot venue: small orders swap directly against one
+/// pool instead of going through the Jupiter API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrcaConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_orca_program")]
+    pub program_id: String,
+    /// SOL/USDC Whirlpool
+    #[serde(default = "default_orca_pool")]
+    pub pool: String,
+    /// Largest spot order swapped directly (SOL); larger ones go to Jupiter
+    #[serde(default = "default_orca_max_size")]
+    pub max_size_sol: f64,
+}
+
+fn default_orca_program() -> String { "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string() }
+fn default_orca_pool() -> String { "Czfq3xZZDmsdGdUyrNLtRhGc47cXcZtLG4crryfu44zE".to_string() }
+fn default_orca_max_size() -> f64 { 5.0 }
+
+impl Default for OrcaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            program_id: default_orca_program(),
+            pool: default_orca_pool(),
+            max_size_sol: default_orca_max_size(),
+        }
+    }
+}
+
+/// Response to a High funding reversal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HighReversalAction {
+    /// Log only
+    #[default]
+    Warn,
+    /// Buy back `reversal_tilt_pct` of the perp short, once per trade
+    Tilt,
 }
 
 /// Agentic features configuration
@@ -333,6 +3123,70 @@ pub struct AgenticConfig {
     #[serde(default = "default_min_position_multiplier")]
     pub min_position_multiplier: f64,
     
+    /// Monte Carlo paths for the risk-of-ruin estimate (at most 20000)
+    #[serde(default = "default_monte_carlo_paths")]
+    pub monte_carlo_paths: usize,
+    
+    /// Equity loss counted as ruin (%)
+    #[serde(default = "default_ruin_threshold_pct")]
+    pub ruin_threshold_pct: f64,
+    
+    /// Highest acceptable 30-day risk of ruin (%); Kelly is cut above it
+    #[serde(default = "default_max_risk_of_ruin_pct")]
+    pub max_risk_of_ruin_pct: f64,
+    
+    /// Highest acceptable 30-day probability of hitting max_drawdown_pct (%)
+    #[serde(default = "default_max_drawdown_breach_pct")]
+    pub max_drawdown_breach_pct: f64,
+    
+    /// Rolling windows reported next to lifetime metrics (days)
+    #[serde(default = "default_metric_windows_days")]
+    pub metric_windows_days: Vec<u32>,
+    
+    /// Share of the sizing edge taken from rolling windows (0-1); the rest is lifetime
+    #[serde(default = "default_recent_window_weight")]
+    pub recent_window_weight: f64,
+    
+    /// Shrink signal confidence when realized win rates fall short of it
+    #[serde(default = "default_true")]
+    pub enable_confidence_calibration: bool,
+    
+    /// Confidence buckets in the calibration report
+    #[serde(default = "default_calibration_buckets")]
+    pub calibration_buckets: usize,
+    
+    /// Trades needed before confidence is shrunk
+    #[serde(default = "default_calibration_min_trades")]
+    pub calibration_min_trades: u32,
+    
+    /// Smallest multiplier applied to confidence (0-1)
+    #[serde(default = "default_min_confidence_shrink")]
+    pub min_confidence_shrink: f64,
+    
+    /// Follow blocked open signals as hypothetical trades
+    #[serde(default = "default_true")]
+    pub track_skipped_trades: bool,
+    
+    /// Longest a hypothetical trade is followed before it is resolved (minutes)
+    #[serde(default = "default_skipped_trade_horizon_mins")]
+    pub skipped_trade_horizon_mins: u64,
+    
+    /// Grow the max position size with realized P&L
+    #[serde(default)]
+    pub enable_compounding: bool,
+    
+    /// Share of each trade's realized P&L reinvested into the max size (0-1)
+    #[serde(default = "default_compounding_fraction")]
+    pub compounding_fraction: f64,
+    
+    /// Largest compounded max size, as a multiple of the configured one
+    #[serde(default = "default_max_compounding_multiplier")]
+    pub max_compounding_multiplier: f64,
+    
+    /// Smallest compounded max size after losses, as a multiple of the configured one
+    #[serde(default = "default_min_compounding_multiplier")]
+    pub min_compounding_multiplier: f64,
+    
     /// Alert cooldown for reversal detection (seconds)
     #[serde(default = "default_reversal_alert_cooldown")]
     pub reversal_alert_cooldown_secs: u64,
@@ -341,6 +3195,34 @@ pub struct AgenticConfig {
     #[serde(default = "default_true")]
     pub force_close_on_critical_reversal: bool,
     
+    /// Response to a High (not Critical) reversal
+    #[serde(default)]
+    pub high_reversal_action: HighReversalAction,
+    
+    /// Share of the perp short bought back by a tilt (%)
+    #[serde(default = "default_reversal_tilt_pct")]
+    pub reversal_tilt_pct: f64,
+    
+    /// Enable basis regime detection
+    #[serde(default = "default_true")]
+    pub enable_regime_detection: bool,
+    
+    /// Basis history window for regime classification (hours)
+    #[serde(default = "default_regime_window_hours")]
+    pub regime_window_hours: f64,
+    
+    /// Variance-ratio lag (basis samples)
+    #[serde(default = "default_regime_lag")]
+    pub regime_lag: usize,
+    
+    /// Variance ratio at or above which the basis is trending
+    #[serde(default = "default_trending_variance_ratio")]
+    pub trending_variance_ratio: f64,
+    
+    /// Variance ratio at or below which the basis is mean-reverting
+    #[serde(default = "default_mean_reverting_variance_ratio")]
+    pub mean_reverting_variance_ratio: f64,
+    
     /// Export trades to CSV periodically
     #[serde(default)]
     pub auto_export_trades: bool,
@@ -348,14 +3230,122 @@ pub struct AgenticConfig {
     /// CSV export path
     #[serde(default = "default_csv_export_path")]
     pub csv_export_path: String,
+    
+    /// Flag losses this many standard deviations below the mean for review
+    #[serde(default = "default_review_loss_sigma")]
+    pub review_loss_sigma: f64,
+    
+    /// Flag trades whose slippage exceeds this share of notional (bps)
+    #[serde(default = "default_review_slippage_bps")]
+    pub review_slippage_bps: f64,
+    
+    /// Flag trades whose funding missed the entry-APR prediction by this much (%)
+    #[serde(default = "default_review_funding_deviation_pct")]
+    pub review_funding_deviation_pct: f64,
+    
+    /// Trade review report path
+    #[serde(default = "default_review_report_path")]
+    pub review_report_path: String,
+    
+    /// Record the agent's inputs for `replay` (sessions/ next to the performance database)
+    #[serde(default)]
+    pub record_session: bool,
+    
+    /// Online adjustment of the entry thresholds from realized trade ROI
+    #[serde(default)]
+    pub threshold_learning: ThresholdLearningConfig,
+}
+
+/// Online threshold learning: once `min_trades` trades opened under the
+/// current thresholds have closed, their average net P&L (the last
+/// `window_trades` of them) tightens the entry thresholds when below
+/// `tighten_below_ev_usd` and loosens them when above
+/// `loosen_above_ev_usd`, by `step_pct` of their value, within the floors
+/// and ceilings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdLearningConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Recent trades the average P&L is taken over
+    #[serde(default = "default_learning_window")]
+    pub window_trades: usize,
+    /// Trades opened under the current thresholds needed before the next
+    /// adjustment
+    #[serde(default = "default_learning_min_trades")]
+    pub min_trades: usize,
+    /// Relative change per adjustment (%)
+    #[serde(default = "default_learning_step")]
+    pub step_pct: f64,
+    /// Average net P&L per trade, fees included, that tightens (USD)
+    #[serde(default)]
+    pub tighten_below_ev_usd: f64,
+    /// Average net P&L per trade, fees included, that loosens (USD)
+    #[serde(default = "default_learning_loosen_ev")]
+    pub loosen_above_ev_usd: f64,
+    #[serde(default = "default_learning_basis_floor")]
+    pub basis_floor_pct: f64,
+    #[serde(default = "default_learning_basis_ceiling")]
+    pub basis_ceiling_pct: f64,
+    #[serde(default = "default_learning_funding_floor")]
+    pub funding_floor_apr_pct: f64,
+    #[serde(default = "default_learning_funding_ceiling")]
+    pub funding_ceiling_apr_pct: f64,
+}
+
+fn default_learning_window() -> usize { 20 }
+fn default_learning_min_trades() -> usize { 10 }
+fn default_learning_step() -> f64 { 5.0 }
+fn default_learning_loosen_ev() -> f64 { 25.0 }
+fn default_learning_basis_floor() -> f64 { 0.1 }
+fn default_learning_basis_ceiling() -> f64 { 1.0 }
+fn default_learning_funding_floor() -> f64 { 5.0 }
+fn default_learning_funding_ceiling() -> f64 { 50.0 }
+
+impl Default for ThresholdLearningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_trades: default_learning_window(),
+            min_trades: default_learning_min_trades(),
+            step_pct: default_learning_step(),
+            tighten_below_ev_usd: 0.0,
+            loosen_above_ev_usd: default_learning_loosen_ev(),
+            basis_floor_pct: default_learning_basis_floor(),
+            basis_ceiling_pct: default_learning_basis_ceiling(),
+            funding_floor_apr_pct: default_learning_funding_floor(),
+            funding_ceiling_apr_pct: default_learning_funding_ceiling(),
+        }
+    }
 }
 
 fn default_performance_db_path() -> String { "data/performance.json".to_string() }
 fn default_min_trades_for_adaptation() -> u32 { 10 }
 fn default_max_kelly_fraction() -> f64 { 0.25 }
 fn default_min_position_multiplier() -> f64 { 0.2 }
+fn default_monte_carlo_paths() -> usize { 2000 }
+fn default_ruin_threshold_pct() -> f64 { 50.0 }
+fn default_max_risk_of_ruin_pct() -> f64 { 1.0 }
+fn default_max_drawdown_breach_pct() -> f64 { 10.0 }
+fn default_metric_windows_days() -> Vec<u32> { vec![7, 30, 90] }
+fn default_recent_window_weight() -> f64 { 0.6 }
+fn default_calibration_buckets() -> usize { 5 }
+fn default_calibration_min_trades() -> u32 { 20 }
+fn default_min_confidence_shrink() -> f64 { 0.5 }
+fn default_skipped_trade_horizon_mins() -> u64 { 240 }
+fn default_compounding_fraction() -> f64 { 0.5 }
+fn default_max_compounding_multiplier() -> f64 { 2.0 }
+fn default_min_compounding_multiplier() -> f64 { 0.5 }
 fn default_reversal_alert_cooldown() -> u64 { 300 }
+fn default_reversal_tilt_pct() -> f64 { 25.0 }
 fn default_csv_export_path() -> String { "data/trades.csv".to_string() }
+fn default_review_loss_sigma() -> f64 { 2.0 }
+fn default_review_slippage_bps() -> f64 { 25.0 }
+fn default_review_funding_deviation_pct() -> f64 { 50.0 }
+fn default_review_report_path() -> String { "data/trade_review.md".to_string() }
+fn default_regime_window_hours() -> f64 { 4.0 }
+fn default_regime_lag() -> usize { 30 }
+fn default_trending_variance_ratio() -> f64 { 1.3 }
+fn default_mean_reverting_variance_ratio() -> f64 { 0.8 }
 
 impl Default for AgenticConfig {
     fn default() -> Self {
@@ -368,10 +3358,73 @@ impl Default for AgenticConfig {
             max_kelly_fraction: default_max_kelly_fraction(),
             use_half_kelly: true,
             min_position_multiplier: default_min_position_multiplier(),
+            monte_carlo_paths: default_monte_carlo_paths(),
+            ruin_threshold_pct: default_ruin_threshold_pct(),
+            max_risk_of_ruin_pct: default_max_risk_of_ruin_pct(),
+            max_drawdown_breach_pct: default_max_drawdown_breach_pct(),
+            metric_windows_days: default_metric_windows_days(),
+            recent_window_weight: default_recent_window_weight(),
+            enable_confidence_calibration: true,
+            calibration_buckets: default_calibration_buckets(),
+            calibration_min_trades: default_calibration_min_trades(),
+            min_confidence_shrink: default_min_confidence_shrink(),
+            track_skipped_trades: true,
+            skipped_trade_horizon_mins: default_skipped_trade_horizon_mins(),
+            enable_compounding: false,
+            compounding_fraction: default_compounding_fraction(),
+            max_compounding_multiplier: default_max_compounding_multiplier(),
+            min_compounding_multiplier: default_min_compounding_multiplier(),
             reversal_alert_cooldown_secs: default_reversal_alert_cooldown(),
             force_close_on_critical_reversal: true,
+            high_reversal_action: HighReversalAction::default(),
+            reversal_tilt_pct: default_reversal_tilt_pct(),
+            enable_regime_detection: true,
+            regime_window_hours: default_regime_window_hours(),
+            regime_lag: default_regime_lag(),
+            trending_variance_ratio: default_trending_variance_ratio(),
+            mean_reverting_variance_ratio: default_mean_reverting_variance_ratio(),
             auto_export_trades: false,
             csv_export_path: default_csv_export_path(),
+            review_loss_sigma: default_review_loss_sigma(),
+            review_slippage_bps: default_review_slippage_bps(),
+            review_funding_deviation_pct: default_review_funding_deviation_pct(),
+            review_report_path: default_review_report_path(),
+            record_session: false,
+            threshold_learning: ThresholdLearningConfig::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exposed_api_requires_a_token() {
+        let mut api = ApiConfig::default();
+        assert!(api.check_exposure("127.0.0.1:8081").is_ok());
+        assert!(api.check_exposure("localhost:8081").is_ok());
+        assert!(api.check_exposure("0.0.0.0:8081").is_err());
+
+        api.auth_token = Some(String::new());
+        assert!(api.check_exposure("[::]:50051").is_err());
+        api.auth_token = Some("secret".to_string());
+        assert!(api.check_exposure("0.0.0.0:8081").is_ok());
+    }
+
+    #[test]
+    fn test_live_mode_requires_confirmation() {
+        let mut config = AppConfig::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("config.yaml")).unwrap();
+        config.paper_trading = false;
+        config.api.enabled = false;
+        assert!(config.validate().is_err());
+
+        config.api.enabled = true;
+        config.api.auth_token = Some("secret".to_string());
+        config.api.live_confirmation.confirm_token = Some("secret".to_string());
+        assert!(config.validate().is_err());
+
+        config.api.live_confirmation.confirm_token = Some("second-operator".to_string());
+        assert!(config.validate().is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+//! Config Secrets
+//!
+//! String values in the config may reference secrets as `${NAME}` or
+//! `${NAME:-default}`, so credentials stay out of the YAML. Names resolve
+//! through the configured provider (`secrets.provider`), falling back to
+//! the process environment:
+//! - `env`: environment variables
+//! - `file`: one file per secret in `secrets.file_dir`, contents trimmed
+//! - `vault`: a Vault-compatible KV secret, fetched once at load
+//!
+//! The `secrets` section itself only interpolates from the environment.
+
+use anyhow::{Context, Result};
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::{SecretProvider, SecretsConfig};
+
+/// Source of secret values by name
+pub trait SecretSource {
+    fn get(&self, name: &str) -> Result<Option<String>>;
+}
+
+/// Process environment
+pub struct EnvSecrets;
+
+impl SecretSource for EnvSecrets {
+    fn get(&self, name: &str) -> Result<Option<String>> {
+        Ok(std::env::var(name).ok())
+    }
+}
+
+/// Secret files in a directory
+pub struct FileSecrets {
+    dir: PathBuf,
+}
+
+impl SecretSource for FileSecrets {
+    fn get(&self, name: &str) -> Result<Option<String>> {
+        match std::fs::read_to_string(self.dir.join(name)) {
+            Ok(content) => Ok(Some(content.trim().to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read secret file for {}", name)),
+        }
+    }
+}
+
+/// Key/value pairs of one Vault KV secret
+pub struct VaultSecrets {
+    values: HashMap<String, String>,
+}
+
+impl VaultSecrets {
+    /// Fetch the secret at `vault_path`; accepts KV v2 and v1 responses
+    pub fn fetch(config: &SecretsConfig) -> Result<Self> {
+        let base = config.vault_url.as_deref().context("secrets.vault_url is required for the vault provider")?;
+        let token = std::env::var(&config.vault_token_env)
+            .with_context(|| format!("{} is not set", config.vault_token_env))?;
+        let url = format!("{}/v1/{}", base.trim_end_matches('/'), config.vault_path.trim_start_matches('/'));
+
+        // Config loads inside the async runtime; the blocking client needs its own thread
+        let body: serde_json::Value = std::thread::spawn(move || -> Result<serde_json::Value> {
+            let response = reqwest::blocking::Client::new()
+                .get(&url)
+                .header("X-Vault-Token", token)
+                .send()
+                .context("Vault request failed")?
+                .error_for_status()
+                .context("Vault returned an error")?;
+            response.json().context("Invalid Vault response")
+        })
+        .join()
+        .map_err(|_| anyhow::anyhow!("Vault fetch panicked"))??;
+
+        let data = body["data"].get("data").unwrap_or(&body["data"]);
+        let values = data
+            .as_object()
+            .context("Vault secret has no data")?
+            .iter()
+            .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+            .collect();
+        Ok(Self { values })
+    }
+}
+
+impl SecretSource for VaultSecrets {
+    fn get(&self, name: &str) -> Result<Option<String>> {
+        Ok(self.values.get(name).cloned())
+    }
+}
+
+/// Build the configured provider
+pub fn provider(config: &SecretsConfig) -> Result<Box<dyn SecretSource>> {
+    Ok(match config.provider {
+        SecretProvider::Env => Box::new(EnvSecrets),
+        SecretProvider::File => Box::new(FileSecrets { dir: PathBuf::from(&config.file_dir) }),
+        SecretProvider::Vault => Box::new(VaultSecrets::fetch(config)?),
+    })
+}
+
+/// Resolve every `${NAME}` placeholder in an untyped config
+pub fn resolve(config: &mut Value) -> Result<()> {
+    let secrets_config = match config.get_mut("secrets") {
+        Some(section) => {
+            interpolate(section, &EnvSecrets)?;
+            serde_yaml::from_value(section.clone()).context("Invalid secrets section")?
+        }
+        None => SecretsConfig::default(),
+    };
+    let source = provider(&secrets_config)?;
+    interpolate(config, &Fallback(source.as_ref()))
+}
+
+/// Provider first, then the environment
+struct Fallback<'a>(&'a dyn SecretSource);
+
+impl SecretSource for Fallback<'_> {
+    fn get(&self, name: &str) -> Result<Option<String>> {
+        match self.0.get(name)? {
+            Some(value) => Ok(Some(value)),
+            None => EnvSecrets.get(name),
+        }
+    }
+}
+
+/// Replace placeholders in all string values
+pub fn interpolate(value: &mut Value, source: &dyn SecretSource) -> Result<()> {
+    match value {
+        Value::String(s) if s.contains("${") => *s = expand(s, source)?,
+        Value::Mapping(map) => {
+            for (_, v) in map.iter_mut() {
+                interpolate(v, source)?;
+            }
+        }
+        Value::Sequence(items) => {
+            for v in items {
+                interpolate(v, source)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn expand(input: &str, source: &dyn SecretSource) -> Result<String> {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let end = rest[start..].find('}').with_context(|| format!("Unclosed placeholder in {:?}", input))?;
+        let token = &rest[start + 2..start + end];
+        let (name, default) = match token.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (token, None),
+        };
+        let value = match (source.get(name)?, default) {
+            (Some(value), _) => value,
+            (None, Some(default)) => default.to_string(),
+            (None, None) => anyhow::bail!("Secret {} is not set", name),
+        };
+        out.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed;
+
+    impl SecretSource for Fixed {
+        fn get(&self, name: &str) -> Result<Option<String>> {
+            Ok((name == "BOT_TOKEN").then(|| "123:abc".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let mut config: Value = serde_yaml::from_str(
+            "telegram:\n  bot_token: \"${BOT_TOKEN}\"\n  chat_id: \"${CHAT_ID:-42}\"\nurl: \"https://x/${BOT_TOKEN}/y\"\n",
+        ).unwrap();
+        interpolate(&mut config, &Fixed).unwrap();
+        assert_eq!(config["telegram"]["bot_token"].as_str(), Some("123:abc"));
+        assert_eq!(config["telegram"]["chat_id"].as_str(), Some("42"));
+        assert_eq!(config["url"].as_str(), Some("https://x/123:abc/y"));
+
+        let mut missing = Value::String("${NOPE}".to_string());
+        assert!(interpolate(&mut missing, &Fixed).is_err());
+    }
+}
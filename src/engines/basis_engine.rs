@@ -2,29 +2,24 @@
 //!
 //! Calculates and monitors:
 //! - Real-time basis spread (perp vs spot)
+//! - Executable basis at the typical trade size (from depth quotes)
 //! - Optimal hedge ratio for delta-neutral
 //! - Hedge drift detection
-//! - Historical basis percentiles
+//! - Historical basis percentiles (from the shared basis history)
+//! - Mean-reversion half-life and long-run mean (OU fit of the 8h history)
 
 use anyhow::Result;
-use std::collections::VecDeque;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 
 use crate::config::AppConfig;
 use crate::network::event_bus::Event;
-use crate::state::SharedState;
+use crate::state::history::{mean_of, std_dev_of};
+use crate::state::{Heartbeat, SharedState, TimeSeries};
 
-/// Basis spread snapshot
-#[derive(Debug, Clone)]
-pub struct BasisSnapshot {
-    pub timestamp: i64,
-    pub spot_price: f64,
-    pub perp_price: f64,
-    pub spread_pct: f64,
-}
+use super::mean_reversion::OuFit;
 
 /// Basis analysis result
 #[derive(Debug, Clone)]
@@ -35,6 +30,8 @@ pub struct BasisAnalysis {
     pub perp_price: f64,
     /// Current basis spread percentage: (perp - spot) / spot * 100
     pub spread_pct: f64,
+    /// Basis achievable at the typical trade size, if depth quotes are available
+    pub executable_spread_pct: Option<f64>,
     /// Annualized basis yield
     pub annualized_yield: f64,
     /// 1-hour average spread
@@ -47,11 +44,13 @@ pub struct BasisAnalysis {
     pub std_dev: f64,
     /// Z-score (how many std devs from mean)
     pub z_score: f64,
+    /// Ornstein-Uhlenbeck fit of the 8h history, if the basis reverts
+    pub mean_reversion: Option<OuFit>,
     /// Optimal hedge ratio for delta-neutral
     pub hedge_ratio: f64,
     /// Current hedge drift (if positions exist)
     pub hedge_drift: f64,
-    /// Is basis spread above minimum threshold
+    /// Is basis spread (executable, when known) above minimum threshold
     pub is_tradeable: bool,
     /// Timestamp
     pub timestamp: i64,
@@ -67,8 +66,9 @@ pub struct BasisEngine {
     event_tx: broadcast::Sender<Event>,
     /// Is running
     running: Arc<RwLock<bool>>,
-    /// Basis history (8-hour rolling window)
-    history: Arc<RwLock<VecDeque<BasisSnapshot>>>,
+    /// Beaten on every loop iteration, for the task supervisor
+    heartbeat: Heartbeat,
+    task: Mutex<Option<JoinHandle<()>>>,
     /// Last analysis result
     last_analysis: Arc<RwLock<Option<BasisAnalysis>>>,
 }
@@ -85,70 +85,63 @@ impl BasisEngine {
             state,
             event_tx,
             running: Arc::new(RwLock::new(false)),
-            history: Arc::new(RwLock::new(VecDeque::with_capacity(2880))), // 8 hours at 10s intervals
+            heartbeat: Heartbeat::default(),
+            task: Mutex::new(None),
             last_analysis: Arc::new(RwLock::new(None)),
         }
     }
     
-    /// Start the basis engine
+    /// Loop heartbeat, for supervision
+    pub fn heartbeat(&self) -> Heartbeat {
+        self.heartbeat.clone()
+    }
+    
+    /// Start the basis engine; also restarts a loop that has died or stalled
     pub async fn start(&self) -> Result<()> {
         *self.running.write().await = true;
         info!("Basis engine starting");
         
         let running = self.running.clone();
+        let heartbeat = self.heartbeat.clone();
         let state = self.state.clone();
         let config = self.config.clone();
         let event_tx = self.event_tx.clone();
-        let history = self.history.clone();
         let last_analysis = self.last_analysis.clone();
         
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(10));
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.engines.basis_interval());
             
             while *running.read().await {
                 interval.tick().await;
+                heartbeat.beat();
                 
-                let spot_price = state.spot_price.load();
-                let perp_price = state.perp_mark_price.load();
+                let market = state.snapshot();
+                let spot_price = market.spot_price;
+                let perp_price = market.perp_mark_price;
                 let timestamp = chrono::Utc::now().timestamp_millis();
                 
-                if spot_price > 0.0 && perp_price > 0.0 {
-                    let spread_pct = ((perp_price - spot_price) / spot_price) * 100.0;
-                    
-                    // Add to history
-                    {
-                        let mut hist = history.write().await;
-                        hist.push_back(BasisSnapshot {
-                            timestamp,
-                            spot_price,
-                            perp_price,
-                            spread_pct,
-                        });
-                        
-                        // Keep only last 8 hours
-                        let cutoff = timestamp - (8 * 60 * 60 * 1000);
-                        while hist.front().map(|s| s.timestamp < cutoff).unwrap_or(false) {
-                            hist.pop_front();
-                        }
-                    }
+                if market.has_prices() {
+                    let spread_pct = market.basis_spread;
                     
                     // Perform analysis
                     let analysis = Self::analyze(
-                        &history,
                         &state,
                         spot_price,
                         perp_price,
                         spread_pct,
+                        market.executable_basis(),
                         config.trading.min_basis_spread_pct,
                         timestamp,
-                    ).await;
+                    );
                     
                     debug!(
-                        "Basis analysis: spread={:.4}%, 1h_avg={:.4}%, percentile={:.1}, z={:.2}",
+                        "Basis analysis: spread={:.4}%, executable={:?}, 1h_avg={:.4}%, percentile={:.1}, z={:.2}, half_life={:?}h",
                         analysis.spread_pct,
+                        analysis.executable_spread_pct,
                         analysis.avg_1h_spread,
                         analysis.percentile,
-                        analysis.z_score
+                        analysis.z_score,
+                        analysis.mean_reversion.map(|ou| ou.half_life_hours)
                     );
                     
                     // Store analysis
@@ -164,14 +157,9 @@ impl BasisEngine {
                     
                     // Check for hedge drift alert
                     if analysis.hedge_drift.abs() > config.risk.hedge_drift_threshold_pct {
-                        let _ = event_tx.send(Event::TradeSignal {
-                            signal_type: "hedge_drift".to_string(),
-                            size: 0.0,
-                            reason: format!(
-                                "Hedge drift {:.2}% exceeds threshold {:.2}%",
-                                analysis.hedge_drift,
-                                config.risk.hedge_drift_threshold_pct
-                            ),
+                        let _ = event_tx.send(Event::HedgeDriftAlert {
+                            drift_pct: analysis.hedge_drift,
+                            threshold_pct: config.risk.hedge_drift_threshold_pct,
                         });
                     }
                 }
@@ -179,31 +167,41 @@ impl BasisEngine {
             
             info!("Basis engine stopped");
         });
+        // A restart replaces a loop that is stuck rather than dead
+        if let Some(previous) = self.task.lock().await.replace(task) {
+            previous.abort();
+        }
         
         Ok(())
     }
     
     /// Analyze basis spread
-    async fn analyze(
-        history: &Arc<RwLock<VecDeque<BasisSnapshot>>>,
-        state: &Arc<SharedState>,
+    fn analyze(
+        state: &SharedState,
         spot_price: f64,
         perp_price: f64,
         spread_pct: f64,
+        executable_spread_pct: Option<f64>,
         min_spread: f64,
         timestamp: i64,
     ) -> BasisAnalysis {
-        let hist = history.read().await;
+        let history = &state.basis_history;
+        let hour_ms = 60 * 60 * 1000;
         
         // Calculate averages
-        let avg_1h = Self::calculate_avg(&hist, timestamp, 1);
-        let avg_8h = Self::calculate_avg(&hist, timestamp, 8);
+        let avg_1h = history.mean(timestamp - hour_ms, timestamp).unwrap_or(0.0);
+        let avg_8h = history.mean(timestamp - 8 * hour_ms, timestamp).unwrap_or(0.0);
         
         // Calculate standard deviation and z-score
-        let (std_dev, z_score) = Self::calculate_stats(&hist, spread_pct);
+        let (std_dev, z_score) = Self::calculate_stats(history, timestamp, spread_pct);
         
         // Calculate percentile
-        let percentile = Self::calculate_percentile(&hist, spread_pct);
+        let percentile = history
+            .percentile_rank(timestamp - 8 * hour_ms, timestamp, spread_pct)
+            .unwrap_or(50.0);
+        
+        // Fit mean reversion: half-life and long-run mean
+        let mean_reversion = OuFit::fit(&history.since(timestamp - 8 * hour_ms));
         
         // Calculate annualized yield (assuming 1-hour funding)
         // Basis yield = spread * 24 * 365 (simplified)
@@ -217,19 +215,22 @@ impl BasisEngine {
         // Calculate hedge drift from positions
         let hedge_drift = state.hedge_drift.load();
         
-        // Check if tradeable
-        let is_tradeable = spread_pct.abs() >= min_spread;
+        // Check if tradeable; the oracle basis overstates what a full-size
+        // entry can capture, so prefer the executable basis when known
+        let is_tradeable = executable_spread_pct.unwrap_or(spread_pct).abs() >= min_spread;
         
         BasisAnalysis {
             spot_price,
             perp_price,
             spread_pct,
+            executable_spread_pct,
             annualized_yield,
             avg_1h_spread: avg_1h,
             avg_8h_spread: avg_8h,
             percentile,
             std_dev,
             z_score,
+            mean_reversion,
             hedge_ratio,
             hedge_drift,
             is_tradeable,
@@ -237,32 +238,15 @@ impl BasisEngine {
         }
     }
     
-    /// Calculate average spread over N hours
-    fn calculate_avg(history: &VecDeque<BasisSnapshot>, now: i64, hours: i64) -> f64 {
-        let cutoff = now - (hours * 60 * 60 * 1000);
-        let relevant: Vec<_> = history.iter()
-            .filter(|s| s.timestamp >= cutoff)
-            .collect();
-        
-        if relevant.is_empty() {
-            return 0.0;
-        }
-        
-        let sum: f64 = relevant.iter().map(|s| s.spread_pct).sum();
-        sum / relevant.len() as f64
-    }
-    
-    /// Calculate standard deviation and z-score
-    fn calculate_stats(history: &VecDeque<BasisSnapshot>, current: f64) -> (f64, f64) {
-        if history.len() < 2 {
+    /// Calculate standard deviation and z-score over the last 8 hours
+    fn calculate_stats(history: &TimeSeries, now: i64, current: f64) -> (f64, f64) {
+        let samples = history.since(now - 8 * 60 * 60 * 1000);
+        if samples.len() < 2 {
             return (0.0, 0.0);
         }
         
-        let mean: f64 = history.iter().map(|s| s.spread_pct).sum::<f64>() / history.len() as f64;
-        let variance: f64 = history.iter()
-            .map(|s| (s.spread_pct - mean).powi(2))
-            .sum::<f64>() / history.len() as f64;
-        let std_dev = variance.sqrt();
+        let mean = mean_of(&samples).unwrap_or(0.0);
+        let std_dev = std_dev_of(&samples).unwrap_or(0.0);
         
         let z_score = if std_dev > 0.0 {
             (current - mean) / std_dev
@@ -273,19 +257,6 @@ impl BasisEngine {
         (std_dev, z_score)
     }
     
-    /// Calculate percentile rank
-    fn calculate_percentile(history: &VecDeque<BasisSnapshot>, current: f64) -> f64 {
-        if history.is_empty() {
-            return 50.0;
-        }
-        
-        let count_below = history.iter()
-            .filter(|s| s.spread_pct < current)
-            .count();
-        
-        (count_below as f64 / history.len() as f64) * 100.0
-    }
-    
     /// Stop the basis engine
     pub async fn stop(&self) {
         *self.running.write().await = false;
@@ -297,6 +268,11 @@ impl BasisEngine {
         self.last_analysis.read().await.clone()
     }
     
+    /// Shared handle to the latest analysis, for downstream engines
+    pub fn analysis_handle(&self) -> Arc<RwLock<Option<BasisAnalysis>>> {
+        self.last_analysis.clone()
+    }
+    
     /// Get current spread
     pub async fn get_current_spread(&self) -> f64 {
         self.last_analysis.read().await
@@ -305,6 +281,13 @@ impl BasisEngine {
             .unwrap_or(0.0)
     }
     
+    /// Get executable spread at the typical trade size
+    pub async fn get_executable_spread(&self) -> Option<f64> {
+        self.last_analysis.read().await
+            .as_ref()
+            .and_then(|a| a.executable_spread_pct)
+    }
+    
     /// Check if basis is tradeable
     pub async fn is_tradeable(&self) -> bool {
         self.last_analysis.read().await
@@ -0,0 +1,127 @@
+//! Expected Value
+//!
+//! Expected P&L of opening a basis trade, net of costs:
+//! - Funding: the funding engine's 8h average APR, decaying along its
+//!   velocity when falling; a trend towards zero ends the hold at the
+//!   crossing, where a reversal would close the trade
+//! - Convergence: the basis reverting from entry to where its
+//!   Ornstein-Uhlenbeck fit expects it at the end of the hold; without a
+//!   fit, to its 8h average (to the close threshold before the basis engine
+//!   has history). Also given per hour of the hold, to compare entries
+//!   that converge at different speeds
+//! - Costs: perp taker fees, the perp book's execution cost and the spot
+//!   swap cost (`spot_cost_bps`), each paid on entry and on exit
+//!
+//! The hold before exit is the fitted basis's time to decay to the close
+//! threshold (see `SignalEngine::expected_hold_hours`); without a fit, the
+//! average of closed trades once `min_trades` have closed, else
+//! `default_hold_hours`.
+
+use crate::config::AppConfig;
+use crate::utils::types::ExpectedValue;
+
+use super::basis_engine::BasisAnalysis;
+use super::funding_engine::FundingAnalysis;
+
+const HOURS_PER_YEAR: f64 = 8760.0;
+
+/// Market inputs of an entry being considered
+#[derive(Debug, Clone, Copy)]
+pub struct EntryInputs {
+    pub size_sol: f64,
+    pub spot_price: f64,
+    /// Basis the entry captures (%)
+    pub basis_pct: f64,
+    /// Current funding APR (%), used without a funding analysis
+    pub funding_apr_pct: f64,
+    /// Perp taker cost at this size (% vs mid), when the book is known
+    pub perp_cost_pct: Option<f64>,
+}
+
+/// Average APR over the hold and the hold itself, cut at a predicted zero
+/// crossing. `apr` is signed in the position's favour; a rising trend is
+/// not extrapolated.
+fn funding_over_hold(apr: f64, slope_per_hour: f64, hold_hours: f64) -> (f64, f64) {
+    let slope_per_hour = slope_per_hour.min(0.0);
+    let hold = if apr > 0.0 && slope_per_hour < 0.0 {
+        hold_hours.min(apr / -slope_per_hour)
+    } else {
+        hold_hours
+    };
+    (apr + slope_per_hour * hold / 2.0, hold)
+}
+
+/// Expected value of opening `entry`, held for `hold_hours` unless funding
+/// is predicted to cross zero first
+pub fn estimate(
+    config: &AppConfig,
+    entry: EntryInputs,
+    funding: Option<&FundingAnalysis>,
+    basis: Option<&BasisAnalysis>,
+    hold_hours: f64,
+) -> ExpectedValue {
+    let notional = entry.size_sol * entry.spot_price;
+    // Positive basis: long spot, short perp, receiving positive funding
+    let direction = if entry.basis_pct >= 0.0 { 1.0 } else { -1.0 };
+
+    let apr = funding.map(|f| f.avg_8h_apr).unwrap_or(entry.funding_apr_pct) * direction;
+    let slope = funding.map(|f| f.velocity * HOURS_PER_YEAR * 100.0).unwrap_or(0.0) * direction;
+    let (funding_apr_pct, hold_hours) = funding_over_hold(apr, slope, hold_hours);
+    let expected_funding = notional * funding_apr_pct / 100.0 * hold_hours / HOURS_PER_YEAR;
+
+    let mean_reversion = basis
+        .and_then(|b| b.mean_reversion)
+        .filter(|_| config.signals.expected_value.use_half_life);
+    let exit_basis_pct = match (basis, mean_reversion) {
+        (_, Some(ou)) => ou.expected_at(entry.basis_pct, hold_hours),
+        (Some(b), None) => b.avg_8h_spread,
+        (None, None) => config.trading.basis_close_threshold_pct * direction,
+    };
+    let expected_convergence = notional * (entry.basis_pct - exit_basis_pct) * direction / 100.0;
+
+    let per_side_bps = config.execution.perp_taker_fee_bps
+        + entry.perp_cost_pct.unwrap_or(0.0) * 100.0
+        + config.signals.expected_value.spot_cost_bps;
+    let expected_costs = notional * per_side_bps * 2.0 / 10_000.0;
+
+    ExpectedValue {
+        notional_usd: notional,
+        hold_hours,
+        half_life_hours: mean_reversion.map(|ou| ou.half_life_hours),
+        funding_apr_pct,
+        expected_funding,
+        entry_basis_pct: entry.basis_pct,
+        exit_basis_pct,
+        expected_convergence,
+        convergence_per_hour: if hold_hours > 0.0 { expected_convergence / hold_hours } else { 0.0 },
+        expected_costs,
+        ev: expected_funding + expected_convergence - expected_costs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_value() {
+        let mut config = AppConfig::default_for_test();
+        config.execution.perp_taker_fee_bps = 5.0;
+        config.signals.expected_value.spot_cost_bps = 5.0;
+        let entry = EntryInputs { size_sol: 10.0, spot_price: 100.0, basis_pct: 0.5, funding_apr_pct: 20.0, perp_cost_pct: None };
+
+        // 24h at 20% APR on $1000, basis 0.5% -> close threshold, 10 bps a side
+        let ev = estimate(&config, entry, None, None, 24.0);
+        let close = config.trading.basis_close_threshold_pct;
+        assert!((ev.expected_funding - 1000.0 * 0.2 * 24.0 / 8760.0).abs() < 1e-9);
+        assert!((ev.expected_convergence - 1000.0 * (0.5 - close) / 100.0).abs() < 1e-9);
+        assert!((ev.expected_costs - 2.0).abs() < 1e-9);
+        assert!((ev.ev - (ev.expected_funding + ev.expected_convergence - 2.0)).abs() < 1e-9);
+
+        // Funding falling 1% APR an hour from 20% crosses zero after 20h
+        let (apr, hold) = funding_over_hold(20.0, -1.0, 48.0);
+        assert_eq!(hold, 20.0);
+        assert_eq!(apr, 10.0);
+        assert_eq!(funding_over_hold(20.0, 1.0, 48.0), (20.0, 48.0));
+    }
+}
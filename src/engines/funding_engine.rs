@@ -1,43 +1,45 @@
 //! Funding Rate Engine
 //!
 //! Analyzes funding rates with:
-//! - 8-hour rolling window tracking
-//! - Annualized APR calculation
+//! - 8-hour rolling window (from the shared funding history)
+//! - Annualized APR calculation (per the venue's funding interval)
 //! - Funding velocity (rate of change)
 //! - Predicted next funding payment
 //! - Volatility detection
+//! - Cross-venue short carry: funding net of borrow fees, annualized per
+//!   venue so funding-based and borrow-based venues compare directly
 
 use anyhow::Result;
-use std::collections::VecDeque;
+use serde::Serialize;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 
 use crate::config::AppConfig;
+use crate::execution::{PerpVenue, VenueFunding};
 use crate::network::event_bus::Event;
-use crate::state::SharedState;
+use crate::state::{Heartbeat, SharedState};
+use crate::utils::types::FundingInterval;
 
-/// Funding rate snapshot for history
-#[derive(Debug, Clone)]
-pub struct FundingRateSnapshot {
-    pub timestamp: i64,
-    pub rate: f64,
-    pub apr: f64,
-}
+/// Rolling window for funding averages and volatility
+const FUNDING_WINDOW: Duration = Duration::from_secs(8 * 60 * 60);
+/// Span over which funding velocity is measured
+const VELOCITY_SPAN: Duration = Duration::from_secs(5 * 60);
 
 /// Funding analysis result
 #[derive(Debug, Clone)]
 pub struct FundingAnalysis {
-    /// Current hourly funding rate
+    /// Current funding rate (per venue funding period)
     pub current_rate: f64,
-    /// Annualized APR (current_rate * 24 * 365 * 100)
+    /// Annualized APR (current_rate * periods per year * 100)
     pub annualized_apr: f64,
     /// Average rate over last 8 hours
     pub avg_8h_rate: f64,
     /// Average APR over last 8 hours
     pub avg_8h_apr: f64,
-    /// Funding velocity (rate of change per hour)
+    /// Funding velocity (change in hourly-normalized rate per hour)
     pub velocity: f64,
     /// Predicted next funding payment (in USD per $1000 position)
     pub predicted_payment: f64,
@@ -51,6 +53,33 @@ pub struct FundingAnalysis {
     pub timestamp: i64,
 }
 
+/// Short-side carry on one perp venue
+#[derive(Debug, Clone, Serialize)]
+pub struct VenueCarry {
+    /// Venue name
+    pub venue: String,
+    /// Annualized funding received by shorts (%)
+    pub funding_apr: f64,
+    /// Annualized borrow fee paid by the position (%)
+    pub borrow_apr: f64,
+    /// Net annualized carry for a short (%)
+    pub short_apr: f64,
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+impl VenueCarry {
+    pub fn from_funding(venue: &str, funding: &VenueFunding, timestamp: i64) -> Self {
+        Self {
+            venue: venue.to_string(),
+            funding_apr: funding.apr(),
+            borrow_apr: funding.borrow_apr(),
+            short_apr: funding.short_apr(),
+            timestamp,
+        }
+    }
+}
+
 /// Funding rate engine
 pub struct FundingEngine {
     /// Configuration
@@ -61,10 +90,15 @@ pub struct FundingEngine {
     event_tx: broadcast::Sender<Event>,
     /// Is running
     running: Arc<RwLock<bool>>,
-    /// Funding history (8-hour rolling window)
-    history: Arc<RwLock<VecDeque<FundingRateSnapshot>>>,
+    /// Beaten on every loop iteration, for the task supervisor
+    heartbeat: Heartbeat,
+    task: Mutex<Option<JoinHandle<()>>>,
     /// Last analysis result
     last_analysis: Arc<RwLock<Option<FundingAnalysis>>>,
+    /// Perp venues compared on short carry
+    venues: Vec<Arc<dyn PerpVenue>>,
+    /// Latest carry per venue, best first
+    venue_carry: Arc<RwLock<Vec<VenueCarry>>>,
 }
 
 impl FundingEngine {
@@ -79,59 +113,74 @@ impl FundingEngine {
             state,
             event_tx,
             running: Arc::new(RwLock::new(false)),
-            history: Arc::new(RwLock::new(VecDeque::with_capacity(960))), // 8 hours at 30s intervals
+            heartbeat: Heartbeat::default(),
+            task: Mutex::new(None),
             last_analysis: Arc::new(RwLock::new(None)),
+            venues: Vec::new(),
+            venue_carry: Arc::new(RwLock::new(Vec::new())),
         }
     }
     
-    /// Start the funding engine
+    /// Compare short carry across these venues
+    pub fn with_venues(mut self, venues: Vec<Arc<dyn PerpVenue>>) -> Self {
+        self.venues = venues;
+        self
+    }
+    
+    /// Loop heartbeat, for supervision
+    pub fn heartbeat(&self) -> Heartbeat {
+        self.heartbeat.clone()
+    }
+    
+    /// Start the funding engine; also restarts a loop that has died or stalled
     pub async fn start(&self) -> Result<()> {
         *self.running.write().await = true;
         info!("Funding engine starting");
         
         let running = self.running.clone();
+        let heartbeat = self.heartbeat.clone();
         let state = self.state.clone();
         let config = self.config.clone();
         let event_tx = self.event_tx.clone();
-        let history = self.history.clone();
         let last_analysis = self.last_analysis.clone();
+        let venues = self.venues.clone();
+        let venue_carry = self.venue_carry.clone();
         
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(30));
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.engines.funding_interval());
             
             while *running.read().await {
                 interval.tick().await;
+                heartbeat.beat();
                 
                 // Get current funding rate from state
-                let current_rate = state.current_funding_rate.load();
-                let current_apr = state.funding_apr.load();
+                let market = state.snapshot();
+                let current_rate = market.funding_rate;
+                let current_apr = market.funding_apr;
                 let timestamp = chrono::Utc::now().timestamp_millis();
                 
-                if current_rate.abs() > 0.0 {
-                    // Add to history
-                    {
-                        let mut hist = history.write().await;
-                        hist.push_back(FundingRateSnapshot {
-                            timestamp,
-                            rate: current_rate,
-                            apr: current_apr,
-                        });
-                        
-                        // Keep only last 8 hours (960 samples at 30s intervals)
-                        let cutoff = timestamp - (8 * 60 * 60 * 1000);
-                        while hist.front().map(|s| s.timestamp < cutoff).unwrap_or(false) {
-                            hist.pop_front();
-                        }
+                if !venues.is_empty() {
+                    let carry = Self::compare_venues(&venues, timestamp).await;
+                    if let Some(best) = carry.first() {
+                        debug!(
+                            "Best short carry: {} {:.2}% (funding {:.2}%, borrow {:.2}%)",
+                            best.venue, best.short_apr, best.funding_apr, best.borrow_apr
+                        );
                     }
-                    
+                    *venue_carry.write().await = carry;
+                }
+                
+                if current_rate.abs() > 0.0 {
                     // Perform analysis
                     let analysis = Self::analyze(
-                        &history,
+                        &state,
                         current_rate,
                         current_apr,
                         config.trading.min_funding_apr_pct,
+                        config.protocols.drift.funding_interval,
+                        config.engines.funding_samples(VELOCITY_SPAN),
                         timestamp,
-                    ).await;
+                    );
                     
                     debug!(
                         "Funding analysis: APR={:.2}%, 8h_avg={:.2}%, velocity={:.4}, vol={:.4}",
@@ -146,82 +195,78 @@ impl FundingEngine {
                     
                     // Emit events for significant changes
                     if analysis.is_elevated {
-                        let _ = event_tx.send(Event::TradeSignal {
-                            signal_type: "funding_elevated".to_string(),
-                            size: 0.0,
-                            reason: format!(
-                                "Funding APR {:.2}% exceeds threshold {:.2}%",
-                                analysis.annualized_apr,
-                                config.trading.min_funding_apr_pct
-                            ),
+                        let _ = event_tx.send(Event::FundingElevated {
+                            apr_pct: analysis.annualized_apr,
+                            threshold_pct: config.trading.min_funding_apr_pct,
                         });
                     }
                     
                     if analysis.is_reversing {
-                        let _ = event_tx.send(Event::TradeSignal {
-                            signal_type: "funding_reversing".to_string(),
-                            size: 0.0,
-                            reason: format!(
-                                "Funding rate reversing: velocity={:.6}",
-                                analysis.velocity
-                            ),
-                        });
+                        let _ = event_tx.send(Event::FundingReversing { velocity: analysis.velocity });
                     }
                 }
             }
             
             info!("Funding engine stopped");
         });
+        // A restart replaces a loop that is stuck rather than dead
+        if let Some(previous) = self.task.lock().await.replace(task) {
+            previous.abort();
+        }
         
         Ok(())
     }
     
+    /// Short carry on each venue that has funding data, best first
+    async fn compare_venues(venues: &[Arc<dyn PerpVenue>], timestamp: i64) -> Vec<VenueCarry> {
+        let mut carry = Vec::with_capacity(venues.len());
+        for venue in venues {
+            match venue.funding().await {
+                Ok(funding) => carry.push(VenueCarry::from_funding(venue.name(), &funding, timestamp)),
+                Err(e) => warn!("Funding unavailable on {}: {}", venue.name(), e),
+            }
+        }
+        carry.sort_by(|a, b| b.short_apr.total_cmp(&a.short_apr));
+        carry
+    }
+    
     /// Analyze funding rates
-    async fn analyze(
-        history: &Arc<RwLock<VecDeque<FundingRateSnapshot>>>,
+    fn analyze(
+        state: &SharedState,
         current_rate: f64,
         current_apr: f64,
         threshold_apr: f64,
+        interval: FundingInterval,
+        velocity_samples: usize,
         timestamp: i64,
     ) -> FundingAnalysis {
-        let hist = history.read().await;
+        let start = timestamp - FUNDING_WINDOW.as_millis() as i64;
         
         // Calculate averages
-        let (avg_rate, avg_apr) = if hist.is_empty() {
-            (current_rate, current_apr)
-        } else {
-            let sum_rate: f64 = hist.iter().map(|s| s.rate).sum();
-            let sum_apr: f64 = hist.iter().map(|s| s.apr).sum();
-            let count = hist.len() as f64;
-            (sum_rate / count, sum_apr / count)
+        let (avg_rate, avg_apr) = match state.funding_avg_over(FUNDING_WINDOW, timestamp) {
+            Some(avg) => (avg.rate, interval.annualize(avg.rate)),
+            None => (current_rate, current_apr),
         };
         
-        // Calculate velocity (rate of change)
-        let velocity = if hist.len() >= 2 {
-            let recent: Vec<_> = hist.iter().rev().take(10).collect();
-            if recent.len() >= 2 {
-                let first = recent.last().unwrap();
-                let last = recent.first().unwrap();
+        // Calculate velocity (rate of change over the last VELOCITY_SPAN of samples)
+        let recent = state.funding_last_n(velocity_samples);
+        let velocity = match (recent.first(), recent.last()) {
+            (Some(first), Some(last)) if recent.len() >= 2 => {
                 let time_diff = (last.timestamp - first.timestamp) as f64 / 3600000.0; // hours
                 if time_diff > 0.0 {
-                    (last.rate - first.rate) / time_diff
+                    interval.hourly_rate(last.rate - first.rate) / time_diff
                 } else {
                     0.0
                 }
-            } else {
-                0.0
             }
-        } else {
-            0.0
+            _ => 0.0,
         };
         
         // Calculate volatility (standard deviation)
-        let volatility = if hist.len() >= 2 {
-            let mean = avg_rate;
-            let variance: f64 = hist.iter()
-                .map(|s| (s.rate - mean).powi(2))
-                .sum::<f64>() / hist.len() as f64;
-            variance.sqrt()
+        let window = state.get_funding_series(start, timestamp);
+        let volatility = if window.len() >= 2 {
+            let mean = window.iter().map(|s| s.rate).sum::<f64>() / window.len() as f64;
+            (window.iter().map(|s| (s.rate - mean).powi(2)).sum::<f64>() / window.len() as f64).sqrt()
         } else {
             0.0
         };
@@ -233,8 +278,9 @@ impl FundingEngine {
         let is_elevated = current_apr.abs() >= threshold_apr;
         
         // Check if reversing (velocity opposing current direction)
-        let is_reversing = (current_rate > 0.0 && velocity < -0.0001) ||
-                          (current_rate < 0.0 && velocity > 0.0001);
+        let current_rate_hourly = interval.hourly_rate(current_rate);
+        let is_reversing = (current_rate_hourly > 0.0 && velocity < -0.0001) ||
+                          (current_rate_hourly < 0.0 && velocity > 0.0001);
         
         FundingAnalysis {
             current_rate,
@@ -261,6 +307,16 @@ impl FundingEngine {
         self.last_analysis.read().await.clone()
     }
     
+    /// Shared handle to the latest analysis, for downstream engines
+    pub fn analysis_handle(&self) -> Arc<RwLock<Option<FundingAnalysis>>> {
+        self.last_analysis.clone()
+    }
+    
+    /// Latest short carry per venue, best first
+    pub async fn get_venue_carry(&self) -> Vec<VenueCarry> {
+        self.venue_carry.read().await.clone()
+    }
+    
     /// Get 8-hour average APR
     pub async fn get_avg_8h_apr(&self) -> f64 {
         self.last_analysis.read().await
@@ -292,11 +348,43 @@ mod tests {
 
     #[test]
     fn test_funding_analysis() {
-        let snapshot = FundingRateSnapshot {
-            timestamp: 1000,
-            rate: 0.0001,
-            apr: 8.76,
-        };
-        assert!(snapshot.rate > 0.0);
+        let state = SharedState::new();
+        let now = 10 * 60 * 60 * 1000;
+        for i in 0..10 {
+            state.funding_history.record(now - (9 - i) * 60_000, 0.0001 - i as f64 * 0.00002);
+        }
+        
+        let analysis = FundingEngine::analyze(
+            &state, -0.00008, -70.08, 15.0, FundingInterval::Hourly, 10, now,
+        );
+        assert!(analysis.velocity < 0.0);
+        assert!(analysis.avg_8h_rate < 0.0001);
+        assert!(analysis.is_elevated);
+    }
+    
+    #[test]
+    fn test_funding_interval_annualization() {
+        // 0.01% per period
+        assert!((FundingInterval::Hourly.annualize(0.0001) - 87.6).abs() < 1e-9);
+        assert!((FundingInterval::EightHourly.annualize(0.0001) - 10.95).abs() < 1e-9);
+        assert!((FundingInterval::EightHourly.hourly_rate(0.0008) - 0.0001).abs() < 1e-12);
+    }
+    
+    #[test]
+    fn test_venue_carry_nets_borrow() {
+        // 0.01% per 8h funding vs no funding and a 0.001%/h borrow fee
+        let drift = VenueCarry::from_funding(
+            "drift",
+            &VenueFunding { rate: 0.0001, borrow_rate: 0.0, interval: FundingInterval::EightHourly },
+            0,
+        );
+        let jupiter = VenueCarry::from_funding(
+            "jupiter_perps",
+            &VenueFunding { rate: 0.0, borrow_rate: 0.00001, interval: FundingInterval::Hourly },
+            0,
+        );
+        assert!((drift.short_apr - 10.95).abs() < 1e-9);
+        assert!((jupiter.borrow_apr - 8.76).abs() < 1e-9);
+        assert!((jupiter.short_apr + 8.76).abs() < 1e-9);
     }
 }
@@ -0,0 +1,132 @@
+//! Basis Mean Reversion
+//!
+//! Fits an Ornstein-Uhlenbeck process, dx = κ(μ - x)dt + σdW, to the basis
+//! history by regressing each sample on the one before it (the exact AR(1)
+//! discretization, x' = a + b·x):
+//! - Reversion speed κ = -ln(b) / Δt, and half-life ln 2 / κ
+//! - Long-run mean μ = a / (1 - b)
+//!
+//! No fit is returned for a basis that does not revert (b outside (0, 1)),
+//! a half-life longer than the history it was fitted on, too little
+//! history, or a flat series. The signal engine expects an entry to be held
+//! until the fitted basis decays to the close threshold, and the EV model
+//! takes the basis expected at the end of that hold as its exit.
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::Sample;
+
+/// Samples needed for a fit
+pub const MIN_SAMPLES: usize = 60;
+
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+/// Ornstein-Uhlenbeck fit of the basis
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OuFit {
+    /// Reversion speed κ (per hour)
+    pub kappa_per_hour: f64,
+    /// Long-run mean μ (%)
+    pub long_run_mean_pct: f64,
+    /// Time for a deviation from μ to halve (hours)
+    pub half_life_hours: f64,
+    /// Samples fitted
+    pub samples: usize,
+}
+
+impl OuFit {
+    /// Fit `samples` (oldest first), None if the series does not revert
+    pub fn fit(samples: &[Sample]) -> Option<Self> {
+        if samples.len() < MIN_SAMPLES {
+            return None;
+        }
+        let span_ms = (samples[samples.len() - 1].timestamp - samples[0].timestamp) as f64;
+        let dt_hours = span_ms / (samples.len() - 1) as f64 / MS_PER_HOUR;
+
+        let pairs = samples.windows(2).map(|w| (w[0].value, w[1].value));
+        let n = (samples.len() - 1) as f64;
+        let (sum_x, sum_y) = pairs.clone().fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+        let (cov, var) = pairs.fold((0.0, 0.0), |(cov, var), (x, y)| {
+            (cov + (x - mean_x) * (y - mean_y), var + (x - mean_x).powi(2))
+        });
+        if var <= 0.0 || dt_hours <= 0.0 {
+            return None;
+        }
+
+        let b = cov / var;
+        if !(b > 0.0 && b < 1.0) {
+            return None;
+        }
+        let kappa_per_hour = -b.ln() / dt_hours;
+        let half_life_hours = std::f64::consts::LN_2 / kappa_per_hour;
+        if half_life_hours > span_ms / MS_PER_HOUR {
+            return None;
+        }
+        Some(Self {
+            kappa_per_hour,
+            long_run_mean_pct: (mean_y - b * mean_x) / (1.0 - b),
+            half_life_hours,
+            samples: samples.len(),
+        })
+    }
+
+    /// Basis expected `hours` after it is at `from` (%)
+    pub fn expected_at(&self, from: f64, hours: f64) -> f64 {
+        self.long_run_mean_pct + (from - self.long_run_mean_pct) * (-self.kappa_per_hour * hours).exp()
+    }
+
+    /// Hours for the expected basis to decay from `from` to `target`; None
+    /// if `target` is not between `from` and the long-run mean
+    pub fn time_to(&self, from: f64, target: f64) -> Option<f64> {
+        let start = from - self.long_run_mean_pct;
+        let end = target - self.long_run_mean_pct;
+        if start * end <= 0.0 {
+            return None;
+        }
+        Some(((start / end).ln() / self.kappa_per_hour).max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// OU path sampled every 10s, with small pseudo-random shocks
+    fn path(mean: f64, b: f64, from: f64, n: usize) -> Vec<Sample> {
+        let (mut x, mut seed) = (from, 7u64);
+        (0..n)
+            .map(|i| {
+                let sample = Sample { timestamp: i as i64 * 10_000, value: x };
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                let shock = ((seed >> 33) as f64 / (1u64 << 31) as f64 - 0.5) * 0.001;
+                x = mean + (x - mean) * b + shock;
+                sample
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fit_recovers_half_life_and_mean() {
+        // b = 0.5^(1/360): the deviation halves every 360 samples (1h)
+        let b = 0.5f64.powf(1.0 / 360.0);
+        let fit = OuFit::fit(&path(0.1, b, 0.6, 2880)).unwrap();
+        assert!((fit.half_life_hours - 1.0).abs() < 0.1, "{:?}", fit);
+        assert!((fit.long_run_mean_pct - 0.1).abs() < 0.02, "{:?}", fit);
+
+        // From 0.5% to 0.2% around a 0.1% mean: 4x decay, two half-lives
+        let exact = OuFit { kappa_per_hour: std::f64::consts::LN_2, long_run_mean_pct: 0.1, half_life_hours: 1.0, samples: 0 };
+        assert!((exact.time_to(0.5, 0.2).unwrap() - 2.0).abs() < 1e-9);
+        assert!((exact.expected_at(0.5, 1.0) - 0.3).abs() < 1e-9);
+        // A close target beyond the mean is never reached in expectation
+        assert_eq!(exact.time_to(0.5, 0.05), None);
+    }
+
+    #[test]
+    fn test_no_fit_without_reversion() {
+        // A trending basis (no half-life within the history) and too little history
+        let trending: Vec<Sample> = (0..200).map(|i| Sample { timestamp: i * 10_000, value: 0.1 + i as f64 * 0.001 }).collect();
+        assert_eq!(OuFit::fit(&trending), None);
+        assert_eq!(OuFit::fit(&trending[..10]), None);
+    }
+}
@@ -1,24 +1,36 @@
 //! Calculation Engines Module - Phase 3
 //!
 //! Provides real-time calculation engines for:
-//! - Funding rate analysis and prediction
+//! - Funding rate analysis and prediction, with cross-venue short carry
 //! - Basis spread calculation and hedge ratios
-//! - Trade signal generation
+//! - Trade signal generation, debounced by the signal gate
+//! - Expected value of entries, net of costs
+//! - Basis mean reversion (Ornstein-Uhlenbeck half-life and long-run mean)
+//! - Trading windows and blackout periods for entries
 
 pub mod funding_engine;
 pub mod basis_engine;
 pub mod signal_engine;
+pub mod expected_value;
+pub mod mean_reversion;
+pub mod signal_gate;
+pub mod trading_window;
 
-pub use funding_engine::FundingEngine;
+pub use funding_engine::{FundingEngine, VenueCarry};
 pub use basis_engine::BasisEngine;
+pub use mean_reversion::OuFit;
 pub use signal_engine::SignalEngine;
+pub use signal_gate::SignalGate;
+pub use trading_window::{TradingWindow, WindowStatus};
 
 use anyhow::Result;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, RwLock};
 use tracing::info;
 
+use crate::agentic::RegimeAnalysis;
 use crate::config::AppConfig;
+use crate::execution::PerpVenue;
 use crate::network::event_bus::Event;
 use crate::state::SharedState;
 
@@ -38,12 +50,26 @@ impl EngineManager {
         config: Arc<AppConfig>,
         state: Arc<SharedState>,
         event_tx: broadcast::Sender<Event>,
+        regime_analysis: Arc<RwLock<Option<RegimeAnalysis>>>,
     ) -> Self {
-        Self {
-            funding: FundingEngine::new(config.clone(), state.clone(), event_tx.clone()),
-            basis: BasisEngine::new(config.clone(), state.clone(), event_tx.clone()),
-            signal: SignalEngine::new(config, state, event_tx),
-        }
+        let funding = FundingEngine::new(config.clone(), state.clone(), event_tx.clone());
+        let basis = BasisEngine::new(config.clone(), state.clone(), event_tx.clone());
+        let signal = SignalEngine::new(
+            config,
+            state,
+            event_tx,
+            funding.analysis_handle(),
+            basis.analysis_handle(),
+            regime_analysis,
+        );
+        
+        Self { funding, basis, signal }
+    }
+    
+    /// Have the funding engine compare short carry across perp venues
+    pub fn with_perp_venues(mut self, venues: Vec<Arc<dyn PerpVenue>>) -> Self {
+        self.funding = self.funding.with_venues(venues);
+        self
     }
     
     /// Start all engines
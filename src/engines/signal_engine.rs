@@ -1,27 +1,57 @@
 //! Signal Generation Engine
 //!
-//! Combines funding and basis analysis to generate trade signals:
+//! Combines the funding and basis engines' latest analyses with the market
+//! snapshot to generate trade signals:
 //! - Open basis trade signals
 //! - Close basis trade signals
 //! - Rebalance signals
 //! - Risk-adjusted position sizing
+//! - Persistence and cooldowns via the signal gate
+//! - No re-entry into the setup of the last failed or closed entry unless
+//!   its edge has improved (`signals.reentry`)
+//! - Perp execution cost from the DLOB order book
+//! - Expected value of opens from funding over the expected hold, basis
+//!   convergence and costs (see `expected_value`), the hold estimated from
+//!   the basis half-life
+//! - No entries while the basis regime is trending
+//! - No entries outside the configured trading windows
+//! - Signals annotated while the perp mark strays from its index
+//! - Blocked opens reported as `SignalSkipped` events, for skipped-trade tracking
+//! - Entry confidence scored from configurable factor weights
+//!   (`signals.scoring`), with each factor's contribution logged
+//! - Entry confidence nudged by past win rates at this UTC hour and weekday
+//! - Entry thresholds from the threshold learner when it has adjusted them,
+//!   raised by the trade-frequency governor while realized edge deteriorates
+//! - In oracle contingency, thresholds widened and entries blocked
+//! - While USDC is off its peg, entries blocked
+//! - While an API dependency's circuit breaker is open, signals flagged and
+//!   entries blocked if it is needed to enter
 
 use anyhow::Result;
+use serde::Serialize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 
+use crate::agentic::regime_detector::{MarketRegime, RegimeAnalysis};
+use crate::agentic::{threshold_learner, EntryTimePerformance};
 use crate::config::AppConfig;
 use crate::network::event_bus::Event;
-use crate::state::SharedState;
-use crate::utils::types::{SignalType, TradeSignal};
+use crate::network::http;
+use crate::state::{Heartbeat, MarketSnapshot, SharedState};
+use crate::telemetry::record_trading_window;
+use crate::utils::types::{Dependency, ExpectedValue, SignalType, SkipReason, TradeSignal};
 
 use super::funding_engine::FundingAnalysis;
 use super::basis_engine::BasisAnalysis;
+use super::expected_value::{self, EntryInputs};
+use super::signal_gate::{EntrySetup, ReentryGuard, SignalGate};
+use super::trading_window::{TradingWindow, WindowStatus};
 
 /// Signal evaluation result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SignalEvaluation {
     /// Should open a new basis trade
     pub should_open: bool,
@@ -33,14 +63,35 @@ pub struct SignalEvaluation {
     pub recommended_size: f64,
     /// Confidence score (0-1)
     pub confidence: f64,
-    /// Expected profit in USD
+    /// Contribution of each scoring factor to the confidence, before clamping
+    pub factors: Vec<(&'static str, f64)>,
+    /// Expected profit in USD: the expected value of an open, net of costs
     pub expected_profit: f64,
+    /// Estimated perp taker cost at the recommended size (% vs mid)
+    pub perp_execution_cost_pct: Option<f64>,
+    /// Expected value of an open and its inputs
+    pub expected_value: Option<ExpectedValue>,
     /// Reasons for the signal
     pub reasons: Vec<String>,
+    /// Filter that stopped an otherwise qualifying open
+    pub blocked: Option<SkipReason>,
+    /// Perp mark deviation from the index (%), when beyond the risk limit
+    pub mark_index_divergence_pct: Option<f64>,
     /// Timestamp
     pub timestamp: i64,
 }
 
+impl SignalEvaluation {
+    /// "basis_strength +0.30, funding_strength +0.30, ..." for logs
+    pub fn factor_summary(&self) -> String {
+        self.factors
+            .iter()
+            .map(|(factor, weight)| format!("{} {:+.2}", factor, weight))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 /// Trade signal with full context
 #[derive(Debug, Clone)]
 pub struct FullTradeSignal {
@@ -64,10 +115,23 @@ pub struct SignalEngine {
     event_tx: broadcast::Sender<Event>,
     /// Is running
     running: Arc<RwLock<bool>>,
+    /// Beaten on every loop iteration, for the task supervisor
+    heartbeat: Heartbeat,
+    task: Mutex<Option<JoinHandle<()>>>,
     /// Last signal
     last_signal: Arc<RwLock<Option<FullTradeSignal>>>,
     /// Signal history
     signal_history: Arc<RwLock<Vec<FullTradeSignal>>>,
+    /// Latest funding engine analysis
+    funding_analysis: Arc<RwLock<Option<FundingAnalysis>>>,
+    /// Latest basis engine analysis
+    basis_analysis: Arc<RwLock<Option<BasisAnalysis>>>,
+    /// Latest basis regime analysis
+    regime_analysis: Arc<RwLock<Option<RegimeAnalysis>>>,
+    /// Closed-trade performance by entry time, kept current by the agent
+    entry_times: Arc<RwLock<Option<EntryTimePerformance>>>,
+    /// Average hold of closed trades (hours), kept current by the agent
+    hold_hours: Arc<RwLock<Option<f64>>>,
 }
 
 impl SignalEngine {
@@ -76,71 +140,179 @@ impl SignalEngine {
         config: Arc<AppConfig>,
         state: Arc<SharedState>,
         event_tx: broadcast::Sender<Event>,
+        funding_analysis: Arc<RwLock<Option<FundingAnalysis>>>,
+        basis_analysis: Arc<RwLock<Option<BasisAnalysis>>>,
+        regime_analysis: Arc<RwLock<Option<RegimeAnalysis>>>,
     ) -> Self {
         Self {
             config,
             state,
             event_tx,
             running: Arc::new(RwLock::new(false)),
+            heartbeat: Heartbeat::default(),
+            task: Mutex::new(None),
             last_signal: Arc::new(RwLock::new(None)),
             signal_history: Arc::new(RwLock::new(Vec::new())),
+            funding_analysis,
+            basis_analysis,
+            regime_analysis,
+            entry_times: Arc::new(RwLock::new(None)),
+            hold_hours: Arc::new(RwLock::new(None)),
         }
     }
     
-    /// Start the signal engine
+    /// Handle the agent refreshes with performance by entry time
+    pub fn entry_time_handle(&self) -> Arc<RwLock<Option<EntryTimePerformance>>> {
+        self.entry_times.clone()
+    }
+    
+    /// Handle the agent refreshes with the average hold of closed trades
+    pub fn hold_time_handle(&self) -> Arc<RwLock<Option<f64>>> {
+        self.hold_hours.clone()
+    }
+    
+    /// Loop heartbeat, for supervision
+    pub fn heartbeat(&self) -> Heartbeat {
+        self.heartbeat.clone()
+    }
+    
+    /// Start the signal engine; also restarts a loop that has died or stalled
     pub async fn start(&self) -> Result<()> {
         *self.running.write().await = true;
         info!("Signal engine starting");
+        let weights: Vec<String> = self.config.signals.scoring.weights()
+            .iter()
+            .map(|(factor, weight)| format!("{} {:.2}", factor, weight))
+            .collect();
+        info!("Entry confidence weights: {}", weights.join(", "));
         
         let running = self.running.clone();
+        let heartbeat = self.heartbeat.clone();
         let state = self.state.clone();
         let config = self.config.clone();
         let event_tx = self.event_tx.clone();
         let last_signal = self.last_signal.clone();
         let signal_history = self.signal_history.clone();
+        let funding_analysis = self.funding_analysis.clone();
+        let basis_analysis = self.basis_analysis.clone();
+        let regime_analysis = self.regime_analysis.clone();
+        let entry_times = self.entry_times.clone();
+        let hold_times = self.hold_hours.clone();
         
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             // Evaluate signals every 5 seconds
-            let mut interval = tokio::time::interval(Duration::from_secs(5));
+            let mut interval = tokio::time::interval(config.engines.signal_interval());
+            let mut gate = SignalGate::new(&config.signals);
+            let mut reentry = ReentryGuard::new(&config.signals.reentry);
+            // Whether positions were held last evaluation, to see closes
+            let mut had_positions = false;
+            let trading_window = TradingWindow::new(&config);
+            // Last filter reported as blocking an open, so each block is reported once
+            let mut last_blocked: Option<SkipReason> = None;
             
             while *running.read().await {
                 interval.tick().await;
+                heartbeat.beat();
                 
                 // Get current market state
-                let spot_price = state.spot_price.load();
-                let perp_price = state.perp_mark_price.load();
-                let basis_spread = state.get_basis_spread();
-                let funding_apr = state.funding_apr.load();
+                let market = state.snapshot();
+                let basis_spread = market.basis_spread;
+                let funding_apr = market.funding_apr;
                 let timestamp = chrono::Utc::now().timestamp_millis();
                 
-                if spot_price <= 0.0 || perp_price <= 0.0 {
+                let window = trading_window.status();
+                record_trading_window(window.open);
+                
+                if !market.has_prices() {
                     continue;
                 }
                 
                 // Check if we have open positions
                 let has_positions = state.spot_position.read().is_some() 
                     || state.perp_position.read().is_some();
+                if had_positions && !has_positions {
+                    reentry.closed(timestamp);
+                }
+                had_positions = has_positions;
+                
+                // Latest engine analyses (None until each engine's first pass)
+                let funding = funding_analysis.read().await.clone();
+                let basis = basis_analysis.read().await.clone();
+                let regime = regime_analysis.read().await.clone();
+                let entry_time_edge = if config.signals.entry_time.enabled {
+                    entry_times
+                        .read()
+                        .await
+                        .as_ref()
+                        .map(|p| p.preference(timestamp, config.signals.entry_time.min_trades))
+                } else {
+                    None
+                };
+                let degraded = http::degraded();
+                let hold_hours = hold_times
+                    .read()
+                    .await
+                    .unwrap_or(config.signals.expected_value.default_hold_hours);
                 
                 // Evaluate trading conditions
                 let evaluation = Self::evaluate_conditions(
                     &config,
                     &state,
-                    basis_spread,
-                    funding_apr,
+                    &market,
+                    funding.as_ref(),
+                    basis.as_ref(),
+                    regime.as_ref(),
+                    &window,
+                    entry_time_edge,
+                    &degraded,
+                    hold_hours,
+                    &reentry,
                     has_positions,
                     timestamp,
                 ).await;
                 
-                // Generate signal if conditions met
-                if evaluation.should_open || evaluation.should_close || evaluation.should_rebalance {
-                    let signal_type = if evaluation.should_open {
-                        SignalType::OpenBasis
-                    } else if evaluation.should_close {
-                        SignalType::CloseBasis
-                    } else {
-                        SignalType::Rebalance
-                    };
-                    
+                let candidate = if evaluation.should_open {
+                    Some(SignalType::OpenBasis)
+                } else if evaluation.should_close {
+                    Some(SignalType::CloseBasis)
+                } else if evaluation.should_rebalance {
+                    Some(SignalType::Rebalance)
+                } else {
+                    None
+                };
+                
+                // Generate signal once the condition has persisted and its cooldown elapsed
+                let admitted = gate.admit(candidate, evaluation.confidence, timestamp);
+                
+                // Report opens held back by a filter or the signal cooldown
+                let blocked = evaluation.blocked.or_else(|| {
+                    (candidate == Some(SignalType::OpenBasis)
+                        && !admitted
+                        && gate.cooling_down(SignalType::OpenBasis, timestamp))
+                        .then_some(SkipReason::Cooldown)
+                });
+                if let Some(reason) = blocked.filter(|r| last_blocked != Some(*r)) {
+                    let _ = event_tx.send(Event::SignalSkipped {
+                        reason,
+                        detail: evaluation.reasons.join("; "),
+                        size: Self::calculate_recommended_size(
+                            &config,
+                            basis_spread,
+                            funding_apr,
+                            evaluation.confidence,
+                        ),
+                        basis_spread,
+                        funding_apr,
+                        spot_price: market.spot_price,
+                        confidence: evaluation.confidence,
+                        timestamp,
+                    });
+                }
+                last_blocked = blocked;
+                if let Some(signal_type) = candidate.filter(|_| admitted) {
+                    if signal_type == SignalType::OpenBasis {
+                        reentry.entered(EntrySetup { basis_pct: basis_spread, funding_apr_pct: funding_apr }, timestamp);
+                    }
                     let trade_signal = TradeSignal {
                         signal_type,
                         size: evaluation.recommended_size,
@@ -149,13 +321,15 @@ impl SignalEngine {
                         expected_profit: evaluation.expected_profit,
                         confidence: evaluation.confidence,
                         timestamp,
+                        market_timestamp: market.spot_updated_at.max(market.perp_updated_at),
                         reason: evaluation.reasons.join("; "),
+                        expected_value: evaluation.expected_value,
                     };
                     
                     let full_signal = FullTradeSignal {
                         signal: trade_signal.clone(),
-                        funding: None, // Would be populated from funding engine
-                        basis: None,   // Would be populated from basis engine
+                        funding,
+                        basis,
                         evaluation: evaluation.clone(),
                     };
                     
@@ -178,70 +352,167 @@ impl SignalEngine {
                         evaluation.confidence * 100.0,
                         evaluation.reasons.join("; ")
                     );
+                    info!("Confidence factors: {}", evaluation.factor_summary());
                     
                     // Emit event
-                    let _ = event_tx.send(Event::TradeSignal {
-                        signal_type: format!("{:?}", signal_type),
-                        size: evaluation.recommended_size,
-                        reason: evaluation.reasons.join("; "),
-                    });
+                    let _ = event_tx.send(Event::SignalGenerated(trade_signal));
                 }
             }
             
             info!("Signal engine stopped");
         });
+        // A restart replaces a loop that is stuck rather than dead
+        if let Some(previous) = self.task.lock().await.replace(task) {
+            previous.abort();
+        }
         
         Ok(())
     }
     
+    /// Evaluate the market in `state` once, outside the loop: no engine
+    /// analyses, trading window open, no positions and no venue outage
+    pub async fn evaluate(config: &Arc<AppConfig>, state: &Arc<SharedState>) -> SignalEvaluation {
+        let window = WindowStatus { open: true, reason: None };
+        Self::evaluate_conditions(
+            config,
+            state,
+            &state.snapshot(),
+            None,
+            None,
+            None,
+            &window,
+            None,
+            &[],
+            config.signals.expected_value.default_hold_hours,
+            &ReentryGuard::new(&config.signals.reentry),
+            false,
+            chrono::Utc::now().timestamp_millis(),
+        ).await
+    }
+    
     /// Evaluate trading conditions
     async fn evaluate_conditions(
         config: &Arc<AppConfig>,
         state: &Arc<SharedState>,
-        basis_spread: f64,
-        funding_apr: f64,
+        market: &MarketSnapshot,
+        funding: Option<&FundingAnalysis>,
+        basis: Option<&BasisAnalysis>,
+        regime: Option<&RegimeAnalysis>,
+        window: &WindowStatus,
+        entry_time_edge: Option<f64>,
+        degraded: &[Dependency],
+        hold_hours: f64,
+        reentry: &ReentryGuard,
         has_positions: bool,
         timestamp: i64,
     ) -> SignalEvaluation {
+        let basis_spread = market.basis_spread;
+        let funding_apr = market.funding_apr;
         let mut reasons = Vec::new();
-        let mut confidence = 0.0;
+        let weights = &config.signals.scoring;
+        // Confidence is the sum of the factors' contributions
+        let mut factors: Vec<(&'static str, f64)> = Vec::new();
         let mut should_open = false;
         let mut should_close = false;
         let mut should_rebalance = false;
+        let mut blocked = None;
         
-        let min_basis = config.trading.min_basis_spread_pct;
-        let min_funding = config.trading.min_funding_apr_pct;
+        // Spot from the secondary oracles is noisier: demand more before acting on it
+        let contingency = *state.oracle_contingency.read();
+        let scale = config.protocols.oracles.contingency.scale(contingency);
+        let thresholds = state.entry_thresholds.read().unwrap_or_else(|| threshold_learner::configured(config));
+        let governor = state.frequency_governor.read().map_or(1.0, |g| g.multiplier);
+        let thresholds = threshold_learner::governed(config, thresholds, governor);
+        let min_basis = thresholds.min_basis_spread_pct * scale;
+        let min_funding = thresholds.min_funding_apr_pct * scale;
         let close_threshold = config.trading.basis_close_threshold_pct;
-        let hedge_drift_threshold = config.risk.hedge_drift_threshold_pct;
+        let hedge_drift_threshold = config.risk.hedge_drift_threshold_pct * scale;
+        
+        let funding_reversing = funding.map(|f| f.is_reversing).unwrap_or(false);
+        let basis_trending = regime.map(|r| r.regime == MarketRegime::Trending).unwrap_or(false);
+        // Jito alone only loses bundling; sends fall back to the RPC
+        let venue_down = degraded.iter().any(|d| d.blocks_entries());
+        let usdc_depeg = *state.usdc_depeg.read();
         
         // Check open conditions (no existing position)
         if !has_positions {
+            // Entries are judged on the executable basis when depth is known
+            let entry_basis = basis
+                .and_then(|b| b.executable_spread_pct)
+                .unwrap_or(basis_spread);
+            
             // Check basis spread
-            if basis_spread.abs() >= min_basis {
-                confidence += 0.3;
-                reasons.push(format!("Basis {:.3}% >= {:.3}%", basis_spread, min_basis));
+            if entry_basis.abs() >= min_basis {
+                factors.push(("basis_strength", weights.basis_strength));
+                reasons.push(format!("Basis {:.3}% >= {:.3}%", entry_basis, min_basis));
                 
                 // Check funding APR
                 if funding_apr.abs() >= min_funding {
-                    confidence += 0.3;
+                    factors.push(("funding_strength", weights.funding_strength));
                     reasons.push(format!("Funding APR {:.1}% >= {:.1}%", funding_apr, min_funding));
                     
                     // Check alignment (basis and funding same direction)
-                    let aligned = (basis_spread > 0.0 && funding_apr > 0.0) ||
-                                 (basis_spread < 0.0 && funding_apr < 0.0);
-                    if aligned {
-                        confidence += 0.2;
+                    if market.is_aligned() {
+                        factors.push(("alignment", weights.alignment));
                         reasons.push("Basis and funding aligned".to_string());
                     }
                     
+                    // Historical context: reward a basis that is rich versus its
+                    // own history, penalize one that is historically thin
+                    if let Some(b) = basis {
+                        let direction = basis_spread.signum();
+                        if b.z_score * direction >= 1.0 {
+                            factors.push(("z_score", weights.z_score));
+                            reasons.push(format!("Basis z-score {:.2}", b.z_score));
+                        }
+                        let richness = if direction >= 0.0 { b.percentile } else { 100.0 - b.percentile };
+                        if richness < 25.0 {
+                            factors.push(("percentile", -weights.percentile));
+                            reasons.push(format!("Basis only at {:.0}th percentile", richness));
+                        }
+                    }
+                    
                     // Check time since last trade
-                    let last_trade = state.last_trade.load(std::sync::atomic::Ordering::SeqCst);
+                    let last_trade = state.last_trade.load(Ordering::SeqCst);
                     let time_since_trade = timestamp - last_trade;
-                    if time_since_trade > (config.risk.min_trade_interval_secs as i64 * 1000) {
-                        confidence += 0.2;
+                    let setup = EntrySetup { basis_pct: basis_spread, funding_apr_pct: funding_apr };
+                    if contingency {
+                        reasons.push("Pyth stale, spot from secondary oracles, not entering".to_string());
+                        blocked = Some(SkipReason::OracleOutage);
+                    } else if venue_down {
+                        reasons.push("Execution venue degraded, not entering".to_string());
+                        blocked = Some(SkipReason::VenueDegraded);
+                    } else if usdc_depeg {
+                        reasons.push(format!("USDC off peg at ${:.4}, not entering", state.usdc_usd.load()));
+                        blocked = Some(SkipReason::UsdcDepeg);
+                    } else if funding_reversing {
+                        reasons.push("Funding reversing, not entering".to_string());
+                        blocked = Some(SkipReason::Reversal);
+                    } else if basis_trending {
+                        reasons.push(format!(
+                            "Basis trending (VR {:.2}), not entering",
+                            regime.and_then(|r| r.variance_ratio).unwrap_or(0.0)
+                        ));
+                        blocked = Some(SkipReason::Regime);
+                    } else if let Some(blackout) = window.reason.as_ref().filter(|_| !window.open) {
+                        reasons.push(format!("{}, not entering", blackout));
+                        blocked = Some(SkipReason::TradingWindow);
+                    } else if let Some(repeat) = reentry.check(setup, timestamp) {
+                        reasons.push(repeat);
+                        blocked = Some(SkipReason::Reentry);
+                    } else if time_since_trade > (config.risk.min_trade_interval_secs as i64 * 1000) {
+                        factors.push(("freshness", weights.freshness));
                         should_open = true;
+                        
+                        // Funding patterns are time-dependent: favour hours
+                        // and weekdays that have paid off before
+                        if let Some(edge) = entry_time_edge.filter(|e| *e != 0.0) {
+                            factors.push(("entry_time", config.signals.entry_time.weight * edge));
+                            reasons.push(format!("Entry time win rate {:+.0}% vs all trades", edge * 100.0));
+                        }
                     } else {
                         reasons.push("Too soon since last trade".to_string());
+                        blocked = Some(SkipReason::Cooldown);
                     }
                 }
             }
@@ -250,23 +521,29 @@ impl SignalEngine {
             
             // Basis convergence
             if basis_spread.abs() <= close_threshold {
-                confidence += 0.5;
+                factors.push(("converged", 0.5));
                 reasons.push(format!("Basis converged to {:.4}%", basis_spread));
                 should_close = true;
             }
             
             // Funding reversal
-            // (Would need to track funding direction change)
+            if funding_reversing {
+                factors.push(("funding_reversing", 0.3));
+                reasons.push("Funding reversing".to_string());
+                should_close = true;
+            }
             
             // Hedge drift
             let hedge_drift = state.hedge_drift.load();
             if hedge_drift.abs() > hedge_drift_threshold {
-                confidence += 0.3;
+                factors.push(("hedge_drift", 0.3));
                 reasons.push(format!("Hedge drift {:.2}%", hedge_drift));
                 should_rebalance = true;
             }
         }
         
+        let confidence: f64 = factors.iter().map(|(_, weight)| weight).sum();
+        
         // Calculate position size
         let recommended_size = if should_open {
             Self::calculate_recommended_size(
@@ -279,27 +556,93 @@ impl SignalEngine {
             0.0
         };
         
-        // Calculate expected profit (simplified)
-        let expected_profit = if should_open {
-            // Assume we capture half the basis over a week
-            let notional = recommended_size * state.spot_price.load();
-            notional * (basis_spread.abs() / 100.0) * 0.5
+        // Estimate perp execution cost: a positive basis is entered by
+        // shorting the perp (hitting bids), a negative one by buying it
+        let perp_execution_cost_pct = if should_open {
+            state
+                .perp_book()
+                .and_then(|book| book.taker_cost_pct(recommended_size, basis_spread < 0.0))
         } else {
-            0.0
+            None
         };
         
+        if let Some(cost) = perp_execution_cost_pct {
+            reasons.push(format!("Perp execution cost {:.3}%", cost));
+        }
+        
+        // Expected value: funding over the hold + convergence - costs
+        let expected_value = should_open.then(|| {
+            let entry = EntryInputs {
+                size_sol: recommended_size,
+                spot_price: market.spot_price,
+                basis_pct: basis.and_then(|b| b.executable_spread_pct).unwrap_or(basis_spread),
+                funding_apr_pct: funding_apr,
+                perp_cost_pct: perp_execution_cost_pct,
+            };
+            let hold_hours = Self::expected_hold_hours(config, basis, entry.basis_pct, hold_hours);
+            expected_value::estimate(config, entry, funding, basis, hold_hours)
+        });
+        if let Some(ev) = &expected_value {
+            reasons.push(format!(
+                "EV ${:.2} (funding ${:.2}, convergence ${:.2} at ${:.2}/h, costs ${:.2}, {:.0}h hold)",
+                ev.ev, ev.expected_funding, ev.expected_convergence, ev.convergence_per_hour, ev.expected_costs, ev.hold_hours
+            ));
+            if let Some(half_life) = ev.half_life_hours {
+                reasons.push(format!("Basis half-life {:.1}h", half_life));
+            }
+        }
+        let expected_profit = expected_value.map(|ev| ev.ev).unwrap_or(0.0);
+        
+        // Flag signals taken while the perp market is stressed
+        let mark_index_divergence_pct = market
+            .mark_index_divergence_pct()
+            .filter(|d| d.abs() > config.risk.max_mark_index_divergence_pct);
+        if let Some(divergence) = mark_index_divergence_pct {
+            reasons.push(format!("Perp mark {:+.3}% off index", divergence));
+        }
+        
+        if !degraded.is_empty() {
+            let names: Vec<&str> = degraded.iter().map(|d| d.as_str()).collect();
+            reasons.push(format!("Execution venue degraded: {}", names.join(", ")));
+        }
+        
         SignalEvaluation {
             should_open,
             should_close,
             should_rebalance,
             recommended_size,
-            confidence: confidence.min(1.0),
+            confidence: confidence.clamp(0.0, 1.0),
+            factors,
             expected_profit,
+            perp_execution_cost_pct,
+            expected_value,
             reasons,
+            blocked,
+            mark_index_divergence_pct,
             timestamp,
         }
     }
     
+    /// Expected hold of an entry at `entry_basis`: the time the fitted basis
+    /// takes to decay to the close threshold, capped at the max hold.
+    /// `fallback` without a fit, or if the fitted basis settles short of
+    /// the threshold.
+    fn expected_hold_hours(config: &AppConfig, basis: Option<&BasisAnalysis>, entry_basis: f64, fallback: f64) -> f64 {
+        if !config.signals.expected_value.use_half_life {
+            return fallback;
+        }
+        let target = config.trading.basis_close_threshold_pct * entry_basis.signum();
+        basis
+            .and_then(|b| b.mean_reversion)
+            .and_then(|ou| ou.time_to(entry_basis, target))
+            // Capped by the hold limit (0 = no limit)
+            .map(|hours| match config.trading.max_hold_time_hours {
+                0 => hours,
+                max => hours.min(max as f64),
+            })
+            .unwrap_or(fallback)
+    }
+    
     /// Calculate recommended position size
     fn calculate_recommended_size(
         config: &Arc<AppConfig>,
@@ -337,6 +680,11 @@ impl SignalEngine {
         self.last_signal.read().await.clone()
     }
     
+    /// Shared handle to the latest signal, for the agent's decision loop
+    pub fn signal_handle(&self) -> Arc<RwLock<Option<FullTradeSignal>>> {
+        self.last_signal.clone()
+    }
+    
     /// Get signal history
     pub async fn get_signal_history(&self) -> Vec<FullTradeSignal> {
         self.signal_history.read().await.clone()
@@ -360,11 +708,17 @@ mod tests {
             should_rebalance: false,
             recommended_size: 10.0,
             confidence: 0.8,
+            factors: vec![("basis_strength", 0.3), ("funding_strength", 0.3), ("percentile", -0.2)],
             expected_profit: 50.0,
+            perp_execution_cost_pct: Some(0.02),
+            expected_value: None,
             reasons: vec!["Test".to_string()],
+            blocked: None,
+            mark_index_divergence_pct: None,
             timestamp: 0,
         };
         assert!(eval.should_open);
         assert!(!eval.should_close);
+        assert_eq!(eval.factor_summary(), "basis_strength +0.30, funding_strength +0.30, percentile -0.20");
     }
 }
@@ -0,0 +1,189 @@
+//! Signal Gate
+//!
+//! Debounces raw signal evaluations so a basis oscillating around a
+//! threshold does not cause open/close churn:
+//! - Persistence: a condition must hold for N consecutive evaluations
+//! - Minimum confidence: weak open evaluations reset the streak
+//! - Per-signal-type cooldowns between emitted signals
+//! - Re-entry guard: after an entry fails or closes, the same setup (basis
+//!   and funding at the entry) is refused for `reentry.cooldown_secs` unless
+//!   the edge has improved by a minimum delta
+
+use std::collections::HashMap;
+
+use crate::config::{ReentryConfig, SignalConfig};
+use crate::utils::types::SignalType;
+
+/// Persistence and cooldown tracking per signal type
+pub struct SignalGate {
+    config: SignalConfig,
+    /// Consecutive evaluations each signal type has held
+    streaks: HashMap<SignalType, u32>,
+    /// Last emission time per signal type (ms)
+    last_emitted: HashMap<SignalType, i64>,
+}
+
+impl SignalGate {
+    pub fn new(config: &SignalConfig) -> Self {
+        Self {
+            config: config.clone(),
+            streaks: HashMap::new(),
+            last_emitted: HashMap::new(),
+        }
+    }
+
+    /// Feed one evaluation's candidate signal; returns true if it should be emitted
+    pub fn admit(&mut self, candidate: Option<SignalType>, confidence: f64, now: i64) -> bool {
+        let candidate = candidate.filter(|signal| {
+            *signal != SignalType::OpenBasis || confidence >= self.config.min_open_confidence
+        });
+
+        // Any type not seen this evaluation loses its streak
+        self.streaks.retain(|signal, _| Some(*signal) == candidate);
+        let Some(signal) = candidate else {
+            return false;
+        };
+
+        let streak = self.streaks.entry(signal).or_insert(0);
+        *streak += 1;
+        if *streak < self.config.confirmations_required {
+            return false;
+        }
+
+        if let Some(last) = self.last_emitted.get(&signal) {
+            if now - last < self.cooldown_ms(signal) {
+                return false;
+            }
+        }
+
+        self.last_emitted.insert(signal, now);
+        self.streaks.remove(&signal);
+        true
+    }
+
+    /// Whether a persisted signal is only held back by its cooldown
+    pub fn cooling_down(&self, signal: SignalType, now: i64) -> bool {
+        self.streak(signal) >= self.config.confirmations_required
+            && self.last_emitted.get(&signal).is_some_and(|last| now - last < self.cooldown_ms(signal))
+    }
+
+    /// Current streak for a signal type
+    pub fn streak(&self, signal: SignalType) -> u32 {
+        self.streaks.get(&signal).copied().unwrap_or(0)
+    }
+
+    fn cooldown_ms(&self, signal: SignalType) -> i64 {
+        let secs = match signal {
+            SignalType::OpenBasis => self.config.open_cooldown_secs,
+            SignalType::CloseBasis => self.config.close_cooldown_secs,
+            SignalType::Rebalance => self.config.rebalance_cooldown_secs,
+            SignalType::Hold => 0,
+        };
+        secs as i64 * 1000
+    }
+}
+
+/// Market state an entry is signalled at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntrySetup {
+    pub basis_pct: f64,
+    pub funding_apr_pct: f64,
+}
+
+/// Setup of the last entry, refused again until the cooldown ends or the
+/// edge improves
+pub struct ReentryGuard {
+    config: ReentryConfig,
+    /// Last entry's setup and when it ended (ms): the signal time, moved to
+    /// the close if a position opened
+    last: Option<(EntrySetup, i64)>,
+}
+
+impl ReentryGuard {
+    pub fn new(config: &ReentryConfig) -> Self {
+        Self { config: config.clone(), last: None }
+    }
+
+    /// An open was signalled; it counts as failed unless a position closes later
+    pub fn entered(&mut self, setup: EntrySetup, now: i64) {
+        self.last = Some((setup, now));
+    }
+
+    /// The position from the last entry closed
+    pub fn closed(&mut self, now: i64) {
+        if let Some((_, ended)) = self.last.as_mut() {
+            *ended = now;
+        }
+    }
+
+    /// Why `setup` is refused, when it repeats the last entry within the cooldown
+    pub fn check(&self, setup: EntrySetup, now: i64) -> Option<String> {
+        let (last, ended) = self.last.filter(|_| self.config.enabled)?;
+        let age_ms = now - ended;
+        if age_ms >= self.config.cooldown_secs as i64 * 1000 || setup.basis_pct.signum() != last.basis_pct.signum() {
+            return None;
+        }
+        // Richer basis or more funding in the trade's direction
+        let direction = last.basis_pct.signum();
+        let basis_gain = (setup.basis_pct - last.basis_pct) * direction;
+        let funding_gain = (setup.funding_apr_pct - last.funding_apr_pct) * direction;
+        if basis_gain >= self.config.min_basis_improvement_pct || funding_gain >= self.config.min_funding_improvement_pct {
+            return None;
+        }
+        Some(format!(
+            "Same setup as last entry {}m ago (basis {:+.3}%, funding {:+.1}%), not entering",
+            age_ms / 60_000,
+            basis_gain,
+            funding_gain
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_persistence_and_cooldown() {
+        let mut gate = SignalGate::new(&SignalConfig::default());
+        let open = Some(SignalType::OpenBasis);
+
+        // Needs three consecutive confident evaluations
+        assert!(!gate.admit(open, 0.8, 0));
+        assert!(!gate.admit(open, 0.8, 5_000));
+        assert!(gate.admit(open, 0.8, 10_000));
+
+        // Held again, but still inside the open cooldown
+        for t in [15_000, 20_000, 25_000] {
+            assert!(!gate.admit(open, 0.8, t));
+        }
+        assert!(gate.cooling_down(SignalType::OpenBasis, 25_000));
+
+        // A weak or missing evaluation resets the streak
+        assert!(!gate.admit(open, 0.3, 400_000));
+        assert_eq!(gate.streak(SignalType::OpenBasis), 0);
+        assert!(!gate.admit(None, 0.0, 405_000));
+        assert!(!gate.admit(open, 0.8, 410_000));
+        assert_eq!(gate.streak(SignalType::OpenBasis), 1);
+    }
+
+    #[test]
+    fn test_reentry_guard() {
+        let mut guard = ReentryGuard::new(&ReentryConfig::default());
+        let setup = |basis_pct, funding_apr_pct| EntrySetup { basis_pct, funding_apr_pct };
+        assert!(guard.check(setup(0.5, 20.0), 0).is_none());
+
+        // A failed entry blocks the same setup from the signal time
+        guard.entered(setup(0.5, 20.0), 0);
+        assert!(guard.check(setup(0.52, 22.0), 60_000).is_some());
+        // A better edge, or the other direction, is a new setup
+        assert!(guard.check(setup(0.56, 20.0), 60_000).is_none());
+        assert!(guard.check(setup(0.5, 26.0), 60_000).is_none());
+        assert!(guard.check(setup(-0.5, -20.0), 60_000).is_none());
+
+        // A closed trade blocks it from the close
+        guard.closed(7_200_000);
+        assert!(guard.check(setup(0.5, 20.0), 7_300_000).is_some());
+        assert!(guard.check(setup(0.5, 20.0), 10_800_000).is_none());
+    }
+}
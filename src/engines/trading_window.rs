@@ -0,0 +1,119 @@
+//! Trading Windows
+//!
+//! Time-based entry restrictions, all in UTC:
+//! - Funding blackout: no entries in the minutes before a funding settlement
+//! - Weekend blackout: no entries Saturday and Sunday
+//! - Blackout hours: no entries during configured low-liquidity hours
+//!
+//! Windows only gate new entries; open positions can always be closed
+//! or rebalanced.
+
+use chrono::{DateTime, Datelike, Timelike, Utc, Weekday};
+use serde::Serialize;
+
+use crate::config::{AppConfig, TradingWindowConfig};
+use crate::utils::types::FundingInterval;
+
+/// Whether entries are currently allowed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WindowStatus {
+    /// Entries allowed
+    pub open: bool,
+    /// Active blackout, when closed
+    pub reason: Option<String>,
+}
+
+impl WindowStatus {
+    fn open() -> Self {
+        Self { open: true, reason: None }
+    }
+
+    fn closed(reason: impl Into<String>) -> Self {
+        Self { open: false, reason: Some(reason.into()) }
+    }
+}
+
+/// Evaluates the configured trading windows
+#[derive(Debug, Clone)]
+pub struct TradingWindow {
+    config: TradingWindowConfig,
+    /// Settlement cadence of the perp venue
+    funding_interval: FundingInterval,
+}
+
+impl TradingWindow {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            config: config.trading_windows.clone(),
+            funding_interval: config.protocols.drift.funding_interval,
+        }
+    }
+
+    /// Window status at the current time
+    pub fn status(&self) -> WindowStatus {
+        self.status_at(Utc::now())
+    }
+
+    /// Window status at a given time
+    pub fn status_at(&self, now: DateTime<Utc>) -> WindowStatus {
+        if !self.config.enabled {
+            return WindowStatus::open();
+        }
+
+        if self.config.weekend_blackout && matches!(now.weekday(), Weekday::Sat | Weekday::Sun) {
+            return WindowStatus::closed("Weekend blackout");
+        }
+
+        if self.config.blackout_hours_utc.contains(&now.hour()) {
+            return WindowStatus::closed(format!("Blackout hour {:02}:00 UTC", now.hour()));
+        }
+
+        if let Some(mins) = self.minutes_to_settlement(now) {
+            if mins < self.config.funding_blackout_mins as f64 {
+                return WindowStatus::closed(format!("Funding settles in {:.1} min", mins));
+            }
+        }
+
+        WindowStatus::open()
+    }
+
+    /// Minutes until the next funding settlement; None for continuous funding
+    fn minutes_to_settlement(&self, now: DateTime<Utc>) -> Option<f64> {
+        if self.funding_interval == FundingInterval::Continuous {
+            return None;
+        }
+        // Settlements fall on period boundaries counted from midnight UTC
+        let period_secs = (self.funding_interval.period_hours() * 3600.0) as i64;
+        let elapsed = now.num_seconds_from_midnight() as i64 % period_secs;
+        Some((period_secs - elapsed) as f64 / 60.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn window() -> TradingWindow {
+        let mut config = AppConfig::default_for_test();
+        config.trading_windows = TradingWindowConfig {
+            enabled: true,
+            funding_blackout_mins: 5,
+            weekend_blackout: true,
+            blackout_hours_utc: vec![3],
+        };
+        TradingWindow::new(&config)
+    }
+
+    #[test]
+    fn test_blackouts() {
+        let window = window();
+        // Wednesday
+        assert!(window.status_at(Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap()).open);
+        assert!(!window.status_at(Utc.with_ymd_and_hms(2024, 5, 1, 12, 57, 0).unwrap()).open);
+        assert!(!window.status_at(Utc.with_ymd_and_hms(2024, 5, 1, 3, 10, 0).unwrap()).open);
+        // Saturday
+        let status = window.status_at(Utc.with_ymd_and_hms(2024, 5, 4, 12, 30, 0).unwrap());
+        assert_eq!(status.reason.as_deref(), Some("Weekend blackout"));
+    }
+}
@@ -0,0 +1,171 @@
+//! Wallet Balances
+//!
+//! Fetches the capital actually available for trading:
+//! - SOL balance (net of a gas reserve)
+//! - USDC balance for the spot leg
+//! - Drift free collateral for the perp leg
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::config::AppConfig;
+use crate::network::RpcManager;
+use crate::utils::amount::Amount;
+use crate::utils::helpers::drift_user_address;
+
+/// Snapshot of capital available to size trades against
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AvailableCapital {
+    /// Wallet SOL balance
+    pub sol_balance: f64,
+    /// Wallet USDC balance
+    pub usdc_balance: f64,
+    /// Drift free collateral (USD)
+    pub drift_free_collateral: f64,
+    /// SOL reserved for transaction fees
+    pub gas_reserve_sol: f64,
+    /// Snapshot timestamp (ms)
+    pub timestamp: i64,
+}
+
+impl AvailableCapital {
+    /// SOL that can be committed to the spot leg after the gas reserve
+    pub fn spendable_sol(&self) -> f64 {
+        (self.sol_balance - self.gas_reserve_sol).max(0.0)
+    }
+
+    /// Whether the wallet can still pay for transactions
+    pub fn has_gas(&self) -> bool {
+        self.sol_balance > self.gas_reserve_sol
+    }
+
+    /// Largest spot leg (SOL) fundable from USDC plus spendable SOL
+    pub fn max_spot_size(&self, spot_price: f64) -> f64 {
+        if spot_price <= 0.0 {
+            return 0.0;
+        }
+        self.usdc_balance / spot_price + self.spendable_sol()
+    }
+
+    /// Largest perp leg (SOL) supported by Drift free collateral
+    pub fn max_perp_size(&self, perp_price: f64, max_leverage: f64) -> f64 {
+        if perp_price <= 0.0 {
+            return 0.0;
+        }
+        self.drift_free_collateral * max_leverage / perp_price
+    }
+}
+
+/// Drift user stats response
+#[derive(Debug, Deserialize)]
+struct DriftUserResponse {
+    #[serde(rename = "freeCollateral")]
+    free_collateral: Option<String>,
+}
+
+/// Fetches wallet and Drift balances
+pub struct BalanceFetcher {
+    /// RPC manager
+    rpc: Arc<RpcManager>,
+    /// HTTP client for the Drift API
+    client: reqwest::Client,
+    /// Drift data API
+    drift_api_url: String,
+    /// Wallet owner
+    owner: Pubkey,
+    /// The wallet's Drift user account (sub-account 0)
+    drift_user: Pubkey,
+    /// USDC mint
+    usdc_mint: Pubkey,
+    /// SOL reserved for gas
+    gas_reserve_sol: f64,
+}
+
+impl BalanceFetcher {
+    /// Create a new balance fetcher
+    pub fn new(rpc: Arc<RpcManager>, config: &AppConfig, owner: Pubkey) -> Result<Self> {
+        let usdc_mint = Pubkey::from_str(&config.protocols.jupiter.usdc_mint)
+            .context("Invalid USDC mint address")?;
+        let drift_program = Pubkey::from_str(&config.protocols.drift.program_id)
+            .context("Invalid Drift program id")?;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            rpc,
+            client,
+            drift_api_url: config.protocols.drift.api_url.trim_end_matches('/').to_string(),
+            owner,
+            drift_user: drift_user_address(&drift_program, &owner, 0),
+            usdc_mint,
+            gas_reserve_sol: config.wallet.gas_reserve_sol,
+        })
+    }
+
+    /// Fetch a fresh capital snapshot
+    pub async fn fetch(&self) -> Result<AvailableCapital> {
+        let lamports = self.rpc.get_balance(&self.owner).await?;
+        let usdc_balance = self.rpc.get_token_balance(&self.owner, &self.usdc_mint).await?;
+        let drift_free_collateral = self.fetch_drift_free_collateral().await?;
+
+        let capital = AvailableCapital {
+            sol_balance: Amount::lamports(lamports).ui(),
+            usdc_balance,
+            drift_free_collateral,
+            gas_reserve_sol: self.gas_reserve_sol,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+
+        debug!(
+            "Balances: {:.4} SOL | {:.2} USDC | ${:.2} Drift free collateral",
+            capital.sol_balance, capital.usdc_balance, capital.drift_free_collateral
+        );
+
+        Ok(capital)
+    }
+
+    /// Fetch Drift free collateral of the wallet's user account
+    async fn fetch_drift_free_collateral(&self) -> Result<f64> {
+        let url = format!("{}/user/{}", self.drift_api_url, self.drift_user);
+
+        let response: DriftUserResponse = self.client
+            .get(&url)
+            .send()
+            .await?
+            .json()
+            .await
+            .context("Failed to parse Drift user response")?;
+
+        Ok(response.free_collateral
+            .and_then(|c| c.parse::<f64>().ok())
+            .unwrap_or(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capital_limits() {
+        let capital = AvailableCapital {
+            sol_balance: 1.05,
+            usdc_balance: 1500.0,
+            drift_free_collateral: 500.0,
+            gas_reserve_sol: 0.05,
+            timestamp: 0,
+        };
+
+        assert!((capital.spendable_sol() - 1.0).abs() < 1e-9);
+        assert!((capital.max_spot_size(150.0) - 11.0).abs() < 1e-9);
+        assert!((capital.max_perp_size(150.0, 3.0) - 10.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,107 @@
+//! Gas Watchdog
+//!
+//! Keeps an eye on the wallet's SOL, which pays every transaction fee and
+//! Jito tip:
+//! - Below `wallet.gas_warn_sol` a warning alert is raised
+//! - Below `wallet.gas_critical_sol` new entries are blocked (via the risk
+//!   manager); closes still go through
+//! - The latest balance is published to shared state, metrics and `/status`
+
+use anyhow::Result;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::WalletConfig;
+use crate::network::{Event, RpcManager};
+use crate::state::SharedState;
+use crate::telemetry::{record_wallet_gas, Alert};
+use crate::utils::amount::Amount;
+use crate::utils::types::{GasLevel, GasStatus};
+
+/// Periodic check of the wallet's SOL balance
+pub struct GasWatchdog {
+    rpc: Arc<RpcManager>,
+    /// Wallet paying fees and tips
+    owner: Pubkey,
+    config: WalletConfig,
+    state: Arc<SharedState>,
+    event_tx: broadcast::Sender<Event>,
+}
+
+impl GasWatchdog {
+    pub fn new(
+        rpc: Arc<RpcManager>,
+        owner: Pubkey,
+        config: &WalletConfig,
+        state: Arc<SharedState>,
+        event_tx: broadcast::Sender<Event>,
+    ) -> Self {
+        Self { rpc, owner, config: config.clone(), state, event_tx }
+    }
+
+    /// Fetch the balance, publish it, and alert when the level changes
+    pub async fn check(&self) -> Result<GasStatus> {
+        let sol_balance = Amount::lamports(self.rpc.get_balance(&self.owner).await?).ui();
+        let status = GasStatus {
+            sol_balance,
+            level: GasLevel::classify(sol_balance, self.config.gas_warn_sol, self.config.gas_critical_sol),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let previous = self.state.gas.write().replace(status).map(|s| s.level);
+        record_wallet_gas(status.sol_balance, status.level);
+        if previous != Some(status.level) {
+            self.alert(previous, &status);
+        }
+        Ok(status)
+    }
+
+    fn alert(&self, previous: Option<GasLevel>, status: &GasStatus) {
+        let alert = match status.level {
+            GasLevel::Ok if previous.is_some() => {
+                info!("Wallet SOL back to {:.4}", status.sol_balance);
+                Alert::info("Wallet gas restored", format!("{:.4} SOL available for fees", status.sol_balance))
+            }
+            GasLevel::Ok => return,
+            GasLevel::Low => {
+                warn!("Wallet SOL low: {:.4} (warn below {})", status.sol_balance, self.config.gas_warn_sol);
+                Alert::warning(
+                    "Wallet gas low",
+                    format!(
+                        "{:.4} SOL left for fees and tips; top up {} before it drops below {}",
+                        status.sol_balance, self.owner, self.config.gas_critical_sol
+                    ),
+                )
+            }
+            GasLevel::Critical => {
+                warn!("Wallet SOL critical: {:.4}, blocking new entries", status.sol_balance);
+                Alert::critical(
+                    "Wallet gas critical",
+                    format!(
+                        "{:.4} SOL left (critical below {}); new entries blocked until {} is topped up",
+                        status.sol_balance, self.config.gas_critical_sol, self.owner
+                    ),
+                )
+            }
+        };
+        let _ = self.event_tx.send(Event::Alert(alert));
+    }
+
+    /// Check on `wallet.gas_check_secs` until the task is aborted
+    pub fn spawn(self) -> JoinHandle<()> {
+        let period = Duration::from_secs(self.config.gas_check_secs.max(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.check().await {
+                    warn!("Gas check failed: {}", e);
+                }
+            }
+        })
+    }
+}
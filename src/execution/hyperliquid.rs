@@ -0,0 +1,468 @@
+//! Hyperliquid Perp Venue
+//!
+//! `PerpVenue` implementation for Hyperliquid:
+//! - Mark price and hourly funding from the `metaAndAssetCtxs` info endpoint
+//! - Positions from `clearinghouseState`, order state from `orderStatus`
+//! - Orders and cancels as exchange actions signed with an API wallet
+//!   (msgpack action hash, EIP-712 "Agent" signature)
+//!
+//! Market orders are sent as IOC limits a configurable distance through
+//! the mark, which is how Hyperliquid expresses market orders.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use libsecp256k1::{Message, PublicKey, SecretKey};
+use parking_lot::RwLock;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::time::Duration;
+use tracing::{debug, info};
+
+use super::orders::{OrderStatus, VenueOrderState};
+use super::perp_venue::{PerpOrder, PerpOrderAck, PerpVenue, VenueFunding, VenuePosition};
+use super::tx_builder::OrderType;
+use crate::config::HyperliquidConfig;
+use crate::utils::types::FundingInterval;
+
+/// Chain id of the EIP-712 domain used for exchange actions
+const SIGNING_CHAIN_ID: u64 = 1337;
+/// Maximum significant figures in an order price
+const PRICE_SIG_FIGS: i32 = 5;
+/// Maximum price decimals for perps, before subtracting size decimals
+const MAX_PRICE_DECIMALS: i32 = 6;
+
+#[derive(Debug, Deserialize)]
+struct Meta {
+    universe: Vec<UniverseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UniverseAsset {
+    name: String,
+    sz_decimals: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AssetCtx {
+    funding: String,
+    mark_px: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClearinghouseState {
+    asset_positions: Vec<AssetPosition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetPosition {
+    position: PositionData,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionData {
+    coin: String,
+    szi: String,
+    entry_px: Option<String>,
+    unrealized_pnl: String,
+}
+
+/// Order wire format; field names and order are part of the signed hash
+#[derive(Debug, Serialize)]
+struct OrderWire {
+    a: u32,
+    b: bool,
+    p: String,
+    s: String,
+    r: bool,
+    t: OrderTypeWire,
+}
+
+#[derive(Debug, Serialize)]
+struct OrderTypeWire {
+    limit: LimitWire,
+}
+
+#[derive(Debug, Serialize)]
+struct LimitWire {
+    tif: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct CancelWire {
+    a: u32,
+    o: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Action {
+    Order { orders: Vec<OrderWire>, grouping: &'static str },
+    Cancel { cancels: Vec<CancelWire> },
+}
+
+/// Cached market metadata
+#[derive(Debug, Clone, Copy)]
+struct AssetInfo {
+    index: u32,
+    sz_decimals: u32,
+}
+
+/// Round a price to 5 significant figures and the allowed decimals
+fn round_price(price: f64, sz_decimals: u32) -> f64 {
+    if price <= 0.0 {
+        return 0.0;
+    }
+    let magnitude = price.log10().floor() as i32 + 1;
+    let decimals = (PRICE_SIG_FIGS - magnitude).min(MAX_PRICE_DECIMALS - sz_decimals as i32).max(0);
+    let factor = 10f64.powi(decimals);
+    (price * factor).round() / factor
+}
+
+/// Round a size down to the asset's size decimals
+fn round_size(size: f64, sz_decimals: u32) -> f64 {
+    let factor = 10f64.powi(sz_decimals as i32);
+    (size * factor).floor() / factor
+}
+
+/// Decimal string without trailing zeros, as the exchange expects
+fn float_to_wire(value: f64) -> String {
+    let s = format!("{:.8}", value);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" { "0".to_string() } else { s.to_string() }
+}
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Hash of an action as signed by the API wallet (no vault)
+fn action_hash(action: &Action, nonce: u64) -> Result<[u8; 32]> {
+    let mut data = rmp_serde::to_vec_named(action).context("Failed to encode Hyperliquid action")?;
+    data.extend_from_slice(&nonce.to_be_bytes());
+    data.push(0);
+    Ok(keccak(&data))
+}
+
+/// EIP-712 digest of the phantom agent for an action hash
+fn agent_digest(source: &str, connection_id: &[u8; 32]) -> [u8; 32] {
+    let mut domain = keccak(
+        b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+    ).to_vec();
+    domain.extend_from_slice(&keccak(b"Exchange"));
+    domain.extend_from_slice(&keccak(b"1"));
+    let mut chain_id = [0u8; 32];
+    chain_id[24..].copy_from_slice(&SIGNING_CHAIN_ID.to_be_bytes());
+    domain.extend_from_slice(&chain_id);
+    domain.extend_from_slice(&[0u8; 32]);
+    let domain_separator = keccak(&domain);
+
+    let mut agent = keccak(b"Agent(string source,bytes32 connectionId)").to_vec();
+    agent.extend_from_slice(&keccak(source.as_bytes()));
+    agent.extend_from_slice(connection_id);
+    let struct_hash = keccak(&agent);
+
+    let mut message = vec![0x19, 0x01];
+    message.extend_from_slice(&domain_separator);
+    message.extend_from_slice(&struct_hash);
+    keccak(&message)
+}
+
+/// Hyperliquid perp venue
+pub struct HyperliquidVenue {
+    config: HyperliquidConfig,
+    client: Client,
+    /// API wallet key; without one the venue is read-only
+    signing_key: Option<SecretKey>,
+    /// Account address positions are read for
+    address: Option<String>,
+    asset: RwLock<Option<AssetInfo>>,
+}
+
+impl HyperliquidVenue {
+    pub fn new(config: &HyperliquidConfig) -> Result<Self> {
+        let signing_key = config
+            .private_key
+            .as_deref()
+            .map(|key| {
+                let bytes = hex::decode(key.trim_start_matches("0x"))
+                    .context("Hyperliquid private key is not hex")?;
+                SecretKey::parse_slice(&bytes)
+                    .map_err(|e| anyhow::anyhow!("Invalid Hyperliquid private key: {:?}", e))
+            })
+            .transpose()?;
+
+        // Positions belong to the main account, which may differ from the API wallet
+        let address = config.account_address.clone().or_else(|| {
+            signing_key.as_ref().map(|key| {
+                let point = PublicKey::from_secret_key(key).serialize();
+                format!("0x{}", hex::encode(&keccak(&point[1..])[12..]))
+            })
+        });
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            config: config.clone(),
+            client,
+            signing_key,
+            address,
+            asset: RwLock::new(None),
+        })
+    }
+
+    async fn info<T: serde::de::DeserializeOwned>(&self, body: serde_json::Value) -> Result<T> {
+        let response = self.client
+            .post(format!("{}/info", self.config.api_url))
+            .json(&body)
+            .send()
+            .await
+            .context("Hyperliquid info request failed")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Hyperliquid info request failed: {} - {}", status, text);
+        }
+        response.json().await.context("Failed to parse Hyperliquid info response")
+    }
+
+    /// Market metadata and the configured coin's context
+    async fn market(&self) -> Result<(AssetInfo, AssetCtx)> {
+        let (meta, ctxs): (Meta, Vec<AssetCtx>) =
+            self.info(serde_json::json!({ "type": "metaAndAssetCtxs" })).await?;
+        let index = meta.universe
+            .iter()
+            .position(|a| a.name == self.config.coin)
+            .with_context(|| format!("Hyperliquid has no {} perp", self.config.coin))?;
+        let info = AssetInfo {
+            index: index as u32,
+            sz_decimals: meta.universe[index].sz_decimals,
+        };
+        *self.asset.write() = Some(info);
+        let ctx = ctxs.into_iter().nth(index).context("Missing Hyperliquid asset context")?;
+        Ok((info, ctx))
+    }
+
+    async fn asset_info(&self) -> Result<AssetInfo> {
+        let cached = *self.asset.read();
+        match cached {
+            Some(info) => Ok(info),
+            None => Ok(self.market().await?.0),
+        }
+    }
+
+    /// Sign and send an exchange action; returns the response payload
+    async fn exchange(&self, action: Action) -> Result<serde_json::Value> {
+        let key = self.signing_key.as_ref().context("Hyperliquid private key not configured")?;
+        let nonce = chrono::Utc::now().timestamp_millis() as u64;
+        let source = if self.config.api_url.contains("testnet") { "b" } else { "a" };
+        let digest = agent_digest(source, &action_hash(&action, nonce)?);
+        // Deterministic (RFC 6979), low-s
+        let (signature, recovery_id) = libsecp256k1::sign(&Message::parse(&digest), key);
+        let bytes = signature.serialize();
+
+        let body = serde_json::json!({
+            "action": action,
+            "nonce": nonce,
+            "signature": {
+                "r": format!("0x{}", hex::encode(&bytes[..32])),
+                "s": format!("0x{}", hex::encode(&bytes[32..])),
+                "v": 27 + recovery_id.serialize() as u64,
+            },
+            "vaultAddress": null,
+        });
+
+        debug!("Hyperliquid exchange action: {}", body["action"]);
+        let response: serde_json::Value = self.client
+            .post(format!("{}/exchange", self.config.api_url))
+            .json(&body)
+            .send()
+            .await
+            .context("Hyperliquid exchange request failed")?
+            .json()
+            .await
+            .context("Failed to parse Hyperliquid exchange response")?;
+
+        if response["status"] != "ok" {
+            anyhow::bail!("Hyperliquid rejected action: {}", response["response"]);
+        }
+        Ok(response["response"]["data"].clone())
+    }
+}
+
+#[async_trait]
+impl PerpVenue for HyperliquidVenue {
+    fn name(&self) -> &str {
+        "hyperliquid"
+    }
+
+    async fn mark_price(&self) -> Result<f64> {
+        let (_, ctx) = self.market().await?;
+        ctx.mark_px.parse().context("Invalid Hyperliquid mark price")
+    }
+
+    async fn funding(&self) -> Result<VenueFunding> {
+        let (_, ctx) = self.market().await?;
+        Ok(VenueFunding {
+            rate: ctx.funding.parse().context("Invalid Hyperliquid funding rate")?,
+            borrow_rate: 0.0,
+            interval: FundingInterval::Hourly,
+        })
+    }
+
+    async fn place_order(&self, order: &PerpOrder) -> Result<PerpOrderAck> {
+        let (asset, ctx) = self.market().await?;
+        let is_buy = order.size_sol > 0.0;
+        let size = round_size(order.size_sol.abs(), asset.sz_decimals);
+        anyhow::ensure!(size > 0.0, "Order size {:.6} SOL is below the size increment", order.size_sol.abs());
+
+        let (price, tif) = match order.order_type {
+            OrderType::Market => {
+                let mark: f64 = ctx.mark_px.parse().context("Invalid Hyperliquid mark price")?;
+                let slippage = self.config.market_slippage_pct / 100.0;
+                let price = if is_buy { mark * (1.0 + slippage) } else { mark * (1.0 - slippage) };
+                (price, "Ioc")
+            }
+            OrderType::Limit => {
+                let price = order.limit_price.context("Limit order needs a price")?;
+                (price, if order.post_only { "Alo" } else { "Gtc" })
+            }
+            other => anyhow::bail!("Hyperliquid venue does not support {:?} orders", other),
+        };
+        let price = round_price(price, asset.sz_decimals);
+
+        let data = self.exchange(Action::Order {
+            orders: vec![OrderWire {
+                a: asset.index,
+                b: is_buy,
+                p: float_to_wire(price),
+                s: float_to_wire(size),
+                r: order.reduce_only,
+                t: OrderTypeWire { limit: LimitWire { tif } },
+            }],
+            grouping: "na",
+        }).await?;
+
+        let status = &data["statuses"][0];
+        if let Some(error) = status["error"].as_str() {
+            anyhow::bail!("Hyperliquid order rejected: {}", error);
+        }
+        let ack = if let Some(filled) = status.get("filled") {
+            let total: f64 = filled["totalSz"].as_str().unwrap_or("0").parse().unwrap_or(0.0);
+            PerpOrderAck {
+                order_id: filled["oid"].to_string(),
+                filled: total.copysign(order.size_sol),
+                price: filled["avgPx"].as_str().unwrap_or("0").parse().unwrap_or(price),
+            }
+        } else if let Some(resting) = status.get("resting") {
+            PerpOrderAck { order_id: resting["oid"].to_string(), filled: 0.0, price }
+        } else {
+            anyhow::bail!("Unexpected Hyperliquid order status: {}", status);
+        };
+
+        info!(
+            "Hyperliquid {} {} {} @ {} -> filled {:.4} @ ${:.4} (oid {})",
+            if is_buy { "buy" } else { "sell" }, size, self.config.coin, price, ack.filled, ack.price, ack.order_id
+        );
+        Ok(ack)
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let asset = self.asset_info().await?;
+        let oid: u64 = order_id.parse().context("Hyperliquid order ids are numeric")?;
+        let data = self.exchange(Action::Cancel { cancels: vec![CancelWire { a: asset.index, o: oid }] }).await?;
+        if let Some(error) = data["statuses"][0]["error"].as_str() {
+            anyhow::bail!("Hyperliquid cancel rejected: {}", error);
+        }
+        Ok(())
+    }
+
+    async fn order_state(&self, order_id: &str) -> Result<Option<VenueOrderState>> {
+        let address = self.address.as_ref().context("Hyperliquid account address not configured")?;
+        let oid: u64 = order_id.parse().context("Hyperliquid order ids are numeric")?;
+        let response: serde_json::Value = self
+            .info(serde_json::json!({ "type": "orderStatus", "user": address, "oid": oid }))
+            .await?;
+        if response["status"] != "order" {
+            return Ok(None);
+        }
+
+        // `sz` is what remains of `origSz`; the fill price is not reported
+        let order = &response["order"]["order"];
+        let size = |key: &str| order[key].as_str().and_then(|v| v.parse::<f64>().ok()).unwrap_or(0.0);
+        let filled = size("origSz") - size("sz");
+        let status = match response["order"]["status"].as_str() {
+            Some("open") if filled > 0.0 => OrderStatus::PartiallyFilled,
+            Some("open") => OrderStatus::Open,
+            Some("filled") => OrderStatus::Filled,
+            _ => OrderStatus::Cancelled,
+        };
+        Ok(Some(VenueOrderState { filled, avg_price: None, status }))
+    }
+
+    async fn position(&self) -> Result<Option<VenuePosition>> {
+        let address = self.address.as_ref().context("Hyperliquid account address not configured")?;
+        let state: ClearinghouseState = self
+            .info(serde_json::json!({ "type": "clearinghouseState", "user": address }))
+            .await?;
+
+        Ok(state.asset_positions
+            .into_iter()
+            .map(|p| p.position)
+            .find(|p| p.coin == self.config.coin)
+            .and_then(|p| {
+                let size: f64 = p.szi.parse().ok()?;
+                (size != 0.0).then(|| VenuePosition {
+                    size,
+                    entry_price: p.entry_px.and_then(|e| e.parse().ok()).unwrap_or(0.0),
+                    unrealized_pnl: p.unrealized_pnl.parse().unwrap_or(0.0),
+                })
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_wire_rounding() {
+        // SOL has 2 size decimals, so prices keep at most 4 decimals
+        assert_eq!(round_price(151.23456, 2), 151.23);
+        assert_eq!(round_price(0.123456789, 2), 0.1235);
+        assert_eq!(round_size(1.23999, 2), 1.23);
+        assert_eq!(float_to_wire(151.2), "151.2");
+        assert_eq!(float_to_wire(2.0), "2");
+    }
+
+    #[test]
+    fn test_action_hash_depends_on_nonce() {
+        let action = || Action::Cancel { cancels: vec![CancelWire { a: 5, o: 42 }] };
+        let a = action_hash(&action(), 1).unwrap();
+        let b = action_hash(&action(), 2).unwrap();
+        assert_ne!(a, b);
+        assert_ne!(agent_digest("a", &a), agent_digest("b", &a));
+    }
+
+    #[test]
+    fn test_signing_key_address_and_recovery() {
+        let config = HyperliquidConfig { private_key: Some(format!("0x{:064x}", 1)), ..Default::default() };
+        let venue = HyperliquidVenue::new(&config).unwrap();
+        // Ethereum address of private key 1
+        assert_eq!(venue.address.as_deref(), Some("0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"));
+
+        let key = venue.signing_key.as_ref().unwrap();
+        let message = Message::parse(&agent_digest("a", &[7; 32]));
+        let (signature, recovery_id) = libsecp256k1::sign(&message, key);
+        assert!(!signature.s.is_high());
+        let recovered = libsecp256k1::recover(&message, &signature, &recovery_id).unwrap();
+        assert_eq!(recovered, PublicKey::from_secret_key(key));
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Handles Jito bundle submission for MEV protection:
 //! - Bundle creation with tip
+//! - Tip sizing from recent landed-tip percentiles, capped by trade edge
 //! - Block engine submission
 //! - Bundle status tracking
 
@@ -11,13 +12,17 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::{
     pubkey::Pubkey,
     signature::Signature,
-    transaction::Transaction,
+    transaction::VersionedTransaction,
 };
+use parking_lot::Mutex;
 use std::str::FromStr;
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
-use crate::config::ExecutionConfig;
+use crate::config::{ExecutionConfig, JitoTipConfig};
+use crate::network::HttpRetryPolicy;
+use crate::utils::amount::Amount;
 
 /// Jito tip accounts (rotated periodically)
 const JITO_TIP_ACCOUNTS: [&str; 8] = [
@@ -89,16 +94,59 @@ pub struct BundleStatusValue {
     pub landed_slot: Option<u64>,
 }
 
+/// Recently landed tip percentiles (SOL), from the tip floor endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct TipFloor {
+    pub landed_tips_25th_percentile: f64,
+    pub landed_tips_50th_percentile: f64,
+    pub landed_tips_75th_percentile: f64,
+    pub landed_tips_95th_percentile: f64,
+    pub landed_tips_99th_percentile: f64,
+}
+
+impl TipFloor {
+    /// Landed tip at a percentile (lamports)
+    pub fn percentile_lamports(&self, percentile: u8) -> Option<u64> {
+        let sol = match percentile {
+            25 => self.landed_tips_25th_percentile,
+            50 => self.landed_tips_50th_percentile,
+            75 => self.landed_tips_75th_percentile,
+            95 => self.landed_tips_95th_percentile,
+            99 => self.landed_tips_99th_percentile,
+            _ => return None,
+        };
+        Amount::sol(sol).and_then(|tip| tip.base_units()).ok()
+    }
+}
+
+/// Tip for a bundle: the base tip (recent percentile, else fixed), capped
+/// at a share of the expected profit, clamped to the configured range. The
+/// minimum wins over the profit cap: a tip below it would rarely land.
+pub fn size_tip(config: &JitoTipConfig, base_lamports: u64, expected_profit_usd: Option<f64>, sol_price: f64) -> u64 {
+    let mut tip = base_lamports;
+    if let Some(profit) = expected_profit_usd.filter(|p| *p > 0.0 && sol_price > 0.0) {
+        let cap = Amount::sol(profit * config.max_profit_share_pct / 100.0 / sol_price).and_then(|cap| cap.base_units());
+        tip = tip.min(cap.unwrap_or(u64::MAX));
+    }
+    tip.clamp(config.min_lamports, config.max_lamports)
+}
+
 /// Jito client for bundle submission
 pub struct JitoClient {
     /// HTTP client
     client: Client,
+    /// Retries and cool-off of the block engine calls
+    retry: HttpRetryPolicy,
     /// Block engine URL
     block_engine_url: String,
     /// Tip amount in lamports
     tip_lamports: u64,
     /// Current tip account index
-    tip_account_index: std::sync::atomic::AtomicUsize,
+    tip_account_index: AtomicUsize,
+    /// Tip sizing
+    tip_config: JitoTipConfig,
+    /// Last fetched tip floor
+    tip_floor: Mutex<Option<(TipFloor, Instant)>>,
 }
 
 impl JitoClient {
@@ -111,43 +159,97 @@ impl JitoClient {
         
         Ok(Self {
             client,
+            retry: HttpRetryPolicy::default(),
             block_engine_url: config.jito_block_engine_url.clone(),
             tip_lamports: config.jito_tip_lamports,
-            tip_account_index: std::sync::atomic::AtomicUsize::new(0),
+            tip_account_index: AtomicUsize::new(0),
+            tip_config: config.jito_tip.clone(),
+            tip_floor: Mutex::new(None),
         })
     }
     
+    /// Retry and cool off block engine calls under `retry`
+    pub fn with_retry_policy(mut self, retry: HttpRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    
     /// Get current tip account
     pub fn get_tip_account(&self) -> Pubkey {
-        let index = self.tip_account_index.load(std::sync::atomic::Ordering::Relaxed);
+        let index = self.tip_account_index.load(Ordering::Relaxed);
         Pubkey::from_str(JITO_TIP_ACCOUNTS[index % JITO_TIP_ACCOUNTS.len()]).unwrap()
     }
     
     /// Rotate to next tip account
     pub fn rotate_tip_account(&self) {
-        self.tip_account_index.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.tip_account_index.fetch_add(1, Ordering::Relaxed);
     }
     
-    /// Get tip amount
+    /// Fixed tip amount
     pub fn tip_lamports(&self) -> u64 {
         self.tip_lamports
     }
     
+    /// Fetch recent landed tip percentiles
+    pub async fn fetch_tip_floor(&self) -> Result<TipFloor> {
+        let floors: Vec<TipFloor> = self.retry
+            .send(self.client.get(&self.tip_config.tip_floor_url))
+            .await
+            .context("Failed to fetch Jito tip floor")?
+            .error_for_status()
+            .context("Jito tip floor request failed")?
+            .json()
+            .await
+            .context("Failed to parse Jito tip floor")?;
+        floors.into_iter().next().context("Empty Jito tip floor response")
+    }
+    
+    /// Tip for a bundle given the trade's expected profit, if known
+    pub async fn tip_for(&self, expected_profit_usd: Option<f64>, sol_price: f64) -> u64 {
+        let base = match self.tip_config.strategy.as_str() {
+            "dynamic" => self.recent_tip().await.unwrap_or(self.tip_lamports),
+            _ => self.tip_lamports,
+        };
+        let tip = size_tip(&self.tip_config, base, expected_profit_usd, sol_price);
+        debug!("Jito tip {} lamports (base {}, expected profit {:?})", tip, base, expected_profit_usd);
+        tip
+    }
+    
+    /// Configured percentile of recently landed tips, refreshed when stale
+    async fn recent_tip(&self) -> Option<u64> {
+        let max_age = Duration::from_secs(self.tip_config.refresh_secs);
+        let cached = self.tip_floor.lock().clone();
+        let floor = match cached {
+            Some((floor, fetched)) if fetched.elapsed() < max_age => floor,
+            stale => match self.fetch_tip_floor().await {
+                Ok(floor) => {
+                    *self.tip_floor.lock() = Some((floor.clone(), Instant::now()));
+                    floor
+                }
+                Err(e) => {
+                    warn!("Jito tip floor unavailable: {:#}", e);
+                    stale?.0
+                }
+            },
+        };
+        floor.percentile_lamports(self.tip_config.percentile)
+    }
+    
     /// Submit a bundle of transactions
-    pub async fn submit_bundle(&self, transactions: Vec<Transaction>) -> Result<String> {
+    pub async fn submit_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String> {
         if transactions.is_empty() {
             anyhow::bail!("Cannot submit empty bundle");
         }
         
         // Serialize transactions to base64
-        let encoded_txs: Vec<String> = transactions
+        let encoded_txs = transactions
             .iter()
             .map(|tx| {
                 let serialized = bincode::serialize(tx)
-                    .expect("Failed to serialize transaction");
-                base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &serialized)
+                    .context("Failed to serialize transaction")?;
+                Ok(base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &serialized))
             })
-            .collect();
+            .collect::<Result<Vec<String>>>()?;
         
         let request = serde_json::json!({
             "jsonrpc": "2.0",
@@ -158,10 +260,8 @@ impl JitoClient {
         
         debug!("Submitting Jito bundle with {} transactions", transactions.len());
         
-        let response = self.client
-            .post(&format!("{}/api/v1/bundles", self.block_engine_url))
-            .json(&request)
-            .send()
+        let response = self.retry
+            .send(self.client.post(&format!("{}/api/v1/bundles", self.block_engine_url)).json(&request))
             .await
             .context("Failed to submit Jito bundle")?;
         
@@ -198,10 +298,8 @@ impl JitoClient {
             "params": [[bundle_id]]
         });
         
-        let response = self.client
-            .post(&format!("{}/api/v1/bundles", self.block_engine_url))
-            .json(&request)
-            .send()
+        let response = self.retry
+            .send(self.client.post(&format!("{}/api/v1/bundles", self.block_engine_url)).json(&request))
             .await
             .context("Failed to get bundle status")?;
         
@@ -265,11 +363,12 @@ impl JitoClient {
     pub fn create_tip_instruction(
         &self,
         payer: &Pubkey,
+        lamports: u64,
     ) -> solana_sdk::instruction::Instruction {
         solana_sdk::system_instruction::transfer(
             payer,
             &self.get_tip_account(),
-            self.tip_lamports,
+            lamports,
         )
     }
 }
@@ -285,6 +384,18 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_size_tip() {
+        let config = JitoTipConfig { min_lamports: 1_000, max_lamports: 500_000, max_profit_share_pct: 10.0, ..JitoTipConfig::default() };
+        // No edge: base tip, clamped
+        assert_eq!(size_tip(&config, 20_000, None, 100.0), 20_000);
+        assert_eq!(size_tip(&config, 2_000_000, None, 100.0), 500_000);
+        // $0.10 profit at $100/SOL: 10% is 0.0001 SOL = 100k lamports
+        assert_eq!(size_tip(&config, 300_000, Some(0.10), 100.0), 100_000);
+        // A tiny edge still pays the minimum
+        assert_eq!(size_tip(&config, 300_000, Some(0.0001), 100.0), 1_000);
+    }
+    
     #[test]
     fn test_bundle_status() {
         assert_eq!(BundleStatus::Pending, BundleStatus::Pending);
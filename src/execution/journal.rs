@@ -0,0 +1,84 @@
+//! Execution Journal
+//!
+//! Append-only JSON-lines record of execution events that need a paper
+//! trail beyond the log, such as leg reconciliations, dry-run reports and
+//! recorded session inputs.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Journal entry kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalKind {
+    LegReconciliation,
+    /// Dry-run build and simulation of a trade
+    ShadowExecution,
+    /// Market, link or signal input recorded for session replay
+    SessionInput,
+}
+
+/// Single journal line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: i64,
+    pub kind: JournalKind,
+    /// Trade the entry belongs to
+    pub trade_id: String,
+    pub details: serde_json::Value,
+}
+
+impl JournalEntry {
+    pub fn new(kind: JournalKind, trade_id: impl Into<String>, details: serde_json::Value) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            kind,
+            trade_id: trade_id.into(),
+            details,
+        }
+    }
+}
+
+/// Append-only execution journal
+pub struct ExecutionJournal {
+    path: String,
+    /// Serializes appends so lines never interleave
+    write_lock: Mutex<()>,
+}
+
+impl ExecutionJournal {
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Append an entry as one JSON line
+    pub async fn append(&self, entry: &JournalEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry).context("Failed to serialize journal entry")?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .with_context(|| format!("Failed to open journal {}", self.path))?;
+        file.write_all(line.as_bytes()).await.context("Failed to write journal entry")?;
+        Ok(())
+    }
+
+    /// Read all entries, skipping lines that fail to parse
+    pub async fn entries(&self) -> Result<Vec<JournalEntry>> {
+        let content = match tokio::fs::read_to_string(&self.path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context("Failed to read journal"),
+        };
+        Ok(content.lines().filter_map(|l| serde_json::from_str(l).ok()).collect())
+    }
+}
@@ -14,6 +14,8 @@ use std::time::Duration;
 use tracing::{debug, info};
 
 use crate::config::JupiterConfig;
+use crate::network::HttpRetryPolicy;
+use crate::telemetry::{timed, LatencyStage};
 
 /// Jupiter quote response
 #[derive(Debug, Clone, Deserialize)]
@@ -90,12 +92,16 @@ pub struct SwapResult {
 pub struct JupiterClient {
     /// HTTP client
     client: Client,
+    /// Retries and cool-off of the API calls
+    retry: HttpRetryPolicy,
     /// Jupiter API URL
     api_url: String,
     /// SOL mint address
     sol_mint: Pubkey,
     /// USDC mint address
     usdc_mint: Pubkey,
+    /// Decimals of the spot token (9 for SOL)
+    spot_decimals: u8,
 }
 
 impl JupiterClient {
@@ -113,13 +119,22 @@ impl JupiterClient {
         
         Ok(Self {
             client,
+            retry: HttpRetryPolicy::default(),
             api_url: config.api_url.clone(),
             sol_mint,
             usdc_mint,
+            spot_decimals: config.spot_decimals,
         })
     }
     
+    /// Retry and cool off API calls under `retry`
+    pub fn with_retry_policy(mut self, retry: HttpRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    
     /// Get a quote for swapping tokens
+    #[tracing::instrument(name = "quote", skip_all, fields(amount))]
     pub async fn get_quote(
         &self,
         input_mint: &Pubkey,
@@ -134,9 +149,7 @@ impl JupiterClient {
         
         debug!("Fetching Jupiter quote: {}", url);
         
-        let response = self.client
-            .get(&url)
-            .send()
+        let response = timed(LatencyStage::Quote, self.retry.send(self.client.get(&url)))
             .await
             .context("Failed to fetch Jupiter quote")?;
         
@@ -198,10 +211,8 @@ impl JupiterClient {
         
         debug!("Fetching Jupiter swap transaction");
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
+        let response = self.retry
+            .send(self.client.post(&url).json(&request))
             .await
             .context("Failed to fetch Jupiter swap transaction")?;
         
@@ -260,6 +271,11 @@ impl JupiterClient {
     pub fn usdc_mint(&self) -> &Pubkey {
         &self.usdc_mint
     }
+    
+    /// Decimals of the spot token
+    pub fn spot_decimals(&self) -> u8 {
+        self.spot_decimals
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,500 @@
+//! Jupiter Perps Venue
+//!
+//! `PerpVenue` implementation for Jupiter Perpetuals (JLP pool):
+//! - Fills at the oracle price; there is no mark/index spread
+//! - No funding: positions pay an hourly borrow fee to the pool, scaled by
+//!   the utilization of the custody the position borrows from (USDC for
+//!   shorts, SOL for longs)
+//! - Positions are read from the on-chain position account
+//! - Orders are market position requests, filled by Jupiter's keepers; an
+//!   order reports what the position account shows filled within
+//!   `fill_timeout_secs`, nothing if the keeper has not acted by then
+//! - Only the short hedge is carried: requests post and return USDC
+//!
+//! The borrow fee is reported through `VenueFunding::borrow_rate`, so the
+//! router and the funding engine compare it against funding venues on the
+//! same per-hour basis.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use super::orders::VenueOrderState;
+use super::perp_venue::{PerpOrder, PerpOrderAck, PerpVenue, VenueFunding, VenuePosition};
+use super::submitter::TransactionSubmitter;
+use super::tx_builder::{OrderType, TransactionBuilder};
+use crate::config::JupiterPerpsConfig;
+use crate::network::RpcManager;
+use crate::state::SharedState;
+use crate::utils::amount::{from_base_units, to_base_units, JUPITER_USD_DECIMALS};
+use crate::utils::helpers::{associated_token_address, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use crate::utils::types::FundingInterval;
+
+/// Borrow rates are quoted in decibasis points per hour
+const DBPS_PRECISION: f64 = 100_000.0;
+/// Anchor account discriminators
+const CUSTODY_DISCRIMINATOR: [u8; 8] = [1, 184, 48, 81, 93, 131, 63, 145];
+/// Anchor instruction discriminators
+const INCREASE_REQUEST_DISCRIMINATOR: [u8; 8] = [184, 85, 199, 24, 105, 171, 156, 56];
+const DECREASE_REQUEST_DISCRIMINATOR: [u8; 8] = [74, 198, 195, 86, 193, 99, 1, 79];
+/// How often the position account is read while a keeper fills a request
+const KEEPER_POLL_MS: u64 = 1000;
+
+/// Position request kind, as seeded into the request PDA
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RequestChange {
+    Increase = 1,
+    Decrease = 2,
+}
+
+/// Position side as stored on chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+enum Side {
+    None,
+    Long,
+    Short,
+}
+
+#[derive(Debug, BorshDeserialize)]
+struct OracleParams {
+    _oracle_account: [u8; 32],
+    _oracle_type: u8,
+    _buffer: u8,
+    _max_price_age_sec: u32,
+}
+
+#[derive(Debug, BorshDeserialize)]
+struct PricingParams {
+    _trade_impact_fee_scalar: u64,
+    _buffer: u64,
+    _swap_spread: u64,
+    _max_leverage: u64,
+    _max_global_long_sizes: u64,
+    _max_global_short_sizes: u64,
+}
+
+#[derive(Debug, BorshDeserialize)]
+struct Assets {
+    _fees_reserves: u64,
+    owned: u64,
+    locked: u64,
+    _guaranteed_usd: u64,
+    _global_short_sizes: u64,
+    _global_short_average_prices: u64,
+}
+
+#[derive(Debug, BorshDeserialize)]
+struct FundingRateState {
+    _cumulative_interest_rate: u128,
+    _last_update: i64,
+    hourly_funding_dbps: u64,
+}
+
+/// Leading fields of the custody account, up to the borrow rate state
+#[derive(Debug, BorshDeserialize)]
+struct Custody {
+    _pool: [u8; 32],
+    mint: [u8; 32],
+    _token_account: [u8; 32],
+    _decimals: u8,
+    _is_stable: bool,
+    _oracle: OracleParams,
+    _pricing: PricingParams,
+    _permissions: [bool; 7],
+    _target_ratio_bps: u64,
+    assets: Assets,
+    funding_rate_state: FundingRateState,
+}
+
+impl Custody {
+    /// Hourly borrow fee (fraction of position size)
+    fn hourly_borrow_rate(&self) -> f64 {
+        if self.assets.owned == 0 {
+            return 0.0;
+        }
+        let utilization = self.assets.locked as f64 / self.assets.owned as f64;
+        self.funding_rate_state.hourly_funding_dbps as f64 / DBPS_PRECISION * utilization
+    }
+}
+
+/// Leading fields of the position account
+#[derive(Debug, BorshDeserialize)]
+struct PositionAccount {
+    _owner: [u8; 32],
+    _pool: [u8; 32],
+    _custody: [u8; 32],
+    _collateral_custody: [u8; 32],
+    _open_time: i64,
+    _update_time: i64,
+    side: Side,
+    price: u64,
+    size_usd: u64,
+    _collateral_usd: u64,
+    _realised_pnl_usd: i64,
+}
+
+#[derive(Debug, BorshSerialize)]
+struct IncreaseRequestParams {
+    size_usd_delta: u64,
+    collateral_token_delta: u64,
+    side: Side,
+    price_slippage: u64,
+    jupiter_minimum_out: Option<u64>,
+    counter: u64,
+}
+
+#[derive(Debug, BorshSerialize)]
+struct DecreaseRequestParams {
+    collateral_usd_delta: u64,
+    size_usd_delta: u64,
+    price_slippage: u64,
+    jupiter_minimum_out: Option<u64>,
+    entire_position: Option<bool>,
+    counter: u64,
+}
+
+/// Decode an Anchor account, checking its discriminator
+fn decode_custody(data: &[u8]) -> Result<Custody> {
+    anyhow::ensure!(
+        data.len() > 8 && data[..8] == CUSTODY_DISCRIMINATOR,
+        "Account is not a Jupiter Perps custody"
+    );
+    Custody::deserialize(&mut &data[8..]).context("Failed to decode Jupiter Perps custody")
+}
+
+/// Jupiter Perps venue
+pub struct JupiterPerpsVenue {
+    config: JupiterPerpsConfig,
+    state: Arc<SharedState>,
+    rpc: Arc<RpcManager>,
+    tx_builder: TransactionBuilder,
+    submitter: TransactionSubmitter,
+    /// Wallet that owns the positions; None for read-only use
+    payer: Option<Keypair>,
+    program_id: Pubkey,
+    pool: Pubkey,
+    custody: Pubkey,
+    collateral_custody: Pubkey,
+}
+
+impl JupiterPerpsVenue {
+    pub fn new(
+        config: &JupiterPerpsConfig,
+        state: Arc<SharedState>,
+        rpc: Arc<RpcManager>,
+        tx_builder: TransactionBuilder,
+        submitter: TransactionSubmitter,
+        payer: Option<Keypair>,
+    ) -> Result<Self> {
+        let pubkey = |key: &str, name: &str| {
+            Pubkey::from_str(key).with_context(|| format!("Invalid jupiter_perps.{}", name))
+        };
+        Ok(Self {
+            program_id: pubkey(&config.program_id, "program_id")?,
+            pool: pubkey(&config.pool, "pool")?,
+            custody: pubkey(&config.custody, "custody")?,
+            collateral_custody: pubkey(&config.collateral_custody, "collateral_custody")?,
+            config: config.clone(),
+            state,
+            rpc,
+            tx_builder,
+            submitter,
+            payer,
+        })
+    }
+
+    fn payer(&self) -> Result<&Keypair> {
+        self.payer.as_ref().context("Jupiter Perps orders need the wallet keypair")
+    }
+
+    /// Custody a position on `side` borrows from: stables for shorts, the asset for longs
+    fn collateral_for(&self, side: Side) -> Pubkey {
+        if side == Side::Short { self.collateral_custody } else { self.custody }
+    }
+
+    /// Mint of the custody backing `side`'s collateral
+    async fn collateral_mint(&self, side: Side) -> Result<Pubkey> {
+        let account = self.rpc.get_multiple_accounts(&[self.collateral_for(side)]).await?
+            .into_iter()
+            .next()
+            .flatten()
+            .context("Jupiter Perps collateral custody not found")?;
+        Ok(Pubkey::new_from_array(decode_custody(&account.data)?.mint))
+    }
+
+    /// Request account for one change to `position`
+    fn position_request_address(&self, position: &Pubkey, counter: u64, change: RequestChange) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"position_request", position.as_ref(), &counter.to_le_bytes(), &[change as u8]],
+            &self.program_id,
+        ).0
+    }
+
+    /// Position account for the wallet on one side
+    fn position_address(&self, owner: &Pubkey, side: Side) -> Pubkey {
+        let side_seed = [side as u8];
+        Pubkey::find_program_address(
+            &[
+                b"position",
+                owner.as_ref(),
+                self.pool.as_ref(),
+                self.custody.as_ref(),
+                self.collateral_for(side).as_ref(),
+                &side_seed,
+            ],
+            &self.program_id,
+        ).0
+    }
+
+    /// Accounts of a market position request. Increases and decreases
+    /// share the layout; `mint` is the token posted or received, moved
+    /// through the owner's and the request's token accounts.
+    fn request_accounts(&self, owner: &Pubkey, side: Side, mint: &Pubkey, counter: u64, change: RequestChange) -> Vec<AccountMeta> {
+        let program = |id: &str| Pubkey::from_str(id).expect("valid program id");
+        let position = self.position_address(owner, side);
+        let request = self.position_request_address(&position, counter, change);
+        let perpetuals = Pubkey::find_program_address(&[b"perpetuals"], &self.program_id).0;
+        let event_authority = Pubkey::find_program_address(&[b"__event_authority"], &self.program_id).0;
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(perpetuals, false),
+            AccountMeta::new_readonly(self.pool, false),
+            AccountMeta::new(position, false),
+            AccountMeta::new(request, false),
+            AccountMeta::new(associated_token_address(&request, mint), false),
+            AccountMeta::new(self.custody, false),
+            AccountMeta::new(self.collateral_for(side), false),
+            AccountMeta::new_readonly(*mint, false),
+            // No referral: Anchor reads the program id as an absent optional account
+            AccountMeta::new_readonly(self.program_id, false),
+            AccountMeta::new_readonly(program(TOKEN_PROGRAM_ID), false),
+            AccountMeta::new_readonly(program(ASSOCIATED_TOKEN_PROGRAM_ID), false),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            AccountMeta::new_readonly(event_authority, false),
+            AccountMeta::new_readonly(self.program_id, false),
+        ]
+    }
+
+    /// Build and sign a position request transaction
+    async fn build_request(&self, payer: &Keypair, accounts: Vec<AccountMeta>, data: Vec<u8>) -> Result<Transaction> {
+        let owner = payer.pubkey();
+        let mut instructions = self.tx_builder.build_priority_fee_ix(self.tx_builder.priority_fee().await?);
+        instructions.push(Instruction { program_id: self.program_id, accounts, data });
+
+        let blockhash = self.rpc.get_recent_blockhash().await?;
+        let message = Message::new(&instructions, Some(&owner));
+        let mut tx = Transaction::new_unsigned(message);
+        tx.partial_sign(&[payer], blockhash);
+        Ok(tx)
+    }
+
+    /// Signed size the keeper filled since the position held `before`,
+    /// read from the position account until it changes or the fill
+    /// timeout passes (0 if it never does)
+    async fn confirm_fill(&self, before: f64) -> f64 {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(self.config.fill_timeout_secs);
+        loop {
+            match self.position().await {
+                Ok(position) => {
+                    let filled = position.map(|p| p.size).unwrap_or(0.0) - before;
+                    if filled.abs() > 1e-9 {
+                        return filled;
+                    }
+                }
+                Err(e) => debug!("Jupiter Perps position read failed: {:#}", e),
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return 0.0;
+            }
+            tokio::time::sleep(Duration::from_millis(KEEPER_POLL_MS)).await;
+        }
+    }
+}
+
+#[async_trait]
+impl PerpVenue for JupiterPerpsVenue {
+    fn name(&self) -> &str {
+        "jupiter_perps"
+    }
+
+    async fn mark_price(&self) -> Result<f64> {
+        // Trades settle at the oracle price
+        let price = self.state.spot_price.load();
+        anyhow::ensure!(price > 0.0, "No oracle price yet");
+        Ok(price)
+    }
+
+    async fn funding(&self) -> Result<VenueFunding> {
+        let account = self.rpc.get_multiple_accounts(&[self.collateral_custody]).await?
+            .into_iter()
+            .next()
+            .flatten()
+            .context("Jupiter Perps collateral custody not found")?;
+        let custody = decode_custody(&account.data)?;
+        Ok(VenueFunding {
+            rate: 0.0,
+            borrow_rate: custody.hourly_borrow_rate(),
+            interval: FundingInterval::Hourly,
+        })
+    }
+
+    async fn place_order(&self, order: &PerpOrder) -> Result<PerpOrderAck> {
+        anyhow::ensure!(
+            order.order_type == OrderType::Market,
+            "Jupiter Perps venue only supports market orders"
+        );
+        // The venue carries the short hedge only, so collateral is always USDC
+        anyhow::ensure!(
+            (order.size_sol < 0.0) != order.reduce_only,
+            "Jupiter Perps venue only opens and reduces shorts"
+        );
+        let payer = self.payer()?;
+        let owner = payer.pubkey();
+        let side = Side::Short;
+        let price = self.mark_price().await?;
+        let size_usd = to_base_units(order.size_sol.abs() * price, JUPITER_USD_DECIMALS)?;
+        let slippage = self.config.market_slippage_pct / 100.0;
+        let counter = chrono::Utc::now().timestamp_millis() as u64;
+        let before = self.position().await?.map(|p| p.size).unwrap_or(0.0);
+
+        // Increasing a short sells, decreasing it buys; the slippage bound is a price
+        let (change, data) = if order.reduce_only {
+            let params = DecreaseRequestParams {
+                collateral_usd_delta: 0,
+                size_usd_delta: size_usd,
+                price_slippage: to_base_units(price * (1.0 + slippage), JUPITER_USD_DECIMALS)?,
+                jupiter_minimum_out: None,
+                entire_position: Some(order.size_sol >= before.abs()),
+                counter,
+            };
+            let mut data = DECREASE_REQUEST_DISCRIMINATOR.to_vec();
+            data.extend(borsh::to_vec(&params)?);
+            (RequestChange::Decrease, data)
+        } else {
+            let collateral_usd = order.size_sol.abs() * price / self.config.leverage;
+            let params = IncreaseRequestParams {
+                size_usd_delta: size_usd,
+                collateral_token_delta: to_base_units(collateral_usd, JUPITER_USD_DECIMALS)?,
+                side,
+                price_slippage: to_base_units(price * (1.0 - slippage), JUPITER_USD_DECIMALS)?,
+                jupiter_minimum_out: None,
+                counter,
+            };
+            let mut data = INCREASE_REQUEST_DISCRIMINATOR.to_vec();
+            data.extend(borsh::to_vec(&params)?);
+            (RequestChange::Increase, data)
+        };
+        let mint = self.collateral_mint(side).await?;
+        let accounts = self.request_accounts(&owner, side, &mint, counter, change);
+
+        let submitted = self.submitter
+            .submit_with_rebuild(|| self.build_request(payer, accounts.clone(), data.clone()))
+            .await?;
+        debug!("Jupiter Perps {:?} request for ${:.2}", change, from_base_units(size_usd, JUPITER_USD_DECIMALS));
+        info!("Jupiter Perps request {} submitted", submitted.signature);
+
+        // Keepers fill market requests at the oracle price, or refund them
+        let filled = self.confirm_fill(before).await;
+        if filled == 0.0 {
+            warn!(
+                "Jupiter Perps request {} not filled by a keeper within {}s",
+                submitted.signature, self.config.fill_timeout_secs
+            );
+        }
+        let price = self.mark_price().await.unwrap_or(price);
+        Ok(PerpOrderAck { order_id: submitted.signature.to_string(), filled, price })
+    }
+
+    async fn cancel_order(&self, _order_id: &str) -> Result<()> {
+        anyhow::bail!("Jupiter Perps market requests are filled or refunded by keepers and cannot be cancelled")
+    }
+
+    async fn order_state(&self, _order_id: &str) -> Result<Option<VenueOrderState>> {
+        // Market requests never rest
+        Ok(None)
+    }
+
+    async fn position(&self) -> Result<Option<VenuePosition>> {
+        let owner = self.payer()?.pubkey();
+        let addresses = [self.position_address(&owner, Side::Short), self.position_address(&owner, Side::Long)];
+        let mark = self.mark_price().await.unwrap_or(0.0);
+
+        for account in self.rpc.get_multiple_accounts(&addresses).await?.into_iter().flatten() {
+            if account.data.len() <= 8 {
+                continue;
+            }
+            let position = PositionAccount::deserialize(&mut &account.data[8..])
+                .context("Failed to decode Jupiter Perps position")?;
+            if position.size_usd == 0 || position.price == 0 {
+                continue;
+            }
+            let entry_price = from_base_units(position.price, JUPITER_USD_DECIMALS);
+            let size_usd = from_base_units(position.size_usd, JUPITER_USD_DECIMALS);
+            let size = size_usd / entry_price;
+            let (size, pnl) = match position.side {
+                Side::Short => (-size, size * (entry_price - mark)),
+                _ => (size, size * (mark - entry_price)),
+            };
+            return Ok(Some(VenuePosition {
+                size,
+                entry_price,
+                unrealized_pnl: if mark > 0.0 { pnl } else { 0.0 },
+            }));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrow_rate_scales_with_utilization() {
+        let custody = Custody {
+            _pool: [0; 32],
+            mint: [0; 32],
+            _token_account: [0; 32],
+            _decimals: 6,
+            _is_stable: true,
+            _oracle: OracleParams { _oracle_account: [0; 32], _oracle_type: 0, _buffer: 0, _max_price_age_sec: 0 },
+            _pricing: PricingParams {
+                _trade_impact_fee_scalar: 0,
+                _buffer: 0,
+                _swap_spread: 0,
+                _max_leverage: 0,
+                _max_global_long_sizes: 0,
+                _max_global_short_sizes: 0,
+            },
+            _permissions: [true; 7],
+            _target_ratio_bps: 0,
+            assets: Assets {
+                _fees_reserves: 0,
+                owned: 1_000,
+                locked: 500,
+                _guaranteed_usd: 0,
+                _global_short_sizes: 0,
+                _global_short_average_prices: 0,
+            },
+            // 0.012% per hour at full utilization
+            funding_rate_state: FundingRateState { _cumulative_interest_rate: 0, _last_update: 0, hourly_funding_dbps: 12 },
+        };
+        assert!((custody.hourly_borrow_rate() - 0.00006).abs() < 1e-12);
+
+        let funding = VenueFunding { rate: 0.0, borrow_rate: custody.hourly_borrow_rate(), interval: FundingInterval::Hourly };
+        assert!((funding.short_apr() + 52.56).abs() < 1e-9);
+    }
+}
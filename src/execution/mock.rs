@@ -0,0 +1,191 @@
+//! Mock Execution
+//!
+//! Fills single-leg trades against `SharedState` prices, for tests:
+//! - Spot fills at the spot price, perp fills at the mark, each moved
+//!   against the trader by the configured slippage
+//! - Taker fees charged as `cost_usd`, like the live path
+//! - Failures injected per leg (the next N orders), to exercise
+//!   reconciliation and rebalance retries
+//! - Every fill is recorded for assertions
+//!
+//! Implements both `TradeExecutor` (rebalancer) and `LegExecutor`
+//! (leg reconciliation).
+
+use anyhow::Result;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use crate::state::SharedState;
+
+use super::reconcile::{LegExecutor, LegFills, LegTrade};
+use super::{LegExecution, SubmissionPath, TradeExecutor};
+
+/// Which leg an order was for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MockLeg {
+    Spot,
+    Perp,
+}
+
+/// A recorded mock fill
+#[derive(Debug, Clone, Serialize)]
+pub struct MockFill {
+    pub leg: MockLeg,
+    /// Signed size filled (SOL)
+    pub filled: f64,
+    pub price: f64,
+    pub reduce_only: bool,
+}
+
+/// Scripted executor for tests
+pub struct MockExecution {
+    state: Arc<SharedState>,
+    /// Adverse slippage applied to every fill (bps)
+    slippage_bps: f64,
+    /// Taker fee charged on every fill (bps)
+    fee_bps: f64,
+    /// Spot orders still to fail
+    fail_spot: AtomicU32,
+    /// Perp orders still to fail
+    fail_perp: AtomicU32,
+    fills: Mutex<Vec<MockFill>>,
+}
+
+impl MockExecution {
+    pub fn new(state: Arc<SharedState>) -> Self {
+        Self {
+            state,
+            slippage_bps: 0.0,
+            fee_bps: 0.0,
+            fail_spot: AtomicU32::new(0),
+            fail_perp: AtomicU32::new(0),
+            fills: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Adverse slippage on every fill
+    pub fn with_slippage_bps(mut self, bps: f64) -> Self {
+        self.slippage_bps = bps;
+        self
+    }
+
+    /// Taker fee on every fill
+    pub fn with_fee_bps(mut self, bps: f64) -> Self {
+        self.fee_bps = bps;
+        self
+    }
+
+    /// Reject the next `n` spot orders
+    pub fn fail_next_spot(&self, n: u32) {
+        self.fail_spot.store(n, Ordering::Relaxed);
+    }
+
+    /// Reject the next `n` perp orders
+    pub fn fail_next_perp(&self, n: u32) {
+        self.fail_perp.store(n, Ordering::Relaxed);
+    }
+
+    /// Fills so far, oldest first
+    pub fn fills(&self) -> Vec<MockFill> {
+        self.fills.lock().clone()
+    }
+
+    /// Net signed size filled on a leg
+    pub fn net_filled(&self, leg: MockLeg) -> f64 {
+        self.fills.lock().iter().filter(|f| f.leg == leg).map(|f| f.filled).sum()
+    }
+
+    fn fill(&self, leg: MockLeg, size_sol: f64, reduce_only: bool) -> Result<LegExecution> {
+        let failures = match leg {
+            MockLeg::Spot => &self.fail_spot,
+            MockLeg::Perp => &self.fail_perp,
+        };
+        if failures.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1)).is_ok() {
+            anyhow::bail!("Mock {:?} order rejected", leg);
+        }
+
+        let market = self.state.snapshot();
+        let reference = match leg {
+            MockLeg::Spot => market.spot_price,
+            MockLeg::Perp => market.perp_mark_price,
+        };
+        anyhow::ensure!(reference > 0.0, "No {:?} price to fill against", leg);
+
+        let slippage = reference * self.slippage_bps / 10_000.0;
+        let price = if size_sol > 0.0 { reference + slippage } else { reference - slippage };
+        let fee = price * size_sol.abs() * self.fee_bps / 10_000.0;
+
+        let mut fills = self.fills.lock();
+        fills.push(MockFill { leg, filled: size_sol, price, reduce_only });
+        Ok(LegExecution {
+            filled: size_sol,
+            price,
+            cost_usd: fee + slippage * size_sol.abs(),
+            fee_usd: fee,
+            slippage_usd: slippage * size_sol.abs(),
+            signature: format!("mock:{:?}:{}", leg, fills.len()).to_lowercase(),
+            path: SubmissionPath::Venue,
+        })
+    }
+}
+
+#[async_trait]
+impl TradeExecutor for MockExecution {
+    async fn trade_spot(&self, size_sol: f64) -> Result<LegExecution> {
+        self.fill(MockLeg::Spot, size_sol, false)
+    }
+
+    async fn trade_perp(&self, size_sol: f64, reduce_only: bool) -> Result<LegExecution> {
+        self.fill(MockLeg::Perp, size_sol, reduce_only)
+    }
+
+    async fn perp_position(&self) -> Result<f64> {
+        Ok(self.net_filled(MockLeg::Perp))
+    }
+}
+
+#[async_trait]
+impl LegExecutor for MockExecution {
+    async fn trade_spot(&self, size: f64) -> Result<LegTrade> {
+        let fill = self.fill(MockLeg::Spot, size, false)?;
+        Ok(LegTrade { filled: fill.filled, price: fill.price, slippage_usd: fill.slippage_usd, fee_usd: fill.fee_usd })
+    }
+
+    async fn trade_perp(&self, size: f64) -> Result<LegTrade> {
+        let fill = self.fill(MockLeg::Perp, size, false)?;
+        Ok(LegTrade { filled: fill.filled, price: fill.price, slippage_usd: fill.slippage_usd, fee_usd: fill.fee_usd })
+    }
+
+    async fn leg_fills(&self) -> Result<LegFills> {
+        Ok(LegFills::new(self.net_filled(MockLeg::Spot), self.net_filled(MockLeg::Perp)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fills_and_injected_failures() {
+        let state = Arc::new(SharedState::new());
+        state.update_spot_price(100.0);
+        state.update_perp_mark_price(100.5);
+        let execution = MockExecution::new(state).with_slippage_bps(10.0);
+
+        let fill = TradeExecutor::trade_spot(&execution, 2.0).await.unwrap();
+        assert!((fill.price - 100.1).abs() < 1e-9);
+        assert!((fill.cost_usd - 0.2).abs() < 1e-9);
+        assert!((fill.slippage_usd - 0.2).abs() < 1e-9);
+
+        execution.fail_next_perp(1);
+        assert!(TradeExecutor::trade_perp(&execution, -2.0, false).await.is_err());
+        let trade = LegExecutor::trade_perp(&execution, -2.0).await.unwrap();
+        assert_eq!(trade.filled, -2.0);
+
+        assert_eq!(execution.fills().len(), 2);
+        assert_eq!(execution.net_filled(MockLeg::Perp), -2.0);
+    }
+}
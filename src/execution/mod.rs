@@ -5,34 +5,129 @@
 //! - Jito bundle integration for MEV protection
 //! - Priority fee management
 //! - Simulation and retry logic
+//! - Wallet and collateral balance queries
+//! - Resting order tracking for passive entry
+//! - Leg reconciliation and the execution journal
+//! - Dry-run (shadow) execution: build and simulate, never submit
+//! - Single-leg spot and perp executions for hedge adjustments
+//! - Optional CEX spot leg, with balances and transfers tracked across venues
+//! - Perp leg behind `PerpVenue` (Drift, Hyperliquid, Jupiter Perps), routed by carry
+//! - Spot swaps behind `SpotVenue`: Jupiter, or an Orca Whirlpool directly for small orders
+//! - Stale swap quotes re-quoted (and slippage re-checked) before signing
+//! - Realized slippage per fill, adapting the spot swap tolerance
+//! - Pre-trade checklist before every live order; failures recorded as skipped trades
+//! - Single-leg trading behind `TradeExecutor`, with a scripted mock for tests
+//! - Optional durable nonce account for close transactions
+//! - Jito-first submission, falling back to a regular priority-fee send
+//! - Gas watchdog on the wallet's SOL for fees and tips
+//! - Spot inventory (treasury): entry funding and idle capital
+//! - Scheduled profit sweeps to a cold wallet
 
 pub mod tx_builder;
 pub mod jupiter;
 pub mod jito;
 pub mod simulator;
 pub mod submitter;
+pub mod balances;
+pub mod orders;
+pub mod reconcile;
+pub mod journal;
+pub mod shadow;
+pub mod cex;
+pub mod transfers;
+pub mod perp_venue;
+pub mod hyperliquid;
+pub mod jupiter_perps;
+pub mod spot_venue;
+pub mod orca;
+pub mod slippage;
+pub mod pretrade;
+pub mod mock;
+pub mod nonce;
+pub mod gas;
+pub mod treasury;
+pub mod sweep;
 
 pub use tx_builder::TransactionBuilder;
 pub use jupiter::JupiterClient;
 pub use jito::JitoClient;
 pub use simulator::TransactionSimulator;
-pub use submitter::TransactionSubmitter;
+pub use submitter::{SubmissionPath, SubmissionResult, TransactionSubmitter};
+pub use balances::{AvailableCapital, BalanceFetcher};
+pub use orders::{OrderFill, OrderStatus, OrderTracker, OrderVenue, TrackedOrder, VenueOrderState};
+pub use reconcile::{LegAction, LegExecutor, LegFills, LegPrices, LegReconciler, LegSlippage, LegTrade, LegVolume, ReconcileOutcome, VenueLegs};
+pub use journal::{ExecutionJournal, JournalEntry, JournalKind};
+pub use shadow::{ShadowExecutor, ShadowReport};
+pub use cex::{BinanceSpot, CexFill, SpotExchange};
+pub use perp_venue::{DriftVenue, PerpOrder, PerpOrderAck, PerpRouter, PerpVenue, VenueFunding, VenuePosition};
+pub use hyperliquid::HyperliquidVenue;
+pub use jupiter_perps::JupiterPerpsVenue;
+pub use spot_venue::{JupiterSpot, SpotRouter, SpotSwap, SpotVenue};
+pub use orca::OrcaWhirlpoolVenue;
+pub use slippage::{SlippageLeg, SlippageStats, SlippageTracker};
+pub use pretrade::{CheckResult, OrderLeg, PreTradeCheck, PreTradeChecker, PreTradeChecklist, PreTradeOrder};
+pub use gas::GasWatchdog;
+pub use treasury::{EntryCurrency, EntryFunding, InventoryReport, Treasury};
+pub use sweep::{ProfitSweeper, SweepRecord};
+pub use mock::{MockExecution, MockFill, MockLeg};
+pub use nonce::NonceManager;
+pub use transfers::{Asset, TransferLedger, TransferOrchestrator, TransferStatus, VenueBalance, WALLET_VENUE};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::Serialize;
+use solana_client::rpc_client::SerializableTransaction;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::VersionedTransaction};
+use std::future::Future;
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::info;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{info, warn};
 
 use crate::config::AppConfig;
-use crate::network::RpcManager;
+use crate::network::{Event, HttpRetryPolicy, RpcManager};
 use crate::state::SharedState;
+use crate::utils::amount::Amount;
+use crate::utils::types::{Dependency, SkipReason};
+use tx_builder::OrderSide;
+
+/// A confirmed single-leg execution
+#[derive(Debug, Clone, Serialize)]
+pub struct LegExecution {
+    /// Signed size filled (SOL; spot buy / perp long positive)
+    pub filled: f64,
+    /// Average fill price (USD per SOL)
+    pub price: f64,
+    /// Fees plus slippage against the reference price (USD)
+    pub cost_usd: f64,
+    /// Venue fees alone (USD)
+    pub fee_usd: f64,
+    /// Measured slippage against the quote or mark (USD, negative when the
+    /// fill improved on it)
+    pub slippage_usd: f64,
+    /// Transaction signature
+    pub signature: String,
+    /// How the order reached the market
+    pub path: SubmissionPath,
+}
+
+/// Places single-leg spot and perp trades
+#[async_trait]
+pub trait TradeExecutor: Send + Sync {
+    /// Buy (positive) or sell (negative) spot SOL
+    async fn trade_spot(&self, size_sol: f64) -> Result<LegExecution>;
+    /// Go long (positive) or short (negative) the perp
+    async fn trade_perp(&self, size_sol: f64, reduce_only: bool) -> Result<LegExecution>;
+    /// Signed perp position on the venue holding the trade's perp leg (SOL)
+    async fn perp_position(&self) -> Result<f64>;
+}
 
 /// Execution manager coordinates all execution components
 pub struct ExecutionManager {
     /// Transaction builder
     pub tx_builder: TransactionBuilder,
-    /// Jupiter client for swaps
-    pub jupiter: JupiterClient,
+    /// Spot swap venues: Jupiter, plus any direct pool for small orders
+    pub spot_router: SpotRouter,
     /// Jito client for bundles
     pub jito: Option<JitoClient>,
     /// Transaction simulator
@@ -41,6 +136,27 @@ pub struct ExecutionManager {
     pub submitter: TransactionSubmitter,
     /// Is execution enabled
     enabled: Arc<RwLock<bool>>,
+    /// Configuration
+    config: Arc<AppConfig>,
+    /// Shared state (reference prices)
+    state: Arc<SharedState>,
+    /// Signing wallet; required for submission
+    payer: Option<Keypair>,
+    /// Exchange filling the spot leg instead of Jupiter, with the transfers
+    /// that fund it
+    cex: Option<Arc<TransferOrchestrator>>,
+    /// Balances across the wallet and exchange
+    ledger: Arc<TransferLedger>,
+    /// Perp venues; without a router, perp orders go to Drift directly
+    perp_router: Option<Arc<PerpRouter>>,
+    /// RPC for the wallet balances a swap is measured against
+    rpc: Arc<RpcManager>,
+    /// Realized slippage and the adaptive spot tolerance
+    slippage: Arc<SlippageTracker>,
+    /// Checklist run before every live order
+    pre_trade: PreTradeChecker,
+    /// Event bus for orders the checklist skipped
+    event_tx: Option<broadcast::Sender<Event>>,
 }
 
 impl ExecutionManager {
@@ -48,30 +164,488 @@ impl ExecutionManager {
     pub async fn new(
         config: Arc<AppConfig>,
         rpc: Arc<RpcManager>,
-        _state: Arc<SharedState>,
+        state: Arc<SharedState>,
     ) -> Result<Self> {
         let tx_builder = TransactionBuilder::new(config.clone(), rpc.clone())?;
-        let jupiter = JupiterClient::new(&config.protocols.jupiter)?;
+        let retry = HttpRetryPolicy::new(&config.http);
+        let jupiter = JupiterClient::new(&config.protocols.jupiter)?
+            .with_retry_policy(retry.clone().for_dependency(Dependency::Jupiter));
         let simulator = TransactionSimulator::new(rpc.clone());
         let submitter = TransactionSubmitter::new(config.clone(), rpc.clone());
         
         // Initialize Jito if enabled
         let jito = if config.execution.use_jito {
-            Some(JitoClient::new(&config.execution)?)
+            Some(JitoClient::new(&config.execution)?.with_retry_policy(retry.for_dependency(Dependency::Jito)))
         } else {
             None
         };
         
+        let pre_trade = PreTradeChecker::new(config.clone(), state.clone())?;
+        let slippage = Arc::new(SlippageTracker::new(
+            &config.trading.adaptive_slippage,
+            config.trading.slippage_tolerance_pct,
+        ));
+        
         Ok(Self {
             tx_builder,
-            jupiter,
+            spot_router: SpotRouter::new(vec![Arc::new(JupiterSpot::new(jupiter))]),
             jito,
             simulator,
             submitter,
             enabled: Arc::new(RwLock::new(!config.paper_trading)),
+            config,
+            state,
+            payer: None,
+            cex: None,
+            ledger: Arc::new(TransferLedger::new()),
+            perp_router: None,
+            rpc,
+            slippage,
+            pre_trade,
+            event_tx: None,
+        })
+    }
+    
+    /// Sign transactions with this wallet
+    pub fn with_payer(mut self, payer: Keypair) -> Self {
+        match BalanceFetcher::new(self.rpc.clone(), &self.config, payer.pubkey()) {
+            Ok(balances) => self.pre_trade = self.pre_trade.with_balances(balances),
+            Err(e) => warn!("Pre-trade balance checks unavailable: {}", e),
+        }
+        self.payer = Some(payer);
+        self
+    }
+    
+    /// Report orders skipped by the pre-trade checklist on the event bus
+    pub fn with_events(mut self, event_tx: broadcast::Sender<Event>) -> Self {
+        self.event_tx = Some(event_tx);
+        self
+    }
+    
+    /// Fill the spot leg on the orchestrator's exchange, tracking balances
+    /// in its ledger
+    pub fn with_cex(mut self, transfers: Arc<TransferOrchestrator>) -> Self {
+        self.ledger = transfers.ledger().clone();
+        self.cex = Some(transfers);
+        self
+    }
+    
+    /// Sign reduce-only orders over a durable nonce
+    pub fn with_nonce(mut self, nonce: Option<Arc<NonceManager>>) -> Self {
+        self.tx_builder = self.tx_builder.with_nonce(nonce.clone());
+        self.submitter = self.submitter.with_nonce(nonce);
+        self
+    }
+    
+    /// Track balances in a ledger shared with the treasury
+    pub fn with_ledger(mut self, ledger: Arc<TransferLedger>) -> Self {
+        self.ledger = ledger;
+        self
+    }
+    
+    /// Swap the spot leg on these venues instead of Jupiter alone
+    pub fn with_spot_venues(mut self, venues: Vec<Arc<dyn SpotVenue>>) -> Self {
+        self.spot_router = SpotRouter::new(venues);
+        self
+    }
+    
+    /// Route perp orders across venues
+    pub fn with_perp_router(mut self, router: Arc<PerpRouter>) -> Self {
+        self.perp_router = Some(router);
+        self
+    }
+    
+    /// Balances across venues
+    pub fn ledger(&self) -> &Arc<TransferLedger> {
+        &self.ledger
+    }
+    
+    /// Realized slippage per leg and size bucket
+    pub fn slippage(&self) -> &Arc<SlippageTracker> {
+        &self.slippage
+    }
+    
+    /// Run the pre-trade checklist for `order`. A failure is reported as a
+    /// skipped trade and stops the order.
+    async fn check_pre_trade(&self, order: PreTradeOrder) -> Result<()> {
+        let venue_balance = self.cex.as_ref().map(|transfers| {
+            let balance = self.ledger.balance(transfers.exchange().name());
+            (balance.sol, balance.usdc)
+        });
+        let checklist = self.pre_trade.run(order, venue_balance).await;
+        if checklist.passed() {
+            return Ok(());
+        }
+        let summary = checklist.summary();
+        if let Some(event_tx) = &self.event_tx {
+            let market = self.state.snapshot();
+            let _ = event_tx.send(Event::SignalSkipped {
+                reason: SkipReason::PreTradeCheck,
+                detail: summary.clone(),
+                size: order.size_sol.abs(),
+                basis_spread: market.basis_spread,
+                funding_apr: market.funding_apr,
+                spot_price: market.spot_price,
+                // Not a signal: kept out of confidence calibration
+                confidence: 0.0,
+                timestamp: checklist.timestamp,
+            });
+        }
+        anyhow::bail!("Pre-trade checklist failed: {}", summary)
+    }
+    
+    /// Wallet SOL and USDC, for measuring a swap; None if either read fails
+    async fn wallet_balances(&self, owner: &Pubkey) -> Option<(f64, f64)> {
+        let mint = Pubkey::from_str(&self.config.protocols.jupiter.usdc_mint).ok()?;
+        let lamports = self.rpc.get_balance(owner).await.ok()?;
+        let usdc = self.rpc.get_token_balance(owner, &mint).await.ok()?;
+        Some((Amount::lamports(lamports).ui(), usdc))
+    }
+    
+    /// Wallet for live submission, if execution is enabled
+    async fn live_payer(&self) -> Result<&Keypair> {
+        anyhow::ensure!(self.is_enabled().await, "Execution is disabled");
+        self.payer.as_ref().context("No wallet configured for execution")
+    }
+    
+    /// Submit the transaction from `build`: as a tipped Jito bundle first
+    /// when Jito is enabled, then, if the bundle fails or does not land in
+    /// `jito_landing_timeout_secs`, as a regular priority-fee send. The
+    /// fallback resends the same signed transaction, so a late-landing
+    /// bundle and the fallback cannot both execute. The tip is capped by
+    /// `expected_profit_usd` when known.
+    async fn submit<T, F, Fut>(&self, payer: &Keypair, expected_profit_usd: Option<f64>, build: F) -> Result<SubmissionResult>
+    where
+        T: SerializableTransaction + Clone + Into<VersionedTransaction> + Sync,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let Some(jito) = &self.jito else {
+            return self.submitter.submit_with_rebuild(build).await;
+        };
+        
+        let tx = build().await?;
+        let signature = *tx.get_signature();
+        match self.submit_bundle(jito, payer, &tx, expected_profit_usd).await {
+            Ok(true) => {
+                info!("Transaction {} landed via Jito bundle", signature);
+                return Ok(SubmissionResult {
+                    signature,
+                    path: SubmissionPath::JitoBundle,
+                    slot: None,
+                    retries: 0,
+                    rebuilds: 0,
+                    confirmation_time_ms: 0,
+                });
+            }
+            Ok(false) => warn!("Jito bundle for {} did not land; falling back to regular send", signature),
+            Err(e) => warn!("Jito bundle for {} failed: {:#}; falling back to regular send", signature, e),
+        }
+        
+        // Same transaction first; rebuilt only once its blockhash has expired
+        let first = parking_lot::Mutex::new(Some(tx));
+        let first = &first;
+        self.submitter
+            .submit_with_rebuild(move || {
+                let ready = first.lock().take();
+                let rebuilt = build();
+                async move {
+                    match ready {
+                        Some(tx) => Ok(tx),
+                        None => rebuilt.await,
+                    }
+                }
+            })
+            .await
+    }
+    
+    /// Send `tx` with a tip transaction as a bundle; whether it landed in time
+    async fn submit_bundle<T>(
+        &self,
+        jito: &JitoClient,
+        payer: &Keypair,
+        tx: &T,
+        expected_profit_usd: Option<f64>,
+    ) -> Result<bool>
+    where
+        T: Clone + Into<VersionedTransaction>,
+    {
+        let tip_lamports = jito.tip_for(expected_profit_usd, self.state.spot_price.load()).await;
+        let tip = self.tx_builder
+            .build_instructions(payer, vec![jito.create_tip_instruction(&payer.pubkey(), tip_lamports)])
+            .await?;
+        let bundle = [tx.clone().into(), VersionedTransaction::from(tip)];
+        let bundle_id = jito.submit_bundle(&bundle).await?;
+        let status = jito.wait_for_bundle(&bundle_id, self.config.execution.jito_landing_timeout_secs).await?;
+        Ok(status == jito::BundleStatus::Landed)
+    }
+    
+    /// Buy (positive) or sell (negative) spot SOL, on the exchange if one
+    /// is configured, otherwise on the routed spot venue
+    pub async fn trade_spot(&self, size_sol: f64) -> Result<LegExecution> {
+        self.trade_spot_with_edge(size_sol, None).await
+    }
+    
+    /// `trade_spot`, capping any Jito tip by the trade's expected profit
+    pub async fn trade_spot_with_edge(&self, size_sol: f64, expected_profit_usd: Option<f64>) -> Result<LegExecution> {
+        let payer = self.live_payer().await?;
+        let reference = self.state.spot_price.load();
+        anyhow::ensure!(reference > 0.0, "No spot price to size the swap");
+        
+        // Jupiter does not route on devnet: fill at the oracle with simulated slippage
+        if self.config.devnet && self.config.devnet_profile.mock_swaps {
+            let slippage = reference * self.config.devnet_profile.mock_swap_slippage_bps / 10_000.0;
+            let price = if size_sol > 0.0 { reference + slippage } else { reference - slippage };
+            self.ledger.apply_fill(WALLET_VENUE, size_sol, -size_sol * price);
+            return Ok(LegExecution {
+                filled: size_sol,
+                price,
+                cost_usd: slippage * size_sol.abs(),
+                fee_usd: 0.0,
+                slippage_usd: slippage * size_sol.abs(),
+                signature: format!("devnet-mock:{}", uuid::Uuid::new_v4()),
+                path: SubmissionPath::Venue,
+            });
+        }
+        
+        if let Some(transfers) = self.cex.as_ref().filter(|_| size_sol > 0.0) {
+            let cost = size_sol * reference * (1.0 + self.config.execution.cex.taker_fee_bps / 10_000.0);
+            self.fund_exchange(payer, transfers, cost).await?;
+        }
+        
+        let order = PreTradeOrder { leg: OrderLeg::Spot, size_sol, adds_exposure: size_sol > 0.0 };
+        self.check_pre_trade(order).await?;
+        
+        if let Some(transfers) = &self.cex {
+            let exchange = transfers.exchange();
+            let fill = exchange.market_order(size_sol).await?;
+            let usdc_delta = -fill.quote_amount.copysign(fill.filled) - fill.fee_usd;
+            self.ledger.apply_fill(exchange.name(), fill.filled, usdc_delta);
+            // A sell's proceeds go back to the wallet; the leg has filled
+            // either way, so a failed withdrawal is left to the operator
+            if fill.filled < 0.0 && usdc_delta > 0.0 {
+                if let Err(e) = transfers.withdraw_to_wallet(Asset::Usdc, usdc_delta).await {
+                    warn!("Withdrawing {:.2} USDC from {} failed: {:#}", usdc_delta, exchange.name(), e);
+                }
+            }
+            let slippage = if size_sol > 0.0 { fill.price - reference } else { reference - fill.price };
+            let slippage_usd = slippage * fill.filled.abs();
+            return Ok(LegExecution {
+                filled: fill.filled,
+                price: fill.price,
+                cost_usd: fill.fee_usd + slippage_usd.max(0.0),
+                fee_usd: fill.fee_usd,
+                slippage_usd,
+                signature: format!("{}:{}", exchange.name(), fill.order_id),
+                path: SubmissionPath::Venue,
+            });
+        }
+        
+        // Direct pool first for small orders, falling back to the aggregator
+        let tolerance_pct = self.slippage.tolerance_pct(size_sol);
+        let slippage_bps = (tolerance_pct * 100.0).round() as u16;
+        let owner = payer.pubkey();
+        let before = self.wallet_balances(&owner).await;
+        let mut quoted = None;
+        for venue in self.spot_router.route(size_sol) {
+            match venue.prepare_swap(&owner, size_sol, reference, slippage_bps).await {
+                Ok(swap) => {
+                    info!("Spot swap of {:.4} SOL routed to {}", size_sol, venue.name());
+                    quoted = Some((venue, swap));
+                    break;
+                }
+                Err(e) => warn!("{} could not quote the spot swap: {:#}", venue.name(), e),
+            }
+        }
+        let (venue, swap) = quoted.context("No spot venue could quote the swap")?;
+        let quoted_price = swap.price(size_sol).context("Swap quote moves no SOL")?;
+        
+        // Each (re)build signs a fresh quote on a fresh blockhash; the
+        // minimum-out guard still holds
+        let current = parking_lot::Mutex::new(swap);
+        let current = &current;
+        let venue = venue.as_ref();
+        let submitted = self
+            .submit(payer, expected_profit_usd, move || async move {
+                let stale = current.lock().clone();
+                let swap = self.fresh_swap(venue, stale, &owner, size_sol, tolerance_pct, quoted_price).await?;
+                let message = swap.message.clone();
+                *current.lock() = swap;
+                self.tx_builder.sign_versioned(payer, message).await
+            })
+            .await?;
+        let swap = current.lock().clone();
+        
+        // Fill at what the wallet actually moved, or the quoted output if it
+        // cannot be measured; the minimum-out guard bounds the error
+        let quoted = swap.amounts(size_sol);
+        anyhow::ensure!(quoted.0 > 0.0, "Swap filled no SOL");
+        let (sol, usdc) = match (before, self.wallet_balances(&owner).await) {
+            (Some(before), Some(after)) => slippage::realized_swap_amounts(size_sol, before, after, quoted),
+            _ => quoted,
+        };
+        let price = usdc / sol;
+        let slipped_bps = self.slippage.record(SlippageLeg::Spot, size_sol, quoted.1 / quoted.0, price);
+        info!("Spot swap on {} slipped {:.1} bps against its quote", venue.name(), slipped_bps);
+        let slippage = if size_sol > 0.0 { price - reference } else { reference - price };
+        self.ledger.apply_fill(WALLET_VENUE, sol.copysign(size_sol), -usdc.copysign(size_sol));
+        
+        Ok(LegExecution {
+            filled: sol.copysign(size_sol),
+            price,
+            cost_usd: (slippage * sol).max(0.0),
+            // Pool fees are priced into the swap
+            fee_usd: 0.0,
+            slippage_usd: slipped_bps / 10_000.0 * quoted.1 / quoted.0 * sol,
+            signature: submitted.signature.to_string(),
+            path: submitted.path,
+        })
+    }
+    
+    /// `swap`, re-quoted on `venue` while older than `max_quote_age_ms`.
+    /// A re-quote more than `tolerance_pct` worse than the quote the trade
+    /// was routed on is refused, as is a quote still stale after
+    /// `max_requotes`.
+    async fn fresh_swap(
+        &self,
+        venue: &dyn SpotVenue,
+        mut swap: SpotSwap,
+        owner: &Pubkey,
+        size_sol: f64,
+        tolerance_pct: f64,
+        quoted_price: f64,
+    ) -> Result<SpotSwap> {
+        let slippage_bps = (tolerance_pct * 100.0).round() as u16;
+        let max_age_ms = self.config.execution.max_quote_age_ms as i64;
+        let mut requotes = 0;
+        loop {
+            let age_ms = swap.age_ms(chrono::Utc::now().timestamp_millis());
+            if age_ms <= max_age_ms {
+                return Ok(swap);
+            }
+            anyhow::ensure!(
+                requotes < self.config.execution.max_requotes,
+                "{} swap quote is {}ms old after {} re-quotes (max {}ms)",
+                venue.name(), age_ms, requotes, max_age_ms
+            );
+            requotes += 1;
+            info!("{} swap quote is {}ms old, re-quoting", venue.name(), age_ms);
+            
+            let reference = self.state.spot_price.load();
+            swap = venue.prepare_swap(owner, size_sol, reference, slippage_bps).await?;
+            let slippage_pct = swap.slippage_vs_pct(size_sol, quoted_price).context("Re-quote moves no SOL")?;
+            anyhow::ensure!(
+                slippage_pct <= tolerance_pct,
+                "Re-quoted swap is {:.3}% worse than the ${:.4} it was routed at (tolerance {:.3}%)",
+                slippage_pct, quoted_price, tolerance_pct
+            );
+        }
+    }
+    
+    /// Go long (positive) or short (negative) the perp with a market order
+    pub async fn trade_perp(&self, size_sol: f64, reduce_only: bool) -> Result<LegExecution> {
+        self.trade_perp_with_edge(size_sol, reduce_only, None).await
+    }
+    
+    /// `trade_perp`, capping any Jito tip by the trade's expected profit
+    pub async fn trade_perp_with_edge(
+        &self,
+        size_sol: f64,
+        reduce_only: bool,
+        expected_profit_usd: Option<f64>,
+    ) -> Result<LegExecution> {
+        let payer = self.live_payer().await?;
+        let order = PreTradeOrder { leg: OrderLeg::Perp, size_sol, adds_exposure: !reduce_only };
+        self.check_pre_trade(order).await?;
+        
+        if let Some(router) = &self.perp_router {
+            let mark = self.state.perp_mark_price.load();
+            let (venue, ack) = router.place_order(&PerpOrder::market(size_sol, reduce_only)).await?;
+            let slippage_usd = if mark > 0.0 && ack.price > 0.0 {
+                let bps = self.slippage.record(SlippageLeg::Perp, size_sol, mark, ack.price);
+                bps / 10_000.0 * mark * ack.filled.abs()
+            } else {
+                0.0
+            };
+            let fee_usd = ack.price * ack.filled.abs() * self.config.execution.perp_taker_fee_bps / 10_000.0;
+            return Ok(LegExecution {
+                filled: ack.filled,
+                price: ack.price,
+                cost_usd: fee_usd,
+                fee_usd,
+                slippage_usd,
+                signature: format!("{}:{}", venue, ack.order_id),
+                path: SubmissionPath::Venue,
+            });
+        }
+        let side = if size_sol > 0.0 { OrderSide::Long } else { OrderSide::Short };
+        let base = Amount::drift_base(size_sol)?.base_units()?;
+        
+        let submitted = self
+            .submit(payer, expected_profit_usd, || self.tx_builder.build_perp_order(payer, side, base, reduce_only))
+            .await?;
+        
+        // Recorded at the mark; a direct order's fill price is not read back
+        let price = self.state.perp_mark_price.load();
+        let fee_usd = price * size_sol.abs() * self.config.execution.perp_taker_fee_bps / 10_000.0;
+        Ok(LegExecution {
+            filled: size_sol,
+            price,
+            cost_usd: fee_usd,
+            fee_usd,
+            slippage_usd: 0.0,
+            signature: submitted.signature.to_string(),
+            path: submitted.path,
         })
     }
     
+    /// Make sure the exchange holds `cost_usdc` for a spot buy. A shortfall
+    /// not already on its way is deposited from the wallet; either way the
+    /// buy fails until the exchange credits the deposit.
+    async fn fund_exchange(&self, payer: &Keypair, transfers: &TransferOrchestrator, cost_usdc: f64) -> Result<()> {
+        let exchange = transfers.exchange();
+        let held = self.ledger.balance(exchange.name()).usdc;
+        if held >= cost_usdc {
+            return Ok(());
+        }
+        let pending = transfers.pending_deposits(Asset::Usdc);
+        let shortfall = cost_usdc - held - pending;
+        if shortfall > 0.0 {
+            let address = exchange.deposit_address(Asset::Usdc).await?;
+            let destination = Pubkey::from_str(&address)
+                .with_context(|| format!("Invalid {} deposit address {}", exchange.name(), address))?;
+            let submitted = self.send_usdc(payer, &destination, shortfall).await?;
+            transfers.track_deposit(Asset::Usdc, shortfall, &submitted.signature.to_string());
+        }
+        anyhow::bail!(
+            "{} holds {:.2} USDC of {:.2}; waiting for {:.2} USDC in deposits",
+            exchange.name(),
+            held,
+            cost_usdc,
+            pending + shortfall.max(0.0)
+        )
+    }
+    
+    /// Transfer USDC from the wallet to `destination` and wait for it to confirm
+    async fn send_usdc(&self, payer: &Keypair, destination: &Pubkey, amount_usdc: f64) -> Result<SubmissionResult> {
+        anyhow::ensure!(amount_usdc > 0.0, "Transfer amount must be positive");
+        let units = Amount::usdc(amount_usdc)?.base_units()?;
+        let submitted = self.submitter
+            .submit_with_rebuild(|| self.tx_builder.build_usdc_transfer(payer, destination, units))
+            .await?;
+        info!("Transferred {:.2} USDC to {} ({})", amount_usdc, destination, submitted.signature);
+        Ok(submitted)
+    }
+    
+    /// Transfer USDC from the wallet to `destination` and wait for it to
+    /// confirm; the ledger's wallet balance drops by the amount
+    pub async fn transfer_usdc(&self, destination: &Pubkey, amount_usdc: f64) -> Result<SubmissionResult> {
+        let payer = self.live_payer().await?;
+        let submitted = self.send_usdc(payer, destination, amount_usdc).await?;
+        self.ledger.apply_fill(WALLET_VENUE, 0.0, -amount_usdc);
+        Ok(submitted)
+    }
+    
     /// Check if execution is enabled
     pub async fn is_enabled(&self) -> bool {
         *self.enabled.read().await
@@ -94,3 +668,40 @@ impl ExecutionManager {
         self.jito.is_some()
     }
 }
+
+#[async_trait]
+impl TradeExecutor for ExecutionManager {
+    async fn trade_spot(&self, size_sol: f64) -> Result<LegExecution> {
+        ExecutionManager::trade_spot(self, size_sol).await
+    }
+
+    async fn trade_perp(&self, size_sol: f64, reduce_only: bool) -> Result<LegExecution> {
+        ExecutionManager::trade_perp(self, size_sol, reduce_only).await
+    }
+
+    async fn perp_position(&self) -> Result<f64> {
+        let router = self.perp_router.as_ref().context("Reading the perp position needs the perp venues")?;
+        router.active_position().await
+    }
+}
+
+#[async_trait]
+impl OrderVenue for ExecutionManager {
+    async fn place(&self, order: &PerpOrder) -> Result<(String, PerpOrderAck)> {
+        self.live_payer().await?;
+        let check = PreTradeOrder { leg: OrderLeg::Perp, size_sol: order.size_sol, adds_exposure: !order.reduce_only };
+        self.check_pre_trade(check).await?;
+        let router = self.perp_router.as_ref().context("Resting perp orders need the perp router")?;
+        router.place_order(order).await
+    }
+
+    async fn cancel(&self, venue: &str, order_id: &str) -> Result<()> {
+        let router = self.perp_router.as_ref().context("Resting perp orders need the perp router")?;
+        router.cancel_order(venue, order_id).await
+    }
+
+    async fn order_state(&self, venue: &str, order_id: &str) -> Result<Option<VenueOrderState>> {
+        let router = self.perp_router.as_ref().context("Resting perp orders need the perp router")?;
+        router.order_state(venue, order_id).await
+    }
+}
@@ -0,0 +1,168 @@
+//! Durable Nonce Accounts
+//!
+//! A transaction signed over a nonce account's stored value (instead of a
+//! recent blockhash) stays valid until the nonce is advanced, so critical
+//! close transactions cannot expire on a slow network:
+//! - The nonce account keypair is loaded from disk, or generated and the
+//!   account created on chain when missing
+//! - Durable transactions start with an advance instruction, so landing one
+//!   moves the nonce on and it cannot be replayed
+//! - An abandoned durable transaction is invalidated by advancing the nonce
+
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    nonce::state::{State, Versions},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::Transaction,
+};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::config::DurableNonceConfig;
+use crate::network::RpcManager;
+use crate::utils::helpers::load_keypair;
+
+/// Nonce account owned and advanced by the trading wallet
+pub struct NonceManager {
+    rpc: Arc<RpcManager>,
+    /// Nonce account
+    account: Keypair,
+    /// Nonce authority (the trading wallet)
+    authority: Keypair,
+    /// Nonce value most recently handed out for signing
+    issued: Mutex<Option<Hash>>,
+}
+
+impl NonceManager {
+    pub fn new(rpc: Arc<RpcManager>, account: Keypair, authority: Keypair) -> Self {
+        Self { rpc, account, authority, issued: Mutex::new(None) }
+    }
+
+    /// Load the nonce account keypair (generating it if allowed) and make
+    /// sure the account exists on chain
+    pub async fn from_config(config: &DurableNonceConfig, rpc: Arc<RpcManager>, authority: Keypair) -> Result<Self> {
+        let path = Path::new(&config.account_keypair_path);
+        let account = if path.exists() {
+            load_keypair(path)?
+        } else {
+            anyhow::ensure!(config.create_if_missing, "Nonce account keypair {:?} not found", path);
+            let account = Keypair::new();
+            std::fs::write(path, serde_json::to_string(&account.to_bytes().to_vec())?)
+                .with_context(|| format!("Failed to write nonce account keypair: {:?}", path))?;
+            info!("Generated nonce account keypair {} at {:?}", account.pubkey(), path);
+            account
+        };
+
+        let manager = Self::new(rpc, account, authority);
+        manager.ensure_account(config.create_if_missing).await?;
+        Ok(manager)
+    }
+
+    /// Nonce account address
+    pub fn address(&self) -> Pubkey {
+        self.account.pubkey()
+    }
+
+    /// Create the nonce account if it does not exist yet
+    async fn ensure_account(&self, create: bool) -> Result<()> {
+        let exists = self.rpc.get_multiple_accounts(&[self.address()]).await?
+            .into_iter()
+            .next()
+            .flatten()
+            .is_some();
+        if exists {
+            info!("Using nonce account {}", self.address());
+            return Ok(());
+        }
+        anyhow::ensure!(create, "Nonce account {} does not exist", self.address());
+
+        let lamports = self.rpc.get_minimum_balance_for_rent_exemption(State::size()).await?;
+        let authority = self.authority.pubkey();
+        let instructions = system_instruction::create_nonce_account(&authority, &self.address(), &authority, lamports);
+        let blockhash = self.rpc.get_recent_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&authority),
+            &[&self.authority, &self.account],
+            blockhash,
+        );
+        let signature = self.rpc.send_transaction(&tx).await.context("Failed to create nonce account")?;
+        info!("Created nonce account {} ({} lamports): {}", self.address(), lamports, signature);
+        Ok(())
+    }
+
+    /// Current nonce value, to sign a durable transaction over
+    pub async fn fetch_nonce(&self) -> Result<Hash> {
+        let account = self.rpc.get_multiple_accounts(&[self.address()]).await?
+            .into_iter()
+            .next()
+            .flatten()
+            .with_context(|| format!("Nonce account {} not found", self.address()))?;
+        let nonce = nonce_from_account_data(&account.data)?;
+        *self.issued.lock() = Some(nonce);
+        Ok(nonce)
+    }
+
+    /// Instruction advancing the nonce; must come first in a durable transaction
+    pub fn advance_ix(&self) -> Instruction {
+        system_instruction::advance_nonce_account(&self.address(), &self.authority.pubkey())
+    }
+
+    /// Whether a transaction's blockhash is a nonce from this account
+    pub fn is_durable(&self, blockhash: &Hash) -> bool {
+        *self.issued.lock() == Some(*blockhash)
+    }
+
+    /// Advance the nonce so an unconfirmed durable transaction can never land
+    pub async fn invalidate(&self) -> Result<()> {
+        let authority = self.authority.pubkey();
+        let blockhash = self.rpc.get_recent_blockhash().await?;
+        let message = Message::new(&[self.advance_ix()], Some(&authority));
+        let tx = Transaction::new(&[&self.authority], message, blockhash);
+        match self.rpc.send_transaction(&tx).await {
+            Ok(signature) => {
+                info!("Advanced nonce {} to invalidate pending transactions: {}", self.address(), signature);
+                *self.issued.lock() = None;
+                Ok(())
+            }
+            Err(e) => {
+                warn!("Failed to advance nonce {}: {}", self.address(), e);
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Stored nonce value of a nonce account
+fn nonce_from_account_data(data: &[u8]) -> Result<Hash> {
+    let versions: Versions = bincode::deserialize(data).context("Invalid nonce account data")?;
+    match versions.state() {
+        State::Initialized(data) => Ok(data.blockhash()),
+        State::Uninitialized => anyhow::bail!("Nonce account is not initialized"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::nonce::state::{Data, DurableNonce};
+
+    #[test]
+    fn test_nonce_from_account_data() {
+        let durable = DurableNonce::from_blockhash(&Hash::new_unique());
+        let data = Data::new(Pubkey::new_unique(), durable, 5000);
+        let bytes = bincode::serialize(&Versions::new(State::Initialized(data.clone()))).unwrap();
+        assert_eq!(nonce_from_account_data(&bytes).unwrap(), data.blockhash());
+
+        let bytes = bincode::serialize(&Versions::new(State::Uninitialized)).unwrap();
+        assert!(nonce_from_account_data(&bytes).is_err());
+    }
+}
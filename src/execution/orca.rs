@@ -0,0 +1,324 @@
+//! Orca Whirlpool Venue
+//!
+//! `SpotVenue` swapping directly against one configured SOL/USDC Whirlpool,
+//! for orders under `protocols.orca.max_size_sol`:
+//! - The pool account is read over RPC and the output estimated from its
+//!   liquidity and price, as if the swap stays in the current tick range;
+//!   small orders do, and the minimum-out guard covers the rest
+//! - Quotes moving the price by more than the slippage tolerance are
+//!   refused, so the router falls back to Jupiter
+//! - SOL is wrapped into the wallet's wSOL account for sells and unwrapped
+//!   after every swap
+//!
+//! Tick arrays and the oracle are derived from the pool; nothing is asked
+//! of an off-chain API.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use borsh::{BorshDeserialize, BorshSerialize};
+use sha2::{Digest, Sha256};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    system_instruction, system_program,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::debug;
+
+use super::spot_venue::{SpotSwap, SpotVenue};
+use super::tx_builder::TransactionBuilder;
+use crate::config::{JupiterConfig, OrcaConfig};
+use crate::network::RpcManager;
+use crate::utils::amount::{Amount, SOL_DECIMALS};
+use crate::utils::helpers::{associated_token_address, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+/// Ticks per tick array
+const TICK_ARRAY_SIZE: i32 = 88;
+/// Sqrt price bounds (Q64.64), used as "no limit"
+const MIN_SQRT_PRICE: u128 = 4_295_048_016;
+const MAX_SQRT_PRICE: u128 = 79_226_673_515_401_279_992_447_579_055;
+/// Fee rates are in millionths
+const FEE_RATE_DENOMINATOR: f64 = 1_000_000.0;
+/// SPL token instruction tags
+const CLOSE_ACCOUNT_TAG: u8 = 9;
+const SYNC_NATIVE_TAG: u8 = 17;
+/// Wrapped SOL mint; the venue wraps and unwraps it around swaps
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+/// Associated token account instruction: create idempotent
+const CREATE_IDEMPOTENT_TAG: u8 = 1;
+
+/// Anchor discriminator: first 8 bytes of sha256("namespace:name")
+fn discriminator(preimage: &str) -> [u8; 8] {
+    let hash = Sha256::digest(preimage.as_bytes());
+    let mut out = [0u8; 8];
+    out.copy_from_slice(&hash[..8]);
+    out
+}
+
+/// Leading fields of the Whirlpool account, up to the token vaults
+#[derive(Debug, BorshDeserialize)]
+struct Whirlpool {
+    _whirlpools_config: [u8; 32],
+    _whirlpool_bump: [u8; 1],
+    tick_spacing: u16,
+    _tick_spacing_seed: [u8; 2],
+    fee_rate: u16,
+    _protocol_fee_rate: u16,
+    liquidity: u128,
+    sqrt_price: u128,
+    tick_current_index: i32,
+    _protocol_fee_owed_a: u64,
+    _protocol_fee_owed_b: u64,
+    token_mint_a: [u8; 32],
+    token_vault_a: [u8; 32],
+    _fee_growth_global_a: u128,
+    token_mint_b: [u8; 32],
+    token_vault_b: [u8; 32],
+}
+
+fn decode_whirlpool(data: &[u8]) -> Result<Whirlpool> {
+    anyhow::ensure!(
+        data.len() > 8 && data[..8] == discriminator("account:Whirlpool"),
+        "Account is not an Orca Whirlpool"
+    );
+    Whirlpool::deserialize(&mut &data[8..]).context("Failed to decode Orca Whirlpool")
+}
+
+#[derive(Debug, BorshSerialize)]
+struct SwapParams {
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+}
+
+/// Output of swapping `amount_in` (base units) within the current tick range,
+/// and the price move it causes (fraction). Token A is SOL, B is USDC.
+fn estimate_output(liquidity: u128, sqrt_price: u128, fee_rate: u16, amount_in: u64, a_to_b: bool) -> Result<(u64, f64)> {
+    anyhow::ensure!(liquidity > 0, "Whirlpool has no liquidity in range");
+    let l = liquidity as f64;
+    let s = sqrt_price as f64 / 2f64.powi(64);
+    let amount = amount_in as f64 * (1.0 - fee_rate as f64 / FEE_RATE_DENOMINATOR);
+    let (out, next) = if a_to_b {
+        let next = l * s / (l + amount * s);
+        (l * (s - next), next)
+    } else {
+        let next = s + amount / l;
+        (l * (1.0 / s - 1.0 / next), next)
+    };
+    Ok((out.max(0.0).floor() as u64, (next * next / (s * s) - 1.0).abs()))
+}
+
+/// First tick of the tick array holding `tick`, `offset` arrays along
+fn tick_array_start(tick: i32, tick_spacing: u16, offset: i32) -> i32 {
+    let ticks_per_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    (tick.div_euclid(ticks_per_array) + offset) * ticks_per_array
+}
+
+/// Direct swaps against one SOL/USDC Whirlpool
+pub struct OrcaWhirlpoolVenue {
+    config: OrcaConfig,
+    rpc: Arc<RpcManager>,
+    tx_builder: TransactionBuilder,
+    program_id: Pubkey,
+    pool: Pubkey,
+    sol_mint: Pubkey,
+    usdc_mint: Pubkey,
+}
+
+impl OrcaWhirlpoolVenue {
+    pub fn new(config: &OrcaConfig, jupiter: &JupiterConfig, rpc: Arc<RpcManager>, tx_builder: TransactionBuilder) -> Result<Self> {
+        let pubkey = |key: &str, name: &str| Pubkey::from_str(key).with_context(|| format!("Invalid {}", name));
+        anyhow::ensure!(jupiter.sol_mint == WSOL_MINT, "The Orca venue only trades SOL");
+        Ok(Self {
+            program_id: pubkey(&config.program_id, "orca.program_id")?,
+            pool: pubkey(&config.pool, "orca.pool")?,
+            sol_mint: pubkey(&jupiter.sol_mint, "SOL mint")?,
+            usdc_mint: pubkey(&jupiter.usdc_mint, "USDC mint")?,
+            config: config.clone(),
+            rpc,
+            tx_builder,
+        })
+    }
+
+    fn tick_array(&self, start: i32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"tick_array", self.pool.as_ref(), start.to_string().as_bytes()],
+            &self.program_id,
+        ).0
+    }
+
+    fn oracle(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"oracle", self.pool.as_ref()], &self.program_id).0
+    }
+
+    async fn whirlpool(&self) -> Result<Whirlpool> {
+        let account = self.rpc.get_multiple_accounts(&[self.pool]).await?
+            .into_iter()
+            .next()
+            .flatten()
+            .context("Orca Whirlpool not found")?;
+        let pool = decode_whirlpool(&account.data)?;
+        anyhow::ensure!(
+            pool.token_mint_a == self.sol_mint.to_bytes() && pool.token_mint_b == self.usdc_mint.to_bytes(),
+            "Orca pool {} is not SOL/USDC",
+            self.pool
+        );
+        Ok(pool)
+    }
+
+    fn create_ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Result<Instruction> {
+        Ok(Instruction {
+            program_id: Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID)?,
+            accounts: vec![
+                AccountMeta::new(*owner, true),
+                AccountMeta::new(associated_token_address(owner, mint), false),
+                AccountMeta::new_readonly(*owner, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+            data: vec![CREATE_IDEMPOTENT_TAG],
+        })
+    }
+}
+
+#[async_trait]
+impl SpotVenue for OrcaWhirlpoolVenue {
+    fn name(&self) -> &str {
+        "orca"
+    }
+
+    fn max_size_sol(&self) -> Option<f64> {
+        Some(self.config.max_size_sol)
+    }
+
+    async fn prepare_swap(&self, owner: &Pubkey, size_sol: f64, reference: f64, slippage_bps: u16) -> Result<SpotSwap> {
+        let pool = self.whirlpool().await?;
+        let quoted_at = chrono::Utc::now().timestamp_millis();
+        // Buying SOL pays USDC (B -> A); selling pays SOL (A -> B)
+        let a_to_b = size_sol < 0.0;
+        let amount_in = if a_to_b {
+            Amount::sol(-size_sol)?.base_units()?
+        } else {
+            Amount::usdc(size_sol * reference)?.base_units()?
+        };
+        let (output_amount, impact) = estimate_output(pool.liquidity, pool.sqrt_price, pool.fee_rate, amount_in, a_to_b)?;
+        let tolerance = slippage_bps as f64 / 10_000.0;
+        anyhow::ensure!(
+            impact <= tolerance,
+            "Orca swap would move the price {:.3}%, over the {:.3}% tolerance",
+            impact * 100.0,
+            tolerance * 100.0
+        );
+        let min_output_amount = (output_amount as f64 * (1.0 - tolerance)).floor() as u64;
+
+        let token_program = Pubkey::from_str(TOKEN_PROGRAM_ID)?;
+        let wsol = associated_token_address(owner, &self.sol_mint);
+        let usdc = associated_token_address(owner, &self.usdc_mint);
+        let step = if a_to_b { -1 } else { 1 };
+        let tick_arrays: Vec<Pubkey> = (0..3)
+            .map(|i| self.tick_array(tick_array_start(pool.tick_current_index, pool.tick_spacing, i * step)))
+            .collect();
+
+        let mut instructions = self.tx_builder.build_priority_fee_ix(self.tx_builder.priority_fee().await?);
+        instructions.push(Self::create_ata(owner, &self.sol_mint, &token_program)?);
+        instructions.push(Self::create_ata(owner, &self.usdc_mint, &token_program)?);
+        if a_to_b {
+            // Wrap the SOL being sold
+            instructions.push(system_instruction::transfer(owner, &wsol, amount_in));
+            instructions.push(Instruction {
+                program_id: token_program,
+                accounts: vec![AccountMeta::new(wsol, false)],
+                data: vec![SYNC_NATIVE_TAG],
+            });
+        }
+        let params = SwapParams {
+            amount: amount_in,
+            other_amount_threshold: min_output_amount,
+            sqrt_price_limit: if a_to_b { MIN_SQRT_PRICE } else { MAX_SQRT_PRICE },
+            amount_specified_is_input: true,
+            a_to_b,
+        };
+        let mut data = discriminator("global:swap").to_vec();
+        data.extend(borsh::to_vec(&params)?);
+        instructions.push(Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(token_program, false),
+                AccountMeta::new_readonly(*owner, true),
+                AccountMeta::new(self.pool, false),
+                AccountMeta::new(wsol, false),
+                AccountMeta::new(Pubkey::new_from_array(pool.token_vault_a), false),
+                AccountMeta::new(usdc, false),
+                AccountMeta::new(Pubkey::new_from_array(pool.token_vault_b), false),
+                AccountMeta::new(tick_arrays[0], false),
+                AccountMeta::new(tick_arrays[1], false),
+                AccountMeta::new(tick_arrays[2], false),
+                AccountMeta::new(self.oracle(), false),
+            ],
+            data,
+        });
+        // Unwrap: wSOL back to native SOL
+        instructions.push(Instruction {
+            program_id: token_program,
+            accounts: vec![
+                AccountMeta::new(wsol, false),
+                AccountMeta::new(*owner, false),
+                AccountMeta::new_readonly(*owner, true),
+            ],
+            data: vec![CLOSE_ACCOUNT_TAG],
+        });
+
+        debug!(
+            "Orca swap: {} in, {} out (min {}), {:.4}% impact",
+            amount_in, output_amount, min_output_amount, impact * 100.0
+        );
+        Ok(SpotSwap {
+            message: VersionedMessage::Legacy(Message::new(&instructions, Some(owner))),
+            input_amount: amount_in,
+            output_amount,
+            min_output_amount,
+            quoted_at,
+            spot_decimals: SOL_DECIMALS,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_output_in_range() {
+        // SOL at $150: sqrt of 150 USDC units per 1000 lamports, in Q64.64
+        let sqrt_price = ((150.0f64 * 1e6 / 1e9).sqrt() * 2f64.powi(64)) as u128;
+        let liquidity = 10u128.pow(15);
+        let fee_rate = 400; // 0.04%
+
+        // Sell 1 SOL
+        let (out, impact) = estimate_output(liquidity, sqrt_price, fee_rate, 1_000_000_000, true).unwrap();
+        let usdc = out as f64 / 1e6;
+        assert!(usdc < 150.0 * (1.0 - 0.0004) && usdc > 149.9);
+        assert!(impact > 0.0 && impact < 0.0001);
+
+        // Buy with 150 USDC
+        let (out, _) = estimate_output(liquidity, sqrt_price, fee_rate, 150_000_000, false).unwrap();
+        let sol = out as f64 / 1e9;
+        assert!(sol < 1.0 && sol > 0.999);
+
+        assert!(estimate_output(0, sqrt_price, fee_rate, 1, true).is_err());
+    }
+
+    #[test]
+    fn test_tick_array_start() {
+        // Spacing 4: arrays span 352 ticks
+        assert_eq!(tick_array_start(100, 4, 0), 0);
+        assert_eq!(tick_array_start(-1, 4, 0), -352);
+        assert_eq!(tick_array_start(-1, 4, -1), -704);
+        assert_eq!(tick_array_start(352, 4, 1), 704);
+    }
+}
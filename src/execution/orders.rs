@@ -0,0 +1,491 @@
+//! Order Tracking
+//!
+//! Tracks resting perp orders from placement to fill, cancellation or
+//! expiry. Used by passive entry, where a post-only limit order must fill
+//! before the spot leg is executed.
+//!
+//! With an `OrderVenue` attached (live trading), orders are submitted to
+//! the perp venue and the tracker follows them there:
+//! - Cancel and replace (cancel, then place the remaining size at a new
+//!   price) go to the venue first
+//! - `sync` reconciles each live order with the venue's order state (the
+//!   Drift user account's order slots), recording new fills and closing
+//!   orders the venue has filled, cancelled or dropped
+//!
+//! Every fill, paper or live, is queued as an `OrderFill` until
+//! `take_fills`, for the position manager to apply.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::perp_venue::{PerpOrder, PerpOrderAck};
+use super::tx_builder::{OrderSide, OrderType};
+
+/// Order lifecycle status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum OrderStatus {
+    Open,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Expired,
+}
+
+impl OrderStatus {
+    /// Whether the order can still fill
+    pub fn is_live(&self) -> bool {
+        matches!(self, OrderStatus::Open | OrderStatus::PartiallyFilled)
+    }
+}
+
+/// A tracked perp order
+#[derive(Debug, Clone, Serialize)]
+pub struct TrackedOrder {
+    pub id: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    /// Limit price (None for market orders)
+    pub price: Option<f64>,
+    /// Order size (SOL)
+    pub size: f64,
+    /// Filled size (SOL)
+    pub filled: f64,
+    pub post_only: bool,
+    pub status: OrderStatus,
+    /// Placement time (ms)
+    pub placed_at: i64,
+    /// Expiry time (ms)
+    pub expires_at: i64,
+    /// Venue holding the order; None for paper orders
+    pub venue: Option<String>,
+    /// The venue's id for the order
+    pub venue_order_id: Option<String>,
+    /// Average price of the fills so far
+    pub avg_fill_price: Option<f64>,
+    /// Order this one replaced
+    pub replaces: Option<String>,
+}
+
+impl TrackedOrder {
+    /// Size still resting on the book
+    pub fn remaining(&self) -> f64 {
+        (self.size - self.filled).max(0.0)
+    }
+
+    /// Signed size (SOL; long positive)
+    pub fn signed(&self, size: f64) -> f64 {
+        match self.side {
+            OrderSide::Long => size,
+            OrderSide::Short => -size,
+        }
+    }
+
+    /// Whether the market has traded through a resting limit price:
+    /// a sell fills once the best bid reaches it, a buy once the best ask does
+    pub fn is_crossed(&self, best_bid: f64, best_ask: f64) -> bool {
+        let Some(price) = self.price else {
+            return true;
+        };
+        match self.side {
+            OrderSide::Short => best_bid > 0.0 && best_bid >= price,
+            OrderSide::Long => best_ask > 0.0 && best_ask <= price,
+        }
+    }
+}
+
+/// Post-only limit price sitting `offset_bps` behind the same-side touch
+pub fn passive_limit_price(side: OrderSide, best_bid: f64, best_ask: f64, offset_bps: f64) -> f64 {
+    let offset = offset_bps / 10_000.0;
+    match side {
+        OrderSide::Short => best_ask * (1.0 + offset),
+        OrderSide::Long => best_bid * (1.0 - offset),
+    }
+}
+
+/// A fill on a tracked order
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderFill {
+    pub order_id: String,
+    pub side: OrderSide,
+    /// Size filled (SOL)
+    pub size: f64,
+    pub price: f64,
+    /// Fill time (ms)
+    pub timestamp: i64,
+}
+
+impl OrderFill {
+    /// Signed size (SOL; long positive)
+    pub fn signed_size(&self) -> f64 {
+        match self.side {
+            OrderSide::Long => self.size,
+            OrderSide::Short => -self.size,
+        }
+    }
+}
+
+/// An order as the venue reports it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct VenueOrderState {
+    /// Size filled so far (SOL)
+    pub filled: f64,
+    /// Average fill price, if any has filled
+    pub avg_price: Option<f64>,
+    pub status: OrderStatus,
+}
+
+/// Where tracked orders are placed, cancelled and looked up
+#[async_trait]
+pub trait OrderVenue: Send + Sync {
+    /// Place an order; returns the venue holding it and its acknowledgement
+    async fn place(&self, order: &PerpOrder) -> Result<(String, PerpOrderAck)>;
+
+    /// Cancel a resting order
+    async fn cancel(&self, venue: &str, order_id: &str) -> Result<()>;
+
+    /// The order's state on the venue; None once the venue no longer holds it
+    async fn order_state(&self, venue: &str, order_id: &str) -> Result<Option<VenueOrderState>>;
+}
+
+/// Tracks orders by id
+#[derive(Default)]
+pub struct OrderTracker {
+    orders: DashMap<String, TrackedOrder>,
+    /// Fills not yet taken by the position manager
+    fills: Mutex<Vec<OrderFill>>,
+    /// Live venue; None tracks paper orders only
+    venue: Option<Arc<dyn OrderVenue>>,
+}
+
+impl OrderTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submit orders to `venue` and follow them there
+    pub fn with_venue(mut self, venue: Arc<dyn OrderVenue>) -> Self {
+        self.venue = Some(venue);
+        self
+    }
+
+    /// Whether orders go to a live venue
+    pub fn has_venue(&self) -> bool {
+        self.venue.is_some()
+    }
+
+    /// Place a limit order: on the venue when live, then tracked
+    pub async fn submit(&self, side: OrderSide, price: f64, size: f64, post_only: bool, timeout_ms: i64) -> Result<TrackedOrder> {
+        let Some(venue) = &self.venue else {
+            return Ok(self.place(side, OrderType::Limit, Some(price), size, post_only, timeout_ms));
+        };
+        let order = PerpOrder {
+            size_sol: if side == OrderSide::Long { size } else { -size },
+            order_type: OrderType::Limit,
+            limit_price: Some(price),
+            reduce_only: false,
+            post_only,
+        };
+        let (venue_name, ack) = venue.place(&order).await?;
+        let tracked = self.place(side, OrderType::Limit, Some(price), size, post_only, timeout_ms);
+        if let Some(mut entry) = self.orders.get_mut(&tracked.id) {
+            entry.venue = Some(venue_name);
+            entry.venue_order_id = Some(ack.order_id);
+        }
+        if ack.filled != 0.0 {
+            self.record_fill(&tracked.id, ack.filled.abs(), ack.price);
+        }
+        self.get(&tracked.id).context("Order not tracked")
+    }
+
+    /// Cancel an order, on its venue first
+    pub async fn cancel_order(&self, id: &str) -> Result<Option<TrackedOrder>> {
+        let Some(order) = self.get(id) else {
+            return Ok(None);
+        };
+        if let (Some(venue), Some(name), Some(venue_id)) = (&self.venue, &order.venue, &order.venue_order_id) {
+            if order.status.is_live() {
+                venue.cancel(name, venue_id).await?;
+            }
+        }
+        Ok(self.cancel(id))
+    }
+
+    /// Cancel a live order and place its remaining size at `price`, keeping
+    /// its expiry. Returns the new order.
+    pub async fn replace_order(&self, id: &str, price: f64) -> Result<Option<TrackedOrder>> {
+        let Some(order) = self.get(id).filter(|o| o.status.is_live()) else {
+            return Ok(None);
+        };
+        // Fills that landed before the cancel stay on the old order
+        self.sync_order(&order).await?;
+        self.cancel_order(id).await?;
+        let order = self.get(id).unwrap_or(order);
+        if order.remaining() <= f64::EPSILON {
+            return Ok(None);
+        }
+        let timeout_ms = (order.expires_at - chrono::Utc::now().timestamp_millis()).max(0);
+        let replacement = self.submit(order.side, price, order.remaining(), order.post_only, timeout_ms).await?;
+        if let Some(mut entry) = self.orders.get_mut(&replacement.id) {
+            entry.replaces = Some(order.id.clone());
+        }
+        info!(
+            "Replaced order {} @ ${:.4} with {} @ ${:.4} for {:.4} SOL",
+            order.id, order.price.unwrap_or_default(), replacement.id, price, replacement.size
+        );
+        Ok(self.get(&replacement.id))
+    }
+
+    /// Reconcile every live venue order with the venue's state
+    pub async fn sync(&self) -> Result<()> {
+        for order in self.open_orders() {
+            self.sync_order(&order).await?;
+        }
+        Ok(())
+    }
+
+    async fn sync_order(&self, order: &TrackedOrder) -> Result<()> {
+        let (Some(venue), Some(name), Some(venue_id)) = (&self.venue, &order.venue, &order.venue_order_id) else {
+            return Ok(());
+        };
+        let state = venue.order_state(name, venue_id).await?;
+        self.reconcile(&order.id, state);
+        Ok(())
+    }
+
+    /// Apply the venue's view of an order: new fills are recorded, and an
+    /// order the venue has closed (or no longer holds) stops being live
+    pub fn reconcile(&self, id: &str, venue_state: Option<VenueOrderState>) -> Option<TrackedOrder> {
+        let order = self.get(id)?;
+        let Some(venue_state) = venue_state else {
+            if order.status.is_live() {
+                // Fills not seen before it dropped off are left to leg reconciliation
+                warn!("Order {} no longer on {}, marking cancelled", id, order.venue.as_deref().unwrap_or("venue"));
+                return self.cancel(id);
+            }
+            return Some(order);
+        };
+
+        let new_fill = venue_state.filled.min(order.size) - order.filled;
+        if new_fill > f64::EPSILON {
+            // Price of the new fill, from the change in the venue's average
+            let price = venue_state
+                .avg_price
+                .map(|avg| (avg * (order.filled + new_fill) - order.avg_fill_price.unwrap_or(avg) * order.filled) / new_fill)
+                .or(order.price)
+                .unwrap_or_default();
+            self.record_fill(id, new_fill, price);
+        }
+        if !venue_state.status.is_live() {
+            if let Some(mut entry) = self.orders.get_mut(id).filter(|o| o.status.is_live()) {
+                entry.status = venue_state.status;
+            }
+        }
+        self.get(id)
+    }
+
+    /// `expire`, cancelling the expired orders on their venue first. An
+    /// order whose cancel fails is logged and stays live for the next pass.
+    pub async fn expire_orders(&self, now: i64) -> Vec<TrackedOrder> {
+        let mut expired = Vec::new();
+        for order in self.open_orders().into_iter().filter(|o| now >= o.expires_at) {
+            if let (Some(venue), Some(name), Some(venue_id)) = (&self.venue, &order.venue, &order.venue_order_id) {
+                if let Err(e) = venue.cancel(name, venue_id).await {
+                    warn!("Cancelling expired order {} on {} failed: {}", order.id, name, e);
+                    continue;
+                }
+            }
+            if let Some(mut entry) = self.orders.get_mut(&order.id).filter(|o| o.status.is_live()) {
+                entry.status = OrderStatus::Expired;
+                expired.push(entry.clone());
+            }
+        }
+        expired
+    }
+
+    /// Fills recorded since the last call, oldest first
+    pub fn take_fills(&self) -> Vec<OrderFill> {
+        std::mem::take(&mut *self.fills.lock())
+    }
+
+    /// Start tracking a newly placed order
+    pub fn place(
+        &self,
+        side: OrderSide,
+        order_type: OrderType,
+        price: Option<f64>,
+        size: f64,
+        post_only: bool,
+        timeout_ms: i64,
+    ) -> TrackedOrder {
+        let now = chrono::Utc::now().timestamp_millis();
+        let order = TrackedOrder {
+            id: uuid::Uuid::new_v4().to_string(),
+            side,
+            order_type,
+            price,
+            size,
+            filled: 0.0,
+            post_only,
+            status: OrderStatus::Open,
+            placed_at: now,
+            expires_at: now + timeout_ms,
+            venue: None,
+            venue_order_id: None,
+            avg_fill_price: None,
+            replaces: None,
+        };
+        self.orders.insert(order.id.clone(), order.clone());
+        order
+    }
+
+    /// Record a (partial) fill at `price`, queueing it for `take_fills`
+    pub fn record_fill(&self, id: &str, size: f64, price: f64) -> Option<TrackedOrder> {
+        let mut order = self.orders.get_mut(id)?;
+        let size = size.min(order.remaining());
+        if !order.status.is_live() || size <= 0.0 {
+            return Some(order.clone());
+        }
+        let filled_value = order.avg_fill_price.unwrap_or(price) * order.filled + price * size;
+        order.filled += size;
+        order.avg_fill_price = Some(filled_value / order.filled);
+        order.status = if order.remaining() <= f64::EPSILON {
+            OrderStatus::Filled
+        } else {
+            OrderStatus::PartiallyFilled
+        };
+        self.fills.lock().push(OrderFill {
+            order_id: order.id.clone(),
+            side: order.side,
+            size,
+            price,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        });
+        Some(order.clone())
+    }
+
+    /// Cancel a live order
+    pub fn cancel(&self, id: &str) -> Option<TrackedOrder> {
+        let mut order = self.orders.get_mut(id)?;
+        if order.status.is_live() {
+            order.status = OrderStatus::Cancelled;
+        }
+        Some(order.clone())
+    }
+
+    /// Mark live orders past their expiry as expired, returning them
+    pub fn expire(&self, now: i64) -> Vec<TrackedOrder> {
+        let mut expired = Vec::new();
+        for mut order in self.orders.iter_mut() {
+            if order.status.is_live() && now >= order.expires_at {
+                order.status = OrderStatus::Expired;
+                expired.push(order.clone());
+            }
+        }
+        expired
+    }
+
+    pub fn get(&self, id: &str) -> Option<TrackedOrder> {
+        self.orders.get(id).map(|o| o.clone())
+    }
+
+    /// Orders that can still fill
+    pub fn open_orders(&self) -> Vec<TrackedOrder> {
+        self.orders.iter().filter(|o| o.status.is_live()).map(|o| o.clone()).collect()
+    }
+
+    /// Drop orders that are no longer live
+    pub fn prune(&self) {
+        self.orders.retain(|_, o| o.status.is_live());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_lifecycle() {
+        let tracker = OrderTracker::new();
+        let order = tracker.place(OrderSide::Short, OrderType::Limit, Some(150.2), 10.0, true, 30_000);
+
+        assert!(!order.is_crossed(150.1, 150.3));
+        assert!(order.is_crossed(150.2, 150.4));
+
+        let partial = tracker.record_fill(&order.id, 4.0, 150.2).unwrap();
+        assert_eq!(partial.status, OrderStatus::PartiallyFilled);
+        assert_eq!(tracker.open_orders().len(), 1);
+
+        let expired = tracker.expire(order.expires_at);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(tracker.get(&order.id).unwrap().status, OrderStatus::Expired);
+        assert!(tracker.open_orders().is_empty());
+
+        let fills = tracker.take_fills();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].signed_size(), -4.0);
+        assert!(tracker.take_fills().is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_with_venue_state() {
+        let tracker = OrderTracker::new();
+        let order = tracker.place(OrderSide::Short, OrderType::Limit, Some(150.0), 10.0, true, 30_000);
+        tracker.record_fill(&order.id, 4.0, 150.0);
+
+        // The venue has filled 10 SOL at an average of $150.30: the new 6 SOL filled at $150.50
+        let filled = VenueOrderState { filled: 10.0, avg_price: Some(150.3), status: OrderStatus::Filled };
+        let order = tracker.reconcile(&order.id, Some(filled)).unwrap();
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert!((order.avg_fill_price.unwrap() - 150.3).abs() < 1e-9);
+        let fills = tracker.take_fills();
+        assert_eq!(fills.len(), 2);
+        assert!((fills[1].size - 6.0).abs() < 1e-9 && (fills[1].price - 150.5).abs() < 1e-9);
+
+        // An order the venue no longer holds is no longer live
+        let dropped = tracker.place(OrderSide::Short, OrderType::Limit, Some(150.0), 5.0, true, 30_000);
+        assert_eq!(tracker.reconcile(&dropped.id, None).unwrap().status, OrderStatus::Cancelled);
+        assert!(tracker.take_fills().is_empty());
+    }
+
+    /// Venue whose cancels fail for orders with ids starting "stuck"
+    struct FlakyVenue {
+        placed: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl OrderVenue for FlakyVenue {
+        async fn place(&self, order: &PerpOrder) -> Result<(String, PerpOrderAck)> {
+            let mut placed = self.placed.lock();
+            *placed += 1;
+            let id = if *placed == 1 { "stuck-1".to_string() } else { format!("ok-{}", placed) };
+            Ok(("drift".to_string(), PerpOrderAck { order_id: id, filled: 0.0, price: order.limit_price.unwrap_or_default() }))
+        }
+
+        async fn cancel(&self, _venue: &str, order_id: &str) -> Result<()> {
+            anyhow::ensure!(!order_id.starts_with("stuck"), "venue unavailable");
+            Ok(())
+        }
+
+        async fn order_state(&self, _venue: &str, _order_id: &str) -> Result<Option<VenueOrderState>> {
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_cancel_does_not_stop_expiry() {
+        let tracker = OrderTracker::new().with_venue(Arc::new(FlakyVenue { placed: Mutex::new(0) }));
+        let stuck = tracker.submit(OrderSide::Short, 150.0, 5.0, true, 0).await.unwrap();
+        let ok = tracker.submit(OrderSide::Short, 150.0, 5.0, true, 0).await.unwrap();
+
+        let expired = tracker.expire_orders(ok.expires_at).await;
+        assert_eq!(expired.len(), 1);
+        assert_eq!(tracker.get(&ok.id).unwrap().status, OrderStatus::Expired);
+        // Still live on the venue, so still tracked as live
+        assert!(tracker.get(&stuck.id).unwrap().status.is_live());
+    }
+}
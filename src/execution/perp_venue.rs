@@ -0,0 +1,556 @@
+//! Perp Venues
+//!
+//! The perp (short) leg behind one interface, so it can be placed on
+//! whichever venue pays the most funding:
+//! - `PerpVenue`: mark price, funding, place/cancel order, order state,
+//!   position
+//! - `DriftVenue`: Drift, priced from the shared market state, with order
+//!   state read from the wallet's user account
+//! - `PerpRouter`: picks the venue for each order; adjustments to an
+//!   open position stay on the venue that holds it
+//!
+//! Venues are compared on short carry: funding received minus any borrow
+//! fee the venue charges instead of (or on top of) funding.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use parking_lot::RwLock;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::orders::{OrderStatus, VenueOrderState};
+use super::submitter::TransactionSubmitter;
+use super::tx_builder::{DriftOrderParams, OrderSide, OrderType, TransactionBuilder};
+use crate::config::{AppConfig, PerpRouting};
+use crate::network::RpcManager;
+use crate::state::SharedState;
+use crate::utils::amount::{from_base_units, Amount, DRIFT_BASE_DECIMALS, USDC_DECIMALS};
+use crate::utils::helpers::drift_user_address;
+use crate::utils::types::{FundingInterval, PositionSide};
+
+/// Order for the perp leg
+#[derive(Debug, Clone, Serialize)]
+pub struct PerpOrder {
+    /// Signed size (SOL; long positive)
+    pub size_sol: f64,
+    pub order_type: OrderType,
+    /// Limit price (USD), required for limit orders
+    pub limit_price: Option<f64>,
+    pub reduce_only: bool,
+    pub post_only: bool,
+}
+
+impl PerpOrder {
+    /// Market order for a signed size
+    pub fn market(size_sol: f64, reduce_only: bool) -> Self {
+        Self {
+            size_sol,
+            order_type: OrderType::Market,
+            limit_price: None,
+            reduce_only,
+            post_only: false,
+        }
+    }
+
+    pub fn side(&self) -> OrderSide {
+        if self.size_sol > 0.0 { OrderSide::Long } else { OrderSide::Short }
+    }
+}
+
+/// Venue acknowledgement of an order
+#[derive(Debug, Clone, Serialize)]
+pub struct PerpOrderAck {
+    /// Venue order id (or transaction signature)
+    pub order_id: String,
+    /// Signed size filled immediately (0 for resting orders)
+    pub filled: f64,
+    /// Average fill price, or the limit price for resting orders
+    pub price: f64,
+}
+
+/// Current funding on a venue
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct VenueFunding {
+    /// Rate per funding period (positive = longs pay shorts)
+    pub rate: f64,
+    /// Borrow fee per period, paid by either side (borrow-based venues)
+    pub borrow_rate: f64,
+    pub interval: FundingInterval,
+}
+
+impl VenueFunding {
+    /// Annualized funding (%)
+    pub fn apr(&self) -> f64 {
+        self.interval.annualize(self.rate)
+    }
+
+    /// Annualized borrow fee (%)
+    pub fn borrow_apr(&self) -> f64 {
+        self.interval.annualize(self.borrow_rate)
+    }
+
+    /// Annualized carry earned by a short, net of borrow fees (%)
+    pub fn short_apr(&self) -> f64 {
+        self.interval.annualize(self.rate - self.borrow_rate)
+    }
+}
+
+/// Open position on a venue
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct VenuePosition {
+    /// Signed size (SOL; long positive)
+    pub size: f64,
+    pub entry_price: f64,
+    pub unrealized_pnl: f64,
+}
+
+/// A perp venue the short leg can be placed on
+#[async_trait]
+pub trait PerpVenue: Send + Sync {
+    /// Venue name ("drift", "hyperliquid", "jupiter_perps")
+    fn name(&self) -> &str;
+
+    /// Current mark price (USD)
+    async fn mark_price(&self) -> Result<f64>;
+
+    /// Current funding rate
+    async fn funding(&self) -> Result<VenueFunding>;
+
+    /// Place an order
+    async fn place_order(&self, order: &PerpOrder) -> Result<PerpOrderAck>;
+
+    /// Cancel a resting order
+    async fn cancel_order(&self, order_id: &str) -> Result<()>;
+
+    /// State of an order; None once the venue no longer holds it
+    async fn order_state(&self, order_id: &str) -> Result<Option<VenueOrderState>>;
+
+    /// Open position in the configured market, if any
+    async fn position(&self) -> Result<Option<VenuePosition>>;
+}
+
+/// Drift user account layout (bytes): discriminator, authority, delegate
+/// and name, 8 spot positions, 8 perp positions, then 32 order slots
+const DRIFT_USER_PERP_POSITIONS: usize = 8 + 32 * 3 + 8 * 40;
+const DRIFT_PERP_POSITION_LEN: usize = 96;
+const DRIFT_USER_ORDERS: usize = DRIFT_USER_PERP_POSITIONS + 8 * DRIFT_PERP_POSITION_LEN;
+const DRIFT_ORDER_LEN: usize = 96;
+const DRIFT_MAX_ORDERS: usize = 32;
+/// Drift order status: Init (empty slot), Open, Filled, Canceled
+const DRIFT_ORDER_OPEN: u8 = 1;
+const DRIFT_ORDER_FILLED: u8 = 2;
+/// Drift market type of perp orders
+const DRIFT_MARKET_PERP: u8 = 1;
+
+/// A perp order slot in a Drift user account
+#[derive(Debug, Clone, Copy)]
+struct DriftOrderSlot {
+    order_id: u32,
+    /// Order size (base units)
+    base: u64,
+    /// Filled size (base units)
+    base_filled: u64,
+    /// Quote filled (quote units)
+    quote_filled: u64,
+    status: u8,
+}
+
+/// One market's orders and perp position in a Drift user account
+struct DriftUserMarket {
+    orders: Vec<DriftOrderSlot>,
+    /// Signed perp position (base units)
+    perp_base: i64,
+}
+
+impl DriftUserMarket {
+    fn decode(data: &[u8], market_index: u16) -> Result<Self> {
+        anyhow::ensure!(
+            data.len() >= DRIFT_USER_ORDERS + DRIFT_MAX_ORDERS * DRIFT_ORDER_LEN,
+            "Drift user account too short ({} bytes)",
+            data.len()
+        );
+        let u64_at = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+        let u16_at = |at: usize| u16::from_le_bytes(data[at..at + 2].try_into().unwrap());
+
+        let perp_base = (0..8)
+            .map(|i| DRIFT_USER_PERP_POSITIONS + i * DRIFT_PERP_POSITION_LEN)
+            .find(|&at| u16_at(at + 92) == market_index)
+            .map_or(0, |at| u64_at(at + 8) as i64);
+        let orders = (0..DRIFT_MAX_ORDERS)
+            .map(|i| DRIFT_USER_ORDERS + i * DRIFT_ORDER_LEN)
+            .filter(|&at| data[at + 84] == DRIFT_MARKET_PERP && u16_at(at + 80) == market_index)
+            .map(|at| DriftOrderSlot {
+                order_id: u32::from_le_bytes(data[at + 76..at + 80].try_into().unwrap()),
+                base: u64_at(at + 16),
+                base_filled: u64_at(at + 24),
+                quote_filled: u64_at(at + 32),
+                status: data[at + 82],
+            })
+            .filter(|o| o.status != 0)
+            .collect();
+        Ok(Self { orders, perp_base })
+    }
+}
+
+/// A limit order placed through the venue
+#[derive(Debug, Clone, Copy)]
+struct DriftPlacedOrder {
+    /// Signed order size (base units)
+    base: i64,
+    /// Perp position before the order filled (base units)
+    position_before: i64,
+}
+
+/// Drift perp venue
+pub struct DriftVenue {
+    config: Arc<AppConfig>,
+    state: Arc<SharedState>,
+    rpc: Arc<RpcManager>,
+    tx_builder: TransactionBuilder,
+    submitter: TransactionSubmitter,
+    /// Wallet placing orders; None for read-only use
+    payer: Option<Keypair>,
+    /// Limit orders placed, by Drift order id
+    placed: DashMap<u32, DriftPlacedOrder>,
+}
+
+impl DriftVenue {
+    pub fn new(
+        config: Arc<AppConfig>,
+        state: Arc<SharedState>,
+        rpc: Arc<RpcManager>,
+        tx_builder: TransactionBuilder,
+        submitter: TransactionSubmitter,
+        payer: Option<Keypair>,
+    ) -> Self {
+        Self { config, state, rpc, tx_builder, submitter, payer, placed: DashMap::new() }
+    }
+
+    fn payer(&self) -> Result<&Keypair> {
+        self.payer.as_ref().context("Drift orders need the wallet keypair")
+    }
+
+    /// The wallet's user account (sub-account 0)
+    fn user_address(&self) -> Result<Pubkey> {
+        let program_id: Pubkey = self.config.protocols.drift.program_id.parse()
+            .context("Invalid Drift program id")?;
+        Ok(drift_user_address(&program_id, &self.payer()?.pubkey(), 0))
+    }
+
+    /// Orders and perp position in the configured market
+    async fn user_market(&self) -> Result<DriftUserMarket> {
+        let account = self.rpc.get_multiple_accounts(&[self.user_address()?]).await?
+            .into_iter()
+            .flatten()
+            .next()
+            .context("Drift user account not found")?;
+        DriftUserMarket::decode(&account.data, self.config.protocols.drift.market_index)
+    }
+
+    /// Id of a limit order just placed: the newest open order in the market
+    async fn track_placed(&self, order: &PerpOrder) -> Result<u32> {
+        let user = self.user_market().await?;
+        let slot = user.orders.iter()
+            .filter(|o| o.status == DRIFT_ORDER_OPEN)
+            .max_by_key(|o| o.order_id)
+            .context("Placed order not found in the Drift user account")?;
+        let sign = if order.size_sol > 0.0 { 1 } else { -1 };
+        self.placed.insert(slot.order_id, DriftPlacedOrder {
+            base: sign * slot.base as i64,
+            position_before: user.perp_base - sign * slot.base_filled as i64,
+        });
+        Ok(slot.order_id)
+    }
+}
+
+#[async_trait]
+impl PerpVenue for DriftVenue {
+    fn name(&self) -> &str {
+        "drift"
+    }
+
+    async fn mark_price(&self) -> Result<f64> {
+        let mark = self.state.perp_mark_price.load();
+        anyhow::ensure!(mark > 0.0, "No Drift mark price yet");
+        Ok(mark)
+    }
+
+    async fn funding(&self) -> Result<VenueFunding> {
+        Ok(VenueFunding {
+            rate: self.state.current_funding_rate.load(),
+            borrow_rate: 0.0,
+            interval: self.config.protocols.drift.funding_interval,
+        })
+    }
+
+    async fn place_order(&self, order: &PerpOrder) -> Result<PerpOrderAck> {
+        let params = DriftOrderParams {
+            market_index: self.config.protocols.drift.market_index,
+            side: order.side(),
+            order_type: order.order_type,
+            base_asset_amount: Amount::drift_base(order.size_sol)?.base_units()?,
+            price: order.limit_price.map(|p| Amount::drift_price(p)?.base_units()).transpose()?,
+            reduce_only: order.reduce_only,
+            post_only: order.post_only,
+        };
+        let payer = self.payer()?;
+        let submitted = self.submitter
+            .submit_with_rebuild(|| self.tx_builder.build_order(payer, &params))
+            .await?;
+
+        // Market orders fill on landing; limit orders rest on the book
+        let (filled, price) = match order.order_type {
+            OrderType::Market => (order.size_sol, self.mark_price().await?),
+            _ => (0.0, order.limit_price.unwrap_or_default()),
+        };
+        let order_id = match order.order_type {
+            OrderType::Market => submitted.signature.to_string(),
+            _ => match self.track_placed(order).await {
+                Ok(id) => id.to_string(),
+                Err(e) => {
+                    warn!("Drift order id unavailable, tracking by signature: {}", e);
+                    submitted.signature.to_string()
+                }
+            },
+        };
+        Ok(PerpOrderAck { order_id, filled, price })
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        // Drift order ids are u32; a transaction signature cancels the latest order
+        let payer = self.payer()?;
+        let id = order_id.parse().ok();
+        self.submitter
+            .submit_with_rebuild(|| self.tx_builder.build_cancel_order(payer, id))
+            .await?;
+        Ok(())
+    }
+
+    async fn order_state(&self, order_id: &str) -> Result<Option<VenueOrderState>> {
+        let Ok(id) = order_id.parse::<u32>() else {
+            return Ok(None);
+        };
+        let user = self.user_market().await?;
+        let base_sol = |units: u64| from_base_units(units, DRIFT_BASE_DECIMALS);
+
+        if let Some(slot) = user.orders.iter().find(|o| o.order_id == id) {
+            let filled = base_sol(slot.base_filled);
+            let avg_price = (slot.base_filled > 0)
+                .then(|| from_base_units(slot.quote_filled, USDC_DECIMALS) / filled);
+            let status = match slot.status {
+                DRIFT_ORDER_FILLED => OrderStatus::Filled,
+                DRIFT_ORDER_OPEN if slot.base_filled > 0 => OrderStatus::PartiallyFilled,
+                DRIFT_ORDER_OPEN => OrderStatus::Open,
+                _ => OrderStatus::Cancelled,
+            };
+            return Ok(Some(VenueOrderState { filled, avg_price, status }));
+        }
+
+        // Drift clears an order's slot once it fills or is cancelled; what
+        // filled shows in the position's change since it was placed
+        let Some((_, placed)) = self.placed.remove(&id) else {
+            return Ok(None);
+        };
+        let filled_base = ((user.perp_base - placed.position_before) * placed.base.signum()).clamp(0, placed.base.abs());
+        let status = if filled_base == placed.base.abs() { OrderStatus::Filled } else { OrderStatus::Cancelled };
+        Ok(Some(VenueOrderState { filled: base_sol(filled_base as u64), avg_price: None, status }))
+    }
+
+    async fn position(&self) -> Result<Option<VenuePosition>> {
+        // Tracked from our own fills until user account decoding is in place
+        Ok(self.state.perp_position.read().as_ref().map(|p| VenuePosition {
+            size: if p.side == PositionSide::Short { -p.size } else { p.size },
+            entry_price: p.entry_price,
+            unrealized_pnl: p.unrealized_pnl,
+        }))
+    }
+}
+
+/// Chooses the venue for each perp order
+pub struct PerpRouter {
+    routing: PerpRouting,
+    venues: Vec<Arc<dyn PerpVenue>>,
+    /// Venue holding the open position
+    active: RwLock<Option<String>>,
+}
+
+impl PerpRouter {
+    pub fn new(routing: PerpRouting, venues: Vec<Arc<dyn PerpVenue>>) -> Self {
+        Self { routing, venues, active: RwLock::new(None) }
+    }
+
+    pub fn venues(&self) -> &[Arc<dyn PerpVenue>] {
+        &self.venues
+    }
+
+    fn by_name(&self, name: &str) -> Option<Arc<dyn PerpVenue>> {
+        self.venues.iter().find(|v| v.name() == name).cloned()
+    }
+
+    /// Venue currently holding the position, if any
+    pub fn active_venue(&self) -> Option<String> {
+        self.active.read().clone()
+    }
+
+    /// Signed position on the venue holding the open position (SOL, 0 when
+    /// none); positions on the other venues belong to other hedges
+    pub async fn active_position(&self) -> Result<f64> {
+        let Some(venue) = self.active_venue().and_then(|name| self.by_name(&name)) else {
+            return Ok(0.0);
+        };
+        Ok(venue.position().await?.map_or(0.0, |p| p.size))
+    }
+
+    /// Pick the venue for a new short: the best carry net of borrow fees
+    pub async fn best_funding_venue(&self) -> Result<Arc<dyn PerpVenue>> {
+        let mut best: Option<(Arc<dyn PerpVenue>, f64)> = None;
+        for venue in &self.venues {
+            match venue.funding().await {
+                Ok(funding) => {
+                    let apr = funding.short_apr();
+                    if best.as_ref().map_or(true, |(_, b)| apr > *b) {
+                        best = Some((venue.clone(), apr));
+                    }
+                }
+                Err(e) => warn!("Funding unavailable on {}: {}", venue.name(), e),
+            }
+        }
+        best.map(|(venue, _)| venue).context("No perp venue has funding data")
+    }
+
+    /// Venue an order should go to
+    pub async fn route(&self, order: &PerpOrder) -> Result<Arc<dyn PerpVenue>> {
+        if let Some(active) = self.active_venue().and_then(|name| self.by_name(&name)) {
+            return Ok(active);
+        }
+        anyhow::ensure!(!order.reduce_only, "No open perp position to reduce");
+        match self.routing.venue_name() {
+            Some(name) => self.by_name(name)
+                .with_context(|| format!("Perp venue {} is not configured", name)),
+            None => self.best_funding_venue().await,
+        }
+    }
+
+    /// Place an order on the routed venue, tracking where the position lives
+    pub async fn place_order(&self, order: &PerpOrder) -> Result<(String, PerpOrderAck)> {
+        let venue = self.route(order).await?;
+        let ack = venue.place_order(order).await?;
+        let name = venue.name().to_string();
+
+        if self.active_venue().is_none() && ack.filled != 0.0 {
+            info!("Perp leg routed to {}", name);
+            *self.active.write() = Some(name.clone());
+        } else if order.reduce_only && ack.filled != 0.0 {
+            // Release the venue once its position is flat
+            if matches!(venue.position().await, Ok(None)) {
+                *self.active.write() = None;
+            }
+        }
+        Ok((name, ack))
+    }
+
+    /// Cancel a resting order on `venue`
+    pub async fn cancel_order(&self, venue: &str, order_id: &str) -> Result<()> {
+        let perp = self.by_name(venue).with_context(|| format!("Perp venue {} is not configured", venue))?;
+        perp.cancel_order(order_id).await
+    }
+
+    /// State of an order on `venue`; a fill makes it the active venue
+    pub async fn order_state(&self, venue: &str, order_id: &str) -> Result<Option<VenueOrderState>> {
+        let perp = self.by_name(venue).with_context(|| format!("Perp venue {} is not configured", venue))?;
+        let state = perp.order_state(order_id).await?;
+        if self.active_venue().is_none() && state.is_some_and(|s| s.filled > 0.0) {
+            info!("Perp leg routed to {}", venue);
+            *self.active.write() = Some(venue.to_string());
+        }
+        Ok(state)
+    }
+
+    /// Forget the active venue (after the position is closed)
+    pub fn release(&self) {
+        *self.active.write() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeVenue {
+        name: &'static str,
+        rate: f64,
+        borrow_rate: f64,
+        /// Signed position the venue reports
+        held: f64,
+    }
+
+    #[async_trait]
+    impl PerpVenue for FakeVenue {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn mark_price(&self) -> Result<f64> {
+            Ok(150.0)
+        }
+
+        async fn funding(&self) -> Result<VenueFunding> {
+            Ok(VenueFunding { rate: self.rate, borrow_rate: self.borrow_rate, interval: FundingInterval::Hourly })
+        }
+
+        async fn place_order(&self, order: &PerpOrder) -> Result<PerpOrderAck> {
+            Ok(PerpOrderAck { order_id: "1".to_string(), filled: order.size_sol, price: 150.0 })
+        }
+
+        async fn cancel_order(&self, _order_id: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn order_state(&self, _order_id: &str) -> Result<Option<VenueOrderState>> {
+            Ok(None)
+        }
+
+        async fn position(&self) -> Result<Option<VenuePosition>> {
+            Ok((self.held != 0.0).then_some(VenuePosition { size: self.held, entry_price: 150.0, unrealized_pnl: 0.0 }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_short_to_best_funding_and_sticks() {
+        let router = PerpRouter::new(PerpRouting::BestFunding, vec![
+            Arc::new(FakeVenue { name: "drift", rate: 0.0001, borrow_rate: 0.0, held: 0.0 }),
+            Arc::new(FakeVenue { name: "hyperliquid", rate: 0.0002, borrow_rate: 0.0, held: 0.0 }),
+            // Highest gross funding, but the borrow fee leaves the least carry
+            Arc::new(FakeVenue { name: "jupiter_perps", rate: 0.0003, borrow_rate: 0.00025, held: 0.0 }),
+        ]);
+
+        assert!(router.place_order(&PerpOrder::market(1.0, true)).await.is_err());
+
+        let (venue, ack) = router.place_order(&PerpOrder::market(-10.0, false)).await.unwrap();
+        assert_eq!(venue, "hyperliquid");
+        assert_eq!(ack.filled, -10.0);
+        assert_eq!(router.active_venue().as_deref(), Some("hyperliquid"));
+
+        // Closing the position releases the venue
+        let (venue, _) = router.place_order(&PerpOrder::market(10.0, true)).await.unwrap();
+        assert_eq!(venue, "hyperliquid");
+        assert_eq!(router.active_venue(), None);
+    }
+
+    #[tokio::test]
+    async fn test_position_is_read_from_the_active_venue_only() {
+        // Drift holds another hedge; this trade's leg goes to Hyperliquid
+        let router = PerpRouter::new(PerpRouting::Hyperliquid, vec![
+            Arc::new(FakeVenue { name: "drift", rate: 0.0001, borrow_rate: 0.0, held: -3.0 }),
+            Arc::new(FakeVenue { name: "hyperliquid", rate: 0.0002, borrow_rate: 0.0, held: -10.0 }),
+        ]);
+        assert_eq!(router.active_position().await.unwrap(), 0.0);
+
+        router.place_order(&PerpOrder::market(-10.0, false)).await.unwrap();
+        assert_eq!(router.active_position().await.unwrap(), -10.0);
+    }
+}
@@ -0,0 +1,504 @@
+//! Pre-Trade Checklist
+//!
+//! Every live order passes a checklist right before submission:
+//! - Price freshness: oracle and mark updated within `max_price_age_ms`
+//! - Spread sanity: oracle spot against the CEX ticker, perp mark against spot
+//! - Wallet balance: USDC for buys, SOL for sells, gas for every order
+//! - Collateral: Drift free collateral for perp orders adding exposure
+//! - Exposure: position size and notional limits
+//! - Reversal: no funding reversal alert in the last 30 minutes
+//! - RPC health: connected, latency within `max_rpc_latency_ms`
+//! - Rate limits: order rate and circuit breakers on the APIs the leg uses
+//!
+//! Inputs are read once into a `PreTradeInputs` snapshot and every check is
+//! evaluated against it, so the result describes one moment. The full
+//! checklist is logged; a failed one stops the order, and the execution
+//! manager records it as a skipped trade. Orders reducing exposure (closes,
+//! unwinds, flattening) only warn on price freshness, spread sanity, RPC
+//! health and rate limits: those fail in exactly the markets the bot must
+//! be able to de-risk in.
+
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::agentic::reversal_detector::REVERSAL_ACTIVE_MS;
+use crate::config::{AppConfig, PerpRouting};
+use crate::network::http;
+use crate::state::SharedState;
+use crate::telemetry::record_pre_trade_failure;
+use crate::utils::types::Dependency;
+
+use super::balances::{AvailableCapital, BalanceFetcher};
+
+/// Window the order rate is counted over (ms)
+const RATE_WINDOW_MS: i64 = 60_000;
+
+/// One item of the checklist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreTradeCheck {
+    PriceFreshness,
+    SpreadSanity,
+    WalletBalance,
+    Collateral,
+    Exposure,
+    Reversal,
+    RpcHealth,
+    RateLimit,
+}
+
+impl PreTradeCheck {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PriceFreshness => "price_freshness",
+            Self::SpreadSanity => "spread_sanity",
+            Self::WalletBalance => "wallet_balance",
+            Self::Collateral => "collateral",
+            Self::Exposure => "exposure",
+            Self::Reversal => "reversal",
+            Self::RpcHealth => "rpc_health",
+            Self::RateLimit => "rate_limit",
+        }
+    }
+
+    /// Market and connectivity checks, which do not stop reducing orders
+    fn warns_on_reduce(&self) -> bool {
+        matches!(self, Self::PriceFreshness | Self::SpreadSanity | Self::RpcHealth | Self::RateLimit)
+    }
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub check: PreTradeCheck,
+    pub passed: bool,
+    /// Failed, but not blocking: the order reduces exposure
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub warning: bool,
+    pub detail: String,
+}
+
+/// Leg an order trades
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderLeg {
+    Spot,
+    Perp,
+}
+
+/// The order being checked
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PreTradeOrder {
+    pub leg: OrderLeg,
+    /// Signed size (SOL; buy / long positive)
+    pub size_sol: f64,
+    /// Adds to the position on its leg (false for reduce-only and unwinds)
+    pub adds_exposure: bool,
+}
+
+/// Everything the checks look at, read at one moment
+#[derive(Debug, Clone)]
+pub struct PreTradeInputs {
+    pub now: i64,
+    pub last_price_update: i64,
+    pub spot_price: f64,
+    pub perp_price: f64,
+    /// CEX ticker; None when not configured
+    pub cex_price: Option<Result<f64, String>>,
+    /// Wallet and collateral balances; Err when they could not be read
+    pub capital: Result<AvailableCapital, String>,
+    /// SOL and USDC on the exchange, when the spot leg trades there
+    pub venue_balance: Option<(f64, f64)>,
+    /// Position already held on the order's leg (SOL, unsigned)
+    pub position_sol: f64,
+    pub reversal_active: bool,
+    pub rpc_connected: bool,
+    pub rpc_latency_ms: f64,
+    /// Orders submitted in the last minute
+    pub recent_orders: usize,
+    /// Dependencies of the order's leg whose circuit breaker is open
+    pub degraded: Vec<Dependency>,
+}
+
+/// Result of the whole checklist
+#[derive(Debug, Clone, Serialize)]
+pub struct PreTradeChecklist {
+    pub order: PreTradeOrder,
+    pub timestamp: i64,
+    pub results: Vec<CheckResult>,
+}
+
+impl PreTradeChecklist {
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Failed checks that stop the order
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results.iter().filter(|r| !r.passed && !r.warning)
+    }
+
+    /// Failed checks let through because the order reduces exposure
+    pub fn warnings(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results.iter().filter(|r| r.warning)
+    }
+
+    /// Failed checks, or "all checks passed"
+    pub fn summary(&self) -> String {
+        let failures: Vec<String> = self.failures().map(|r| format!("{}: {}", r.check.as_str(), r.detail)).collect();
+        if failures.is_empty() {
+            "all checks passed".to_string()
+        } else {
+            failures.join("; ")
+        }
+    }
+}
+
+/// Evaluate every check for `order` against `inputs`
+pub fn evaluate(order: PreTradeOrder, inputs: &PreTradeInputs, config: &AppConfig) -> PreTradeChecklist {
+    let limits = &config.execution.pre_trade;
+    let size = order.size_sol.abs();
+    let mut results = Vec::with_capacity(8);
+    let mut check = |check: PreTradeCheck, passed: bool, detail: String| {
+        let warning = !passed && !order.adds_exposure && check.warns_on_reduce();
+        results.push(CheckResult { check, passed, warning, detail });
+    };
+
+    let age_ms = inputs.now - inputs.last_price_update;
+    check(
+        PreTradeCheck::PriceFreshness,
+        inputs.spot_price > 0.0 && inputs.perp_price > 0.0 && age_ms <= limits.max_price_age_ms as i64,
+        format!("prices {}ms old (max {}ms)", age_ms, limits.max_price_age_ms),
+    );
+
+    let basis_pct = if inputs.spot_price > 0.0 {
+        (inputs.perp_price - inputs.spot_price).abs() / inputs.spot_price * 100.0
+    } else {
+        f64::INFINITY
+    };
+    let (cex_ok, cex_detail) = match &inputs.cex_price {
+        None => (true, "no CEX reference".to_string()),
+        Some(Err(e)) => (false, format!("CEX reference unavailable ({})", e)),
+        Some(Ok(cex)) => {
+            let deviation = (inputs.spot_price - cex).abs() / cex * 100.0;
+            (
+                deviation <= limits.max_cex_deviation_pct,
+                format!("spot {:.3}% from CEX ${:.4} (max {:.2}%)", deviation, cex, limits.max_cex_deviation_pct),
+            )
+        }
+    };
+    check(
+        PreTradeCheck::SpreadSanity,
+        cex_ok && basis_pct <= limits.max_basis_pct,
+        format!("{}, mark {:.3}% from spot (max {:.2}%)", cex_detail, basis_pct, limits.max_basis_pct),
+    );
+
+    match &inputs.capital {
+        Err(e) => check(PreTradeCheck::WalletBalance, false, format!("balances unavailable ({})", e)),
+        Ok(capital) => {
+            let (sol, usdc) = inputs.venue_balance.unwrap_or((capital.spendable_sol(), capital.usdc_balance));
+            let (funded, detail) = match order.leg {
+                OrderLeg::Spot if order.size_sol > 0.0 => {
+                    let cost = size * inputs.spot_price;
+                    (usdc >= cost, format!("{:.2} USDC for ${:.2}", usdc, cost))
+                }
+                OrderLeg::Spot => (sol >= size, format!("{:.4} SOL spendable for {:.4}", sol, size)),
+                OrderLeg::Perp => (true, String::new()),
+            };
+            let gas = capital.has_gas();
+            let detail = format!(
+                "{}{}{:.4} SOL for gas (reserve {:.4})",
+                detail,
+                if detail.is_empty() { "" } else { ", " },
+                capital.sol_balance,
+                capital.gas_reserve_sol
+            );
+            check(PreTradeCheck::WalletBalance, funded && gas, detail);
+        }
+    }
+
+    let drift_collateral = config.execution.perp_routing == PerpRouting::Drift;
+    match (&inputs.capital, order.leg) {
+        (Ok(capital), OrderLeg::Perp) if order.adds_exposure && drift_collateral => {
+            let supported = capital.max_perp_size(inputs.perp_price, config.trading.max_leverage);
+            check(
+                PreTradeCheck::Collateral,
+                supported >= size,
+                format!("${:.2} free collateral supports {:.4} SOL", capital.drift_free_collateral, supported),
+            );
+        }
+        (Err(_), OrderLeg::Perp) if order.adds_exposure && drift_collateral => {
+            check(PreTradeCheck::Collateral, false, "collateral unavailable".to_string());
+        }
+        _ => check(PreTradeCheck::Collateral, true, "not required".to_string()),
+    }
+
+    if order.adds_exposure {
+        let after = inputs.position_sol + size;
+        let notional = after * inputs.spot_price;
+        check(
+            PreTradeCheck::Exposure,
+            after <= config.trading.max_position_size_sol && notional <= config.trading.max_total_exposure_usd,
+            format!(
+                "{:.4} SOL / ${:.0} after the order (max {:.4} SOL / ${:.0})",
+                after, notional, config.trading.max_position_size_sol, config.trading.max_total_exposure_usd
+            ),
+        );
+        check(
+            PreTradeCheck::Reversal,
+            !inputs.reversal_active,
+            if inputs.reversal_active { "funding reversal active" } else { "no reversal" }.to_string(),
+        );
+    } else {
+        check(PreTradeCheck::Exposure, true, "reduces exposure".to_string());
+        check(PreTradeCheck::Reversal, true, "reduces exposure".to_string());
+    }
+
+    check(
+        PreTradeCheck::RpcHealth,
+        inputs.rpc_connected && inputs.rpc_latency_ms <= limits.max_rpc_latency_ms as f64,
+        format!(
+            "{}, {:.0}ms (max {}ms)",
+            if inputs.rpc_connected { "connected" } else { "disconnected" },
+            inputs.rpc_latency_ms,
+            limits.max_rpc_latency_ms
+        ),
+    );
+
+    let mut detail = format!("{} orders in the last minute (max {})", inputs.recent_orders, limits.max_orders_per_minute);
+    if !inputs.degraded.is_empty() {
+        let names: Vec<&str> = inputs.degraded.iter().map(|d| d.as_str()).collect();
+        detail.push_str(&format!(", cooling off: {}", names.join(", ")));
+    }
+    check(
+        PreTradeCheck::RateLimit,
+        inputs.recent_orders < limits.max_orders_per_minute as usize && inputs.degraded.is_empty(),
+        detail,
+    );
+
+    PreTradeChecklist { order, timestamp: inputs.now, results }
+}
+
+#[derive(Debug, Deserialize)]
+struct TickerResponse {
+    price: String,
+}
+
+/// Reads the checklist inputs and runs it before each live order
+pub struct PreTradeChecker {
+    config: Arc<AppConfig>,
+    state: Arc<SharedState>,
+    client: reqwest::Client,
+    /// Wallet and Drift balances; set with the payer
+    balances: Option<BalanceFetcher>,
+    /// Submission times of recent orders (ms)
+    orders: Mutex<VecDeque<i64>>,
+}
+
+impl PreTradeChecker {
+    pub fn new(config: Arc<AppConfig>, state: Arc<SharedState>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(3))
+            .build()
+            .context("Failed to create HTTP client")?;
+        Ok(Self {
+            config,
+            state,
+            client,
+            balances: None,
+            orders: Mutex::new(VecDeque::new()),
+        })
+    }
+
+    /// Read wallet and collateral balances with `balances`
+    pub fn with_balances(mut self, balances: BalanceFetcher) -> Self {
+        self.balances = Some(balances);
+        self
+    }
+
+    /// CEX ticker price for the spot market
+    async fn cex_price(&self) -> Result<f64> {
+        let cex = &self.config.execution.cex;
+        let url = format!("{}/api/v3/ticker/price?symbol={}", cex.api_url, cex.symbol);
+        let ticker: TickerResponse = self.client.get(&url).send().await?.error_for_status()?.json().await?;
+        ticker.price.parse().context("Invalid CEX ticker price")
+    }
+
+    /// Read the inputs for `order`. `venue_balance` replaces the wallet's
+    /// SOL and USDC when the spot leg trades on an exchange.
+    async fn inputs(&self, order: &PreTradeOrder, venue_balance: Option<(f64, f64)>) -> PreTradeInputs {
+        let cex_price = if self.config.execution.pre_trade.cex_reference {
+            Some(self.cex_price().await.map_err(|e| e.to_string()))
+        } else {
+            None
+        };
+        let capital = match &self.balances {
+            Some(balances) => balances.fetch().await.map_err(|e| format!("{:#}", e)),
+            None => Err("no wallet".to_string()),
+        };
+        let venue_balance = venue_balance.filter(|_| order.leg == OrderLeg::Spot);
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let market = self.state.snapshot();
+        let position = match order.leg {
+            OrderLeg::Spot => self.state.spot_position.read().as_ref().map(|p| p.size.abs()),
+            OrderLeg::Perp => self.state.perp_position.read().as_ref().map(|p| p.size.abs()),
+        };
+        let uses = match order.leg {
+            OrderLeg::Spot if venue_balance.is_none() => vec![Dependency::Jupiter],
+            OrderLeg::Spot => vec![],
+            OrderLeg::Perp if self.config.execution.perp_routing == PerpRouting::Drift => vec![Dependency::DriftData],
+            OrderLeg::Perp => vec![],
+        };
+        let recent_orders = {
+            let mut orders = self.orders.lock();
+            while orders.front().is_some_and(|t| now - t > RATE_WINDOW_MS) {
+                orders.pop_front();
+            }
+            orders.len()
+        };
+        let reversal_at = self.state.funding_reversal_at.load(Ordering::Relaxed);
+
+        PreTradeInputs {
+            now,
+            last_price_update: self.state.last_price_update.load(Ordering::Relaxed),
+            spot_price: market.spot_price,
+            perp_price: market.perp_mark_price,
+            cex_price,
+            capital,
+            venue_balance,
+            position_sol: position.unwrap_or(0.0),
+            reversal_active: reversal_at > 0 && now - reversal_at < REVERSAL_ACTIVE_MS,
+            rpc_connected: *self.state.rpc_connected.read(),
+            rpc_latency_ms: self.state.rpc_latency_us.load(Ordering::Relaxed) as f64 / 1000.0,
+            recent_orders,
+            degraded: http::degraded().into_iter().filter(|d| uses.contains(d)).collect(),
+        }
+    }
+
+    /// Run the checklist for `order` and log it; a passing order counts
+    /// towards the order rate
+    pub async fn run(&self, order: PreTradeOrder, venue_balance: Option<(f64, f64)>) -> PreTradeChecklist {
+        let inputs = self.inputs(&order, venue_balance).await;
+        let checklist = evaluate(order, &inputs, &self.config);
+        let json = serde_json::to_string(&checklist).unwrap_or_default();
+        if checklist.passed() {
+            self.orders.lock().push_back(inputs.now);
+            if checklist.warnings().next().is_some() {
+                warn!("Pre-trade checklist passed with warnings for reducing {:?} {:+.4} SOL: {}", order.leg, order.size_sol, json);
+            } else {
+                info!("Pre-trade checklist passed for {:?} {:+.4} SOL: {}", order.leg, order.size_sol, json);
+            }
+        } else {
+            for failure in checklist.failures() {
+                record_pre_trade_failure(failure.check.as_str());
+            }
+            warn!("Pre-trade checklist failed for {:?} {:+.4} SOL: {}", order.leg, order.size_sol, json);
+        }
+        checklist
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> PreTradeInputs {
+        PreTradeInputs {
+            now: 10_000,
+            last_price_update: 9_000,
+            spot_price: 150.0,
+            perp_price: 150.3,
+            cex_price: Some(Ok(150.2)),
+            capital: Ok(AvailableCapital {
+                sol_balance: 5.0,
+                usdc_balance: 2_000.0,
+                drift_free_collateral: 1_000.0,
+                gas_reserve_sol: 0.1,
+                timestamp: 9_000,
+            }),
+            venue_balance: None,
+            position_sol: 0.0,
+            reversal_active: false,
+            rpc_connected: true,
+            rpc_latency_ms: 80.0,
+            recent_orders: 1,
+            degraded: vec![],
+        }
+    }
+
+    #[test]
+    fn test_checklist_passes_and_reports_every_failure() {
+        let config = AppConfig::default_for_test();
+        let buy = PreTradeOrder { leg: OrderLeg::Spot, size_sol: 10.0, adds_exposure: true };
+        let checklist = evaluate(buy, &inputs(), &config);
+        assert!(checklist.passed(), "{}", checklist.summary());
+        assert_eq!(checklist.results.len(), 8);
+
+        // 20 SOL needs $3,000 of USDC; stale prices and a reversal also fail
+        let big = PreTradeOrder { size_sol: 20.0, ..buy };
+        let stale = PreTradeInputs { last_price_update: 0, reversal_active: true, ..inputs() };
+        let failed: Vec<_> = evaluate(big, &stale, &config).failures().map(|r| r.check).collect();
+        assert_eq!(
+            failed,
+            vec![PreTradeCheck::PriceFreshness, PreTradeCheck::WalletBalance, PreTradeCheck::Reversal]
+        );
+    }
+
+    #[test]
+    fn test_reducing_orders_skip_exposure_checks() {
+        let config = AppConfig::default_for_test();
+        let close = PreTradeOrder { leg: OrderLeg::Perp, size_sol: 10.0, adds_exposure: false };
+        let inputs = PreTradeInputs { reversal_active: true, position_sol: 5_000.0, ..inputs() };
+        assert!(evaluate(close, &inputs, &config).passed());
+
+        let open = PreTradeOrder { adds_exposure: true, ..close };
+        let failed: Vec<_> = evaluate(open, &inputs, &config).failures().map(|r| r.check).collect();
+        assert_eq!(failed, vec![PreTradeCheck::Exposure, PreTradeCheck::Reversal]);
+    }
+
+    #[test]
+    fn test_reducing_orders_only_warn_in_stressed_markets() {
+        let config = AppConfig::default_for_test();
+        // Stale prices, mark 3% off spot, CEX down, RPC down, breakers open
+        let stressed = PreTradeInputs {
+            last_price_update: 0,
+            perp_price: 154.5,
+            cex_price: Some(Err("timeout".to_string())),
+            rpc_connected: false,
+            degraded: vec![Dependency::DriftData],
+            ..inputs()
+        };
+        let close = PreTradeOrder { leg: OrderLeg::Perp, size_sol: 10.0, adds_exposure: false };
+        let checklist = evaluate(close, &stressed, &config);
+        assert!(checklist.passed(), "{}", checklist.summary());
+        let warned: Vec<_> = checklist.warnings().map(|r| r.check).collect();
+        assert_eq!(
+            warned,
+            vec![
+                PreTradeCheck::PriceFreshness,
+                PreTradeCheck::SpreadSanity,
+                PreTradeCheck::RpcHealth,
+                PreTradeCheck::RateLimit
+            ]
+        );
+
+        // A close still needs gas
+        let broke = PreTradeInputs {
+            capital: Ok(AvailableCapital { sol_balance: 0.05, ..stressed.capital.clone().unwrap() }),
+            ..stressed.clone()
+        };
+        let failed: Vec<_> = evaluate(close, &broke, &config).failures().map(|r| r.check).collect();
+        assert_eq!(failed, vec![PreTradeCheck::WalletBalance]);
+
+        // The same market stops an open
+        let open = PreTradeOrder { adds_exposure: true, ..close };
+        assert!(!evaluate(open, &stressed, &config).passed());
+    }
+}
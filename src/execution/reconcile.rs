@@ -0,0 +1,461 @@
+//! Leg Reconciliation
+//!
+//! A basis trade is two separate executions. If one leg lands and the other
+//! fails, the bot is left directionally exposed. After every open and close
+//! the filled legs are compared against the target; the missing leg is
+//! completed with retries, and if that keeps failing the filled excess is
+//! unwound back to delta-neutral.
+//!
+//! Paper trading reconciles the simulated positions. Live, `VenueLegs`
+//! sends the corrections through the execution manager and reads the perp
+//! leg back from the venue holding it. Slippage, fees and quotes of the
+//! corrections are summed into the outcome so they can be charged to the
+//! trade and price its legs.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::orders::OrderFill;
+use super::tx_builder::OrderSide;
+use super::TradeExecutor;
+use crate::config::ExecutionConfig;
+use crate::position::PositionManager;
+
+/// Executes single-leg corrections
+#[async_trait]
+pub trait LegExecutor: Send + Sync {
+    /// Buy (positive) or sell (negative) spot SOL
+    async fn trade_spot(&self, size: f64) -> Result<LegTrade>;
+    /// Go long (positive) or short (negative) the perp
+    async fn trade_perp(&self, size: f64) -> Result<LegTrade>;
+    /// Signed legs held now
+    async fn leg_fills(&self) -> Result<LegFills>;
+}
+
+/// A confirmed single-leg correction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LegTrade {
+    /// Signed size filled (SOL)
+    pub filled: f64,
+    /// Average fill price (USD per SOL)
+    pub price: f64,
+    /// Measured slippage (USD)
+    pub slippage_usd: f64,
+    /// Venue fees (USD)
+    pub fee_usd: f64,
+}
+
+impl LegTrade {
+    /// A fee-free fill at the reference price
+    pub fn at_reference(filled: f64, price: f64) -> Self {
+        Self { filled, price, slippage_usd: 0.0, fee_usd: 0.0 }
+    }
+
+    /// USD the fill would have traded for at the quote it slipped from
+    fn quoted_usd(&self) -> f64 {
+        self.price * self.filled.abs() - self.slippage_usd * self.filled.signum()
+    }
+}
+
+/// Price per leg (USD per SOL), where known
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LegPrices {
+    pub spot: Option<f64>,
+    pub perp: Option<f64>,
+}
+
+impl LegPrices {
+    pub fn new(spot: f64, perp: f64) -> Self {
+        Self { spot: Some(spot), perp: Some(perp) }
+    }
+
+    /// Each leg's price, falling back to `other`'s
+    pub fn or(self, other: LegPrices) -> Self {
+        Self { spot: self.spot.or(other.spot), perp: self.perp.or(other.perp) }
+    }
+}
+
+/// Size, quotes and fees of the executions on each leg
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LegVolume {
+    spot_sol: f64,
+    spot_quoted_usd: f64,
+    perp_sol: f64,
+    perp_quoted_usd: f64,
+    /// Venue fees paid (USD)
+    pub fees_usd: f64,
+}
+
+impl LegVolume {
+    fn add_spot(&mut self, trade: &LegTrade) {
+        self.spot_sol += trade.filled.abs();
+        self.spot_quoted_usd += trade.quoted_usd();
+        self.fees_usd += trade.fee_usd;
+    }
+
+    fn add_perp(&mut self, trade: &LegTrade) {
+        self.perp_sol += trade.filled.abs();
+        self.perp_quoted_usd += trade.quoted_usd();
+        self.fees_usd += trade.fee_usd;
+    }
+
+    /// Average quote each leg filled against, for legs that executed.
+    /// Fill prices net of their measured slippage, so the slippage is
+    /// charged once, on its own line.
+    pub fn quotes(&self) -> LegPrices {
+        LegPrices {
+            spot: (self.spot_sol > 0.0).then(|| self.spot_quoted_usd / self.spot_sol),
+            perp: (self.perp_sol > 0.0).then(|| self.perp_quoted_usd / self.perp_sol),
+        }
+    }
+}
+
+/// Slippage measured on each leg's fills (USD)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LegSlippage {
+    pub spot: f64,
+    pub perp: f64,
+}
+
+impl LegSlippage {
+    pub fn total(&self) -> f64 {
+        self.spot + self.perp
+    }
+
+    pub fn add(&mut self, other: LegSlippage) {
+        self.spot += other.spot;
+        self.perp += other.perp;
+    }
+}
+
+/// Signed leg positions in SOL (spot long positive, perp short negative)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LegFills {
+    pub spot: f64,
+    pub perp: f64,
+}
+
+impl LegFills {
+    pub fn new(spot: f64, perp: f64) -> Self {
+        Self { spot, perp }
+    }
+
+    /// Net directional exposure (SOL)
+    pub fn net_delta(&self) -> f64 {
+        self.spot + self.perp
+    }
+}
+
+/// How an imbalance was resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum LegAction {
+    /// Legs already matched the target
+    None,
+    /// Missing leg executed
+    Completed,
+    /// Excess filled leg reversed
+    Unwound,
+    /// Could not restore delta-neutrality
+    Failed,
+}
+
+/// Result of a reconciliation pass
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconcileOutcome {
+    pub target: LegFills,
+    pub before: LegFills,
+    pub after: LegFills,
+    pub action: LegAction,
+    /// Corrective executions attempted
+    pub attempts: u32,
+    /// Slippage measured on the corrective executions
+    pub slippage: LegSlippage,
+    /// What the corrective executions traded
+    pub volume: LegVolume,
+}
+
+impl ReconcileOutcome {
+    /// The legs could not be read, so nothing was corrected
+    pub fn unverified(target: LegFills) -> Self {
+        let unknown = LegFills::default();
+        Self {
+            target,
+            before: unknown,
+            after: unknown,
+            action: LegAction::Failed,
+            attempts: 0,
+            slippage: LegSlippage::default(),
+            volume: LegVolume::default(),
+        }
+    }
+
+    /// Whether the legs were out of balance before reconciliation
+    pub fn was_imbalanced(&self) -> bool {
+        self.action != LegAction::None
+    }
+
+    /// Whether the final position is delta-neutral
+    pub fn is_balanced(&self) -> bool {
+        self.action != LegAction::Failed
+    }
+}
+
+/// Live legs: orders go through the executor and confirmed fills are
+/// applied to the tracked positions. The perp leg is read back from the
+/// venue; the spot leg is the confirmed swaps, as the wallet's SOL balance
+/// also holds the gas reserve.
+pub struct VenueLegs {
+    execution: Arc<dyn TradeExecutor>,
+    positions: Arc<PositionManager>,
+}
+
+impl VenueLegs {
+    pub fn new(execution: Arc<dyn TradeExecutor>, positions: Arc<PositionManager>) -> Self {
+        Self { execution, positions }
+    }
+}
+
+#[async_trait]
+impl LegExecutor for VenueLegs {
+    async fn trade_spot(&self, size: f64) -> Result<LegTrade> {
+        let fill = self.execution.trade_spot(size).await?;
+        self.positions.apply_spot_fill(fill.filled, fill.price).await;
+        self.positions.charge_cost(fill.cost_usd).await;
+        Ok(LegTrade { filled: fill.filled, price: fill.price, slippage_usd: fill.slippage_usd, fee_usd: fill.fee_usd })
+    }
+
+    async fn trade_perp(&self, size: f64) -> Result<LegTrade> {
+        // Buying back the short must never flip it long
+        let fill = self.execution.trade_perp(size, size > 0.0).await?;
+        self.positions.apply_perp_fill(&OrderFill {
+            order_id: fill.signature.clone(),
+            side: if fill.filled > 0.0 { OrderSide::Long } else { OrderSide::Short },
+            size: fill.filled.abs(),
+            price: fill.price,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }).await;
+        self.positions.charge_cost(fill.cost_usd).await;
+        Ok(LegTrade { filled: fill.filled, price: fill.price, slippage_usd: fill.slippage_usd, fee_usd: fill.fee_usd })
+    }
+
+    async fn leg_fills(&self) -> Result<LegFills> {
+        let spot = self.positions.leg_fills().await.spot;
+        Ok(LegFills::new(spot, self.execution.perp_position().await?))
+    }
+}
+
+/// Completes or unwinds one-legged fills
+pub struct LegReconciler {
+    /// Attempts per corrective phase
+    max_retries: u32,
+    /// Delay between attempts
+    retry_delay: Duration,
+    /// Leg mismatch treated as balanced (SOL)
+    tolerance: f64,
+}
+
+impl LegReconciler {
+    pub fn new(config: &ExecutionConfig) -> Self {
+        Self {
+            max_retries: config.max_retries.max(1),
+            retry_delay: Duration::from_millis(config.retry_delay_ms),
+            tolerance: config.leg_tolerance_sol,
+        }
+    }
+
+    /// Bring `fills` to `target`, completing missing legs first and
+    /// unwinding the excess if completion fails
+    pub async fn reconcile(
+        &self,
+        executor: &dyn LegExecutor,
+        target: LegFills,
+        fills: LegFills,
+    ) -> ReconcileOutcome {
+        let before = fills;
+        let mut current = fills;
+        let mut attempts = 0;
+        let mut slippage = LegSlippage::default();
+        let mut volume = LegVolume::default();
+
+        if self.matches(current, target) {
+            return ReconcileOutcome { target, before, after: current, action: LegAction::None, attempts, slippage, volume };
+        }
+
+        warn!(
+            "Leg imbalance: spot {:.4} / perp {:.4}, target {:.4} / {:.4}",
+            current.spot, current.perp, target.spot, target.perp
+        );
+
+        // Phase 1: complete the missing leg(s)
+        for _ in 0..self.max_retries {
+            attempts += 1;
+            current = self.step(executor, current, target, &mut slippage, &mut volume).await;
+            if self.matches(current, target) {
+                info!("Legs completed after {} attempt(s)", attempts);
+                return ReconcileOutcome { target, before, after: current, action: LegAction::Completed, attempts, slippage, volume };
+            }
+            tokio::time::sleep(self.retry_delay).await;
+        }
+
+        // Phase 2: the leg that reached its target is the one that works;
+        // reverse it until it offsets whatever the stuck leg holds
+        let spot_reached = (current.spot - target.spot).abs() <= self.tolerance;
+        for _ in 0..self.max_retries {
+            let delta = current.net_delta();
+            if delta.abs() <= self.tolerance {
+                info!("Excess leg unwound, net delta {:.4} SOL", delta);
+                return ReconcileOutcome { target, before, after: current, action: LegAction::Unwound, attempts, slippage, volume };
+            }
+
+            attempts += 1;
+            let result = if spot_reached {
+                executor.trade_spot(-delta).await
+            } else {
+                executor.trade_perp(-delta).await
+            };
+            match result {
+                Ok(trade) if spot_reached => {
+                    current.spot += trade.filled;
+                    slippage.spot += trade.slippage_usd;
+                    volume.add_spot(&trade);
+                }
+                Ok(trade) => {
+                    current.perp += trade.filled;
+                    slippage.perp += trade.slippage_usd;
+                    volume.add_perp(&trade);
+                }
+                Err(e) => {
+                    warn!("Unwind attempt {} failed: {}", attempts, e);
+                    tokio::time::sleep(self.retry_delay).await;
+                }
+            }
+        }
+
+        let action = if current.net_delta().abs() <= self.tolerance {
+            LegAction::Unwound
+        } else {
+            LegAction::Failed
+        };
+        ReconcileOutcome { target, before, after: current, action, attempts, slippage, volume }
+    }
+
+    /// One attempt at executing each missing leg, adding measured slippage
+    /// to `slippage` and the executions to `volume`
+    async fn step(
+        &self,
+        executor: &dyn LegExecutor,
+        mut current: LegFills,
+        target: LegFills,
+        slippage: &mut LegSlippage,
+        volume: &mut LegVolume,
+    ) -> LegFills {
+        let spot_missing = target.spot - current.spot;
+        if spot_missing.abs() > self.tolerance {
+            match executor.trade_spot(spot_missing).await {
+                Ok(trade) => {
+                    current.spot += trade.filled;
+                    slippage.spot += trade.slippage_usd;
+                    volume.add_spot(&trade);
+                }
+                Err(e) => warn!("Spot leg completion failed: {}", e),
+            }
+        }
+
+        let perp_missing = target.perp - current.perp;
+        if perp_missing.abs() > self.tolerance {
+            match executor.trade_perp(perp_missing).await {
+                Ok(trade) => {
+                    current.perp += trade.filled;
+                    slippage.perp += trade.slippage_usd;
+                    volume.add_perp(&trade);
+                }
+                Err(e) => warn!("Perp leg completion failed: {}", e),
+            }
+        }
+
+        current
+    }
+
+    fn matches(&self, current: LegFills, target: LegFills) -> bool {
+        (current.spot - target.spot).abs() <= self.tolerance
+            && (current.perp - target.perp).abs() <= self.tolerance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    /// Spot always fills; perp always fails
+    struct BrokenPerp;
+
+    #[async_trait]
+    impl LegExecutor for BrokenPerp {
+        async fn trade_spot(&self, size: f64) -> Result<LegTrade> {
+            Ok(LegTrade::at_reference(size, 100.0))
+        }
+
+        async fn trade_perp(&self, _size: f64) -> Result<LegTrade> {
+            anyhow::bail!("perp order rejected")
+        }
+
+        async fn leg_fills(&self) -> Result<LegFills> {
+            Ok(LegFills::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unwinds_spot_when_perp_fails() {
+        let mut config = AppConfig::default_for_test().execution;
+        config.retry_delay_ms = 0;
+        let reconciler = LegReconciler::new(&config);
+
+        // Spot landed, perp did not
+        let outcome = reconciler
+            .reconcile(&BrokenPerp, LegFills::new(10.0, -10.0), LegFills::new(10.0, 0.0))
+            .await;
+
+        assert_eq!(outcome.action, LegAction::Unwound);
+        assert!(outcome.after.spot.abs() < 1e-9);
+        assert!(outcome.is_balanced());
+    }
+
+    #[tokio::test]
+    async fn test_venue_legs_complete_through_the_executor() {
+        use crate::execution::{MockExecution, MockLeg};
+        use crate::state::SharedState;
+
+        let mut config = AppConfig::default_for_test().execution;
+        config.retry_delay_ms = 0;
+        let reconciler = LegReconciler::new(&config);
+        let state = Arc::new(SharedState::new());
+        state.update_spot_price(100.0);
+        state.update_perp_mark_price(100.5);
+        let execution = Arc::new(MockExecution::new(state.clone()).with_slippage_bps(10.0));
+        let positions = Arc::new(PositionManager::new(state));
+        let legs = VenueLegs::new(execution.clone(), positions.clone());
+
+        // Nothing filled yet: both legs are sent to the venues
+        let fills = legs.leg_fills().await.unwrap();
+        let outcome = reconciler.reconcile(&legs, LegFills::new(2.0, -2.0), fills).await;
+
+        assert_eq!(outcome.action, LegAction::Completed);
+        assert_eq!(execution.net_filled(MockLeg::Spot), 2.0);
+        assert_eq!(execution.net_filled(MockLeg::Perp), -2.0);
+        assert_eq!(positions.leg_fills().await, LegFills::new(2.0, -2.0));
+
+        // 10 bps on 2 SOL of each leg
+        assert!((outcome.slippage.spot - 0.2).abs() < 1e-9);
+        assert!((outcome.slippage.perp - 0.201).abs() < 1e-9);
+
+        // Fills net of their slippage are the quotes they traded against
+        let quotes = outcome.volume.quotes();
+        assert!((quotes.spot.unwrap() - 100.0).abs() < 1e-9);
+        assert!((quotes.perp.unwrap() - 100.5).abs() < 1e-9);
+    }
+}
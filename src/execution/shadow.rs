@@ -0,0 +1,367 @@
+//! Dry-run (shadow) execution
+//!
+//! Exercises the full live execution path against mainnet without ever
+//! submitting: Jupiter quote and swap transaction, Drift order transaction,
+//! Jito bundle assembly and RPC simulation of every transaction. Each run
+//! produces a `ShadowReport` with simulated compute, fees and would-be fills,
+//! so account and IDL problems surface before any funds are at risk.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use solana_sdk::{
+    packet::PACKET_DATA_SIZE,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, VersionedTransaction},
+};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::jito::JitoClient;
+use super::jupiter::{JupiterClient, SwapResult};
+use super::simulator::{SimulationResult, TransactionSimulator};
+use super::tx_builder::{BasisTradeParams, OrderSide, OrderType, TransactionBuilder};
+use crate::config::AppConfig;
+use crate::network::{HttpRetryPolicy, RpcManager};
+use crate::utils::amount::{from_base_units, to_base_units, Amount, USDC_DECIMALS};
+use crate::utils::types::Dependency;
+
+/// Base fee per signature (lamports)
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+/// Transactions accepted in one Jito bundle
+const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+/// Simulation of a single transaction in the dry run
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowTx {
+    /// "spot_swap", "perp_order" or "jito_tip"
+    pub leg: &'static str,
+    pub success: bool,
+    pub compute_units: Option<u64>,
+    /// Serialized size (bytes); must fit in one packet
+    pub size_bytes: usize,
+    pub error: Option<String>,
+    /// Last program logs, for diagnosing failures
+    pub logs_tail: Vec<String>,
+}
+
+impl ShadowTx {
+    fn new(leg: &'static str, size_bytes: usize, sim: Result<SimulationResult>) -> Self {
+        match sim {
+            Ok(sim) => Self {
+                leg,
+                success: sim.success,
+                compute_units: sim.compute_units,
+                size_bytes,
+                error: sim.error,
+                logs_tail: sim.logs.iter().rev().take(5).rev().cloned().collect(),
+            },
+            Err(e) => Self {
+                leg,
+                success: false,
+                compute_units: None,
+                size_bytes,
+                error: Some(format!("{:#}", e)),
+                logs_tail: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Outcome of a dry-run open or close
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowReport {
+    /// "open" or "close"
+    pub action: &'static str,
+    pub size_sol: f64,
+    /// Would-be spot fill from the Jupiter quote (USD per SOL)
+    pub spot_fill_price: Option<f64>,
+    /// Worst spot fill allowed by the slippage tolerance (USD per SOL)
+    pub spot_worst_price: Option<f64>,
+    pub spot_price_impact_pct: Option<f64>,
+    /// Would-be perp fill (limit price, or reference price for market orders)
+    pub perp_fill_price: f64,
+    pub transactions: Vec<ShadowTx>,
+    /// Signature plus priority fees across all transactions (lamports)
+    pub network_fee_lamports: u64,
+    /// Jito tip, when bundles are enabled (lamports)
+    pub jito_tip_lamports: u64,
+    /// Problems that would have blocked submission
+    pub issues: Vec<String>,
+}
+
+impl ShadowReport {
+    /// Whether every transaction simulated cleanly
+    pub fn would_succeed(&self) -> bool {
+        self.issues.is_empty() && self.transactions.iter().all(|t| t.success)
+    }
+
+    /// Total compute units consumed across transactions
+    pub fn compute_units(&self) -> u64 {
+        self.transactions.iter().filter_map(|t| t.compute_units).sum()
+    }
+
+    /// Total cost of landing the trade (SOL)
+    pub fn total_fee_sol(&self) -> f64 {
+        Amount::lamports(self.network_fee_lamports + self.jito_tip_lamports).ui()
+    }
+
+    /// Log a one-line summary, plus any failures
+    pub fn log(&self) {
+        info!(
+            "Dry-run {} {:.4} SOL: spot fill {} (impact {:.4}%), perp fill ${:.4}, {} CU, fees {:.6} SOL, {}",
+            self.action,
+            self.size_sol,
+            self.spot_fill_price.map(|p| format!("${:.4}", p)).unwrap_or_else(|| "n/a".to_string()),
+            self.spot_price_impact_pct.unwrap_or(0.0),
+            self.perp_fill_price,
+            self.compute_units(),
+            self.total_fee_sol(),
+            if self.would_succeed() { "would succeed" } else { "WOULD FAIL" },
+        );
+        for issue in &self.issues {
+            warn!("Dry-run {}: {}", self.action, issue);
+        }
+        for tx in self.transactions.iter().filter(|t| !t.success) {
+            warn!("Dry-run {} {} failed: {:?} {:?}", self.action, tx.leg, tx.error, tx.logs_tail);
+        }
+    }
+}
+
+/// Builds and simulates real transactions without submitting them
+pub struct ShadowExecutor {
+    /// Configuration
+    config: Arc<AppConfig>,
+    /// Transaction builder
+    tx_builder: TransactionBuilder,
+    /// Jupiter client for swaps
+    jupiter: JupiterClient,
+    /// Jito client, when bundles are enabled
+    jito: Option<JitoClient>,
+    /// Transaction simulator
+    simulator: TransactionSimulator,
+    /// Wallet the transactions are built for (signs, never submits)
+    payer: Keypair,
+}
+
+impl ShadowExecutor {
+    /// Create a new shadow executor
+    pub fn new(config: Arc<AppConfig>, rpc: Arc<RpcManager>, payer: Keypair) -> Result<Self> {
+        let retry = HttpRetryPolicy::new(&config.http);
+        let jito = if config.execution.use_jito {
+            Some(JitoClient::new(&config.execution)?.with_retry_policy(retry.clone().for_dependency(Dependency::Jito)))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            tx_builder: TransactionBuilder::new(config.clone(), rpc.clone())?,
+            jupiter: JupiterClient::new(&config.protocols.jupiter)?
+                .with_retry_policy(retry.for_dependency(Dependency::Jupiter)),
+            jito,
+            simulator: TransactionSimulator::new(rpc),
+            payer,
+            config,
+        })
+    }
+
+    /// Slippage tolerance in basis points
+    fn slippage_bps(&self) -> u16 {
+        (self.config.trading.slippage_tolerance_pct * 100.0).round() as u16
+    }
+
+    /// Dry-run opening a basis trade: buy spot via Jupiter, short the perp
+    pub async fn dry_run_open(
+        &self,
+        size_sol: f64,
+        spot_price: f64,
+        perp_price: f64,
+        perp_order_type: OrderType,
+    ) -> Result<ShadowReport> {
+        let usdc_in = Amount::usdc(size_sol * spot_price)?.base_units()?;
+        let quote = self.jupiter
+            .get_usdc_to_sol_quote(usdc_in, self.slippage_bps())
+            .await
+            .context("Dry-run spot quote failed")?;
+
+        let params = BasisTradeParams {
+            spot_amount_sol: size_sol,
+            perp_size: Amount::drift_base(size_sol)?.base_units()?,
+            perp_side: OrderSide::Short,
+            perp_order_type,
+            perp_price: (perp_order_type == OrderType::Limit)
+                .then(|| Amount::drift_price(perp_price)?.base_units())
+                .transpose()?,
+            perp_post_only: false,
+            slippage_bps: self.slippage_bps(),
+        };
+        let perp_tx = self.tx_builder
+            .build_basis_trade(&self.payer, &params, Vec::new())
+            .await
+            .context("Dry-run perp order build failed")?;
+
+        let swap = self.jupiter
+            .get_swap_transaction(&quote, &self.payer.pubkey(), None)
+            .await;
+
+        let mut report = self.simulate("open", size_sol, perp_price, swap, perp_tx).await;
+        let spot_decimals = self.jupiter.spot_decimals();
+        if let (Ok(usdc), Ok(units)) = (quote.in_amount.parse::<u64>(), quote.out_amount.parse::<u64>()) {
+            if units > 0 {
+                report.spot_fill_price = Some(from_base_units(usdc, USDC_DECIMALS) / from_base_units(units, spot_decimals));
+            }
+        }
+        if let Ok(min_units) = quote.other_amount_threshold.parse::<u64>() {
+            if min_units > 0 {
+                report.spot_worst_price = Some(from_base_units(usdc_in, USDC_DECIMALS) / from_base_units(min_units, spot_decimals));
+            }
+        }
+        Ok(report)
+    }
+
+    /// Dry-run closing a basis trade: sell spot via Jupiter, buy back the perp
+    pub async fn dry_run_close(&self, size_sol: f64, perp_price: f64) -> Result<ShadowReport> {
+        let lamports_in = to_base_units(size_sol, self.jupiter.spot_decimals())?;
+        let quote = self.jupiter
+            .get_sol_to_usdc_quote(lamports_in, self.slippage_bps())
+            .await
+            .context("Dry-run spot quote failed")?;
+
+        let perp_tx = self.tx_builder
+            .build_close_position(
+                &self.payer,
+                lamports_in,
+                Amount::drift_base(size_sol)?.base_units()?,
+                OrderSide::Short,
+            )
+            .await
+            .context("Dry-run perp close build failed")?;
+
+        let swap = self.jupiter
+            .get_swap_transaction(&quote, &self.payer.pubkey(), None)
+            .await;
+
+        let mut report = self.simulate("close", size_sol, perp_price, swap, perp_tx).await;
+        if let Ok(usdc) = quote.out_amount.parse::<u64>() {
+            report.spot_fill_price = Some(from_base_units(usdc, USDC_DECIMALS) / size_sol);
+        }
+        if let Ok(min_usdc) = quote.other_amount_threshold.parse::<u64>() {
+            report.spot_worst_price = Some(from_base_units(min_usdc, USDC_DECIMALS) / size_sol);
+        }
+        Ok(report)
+    }
+
+    /// Assemble the bundle and simulate both legs
+    async fn simulate(
+        &self,
+        action: &'static str,
+        size_sol: f64,
+        perp_price: f64,
+        swap: Result<SwapResult>,
+        perp_tx: Transaction,
+    ) -> ShadowReport {
+        let mut transactions = Vec::new();
+        let mut issues = Vec::new();
+        let mut spot_price_impact_pct = None;
+        let mut signatures = 0u64;
+
+        match swap.and_then(|s| {
+            let tx: VersionedTransaction = bincode::deserialize(&s.transaction_data)
+                .context("Failed to decode Jupiter swap transaction")?;
+            Ok((s, tx))
+        }) {
+            Ok((swap, swap_tx)) => {
+                spot_price_impact_pct = Some(swap.price_impact_pct);
+                signatures += swap_tx.signatures.len() as u64;
+                let sim = self.simulator.simulate(&swap_tx).await;
+                transactions.push(ShadowTx::new("spot_swap", swap.transaction_data.len(), sim));
+            }
+            Err(e) => issues.push(format!("Spot swap transaction unavailable: {:#}", e)),
+        }
+
+        let perp_size = bincode::serialized_size(&perp_tx).unwrap_or(0) as usize;
+        signatures += perp_tx.signatures.len() as u64;
+        let sim = self.simulator.simulate(&perp_tx).await;
+        transactions.push(ShadowTx::new("perp_order", perp_size, sim));
+
+        // Bundles carry the tip as one more transfer; the bundle is
+        // assembled only to check it would be accepted, never sent
+        let jito_tip_lamports = match &self.jito {
+            Some(jito) => {
+                let tip_lamports = jito.tip_for(None, perp_price).await;
+                let tip_tx = Transaction::new_signed_with_payer(
+                    &[jito.create_tip_instruction(&self.payer.pubkey(), tip_lamports)],
+                    Some(&self.payer.pubkey()),
+                    &[&self.payer],
+                    perp_tx.message.recent_blockhash,
+                );
+                signatures += tip_tx.signatures.len() as u64;
+                let tip_size = bincode::serialized_size(&tip_tx).unwrap_or(0) as usize;
+                let sim = self.simulator.simulate(&tip_tx).await;
+                transactions.push(ShadowTx::new("jito_tip", tip_size, sim));
+                if transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+                    issues.push(format!(
+                        "Bundle has {} transactions, limit is {}",
+                        transactions.len(), MAX_BUNDLE_TRANSACTIONS
+                    ));
+                }
+                tip_lamports
+            }
+            None => 0,
+        };
+
+        for tx in &transactions {
+            if tx.size_bytes > PACKET_DATA_SIZE {
+                issues.push(format!(
+                    "{} transaction is {} bytes, over the {} byte packet limit",
+                    tx.leg, tx.size_bytes, PACKET_DATA_SIZE
+                ));
+            }
+        }
+
+        let priority_fee = self.tx_builder.priority_fee().await.unwrap_or(0);
+        let priority_lamports = priority_fee * self.tx_builder.compute_unit_limit() as u64 / 1_000_000;
+
+        ShadowReport {
+            action,
+            size_sol,
+            spot_fill_price: None,
+            spot_worst_price: None,
+            spot_price_impact_pct,
+            perp_fill_price: perp_price,
+            transactions,
+            network_fee_lamports: signatures * LAMPORTS_PER_SIGNATURE + priority_lamports,
+            jito_tip_lamports,
+            issues,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_outcome() {
+        let failed = ShadowTx::new("perp_order", 400, Err(anyhow::anyhow!("AccountNotFound")));
+        let mut report = ShadowReport {
+            action: "open",
+            size_sol: 1.0,
+            spot_fill_price: Some(150.0),
+            spot_worst_price: Some(150.75),
+            spot_price_impact_pct: Some(0.01),
+            perp_fill_price: 150.3,
+            transactions: vec![failed],
+            network_fee_lamports: 10_000,
+            jito_tip_lamports: 10_000,
+            issues: Vec::new(),
+        };
+        assert!(!report.would_succeed());
+        assert!((report.total_fee_sol() - 0.00002).abs() < 1e-12);
+
+        report.transactions[0].success = true;
+        report.transactions[0].compute_units = Some(42_000);
+        assert!(report.would_succeed());
+        assert_eq!(report.compute_units(), 42_000);
+    }
+}
@@ -6,6 +6,7 @@
 //! - Balance checks
 
 use anyhow::{Context, Result};
+use solana_client::rpc_client::SerializableTransaction;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     transaction::Transaction,
@@ -42,8 +43,8 @@ impl TransactionSimulator {
         Self { rpc }
     }
     
-    /// Simulate a transaction
-    pub async fn simulate(&self, transaction: &Transaction) -> Result<SimulationResult> {
+    /// Simulate a transaction (legacy or versioned)
+    pub async fn simulate(&self, transaction: &impl SerializableTransaction) -> Result<SimulationResult> {
         debug!("Simulating transaction...");
         
         let result = self.rpc.simulate_transaction(transaction).await?;
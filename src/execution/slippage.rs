@@ -0,0 +1,239 @@
+//! Realized Slippage
+//!
+//! Every fill is compared with the price it was quoted at:
+//! - Spot swaps: the wallet's balance change against the swap quote, so
+//!   the realized price reflects what the pool actually paid out
+//! - Routed perp orders: the venue's fill price against the mark
+//!
+//! Slippage is kept in rolling windows per leg and size bucket. With
+//! `trading.adaptive_slippage` enabled, a spot swap's tolerance is its
+//! bucket's mean slippage plus `stdev_multiplier` standard deviations,
+//! clamped to the configured bounds: consistent fills tighten it, scattered
+//! ones (volatile markets) widen it. Buckets with fewer than `min_samples`
+//! fills use `trading.slippage_tolerance_pct`.
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+use crate::config::AdaptiveSlippageConfig;
+use crate::telemetry::{record_slippage, record_slippage_tolerance};
+
+/// Leg a fill was on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlippageLeg {
+    Spot,
+    Perp,
+}
+
+impl SlippageLeg {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Spot => "spot",
+            Self::Perp => "perp",
+        }
+    }
+}
+
+/// Rolling slippage of one leg and size bucket
+#[derive(Debug, Clone, Serialize)]
+pub struct SlippageStats {
+    pub leg: SlippageLeg,
+    /// Bucket label, e.g. "1-10"
+    pub bucket: String,
+    pub count: usize,
+    pub mean_bps: f64,
+    pub stdev_bps: f64,
+    pub p90_bps: f64,
+    pub max_bps: f64,
+}
+
+/// Adverse slippage of a fill at `fill_price` against `quoted_price` (bps):
+/// positive when a buy paid more or a sell received less
+pub fn slippage_bps(size_sol: f64, quoted_price: f64, fill_price: f64) -> f64 {
+    let worse = if size_sol > 0.0 { fill_price - quoted_price } else { quoted_price - fill_price };
+    worse / quoted_price * 10_000.0
+}
+
+/// SOL and USDC a swap actually moved, from wallet balances before and
+/// after it. A sell's SOL input is exact; a buy's USDC input is exact.
+/// Falls back to the quoted amount when a measured one is not positive or
+/// is more than 50% off the quote (another transfer landed in between).
+pub fn realized_swap_amounts(
+    size_sol: f64,
+    before: (f64, f64),
+    after: (f64, f64),
+    quoted: (f64, f64),
+) -> (f64, f64) {
+    let plausible = |measured: f64, quoted: f64| {
+        if measured > 0.0 && (measured - quoted).abs() <= quoted * 0.5 {
+            measured
+        } else {
+            quoted
+        }
+    };
+    if size_sol > 0.0 {
+        (plausible(after.0 - before.0, quoted.0), quoted.1)
+    } else {
+        (quoted.0, plausible(after.1 - before.1, quoted.1))
+    }
+}
+
+/// Rolling realized slippage per leg and size bucket
+pub struct SlippageTracker {
+    config: AdaptiveSlippageConfig,
+    /// `trading.slippage_tolerance_pct`
+    base_tolerance_pct: f64,
+    windows: Mutex<HashMap<(SlippageLeg, usize), VecDeque<f64>>>,
+}
+
+impl SlippageTracker {
+    pub fn new(config: &AdaptiveSlippageConfig, base_tolerance_pct: f64) -> Self {
+        Self {
+            config: config.clone(),
+            base_tolerance_pct,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Size bucket index for `size_sol`
+    fn bucket(&self, size_sol: f64) -> usize {
+        let size = size_sol.abs();
+        self.config
+            .bucket_bounds_sol
+            .iter()
+            .position(|bound| size < *bound)
+            .unwrap_or(self.config.bucket_bounds_sol.len())
+    }
+
+    fn bucket_label(&self, bucket: usize) -> String {
+        let bounds = &self.config.bucket_bounds_sol;
+        match (bucket.checked_sub(1).map(|i| bounds[i]), bounds.get(bucket)) {
+            (None, Some(upper)) => format!("<{}", upper),
+            (Some(lower), Some(upper)) => format!("{}-{}", lower, upper),
+            (Some(lower), None) => format!("{}+", lower),
+            (None, None) => "all".to_string(),
+        }
+    }
+
+    /// Record a fill; returns its slippage (bps)
+    pub fn record(&self, leg: SlippageLeg, size_sol: f64, quoted_price: f64, fill_price: f64) -> f64 {
+        let bps = slippage_bps(size_sol, quoted_price, fill_price);
+        let bucket = self.bucket(size_sol);
+        {
+            let mut windows = self.windows.lock();
+            let window = windows.entry((leg, bucket)).or_default();
+            window.push_back(bps);
+            while window.len() > self.config.window {
+                window.pop_front();
+            }
+        }
+        record_slippage(leg.as_str(), self.bucket_label(bucket), bps);
+        if leg == SlippageLeg::Spot {
+            record_slippage_tolerance(self.bucket_label(bucket), self.tolerance_pct(size_sol));
+        }
+        bps
+    }
+
+    /// Spot swap tolerance for `size_sol` (%)
+    pub fn tolerance_pct(&self, size_sol: f64) -> f64 {
+        if !self.config.enabled {
+            return self.base_tolerance_pct;
+        }
+        let windows = self.windows.lock();
+        match windows.get(&(SlippageLeg::Spot, self.bucket(size_sol))) {
+            Some(window) if window.len() >= self.config.min_samples => {
+                let (mean, stdev) = mean_stdev(window);
+                let bps = mean.max(0.0) + self.config.stdev_multiplier * stdev;
+                (bps / 100.0).clamp(self.config.min_tolerance_pct, self.config.max_tolerance_pct)
+            }
+            _ => self.base_tolerance_pct,
+        }
+    }
+
+    /// Statistics for every leg and bucket with fills
+    pub fn stats(&self) -> Vec<SlippageStats> {
+        let windows = self.windows.lock();
+        let mut stats: Vec<_> = windows
+            .iter()
+            .filter(|(_, window)| !window.is_empty())
+            .map(|(&(leg, bucket), window)| {
+                let (mean_bps, stdev_bps) = mean_stdev(window);
+                let mut sorted: Vec<f64> = window.iter().copied().collect();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                let p90 = sorted[((sorted.len() - 1) as f64 * 0.9).round() as usize];
+                SlippageStats {
+                    leg,
+                    bucket: self.bucket_label(bucket),
+                    count: window.len(),
+                    mean_bps,
+                    stdev_bps,
+                    p90_bps: p90,
+                    max_bps: sorted[sorted.len() - 1],
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| (a.leg.as_str(), &a.bucket).cmp(&(b.leg.as_str(), &b.bucket)));
+        stats
+    }
+}
+
+fn mean_stdev(window: &VecDeque<f64>) -> (f64, f64) {
+    let n = window.len() as f64;
+    let mean = window.iter().sum::<f64>() / n;
+    let variance = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> SlippageTracker {
+        let config = AdaptiveSlippageConfig { enabled: true, min_samples: 3, ..Default::default() };
+        SlippageTracker::new(&config, 0.5)
+    }
+
+    #[test]
+    fn test_tolerance_adapts_per_bucket() {
+        let tracker = tracker();
+        assert_eq!(slippage_bps(1.0, 100.0, 100.1).round(), 10.0);
+        assert_eq!(slippage_bps(-1.0, 100.0, 100.1).round(), -10.0);
+
+        // Too few fills: configured tolerance
+        tracker.record(SlippageLeg::Spot, 2.0, 100.0, 100.02);
+        assert_eq!(tracker.tolerance_pct(2.0), 0.5);
+
+        // Consistent 2 bps fills tighten to the floor
+        tracker.record(SlippageLeg::Spot, 3.0, 100.0, 100.02);
+        tracker.record(SlippageLeg::Spot, -4.0, 100.0, 99.98);
+        assert_eq!(tracker.tolerance_pct(5.0), 0.1);
+        // Other buckets are unaffected
+        assert_eq!(tracker.tolerance_pct(20.0), 0.5);
+
+        // Scattered fills widen it, up to the cap
+        for slipped in [100.3, 100.0, 100.6] {
+            tracker.record(SlippageLeg::Spot, 20.0, 100.0, slipped);
+        }
+        let wide = tracker.tolerance_pct(20.0);
+        assert!(wide > 0.5 && wide <= 1.0);
+
+        let stats = tracker.stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].bucket, "1-10");
+        assert_eq!(stats[0].count, 3);
+    }
+
+    #[test]
+    fn test_realized_swap_amounts() {
+        // Buy: 150 USDC in, 0.995 SOL measured against 1.0 quoted
+        let (sol, usdc) = realized_swap_amounts(1.0, (2.0, 500.0), (2.995, 350.0), (1.0, 150.0));
+        assert!((sol - 0.995).abs() < 1e-9);
+        assert_eq!(usdc, 150.0);
+
+        // Sell with an unrelated deposit in between: quoted amount kept
+        let (sol, usdc) = realized_swap_amounts(-1.0, (2.0, 500.0), (1.0, 1_650.0), (1.0, 150.0));
+        assert_eq!((sol, usdc), (1.0, 150.0));
+    }
+}
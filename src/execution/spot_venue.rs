@@ -0,0 +1,208 @@
+//! Spot Venues
+//!
+//! The spot leg's swap behind one interface:
+//! - `SpotVenue`: prepares a SOL/USDC swap for the execution manager to
+//!   sign and submit
+//! - `JupiterSpot`: the Jupiter aggregator, taking any size
+//! - `SpotRouter`: sends small orders to a direct pool (`OrcaWhirlpoolVenue`)
+//!   under its size limit, skipping the aggregator API round trip, and
+//!   everything else (or a direct venue that cannot quote) to the aggregator
+//!
+//! Swaps carry the time they were quoted, so the execution manager can
+//! re-quote one that has gone stale before building its transaction.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use solana_sdk::{message::VersionedMessage, pubkey::Pubkey, transaction::VersionedTransaction};
+use std::sync::Arc;
+
+use super::jupiter::JupiterClient;
+use crate::utils::amount::{from_base_units, to_base_units, Amount, USDC_DECIMALS};
+
+/// A swap ready to be signed, with the amounts it was quoted at
+#[derive(Debug, Clone)]
+pub struct SpotSwap {
+    /// Unsigned message; the blockhash is set on signing
+    pub message: VersionedMessage,
+    /// Input amount (USDC units for buys, spot token units for sells)
+    pub input_amount: u64,
+    /// Quoted output amount (spot token units for buys, USDC units for sells)
+    pub output_amount: u64,
+    /// Minimum output the swap accepts
+    pub min_output_amount: u64,
+    /// When the quote was taken (ms)
+    pub quoted_at: i64,
+    /// Decimals of the spot token (9 for SOL)
+    pub spot_decimals: u8,
+}
+
+impl SpotSwap {
+    /// Quote age at `now` (ms)
+    pub fn age_ms(&self, now: i64) -> i64 {
+        now - self.quoted_at
+    }
+
+    /// SOL and USDC exchanged by a buy (positive) or sell (negative)
+    pub fn amounts(&self, size_sol: f64) -> (f64, f64) {
+        let spot = |units| from_base_units(units, self.spot_decimals);
+        let usdc = |units| from_base_units(units, USDC_DECIMALS);
+        if size_sol > 0.0 {
+            (spot(self.output_amount), usdc(self.input_amount))
+        } else {
+            (spot(self.input_amount), usdc(self.output_amount))
+        }
+    }
+
+    /// Quoted price (USDC per SOL), if the swap moves any SOL
+    pub fn price(&self, size_sol: f64) -> Option<f64> {
+        let (sol, usdc) = self.amounts(size_sol);
+        (sol > 0.0).then(|| usdc / sol)
+    }
+
+    /// How much worse this quote's price is than `price` (%, 0 if better)
+    pub fn slippage_vs_pct(&self, size_sol: f64, price: f64) -> Option<f64> {
+        let quoted = self.price(size_sol)?;
+        let worse = if size_sol > 0.0 { quoted - price } else { price - quoted };
+        Some((worse / price * 100.0).max(0.0))
+    }
+}
+
+/// A venue the spot leg can be swapped on
+#[async_trait]
+pub trait SpotVenue: Send + Sync {
+    /// Venue name ("jupiter", "orca")
+    fn name(&self) -> &str;
+
+    /// Largest order routed here (SOL); None takes any size
+    fn max_size_sol(&self) -> Option<f64>;
+
+    /// Swap buying (positive) or selling (negative) `size_sol` for `owner`,
+    /// buys sized in USDC at `reference`
+    async fn prepare_swap(&self, owner: &Pubkey, size_sol: f64, reference: f64, slippage_bps: u16) -> Result<SpotSwap>;
+}
+
+/// Jupiter aggregator
+pub struct JupiterSpot {
+    client: JupiterClient,
+}
+
+impl JupiterSpot {
+    pub fn new(client: JupiterClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl SpotVenue for JupiterSpot {
+    fn name(&self) -> &str {
+        "jupiter"
+    }
+
+    fn max_size_sol(&self) -> Option<f64> {
+        None
+    }
+
+    async fn prepare_swap(&self, owner: &Pubkey, size_sol: f64, reference: f64, slippage_bps: u16) -> Result<SpotSwap> {
+        let quote = if size_sol > 0.0 {
+            let usdc_in = Amount::usdc(size_sol * reference)?.base_units()?;
+            self.client.get_usdc_to_sol_quote(usdc_in, slippage_bps).await?
+        } else {
+            let units_in = to_base_units(-size_sol, self.client.spot_decimals())?;
+            self.client.get_sol_to_usdc_quote(units_in, slippage_bps).await?
+        };
+        let quoted_at = chrono::Utc::now().timestamp_millis();
+        let swap = self.client.get_swap_transaction(&quote, owner, None).await?;
+        let unsigned: VersionedTransaction = bincode::deserialize(&swap.transaction_data)
+            .context("Failed to decode Jupiter swap transaction")?;
+        Ok(SpotSwap {
+            message: unsigned.message,
+            input_amount: swap.input_amount,
+            output_amount: swap.output_amount,
+            min_output_amount: swap.min_output_amount,
+            quoted_at,
+            spot_decimals: self.client.spot_decimals(),
+        })
+    }
+}
+
+/// Chooses the venue for each spot swap
+pub struct SpotRouter {
+    venues: Vec<Arc<dyn SpotVenue>>,
+}
+
+impl SpotRouter {
+    pub fn new(venues: Vec<Arc<dyn SpotVenue>>) -> Self {
+        Self { venues }
+    }
+
+    pub fn venues(&self) -> &[Arc<dyn SpotVenue>] {
+        &self.venues
+    }
+
+    /// Venues to try for `size_sol`, in order: size-limited (direct) venues
+    /// taking the size, then the venues taking any size
+    pub fn route(&self, size_sol: f64) -> Vec<Arc<dyn SpotVenue>> {
+        let size = size_sol.abs();
+        let direct = self.venues.iter().filter(|v| v.max_size_sol().is_some_and(|max| size <= max));
+        let any_size = self.venues.iter().filter(|v| v.max_size_sol().is_none());
+        direct.chain(any_size).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeVenue {
+        name: &'static str,
+        max_size_sol: Option<f64>,
+    }
+
+    #[async_trait]
+    impl SpotVenue for FakeVenue {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn max_size_sol(&self) -> Option<f64> {
+            self.max_size_sol
+        }
+
+        async fn prepare_swap(&self, _owner: &Pubkey, _size_sol: f64, _reference: f64, _slippage_bps: u16) -> Result<SpotSwap> {
+            anyhow::bail!("not quoted")
+        }
+    }
+
+    #[test]
+    fn test_small_orders_go_direct_first() {
+        let router = SpotRouter::new(vec![
+            Arc::new(FakeVenue { name: "jupiter", max_size_sol: None }),
+            Arc::new(FakeVenue { name: "orca", max_size_sol: Some(5.0) }),
+        ]);
+        let names = |size: f64| router.route(size).iter().map(|v| v.name().to_string()).collect::<Vec<_>>();
+
+        assert_eq!(names(2.0), vec!["orca", "jupiter"]);
+        assert_eq!(names(-5.0), vec!["orca", "jupiter"]);
+        assert_eq!(names(20.0), vec!["jupiter"]);
+    }
+
+    #[test]
+    fn test_swap_quote_age_and_slippage() {
+        // 1,500 USDC buys 9.9 SOL
+        let swap = SpotSwap {
+            message: VersionedMessage::Legacy(Default::default()),
+            input_amount: 1_500_000_000,
+            output_amount: 9_900_000_000,
+            min_output_amount: 9_850_000_000,
+            quoted_at: 1_000,
+            spot_decimals: 9,
+        };
+        assert_eq!(swap.age_ms(3_500), 2_500);
+
+        let price = swap.price(10.0).unwrap();
+        assert!((price - 151.515).abs() < 1e-3);
+        // Against an earlier quote at $150 the buy is ~1% worse; at $152 it is better
+        assert!((swap.slippage_vs_pct(10.0, 150.0).unwrap() - 1.0101).abs() < 1e-3);
+        assert_eq!(swap.slippage_vs_pct(10.0, 152.0), Some(0.0));
+    }
+}
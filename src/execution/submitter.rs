@@ -3,32 +3,68 @@
 //! Handles transaction submission with:
 //! - Retry logic with exponential backoff
 //! - Jito bundle support
-//! - Confirmation waiting
+//! - Confirmation waiting, bounded by the blockhash's last valid block height
+//! - Expiry detection: an expired transaction is rebuilt with a fresh
+//!   blockhash and re-signed rather than resent
+//! - Durable nonce transactions: never expire by block height, and the
+//!   nonce is advanced when one is abandoned so it cannot land late
 //! - Error handling and recovery
 
 use anyhow::{Context, Result};
+use serde::Serialize;
+use solana_client::rpc_client::SerializableTransaction;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     signature::Signature,
     transaction::Transaction,
 };
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
+use super::nonce::NonceManager;
 use crate::config::AppConfig;
+use crate::network::rpc_client::is_blockhash_expired_error;
 use crate::network::RpcManager;
+use crate::telemetry::{timed, LatencyStage};
+
+/// How a transaction reached the chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionPath {
+    /// Jito bundle with a tip
+    JitoBundle,
+    /// Regular RPC send with a priority fee
+    PriorityFee,
+    /// Placed through a venue connector (exchange, perp venue, mock)
+    Venue,
+}
+
+impl std::fmt::Display for SubmissionPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::JitoBundle => write!(f, "jito_bundle"),
+            Self::PriorityFee => write!(f, "priority_fee"),
+            Self::Venue => write!(f, "venue"),
+        }
+    }
+}
 
 /// Submission result
 #[derive(Debug, Clone)]
 pub struct SubmissionResult {
     /// Transaction signature
     pub signature: Signature,
+    /// Path the transaction landed through
+    pub path: SubmissionPath,
     /// Slot when confirmed
     pub slot: Option<u64>,
     /// Number of retries needed
     pub retries: u32,
+    /// Times the transaction was rebuilt after its blockhash expired
+    pub rebuilds: u32,
     /// Time to confirmation in milliseconds
     pub confirmation_time_ms: u64,
 }
@@ -50,94 +86,246 @@ pub enum SubmissionError {
     Unknown(String),
 }
 
+impl std::fmt::Display for SubmissionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SimulationFailed(e) => write!(f, "Simulation failed: {}", e),
+            Self::Expired => write!(f, "Transaction expired (blockhash too old)"),
+            Self::NetworkError(e) => write!(f, "Network error: {}", e),
+            Self::InsufficientFunds => write!(f, "Insufficient funds"),
+            Self::MaxRetriesExceeded => write!(f, "Max retries exceeded"),
+            Self::Unknown(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for SubmissionError {}
+
+/// Whether a submission failed because its blockhash expired
+pub fn is_expired(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<SubmissionError>() == Some(&SubmissionError::Expired)
+        || is_blockhash_expired_error(error)
+}
+
 /// Transaction submitter
 pub struct TransactionSubmitter {
     /// Configuration
     config: Arc<AppConfig>,
     /// RPC manager
     rpc: Arc<RpcManager>,
+    /// Nonce account behind durable transactions
+    nonce: Option<Arc<NonceManager>>,
 }
 
 impl TransactionSubmitter {
     /// Create a new submitter
     pub fn new(config: Arc<AppConfig>, rpc: Arc<RpcManager>) -> Self {
-        Self { config, rpc }
+        Self { config, rpc, nonce: None }
+    }
+    
+    /// Recognize (and invalidate when abandoned) durable nonce transactions
+    pub fn with_nonce(mut self, nonce: Option<Arc<NonceManager>>) -> Self {
+        self.nonce = nonce;
+        self
+    }
+    
+    /// Whether a transaction is signed over the durable nonce
+    fn is_durable(&self, transaction: &impl SerializableTransaction) -> bool {
+        self.nonce.as_ref().is_some_and(|n| n.is_durable(transaction.get_recent_blockhash()))
+    }
+    
+    /// Last block height the transaction can land at; durable ones have none
+    async fn valid_until(&self, transaction: &impl SerializableTransaction) -> Result<u64> {
+        if self.is_durable(transaction) {
+            return Ok(u64::MAX);
+        }
+        self.rpc.last_valid_block_height(transaction.get_recent_blockhash()).await
+    }
+    
+    /// Slot a transaction landed in, if it has
+    async fn landed(&self, signature: &Signature) -> Option<u64> {
+        let client = self.rpc.get_client().await;
+        match client.get_signature_status(signature).await {
+            Ok(Some(Ok(()))) => Some(self.rpc.get_slot().await.unwrap_or(0)),
+            _ => None,
+        }
     }
     
-    /// Submit transaction with retry logic
+    /// Submit a signed transaction with retry logic. Retries resend the same
+    /// transaction, so they stop with `SubmissionError::Expired` once its
+    /// blockhash expires; use `submit_with_rebuild` to rebuild instead.
+    #[tracing::instrument(name = "submit", skip_all, fields(retries = tracing::field::Empty))]
     pub async fn submit_with_retry(
         &self,
-        transaction: &Transaction,
+        transaction: &(impl SerializableTransaction + Sync),
     ) -> Result<SubmissionResult> {
         let max_retries = self.config.execution.max_retries;
-        let retry_delay = Duration::from_millis(self.config.execution.retry_delay_ms);
-        
         let start = std::time::Instant::now();
+        let valid_until = self.valid_until(transaction).await?;
         let mut last_error = None;
         
         for attempt in 0..=max_retries {
             if attempt > 0 {
-                let backoff = retry_delay * (1 << (attempt - 1).min(4));
-                debug!("Retry {} after {:?}", attempt, backoff);
-                sleep(backoff).await;
+                self.backoff(attempt).await;
             }
             
-            match self.submit_once(transaction).await {
-                Ok(signature) => {
-                    info!("Transaction submitted: {}", signature);
-                    
-                    // Wait for confirmation
-                    match self.wait_for_confirmation(&signature).await {
-                        Ok(slot) => {
-                            let elapsed = start.elapsed().as_millis() as u64;
-                            info!(
-                                "Transaction confirmed in slot {} ({} ms)",
-                                slot, elapsed
-                            );
-                            
-                            return Ok(SubmissionResult {
-                                signature,
-                                slot: Some(slot),
-                                retries: attempt,
-                                confirmation_time_ms: elapsed,
-                            });
-                        }
-                        Err(e) => {
-                            warn!("Confirmation failed: {}", e);
-                            last_error = Some(e);
-                        }
+            match self.submit_and_confirm(transaction, valid_until).await {
+                Ok((signature, slot)) => {
+                    tracing::Span::current().record("retries", attempt);
+                    return Ok(self.result(signature, slot, attempt, 0, start));
+                }
+                Err(e) if is_expired(&e) => {
+                    warn!("Transaction expired after {} attempts; it must be rebuilt", attempt + 1);
+                    return Err(match e.downcast_ref::<SubmissionError>() {
+                        Some(_) => e,
+                        None => e.context(SubmissionError::Expired),
+                    });
+                }
+                Err(e) => {
+                    warn!("Submission attempt {} failed: {}", attempt + 1, e);
+                    let retryable = self.is_retryable_error(&e);
+                    last_error = Some(e);
+                    if !retryable {
+                        break;
+                    }
+                }
+            }
+        }
+        
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Max retries exceeded")))
+    }
+    
+    /// Submit a transaction produced by `build`, with retry logic. Retries
+    /// resend the same signed transaction while its blockhash is valid; once
+    /// it expires (and so can no longer land) `build` is called again for a
+    /// freshly signed transaction on a new blockhash. A durable nonce
+    /// transaction that still has not landed is invalidated on failure.
+    #[tracing::instrument(name = "submit", skip_all, fields(retries = tracing::field::Empty, rebuilds = tracing::field::Empty))]
+    pub async fn submit_with_rebuild<T, F, Fut>(&self, build: F) -> Result<SubmissionResult>
+    where
+        T: SerializableTransaction + Sync,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let max_retries = self.config.execution.max_retries;
+        let start = std::time::Instant::now();
+        let mut current: Option<(T, u64)> = None;
+        let mut rebuilds = 0;
+        let mut pending_durable = false;
+        let mut last_error = None;
+        
+        for attempt in 0..=max_retries {
+            let (transaction, valid_until) = match current.take() {
+                Some(built) => {
+                    self.backoff(attempt).await;
+                    built
+                }
+                None => {
+                    if attempt > 0 {
+                        rebuilds += 1;
+                        info!("Rebuilding expired transaction (rebuild {})", rebuilds);
+                    }
+                    let transaction = build().await.context("Failed to build transaction")?;
+                    let valid_until = self.valid_until(&transaction).await?;
+                    pending_durable = self.is_durable(&transaction);
+                    (transaction, valid_until)
+                }
+            };
+            
+            match self.submit_and_confirm(&transaction, valid_until).await {
+                Ok((signature, slot)) => {
+                    let span = tracing::Span::current();
+                    span.record("retries", attempt);
+                    span.record("rebuilds", rebuilds);
+                    return Ok(self.result(signature, slot, attempt, rebuilds, start));
+                }
+                Err(e) if is_expired(&e) => {
+                    // An expired (or nonce-advanced) transaction can no longer land, so
+                    // rebuilding cannot double-execute, unless it already landed
+                    if let Some(slot) = self.landed(transaction.get_signature()).await {
+                        return Ok(self.result(*transaction.get_signature(), slot, attempt, rebuilds, start));
                     }
+                    warn!("Attempt {} expired: {}", attempt + 1, e);
+                    self.rpc.invalidate_blockhash().await;
+                    pending_durable = false;
+                    last_error = Some(e);
                 }
                 Err(e) => {
                     warn!("Submission attempt {} failed: {}", attempt + 1, e);
+                    let retryable = self.is_retryable_error(&e);
                     last_error = Some(e);
-                    
-                    // Check if error is retryable
-                    if !self.is_retryable_error(&last_error) {
+                    if !retryable {
                         break;
                     }
+                    current = Some((transaction, valid_until));
                 }
             }
         }
         
+        if pending_durable {
+            if let Some(nonce) = &self.nonce {
+                let _ = nonce.invalidate().await;
+            }
+        }
         Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Max retries exceeded")))
     }
     
+    /// Send once and wait for confirmation
+    async fn submit_and_confirm(
+        &self,
+        transaction: &(impl SerializableTransaction + Sync),
+        valid_until: u64,
+    ) -> Result<(Signature, u64)> {
+        let signature = self.submit_once(transaction).await?;
+        info!("Transaction submitted: {}", signature);
+        
+        let slot = timed(LatencyStage::Confirm, self.wait_for_confirmation(&signature, valid_until)).await?;
+        info!("Transaction confirmed in slot {}", slot);
+        Ok((signature, slot))
+    }
+    
+    /// Exponential backoff before retry `attempt`
+    async fn backoff(&self, attempt: u32) {
+        let retry_delay = Duration::from_millis(self.config.execution.retry_delay_ms);
+        let backoff = retry_delay * (1 << attempt.saturating_sub(1).min(4));
+        debug!("Retry {} after {:?}", attempt, backoff);
+        sleep(backoff).await;
+    }
+    
+    fn result(&self, signature: Signature, slot: u64, retries: u32, rebuilds: u32, start: std::time::Instant) -> SubmissionResult {
+        let elapsed = start.elapsed().as_millis() as u64;
+        info!("Transaction {} landed in {} ms ({} retries, {} rebuilds)", signature, elapsed, retries, rebuilds);
+        SubmissionResult {
+            signature,
+            path: SubmissionPath::PriorityFee,
+            slot: Some(slot),
+            retries,
+            rebuilds,
+            confirmation_time_ms: elapsed,
+        }
+    }
+    
     /// Submit transaction once
-    async fn submit_once(&self, transaction: &Transaction) -> Result<Signature> {
+    #[tracing::instrument(name = "submit_once", skip_all)]
+    async fn submit_once(&self, transaction: &(impl SerializableTransaction + Sync)) -> Result<Signature> {
         // Simulate first if configured
         if self.config.execution.simulate_before_submit {
-            let sim_result = self.rpc.simulate_transaction(transaction).await?;
+            let sim_result = timed(LatencyStage::Simulate, self.rpc.simulate_transaction(transaction)).await?;
             if let Some(err) = sim_result.err {
                 anyhow::bail!("Simulation failed: {:?}", err);
             }
         }
         
-        self.rpc.send_transaction(transaction).await
+        if self.is_durable(transaction) {
+            return timed(LatencyStage::Submit, self.rpc.send_transaction_unconfirmed(transaction)).await;
+        }
+        timed(LatencyStage::Submit, self.rpc.send_transaction(transaction)).await
     }
     
-    /// Wait for transaction confirmation
-    async fn wait_for_confirmation(&self, signature: &Signature) -> Result<u64> {
+    /// Wait for transaction confirmation; fails with `SubmissionError::Expired`
+    /// once the chain passes `valid_until` without the transaction landing
+    #[tracing::instrument(name = "confirm", skip_all, fields(signature = %signature))]
+    async fn wait_for_confirmation(&self, signature: &Signature, valid_until: u64) -> Result<u64> {
         let timeout = Duration::from_secs(30);
         let poll_interval = Duration::from_millis(500);
         let start = std::time::Instant::now();
@@ -148,7 +336,7 @@ impl TransactionSubmitter {
             }
             
             // Check transaction status
-            match self.rpc.get_client().get_signature_status(signature).await {
+            match self.rpc.get_client().await.get_signature_status(signature).await {
                 Ok(Some(result)) => {
                     match result {
                         Ok(_) => {
@@ -162,7 +350,17 @@ impl TransactionSubmitter {
                     }
                 }
                 Ok(None) => {
-                    // Not yet confirmed
+                    // Not yet confirmed; past its last valid block height it never will be.
+                    // Re-check the status after the height, in case it landed in between.
+                    if let Ok(height) = self.rpc.get_block_height().await {
+                        if height > valid_until {
+                            let client = self.rpc.get_client().await;
+                            if let Ok(None) = client.get_signature_status(signature).await {
+                                return Err(SubmissionError::Expired.into());
+                            }
+                            continue;
+                        }
+                    }
                     sleep(poll_interval).await;
                 }
                 Err(e) => {
@@ -174,31 +372,29 @@ impl TransactionSubmitter {
     }
     
     /// Check if error is retryable
-    fn is_retryable_error(&self, error: &Option<anyhow::Error>) -> bool {
-        if let Some(e) = error {
-            let msg = e.to_string().to_lowercase();
-            
-            // Not retryable errors
-            if msg.contains("insufficient funds") {
-                return false;
-            }
-            if msg.contains("account not found") {
-                return false;
-            }
-            if msg.contains("invalid signature") {
-                return false;
-            }
-            
-            // Retryable errors
-            if msg.contains("blockhash not found") {
-                return true;
-            }
-            if msg.contains("timeout") {
-                return true;
-            }
-            if msg.contains("connection") {
-                return true;
-            }
+    fn is_retryable_error(&self, error: &anyhow::Error) -> bool {
+        let msg = error.to_string().to_lowercase();
+        
+        // Not retryable errors
+        if msg.contains("insufficient funds") {
+            return false;
+        }
+        if msg.contains("account not found") {
+            return false;
+        }
+        if msg.contains("invalid signature") {
+            return false;
+        }
+        
+        // Retryable errors
+        if msg.contains("blockhash not found") {
+            return true;
+        }
+        if msg.contains("timeout") {
+            return true;
+        }
+        if msg.contains("connection") {
+            return true;
         }
         
         true
@@ -237,4 +433,19 @@ mod tests {
         assert_eq!(SubmissionError::Expired, SubmissionError::Expired);
         assert_ne!(SubmissionError::Expired, SubmissionError::InsufficientFunds);
     }
+
+    #[test]
+    fn test_submission_path_names() {
+        for path in [SubmissionPath::JitoBundle, SubmissionPath::PriorityFee, SubmissionPath::Venue] {
+            assert_eq!(serde_json::to_value(path).unwrap(), path.to_string());
+        }
+    }
+
+    #[test]
+    fn test_expiry_detection() {
+        assert!(is_expired(&SubmissionError::Expired.into()));
+        assert!(is_expired(&anyhow::anyhow!("Blockhash not found")));
+        assert!(!is_expired(&SubmissionError::InsufficientFunds.into()));
+        assert!(!is_expired(&anyhow::anyhow!("Confirmation timeout")));
+    }
 }
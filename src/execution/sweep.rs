@@ -0,0 +1,175 @@
+//! Profit Sweep
+//!
+//! Moves realized profit out of the trading wallet on a schedule:
+//! - Every `treasury.profit_sweep.interval_secs`, wallet USDC that lifts
+//!   equity above `equity_target_usd` is transferred to the cold wallet
+//! - Sweeps run only while flat, so unrealized P&L is never swept
+//! - Excesses below `min_sweep_usd` wait for a later run
+//!
+//! Live sweeps go through the execution layer and are confirmed on-chain
+//! before the alert; paper sweeps only move the paper inventory. Every sweep
+//! is appended to `sweeps.jsonl` next to the performance database.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::agentic::PerformanceDb;
+use crate::config::ProfitSweepConfig;
+use crate::network::Event;
+use crate::telemetry::Alert;
+
+use super::transfers::WALLET_VENUE;
+use super::treasury::{InventoryReport, Treasury};
+use super::ExecutionManager;
+
+/// Committed SOL below this counts as flat
+const FLAT_SOL: f64 = 1e-9;
+
+/// One completed sweep
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepRecord {
+    /// USDC transferred
+    pub amount_usd: f64,
+    /// Equity before the sweep (USD)
+    pub equity_usd: f64,
+    pub equity_target_usd: f64,
+    pub destination: String,
+    /// Transaction signature, or a `paper-sweep:` id
+    pub signature: String,
+    pub paper: bool,
+    /// Sweep timestamp (ms)
+    pub timestamp: i64,
+}
+
+/// USDC to sweep: equity above the target, capped by the wallet's USDC,
+/// rounded down to the cent; `None` while a position is open or below the minimum
+pub fn sweep_amount(report: &InventoryReport, wallet_usdc: f64, config: &ProfitSweepConfig) -> Option<f64> {
+    if report.committed_sol > FLAT_SOL {
+        return None;
+    }
+    let excess = (report.total_capital_usd - config.equity_target_usd).min(wallet_usdc);
+    let amount = (excess * 100.0).floor() / 100.0;
+    (amount > 0.0 && amount >= config.min_sweep_usd).then_some(amount)
+}
+
+/// Scheduled transfer of profit to the cold wallet
+pub struct ProfitSweeper {
+    treasury: Arc<Treasury>,
+    performance_db: Arc<PerformanceDb>,
+    /// Live execution; without it sweeps are simulated
+    execution: Option<Arc<ExecutionManager>>,
+    config: ProfitSweepConfig,
+    destination: Pubkey,
+    event_tx: broadcast::Sender<Event>,
+}
+
+impl ProfitSweeper {
+    pub fn new(
+        treasury: Arc<Treasury>,
+        performance_db: Arc<PerformanceDb>,
+        config: &ProfitSweepConfig,
+        event_tx: broadcast::Sender<Event>,
+    ) -> Result<Self> {
+        let destination = config.cold_wallet.parse().context("Invalid profit sweep cold wallet")?;
+        Ok(Self { treasury, performance_db, execution: None, config: config.clone(), destination, event_tx })
+    }
+
+    /// Sweep for real through the execution layer
+    pub fn with_execution(mut self, execution: Arc<ExecutionManager>) -> Self {
+        self.execution = Some(execution);
+        self
+    }
+
+    /// Sweep the current excess, if any
+    pub async fn run(&self) -> Result<Option<SweepRecord>> {
+        let report = self.treasury.current();
+        if report.total_capital_usd <= 0.0 {
+            return Ok(None);
+        }
+        let wallet_usdc = self.treasury.ledger().balance(WALLET_VENUE).usdc;
+        let Some(amount) = sweep_amount(&report, wallet_usdc, &self.config) else {
+            return Ok(None);
+        };
+
+        let signature = match &self.execution {
+            Some(execution) => execution.transfer_usdc(&self.destination, amount).await?.signature.to_string(),
+            None => {
+                self.treasury.ledger().apply_fill(WALLET_VENUE, 0.0, -amount);
+                format!("paper-sweep:{}", uuid::Uuid::new_v4())
+            }
+        };
+        let record = SweepRecord {
+            amount_usd: amount,
+            equity_usd: report.total_capital_usd,
+            equity_target_usd: self.config.equity_target_usd,
+            destination: self.destination.to_string(),
+            signature,
+            paper: self.execution.is_none(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+
+        info!("Swept ${:.2} of profit to {} ({})", amount, self.destination, record.signature);
+        let _ = self.event_tx.send(Event::Alert(Alert::info(
+            "Profit swept",
+            format!(
+                "${:.2} USDC moved to {}{}; equity ${:.2} -> ${:.2} (target ${:.2})",
+                amount,
+                self.destination,
+                if record.paper { " (paper)" } else { "" },
+                record.equity_usd,
+                record.equity_usd - amount,
+                self.config.equity_target_usd
+            ),
+        )));
+        if let Err(e) = self.performance_db.record_sweep(&record).await {
+            warn!("Failed to record profit sweep: {}", e);
+        }
+        Ok(Some(record))
+    }
+
+    /// Sweep on `interval_secs` until the task is aborted
+    pub fn spawn(self) -> JoinHandle<()> {
+        let period = Duration::from_secs(self.config.interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run().await {
+                    warn!("Profit sweep failed: {:#}", e);
+                    let _ = self.event_tx.send(Event::Alert(Alert::warning(
+                        "Profit sweep failed",
+                        format!("Transfer to {} failed: {:#}; retrying next interval", self.destination, e),
+                    )));
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweeps_excess_while_flat() {
+        let config = ProfitSweepConfig {
+            enabled: true,
+            equity_target_usd: 10_000.0,
+            min_sweep_usd: 100.0,
+            ..ProfitSweepConfig::default()
+        };
+        let report = InventoryReport { usdc: 10_250.555, total_capital_usd: 10_250.555, ..InventoryReport::default() };
+        assert_eq!(sweep_amount(&report, 10_250.555, &config), Some(250.55));
+
+        // Capped by the wallet, skipped below the minimum or while a position is open
+        assert_eq!(sweep_amount(&report, 50.0, &config), None);
+        let open = InventoryReport { committed_sol: 1.0, ..report };
+        assert_eq!(sweep_amount(&open, 10_250.555, &config), None);
+    }
+}
@@ -0,0 +1,264 @@
+//! Cross-Venue Balances and Transfers
+//!
+//! When the spot leg lives on a CEX, inventory is split between the wallet,
+//! the exchange and transfers in flight. The ledger keeps:
+//! - Per-venue SOL and USDC balances, refreshed from each venue and
+//!   adjusted by fills in between
+//! - Transfers (withdrawals and deposits) until the receiving venue credits them
+//!
+//! `TransferOrchestrator` starts withdrawals, tracks deposits and resolves
+//! pending transfers against the exchange. Execution deposits USDC before a
+//! spot buy the exchange cannot cover and withdraws a spot sell's proceeds
+//! back to the wallet.
+
+use anyhow::Result;
+use dashmap::DashMap;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::cex::SpotExchange;
+
+/// On-chain wallet venue key
+pub const WALLET_VENUE: &str = "wallet";
+
+/// Assets moved between venues
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Asset {
+    Sol,
+    Usdc,
+}
+
+/// Transfer progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+/// Balances held on one venue
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct VenueBalance {
+    pub sol: f64,
+    pub usdc: f64,
+    /// Last refresh from the venue (ms)
+    pub updated_at: i64,
+}
+
+impl VenueBalance {
+    fn amount_mut(&mut self, asset: Asset) -> &mut f64 {
+        match asset {
+            Asset::Sol => &mut self.sol,
+            Asset::Usdc => &mut self.usdc,
+        }
+    }
+
+    pub fn amount(&self, asset: Asset) -> f64 {
+        match asset {
+            Asset::Sol => self.sol,
+            Asset::Usdc => self.usdc,
+        }
+    }
+}
+
+/// A movement of funds between venues
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transfer {
+    pub id: String,
+    pub asset: Asset,
+    pub amount: f64,
+    pub from: String,
+    pub to: String,
+    /// Exchange withdrawal id or deposit transaction signature
+    pub reference: String,
+    pub status: TransferStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Balances across venues, including funds in transit
+#[derive(Default)]
+pub struct TransferLedger {
+    balances: DashMap<String, VenueBalance>,
+    transfers: RwLock<Vec<Transfer>>,
+}
+
+impl TransferLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace a venue's balances with a fresh reading
+    pub fn set_balance(&self, venue: &str, balance: VenueBalance) {
+        self.balances.insert(venue.to_string(), balance);
+    }
+
+    /// Adjust a venue's balances for a fill between refreshes
+    pub fn apply_fill(&self, venue: &str, sol_delta: f64, usdc_delta: f64) {
+        let mut balance = self.balances.entry(venue.to_string()).or_default();
+        balance.sol += sol_delta;
+        balance.usdc += usdc_delta;
+    }
+
+    /// Balances of one venue
+    pub fn balance(&self, venue: &str) -> VenueBalance {
+        self.balances.get(venue).map(|b| *b).unwrap_or_default()
+    }
+
+    /// All venue balances
+    pub fn balances(&self) -> Vec<(String, VenueBalance)> {
+        let mut balances: Vec<_> = self.balances.iter().map(|b| (b.key().clone(), *b.value())).collect();
+        balances.sort_by(|a, b| a.0.cmp(&b.0));
+        balances
+    }
+
+    /// Record a transfer that has left `from`; returns the ledger id
+    pub fn begin(&self, asset: Asset, amount: f64, from: &str, to: &str, reference: &str) -> String {
+        let now = chrono::Utc::now().timestamp_millis();
+        let id = uuid::Uuid::new_v4().to_string();
+        *self.balances.entry(from.to_string()).or_default().amount_mut(asset) -= amount;
+        self.transfers.write().push(Transfer {
+            id: id.clone(),
+            asset,
+            amount,
+            from: from.to_string(),
+            to: to.to_string(),
+            reference: reference.to_string(),
+            status: TransferStatus::Pending,
+            created_at: now,
+            updated_at: now,
+        });
+        id
+    }
+
+    /// Settle a pending transfer: completed funds land on `to`, failed ones return to `from`
+    pub fn resolve(&self, id: &str, status: TransferStatus) {
+        let mut transfers = self.transfers.write();
+        let Some(transfer) = transfers.iter_mut().find(|t| t.id == id && t.status == TransferStatus::Pending) else {
+            return;
+        };
+        let venue = match status {
+            TransferStatus::Pending => return,
+            TransferStatus::Completed => &transfer.to,
+            TransferStatus::Failed => &transfer.from,
+        };
+        *self.balances.entry(venue.clone()).or_default().amount_mut(transfer.asset) += transfer.amount;
+        transfer.status = status;
+        transfer.updated_at = chrono::Utc::now().timestamp_millis();
+    }
+
+    /// Transfers not yet credited
+    pub fn pending(&self) -> Vec<Transfer> {
+        self.transfers.read().iter().filter(|t| t.status == TransferStatus::Pending).cloned().collect()
+    }
+
+    /// All recorded transfers, oldest first
+    pub fn transfers(&self) -> Vec<Transfer> {
+        self.transfers.read().clone()
+    }
+
+    /// Amount of an asset in transit between venues
+    pub fn in_transit(&self, asset: Asset) -> f64 {
+        self.pending().iter().filter(|t| t.asset == asset).map(|t| t.amount).sum()
+    }
+
+    /// Total holdings of an asset across venues and in transit
+    pub fn total(&self, asset: Asset) -> f64 {
+        self.balances.iter().map(|b| b.amount(asset)).sum::<f64>() + self.in_transit(asset)
+    }
+}
+
+/// Moves funds between the wallet and an exchange
+pub struct TransferOrchestrator {
+    exchange: Arc<dyn SpotExchange>,
+    ledger: Arc<TransferLedger>,
+    /// Wallet address withdrawals are sent to
+    wallet_address: String,
+}
+
+impl TransferOrchestrator {
+    pub fn new(exchange: Arc<dyn SpotExchange>, ledger: Arc<TransferLedger>, wallet_address: String) -> Self {
+        Self { exchange, ledger, wallet_address }
+    }
+
+    pub fn exchange(&self) -> &Arc<dyn SpotExchange> {
+        &self.exchange
+    }
+
+    pub fn ledger(&self) -> &Arc<TransferLedger> {
+        &self.ledger
+    }
+
+    /// Amount of an asset on its way from the wallet to the exchange
+    pub fn pending_deposits(&self, asset: Asset) -> f64 {
+        self.ledger
+            .pending()
+            .iter()
+            .filter(|t| t.asset == asset && t.from == WALLET_VENUE && t.to == self.exchange.name())
+            .map(|t| t.amount)
+            .sum()
+    }
+
+    /// Withdraw from the exchange to the wallet
+    pub async fn withdraw_to_wallet(&self, asset: Asset, amount: f64) -> Result<String> {
+        let reference = self.exchange.withdraw(asset, amount, &self.wallet_address).await?;
+        Ok(self.ledger.begin(asset, amount, self.exchange.name(), WALLET_VENUE, &reference))
+    }
+
+    /// Track a deposit sent from the wallet to the exchange's deposit address
+    pub fn track_deposit(&self, asset: Asset, amount: f64, signature: &str) -> String {
+        self.ledger.begin(asset, amount, WALLET_VENUE, self.exchange.name(), signature)
+    }
+
+    /// Refresh exchange balances and settle pending transfers
+    pub async fn poll(&self) -> Result<()> {
+        for transfer in self.ledger.pending() {
+            let status = if transfer.from == WALLET_VENUE {
+                self.exchange.deposit_status(transfer.asset, &transfer.reference).await
+            } else {
+                self.exchange.withdrawal_status(&transfer.reference).await
+            };
+            match status {
+                Ok(TransferStatus::Pending) => {}
+                Ok(status) => {
+                    info!(
+                        "Transfer of {} {:?} from {} to {} {:?}",
+                        transfer.amount, transfer.asset, transfer.from, transfer.to, status
+                    );
+                    self.ledger.resolve(&transfer.id, status);
+                }
+                Err(e) => warn!("Transfer status check failed for {}: {}", transfer.reference, e),
+            }
+        }
+
+        let balance = self.exchange.balances().await?;
+        self.ledger.set_balance(self.exchange.name(), balance);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_keeps_totals() {
+        let ledger = TransferLedger::new();
+        ledger.set_balance("binance", VenueBalance { sol: 10.0, usdc: 0.0, updated_at: 0 });
+        ledger.apply_fill(WALLET_VENUE, 0.0, 500.0);
+
+        let id = ledger.begin(Asset::Sol, 4.0, "binance", WALLET_VENUE, "w-1");
+        assert_eq!(ledger.balance("binance").sol, 6.0);
+        assert_eq!(ledger.in_transit(Asset::Sol), 4.0);
+        assert_eq!(ledger.total(Asset::Sol), 10.0);
+
+        ledger.resolve(&id, TransferStatus::Completed);
+        assert_eq!(ledger.balance(WALLET_VENUE).sol, 4.0);
+        assert_eq!(ledger.in_transit(Asset::Sol), 0.0);
+        assert_eq!(ledger.total(Asset::Sol), 10.0);
+    }
+}
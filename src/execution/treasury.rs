@@ -0,0 +1,224 @@
+//! Spot Inventory (Treasury)
+//!
+//! Closing a trade sells the spot leg into USDC, and the next entry has to
+//! buy SOL back. The treasury accounts for that inventory across trades:
+//! - Wallet SOL and USDC, kept in the shared `TransferLedger` (refreshed
+//!   from the chain live, seeded and moved by paper fills in paper mode)
+//! - Which currency funds an entry: idle SOL first, the rest bought with USDC
+//! - Idle versus deployed capital, for utilization and ROI-on-capital
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::config::{AppConfig, TreasuryConfig};
+use crate::state::SharedState;
+
+use super::balances::AvailableCapital;
+use super::transfers::{Asset, TransferLedger, VenueBalance, WALLET_VENUE};
+
+/// Smallest amount of SOL treated as non-zero
+const DUST_SOL: f64 = 1e-9;
+
+/// What an entry's spot leg is paid with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryCurrency {
+    /// SOL already in inventory; no swap
+    Sol,
+    /// SOL bought with USDC
+    Usdc,
+    /// Part inventory, part bought
+    Mixed,
+}
+
+/// How an entry of a given size is funded
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EntryFunding {
+    pub currency: EntryCurrency,
+    /// SOL taken from inventory
+    pub from_inventory_sol: f64,
+    /// SOL to buy
+    pub buy_sol: f64,
+    /// USDC spent on the buy, at the planning price
+    pub usdc_cost: f64,
+    /// USDC missing for the buy (0 when fully funded)
+    pub usdc_shortfall: f64,
+}
+
+impl EntryFunding {
+    pub fn is_funded(&self) -> bool {
+        self.usdc_shortfall <= 0.0
+    }
+}
+
+/// Inventory and capital usage at one moment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct InventoryReport {
+    /// SOL across venues and in transit
+    pub sol: f64,
+    /// USDC across venues and in transit
+    pub usdc: f64,
+    /// SOL held as the open position's spot leg
+    pub committed_sol: f64,
+    /// SOL free for the next entry, after the gas reserve
+    pub idle_sol: f64,
+    /// Idle USDC plus idle SOL (USD)
+    pub idle_usd: f64,
+    /// Capital in the open position's spot leg (USD)
+    pub deployed_usd: f64,
+    /// All inventory, gas reserve included (USD)
+    pub total_capital_usd: f64,
+    /// Deployed share of total capital (%)
+    pub utilization_pct: f64,
+    /// Report timestamp (ms)
+    pub timestamp: i64,
+}
+
+/// Accounts for spot inventory between trades
+pub struct Treasury {
+    ledger: Arc<TransferLedger>,
+    state: Arc<SharedState>,
+    config: TreasuryConfig,
+    /// SOL never counted as idle
+    gas_reserve_sol: f64,
+    /// Paper inventory is moved by paper fills only, never by wallet readings
+    paper: bool,
+}
+
+impl Treasury {
+    /// In paper mode the wallet starts with `paper_starting_usdc`
+    pub fn new(ledger: Arc<TransferLedger>, state: Arc<SharedState>, config: &AppConfig) -> Self {
+        if config.paper_trading {
+            ledger.set_balance(WALLET_VENUE, VenueBalance {
+                sol: 0.0,
+                usdc: config.treasury.paper_starting_usdc,
+                updated_at: chrono::Utc::now().timestamp_millis(),
+            });
+        }
+        Self {
+            ledger,
+            state,
+            config: config.treasury.clone(),
+            gas_reserve_sol: config.wallet.gas_reserve_sol,
+            paper: config.paper_trading,
+        }
+    }
+
+    /// Balances across venues
+    pub fn ledger(&self) -> &Arc<TransferLedger> {
+        &self.ledger
+    }
+
+    /// Take the wallet's balances from a fresh on-chain reading (live only)
+    pub fn refresh_wallet(&self, capital: &AvailableCapital) {
+        if self.paper {
+            return;
+        }
+        self.ledger.set_balance(WALLET_VENUE, VenueBalance {
+            sol: capital.sol_balance,
+            usdc: capital.usdc_balance,
+            updated_at: capital.timestamp,
+        });
+    }
+
+    /// SOL held as the open position's spot leg
+    pub fn committed_sol(&self) -> f64 {
+        self.state.spot_position.read().as_ref().map_or(0.0, |p| p.size)
+    }
+
+    /// SOL that can fund the next entry without a swap
+    pub fn idle_sol(&self) -> f64 {
+        (self.ledger.total(Asset::Sol) - self.committed_sol() - self.gas_reserve_sol).max(0.0)
+    }
+
+    /// Decide how to fund a `size_sol` entry at `spot_price`
+    pub fn plan_entry(&self, size_sol: f64, spot_price: f64) -> EntryFunding {
+        let idle_sol = if self.config.use_sol_inventory { self.idle_sol() } else { 0.0 };
+        plan_entry(size_sol, spot_price, idle_sol, self.ledger.total(Asset::Usdc))
+    }
+
+    /// Apply a paper entry: the bought SOL is paid for in USDC
+    pub fn apply_paper_open(&self, funding: &EntryFunding) {
+        if funding.buy_sol > DUST_SOL {
+            self.ledger.apply_fill(WALLET_VENUE, funding.buy_sol, -funding.usdc_cost);
+        }
+    }
+
+    /// Apply a paper close: the spot leg is sold into USDC
+    pub fn apply_paper_close(&self, size_sol: f64, price: f64) {
+        self.ledger.apply_fill(WALLET_VENUE, -size_sol, size_sol * price);
+    }
+
+    /// Inventory and capital usage at the current spot price
+    pub fn current(&self) -> InventoryReport {
+        self.report(self.state.spot_price.load())
+    }
+
+    /// Inventory and capital usage at `spot_price`
+    pub fn report(&self, spot_price: f64) -> InventoryReport {
+        let sol = self.ledger.total(Asset::Sol);
+        let usdc = self.ledger.total(Asset::Usdc);
+        // USDC valued at USDC/USD, 1.0 until the peg monitor reports
+        let usdc_usd = usdc * self.state.usdc_usd.load();
+        let committed_sol = self.committed_sol();
+        let idle_sol = self.idle_sol();
+        let idle_usd = usdc_usd + idle_sol * spot_price;
+        let deployed_usd = committed_sol * spot_price;
+        let total_capital_usd = usdc_usd + sol * spot_price;
+        InventoryReport {
+            sol,
+            usdc,
+            committed_sol,
+            idle_sol,
+            idle_usd,
+            deployed_usd,
+            total_capital_usd,
+            utilization_pct: if total_capital_usd > 0.0 { deployed_usd / total_capital_usd * 100.0 } else { 0.0 },
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+}
+
+/// Fund from idle SOL first, buying the rest with USDC
+fn plan_entry(size_sol: f64, spot_price: f64, idle_sol: f64, usdc: f64) -> EntryFunding {
+    let from_inventory_sol = idle_sol.min(size_sol).max(0.0);
+    let buy_sol = (size_sol - from_inventory_sol).max(0.0);
+    let usdc_cost = buy_sol * spot_price;
+    let currency = if buy_sol <= DUST_SOL {
+        EntryCurrency::Sol
+    } else if from_inventory_sol > DUST_SOL {
+        EntryCurrency::Mixed
+    } else {
+        EntryCurrency::Usdc
+    };
+    EntryFunding {
+        currency,
+        from_inventory_sol,
+        buy_sol,
+        usdc_cost,
+        usdc_shortfall: (usdc_cost - usdc).max(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_entry_uses_inventory_first() {
+        let all_sol = plan_entry(2.0, 150.0, 5.0, 0.0);
+        assert_eq!(all_sol.currency, EntryCurrency::Sol);
+        assert_eq!(all_sol.buy_sol, 0.0);
+        assert!(all_sol.is_funded());
+
+        let mixed = plan_entry(10.0, 150.0, 4.0, 1_000.0);
+        assert_eq!(mixed.currency, EntryCurrency::Mixed);
+        assert_eq!(mixed.buy_sol, 6.0);
+        assert_eq!(mixed.usdc_cost, 900.0);
+        assert!(mixed.is_funded());
+
+        let short = plan_entry(10.0, 150.0, 0.0, 1_000.0);
+        assert_eq!(short.currency, EntryCurrency::Usdc);
+        assert_eq!(short.usdc_shortfall, 500.0);
+    }
+}
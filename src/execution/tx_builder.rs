@@ -4,34 +4,47 @@
 //! - Drift Protocol perp orders
 //! - Jupiter swaps for spot
 //! - Atomic basis trade bundles
+//! - Durable nonce transactions for reduce-only orders and closes, when a
+//!   nonce account is attached
+//! - USDC transfers out of the wallet (profit sweeps)
 
 use anyhow::{Context, Result};
+use serde::Serialize;
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction,
     hash::Hash,
-    instruction::Instruction,
-    message::Message,
+    instruction::{AccountMeta, Instruction},
+    message::{Message, VersionedMessage},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
 };
 use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{debug, info};
 
+use super::nonce::NonceManager;
 use crate::config::AppConfig;
 use crate::network::RpcManager;
+use crate::telemetry::{timed, LatencyStage};
+use crate::utils::helpers::{associated_token_address, TOKEN_PROGRAM_ID};
+
+/// SPL Token `TransferChecked` instruction tag
+const TRANSFER_CHECKED_TAG: u8 = 12;
+
+/// USDC mint decimals
+const USDC_DECIMALS: u8 = 6;
 
 /// Drift order side
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum OrderSide {
     Long,
     Short,
 }
 
 /// Drift order type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum OrderType {
     Market,
     Limit,
@@ -48,6 +61,8 @@ pub struct DriftOrderParams {
     pub base_asset_amount: u64,
     pub price: Option<u64>,
     pub reduce_only: bool,
+    /// Reject instead of taking liquidity (limit orders only)
+    pub post_only: bool,
 }
 
 /// Jupiter swap parameters
@@ -68,6 +83,12 @@ pub struct BasisTradeParams {
     pub perp_size: u64,
     /// Perp side (opposite of spot)
     pub perp_side: OrderSide,
+    /// Perp order type (market, or limit when the book is thin)
+    pub perp_order_type: OrderType,
+    /// Perp limit price (price precision), required for limit orders
+    pub perp_price: Option<u64>,
+    /// Place the perp leg as a post-only (maker) order
+    pub perp_post_only: bool,
     /// Max slippage in basis points
     pub slippage_bps: u16,
 }
@@ -82,6 +103,8 @@ pub struct TransactionBuilder {
     drift_program_id: Pubkey,
     /// Compute unit limit
     compute_units: u32,
+    /// Nonce account for durable transactions
+    nonce: Option<Arc<NonceManager>>,
 }
 
 impl TransactionBuilder {
@@ -95,9 +118,32 @@ impl TransactionBuilder {
             rpc,
             drift_program_id,
             compute_units: 400_000, // Default compute units
+            nonce: None,
         })
     }
     
+    /// Sign reduce-only orders and closes over a durable nonce
+    pub fn with_nonce(mut self, nonce: Option<Arc<NonceManager>>) -> Self {
+        self.nonce = nonce;
+        self
+    }
+    
+    /// Sign `instructions` into a transaction, over the durable nonce (with
+    /// its advance instruction first) when `durable` and a nonce is attached
+    async fn sign(&self, payer: &Keypair, mut instructions: Vec<Instruction>, durable: bool) -> Result<Transaction> {
+        let blockhash = match self.nonce.as_ref().filter(|_| durable) {
+            Some(nonce) => {
+                instructions.insert(0, nonce.advance_ix());
+                timed(LatencyStage::Blockhash, nonce.fetch_nonce()).await?
+            }
+            None => timed(LatencyStage::Blockhash, self.rpc.get_recent_blockhash()).await?,
+        };
+        let message = Message::new(&instructions, Some(&payer.pubkey()));
+        let mut tx = Transaction::new_unsigned(message);
+        tx.partial_sign(&[payer], blockhash);
+        Ok(tx)
+    }
+    
     /// Build a priority fee instruction
     pub fn build_priority_fee_ix(&self, priority_fee: u64) -> Vec<Instruction> {
         vec![
@@ -151,6 +197,9 @@ impl TransactionBuilder {
         // Reduce only flag
         data.push(if params.reduce_only { 1 } else { 0 });
         
+        // Post only flag
+        data.push(if params.post_only { 1 } else { 0 });
+        
         // In production, we'd need proper account metas for:
         // - State account
         // - User account
@@ -174,6 +223,7 @@ impl TransactionBuilder {
     }
     
     /// Build a complete basis trade transaction bundle
+    #[tracing::instrument(name = "tx_build", skip_all, fields(spot_sol = params.spot_amount_sol))]
     pub async fn build_basis_trade(
         &self,
         payer: &Keypair,
@@ -183,7 +233,7 @@ impl TransactionBuilder {
         let mut instructions = Vec::new();
         
         // 1. Add priority fee
-        let priority_fee = self.get_dynamic_priority_fee().await?;
+        let priority_fee = self.priority_fee().await?;
         instructions.extend(self.build_priority_fee_ix(priority_fee));
         
         // 2. Add spot swap (Jupiter instructions)
@@ -193,20 +243,15 @@ impl TransactionBuilder {
         let perp_order = DriftOrderParams {
             market_index: self.config.protocols.drift.market_index,
             side: params.perp_side,
-            order_type: OrderType::Market,
+            order_type: params.perp_order_type,
             base_asset_amount: params.perp_size,
-            price: None,
+            price: params.perp_price,
             reduce_only: false,
+            post_only: params.perp_post_only,
         };
         instructions.push(self.build_drift_place_order_ix(&payer.pubkey(), &perp_order)?);
         
-        // Get recent blockhash
-        let blockhash = self.rpc.get_recent_blockhash().await?;
-        
-        // Build transaction
-        let message = Message::new(&instructions, Some(&payer.pubkey()));
-        let mut tx = Transaction::new_unsigned(message);
-        tx.partial_sign(&[payer], blockhash);
+        let tx = self.sign(payer, instructions, false).await?;
         
         info!(
             "Built basis trade: spot={:.4} SOL, perp={} ({:?}), priority_fee={}",
@@ -217,6 +262,7 @@ impl TransactionBuilder {
     }
     
     /// Build close position transaction
+    #[tracing::instrument(name = "tx_build_close", skip_all)]
     pub async fn build_close_position(
         &self,
         payer: &Keypair,
@@ -227,7 +273,7 @@ impl TransactionBuilder {
         let mut instructions = Vec::new();
         
         // Priority fee
-        let priority_fee = self.get_dynamic_priority_fee().await?;
+        let priority_fee = self.priority_fee().await?;
         instructions.extend(self.build_priority_fee_ix(priority_fee));
         
         // Close perp (opposite side)
@@ -243,24 +289,127 @@ impl TransactionBuilder {
             base_asset_amount: perp_size,
             price: None,
             reduce_only: true,
+            post_only: false,
         };
         instructions.push(self.build_drift_place_order_ix(&payer.pubkey(), &close_perp)?);
         
         // Note: Would also need Jupiter swap to convert USDC back to SOL
         // or close spot position
         
-        let blockhash = self.rpc.get_recent_blockhash().await?;
-        let message = Message::new(&instructions, Some(&payer.pubkey()));
-        let mut tx = Transaction::new_unsigned(message);
-        tx.partial_sign(&[payer], blockhash);
+        let tx = self.sign(payer, instructions, true).await?;
         
         info!("Built close position: spot={}, perp={}", spot_amount, perp_size);
         
         Ok(tx)
     }
     
-    /// Get dynamic priority fee based on network conditions
-    async fn get_dynamic_priority_fee(&self) -> Result<u64> {
+    /// Build a single perp market order (hedge adjustments)
+    #[tracing::instrument(name = "tx_build_perp", skip_all, fields(base_asset_amount))]
+    pub async fn build_perp_order(
+        &self,
+        payer: &Keypair,
+        side: OrderSide,
+        base_asset_amount: u64,
+        reduce_only: bool,
+    ) -> Result<Transaction> {
+        let order = DriftOrderParams {
+            market_index: self.config.protocols.drift.market_index,
+            side,
+            order_type: OrderType::Market,
+            base_asset_amount,
+            price: None,
+            reduce_only,
+            post_only: false,
+        };
+        self.build_order(payer, &order).await
+    }
+    
+    /// Build a single perp order of any type
+    #[tracing::instrument(name = "tx_build_order", skip_all, fields(base_asset_amount = params.base_asset_amount))]
+    pub async fn build_order(&self, payer: &Keypair, params: &DriftOrderParams) -> Result<Transaction> {
+        let mut instructions = self.build_priority_fee_ix(self.priority_fee().await?);
+        instructions.push(self.build_drift_place_order_ix(&payer.pubkey(), params)?);
+        
+        let tx = self.sign(payer, instructions, params.reduce_only).await?;
+        
+        debug!(
+            "Built perp order: {:?} {:?} {} (reduce_only={})",
+            params.order_type, params.side, params.base_asset_amount, params.reduce_only
+        );
+        
+        Ok(tx)
+    }
+    
+    /// Build a Drift cancel order transaction; `None` cancels the most recent order
+    pub async fn build_cancel_order(&self, payer: &Keypair, order_id: Option<u32>) -> Result<Transaction> {
+        // Drift cancel_order instruction discriminator
+        let mut data = vec![95, 129, 237, 240, 8, 49, 223, 132];
+        match order_id {
+            Some(id) => {
+                data.push(1);
+                data.extend_from_slice(&id.to_le_bytes());
+            }
+            None => data.push(0),
+        }
+        
+        let mut instructions = self.build_priority_fee_ix(self.priority_fee().await?);
+        instructions.push(Instruction {
+            program_id: self.drift_program_id,
+            accounts: vec![
+                // Placeholder - would need state, user and authority accounts
+            ],
+            data,
+        });
+        
+        let tx = self.sign(payer, instructions, false).await?;
+        
+        debug!("Built cancel order: {:?}", order_id);
+        
+        Ok(tx)
+    }
+    
+    /// Build a transfer of `amount` USDC base units from the payer's USDC
+    /// account to `destination`'s
+    pub async fn build_usdc_transfer(&self, payer: &Keypair, destination: &Pubkey, amount: u64) -> Result<Transaction> {
+        let mint = Pubkey::from_str(&self.config.protocols.jupiter.usdc_mint).context("Invalid USDC mint address")?;
+        let token_program = Pubkey::from_str(TOKEN_PROGRAM_ID)?;
+        let mut data = vec![TRANSFER_CHECKED_TAG];
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.push(USDC_DECIMALS);
+        let transfer = Instruction {
+            program_id: token_program,
+            accounts: vec![
+                AccountMeta::new(associated_token_address(&payer.pubkey(), &mint), false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new(associated_token_address(destination, &mint), false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+            ],
+            data,
+        };
+        
+        let mut instructions = self.build_priority_fee_ix(self.priority_fee().await?);
+        instructions.push(transfer);
+        let tx = self.sign(payer, instructions, false).await?;
+        
+        debug!("Built USDC transfer of {} units to {}", amount, destination);
+        
+        Ok(tx)
+    }
+    
+    /// Build a standalone transaction, e.g. a Jito tip
+    pub async fn build_instructions(&self, payer: &Keypair, instructions: Vec<Instruction>) -> Result<Transaction> {
+        self.sign(payer, instructions, false).await
+    }
+    
+    /// Sign a prebuilt message (e.g. a Jupiter swap) on a fresh blockhash
+    pub async fn sign_versioned(&self, payer: &Keypair, mut message: VersionedMessage) -> Result<VersionedTransaction> {
+        let blockhash = timed(LatencyStage::Blockhash, self.rpc.get_recent_blockhash()).await?;
+        message.set_recent_blockhash(blockhash);
+        VersionedTransaction::try_new(message, &[payer]).context("Failed to sign transaction")
+    }
+    
+    /// Priority fee (micro-lamports per compute unit) for the configured strategy
+    pub async fn priority_fee(&self) -> Result<u64> {
         match self.config.execution.priority_fee.strategy.as_str() {
             "fixed" => Ok(self.config.execution.priority_fee.fixed_fee),
             "dynamic" => {
@@ -278,6 +427,11 @@ impl TransactionBuilder {
     pub fn set_compute_units(&mut self, units: u32) {
         self.compute_units = units;
     }
+    
+    /// Compute unit limit requested by built transactions
+    pub fn compute_unit_limit(&self) -> u32 {
+        self.compute_units
+    }
 }
 
 #[cfg(test)]
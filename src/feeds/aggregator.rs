@@ -0,0 +1,265 @@
+//! Spot Price Aggregator
+//!
+//! Combines the spot sources (Pyth, Switchboard, Jupiter) into the one
+//! composite price the rest of the bot uses:
+//! - Quotes older than `max_quote_age_ms` are left out
+//! - With three or more sources, quotes further than `outlier_pct` from the
+//!   median are rejected
+//! - The remaining quotes are averaged by source weight
+//!
+//! The spread between the accepted quotes is published as the oracle
+//! divergence, which the risk manager checks before entries. Jupiter quotes
+//! SOL in USDC; its quotes are converted to USD at the USDC/USD in shared
+//! state first.
+//!
+//! When Pyth stops publishing for `contingency.pyth_stale_ms`, the
+//! aggregator enters oracle contingency: spot is priced from the remaining
+//! sources (Switchboard, Jupiter), the flag in shared state widens the
+//! engines' and risk manager's thresholds and blocks entries, and a critical
+//! alert is sent. It ends once Pyth is fresh again. Time is taken from the
+//! updates themselves, so contingency is only judged while some source
+//! still publishes; a total outage shows up as stale prices instead.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::config::OracleAggregationConfig;
+use crate::network::event_bus::Event;
+use crate::state::SharedState;
+use crate::telemetry::{record_oracle_contingency, record_oracle_divergence, Alert};
+use crate::utils::types::{PriceSource, PriceUpdate};
+
+/// Sources needed before one can be called an outlier
+const MIN_SOURCES_FOR_OUTLIERS: usize = 3;
+
+/// Composite of the fresh spot quotes
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositePrice {
+    pub price: f64,
+    /// Spread between the accepted quotes, over the median (%)
+    pub divergence_pct: f64,
+    pub sources: Vec<PriceSource>,
+    /// Quotes rejected as outliers
+    pub rejected: Vec<PriceSource>,
+}
+
+/// Combine the latest quote of each source into a composite price
+pub fn aggregate(
+    quotes: &[PriceUpdate],
+    now: i64,
+    config: &OracleAggregationConfig,
+) -> Option<CompositePrice> {
+    let fresh: Vec<&PriceUpdate> = quotes
+        .iter()
+        .filter(|q| q.price > 0.0 && now - q.timestamp <= config.max_quote_age_ms)
+        .collect();
+    if fresh.is_empty() {
+        return None;
+    }
+
+    let median = median(fresh.iter().map(|q| q.price).collect());
+    let (accepted, rejected): (Vec<&PriceUpdate>, Vec<&PriceUpdate>) = fresh.iter().partition(|q| {
+        fresh.len() < MIN_SOURCES_FOR_OUTLIERS || (q.price / median - 1.0).abs() * 100.0 <= config.outlier_pct
+    });
+    if accepted.is_empty() {
+        return None;
+    }
+
+    let weight = |source: PriceSource| match source {
+        PriceSource::Pyth => config.pyth_weight,
+        PriceSource::Switchboard => config.switchboard_weight,
+        PriceSource::Jupiter => config.jupiter_weight,
+        _ => 1.0,
+    };
+    let total_weight: f64 = accepted.iter().map(|q| weight(q.source)).sum();
+    let price = if total_weight > 0.0 {
+        accepted.iter().map(|q| q.price * weight(q.source)).sum::<f64>() / total_weight
+    } else {
+        median
+    };
+
+    let (low, high) = accepted.iter().fold((f64::MAX, f64::MIN), |(lo, hi), q| (lo.min(q.price), hi.max(q.price)));
+    Some(CompositePrice {
+        price,
+        divergence_pct: (high - low) / median * 100.0,
+        sources: accepted.iter().map(|q| q.source).collect(),
+        rejected: rejected.iter().map(|q| q.source).collect(),
+    })
+}
+
+/// Whether spot pricing is in contingency at `now`, given Pyth's latest
+/// quote time, when quotes started arriving, and whether it already was
+pub fn in_contingency(
+    pyth_at: Option<i64>,
+    started_at: i64,
+    now: i64,
+    active: bool,
+    config: &OracleAggregationConfig,
+) -> bool {
+    if !config.contingency.enabled {
+        return false;
+    }
+    // A Pyth that never published is silent since the first quote
+    let silent_ms = now - pyth_at.unwrap_or(started_at);
+    if active {
+        silent_ms > config.max_quote_age_ms
+    } else {
+        silent_ms > config.contingency.pyth_stale_ms
+    }
+}
+
+fn median(mut prices: Vec<f64>) -> f64 {
+    prices.sort_by(|a, b| a.total_cmp(b));
+    let mid = prices.len() / 2;
+    if prices.len() % 2 == 0 {
+        (prices[mid - 1] + prices[mid]) / 2.0
+    } else {
+        prices[mid]
+    }
+}
+
+/// Publishes the composite spot price from `OracleUpdate` events
+pub struct SpotAggregator {
+    config: OracleAggregationConfig,
+    state: Arc<SharedState>,
+    event_tx: broadcast::Sender<Event>,
+}
+
+impl SpotAggregator {
+    pub fn new(config: &OracleAggregationConfig, state: Arc<SharedState>, event_tx: broadcast::Sender<Event>) -> Self {
+        Self { config: config.clone(), state, event_tx }
+    }
+
+    /// Aggregate every source update until the task is aborted
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let config = self.config.clone();
+        let state = self.state.clone();
+        let event_tx = self.event_tx.clone();
+        let mut event_rx = event_tx.subscribe();
+
+        tokio::spawn(async move {
+            let mut latest: HashMap<PriceSource, PriceUpdate> = HashMap::new();
+            // Newest update time seen, and the first
+            let mut clock = 0i64;
+            let mut first_quote_at = None;
+            loop {
+                let update = match event_rx.recv().await {
+                    Ok(Event::OracleUpdate(update)) if update.source == PriceSource::Jupiter => PriceUpdate {
+                        price: update.price * state.usdc_usd.load(),
+                        ..update
+                    },
+                    Ok(Event::OracleUpdate(update)) => update,
+                    Ok(_) => continue,
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("Spot aggregator lagged by {} events", n);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                // Pyth carries its publish time, which may be older than the clock
+                clock = clock.max(update.timestamp);
+                let now = clock;
+                let started_at = *first_quote_at.get_or_insert(now);
+                latest.insert(update.source, update);
+
+                let active = *state.oracle_contingency.read();
+                let pyth_at = latest.get(&PriceSource::Pyth).map(|q| q.timestamp);
+                let contingency = in_contingency(pyth_at, started_at, now, active, &config);
+                if contingency != active {
+                    *state.oracle_contingency.write() = contingency;
+                    record_oracle_contingency(contingency);
+                    let alert = if contingency {
+                        let secondary: Vec<String> = latest
+                            .values()
+                            .filter(|q| q.source != PriceSource::Pyth && now - q.timestamp <= config.max_quote_age_ms)
+                            .map(|q| q.source.to_string())
+                            .collect();
+                        warn!("Pyth silent for {}ms, entering oracle contingency", now - pyth_at.unwrap_or(started_at));
+                        Alert::critical(
+                            "Oracle contingency",
+                            format!(
+                                "Pyth silent for {}s; spot priced from {}, thresholds widened x{}, new entries blocked",
+                                (now - pyth_at.unwrap_or(started_at)) / 1000,
+                                if secondary.is_empty() { "no fresh source".to_string() } else { secondary.join(", ") },
+                                config.contingency.threshold_multiplier
+                            ),
+                        )
+                    } else {
+                        info!("Pyth publishing again, leaving oracle contingency");
+                        Alert::info("Oracle contingency over", "Pyth publishing again; normal pricing and entries resumed")
+                    };
+                    let _ = event_tx.send(Event::Alert(alert));
+                }
+
+                let quotes: Vec<PriceUpdate> = latest.values().cloned().collect();
+                let Some(composite) = aggregate(&quotes, now, &config) else {
+                    continue;
+                };
+                if !composite.rejected.is_empty() {
+                    debug!("Rejected outlier spot sources: {:?}", composite.rejected);
+                }
+                state.oracle_divergence_pct.store(composite.divergence_pct);
+                record_oracle_divergence(composite.divergence_pct);
+                let _ = event_tx.send(Event::SpotPriceUpdate(PriceUpdate {
+                    source: PriceSource::Composite,
+                    price: composite.price,
+                    confidence: None,
+                    timestamp: now,
+                }));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(source: PriceSource, price: f64, timestamp: i64) -> PriceUpdate {
+        PriceUpdate { source, price, confidence: None, timestamp }
+    }
+
+    #[test]
+    fn test_outlier_rejected_and_weighted() {
+        let config = OracleAggregationConfig::default();
+        let quotes = vec![
+            quote(PriceSource::Pyth, 100.0, 1_000),
+            quote(PriceSource::Switchboard, 100.2, 1_000),
+            quote(PriceSource::Jupiter, 103.0, 1_000),
+        ];
+        let composite = aggregate(&quotes, 1_500, &config).unwrap();
+        assert_eq!(composite.rejected, vec![PriceSource::Jupiter]);
+        assert!((composite.price - 100.1).abs() < 1e-9);
+        assert!((composite.divergence_pct - 0.2 / 100.2 * 100.0).abs() < 1e-9);
+
+        // Two sources cannot outvote each other: both kept, divergence shows
+        let composite = aggregate(&quotes[1..], 1_500, &config).unwrap();
+        assert!(composite.rejected.is_empty());
+        assert!(composite.divergence_pct > 2.0);
+
+        // Stale quotes are dropped
+        assert!(aggregate(&quotes, 10_000, &config).is_none());
+    }
+
+    #[test]
+    fn test_contingency_enters_on_stale_pyth_and_recovers() {
+        let config = OracleAggregationConfig::default();
+        // Silent Pyth: in contingency past pyth_stale_ms, whether or not it ever published
+        assert!(!in_contingency(Some(1_000), 0, 9_000, false, &config));
+        assert!(in_contingency(Some(1_000), 0, 12_000, false, &config));
+        assert!(in_contingency(None, 1_000, 12_000, false, &config));
+
+        // Leaving needs Pyth within max_quote_age_ms, not just under pyth_stale_ms
+        assert!(in_contingency(Some(5_000), 0, 12_000, true, &config));
+        assert!(!in_contingency(Some(9_000), 0, 12_000, true, &config));
+
+        let disabled = OracleAggregationConfig {
+            contingency: crate::config::OracleContingencyConfig { enabled: false, ..Default::default() },
+            ..config
+        };
+        assert!(!in_contingency(None, 0, 60_000, false, &disabled));
+    }
+}
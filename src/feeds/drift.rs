@@ -12,6 +12,7 @@ use tracing::{debug, error, info, warn};
 
 use crate::config::DriftConfig;
 use crate::network::event_bus::Event;
+use crate::network::HttpRetryPolicy;
 use crate::utils::types::{PriceSource, PriceUpdate};
 
 /// Drift market data response
@@ -51,6 +52,10 @@ pub struct DriftFeed {
     program_id: String,
     /// Market index (0 = SOL-PERP)
     market_index: u16,
+    /// Drift data API base URL
+    api_url: String,
+    /// Publish funding updates (off when synthetic funding replaces them)
+    publish_funding: bool,
     /// Event sender
     event_tx: broadcast::Sender<Event>,
     /// Is running
@@ -63,6 +68,8 @@ pub struct DriftFeed {
     last_funding_rate: Arc<RwLock<Option<f64>>>,
     /// HTTP client
     client: reqwest::Client,
+    /// Retries and cool-off of the API calls
+    retry: HttpRetryPolicy,
 }
 
 impl DriftFeed {
@@ -71,6 +78,8 @@ impl DriftFeed {
         Self {
             program_id: config.program_id.clone(),
             market_index: config.market_index,
+            api_url: config.api_url.clone(),
+            publish_funding: true,
             event_tx,
             running: Arc::new(RwLock::new(false)),
             last_mark_price: Arc::new(RwLock::new(None)),
@@ -80,9 +89,22 @@ impl DriftFeed {
                 .timeout(Duration::from_secs(10))
                 .build()
                 .unwrap(),
+            retry: HttpRetryPolicy::default(),
         }
     }
     
+    /// Retry and cool off API calls under `retry`
+    pub fn with_retry_policy(mut self, retry: HttpRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    
+    /// Stop publishing funding rates; prices are still published
+    pub fn without_funding(mut self) -> Self {
+        self.publish_funding = false;
+        self
+    }
+    
     /// Start the price feed
     pub async fn start(&self) -> Result<()> {
         *self.running.write().await = true;
@@ -90,11 +112,14 @@ impl DriftFeed {
         
         let running = self.running.clone();
         let market_index = self.market_index;
+        let api_url = self.api_url.clone();
+        let publish_funding = self.publish_funding;
         let event_tx = self.event_tx.clone();
         let last_mark_price = self.last_mark_price.clone();
         let last_index_price = self.last_index_price.clone();
         let last_funding_rate = self.last_funding_rate.clone();
         let client = self.client.clone();
+        let retry = self.retry.clone();
         
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_millis(500));
@@ -102,7 +127,7 @@ impl DriftFeed {
             while *running.read().await {
                 interval.tick().await;
                 
-                match Self::fetch_market_data(&client, market_index).await {
+                match Self::fetch_market_data(&client, &retry, &api_url, market_index).await {
                     Ok(data) => {
                         // Parse mark price
                         if let Some(mark_str) = &data.mark_price {
@@ -142,10 +167,12 @@ impl DriftFeed {
                                 debug!("Drift SOL-PERP funding rate: {:.6}%", rate * 100.0);
                                 *last_funding_rate.write().await = Some(rate);
                                 
-                                let _ = event_tx.send(Event::FundingRateUpdate {
-                                    rate,
-                                    timestamp: chrono::Utc::now().timestamp_millis(),
-                                });
+                                if publish_funding {
+                                    let _ = event_tx.send(Event::FundingRateUpdate {
+                                        rate,
+                                        timestamp: chrono::Utc::now().timestamp_millis(),
+                                    });
+                                }
                             }
                         }
                     }
@@ -164,18 +191,18 @@ impl DriftFeed {
     /// Fetch market data from Drift API
     async fn fetch_market_data(
         client: &reqwest::Client,
+        retry: &HttpRetryPolicy,
+        api_url: &str,
         market_index: u16,
     ) -> Result<DriftMarketData> {
         // Use Drift's public API endpoint
         let url = format!(
-            "https://mainnet-beta.api.drift.trade/stats/perpMarket?marketIndex={}",
+            "{}/stats/perpMarket?marketIndex={}",
+            api_url.trim_end_matches('/'),
             market_index
         );
         
-        let response = client
-            .get(&url)
-            .send()
-            .await?;
+        let response = retry.send(client.get(&url)).await?;
         
         // First try to parse as the API response
         let text = response.text().await?;
@@ -236,6 +263,11 @@ mod tests {
         let config = DriftConfig {
             program_id: "dRiftyHA39MWEi3m9aunc5MzRF1JYuBsbn6VPcn33UH".to_string(),
             market_index: 0,
+            funding_interval: Default::default(),
+            api_url: "https://mainnet-beta.api.drift.trade".to_string(),
+            dlob_url: "https://dlob.drift.trade".to_string(),
+            dlob_depth: 10,
+            dlob_poll_ms: 1000,
         };
         let (tx, _) = broadcast::channel(10);
         let feed = DriftFeed::new(&config, tx);
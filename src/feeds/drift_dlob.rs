@@ -0,0 +1,198 @@
+//! Drift DLOB Feed
+//!
+//! Polls the Drift decentralized limit order book (DLOB) server for the
+//! perp market's L2 book, giving real best bid/ask and depth for
+//! estimating perp execution cost.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, info, warn};
+
+use crate::config::DriftConfig;
+use crate::network::event_bus::Event;
+use crate::network::HttpRetryPolicy;
+use crate::utils::amount::{from_base_units, DRIFT_BASE_DECIMALS, DRIFT_PRICE_DECIMALS};
+use crate::utils::types::{BookLevel, OrderBook};
+
+/// DLOB L2 level (precision-scaled integer strings)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DlobLevel {
+    pub price: String,
+    pub size: String,
+}
+
+/// DLOB L2 response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DlobL2Response {
+    pub bids: Vec<DlobLevel>,
+    pub asks: Vec<DlobLevel>,
+    #[serde(default)]
+    pub slot: Option<u64>,
+}
+
+impl DlobL2Response {
+    /// Convert to an order book in human units, skipping unparseable levels
+    pub fn into_book(self, timestamp: i64) -> OrderBook {
+        let convert = |levels: Vec<DlobLevel>| -> Vec<BookLevel> {
+            levels
+                .iter()
+                .filter_map(|l| {
+                    Some(BookLevel {
+                        price: from_base_units(l.price.parse().ok()?, DRIFT_PRICE_DECIMALS),
+                        size: from_base_units(l.size.parse().ok()?, DRIFT_BASE_DECIMALS),
+                    })
+                })
+                .collect()
+        };
+
+        OrderBook {
+            bids: convert(self.bids),
+            asks: convert(self.asks),
+            slot: self.slot.unwrap_or(0),
+            timestamp,
+        }
+    }
+}
+
+/// Drift DLOB order book feed
+pub struct DlobFeed {
+    /// DLOB server URL
+    dlob_url: String,
+    /// Market index (0 = SOL-PERP)
+    market_index: u16,
+    /// Levels requested per side
+    depth: usize,
+    /// Poll interval
+    poll_interval: Duration,
+    /// Event sender
+    event_tx: broadcast::Sender<Event>,
+    /// Is running
+    running: Arc<RwLock<bool>>,
+    /// Last book
+    last_book: Arc<RwLock<Option<OrderBook>>>,
+    /// HTTP client
+    client: reqwest::Client,
+    /// Retries and cool-off of the API calls
+    retry: HttpRetryPolicy,
+}
+
+impl DlobFeed {
+    /// Create a new DLOB feed
+    pub fn new(config: &DriftConfig, event_tx: broadcast::Sender<Event>) -> Self {
+        Self {
+            dlob_url: config.dlob_url.trim_end_matches('/').to_string(),
+            market_index: config.market_index,
+            depth: config.dlob_depth,
+            poll_interval: Duration::from_millis(config.dlob_poll_ms.max(250)),
+            event_tx,
+            running: Arc::new(RwLock::new(false)),
+            last_book: Arc::new(RwLock::new(None)),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap(),
+            retry: HttpRetryPolicy::default(),
+        }
+    }
+    
+    /// Retry and cool off API calls under `retry`
+    pub fn with_retry_policy(mut self, retry: HttpRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    
+    /// Start the order book feed
+    pub async fn start(&self) -> Result<()> {
+        *self.running.write().await = true;
+        info!("Drift DLOB feed starting for market index {}", self.market_index);
+        
+        let running = self.running.clone();
+        let url = format!(
+            "{}/l2?marketIndex={}&marketType=perp&depth={}",
+            self.dlob_url, self.market_index, self.depth
+        );
+        let period = self.poll_interval;
+        let event_tx = self.event_tx.clone();
+        let last_book = self.last_book.clone();
+        let client = self.client.clone();
+        let retry = self.retry.clone();
+        
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            
+            while *running.read().await {
+                interval.tick().await;
+                
+                match Self::fetch_book(&client, &retry, &url).await {
+                    Ok(book) => {
+                        if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+                            debug!("Drift DLOB: bid ${:.4} / ask ${:.4}", bid, ask);
+                        }
+                        *last_book.write().await = Some(book.clone());
+                        let _ = event_tx.send(Event::PerpBookUpdate(book));
+                    }
+                    Err(e) => {
+                        warn!("Failed to fetch Drift DLOB: {}", e);
+                    }
+                }
+            }
+            
+            info!("Drift DLOB feed stopped");
+        });
+        
+        Ok(())
+    }
+    
+    /// Fetch the L2 book from the DLOB server
+    async fn fetch_book(client: &reqwest::Client, retry: &HttpRetryPolicy, url: &str) -> Result<OrderBook> {
+        let response: DlobL2Response = retry
+            .send(client.get(url))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        
+        Ok(response.into_book(chrono::Utc::now().timestamp_millis()))
+    }
+    
+    /// Stop the order book feed
+    pub async fn stop(&self) {
+        *self.running.write().await = false;
+        info!("Drift DLOB feed stopping");
+    }
+    
+    /// Get last order book
+    pub async fn get_last_book(&self) -> Option<OrderBook> {
+        self.last_book.read().await.clone()
+    }
+    
+    /// Check if feed is running
+    pub async fn is_running(&self) -> bool {
+        *self.running.read().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l2_response_conversion() {
+        let json = r#"{
+            "bids": [{"price": "149950000", "size": "2500000000"}],
+            "asks": [{"price": "150050000", "size": "1000000000"}, {"price": "bad", "size": "1"}],
+            "slot": 250000000
+        }"#;
+        let response: DlobL2Response = serde_json::from_str(json).unwrap();
+        let book = response.into_book(0);
+
+        assert_eq!(book.best_bid(), Some(149.95));
+        assert_eq!(book.best_ask(), Some(150.05));
+        assert_eq!(book.bids[0].size, 2.5);
+        assert_eq!(book.asks.len(), 1);
+        assert_eq!(book.slot, 250_000_000);
+    }
+}
@@ -1,6 +1,8 @@
 //! Jupiter Spot Price Feed
 //!
-//! Fetches aggregated spot prices from Jupiter.
+//! Fetches aggregated spot prices from Jupiter, and periodically quotes the
+//! typical trade size in both directions to derive effective (depth-aware)
+//! buy and sell prices.
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -11,7 +13,9 @@ use tracing::{debug, error, info, warn};
 
 use crate::config::JupiterConfig;
 use crate::network::event_bus::Event;
-use crate::utils::types::{PriceSource, PriceUpdate};
+use crate::network::HttpRetryPolicy;
+use crate::utils::amount::{from_base_units, to_base_units, USDC_DECIMALS};
+use crate::utils::types::{DepthQuote, PriceSource, PriceUpdate};
 
 /// Jupiter price response
 #[derive(Debug, Serialize, Deserialize)]
@@ -41,6 +45,12 @@ pub struct JupiterFeed {
     sol_mint: String,
     /// USDC mint address
     usdc_mint: String,
+    /// Decimals of the spot token (9 for SOL)
+    spot_decimals: u8,
+    /// Trade size quoted for depth-aware prices (SOL)
+    depth_quote_size_sol: f64,
+    /// Depth quote refresh interval
+    depth_quote_interval: Duration,
     /// Event sender
     event_tx: broadcast::Sender<Event>,
     /// Is running
@@ -49,6 +59,8 @@ pub struct JupiterFeed {
     last_price: Arc<RwLock<Option<f64>>>,
     /// HTTP client
     client: reqwest::Client,
+    /// Retries and cool-off of the API calls
+    retry: HttpRetryPolicy,
 }
 
 impl JupiterFeed {
@@ -58,6 +70,9 @@ impl JupiterFeed {
             api_url: config.api_url.clone(),
             sol_mint: config.sol_mint.clone(),
             usdc_mint: config.usdc_mint.clone(),
+            spot_decimals: config.spot_decimals,
+            depth_quote_size_sol: config.depth_quote_size_sol,
+            depth_quote_interval: Duration::from_millis(config.depth_quote_interval_ms.max(1000)),
             event_tx,
             running: Arc::new(RwLock::new(false)),
             last_price: Arc::new(RwLock::new(None)),
@@ -65,9 +80,16 @@ impl JupiterFeed {
                 .timeout(Duration::from_secs(10))
                 .build()
                 .unwrap(),
+            retry: HttpRetryPolicy::default(),
         }
     }
     
+    /// Retry and cool off API calls under `retry`
+    pub fn with_retry_policy(mut self, retry: HttpRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    
     /// Start the price feed
     pub async fn start(&self) -> Result<()> {
         *self.running.write().await = true;
@@ -78,6 +100,7 @@ impl JupiterFeed {
         let event_tx = self.event_tx.clone();
         let last_price = self.last_price.clone();
         let client = self.client.clone();
+        let retry = self.retry.clone();
         
         tokio::spawn(async move {
             // Poll every 1 second (Jupiter has rate limits)
@@ -86,7 +109,7 @@ impl JupiterFeed {
             while *running.read().await {
                 interval.tick().await;
                 
-                match Self::fetch_price(&client, &sol_mint).await {
+                match Self::fetch_price(&client, &retry, &sol_mint).await {
                     Ok(price) => {
                         debug!("Jupiter SOL/USDC price: ${:.4}", price);
                         
@@ -99,8 +122,8 @@ impl JupiterFeed {
                             timestamp: chrono::Utc::now().timestamp_millis(),
                         };
                         
-                        // Jupiter provides spot price backup/validation
-                        let _ = event_tx.send(Event::SpotPriceUpdate(update));
+                        // One of the sources of the composite spot price
+                        let _ = event_tx.send(Event::OracleUpdate(update));
                     }
                     Err(e) => {
                         warn!("Failed to fetch Jupiter price: {}", e);
@@ -111,19 +134,95 @@ impl JupiterFeed {
             info!("Jupiter price feed stopped");
         });
         
+        if self.depth_quote_size_sol > 0.0 {
+            self.spawn_depth_quotes();
+        }
+        
         Ok(())
     }
     
+    /// Periodically quote the typical trade size in both directions
+    fn spawn_depth_quotes(&self) {
+        let running = self.running.clone();
+        let api_url = self.api_url.clone();
+        let sol_mint = self.sol_mint.clone();
+        let usdc_mint = self.usdc_mint.clone();
+        let spot_decimals = self.spot_decimals;
+        let size_sol = self.depth_quote_size_sol;
+        let period = self.depth_quote_interval;
+        let event_tx = self.event_tx.clone();
+        let last_price = self.last_price.clone();
+        let client = self.client.clone();
+        let retry = self.retry.clone();
+        
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            
+            while *running.read().await {
+                interval.tick().await;
+                
+                // The buy side is quoted in USDC, so a reference price is needed first
+                let Some(mid) = *last_price.read().await else {
+                    continue;
+                };
+                
+                let (Ok(sell_in), Ok(buy_in)) = (
+                    to_base_units(size_sol, spot_decimals),
+                    to_base_units(size_sol * mid, USDC_DECIMALS),
+                ) else {
+                    warn!("Jupiter depth quote size out of range");
+                    continue;
+                };
+                let sell = Self::fetch_quote(&client, &retry, &api_url, &sol_mint, &usdc_mint, sell_in).await;
+                let buy = Self::fetch_quote(&client, &retry, &api_url, &usdc_mint, &sol_mint, buy_in).await;
+                
+                match (buy, sell) {
+                    (Ok(buy), Ok(sell)) => match Self::depth_quote(size_sol, spot_decimals, &buy, &sell) {
+                        Some(quote) => {
+                            debug!(
+                                "Jupiter depth @ {:.0} SOL: buy ${:.4}, sell ${:.4} ({:.3}% spread)",
+                                size_sol, quote.buy_price, quote.sell_price, quote.spread_pct()
+                            );
+                            let _ = event_tx.send(Event::SpotDepthUpdate(quote));
+                        }
+                        None => warn!("Jupiter depth quote returned empty amounts"),
+                    },
+                    (Err(e), _) | (_, Err(e)) => {
+                        warn!("Failed to fetch Jupiter depth quote: {}", e);
+                    }
+                }
+            }
+        });
+    }
+    
+    /// Derive effective prices from a buy (USDC -> SOL) and sell (SOL -> USDC) quote
+    fn depth_quote(size_sol: f64, spot_decimals: u8, buy: &JupiterQuote, sell: &JupiterQuote) -> Option<DepthQuote> {
+        let usdc_in = from_base_units(buy.in_amount.parse().ok()?, USDC_DECIMALS);
+        let sol_out = from_base_units(buy.out_amount.parse().ok()?, spot_decimals);
+        let sol_in = from_base_units(sell.in_amount.parse().ok()?, spot_decimals);
+        let usdc_out = from_base_units(sell.out_amount.parse().ok()?, USDC_DECIMALS);
+        
+        if sol_out <= 0.0 || sol_in <= 0.0 {
+            return None;
+        }
+        
+        Some(DepthQuote {
+            size_sol,
+            buy_price: usdc_in / sol_out,
+            sell_price: usdc_out / sol_in,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        })
+    }
+    
     /// Fetch price from Jupiter Price API
-    async fn fetch_price(client: &reqwest::Client, sol_mint: &str) -> Result<f64> {
+    async fn fetch_price(client: &reqwest::Client, retry: &HttpRetryPolicy, sol_mint: &str) -> Result<f64> {
         let url = format!(
             "https://price.jup.ag/v6/price?ids={}",
             sol_mint
         );
         
-        let response: JupiterPriceResponse = client
-            .get(&url)
-            .send()
+        let response: JupiterPriceResponse = retry
+            .send(client.get(&url))
             .await?
             .json()
             .await?;
@@ -157,15 +256,26 @@ impl JupiterFeed {
         input_mint: &str,
         output_mint: &str,
         amount: u64,
+    ) -> Result<JupiterQuote> {
+        Self::fetch_quote(&self.client, &self.retry, &self.api_url, input_mint, output_mint, amount).await
+    }
+    
+    /// Fetch a swap quote from the Jupiter Quote API
+    async fn fetch_quote(
+        client: &reqwest::Client,
+        retry: &HttpRetryPolicy,
+        api_url: &str,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
     ) -> Result<JupiterQuote> {
         let url = format!(
             "{}/quote?inputMint={}&outputMint={}&amount={}&slippageBps=50",
-            self.api_url, input_mint, output_mint, amount
+            api_url, input_mint, output_mint, amount
         );
         
-        let quote: JupiterQuote = self.client
-            .get(&url)
-            .send()
+        let quote: JupiterQuote = retry
+            .send(client.get(&url))
             .await?
             .json()
             .await?;
@@ -232,10 +342,38 @@ mod tests {
         let config = JupiterConfig {
             api_url: "https://quote-api.jup.ag/v6".to_string(),
             sol_mint: "So11111111111111111111111111111111111111112".to_string(),
+            spot_decimals: 9,
             usdc_mint: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            depth_quote_size_sol: 100.0,
+            depth_quote_interval_ms: 5000,
         };
         let (tx, _) = broadcast::channel(10);
         let feed = JupiterFeed::new(&config, tx);
         assert_eq!(feed.sol_mint, config.sol_mint);
+        assert_eq!(feed.spot_decimals, 9);
+    }
+
+    #[test]
+    fn test_depth_quote_effective_prices() {
+        let quote = |in_amount: &str, out_amount: &str| JupiterQuote {
+            input_mint: String::new(),
+            in_amount: in_amount.to_string(),
+            output_mint: String::new(),
+            out_amount: out_amount.to_string(),
+            other_amount_threshold: "0".to_string(),
+            swap_mode: "ExactIn".to_string(),
+            slippage_bps: 50,
+            price_impact_pct: "0".to_string(),
+            route_plan: vec![],
+        };
+
+        // 15,000 USDC buys 99.5 SOL; 100 SOL sells for 14,940 USDC
+        let buy = quote("15000000000", "99500000000");
+        let sell = quote("100000000000", "14940000000");
+        let depth = JupiterFeed::depth_quote(100.0, 9, &buy, &sell).unwrap();
+
+        assert!((depth.buy_price - 150.7538).abs() < 1e-3);
+        assert!((depth.sell_price - 149.4).abs() < 1e-9);
+        assert!(depth.spread_pct() > 0.0);
     }
 }
@@ -0,0 +1,201 @@
+//! Mock Market Feed
+//!
+//! Plays a scripted sequence of market ticks for tests, with no network:
+//! - Each tick sets spot, perp mark and (optionally) funding
+//! - A tick can leave spot stale, as if the oracle had stalled
+//! - Ticks are applied to `SharedState` directly, as the bot's event
+//!   processor would, and published on the event bus for other listeners
+//! - Scripts either play on a timer (`start`) or step by hand (`advance`)
+//!
+//! After the last tick the feed holds its final prices.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, info};
+
+use crate::network::event_bus::Event;
+use crate::state::SharedState;
+use crate::utils::types::{PriceSource, PriceUpdate};
+
+use super::MarketFeed;
+
+/// One scripted market update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketTick {
+    /// Spot price (USD)
+    pub spot: f64,
+    /// Perp mark price (USD)
+    pub perp: f64,
+    /// Funding rate per interval, if it changes on this tick
+    pub funding_rate: Option<f64>,
+    /// Oracle stalled: spot is not republished on this tick
+    pub spot_stale: bool,
+}
+
+impl MarketTick {
+    /// Tick with the perp at `basis_pct` over spot
+    pub fn with_basis(spot: f64, basis_pct: f64) -> Self {
+        Self {
+            spot,
+            perp: spot * (1.0 + basis_pct / 100.0),
+            funding_rate: None,
+            spot_stale: false,
+        }
+    }
+
+    /// Also set the funding rate
+    pub fn funding(mut self, rate: f64) -> Self {
+        self.funding_rate = Some(rate);
+        self
+    }
+
+    /// Leave spot stale on this tick
+    pub fn stale_spot(mut self) -> Self {
+        self.spot_stale = true;
+        self
+    }
+}
+
+/// Scripted feed for tests
+pub struct MockFeed {
+    state: Arc<SharedState>,
+    event_tx: broadcast::Sender<Event>,
+    /// Ticks not yet played
+    script: Arc<Mutex<VecDeque<MarketTick>>>,
+    /// Delay between ticks when playing on a timer
+    step: Duration,
+    running: Arc<RwLock<bool>>,
+}
+
+impl MockFeed {
+    pub fn new(state: Arc<SharedState>, event_tx: broadcast::Sender<Event>) -> Self {
+        Self {
+            state,
+            event_tx,
+            script: Arc::new(Mutex::new(VecDeque::new())),
+            step: Duration::from_millis(100),
+            running: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    /// Delay between ticks when started
+    pub fn with_step(mut self, step: Duration) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Queue ticks after any still pending
+    pub fn push(&self, ticks: impl IntoIterator<Item = MarketTick>) {
+        self.script.lock().extend(ticks);
+    }
+
+    /// Ticks not yet played
+    pub fn remaining(&self) -> usize {
+        self.script.lock().len()
+    }
+
+    /// Play the next tick now; false once the script is exhausted
+    pub fn advance(&self) -> bool {
+        let tick = self.script.lock().pop_front();
+        match tick {
+            Some(tick) => {
+                Self::apply(&self.state, &self.event_tx, &tick);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Apply one tick to state and publish it
+    pub fn apply(state: &SharedState, event_tx: &broadcast::Sender<Event>, tick: &MarketTick) {
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        let update = |source, price| PriceUpdate { source, price, confidence: None, timestamp };
+        if !tick.spot_stale {
+            state.update_spot_price(tick.spot);
+            let _ = event_tx.send(Event::SpotPriceUpdate(update(PriceSource::Pyth, tick.spot)));
+        }
+        state.update_perp_mark_price(tick.perp);
+        let _ = event_tx.send(Event::PerpMarkPriceUpdate(update(PriceSource::DriftMark, tick.perp)));
+
+        if let Some(rate) = tick.funding_rate {
+            state.update_funding_rate(rate);
+            let _ = event_tx.send(Event::FundingRateUpdate { rate, timestamp });
+        }
+        debug!("Mock tick: spot ${:.4}, perp ${:.4}", tick.spot, tick.perp);
+    }
+
+    /// Play the script on a timer
+    pub async fn start(&self) -> Result<()> {
+        *self.running.write().await = true;
+        info!("Mock feed starting ({} ticks, {:?} apart)", self.remaining(), self.step);
+
+        let running = self.running.clone();
+        let script = self.script.clone();
+        let state = self.state.clone();
+        let event_tx = self.event_tx.clone();
+        let step = self.step;
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(step);
+
+            while *running.read().await {
+                interval.tick().await;
+                let tick = script.lock().pop_front();
+                if let Some(tick) = tick {
+                    Self::apply(&state, &event_tx, &tick);
+                }
+            }
+
+            info!("Mock feed stopped");
+        });
+
+        Ok(())
+    }
+
+    pub async fn stop(&self) {
+        *self.running.write().await = false;
+    }
+}
+
+#[async_trait]
+impl MarketFeed for MockFeed {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn start(&self) -> Result<()> {
+        MockFeed::start(self).await
+    }
+
+    async fn stop(&self) {
+        MockFeed::stop(self).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripted_ticks() {
+        let state = Arc::new(SharedState::new());
+        let (event_tx, mut rx) = broadcast::channel(16);
+        let feed = MockFeed::new(state.clone(), event_tx);
+        feed.push([MarketTick::with_basis(100.0, 0.5).funding(0.0001), MarketTick::with_basis(101.0, 0.0)]);
+
+        assert!(feed.advance());
+        assert!((state.get_basis_spread() - 0.5).abs() < 1e-9);
+        assert_eq!(state.snapshot().funding_rate, 0.0001);
+        assert!(matches!(rx.try_recv(), Ok(Event::SpotPriceUpdate(_))));
+
+        assert!(feed.advance());
+        assert_eq!(state.spot_price.load(), 101.0);
+        assert!(state.get_basis_spread().abs() < 1e-9);
+        assert!(!feed.advance());
+    }
+}
@@ -2,38 +2,105 @@
 //!
 //! Provides real-time price feeds from multiple sources:
 //! - Pyth oracle for SOL/USD
+//! - Switchboard oracle for SOL/USD (optional)
 //! - Jupiter for spot aggregation
 //! - Drift Protocol for perp prices
+//! - Drift DLOB for perp order book depth
+//! - Synthetic funding in place of Drift's (devnet)
+//! - Scripted market ticks for tests (`mock`)
+//! - USDC/USD peg monitor and depeg guard (`usdc_peg`)
+//!
+//! Which feeds run is up to the `protocols` venue registry.
+//!
+//! Spot sources publish `OracleUpdate`s; the `aggregator` combines them into
+//! the composite `SpotPriceUpdate` everything else trades on.
 
 pub mod pyth;
+pub mod switchboard;
 pub mod jupiter;
 pub mod drift;
+pub mod drift_dlob;
+pub mod synthetic;
+pub mod mock;
+pub mod aggregator;
+pub mod usdc_peg;
 
 pub use pyth::PythFeed;
+pub use switchboard::SwitchboardFeed;
 pub use jupiter::JupiterFeed;
 pub use drift::DriftFeed;
+pub use drift_dlob::DlobFeed;
+pub use synthetic::SyntheticFundingFeed;
+pub use mock::{MarketTick, MockFeed};
+pub use aggregator::{aggregate, in_contingency, CompositePrice, SpotAggregator};
+pub use usdc_peg::UsdcPegMonitor;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, Mutex};
+use tokio::task::JoinHandle;
 use tracing::info;
 
-use crate::config::ProtocolsConfig;
+use crate::config::{ProtocolsConfig, SyntheticFundingConfig};
 use crate::network::event_bus::Event;
+use crate::network::HttpRetryPolicy;
+use crate::protocols::{FeedContext, VenueRegistry};
 use crate::state::SharedState;
 
+/// A source of market data that publishes onto the event bus
+#[async_trait]
+pub trait MarketFeed: Send + Sync {
+    /// Short name for logs
+    fn name(&self) -> &'static str;
+    /// Start publishing in the background
+    async fn start(&self) -> Result<()>;
+    /// Stop publishing
+    async fn stop(&self);
+}
+
+macro_rules! market_feed {
+    ($feed:ty, $name:literal) => {
+        #[async_trait]
+        impl MarketFeed for $feed {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            async fn start(&self) -> Result<()> {
+                <$feed>::start(self).await
+            }
+
+            async fn stop(&self) {
+                <$feed>::stop(self).await
+            }
+        }
+    };
+}
+
+market_feed!(PythFeed, "pyth");
+market_feed!(SwitchboardFeed, "switchboard");
+market_feed!(JupiterFeed, "jupiter");
+market_feed!(DriftFeed, "drift");
+market_feed!(DlobFeed, "drift_dlob");
+market_feed!(SyntheticFundingFeed, "synthetic_funding");
+
 /// Price feed manager that coordinates all price sources
 pub struct PriceFeedManager {
-    /// Pyth feed
-    pub pyth: PythFeed,
-    /// Jupiter feed
-    pub jupiter: JupiterFeed,
-    /// Drift feed
-    pub drift: DriftFeed,
+    /// What the registry builds feeds from
+    context: FeedContext,
+    /// Oracles, spot venues and perp feeds
+    registry: VenueRegistry,
+    /// Feeds built and started by `start`
+    feeds: Mutex<Vec<Arc<dyn MarketFeed>>>,
+    /// Composite spot price from the oracle sources
+    aggregator: SpotAggregator,
+    aggregator_task: Mutex<Option<JoinHandle<()>>>,
+    /// USDC/USD tracking and depeg guard
+    usdc_peg: UsdcPegMonitor,
+    usdc_peg_task: Mutex<Option<JoinHandle<()>>>,
     /// Shared state
     state: Arc<SharedState>,
-    /// Event sender
-    event_tx: broadcast::Sender<Event>,
 }
 
 impl PriceFeedManager {
@@ -44,22 +111,54 @@ impl PriceFeedManager {
         event_tx: broadcast::Sender<Event>,
     ) -> Self {
         Self {
-            pyth: PythFeed::new(&config.pyth, event_tx.clone()),
-            jupiter: JupiterFeed::new(&config.jupiter, event_tx.clone()),
-            drift: DriftFeed::new(&config.drift, event_tx.clone()),
+            context: FeedContext {
+                protocols: config.clone(),
+                event_tx: event_tx.clone(),
+                retry: HttpRetryPolicy::default(),
+                synthetic_funding: None,
+            },
+            registry: VenueRegistry::default(),
+            feeds: Mutex::new(Vec::new()),
+            aggregator: SpotAggregator::new(&config.oracles, state.clone(), event_tx.clone()),
+            aggregator_task: Mutex::new(None),
+            usdc_peg: UsdcPegMonitor::new(&config.oracles.usdc_peg, state.clone(), event_tx),
+            usdc_peg_task: Mutex::new(None),
             state,
-            event_tx,
         }
     }
     
+    /// Build feeds from `registry` instead of the built-in integrations
+    pub fn with_registry(mut self, registry: VenueRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+    
+    /// Publish synthetic funding instead of Drift's
+    pub fn with_synthetic_funding(mut self, config: &SyntheticFundingConfig) -> Self {
+        self.context.synthetic_funding = Some(config.clone());
+        self
+    }
+    
+    /// Retry and cool off every feed's API calls under `retry`
+    pub fn with_retry_policy(mut self, retry: HttpRetryPolicy) -> Self {
+        self.context.retry = retry;
+        self
+    }
+    
     /// Start all price feeds
     pub async fn start(&self) -> Result<()> {
         info!("Starting price feed manager");
         
+        // Aggregate first so no oracle update is missed
+        *self.aggregator_task.lock().await = Some(self.aggregator.spawn());
+        *self.usdc_peg_task.lock().await = Some(self.usdc_peg.spawn());
+
         // Start individual feeds
-        self.pyth.start().await?;
-        self.jupiter.start().await?;
-        self.drift.start().await?;
+        let feeds = self.registry.feeds(&self.context)?;
+        for feed in &feeds {
+            feed.start().await.with_context(|| format!("Failed to start {} feed", feed.name()))?;
+        }
+        *self.feeds.lock().await = feeds;
         
         info!("All price feeds started");
         Ok(())
@@ -68,12 +167,18 @@ impl PriceFeedManager {
     /// Stop all price feeds
     pub async fn stop(&self) {
         info!("Stopping price feed manager");
-        self.pyth.stop().await;
-        self.jupiter.stop().await;
-        self.drift.stop().await;
+        for feed in self.feeds.lock().await.drain(..) {
+            feed.stop().await;
+        }
+        if let Some(task) = self.aggregator_task.lock().await.take() {
+            task.abort();
+        }
+        if let Some(task) = self.usdc_peg_task.lock().await.take() {
+            task.abort();
+        }
     }
     
-    /// Get current spot price (best available)
+    /// Get current composite spot price
     pub fn get_spot_price(&self) -> f64 {
         self.state.spot_price.load()
     }
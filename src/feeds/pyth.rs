@@ -1,6 +1,12 @@
 //! Pyth Oracle Price Feed
 //!
-//! Fetches SOL/USD price from Pyth Network oracle.
+//! Fetches SOL/USD price from Pyth Network oracle, and USDC/USD (every
+//! 5s, as `UsdcPriceUpdate`s) for the USDC peg monitor when configured.
+//!
+//! Updates carry Pyth's publish time rather than the fetch time: Hermes
+//! keeps serving the last price when publishers stop, and a frozen price
+//! must age out of the composite instead of looking live. The publish time
+//! is normalized onto the local clock, which the other sources stamp with.
 
 use anyhow::Result;
 use std::sync::Arc;
@@ -10,12 +16,19 @@ use tracing::{debug, error, info, warn};
 
 use crate::config::PythConfig;
 use crate::network::event_bus::Event;
+use crate::network::HttpRetryPolicy;
+use crate::utils::clock;
 use crate::utils::types::{PriceSource, PriceUpdate};
 
+/// USDC/USD poll interval
+const USDC_POLL: Duration = Duration::from_secs(5);
+
 /// Pyth price feed
 pub struct PythFeed {
     /// SOL/USD feed address
     feed_address: String,
+    /// USDC/USD feed address (empty = not polled)
+    usdc_feed_address: String,
     /// Event sender
     event_tx: broadcast::Sender<Event>,
     /// Is running
@@ -24,6 +37,8 @@ pub struct PythFeed {
     last_price: Arc<RwLock<Option<f64>>>,
     /// HTTP client
     client: reqwest::Client,
+    /// Retries and cool-off of the API calls
+    retry: HttpRetryPolicy,
 }
 
 impl PythFeed {
@@ -31,6 +46,7 @@ impl PythFeed {
     pub fn new(config: &PythConfig, event_tx: broadcast::Sender<Event>) -> Self {
         Self {
             feed_address: config.sol_usd_feed.clone(),
+            usdc_feed_address: config.usdc_usd_feed.clone(),
             event_tx,
             running: Arc::new(RwLock::new(false)),
             last_price: Arc::new(RwLock::new(None)),
@@ -38,9 +54,16 @@ impl PythFeed {
                 .timeout(Duration::from_secs(10))
                 .build()
                 .unwrap(),
+            retry: HttpRetryPolicy::default(),
         }
     }
     
+    /// Retry and cool off API calls under `retry`
+    pub fn with_retry_policy(mut self, retry: HttpRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    
     /// Start the price feed
     pub async fn start(&self) -> Result<()> {
         *self.running.write().await = true;
@@ -51,6 +74,17 @@ impl PythFeed {
         let event_tx = self.event_tx.clone();
         let last_price = self.last_price.clone();
         let client = self.client.clone();
+        let retry = self.retry.clone();
+        
+        if !self.usdc_feed_address.is_empty() {
+            Self::spawn_usdc(
+                running.clone(),
+                self.usdc_feed_address.clone(),
+                event_tx.clone(),
+                client.clone(),
+                retry.clone(),
+            );
+        }
         
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_millis(500));
@@ -58,8 +92,8 @@ impl PythFeed {
             while *running.read().await {
                 interval.tick().await;
                 
-                match Self::fetch_price(&client, &feed_address).await {
-                    Ok(price) => {
+                match Self::fetch_price(&client, &retry, &feed_address).await {
+                    Ok((price, published_at)) => {
                         debug!("Pyth SOL/USD price: ${:.4}", price);
                         
                         *last_price.write().await = Some(price);
@@ -68,10 +102,10 @@ impl PythFeed {
                             source: PriceSource::Pyth,
                             price,
                             confidence: None,
-                            timestamp: chrono::Utc::now().timestamp_millis(),
+                            timestamp: published_at,
                         };
                         
-                        let _ = event_tx.send(Event::SpotPriceUpdate(update));
+                        let _ = event_tx.send(Event::OracleUpdate(update));
                     }
                     Err(e) => {
                         warn!("Failed to fetch Pyth price: {}", e);
@@ -85,17 +119,44 @@ impl PythFeed {
         Ok(())
     }
     
-    /// Fetch price from Pyth Hermes API
-    async fn fetch_price(client: &reqwest::Client, feed_id: &str) -> Result<f64> {
+    /// Poll USDC/USD while the feed runs
+    fn spawn_usdc(
+        running: Arc<RwLock<bool>>,
+        feed_address: String,
+        event_tx: broadcast::Sender<Event>,
+        client: reqwest::Client,
+        retry: HttpRetryPolicy,
+    ) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(USDC_POLL);
+            while *running.read().await {
+                interval.tick().await;
+                match Self::fetch_price(&client, &retry, &feed_address).await {
+                    Ok((price, published_at)) => {
+                        debug!("Pyth USDC/USD price: ${:.5}", price);
+                        let _ = event_tx.send(Event::UsdcPriceUpdate(PriceUpdate {
+                            source: PriceSource::Pyth,
+                            price,
+                            confidence: None,
+                            timestamp: published_at,
+                        }));
+                    }
+                    Err(e) => warn!("Failed to fetch Pyth USDC price: {}", e),
+                }
+            }
+        });
+    }
+    
+    /// Fetch price and its publish time (ms) from Pyth Hermes API
+    async fn fetch_price(client: &reqwest::Client, retry: &HttpRetryPolicy, feed_id: &str) -> Result<(f64, i64)> {
         // Use Pyth Hermes API for real-time prices
         let url = format!(
             "https://hermes.pyth.network/api/latest_price_feeds?ids[]={}",
             feed_id
         );
         
-        let response = client
-            .get(&url)
-            .send()
+        let response = retry
+            .send(client.get(&url))
             .await?
             .json::<serde_json::Value>()
             .await?;
@@ -110,7 +171,12 @@ impl PythFeed {
                     ) {
                         let price: i64 = price_str.parse()?;
                         let price_f64 = price as f64 * 10_f64.powi(expo as i32);
-                        return Ok(price_f64);
+                        let published_at = price_obj
+                            .get("publish_time")
+                            .and_then(|t| t.as_i64())
+                            .map(clock::normalize)
+                            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+                        return Ok((price_f64, published_at));
                     }
                 }
             }
@@ -144,6 +210,7 @@ mod tests {
     fn test_pyth_feed_creation() {
         let config = PythConfig {
             sol_usd_feed: "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG".to_string(),
+            usdc_usd_feed: String::new(),
         };
         let (tx, _) = broadcast::channel(10);
         let feed = PythFeed::new(&config, tx);
@@ -0,0 +1,130 @@
+//! Switchboard Oracle Price Feed
+//!
+//! Fetches SOL/USD from a Switchboard on-demand feed, simulated through the
+//! Crossbar gateway, as a second oracle next to Pyth.
+
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, info, warn};
+
+use crate::config::SwitchboardConfig;
+use crate::network::event_bus::Event;
+use crate::network::HttpRetryPolicy;
+use crate::utils::types::{PriceSource, PriceUpdate};
+
+/// Switchboard price feed
+pub struct SwitchboardFeed {
+    config: SwitchboardConfig,
+    event_tx: broadcast::Sender<Event>,
+    running: Arc<RwLock<bool>>,
+    client: reqwest::Client,
+    retry: HttpRetryPolicy,
+}
+
+impl SwitchboardFeed {
+    pub fn new(config: &SwitchboardConfig, event_tx: broadcast::Sender<Event>) -> Self {
+        Self {
+            config: config.clone(),
+            event_tx,
+            running: Arc::new(RwLock::new(false)),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap(),
+            retry: HttpRetryPolicy::default(),
+        }
+    }
+
+    /// Retry and cool off API calls under `retry`
+    pub fn with_retry_policy(mut self, retry: HttpRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Start the price feed
+    pub async fn start(&self) -> Result<()> {
+        *self.running.write().await = true;
+        info!("Switchboard price feed starting for {}", self.config.sol_usd_feed);
+
+        let running = self.running.clone();
+        let config = self.config.clone();
+        let event_tx = self.event_tx.clone();
+        let client = self.client.clone();
+        let retry = self.retry.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(config.poll_interval_ms.max(100)));
+
+            while *running.read().await {
+                interval.tick().await;
+
+                match Self::fetch_price(&client, &retry, &config).await {
+                    Ok(price) => {
+                        debug!("Switchboard SOL/USD price: ${:.4}", price);
+                        let _ = event_tx.send(Event::OracleUpdate(PriceUpdate {
+                            source: PriceSource::Switchboard,
+                            price,
+                            confidence: None,
+                            timestamp: chrono::Utc::now().timestamp_millis(),
+                        }));
+                    }
+                    Err(e) => warn!("Failed to fetch Switchboard price: {}", e),
+                }
+            }
+
+            info!("Switchboard price feed stopped");
+        });
+
+        Ok(())
+    }
+
+    async fn fetch_price(client: &reqwest::Client, retry: &HttpRetryPolicy, config: &SwitchboardConfig) -> Result<f64> {
+        let url = format!("{}/simulate/{}", config.crossbar_url.trim_end_matches('/'), config.sol_usd_feed);
+        let response = retry
+            .send(client.get(&url))
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await?;
+        parse_simulation(&response)
+    }
+
+    /// Stop the price feed
+    pub async fn stop(&self) {
+        *self.running.write().await = false;
+        info!("Switchboard price feed stopping");
+    }
+}
+
+/// First result of a Crossbar simulation: `[{"feedHash": .., "results": [price]}]`
+fn parse_simulation(response: &serde_json::Value) -> Result<f64> {
+    let result = response
+        .as_array()
+        .and_then(|feeds| feeds.first())
+        .and_then(|feed| feed.get("results"))
+        .and_then(|results| results.as_array())
+        .and_then(|results| results.first())
+        .context("Switchboard response has no results")?;
+    let price = match result {
+        serde_json::Value::String(s) => s.parse::<f64>()?,
+        value => value.as_f64().context("Switchboard result is not a number")?,
+    };
+    anyhow::ensure!(price.is_finite() && price > 0.0, "Invalid Switchboard price {}", price);
+    Ok(price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simulation() {
+        let response = serde_json::json!([{ "feedHash": "abc", "results": [142.37] }]);
+        assert_eq!(parse_simulation(&response).unwrap(), 142.37);
+        let response = serde_json::json!([{ "feedHash": "abc", "results": ["142.5"] }]);
+        assert_eq!(parse_simulation(&response).unwrap(), 142.5);
+        assert!(parse_simulation(&serde_json::json!([])).is_err());
+    }
+}
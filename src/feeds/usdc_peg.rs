@@ -0,0 +1,112 @@
+//! USDC Peg Monitor
+//!
+//! Trades settle in USDC, but P&L and risk limits are in USD. The monitor
+//! follows Pyth's USDC/USD (`UsdcPriceUpdate` events) and keeps it in shared
+//! state, where USDC amounts are valued with it: Jupiter's SOL/USDC quotes
+//! before aggregation and the treasury's USDC balances.
+//!
+//! When USDC trades more than `usdc_peg.band_pct` from $1, the depeg flag
+//! in shared state blocks entries and a critical alert is sent. It clears
+//! once USDC is back within half the band, so a price hovering at the edge
+//! does not flap. Without a USDC/USD price, USDC is taken at $1.
+
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::UsdcPegConfig;
+use crate::network::event_bus::Event;
+use crate::state::SharedState;
+use crate::telemetry::{record_usdc_peg, Alert};
+
+/// Whether USDC at `usdc_usd` is depegged, given whether it already was
+pub fn is_depegged(usdc_usd: f64, active: bool, config: &UsdcPegConfig) -> bool {
+    if !config.enabled {
+        return false;
+    }
+    let off_pct = (usdc_usd - 1.0).abs() * 100.0;
+    if active {
+        off_pct > config.band_pct / 2.0
+    } else {
+        off_pct > config.band_pct
+    }
+}
+
+/// Tracks USDC/USD and the depeg flag from `UsdcPriceUpdate` events
+pub struct UsdcPegMonitor {
+    config: UsdcPegConfig,
+    state: Arc<SharedState>,
+    event_tx: broadcast::Sender<Event>,
+}
+
+impl UsdcPegMonitor {
+    pub fn new(config: &UsdcPegConfig, state: Arc<SharedState>, event_tx: broadcast::Sender<Event>) -> Self {
+        Self { config: config.clone(), state, event_tx }
+    }
+
+    /// Follow USDC/USD until the task is aborted
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let config = self.config.clone();
+        let state = self.state.clone();
+        let event_tx = self.event_tx.clone();
+        let mut event_rx = event_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                let usdc_usd = match event_rx.recv().await {
+                    Ok(Event::UsdcPriceUpdate(update)) if update.price > 0.0 => update.price,
+                    Ok(_) => continue,
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("USDC peg monitor lagged by {} events", n);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                state.usdc_usd.store(usdc_usd);
+
+                let active = *state.usdc_depeg.read();
+                let depegged = is_depegged(usdc_usd, active, &config);
+                record_usdc_peg(usdc_usd, depegged);
+                if depegged == active {
+                    continue;
+                }
+                *state.usdc_depeg.write() = depegged;
+                let alert = if depegged {
+                    warn!("USDC depegged at ${:.4}, blocking entries", usdc_usd);
+                    Alert::critical(
+                        "USDC depeg",
+                        format!(
+                            "USDC/USD at ${:.4}, more than {:.2}% off $1; new entries blocked",
+                            usdc_usd, config.band_pct
+                        ),
+                    )
+                } else {
+                    info!("USDC back at ${:.4}, entries resumed", usdc_usd);
+                    Alert::info("USDC peg restored", format!("USDC/USD at ${:.4}; entries resumed", usdc_usd))
+                };
+                let _ = event_tx.send(Event::Alert(alert));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depeg_band_with_hysteresis() {
+        let config = UsdcPegConfig::default();
+        assert!(!is_depegged(0.998, false, &config));
+        assert!(is_depegged(0.994, false, &config));
+        assert!(is_depegged(1.006, false, &config));
+
+        // Clears only within half the band
+        assert!(is_depegged(0.997, true, &config));
+        assert!(!is_depegged(0.998, true, &config));
+
+        let disabled = UsdcPegConfig { enabled: false, ..config };
+        assert!(!is_depegged(0.9, false, &disabled));
+    }
+}
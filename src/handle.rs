@@ -0,0 +1,97 @@
+//! Read-only Bot Handle
+//!
+//! `BotHandle` is how a crate embedding the bot (a dashboard, a notebook
+//! through pyo3) reads it, without reaching into the individual
+//! `Arc<RwLock<..>>` internals of the agent and shared state. It is cheap to
+//! clone, can be sent across tasks, and offers no way to change anything:
+//! - `snapshot` - consistent market readings (prices, basis, funding)
+//! - `positions`, `risk` - legs, P&L, drawdown and daily counters
+//! - `status`, `metrics`, `recent_trades`, `daily_equity`, `daily_ledger` -
+//!   agent state and performance
+//! - `latest_signal` - the signal engine's current signal
+//! - `recent_signals`, `recent_alerts` - signal and alert events seen since
+//!   `with_events`
+
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::agent::{AgentStatus, RiskMetrics, TradingAgent};
+use crate::agentic::{DailyEquity, DailyLedger, PerformanceMetrics, TradeOutcome};
+use crate::api::dashboard::{EventHistory, HistoryEntry};
+use crate::engines::signal_engine::FullTradeSignal;
+use crate::network::Event;
+use crate::position::PositionSummary;
+use crate::state::MarketSnapshot;
+
+/// Read-only view of a running bot
+#[derive(Clone)]
+pub struct BotHandle {
+    agent: Arc<TradingAgent>,
+    history: Arc<EventHistory>,
+}
+
+impl BotHandle {
+    pub fn new(agent: Arc<TradingAgent>) -> Self {
+        Self { agent, history: Arc::new(EventHistory::default()) }
+    }
+
+    /// Keep signal and alert events from `events` for `recent_signals` and
+    /// `recent_alerts`; needs a tokio runtime
+    pub fn with_events(self, events: broadcast::Receiver<Event>) -> Self {
+        let history = self.history.clone();
+        tokio::spawn(async move { history.run(events).await });
+        self
+    }
+
+    /// Market readings at one instant
+    pub fn snapshot(&self) -> MarketSnapshot {
+        self.agent.shared_state().snapshot()
+    }
+
+    /// Spot and perp legs with P&L
+    pub async fn positions(&self) -> PositionSummary {
+        self.agent.position_manager().get_positions().await
+    }
+
+    /// Drawdown, equity and today's P&L
+    pub async fn risk(&self) -> RiskMetrics {
+        self.agent.risk_manager().get_metrics().await
+    }
+
+    /// Agent state, with the execution phase while a trade executes
+    pub async fn status(&self) -> AgentStatus {
+        self.agent.status().await
+    }
+
+    /// Performance of the closed trades
+    pub async fn metrics(&self) -> PerformanceMetrics {
+        self.agent.performance_db().get_metrics().await
+    }
+
+    /// The last `n` closed trades
+    pub async fn recent_trades(&self, n: usize) -> Vec<TradeOutcome> {
+        self.agent.performance_db().get_recent_trades(n).await
+    }
+
+    pub async fn daily_equity(&self) -> Vec<DailyEquity> {
+        self.agent.performance_db().get_daily_equity().await
+    }
+
+    pub fn daily_ledger(&self) -> DailyLedger {
+        self.agent.risk_manager().daily_ledger()
+    }
+
+    pub async fn latest_signal(&self) -> Option<FullTradeSignal> {
+        self.agent.latest_signal().await
+    }
+
+    /// Signal events, newest first
+    pub fn recent_signals(&self) -> Vec<HistoryEntry> {
+        self.history.recent("signal").unwrap_or_default()
+    }
+
+    /// Alert events, newest first
+    pub fn recent_alerts(&self) -> Vec<HistoryEntry> {
+        self.history.recent("alert").unwrap_or_default()
+    }
+}
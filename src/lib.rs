@@ -8,6 +8,12 @@
 //! - **Performance Database**: Stores all trade outcomes, calculates win rate, Sharpe ratio, etc.
 //! - **Adaptive Position Sizing**: Uses Kelly criterion adjusted by recent performance
 //! - **Funding Reversal Detection**: Early warning system for funding rate reversals
+//!
+//! ## Embedding
+//!
+//! Crates that only read the bot (dashboards, notebooks) should hold a
+//! [`BotHandle`] rather than the agent's internals. Built with the `python`
+//! feature, the library is also a Python module (see `python`).
 
 pub mod config;
 pub mod state;
@@ -21,6 +27,11 @@ pub mod agent;
 pub mod position;
 pub mod protocols;
 pub mod agentic;
+pub mod api;
+pub mod scenario;
+pub mod handle;
+#[cfg(feature = "python")]
+pub mod python;
 
 // Re-export main types
 pub use config::AppConfig;
@@ -29,10 +40,11 @@ pub use agent::{TradingAgent, AgentState};
 pub use position::PositionManager;
 pub use engines::EngineManager;
 pub use network::{RpcManager, EventBus, Event};
+pub use handle::BotHandle;
 
 // Re-export agentic types
 pub use agentic::{
-    PerformanceDb, TradeOutcome, PerformanceMetrics,
+    PerformanceDb, TradeOutcome, PerformanceMetrics, PnlAttribution,
     AdaptiveSizer, SizingRecommendation,
     ReversalDetector, ReversalAlert, ReversalSeverity,
 };
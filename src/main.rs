@@ -6,10 +6,12 @@
 //! - Executes delta-neutral hedged positions
 //! - Automatically rebalances when conditions are met
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
 use tracing::{info, warn, error, debug};
 
@@ -22,17 +24,29 @@ mod feeds;
 mod engines;
 mod execution;
 mod agent;
+mod agentic;
 mod position;
 mod protocols;
+mod api;
+mod scenario;
 
 use config::AppConfig;
-use state::SharedState;
-use telemetry::{init_logging, init_metrics};
-use network::{RpcManager, EventBus, Event};
+use state::{history_import, warm_start, SharedState};
+use telemetry::{init_logging, init_metrics, record_capital, shutdown_tracing, AlertManager, MarketDataExporter};
+use network::{RpcManager, EventBus, Event, HttpRetryPolicy};
 use feeds::PriceFeedManager;
-use engines::EngineManager;
+use engines::{EngineManager, TradingWindow};
 use position::PositionManager;
-use agent::TradingAgent;
+use agent::{panic_guard, AgentControl, LiveInterlock, PanicGuard, Strategy, Supervisor, TradingAgent};
+use agentic::{RegimeDetector, VolatilityMonitor};
+use api::ControlApi;
+use protocols::{VenueContext, VenueRegistry};
+use scenario::SessionRecorder;
+use execution::{
+    BalanceFetcher, BinanceSpot, ExecutionManager, GasWatchdog, NonceManager, PerpRouter, ProfitSweeper,
+    ShadowExecutor, SpotExchange, TransferLedger, TransferOrchestrator, Treasury,
+};
+use solana_sdk::signer::Signer;
 
 /// SOL Basis Trading Bot - Ultra-low-latency agentic trading
 #[derive(Parser, Debug)]
@@ -42,10 +56,22 @@ struct Args {
     #[arg(short, long, default_value = "config.yaml")]
     config: PathBuf,
 
+    /// Config profile overlaid on the base file (e.g. `devnet` → config.devnet.yaml)
+    #[arg(long, conflicts_with = "overlay")]
+    profile: Option<String>,
+
+    /// Config overlay file merged over the base file
+    #[arg(long)]
+    overlay: Option<PathBuf>,
+
     /// Enable paper trading mode (no real transactions)
     #[arg(long)]
     paper: bool,
 
+    /// Dry-run mode: paper fills, plus real transactions built and simulated but never sent
+    #[arg(long)]
+    dry_run: bool,
+
     /// Enable devnet mode
     #[arg(long)]
     devnet: bool,
@@ -53,6 +79,160 @@ struct Args {
     /// Override log level
     #[arg(long)]
     log_level: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Offline commands; without one the bot runs
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print persisted agent state transitions as JSON lines
+    Transitions {
+        /// Start of the range (RFC 3339)
+        #[arg(long)]
+        from: Option<chrono::DateTime<chrono::Utc>>,
+        /// End of the range (RFC 3339)
+        #[arg(long)]
+        to: Option<chrono::DateTime<chrono::Utc>>,
+        /// Only transitions of this trade
+        #[arg(long)]
+        trade: Option<String>,
+    },
+    /// Print the merged configuration (base + overlay + env + CLI), secrets redacted
+    PrintEffectiveConfig,
+    /// Re-run a recorded session in paper mode and check the agent makes the same decisions
+    Replay {
+        /// Session journal written with `agentic.record_session`
+        #[arg(long)]
+        journal: PathBuf,
+        /// Playback speed, e.g. `10x`
+        #[arg(long, default_value = "1x", value_parser = scenario::replay::parse_speed)]
+        speed: f64,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compare a shadow strategy with the primary agent and write its
+    /// overrides as a config profile (`--profile <name>`)
+    PromoteShadow {
+        /// Shadow strategy name
+        name: String,
+        /// Overwrite an existing profile file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Return to the configured entry thresholds, discarding learned ones;
+    /// for a running bot use `POST /thresholds/rollback`
+    RollbackThresholds,
+    /// Print the daily P&L ledger (days in `risk.daily_timezone`)
+    DailyReport {
+        /// Most recent days to print
+        #[arg(long, default_value_t = 30)]
+        days: usize,
+        /// Print the rows as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Print the state transition audit log for post-mortems
+async fn print_transitions(
+    config: &AppConfig,
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    to: Option<chrono::DateTime<chrono::Utc>>,
+    trade: Option<String>,
+) -> Result<()> {
+    let db = agentic::PerformanceDb::new(&config.agentic.performance_db_path).await?;
+    let start = from.map_or(i64::MIN, |t| t.timestamp_millis());
+    let end = to.map_or(i64::MAX, |t| t.timestamp_millis());
+    for transition in db.get_transitions(start, end).await? {
+        if trade.is_some() && transition.trade_id != trade {
+            continue;
+        }
+        println!("{}", serde_json::to_string(&transition)?);
+    }
+    Ok(())
+}
+
+/// Report a shadow against the primary agent and write its overrides as the
+/// profile next to the base config
+async fn promote_shadow(config: &AppConfig, base: &std::path::Path, name: &str, force: bool) -> Result<()> {
+    let strategy = config
+        .strategies
+        .iter()
+        .find(|s| s.name == name && s.shadow)
+        .with_context(|| format!("No shadow strategy {} in the config", name))?;
+    let shadow_config = config.for_strategy(strategy)?;
+    let since = agent::ab_test::read_since(&shadow_config.agentic.performance_db_path)?
+        .with_context(|| format!("Shadow {} has not run yet", name))?;
+    let primary = agentic::PerformanceDb::new(&config.agentic.performance_db_path).await?;
+    let shadow = agentic::PerformanceDb::new(&shadow_config.agentic.performance_db_path).await?;
+    let comparison = agent::ShadowComparison::compute(
+        name,
+        strategy.overrides.clone(),
+        since,
+        &primary.get_all_trades().await,
+        &shadow.get_all_trades().await,
+    );
+    print!("{}", comparison.summary());
+
+    let profile = config::layers::profile_path(base, name);
+    anyhow::ensure!(force || !profile.exists(), "{} exists; pass --force to overwrite it", profile.display());
+    std::fs::write(&profile, serde_yaml::to_string(&strategy.overrides)?)
+        .with_context(|| format!("Failed to write {}", profile.display()))?;
+    println!("Wrote {}; run with --profile {} to trade these parameters live", profile.display(), name);
+    Ok(())
+}
+
+/// Log a rollback of the learned entry thresholds
+async fn rollback_thresholds(config: AppConfig) -> Result<()> {
+    let db = agentic::PerformanceDb::new(&config.agentic.performance_db_path).await?;
+    let learner = agentic::ThresholdLearner::new(Arc::new(config), Arc::new(SharedState::new()), Arc::new(db));
+    learner.restore().await?;
+    let rollback = learner.rollback().await?;
+    println!(
+        "Entry thresholds rolled back from basis {:.3}%, funding {:.1}% APR to basis {:.3}%, funding {:.1}% APR",
+        rollback.from.min_basis_spread_pct,
+        rollback.from.min_funding_apr_pct,
+        rollback.to.min_basis_spread_pct,
+        rollback.to.min_funding_apr_pct
+    );
+    Ok(())
+}
+
+/// Print the last days of the daily ledger, rebuilt from the trades if the
+/// bot has not written one yet
+async fn daily_report(config: &AppConfig, days: usize, json: bool) -> Result<()> {
+    let db = agentic::PerformanceDb::new(&config.agentic.performance_db_path).await?;
+    let ledger = match db.load_daily_ledger().await? {
+        Some(ledger) => ledger,
+        None => agentic::DailyLedger::rebuild(&db.get_all_trades().await, config.risk.daily_timezone),
+    };
+    if json {
+        println!("{}", serde_json::to_string(ledger.recent(days))?);
+    } else {
+        print!("{}", ledger.summary(days));
+    }
+    Ok(())
+}
+
+/// Replay a recorded session against its audit log; fails on divergence
+async fn replay_session(config: &AppConfig, journal: &std::path::Path, speed: f64, json: bool) -> Result<()> {
+    let session = scenario::Session::load(journal).await?;
+    let (start, end) = session.span();
+    let db = agentic::PerformanceDb::new(&config.agentic.performance_db_path).await?;
+    let recorded = db.get_transitions(start, end).await?;
+    let report = scenario::replay::replay(&session, recorded, config, speed).await?;
+    if json {
+        println!("{}", serde_json::to_string(&report)?);
+    } else {
+        print!("{}", report.summary());
+    }
+    if let Some(divergence) = &report.divergence {
+        anyhow::bail!("Replay of {} diverged at transition {}", report.session, divergence.index + 1);
+    }
+    Ok(())
 }
 
 #[tokio::main]
@@ -60,25 +240,61 @@ async fn main() -> Result<()> {
     // Parse command line arguments
     let args = Args::parse();
 
-    // Load configuration
-    let mut config = AppConfig::load(&args.config)?;
+    // Load configuration: base file, overlay, environment, then CLI flags
+    let overlay = args.overlay.clone()
+        .or_else(|| args.profile.as_deref().map(|p| config::layers::profile_path(&args.config, p)));
+    let mut config = AppConfig::load_layered(&args.config, overlay.as_deref())?;
     
     // Apply CLI overrides
     if args.paper {
         config.paper_trading = true;
     }
+    if args.dry_run {
+        config.dry_run = true;
+    }
+    if config.dry_run {
+        // Positions are still tracked from paper fills
+        config.paper_trading = true;
+    }
     if args.devnet {
         config.devnet = true;
     }
     if let Some(level) = args.log_level {
         config.telemetry.log_level = level;
     }
+    config.apply_devnet();
+
+    match args.command {
+        Some(Command::Transitions { from, to, trade }) => {
+            return print_transitions(&config, from, to, trade).await;
+        }
+        Some(Command::PrintEffectiveConfig) => {
+            print!("{}", config.to_redacted_yaml()?);
+            return Ok(());
+        }
+        Some(Command::Replay { journal, speed, json }) => {
+            return replay_session(&config, &journal, speed, json).await;
+        }
+        Some(Command::PromoteShadow { name, force }) => {
+            return promote_shadow(&config, &args.config, &name, force).await;
+        }
+        Some(Command::RollbackThresholds) => {
+            return rollback_thresholds(config).await;
+        }
+        Some(Command::DailyReport { days, json }) => {
+            return daily_report(&config, days, json).await;
+        }
+        None => {}
+    }
 
     // Initialize logging
     init_logging(&config.telemetry)?;
+    // Panics are reported from here on, and handled once the agent is up
+    let panics = config.supervisor.panic.enabled.then(panic_guard::install);
     
     info!("Starting SOL Basis Trading Bot v{}", env!("CARGO_PKG_VERSION"));
     info!("Paper trading: {}", config.paper_trading);
+    info!("Dry run: {}", config.dry_run);
     info!("Devnet mode: {}", config.devnet);
 
     // Initialize metrics if enabled
@@ -88,9 +304,48 @@ async fn main() -> Result<()> {
     }
 
     // Create shared state
-    let state = Arc::new(SharedState::new());
+    let state = Arc::new(SharedState::with_sampling(
+        config.protocols.drift.funding_interval,
+        config.engines.basis_interval(),
+        config.engines.funding_interval(),
+    ));
     info!("Shared state initialized");
 
+    // Warm start: reload the basis and funding history saved by the last run
+    let history_path = (!config.engines.history_path.is_empty())
+        .then(|| std::path::PathBuf::from(&config.engines.history_path));
+    let mut restored = 0;
+    let history_saver = match &history_path {
+        Some(path) => {
+            match warm_start::load(&state, path, config.engines.history_max_age()).await {
+                Ok(0) => {}
+                Ok(count) => {
+                    info!("Restored {} history samples from {:?}", count, path);
+                    restored = count;
+                }
+                Err(e) => warn!("Could not restore engine history: {}", e),
+            }
+            Some(warm_start::spawn_saver(
+                state.clone(),
+                path.clone(),
+                std::time::Duration::from_secs(config.engines.history_save_secs),
+            ))
+        }
+        None => None,
+    };
+    // Nothing recent saved: warm up from imported history instead
+    if restored == 0 && config.engines.history_import.is_enabled() {
+        let history = history_import::import(&config.engines.history_import, &config.protocols.drift).await;
+        let warmed = history.warm(&state);
+        if warmed > 0 {
+            info!("Warmed up engines with {} imported history samples", warmed);
+        }
+    }
+
+    // Spot inventory across trades; live fills and wallet refreshes land in the same ledger
+    let ledger = Arc::new(TransferLedger::new());
+    let treasury = Arc::new(Treasury::new(ledger.clone(), state.clone(), &config));
+
     // Wrap config in Arc for sharing
     let config = Arc::new(config);
 
@@ -100,10 +355,28 @@ async fn main() -> Result<()> {
     // Create event bus for internal communication
     let event_bus = EventBus::new(2048);
     let event_tx = event_bus.sender();
+    network::http::publish_events(event_tx.clone());
     info!("Event bus initialized");
     
+    // Start alert pipeline
+    let alert_manager = Arc::new(AlertManager::new(&config.telemetry));
+    let alert_pipeline = alert_manager.clone().spawn(event_bus.subscribe());
+    info!("Alert pipeline started");
+    
+    // Operator controls, shared by the control API and the trading agent
+    let agent_control = Arc::new(AgentControl::new());
+    let live_interlock = if config.paper_trading {
+        None
+    } else {
+        Some(Arc::new(LiveInterlock::arm(config.api.confirm_token()?, agent_control.clone(), event_tx.clone())))
+    };
+    
     // Create RPC manager
-    let rpc_manager = Arc::new(RpcManager::new(&config.rpc)?);
+    let chaos = network::FaultInjector::from_config(
+        &config.chaos,
+        std::time::Duration::from_millis(config.rpc.request_timeout_ms),
+    );
+    let rpc_manager = Arc::new(RpcManager::new(&config.rpc)?.with_chaos(chaos.clone()));
     info!("RPC manager initialized");
     
     // Test RPC connection
@@ -116,25 +389,84 @@ async fn main() -> Result<()> {
             warn!("RPC health check failed: {}", e);
         }
     }
+    let rpc_health = rpc_manager.spawn_health_monitor(state.clone());
+    let clock_monitor = config
+        .clock
+        .enabled
+        .then(|| rpc_manager.spawn_clock_monitor(&config.clock, event_tx.clone()));
+    
+    // Devnet: keep the wallet funded from the faucet
+    if config.devnet && config.devnet_profile.airdrop_sol > 0.0 {
+        match utils::helpers::load_keypair(std::path::Path::new(&config.wallet.keypair_path)) {
+            Ok(keypair) => {
+                let to_lamports = |sol: f64| utils::amount::Amount::sol(sol)?.base_units();
+                let airdrop = async {
+                    rpc_manager.airdrop_if_below(
+                        &keypair.pubkey(),
+                        to_lamports(config.devnet_profile.min_sol_balance)?,
+                        to_lamports(config.devnet_profile.airdrop_sol)?,
+                    ).await
+                };
+                if let Err(e) = airdrop.await {
+                    warn!("Devnet airdrop failed: {}", e);
+                }
+            }
+            Err(e) => warn!("Devnet faucet skipped, no wallet: {}", e),
+        }
+    }
     
     // Initialize price feeds
     info!("Initializing price feeds...");
-    let price_feeds = PriceFeedManager::new(
+    let mut price_feeds = PriceFeedManager::new(
         &config.protocols,
         state.clone(),
         event_tx.clone(),
-    );
+    )
+    .with_retry_policy(HttpRetryPolicy::new(&config.http));
+    if config.devnet && config.devnet_profile.synthetic_funding.enabled {
+        price_feeds = price_feeds.with_synthetic_funding(&config.devnet_profile.synthetic_funding);
+    }
     
     // Start price feeds
     price_feeds.start().await?;
+    let price_feeds = Arc::new(price_feeds);
     info!("Price feeds started");
     
+    // Basis regime detector gates entries and scales sizing
+    let regime_detector = RegimeDetector::new(config.clone(), state.clone(), event_tx.clone());
+    regime_detector.start().await?;
+    
+    // Realized volatility de-risks sizing or entries while markets are violent
+    let volatility_monitor = VolatilityMonitor::new(config.clone(), state.clone());
+    volatility_monitor.start().await?;
+    
+    // Perp venues: compared on carry by the funding engine, traded through
+    // the router when live. Without a wallet they are read-only.
+    let venue_payer = utils::helpers::load_keypair(std::path::Path::new(&config.wallet.keypair_path)).ok();
+    // Durable nonce for live reduce-only orders, so closes cannot expire
+    let nonce = match &venue_payer {
+        Some(payer) if config.execution.durable_nonce.enabled && !config.paper_trading => Some(Arc::new(
+            NonceManager::from_config(&config.execution.durable_nonce, rpc_manager.clone(), payer.insecure_clone())
+                .await
+                .context("Failed to set up the durable nonce account")?,
+        )),
+        _ => None,
+    };
+    let venue_registry = VenueRegistry::default();
+    let venue_context = VenueContext {
+        config: config.clone(),
+        state: state.clone(),
+        rpc: rpc_manager.clone(),
+        payer: venue_payer,
+        nonce: nonce.clone(),
+    };
+    let perp_venues = venue_registry.perp_venues(&venue_context)?;
+    
     // Phase 3: Initialize calculation engines
     info!("Initializing calculation engines...");
-    let engine_manager = EngineManager::new(
-        config.clone(),
-        state.clone(),
-        event_tx.clone(),
+    let engine_manager = Arc::new(
+        EngineManager::new(config.clone(), state.clone(), event_tx.clone(), regime_detector.regime_handle())
+            .with_perp_venues(perp_venues.clone()),
     );
     
     // Start engines
@@ -143,19 +475,272 @@ async fn main() -> Result<()> {
     
     // Phase 5: Initialize position manager and trading agent
     info!("Initializing position manager...");
-    let position_manager = Arc::new(PositionManager::new(state.clone()));
+    let position_manager = Arc::new(PositionManager::new(state.clone()).with_treasury(treasury.clone()));
     
     info!("Initializing trading agent...");
-    let trading_agent = TradingAgent::new(
+    let mut trading_agent = TradingAgent::new(
         config.clone(),
         state.clone(),
         position_manager.clone(),
         event_tx.clone(),
-    );
+        engine_manager.signal.signal_handle(),
+        regime_detector.regime_handle(),
+    ).await?
+    .with_basis(engine_manager.basis.analysis_handle())
+    .with_entry_times(engine_manager.signal.entry_time_handle())
+    .with_hold_times(engine_manager.signal.hold_time_handle())
+    .with_control(agent_control.clone());
+    if config.dry_run {
+        // Transactions are built for the real wallet, so it must be present
+        let keypair = utils::helpers::load_keypair(std::path::Path::new(&config.wallet.keypair_path))
+            .context("Dry-run mode needs the wallet keypair to build transactions")?;
+        let shadow = ShadowExecutor::new(config.clone(), rpc_manager.clone(), keypair)?;
+        trading_agent = trading_agent.with_shadow(Arc::new(shadow));
+        info!("Dry-run execution enabled: transactions are simulated, never submitted");
+    }
+    let mut live_execution = None;
+    if !config.paper_trading {
+        let keypair = utils::helpers::load_keypair(std::path::Path::new(&config.wallet.keypair_path))
+            .context("Live trading needs the wallet keypair")?;
+        let wallet_address = keypair.pubkey().to_string();
+        let perp_router = PerpRouter::new(config.execution.perp_routing, perp_venues);
+        let mut execution = ExecutionManager::new(config.clone(), rpc_manager.clone(), state.clone())
+            .await?
+            .with_payer(keypair)
+            .with_events(event_tx.clone())
+            .with_nonce(nonce)
+            .with_ledger(ledger.clone())
+            .with_spot_venues(venue_registry.spot_venues(&venue_context)?)
+            .with_perp_router(Arc::new(perp_router));
+        if config.execution.cex.enabled {
+            let exchange: Arc<dyn SpotExchange> = Arc::new(BinanceSpot::new(&config.execution.cex)?);
+            let transfers = Arc::new(TransferOrchestrator::new(exchange, ledger.clone(), wallet_address));
+            let poller = transfers.clone();
+            let poll_secs = config.execution.cex.poll_interval_secs.max(1);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(poll_secs));
+                loop {
+                    interval.tick().await;
+                    if let Err(e) = poller.poll().await {
+                        warn!("CEX balance refresh failed: {}", e);
+                    }
+                }
+            });
+            execution = execution.with_cex(transfers);
+            info!("Spot leg routed to {}", config.execution.cex.exchange);
+        }
+        let execution = Arc::new(execution);
+        live_execution = Some(execution.clone());
+        trading_agent = trading_agent.with_order_venue(execution.clone()).with_execution(execution);
+        info!("Live execution enabled for rebalancing");
+    }
+    let trading_agent = Arc::new(trading_agent);
+    
+    // Named strategies: further paper agents with their own overrides and stores
+    let mut strategies = Vec::with_capacity(config.strategies.len());
+    for strategy in &config.strategies {
+        let strategy = Strategy::new(
+            &config,
+            strategy,
+            event_tx.clone(),
+            &engine_manager,
+            regime_detector.regime_handle(),
+        )
+        .await
+        .with_context(|| format!("Failed to set up strategy {}", strategy.name))?;
+        strategies.push(Arc::new(strategy));
+    }
+    
+    // Start control API once the agent's trade history is available to the dashboard
+    let control_api = if config.api.enabled {
+        let api = ControlApi::new(
+            &config.api,
+            alert_manager.clone(),
+            event_tx.clone(),
+            TradingWindow::new(&config),
+        )
+        .with_shared_state(state.clone())
+        .with_treasury(treasury.clone())
+        .with_control(agent_control.clone())
+        .with_agent(trading_agent.clone())
+        .with_strategies(strategies.clone());
+        let api = match &live_interlock {
+            Some(live) => api.with_live_interlock(live.clone()),
+            None => api,
+        };
+        Some(tokio::spawn(async move {
+            if let Err(e) = api.serve().await {
+                error!("Control API stopped: {}", e);
+            }
+        }))
+    } else {
+        None
+    };
+    
+    // Keep the sizer informed of wallet and Drift collateral balances
+    let balance_refresher = match utils::helpers::load_keypair(
+        std::path::Path::new(&config.wallet.keypair_path),
+    )
+    .and_then(|keypair| BalanceFetcher::new(rpc_manager.clone(), &config, keypair.pubkey()))
+    {
+        Ok(fetcher) => {
+            let sizer = trading_agent.adaptive_sizer().clone();
+            let treasury = treasury.clone();
+            let refresh_secs = config.wallet.balance_refresh_secs;
+            Some(tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(refresh_secs));
+                loop {
+                    interval.tick().await;
+                    match fetcher.fetch().await {
+                        Ok(capital) => {
+                            treasury.refresh_wallet(&capital);
+                            sizer.update_available_capital(capital).await;
+                        }
+                        Err(e) => warn!("Balance refresh failed: {}", e),
+                    }
+                }
+            }))
+        }
+        Err(e) => {
+            warn!("Wallet unavailable, balance-aware sizing disabled: {}", e);
+            None
+        }
+    };
+    
+    // Watch the wallet's SOL for fees and tips
+    let gas_watchdog = if config.paper_trading {
+        None
+    } else {
+        match utils::helpers::load_keypair(std::path::Path::new(&config.wallet.keypair_path)) {
+            Ok(keypair) => Some(
+                GasWatchdog::new(rpc_manager.clone(), keypair.pubkey(), &config.wallet, state.clone(), event_tx.clone())
+                    .spawn(),
+            ),
+            Err(e) => {
+                warn!("Wallet unavailable, gas watchdog disabled: {}", e);
+                None
+            }
+        }
+    };
+    
+    // Sweep profit above the equity target to the cold wallet
+    let profit_sweeper = if config.treasury.profit_sweep.enabled {
+        let mut sweeper = ProfitSweeper::new(
+            treasury.clone(),
+            trading_agent.performance_db().clone(),
+            &config.treasury.profit_sweep,
+            event_tx.clone(),
+        )?;
+        if let Some(execution) = live_execution {
+            sweeper = sweeper.with_execution(execution);
+        }
+        info!(
+            "Profit sweep enabled: above ${:.2} to {} every {}s",
+            config.treasury.profit_sweep.equity_target_usd,
+            config.treasury.profit_sweep.cold_wallet,
+            config.treasury.profit_sweep.interval_secs
+        );
+        Some(sweeper.spawn())
+    } else {
+        None
+    };
+    
+    // Sampled market data for offline research
+    let market_exporter = config
+        .telemetry
+        .market_export
+        .enabled
+        .then(|| MarketDataExporter::new(&config.telemetry.market_export, state.clone()).spawn());
+    
+    // Agent inputs for later replay
+    let session_recorder = if config.agentic.record_session {
+        Some(SessionRecorder::new(&config, state.clone(), engine_manager.signal.signal_handle())?.spawn())
+    } else {
+        None
+    };
     
     // Start trading agent
     trading_agent.start().await?;
     info!("Trading agent started");
+    for strategy in &strategies {
+        strategy.start(state.clone()).await?;
+    }
+    
+    // Restart stalled engines and feeds; pause if a critical task keeps dying
+    let supervisor = config.supervisor.enabled.then(|| {
+        let stall = Duration::from_secs(config.supervisor.stall_secs);
+        let (basis, funding, signal) = (engine_manager.clone(), engine_manager.clone(), engine_manager.clone());
+        let (feed_state, agent_state, feeds) = (state.clone(), state.clone(), price_feeds.clone());
+        let heartbeats = (
+            engine_manager.basis.heartbeat(),
+            engine_manager.funding.heartbeat(),
+            engine_manager.signal.heartbeat(),
+        );
+        Supervisor::new(&config.supervisor, agent_control.clone(), event_tx.clone())
+            .watch("basis_engine", false, stall, move || heartbeats.0.last(), move || {
+                let engines = basis.clone();
+                async move { engines.basis.start().await }
+            })
+            .watch("funding_engine", false, stall, move || heartbeats.1.last(), move || {
+                let engines = funding.clone();
+                async move { engines.funding.start().await }
+            })
+            .watch("signal_engine", true, stall, move || heartbeats.2.last(), move || {
+                let engines = signal.clone();
+                async move { engines.signal.start().await }
+            })
+            .watch(
+                "price_feeds",
+                true,
+                Duration::from_secs(config.supervisor.feed_stall_secs),
+                move || {
+                    let market = feed_state.snapshot();
+                    market.spot_updated_at.min(market.perp_updated_at)
+                },
+                move || {
+                    let feeds = feeds.clone();
+                    async move {
+                        feeds.stop().await;
+                        feeds.start().await
+                    }
+                },
+            )
+            .watch_only("agent_loop", true, stall, move || agent_state.agent_heartbeat.load(Ordering::Relaxed))
+            .spawn()
+    });
+    
+    // Any panic: stop, flatten, alert, persist and exit
+    let panic_guard = panics.map(|panics| {
+        PanicGuard::new(&config.supervisor.panic, trading_agent.clone(), alert_manager.clone(), state.clone())
+            .with_history(history_path.clone())
+            .spawn(panics)
+    });
+    
+    // gRPC control service for external supervisors
+    #[cfg(feature = "grpc")]
+    let grpc_service = if config.api.grpc_enabled {
+        let service = api::grpc::GrpcControl::new(trading_agent.clone(), state.clone(), event_tx.clone());
+        let api_config = config.api.clone();
+        Some(tokio::spawn(async move {
+            if let Err(e) = service.serve(&api_config).await {
+                error!("gRPC control service stopped: {}", e);
+            }
+        }))
+    } else {
+        None
+    };
+    #[cfg(not(feature = "grpc"))]
+    if config.api.grpc_enabled {
+        warn!("api.grpc_enabled is set but the bot was built without the grpc feature");
+    }
+    
+    // Signals and alerts for the shutdown report
+    let session_log = Arc::new(agentic::SessionLog::new());
+    let session_log_task = {
+        let log = session_log.clone();
+        let rx = event_bus.subscribe();
+        tokio::spawn(async move { log.run(rx).await })
+    };
     
     // Spawn event processor to update shared state
     let state_clone = state.clone();
@@ -166,23 +751,27 @@ async fn main() -> Result<()> {
         loop {
             match event_rx.recv().await {
                 Ok(event) => {
+                    state_clone.apply_market_event(&event);
                     match event {
                         Event::SpotPriceUpdate(update) => {
-                            state_clone.update_spot_price(update.price);
                             debug!("Spot price updated: ${:.4}", update.price);
                         }
                         Event::PerpMarkPriceUpdate(update) => {
-                            state_clone.update_perp_mark_price(update.price);
                             debug!("Perp mark price updated: ${:.4}", update.price);
                             // Update position P&L
                             position_manager_clone.update_pnl().await;
                         }
+                        Event::SpotDepthUpdate(quote) => {
+                            debug!(
+                                "Spot depth @ {:.0} SOL: buy ${:.4} / sell ${:.4}",
+                                quote.size_sol, quote.buy_price, quote.sell_price
+                            );
+                        }
+                        Event::PerpBookUpdate(_) => {}
                         Event::PerpIndexPriceUpdate(update) => {
-                            state_clone.perp_index_price.store(update.price);
                             debug!("Perp index price updated: ${:.4}", update.price);
                         }
                         Event::FundingRateUpdate { rate, .. } => {
-                            state_clone.update_funding_rate(rate);
                             debug!("Funding rate updated: {:.6}%", rate * 100.0);
                         }
                         Event::BasisSpreadUpdate { spread, spot_price, perp_price, .. } => {
@@ -191,10 +780,28 @@ async fn main() -> Result<()> {
                                 spread, spot_price, perp_price
                             );
                         }
-                        Event::TradeSignal { signal_type, size, reason } => {
+                        Event::SignalGenerated(signal) => {
+                            info!(
+                                "Trade signal: {:?} | Size: {:.2} SOL | Confidence: {:.0}% | Reason: {}",
+                                signal.signal_type, signal.size, signal.confidence * 100.0, signal.reason
+                            );
+                        }
+                        Event::HedgeDriftAlert { drift_pct, threshold_pct } => {
+                            warn!("Hedge drift {:.2}% exceeds threshold {:.2}%", drift_pct, threshold_pct);
+                        }
+                        Event::FundingElevated { apr_pct, threshold_pct } => {
+                            debug!("Funding APR {:.2}% exceeds threshold {:.2}%", apr_pct, threshold_pct);
+                        }
+                        Event::FundingReversing { velocity } => {
+                            info!("Funding rate reversing: velocity={:.6}/h", velocity);
+                        }
+                        Event::FundingReversal { severity, recommendation, .. } => {
+                            warn!("Funding reversal ({}): {}", severity.as_str(), recommendation);
+                        }
+                        Event::RegimeChanged { from, to, variance_ratio } => {
                             info!(
-                                "Trade signal: {} | Size: {:.2} SOL | Reason: {}",
-                                signal_type, size, reason
+                                "Basis regime {} -> {} (variance ratio {:.2})",
+                                from.as_str(), to.as_str(), variance_ratio
                             );
                         }
                         Event::PositionOpened { size, entry_price, side } => {
@@ -227,6 +834,12 @@ async fn main() -> Result<()> {
                             error!("Error from {}: {}", source, message);
                             state_clone.increment_error_count();
                         }
+                        Event::LegImbalance { trade_id, phase, spot_filled, perp_filled, resolution } => {
+                            warn!(
+                                "Leg imbalance on {} of {}: spot {:.4} / perp {:.4} -> {}",
+                                phase, trade_id, spot_filled, perp_filled, resolution
+                            );
+                        }
                         _ => {
                             debug!("Unhandled event received");
                         }
@@ -247,17 +860,29 @@ async fn main() -> Result<()> {
     let state_clone = state.clone();
     let agent_for_status = trading_agent.current_state();
     let position_manager_for_status = position_manager.clone();
+    let treasury_for_status = treasury.clone();
+    let performance_db_for_status = trading_agent.performance_db().clone();
+    let config_for_status = config.clone();
     let status_reporter = tokio::spawn(async move {
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
         loop {
             interval.tick().await;
             
-            let spot = state_clone.spot_price.load();
-            let perp = state_clone.perp_mark_price.load();
-            let basis = state_clone.get_basis_spread();
-            let funding_apr = state_clone.funding_apr.load();
+            let market = state_clone.snapshot();
+            let (spot, perp) = (market.spot_price, market.perp_mark_price);
+            let (basis, funding_apr) = (market.basis_spread, market.funding_apr);
             let positions = position_manager_for_status.get_positions().await;
             
+            if spot > 0.0 {
+                let inventory = treasury_for_status.report(spot);
+                performance_db_for_status.record_inventory(&inventory, &config_for_status.treasury).await;
+                performance_db_for_status
+                    .mark_to_market(state_clone.unrealized_pnl.load(), inventory.timestamp)
+                    .await;
+                let roc = performance_db_for_status.get_metrics().await.return_on_capital_pct;
+                record_capital(inventory.idle_usd, inventory.utilization_pct, roc);
+            }
+            
             if spot > 0.0 && perp > 0.0 {
                 if positions.spot_size > 0.0 {
                     info!(
@@ -290,18 +915,65 @@ async fn main() -> Result<()> {
         }
     }
     
-    // Cleanup
+    // Cleanup; the supervisor goes first so it does not restart what is stopping
+    if let Some(task) = supervisor {
+        task.abort();
+    }
+    if let Some(task) = panic_guard {
+        task.abort();
+    }
     info!("Stopping trading agent...");
     trading_agent.stop().await;
+    for strategy in &strategies {
+        strategy.stop().await;
+    }
     
     info!("Stopping engines...");
     engine_manager.stop().await;
+    regime_detector.stop().await;
+    volatility_monitor.stop().await;
     
     info!("Stopping price feeds...");
     price_feeds.stop().await;
     
     event_processor.abort();
+    session_log_task.abort();
     status_reporter.abort();
+    alert_pipeline.abort();
+    rpc_health.abort();
+    if let Some(task) = clock_monitor {
+        task.abort();
+    }
+    if let Some(task) = balance_refresher {
+        task.abort();
+    }
+    if let Some(task) = gas_watchdog {
+        task.abort();
+    }
+    if let Some(task) = profit_sweeper {
+        task.abort();
+    }
+    if let Some(task) = market_exporter {
+        task.abort();
+    }
+    if let Some(task) = session_recorder {
+        task.abort();
+    }
+    if let Some(task) = history_saver {
+        task.abort();
+    }
+    if let Some(path) = &history_path {
+        if let Err(e) = warm_start::save(&state, path).await {
+            warn!("Failed to save engine history: {}", e);
+        }
+    }
+    if let Some(task) = control_api {
+        task.abort();
+    }
+    #[cfg(feature = "grpc")]
+    if let Some(task) = grpc_service {
+        task.abort();
+    }
 
     // Final P&L report
     let final_pnl = position_manager.get_realized_pnl().await;
@@ -309,8 +981,41 @@ async fn main() -> Result<()> {
     info!("===========================================");
     info!("  Session Summary");
     info!("  Trades: {} | Realized P&L: ${:.2}", trade_count, final_pnl);
+    if config.report.enabled {
+        let performance_db = trading_agent.performance_db();
+        let data = agentic::SessionData {
+            trades: performance_db
+                .get_all_trades()
+                .await
+                .into_iter()
+                .filter(|t| t.close_time >= session_log.started_at())
+                .collect(),
+            equity: performance_db.get_daily_equity().await,
+            positions: [state.spot_position.read().clone(), state.perp_position.read().clone()]
+                .into_iter()
+                .flatten()
+                .collect(),
+            unrealized_pnl: state.unrealized_pnl.load(),
+            latency: telemetry::stage_stats(),
+        };
+        if !data.positions.is_empty() {
+            warn!("  {} position legs still open, unrealized P&L ${:.2}", data.positions.len(), data.unrealized_pnl);
+        }
+        match session_log.write(&config.report, &data).await {
+            Ok(path) => info!("  Report: {}", path.display()),
+            Err(e) => warn!("Failed to write session report: {}", e),
+        }
+    }
+    if let Some(chaos) = &chaos {
+        let stats = chaos.stats();
+        info!(
+            "  Chaos: {} RPC calls | {} errors | {} timeouts | {} latency spikes | {} WS drops",
+            stats.calls, stats.errors, stats.timeouts, stats.latency_spikes, stats.ws_drops
+        );
+    }
     info!("===========================================");
 
     info!("SOL Basis Trading Bot stopped");
+    shutdown_tracing();
     Ok(())
 }
@@ -0,0 +1,183 @@
+//! Chaos Fault Injection
+//!
+//! Degrades RPC and WebSocket calls on purpose, so failover, retries and
+//! the RPC-down risk pause can be exercised without a real outage:
+//! - Errors: the call fails immediately
+//! - Timeouts: the call hangs for the request timeout, then fails
+//! - Latency spikes: the call is delayed, then proceeds
+//! - WebSocket drops: the connection is torn down mid-stream
+//!
+//! Hooks are compiled in only with the `chaos` feature; without it an
+//! enabled `chaos` config is ignored with a warning.
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::config::ChaosConfig;
+
+/// What happened to one call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    None,
+    Error,
+    Timeout,
+    LatencySpike,
+}
+
+/// Injected fault counts
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ChaosStats {
+    pub calls: u64,
+    pub errors: u64,
+    pub timeouts: u64,
+    pub latency_spikes: u64,
+    pub ws_drops: u64,
+}
+
+/// Randomly degrades calls per `ChaosConfig`
+pub struct FaultInjector {
+    config: ChaosConfig,
+    /// How long an injected timeout hangs
+    timeout: Duration,
+    rng: Mutex<StdRng>,
+    calls: AtomicU64,
+    errors: AtomicU64,
+    timeouts: AtomicU64,
+    latency_spikes: AtomicU64,
+    ws_drops: AtomicU64,
+}
+
+impl FaultInjector {
+    /// An injector if chaos is enabled and compiled in
+    pub fn from_config(config: &ChaosConfig, timeout: Duration) -> Option<Arc<Self>> {
+        if !config.enabled {
+            return None;
+        }
+        if !cfg!(feature = "chaos") {
+            warn!("chaos.enabled is set but this build lacks the `chaos` feature; no faults will be injected");
+            return None;
+        }
+        warn!(
+            "CHAOS MODE: injecting RPC errors {:.1}%, timeouts {:.1}%, latency spikes {:.1}% (+{}ms), WebSocket drops {:.2}%",
+            config.error_rate * 100.0,
+            config.timeout_rate * 100.0,
+            config.latency_spike_rate * 100.0,
+            config.latency_spike_ms,
+            config.ws_drop_rate * 100.0
+        );
+        Some(Arc::new(Self::new(config, timeout)))
+    }
+
+    fn new(config: &ChaosConfig, timeout: Duration) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self {
+            config: config.clone(),
+            timeout,
+            rng: Mutex::new(rng),
+            calls: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            timeouts: AtomicU64::new(0),
+            latency_spikes: AtomicU64::new(0),
+            ws_drops: AtomicU64::new(0),
+        }
+    }
+
+    /// Draw the fault for one call
+    pub fn roll(&self) -> Fault {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        let (fail, spike) = {
+            let mut rng = self.rng.lock();
+            (rng.gen::<f64>(), rng.gen::<f64>())
+        };
+        if fail < self.config.error_rate {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+            Fault::Error
+        } else if fail < self.config.error_rate + self.config.timeout_rate {
+            self.timeouts.fetch_add(1, Ordering::Relaxed);
+            Fault::Timeout
+        } else if spike < self.config.latency_spike_rate {
+            self.latency_spikes.fetch_add(1, Ordering::Relaxed);
+            Fault::LatencySpike
+        } else {
+            Fault::None
+        }
+    }
+
+    /// Apply a fault before an RPC call; errors stand in for the call's own failure
+    pub async fn before_call(&self, operation: &str) -> Result<()> {
+        match self.roll() {
+            Fault::None => Ok(()),
+            Fault::Error => {
+                debug!("Chaos: failing {}", operation);
+                anyhow::bail!("chaos: injected error in {}", operation)
+            }
+            Fault::Timeout => {
+                debug!("Chaos: timing out {}", operation);
+                tokio::time::sleep(self.timeout).await;
+                anyhow::bail!("chaos: injected timeout in {} after {:?}", operation, self.timeout)
+            }
+            Fault::LatencySpike => {
+                debug!("Chaos: delaying {} by {}ms", operation, self.config.latency_spike_ms);
+                tokio::time::sleep(Duration::from_millis(self.config.latency_spike_ms)).await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether to drop the WebSocket connection now
+    pub fn drop_connection(&self) -> bool {
+        let drop = self.rng.lock().gen::<f64>() < self.config.ws_drop_rate;
+        if drop {
+            self.ws_drops.fetch_add(1, Ordering::Relaxed);
+        }
+        drop
+    }
+
+    pub fn stats(&self) -> ChaosStats {
+        ChaosStats {
+            calls: self.calls.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            latency_spikes: self.latency_spikes.load(Ordering::Relaxed),
+            ws_drops: self.ws_drops.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fault_rates() {
+        let config = ChaosConfig {
+            enabled: true,
+            error_rate: 0.2,
+            timeout_rate: 0.1,
+            latency_spike_rate: 0.5,
+            seed: Some(7),
+            ..ChaosConfig::default()
+        };
+        let injector = FaultInjector::new(&config, Duration::from_millis(10));
+        for _ in 0..10_000 {
+            injector.roll();
+        }
+
+        let stats = injector.stats();
+        assert_eq!(stats.calls, 10_000);
+        assert!((1_800..2_200).contains(&stats.errors), "{:?}", stats);
+        assert!((800..1_200).contains(&stats.timeouts), "{:?}", stats);
+        // Spikes only apply to calls that did not fail: 70% of 50%
+        assert!((3_200..3_800).contains(&stats.latency_spikes), "{:?}", stats);
+    }
+}
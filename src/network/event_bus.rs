@@ -3,14 +3,20 @@
 //! Provides a broadcast-based event system for decoupled communication
 //! between modules, particularly for price updates.
 
+use serde::Serialize;
 use std::sync::Arc;
 use tokio::sync::broadcast;
 use tracing::{debug, warn};
 
-use crate::utils::types::PriceUpdate;
+use crate::agentic::{MarketRegime, ReversalSeverity};
+use crate::telemetry::Alert;
+use crate::utils::types::{Dependency, DepthQuote, OrderBook, PriceUpdate, SkipReason, TradeSignal};
 
-/// Event types that can be broadcast through the system
-#[derive(Debug, Clone)]
+/// Event types that can be broadcast through the system.
+///
+/// Serializes as `{"type": "<variant>", "data": ...}` for external consumers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data")]
 pub enum Event {
     // Connection events
     WebSocketConnected,
@@ -18,9 +24,16 @@ pub enum Event {
     WebSocketMessage(String),
     
     // Price events
+    /// One spot source's price, before aggregation
+    OracleUpdate(PriceUpdate),
+    /// Composite spot price
     SpotPriceUpdate(PriceUpdate),
+    /// USDC/USD, for the peg monitor
+    UsdcPriceUpdate(PriceUpdate),
     PerpMarkPriceUpdate(PriceUpdate),
     PerpIndexPriceUpdate(PriceUpdate),
+    SpotDepthUpdate(DepthQuote),
+    PerpBookUpdate(OrderBook),
     
     // Funding events
     FundingRateUpdate {
@@ -37,10 +50,46 @@ pub enum Event {
     },
     
     // Trading signals
-    TradeSignal {
-        signal_type: String,
+    /// Actionable signal from the signal engine
+    SignalGenerated(TradeSignal),
+    /// Hedge drift past `risk.hedge_drift_threshold_pct`
+    HedgeDriftAlert {
+        drift_pct: f64,
+        threshold_pct: f64,
+    },
+    /// Funding APR above `trading.min_funding_apr_pct`
+    FundingElevated {
+        apr_pct: f64,
+        threshold_pct: f64,
+    },
+    /// Funding rate moving toward a flip (funding engine)
+    FundingReversing {
+        /// Rate change per hour
+        velocity: f64,
+    },
+    /// Reversal detector warning
+    FundingReversal {
+        severity: ReversalSeverity,
+        apr_pct: f64,
+        time_to_zero_hours: Option<f64>,
+        recommendation: String,
+    },
+    /// Basis regime change
+    RegimeChanged {
+        from: MarketRegime,
+        to: MarketRegime,
+        variance_ratio: f64,
+    },
+    /// An open signal blocked by a filter, followed as a hypothetical trade
+    SignalSkipped {
+        reason: SkipReason,
+        detail: String,
         size: f64,
-        reason: String,
+        basis_spread: f64,
+        funding_apr: f64,
+        spot_price: f64,
+        confidence: f64,
+        timestamp: i64,
     },
     
     // System events
@@ -48,10 +97,22 @@ pub enum Event {
         reason: String,
     },
     SystemResume,
+    /// An external API's circuit breaker opened
+    DependencyDegraded {
+        dependency: Dependency,
+        failures: u32,
+        cool_off_secs: u64,
+    },
+    /// A degraded API answered again
+    DependencyRecovered {
+        dependency: Dependency,
+        down_secs: u64,
+    },
     Error {
         source: String,
         message: String,
     },
+    Alert(Alert),
     
     // Position events
     PositionOpened {
@@ -64,6 +125,16 @@ pub enum Event {
         position_id: String,
         pnl: f64,
     },
+    /// Spot and perp legs did not match after an open or close
+    LegImbalance {
+        trade_id: String,
+        /// "open" or "close"
+        phase: String,
+        spot_filled: f64,
+        perp_filled: f64,
+        /// How it was resolved (completed, unwound, failed)
+        resolution: String,
+    },
     
     // Heartbeat
     Heartbeat {
@@ -71,6 +142,77 @@ pub enum Event {
     },
 }
 
+impl Event {
+    /// Variant name, as used in the serialized `type` field
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::WebSocketConnected => "WebSocketConnected",
+            Event::WebSocketDisconnected => "WebSocketDisconnected",
+            Event::WebSocketMessage(_) => "WebSocketMessage",
+            Event::OracleUpdate(_) => "OracleUpdate",
+            Event::SpotPriceUpdate(_) => "SpotPriceUpdate",
+            Event::UsdcPriceUpdate(_) => "UsdcPriceUpdate",
+            Event::PerpMarkPriceUpdate(_) => "PerpMarkPriceUpdate",
+            Event::PerpIndexPriceUpdate(_) => "PerpIndexPriceUpdate",
+            Event::SpotDepthUpdate(_) => "SpotDepthUpdate",
+            Event::PerpBookUpdate(_) => "PerpBookUpdate",
+            Event::FundingRateUpdate { .. } => "FundingRateUpdate",
+            Event::BasisSpreadUpdate { .. } => "BasisSpreadUpdate",
+            Event::SignalGenerated(_) => "SignalGenerated",
+            Event::HedgeDriftAlert { .. } => "HedgeDriftAlert",
+            Event::FundingElevated { .. } => "FundingElevated",
+            Event::FundingReversing { .. } => "FundingReversing",
+            Event::FundingReversal { .. } => "FundingReversal",
+            Event::RegimeChanged { .. } => "RegimeChanged",
+            Event::SignalSkipped { .. } => "SignalSkipped",
+            Event::SystemPause { .. } => "SystemPause",
+            Event::SystemResume => "SystemResume",
+            Event::DependencyDegraded { .. } => "DependencyDegraded",
+            Event::DependencyRecovered { .. } => "DependencyRecovered",
+            Event::Error { .. } => "Error",
+            Event::Alert(_) => "Alert",
+            Event::PositionOpened { .. } => "PositionOpened",
+            Event::PositionClosed { .. } => "PositionClosed",
+            Event::LegImbalance { .. } => "LegImbalance",
+            Event::Heartbeat { .. } => "Heartbeat",
+        }
+    }
+    
+    /// Topic the event belongs to, for subscriber filtering
+    pub fn topic(&self) -> &'static str {
+        match self {
+            Event::WebSocketConnected
+            | Event::WebSocketDisconnected
+            | Event::WebSocketMessage(_) => "connection",
+            Event::OracleUpdate(_)
+            | Event::SpotPriceUpdate(_)
+            | Event::UsdcPriceUpdate(_)
+            | Event::PerpMarkPriceUpdate(_)
+            | Event::PerpIndexPriceUpdate(_)
+            | Event::SpotDepthUpdate(_)
+            | Event::PerpBookUpdate(_) => "price",
+            Event::FundingRateUpdate { .. } => "funding",
+            Event::BasisSpreadUpdate { .. } => "basis",
+            Event::SignalGenerated(_)
+            | Event::SignalSkipped { .. }
+            | Event::HedgeDriftAlert { .. }
+            | Event::FundingElevated { .. }
+            | Event::FundingReversing { .. }
+            | Event::FundingReversal { .. }
+            | Event::RegimeChanged { .. } => "signal",
+            Event::PositionOpened { .. }
+            | Event::PositionClosed { .. }
+            | Event::LegImbalance { .. } => "position",
+            Event::Alert(_) | Event::Error { .. } => "alert",
+            Event::SystemPause { .. }
+            | Event::SystemResume
+            | Event::DependencyDegraded { .. }
+            | Event::DependencyRecovered { .. }
+            | Event::Heartbeat { .. } => "system",
+        }
+    }
+}
+
 /// Event bus for broadcasting events to multiple subscribers
 pub struct EventBus {
     /// Broadcast sender
@@ -0,0 +1,238 @@
+//! HTTP Retry Policy
+//!
+//! Shared by the outbound HTTP clients (Jupiter, Drift, Pyth, Switchboard,
+//! Jito) in place of ad-hoc timeouts:
+//! - Each attempt is bounded by `timeout_ms`
+//! - Connection errors, timeouts, 429 and 5xx responses are retried up to
+//!   `max_retries` times, with exponential backoff from `backoff_ms` (capped
+//!   at `max_backoff_ms`) plus jitter
+//! - Failures are counted per circuit breaker across all clients: one per
+//!   `Dependency` (Jupiter, Drift data, Jito) for clients tagged with it,
+//!   else one per host. After `breaker_failures` consecutive failed calls
+//!   the breaker opens and calls fail fast, without being sent, for
+//!   `cool_off_secs`; the next call then probes the API
+//! - A dependency's breaker opening and healing are published as
+//!   `DependencyDegraded` and `DependencyRecovered` events (once
+//!   `publish_events` is called), and `degraded()` lists the dependencies
+//!   that are down, for the signal engine
+//!
+//! Other responses are returned as they are, for the caller's own status
+//! handling. Requests and cool-offs are exported per host.
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use rand::Rng;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::config::HttpConfig;
+use crate::network::event_bus::Event;
+use crate::telemetry::{record_http_cool_off, record_http_request};
+use crate::utils::types::Dependency;
+
+/// Change of a breaker after a call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transition {
+    None,
+    /// Opened after consecutive failures
+    Opened,
+    /// Closed by a success, after being down this long
+    Recovered(Duration),
+}
+
+/// Failure tracking of one dependency or host
+#[derive(Debug, Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    cooling_off_until: Option<Instant>,
+    /// When the breaker first opened, until a success
+    down_since: Option<Instant>,
+}
+
+impl Breaker {
+    /// Whether calls are held back at `now`
+    fn cooling_off(&self, now: Instant) -> Option<Duration> {
+        self.cooling_off_until.filter(|until| *until > now).map(|until| until - now)
+    }
+
+    /// Count a call's outcome
+    fn record(&mut self, ok: bool, config: &HttpConfig, now: Instant) -> Transition {
+        if ok {
+            self.consecutive_failures = 0;
+            self.cooling_off_until = None;
+            return match self.down_since.take() {
+                Some(since) => Transition::Recovered(now - since),
+                None => Transition::None,
+            };
+        }
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= config.breaker_failures && self.cooling_off(now).is_none() {
+            self.cooling_off_until = Some(now + Duration::from_secs(config.cool_off_secs));
+            if self.down_since.is_none() {
+                self.down_since = Some(now);
+                return Transition::Opened;
+            }
+        }
+        Transition::None
+    }
+}
+
+/// Breakers of every dependency and untagged host, shared by all policies
+fn breakers() -> &'static DashMap<String, Breaker> {
+    static BREAKERS: OnceLock<DashMap<String, Breaker>> = OnceLock::new();
+    BREAKERS.get_or_init(DashMap::new)
+}
+
+static EVENTS: OnceLock<broadcast::Sender<Event>> = OnceLock::new();
+
+/// Publish dependency breaker changes on the event bus
+pub fn publish_events(event_tx: broadcast::Sender<Event>) {
+    let _ = EVENTS.set(event_tx);
+}
+
+/// Dependencies whose breaker is open (down since their last success)
+pub fn degraded() -> Vec<Dependency> {
+    [Dependency::Jupiter, Dependency::DriftData, Dependency::Jito]
+        .into_iter()
+        .filter(|d| breakers().get(d.as_str()).is_some_and(|b| b.down_since.is_some()))
+        .collect()
+}
+
+/// Delay before retry `attempt` (1-based), without jitter
+pub fn backoff_delay(config: &HttpConfig, attempt: u32) -> Duration {
+    let delay = config.backoff_ms.saturating_mul(1 << attempt.saturating_sub(1).min(10));
+    Duration::from_millis(delay.min(config.max_backoff_ms))
+}
+
+fn retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Timeouts, retries and circuit breaking for outbound HTTP calls
+#[derive(Debug, Clone, Default)]
+pub struct HttpRetryPolicy {
+    config: HttpConfig,
+    dependency: Option<Dependency>,
+}
+
+impl HttpRetryPolicy {
+    pub fn new(config: &HttpConfig) -> Self {
+        Self { config: config.clone(), dependency: None }
+    }
+
+    /// Break the circuit per `dependency` rather than per host
+    pub fn for_dependency(mut self, dependency: Dependency) -> Self {
+        self.dependency = Some(dependency);
+        self
+    }
+
+    /// Send a request under the policy
+    pub async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let (client, request) = request.build_split();
+        let request = request.context("Invalid HTTP request")?;
+        let host = request.url().host_str().unwrap_or("unknown").to_string();
+        let key = self.dependency.map(|d| d.as_str().to_string()).unwrap_or_else(|| host.clone());
+
+        if let Some(left) = breakers().get(&key).and_then(|b| b.cooling_off(Instant::now())) {
+            record_http_request(&host, "rejected");
+            anyhow::bail!("{} is cooling off after repeated failures ({}s left)", key, left.as_secs() + 1);
+        }
+
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let mut attempt = 0;
+        let result = loop {
+            // Bodies built with `.json()` can always be cloned
+            let Some(this_attempt) = request.try_clone() else {
+                break tokio::time::timeout(timeout, client.execute(request)).await;
+            };
+            let outcome = tokio::time::timeout(timeout, client.execute(this_attempt)).await;
+            let retry = match &outcome {
+                Ok(Ok(response)) => retryable_status(response.status()),
+                Ok(Err(_)) | Err(_) => true,
+            };
+            if !retry || attempt >= self.config.max_retries {
+                break outcome;
+            }
+            attempt += 1;
+            let delay = backoff_delay(&self.config, attempt);
+            let jitter = rand::thread_rng().gen_range(0..=delay.as_millis() as u64 / 2);
+            debug!("Retrying {} (attempt {}) after {:?}", host, attempt, delay);
+            record_http_request(&host, "retry");
+            tokio::time::sleep(delay + Duration::from_millis(jitter)).await;
+        };
+
+        let result = match result {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(e)) => Err(anyhow::Error::new(e).context(format!("Request to {} failed", host))),
+            Err(_) => Err(anyhow::anyhow!("Request to {} timed out after {:?}", host, timeout)),
+        };
+        let ok = result.as_ref().is_ok_and(|r| !retryable_status(r.status()));
+        record_http_request(&host, if ok { "ok" } else { "failure" });
+
+        let (transition, failures) = {
+            let mut breaker = breakers().entry(key.clone()).or_default();
+            (breaker.record(ok, &self.config, Instant::now()), breaker.consecutive_failures)
+        };
+        match transition {
+            Transition::None => {}
+            Transition::Opened => {
+                warn!("{} failed {} times in a row, cooling off for {}s", key, failures, self.config.cool_off_secs);
+                record_http_cool_off(&key, true);
+                self.publish(|dependency| Event::DependencyDegraded {
+                    dependency,
+                    failures,
+                    cool_off_secs: self.config.cool_off_secs,
+                });
+            }
+            Transition::Recovered(down) => {
+                info!("{} recovered after {}s", key, down.as_secs());
+                record_http_cool_off(&key, false);
+                self.publish(|dependency| Event::DependencyRecovered { dependency, down_secs: down.as_secs() });
+            }
+        }
+        result
+    }
+
+    fn publish(&self, event: impl FnOnce(Dependency) -> Event) {
+        if let (Some(dependency), Some(event_tx)) = (self.dependency, EVENTS.get()) {
+            let _ = event_tx.send(event(dependency));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_and_cool_off() {
+        let config = HttpConfig { backoff_ms: 200, max_backoff_ms: 1_000, breaker_failures: 3, ..HttpConfig::default() };
+        assert_eq!(backoff_delay(&config, 1), Duration::from_millis(200));
+        assert_eq!(backoff_delay(&config, 3), Duration::from_millis(800));
+        assert_eq!(backoff_delay(&config, 4), Duration::from_millis(1_000));
+
+        let now = Instant::now();
+        let mut breaker = Breaker::default();
+        assert_eq!(breaker.record(false, &config, now), Transition::None);
+        assert_eq!(breaker.record(false, &config, now), Transition::None);
+        assert_eq!(breaker.record(false, &config, now), Transition::Opened);
+        assert!(breaker.cooling_off(now).is_some());
+        let after = now + Duration::from_secs(config.cool_off_secs + 1);
+        assert!(breaker.cooling_off(after).is_none());
+
+        // A failed probe after the cool-off reopens it without a second event
+        assert_eq!(breaker.record(false, &config, after), Transition::None);
+        assert!(breaker.cooling_off(after).is_some());
+
+        // A success closes the breaker and reports the outage
+        let healed = after + Duration::from_secs(config.cool_off_secs + 1);
+        assert_eq!(
+            breaker.record(true, &config, healed),
+            Transition::Recovered(healed - now)
+        );
+        assert_eq!(breaker.consecutive_failures, 0);
+        assert!(breaker.cooling_off(healed).is_none());
+    }
+}
@@ -1,11 +1,17 @@
 //! Network module - Phase 2
 //!
-//! Provides RPC client, WebSocket management, and event bus for price feeds.
+//! Provides RPC client, WebSocket management, and event bus for price feeds,
+//! the retry policy for outbound HTTP calls, plus chaos fault injection for
+//! resilience testing.
 
 pub mod rpc_client;
+pub mod http;
 pub mod websocket;
 pub mod event_bus;
+pub mod chaos;
 
+pub use chaos::{ChaosStats, FaultInjector};
+pub use http::HttpRetryPolicy;
 pub use rpc_client::RpcManager;
 pub use websocket::WebSocketManager;
 pub use event_bus::{EventBus, Event};
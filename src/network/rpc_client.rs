@@ -1,22 +1,52 @@
 //! Solana RPC Client Manager
 //!
 //! Provides high-throughput RPC access with connection pooling,
-//! automatic failover, and latency tracking.
+//! automatic failover, and latency tracking. Calls pass through the chaos
+//! fault injector when one is attached. A clock monitor measures the local
+//! clock's skew against block time (see `utils::clock`).
 
 use anyhow::{Context, Result};
+use solana_account_decoder::parse_token::UiTokenAmount;
 use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::SerializableTransaction;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     hash::Hash,
     signature::Signature,
-    transaction::Transaction,
 };
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, error, info, warn};
 
-use crate::config::RpcConfig;
+use crate::config::{ClockConfig, RpcConfig};
+use crate::state::SharedState;
+use crate::telemetry::{record_clock_skew, Alert};
+use crate::utils::clock;
+
+use super::chaos::FaultInjector;
+use super::event_bus::Event;
+
+/// Blockhashes remembered for expiry lookups (~25s of 400ms refreshes)
+const BLOCKHASH_HISTORY: usize = 64;
+/// Blocks a blockhash stays valid for when its expiry height is unknown
+const MAX_PROCESSING_AGE: u64 = 150;
+
+/// Whether an RPC error means the transaction's blockhash has expired
+pub fn is_blockhash_expired_error(error: &anyhow::Error) -> bool {
+    let msg = format!("{:#}", error).to_lowercase();
+    msg.contains("blockhash not found")
+        || msg.contains("block height exceeded")
+        || msg.contains("transaction expiration")
+}
+
+/// UI balance of a token account; an account that does not exist yet
+/// (a null `value`) holds nothing
+fn token_account_balance(amount: Option<UiTokenAmount>) -> f64 {
+    amount.and_then(|a| a.ui_amount).unwrap_or(0.0)
+}
 
 /// RPC Manager with failover support
 pub struct RpcManager {
@@ -30,8 +60,12 @@ pub struct RpcManager {
     config: RpcConfig,
     /// Cached recent blockhash
     cached_blockhash: RwLock<Option<(Hash, Instant)>>,
+    /// Last valid block height of recently fetched blockhashes
+    blockhash_expiry: RwLock<VecDeque<(Hash, u64)>>,
     /// Blockhash cache duration
     blockhash_cache_duration: Duration,
+    /// Fault injection for resilience testing
+    chaos: Option<Arc<FaultInjector>>,
 }
 
 impl RpcManager {
@@ -69,10 +103,26 @@ impl RpcManager {
             active_index: RwLock::new(0),
             config: config.clone(),
             cached_blockhash: RwLock::new(None),
+            blockhash_expiry: RwLock::new(VecDeque::with_capacity(BLOCKHASH_HISTORY)),
             blockhash_cache_duration: Duration::from_millis(400),
+            chaos: None,
         })
     }
     
+    /// Degrade every call through a fault injector
+    pub fn with_chaos(mut self, chaos: Option<Arc<FaultInjector>>) -> Self {
+        self.chaos = chaos;
+        self
+    }
+    
+    /// Injected fault, if any, ahead of a call
+    async fn inject(&self, operation: &str) -> Result<()> {
+        match &self.chaos {
+            Some(chaos) => chaos.before_call(operation).await,
+            None => Ok(()),
+        }
+    }
+    
     /// Get the currently active RPC client
     pub async fn get_client(&self) -> Arc<RpcClient> {
         let index = *self.active_index.read().await;
@@ -118,11 +168,12 @@ impl RpcManager {
         }
         
         // Fetch new blockhash
+        self.inject("get_latest_blockhash").await?;
         let client = self.get_client().await;
         let start = Instant::now();
         
-        let blockhash = client
-            .get_latest_blockhash()
+        let (blockhash, last_valid_block_height) = client
+            .get_latest_blockhash_with_commitment(client.commitment())
             .await
             .context("Failed to get recent blockhash")?;
         
@@ -133,12 +184,50 @@ impl RpcManager {
             let mut cache = self.cached_blockhash.write().await;
             *cache = Some((blockhash, Instant::now()));
         }
+        {
+            let mut expiry = self.blockhash_expiry.write().await;
+            if !expiry.iter().any(|(hash, _)| *hash == blockhash) {
+                if expiry.len() == BLOCKHASH_HISTORY {
+                    expiry.pop_front();
+                }
+                expiry.push_back((blockhash, last_valid_block_height));
+            }
+        }
         
         Ok(blockhash)
     }
     
+    /// Drop the cached blockhash so the next build fetches a fresh one
+    pub async fn invalidate_blockhash(&self) {
+        *self.cached_blockhash.write().await = None;
+    }
+    
+    /// Last block height at which a transaction using `blockhash` can land.
+    /// Blockhashes not fetched through this manager are assumed fresh as of now.
+    pub async fn last_valid_block_height(&self, blockhash: &Hash) -> Result<u64> {
+        let known = self
+            .blockhash_expiry
+            .read()
+            .await
+            .iter()
+            .find(|(hash, _)| hash == blockhash)
+            .map(|(_, height)| *height);
+        match known {
+            Some(height) => Ok(height),
+            None => Ok(self.get_block_height().await? + MAX_PROCESSING_AGE),
+        }
+    }
+    
+    /// Current block height
+    pub async fn get_block_height(&self) -> Result<u64> {
+        self.inject("get_block_height").await?;
+        let client = self.get_client().await;
+        client.get_block_height().await.context("Failed to get block height")
+    }
+    
     /// Get account balance
     pub async fn get_balance(&self, pubkey: &solana_sdk::pubkey::Pubkey) -> Result<u64> {
+        self.inject("get_balance").await?;
         let client = self.get_client().await;
         client
             .get_balance(pubkey)
@@ -146,15 +235,57 @@ impl RpcManager {
             .context("Failed to get balance")
     }
     
-    /// Send transaction with retry logic
-    pub async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+    /// Top up from the faucet when the balance is below `min_lamports` (devnet/testnet only)
+    pub async fn airdrop_if_below(
+        &self,
+        pubkey: &solana_sdk::pubkey::Pubkey,
+        min_lamports: u64,
+        lamports: u64,
+    ) -> Result<Option<Signature>> {
+        let balance = self.get_balance(pubkey).await?;
+        if balance >= min_lamports {
+            return Ok(None);
+        }
+        self.inject("request_airdrop").await?;
+        let client = self.get_client().await;
+        let signature = client
+            .request_airdrop(pubkey, lamports)
+            .await
+            .context("Faucet airdrop failed")?;
+        info!("Requested airdrop of {} lamports to {} ({})", lamports, pubkey, signature);
+        Ok(Some(signature))
+    }
+    
+    /// Get SPL token balance (UI units) held in the owner's associated token account
+    pub async fn get_token_balance(
+        &self,
+        owner: &solana_sdk::pubkey::Pubkey,
+        mint: &solana_sdk::pubkey::Pubkey,
+    ) -> Result<f64> {
+        let ata = crate::utils::helpers::associated_token_address(owner, mint);
+        self.inject("get_token_account_balance").await?;
+        let client = self.get_client().await;
+        
+        let account = client
+            .get_token_account_with_commitment(&ata, client.commitment())
+            .await
+            .context("Failed to get token balance")?;
+        Ok(token_account_balance(account.value.map(|a| a.token_amount)))
+    }
+    
+    /// Send transaction (legacy or versioned) with retry logic
+    pub async fn send_transaction(&self, transaction: &impl SerializableTransaction) -> Result<Signature> {
         let mut last_error = None;
         
         for attempt in 0..self.config.max_retries {
             let client = self.get_client().await;
             let start = Instant::now();
             
-            match client.send_and_confirm_transaction(transaction).await {
+            let result = match self.inject("send_transaction").await {
+                Ok(()) => client.send_and_confirm_transaction(transaction).await.map_err(anyhow::Error::from),
+                Err(e) => Err(e),
+            };
+            match result {
                 Ok(signature) => {
                     debug!(
                         "Transaction sent in {:?} on attempt {}",
@@ -165,6 +296,10 @@ impl RpcManager {
                 }
                 Err(e) => {
                     warn!("Transaction attempt {} failed: {}", attempt + 1, e);
+                    // Resending cannot help once the blockhash is gone
+                    if is_blockhash_expired_error(&e) {
+                        return Err(e);
+                    }
                     last_error = Some(e);
                     
                     // Try failover on repeated failures
@@ -180,16 +315,37 @@ impl RpcManager {
             }
         }
         
-        Err(last_error
-            .map(|e| anyhow::anyhow!("{}", e))
-            .unwrap_or_else(|| anyhow::anyhow!("Transaction failed after retries")))
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("Transaction failed after retries")))
     }
     
-    /// Simulate transaction
+    /// Send a transaction without waiting for confirmation. Durable nonce
+    /// transactions go this way: the client's confirmation loop treats a
+    /// nonce as an expired blockhash.
+    pub async fn send_transaction_unconfirmed(&self, transaction: &impl SerializableTransaction) -> Result<Signature> {
+        self.inject("send_transaction").await?;
+        let client = self.get_client().await;
+        client
+            .send_transaction(transaction)
+            .await
+            .context("Failed to send transaction")
+    }
+    
+    /// Rent-exempt minimum for an account of `size` bytes
+    pub async fn get_minimum_balance_for_rent_exemption(&self, size: usize) -> Result<u64> {
+        self.inject("get_minimum_balance_for_rent_exemption").await?;
+        let client = self.get_client().await;
+        client
+            .get_minimum_balance_for_rent_exemption(size)
+            .await
+            .context("Failed to get rent-exempt minimum")
+    }
+    
+    /// Simulate transaction (legacy or versioned)
     pub async fn simulate_transaction(
         &self,
-        transaction: &Transaction,
+        transaction: &impl SerializableTransaction,
     ) -> Result<solana_client::rpc_response::RpcSimulateTransactionResult> {
+        self.inject("simulate_transaction").await?;
         let client = self.get_client().await;
         let result = client
             .simulate_transaction(transaction)
@@ -200,15 +356,80 @@ impl RpcManager {
     
     /// Get slot
     pub async fn get_slot(&self) -> Result<u64> {
+        self.inject("get_slot").await?;
         let client = self.get_client().await;
         client.get_slot().await.context("Failed to get slot")
     }
     
+    /// Local clock minus the block time of the current slot (ms)
+    pub async fn clock_skew_sample(&self) -> Result<i64> {
+        let slot = self.get_slot().await?;
+        self.inject("get_block_time").await?;
+        let client = self.get_client().await;
+        let block_time = client.get_block_time(slot).await.context("Failed to get block time")?;
+        // Block times are whole seconds: compare against the middle of one
+        Ok(chrono::Utc::now().timestamp_millis() - (block_time * 1000 + 500))
+    }
+    
+    /// Sample the clock skew on an interval, applying the median of recent
+    /// samples to venue timestamps. Crossing `max_skew_ms` warns and sends
+    /// an alert; falling back within it is logged.
+    pub fn spawn_clock_monitor(
+        self: &Arc<Self>,
+        config: &ClockConfig,
+        event_tx: broadcast::Sender<Event>,
+    ) -> tokio::task::JoinHandle<()> {
+        let manager = self.clone();
+        let config = config.clone();
+        
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(config.check_interval_secs.max(1)));
+            let mut samples = VecDeque::with_capacity(config.samples);
+            let mut skewed = false;
+            loop {
+                interval.tick().await;
+                match manager.clock_skew_sample().await {
+                    Ok(sample) => {
+                        if samples.len() == config.samples {
+                            samples.pop_front();
+                        }
+                        samples.push_back(sample);
+                    }
+                    Err(e) => {
+                        debug!("Clock skew sample failed: {:#}", e);
+                        continue;
+                    }
+                }
+                let Some(skew) = clock::estimate_skew(samples.make_contiguous()) else {
+                    continue;
+                };
+                clock::set_skew_ms(skew);
+                record_clock_skew(skew);
+                
+                if skew.abs() > config.max_skew_ms && !skewed {
+                    let message = format!(
+                        "Local clock is {}ms {} block time (limit {}ms)",
+                        skew.abs(),
+                        if skew > 0 { "ahead of" } else { "behind" },
+                        config.max_skew_ms
+                    );
+                    warn!("{}", message);
+                    let _ = event_tx.send(Event::Alert(Alert::warning("Clock skew", message)));
+                    skewed = true;
+                } else if skew.abs() <= config.max_skew_ms && skewed {
+                    info!("Local clock back within {}ms of block time ({}ms)", config.max_skew_ms, skew);
+                    skewed = false;
+                }
+            }
+        })
+    }
+    
     /// Health check - test connection to current endpoint
     pub async fn health_check(&self) -> Result<Duration> {
         let client = self.get_client().await;
         let start = Instant::now();
         
+        self.inject("health_check").await.context("Health check failed")?;
         client
             .get_slot()
             .await
@@ -217,11 +438,48 @@ impl RpcManager {
         Ok(start.elapsed())
     }
     
+    /// Health check the active endpoint on an interval. A failed check fails
+    /// over to the next endpoint; after `max_health_failures` consecutive
+    /// failures RPC is marked down (which pauses trading) until a check passes.
+    pub fn spawn_health_monitor(self: &Arc<Self>, state: Arc<SharedState>) -> tokio::task::JoinHandle<()> {
+        let manager = self.clone();
+        let period = Duration::from_secs(self.config.health_check_interval_secs.max(1));
+        let max_failures = self.config.max_health_failures.max(1);
+        
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            let mut failures = 0u32;
+            loop {
+                interval.tick().await;
+                match manager.health_check().await {
+                    Ok(latency) => {
+                        state.rpc_latency_us.store(latency.as_micros() as u64, Ordering::Relaxed);
+                        if failures >= max_failures {
+                            info!("RPC recovered after {} failed health checks", failures);
+                        }
+                        failures = 0;
+                        *state.rpc_connected.write() = true;
+                    }
+                    Err(e) => {
+                        failures += 1;
+                        warn!("RPC health check failed ({}/{}): {:#}", failures, max_failures, e);
+                        manager.failover().await;
+                        if failures == max_failures {
+                            error!("RPC marked down after {} failed health checks", failures);
+                            *state.rpc_connected.write() = false;
+                        }
+                    }
+                }
+            }
+        })
+    }
+    
     /// Get multiple accounts
     pub async fn get_multiple_accounts(
         &self,
         pubkeys: &[solana_sdk::pubkey::Pubkey],
     ) -> Result<Vec<Option<solana_sdk::account::Account>>> {
+        self.inject("get_multiple_accounts").await?;
         let client = self.get_client().await;
         client
             .get_multiple_accounts(pubkeys)
@@ -244,9 +502,30 @@ mod tests {
             request_timeout_ms: 10000,
             max_retries: 3,
             requests_per_second: 50,
+            health_check_interval_secs: 10,
+            max_health_failures: 3,
         };
         
         let manager = RpcManager::new(&config);
         assert!(manager.is_ok());
     }
+
+    #[test]
+    fn test_blockhash_expired_error() {
+        assert!(is_blockhash_expired_error(&anyhow::anyhow!("RPC response error -32002: Transaction simulation failed: Blockhash not found")));
+        assert!(is_blockhash_expired_error(&anyhow::anyhow!("Transaction expired: block height exceeded").context("send")));
+        assert!(!is_blockhash_expired_error(&anyhow::anyhow!("insufficient funds for fee")));
+    }
+
+    #[test]
+    fn test_missing_token_account_balance() {
+        assert_eq!(token_account_balance(None), 0.0);
+        let amount = UiTokenAmount {
+            ui_amount: Some(12.5),
+            decimals: 6,
+            amount: "12500000".to_string(),
+            ui_amount_string: "12.5".to_string(),
+        };
+        assert_eq!(token_account_balance(Some(amount)), 12.5);
+    }
 }
@@ -1,7 +1,8 @@
 //! WebSocket Connection Manager
 //!
 //! Manages WebSocket connections for streaming price updates
-//! with automatic reconnection and health monitoring.
+//! with automatic reconnection and health monitoring. With chaos enabled,
+//! connects can fail and live connections drop at random.
 
 use anyhow::{Context, Result};
 use futures_util::{SinkExt, StreamExt};
@@ -13,6 +14,7 @@ use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, error, info, warn};
 use url::Url;
 
+use crate::network::chaos::FaultInjector;
 use crate::network::event_bus::Event;
 
 /// WebSocket connection state
@@ -38,6 +40,8 @@ pub struct WebSocketManager {
     reconnect_delay: Duration,
     /// Shutdown signal
     shutdown: Arc<RwLock<bool>>,
+    /// Fault injection for resilience testing
+    chaos: Option<Arc<FaultInjector>>,
 }
 
 impl WebSocketManager {
@@ -50,9 +54,16 @@ impl WebSocketManager {
             max_reconnect_attempts: 10,
             reconnect_delay: Duration::from_secs(1),
             shutdown: Arc::new(RwLock::new(false)),
+            chaos: None,
         }
     }
     
+    /// Fail connects and drop connections through a fault injector
+    pub fn with_chaos(mut self, chaos: Option<Arc<FaultInjector>>) -> Self {
+        self.chaos = chaos;
+        self
+    }
+    
     /// Get current connection state
     pub async fn get_state(&self) -> ConnectionState {
         *self.state.read().await
@@ -71,6 +82,7 @@ impl WebSocketManager {
         let max_attempts = self.max_reconnect_attempts;
         let reconnect_delay = self.reconnect_delay;
         let shutdown = self.shutdown.clone();
+        let chaos = self.chaos.clone();
         
         tokio::spawn(async move {
             let mut reconnect_count = 0;
@@ -85,7 +97,7 @@ impl WebSocketManager {
                 *state.write().await = ConnectionState::Connecting;
                 info!("Connecting to WebSocket: {}", url);
                 
-                match Self::connect_and_run(&url, &state, &event_tx).await {
+                match Self::connect_and_run(&url, &state, &event_tx, chaos.as_deref()).await {
                     Ok(()) => {
                         info!("WebSocket connection closed normally");
                         reconnect_count = 0;
@@ -129,8 +141,12 @@ impl WebSocketManager {
         url: &str,
         state: &Arc<RwLock<ConnectionState>>,
         event_tx: &broadcast::Sender<Event>,
+        chaos: Option<&FaultInjector>,
     ) -> Result<()> {
         let parsed_url = Url::parse(url).context("Invalid WebSocket URL")?;
+        if let Some(chaos) = chaos {
+            chaos.before_call("websocket_connect").await?;
+        }
         
         let (ws_stream, _) = timeout(Duration::from_secs(10), connect_async(parsed_url))
             .await
@@ -154,6 +170,11 @@ impl WebSocketManager {
                 msg = read.next() => {
                     match msg {
                         Some(Ok(Message::Text(text))) => {
+                            if chaos.map_or(false, FaultInjector::drop_connection) {
+                                warn!("Chaos: dropping WebSocket connection");
+                                let _ = event_tx.send(Event::WebSocketDisconnected);
+                                anyhow::bail!("chaos: injected WebSocket disconnect");
+                            }
                             debug!("WebSocket message: {} bytes", text.len());
                             let _ = event_tx.send(Event::WebSocketMessage(text));
                         }
@@ -233,6 +254,12 @@ impl SolanaWebSocket {
         }
     }
     
+    /// Fail connects and drop connections through a fault injector
+    pub fn with_chaos(mut self, chaos: Option<Arc<FaultInjector>>) -> Self {
+        self.manager = self.manager.with_chaos(chaos);
+        self
+    }
+    
     /// Start connection
     pub async fn start(&self) -> Result<()> {
         self.manager.start().await
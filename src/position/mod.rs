@@ -5,11 +5,20 @@
 //! - Realized and unrealized P&L
 //! - Entry/exit price tracking
 //! - Paper trading simulation
+//! - Paper fills applied to the spot inventory, when a treasury is attached
+//! - Resting perp order fills applied to the perp leg as they arrive, with
+//!   the spot leg opened once the order has filled
+//! - Confirmed live fills applied to either leg, so the legs follow what
+//!   the venues executed
 
+use anyhow::Result;
+use async_trait::async_trait;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{info, debug};
+use tracing::{info, debug, warn};
 
+use crate::execution::reconcile::{LegExecutor, LegFills, LegTrade};
+use crate::execution::{OrderFill, Treasury};
 use crate::state::SharedState;
 
 /// Spot position
@@ -77,6 +86,8 @@ pub struct PositionManager {
     realized_pnl: RwLock<f64>,
     /// Trade history
     trade_history: RwLock<Vec<TradeRecord>>,
+    /// Spot inventory moved by paper fills
+    treasury: Option<Arc<Treasury>>,
 }
 
 /// Trade record
@@ -107,13 +118,23 @@ impl PositionManager {
             perp: RwLock::new(None),
             realized_pnl: RwLock::new(0.0),
             trade_history: RwLock::new(Vec::new()),
+            treasury: None,
         }
     }
     
+    /// Fund paper entries from, and return paper closes to, this inventory
+    pub fn with_treasury(mut self, treasury: Arc<Treasury>) -> Self {
+        self.treasury = Some(treasury);
+        self
+    }
+    
     /// Simulate opening a position (paper trading)
     pub async fn simulate_open(&self, spot_price: f64, size: f64) {
         let now = chrono::Utc::now().timestamp_millis();
         
+        // Fund before the spot leg is committed, so it is not counted as idle
+        self.fund_paper_entry(size, spot_price);
+        
         // Open spot long
         *self.spot.write().await = Some(SpotPosition {
             size,
@@ -167,6 +188,182 @@ impl PositionManager {
         );
     }
     
+    /// Simulate adding to the open position (paper trading), blending entry prices
+    pub async fn simulate_add(&self, spot_price: f64, size: f64) {
+        if !self.has_position().await {
+            return self.simulate_open(spot_price, size).await;
+        }
+        let now = chrono::Utc::now().timestamp_millis();
+        self.fund_paper_entry(size, spot_price);
+        let perp_price = self.state.perp_mark_price.load();
+        
+        if let Some(spot) = self.spot.write().await.as_mut() {
+            spot.entry_price = (spot.entry_price * spot.size + spot_price * size) / (spot.size + size);
+            spot.size += size;
+            spot.current_value = spot.size * spot_price;
+            if let Some(position) = self.state.spot_position.write().as_mut() {
+                position.size = spot.size;
+                position.entry_price = spot.entry_price;
+            }
+        }
+        
+        if let Some(perp) = self.perp.write().await.as_mut() {
+            let held = perp.size.abs();
+            perp.entry_price = (perp.entry_price * held + perp_price * size) / (held + size);
+            perp.size -= size; // Short grows more negative
+            if let Some(position) = self.state.perp_position.write().as_mut() {
+                position.size = perp.size.abs();
+                position.entry_price = perp.entry_price;
+            }
+        }
+        
+        self.record_trade(TradeRecord {
+            timestamp: now,
+            side: "ADD".to_string(),
+            size,
+            price: spot_price,
+            pnl: 0.0,
+            trade_type: TradeType::Open,
+        }).await;
+        
+        info!(
+            "Added to position: {:.4} SOL @ ${:.2} spot, short perp @ ${:.2}",
+            size, spot_price, perp_price
+        );
+    }
+    
+    /// Apply a fill on a resting perp order to the perp leg, blending its entry price
+    pub async fn apply_perp_fill(&self, fill: &OrderFill) {
+        let delta = fill.signed_size();
+        let mut perp = self.perp.write().await;
+        let leg = perp.get_or_insert_with(|| PerpPosition { entry_time: fill.timestamp, ..Default::default() });
+        let held = leg.size.abs();
+        if leg.size == 0.0 || leg.size.signum() == delta.signum() {
+            leg.entry_price = (leg.entry_price * held + fill.price * fill.size) / (held + fill.size);
+        }
+        leg.size += delta;
+        leg.mark_price = self.state.perp_mark_price.load();
+        
+        if leg.size.abs() < 1e-9 {
+            *perp = None;
+            *self.state.perp_position.write() = None;
+        } else {
+            *self.state.perp_position.write() = Some(crate::utils::types::Position {
+                size: leg.size.abs(),
+                entry_price: leg.entry_price,
+                side: if leg.size < 0.0 {
+                    crate::utils::types::PositionSide::Short
+                } else {
+                    crate::utils::types::PositionSide::Long
+                },
+                timestamp: leg.entry_time,
+                unrealized_pnl: leg.unrealized_pnl,
+            });
+        }
+        drop(perp);
+        
+        self.record_trade(TradeRecord {
+            timestamp: fill.timestamp,
+            side: "PERP_FILL".to_string(),
+            size: fill.size,
+            price: fill.price,
+            pnl: 0.0,
+            trade_type: TradeType::Open,
+        }).await;
+        debug!("Perp order {} filled {:.4} SOL @ ${:.4}", fill.order_id, delta, fill.price);
+    }
+    
+    /// Open (or add to) the spot leg of a paper entry whose perp leg filled
+    /// as a resting order
+    pub async fn open_spot_leg(&self, spot_price: f64, size: f64) {
+        let now = chrono::Utc::now().timestamp_millis();
+        self.fund_paper_entry(size, spot_price);
+        
+        let mut spot = self.spot.write().await;
+        let leg = spot.get_or_insert_with(|| SpotPosition { entry_time: now, ..Default::default() });
+        leg.entry_price = (leg.entry_price * leg.size + spot_price * size) / (leg.size + size);
+        leg.size += size;
+        leg.current_value = leg.size * spot_price;
+        *self.state.spot_position.write() = Some(crate::utils::types::Position {
+            size: leg.size,
+            entry_price: leg.entry_price,
+            side: crate::utils::types::PositionSide::Long,
+            timestamp: leg.entry_time,
+            unrealized_pnl: leg.unrealized_pnl,
+        });
+        drop(spot);
+        
+        self.record_trade(TradeRecord {
+            timestamp: now,
+            side: "OPEN".to_string(),
+            size,
+            price: spot_price,
+            pnl: 0.0,
+            trade_type: TradeType::Open,
+        }).await;
+        info!("Opened spot leg: {:.4} SOL @ ${:.2}", size, spot_price);
+    }
+    
+    /// Apply a confirmed live spot fill (signed SOL) to the spot leg, opening
+    /// it from flat, blending its entry price when adding and clearing it
+    /// once flat
+    pub async fn apply_spot_fill(&self, filled: f64, price: f64) {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut spot = self.spot.write().await;
+        let leg = spot.get_or_insert_with(|| SpotPosition { entry_time: now, ..Default::default() });
+        if filled > 0.0 {
+            leg.entry_price = (leg.entry_price * leg.size + price * filled) / (leg.size + filled);
+        }
+        leg.size += filled;
+        leg.current_value = leg.size * price;
+        
+        if leg.size.abs() < 1e-9 {
+            *spot = None;
+            *self.state.spot_position.write() = None;
+        } else {
+            *self.state.spot_position.write() = Some(crate::utils::types::Position {
+                size: leg.size,
+                entry_price: leg.entry_price,
+                side: crate::utils::types::PositionSide::Long,
+                timestamp: leg.entry_time,
+                unrealized_pnl: leg.unrealized_pnl,
+            });
+        }
+        drop(spot);
+        
+        self.record_trade(TradeRecord {
+            timestamp: now,
+            side: "SPOT_FILL".to_string(),
+            size: filled.abs(),
+            price,
+            pnl: 0.0,
+            trade_type: if filled > 0.0 { TradeType::Open } else { TradeType::Close },
+        }).await;
+        debug!("Spot filled {:.4} SOL @ ${:.4}", filled, price);
+    }
+    
+    /// Charge an execution cost (USD) to realized P&L
+    pub async fn charge_cost(&self, cost_usd: f64) {
+        *self.realized_pnl.write().await -= cost_usd;
+        self.state.realized_pnl.fetch_add(-cost_usd);
+    }
+    
+    /// Fund a paper entry from the treasury's inventory, if one is attached
+    fn fund_paper_entry(&self, size: f64, spot_price: f64) {
+        let Some(treasury) = &self.treasury else {
+            return;
+        };
+        let funding = treasury.plan_entry(size, spot_price);
+        if !funding.is_funded() {
+            warn!("Paper entry short of ${:.2} USDC; filling anyway", funding.usdc_shortfall);
+        }
+        treasury.apply_paper_open(&funding);
+        debug!(
+            "Entry funded in {:?}: {:.4} SOL from inventory, {:.4} SOL bought for ${:.2}",
+            funding.currency, funding.from_inventory_sol, funding.buy_sol, funding.usdc_cost
+        );
+    }
+    
     /// Simulate closing a position (paper trading)
     pub async fn simulate_close(&self, current_price: f64) -> f64 {
         let now = chrono::Utc::now().timestamp_millis();
@@ -176,6 +373,9 @@ impl PositionManager {
         if let Some(spot) = self.spot.read().await.as_ref() {
             let spot_pnl = (current_price - spot.entry_price) * spot.size;
             total_pnl += spot_pnl;
+            if let Some(treasury) = &self.treasury {
+                treasury.apply_paper_close(spot.size, current_price);
+            }
             
             self.record_trade(TradeRecord {
                 timestamp: now,
@@ -213,7 +413,7 @@ impl PositionManager {
         
         // Update realized P&L
         *self.realized_pnl.write().await += total_pnl;
-        self.state.realized_pnl.store(self.state.realized_pnl.load() + total_pnl);
+        self.state.realized_pnl.fetch_add(total_pnl);
         
         info!("Closed position with P&L: ${:.2}", total_pnl);
         
@@ -222,6 +422,11 @@ impl PositionManager {
     
     /// Adjust positions (for rebalancing)
     pub async fn adjust_positions(&self, spot_delta: f64, perp_delta: f64) {
+        self.adjust_positions_with_cost(spot_delta, perp_delta, 0.0).await;
+    }
+    
+    /// Adjust positions, charging the execution cost to realized P&L
+    pub async fn adjust_positions_with_cost(&self, spot_delta: f64, perp_delta: f64, cost_usd: f64) {
         let now = chrono::Utc::now().timestamp_millis();
         
         // Adjust spot
@@ -236,21 +441,27 @@ impl PositionManager {
             debug!("Adjusted perp by {:.4}, new size: {:.4}", perp_delta, perp.size);
         }
         
+        if cost_usd != 0.0 {
+            self.charge_cost(cost_usd).await;
+            debug!("Rebalance cost: ${:.4}", cost_usd);
+        }
+        
         // Record rebalance
         self.record_trade(TradeRecord {
             timestamp: now,
             side: "REBALANCE".to_string(),
-            size: spot_delta.abs(),
+            size: spot_delta.abs().max(perp_delta.abs()),
             price: self.state.spot_price.load(),
-            pnl: 0.0,
+            pnl: -cost_usd,
             trade_type: TradeType::Rebalance,
         }).await;
     }
     
     /// Update unrealized P&L based on current prices
     pub async fn update_pnl(&self) {
-        let spot_price = self.state.spot_price.load();
-        let perp_price = self.state.perp_mark_price.load();
+        // One snapshot, so both legs are marked at the same instant
+        let market = self.state.snapshot();
+        let (spot_price, perp_price) = (market.spot_price, market.perp_mark_price);
         let mut total_unrealized = 0.0;
         
         // Update spot
@@ -306,6 +517,14 @@ impl PositionManager {
         }
     }
     
+    /// Signed leg sizes (spot long positive, perp short negative)
+    pub async fn leg_fills(&self) -> LegFills {
+        LegFills::new(
+            self.spot.read().await.as_ref().map(|s| s.size).unwrap_or(0.0),
+            self.perp.read().await.as_ref().map(|p| p.size).unwrap_or(0.0),
+        )
+    }
+    
     /// Has open position
     pub async fn has_position(&self) -> bool {
         self.spot.read().await.is_some() || self.perp.read().await.is_some()
@@ -338,6 +557,24 @@ impl PositionManager {
     }
 }
 
+/// Paper execution of single-leg corrections
+#[async_trait]
+impl LegExecutor for PositionManager {
+    async fn trade_spot(&self, size: f64) -> Result<LegTrade> {
+        self.adjust_positions(size, 0.0).await;
+        Ok(LegTrade::at_reference(size, self.state.spot_price.load()))
+    }
+    
+    async fn trade_perp(&self, size: f64) -> Result<LegTrade> {
+        self.adjust_positions(0.0, size).await;
+        Ok(LegTrade::at_reference(size, self.state.perp_mark_price.load()))
+    }
+    
+    async fn leg_fills(&self) -> Result<LegFills> {
+        Ok(PositionManager::leg_fills(self).await)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
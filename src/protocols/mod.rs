@@ -1,8 +1,15 @@
-//! Protocol integrations module - Phase 4
+//! Protocol Integrations
 //!
-//! This module will contain:
-//! - Drift Protocol SDK integration
-//! - Jupiter swap execution
-//! - Mango Markets integration (optional)
+//! Where venues and oracles are plugged in:
+//! - `registry`: the `VenueRegistry` building the configured oracles, spot
+//!   feeds and venues, perp feeds and venues from `ProtocolsConfig`
+//!
+//! The integrations themselves live with their kind: market data in
+//! `feeds`, order placement in `execution`.
+
+pub mod registry;
 
-// TODO: Phase 4 implementation
+pub use registry::{
+    FeedContext, FeedRegistration, PerpVenueRegistration, Registration, SpotVenueRegistration, VenueContext,
+    VenueRegistry,
+};
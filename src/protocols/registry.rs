@@ -0,0 +1,292 @@
+//! Venue Registry
+//!
+//! One table per kind of integration, each entry naming the venue, whether
+//! the configuration enables it and how to build it:
+//! - Oracles: spot price sources aggregated into the composite price
+//! - Spot feeds: SOL/USDC quote sources
+//! - Spot venues: where the spot leg is swapped
+//! - Perp feeds: perp marks, funding and book depth
+//! - Perp venues: where the short leg is priced and placed
+//!
+//! A new integration adds its `ProtocolsConfig` section and one entry in
+//! `VenueRegistry::default`; the feed manager, the funding engine and the
+//! spot and perp routers take whatever the registry builds.
+
+use anyhow::{Context, Result};
+use solana_sdk::signature::Keypair;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::config::{AppConfig, ProtocolsConfig, SyntheticFundingConfig};
+use crate::execution::{
+    DriftVenue, HyperliquidVenue, JupiterClient, JupiterPerpsVenue, JupiterSpot, NonceManager,
+    OrcaWhirlpoolVenue, PerpVenue, SpotVenue, TransactionBuilder, TransactionSubmitter,
+};
+use crate::feeds::{
+    DlobFeed, DriftFeed, JupiterFeed, MarketFeed, PythFeed, SwitchboardFeed, SyntheticFundingFeed,
+};
+use crate::network::event_bus::Event;
+use crate::network::{HttpRetryPolicy, RpcManager};
+use crate::state::SharedState;
+use crate::utils::types::Dependency;
+
+/// What market data feeds are built from
+#[derive(Clone)]
+pub struct FeedContext {
+    pub protocols: ProtocolsConfig,
+    pub event_tx: broadcast::Sender<Event>,
+    pub retry: HttpRetryPolicy,
+    /// Generated funding replacing Drift's (devnet)
+    pub synthetic_funding: Option<SyntheticFundingConfig>,
+}
+
+/// What spot and perp venues are built from
+pub struct VenueContext {
+    pub config: Arc<AppConfig>,
+    pub state: Arc<SharedState>,
+    pub rpc: Arc<RpcManager>,
+    /// Wallet placing orders; None for read-only venues
+    pub payer: Option<Keypair>,
+    /// Durable nonce for live reduce-only orders
+    pub nonce: Option<Arc<NonceManager>>,
+}
+
+impl VenueContext {
+    fn payer(&self) -> Option<Keypair> {
+        self.payer.as_ref().map(|k| k.insecure_clone())
+    }
+}
+
+/// A registered integration
+pub struct Registration<C, T: ?Sized> {
+    pub name: &'static str,
+    /// Whether the configuration turns it on
+    pub enabled: fn(&C) -> bool,
+    pub build: fn(&C) -> Result<Arc<T>>,
+}
+
+impl<C, T: ?Sized> Registration<C, T> {
+    pub fn new(name: &'static str, enabled: fn(&C) -> bool, build: fn(&C) -> Result<Arc<T>>) -> Self {
+        Self { name, enabled, build }
+    }
+}
+
+pub type FeedRegistration = Registration<FeedContext, dyn MarketFeed>;
+pub type SpotVenueRegistration = Registration<VenueContext, dyn SpotVenue>;
+pub type PerpVenueRegistration = Registration<VenueContext, dyn PerpVenue>;
+
+/// Integrations by kind, in start order
+pub struct VenueRegistry {
+    oracles: Vec<FeedRegistration>,
+    spot_feeds: Vec<FeedRegistration>,
+    spot_venues: Vec<SpotVenueRegistration>,
+    perp_feeds: Vec<FeedRegistration>,
+    perp_venues: Vec<PerpVenueRegistration>,
+}
+
+impl Default for VenueRegistry {
+    /// The built-in integrations
+    fn default() -> Self {
+        Self {
+            oracles: vec![
+                Registration::new("pyth", always, pyth),
+                Registration::new("switchboard", |ctx| ctx.protocols.switchboard.enabled, switchboard),
+            ],
+            spot_feeds: vec![Registration::new("jupiter", always, jupiter)],
+            spot_venues: vec![
+                Registration::new("jupiter", always, jupiter_spot),
+                Registration::new("orca", |ctx| ctx.config.protocols.orca.enabled, orca),
+            ],
+            perp_feeds: vec![
+                Registration::new("drift", always, drift_feed),
+                Registration::new("drift_dlob", always, drift_dlob),
+                Registration::new("synthetic_funding", |ctx| ctx.synthetic_funding.is_some(), synthetic_funding),
+            ],
+            perp_venues: vec![
+                Registration::new("drift", always, drift_venue),
+                Registration::new("hyperliquid", |ctx| ctx.config.protocols.hyperliquid.enabled, hyperliquid),
+                Registration::new("jupiter_perps", |ctx| ctx.config.protocols.jupiter_perps.enabled, jupiter_perps),
+            ],
+        }
+    }
+}
+
+fn always<C>(_: &C) -> bool {
+    true
+}
+
+fn pyth(ctx: &FeedContext) -> Result<Arc<dyn MarketFeed>> {
+    Ok(Arc::new(PythFeed::new(&ctx.protocols.pyth, ctx.event_tx.clone()).with_retry_policy(ctx.retry.clone())))
+}
+
+fn switchboard(ctx: &FeedContext) -> Result<Arc<dyn MarketFeed>> {
+    Ok(Arc::new(
+        SwitchboardFeed::new(&ctx.protocols.switchboard, ctx.event_tx.clone()).with_retry_policy(ctx.retry.clone()),
+    ))
+}
+
+fn jupiter(ctx: &FeedContext) -> Result<Arc<dyn MarketFeed>> {
+    Ok(Arc::new(
+        JupiterFeed::new(&ctx.protocols.jupiter, ctx.event_tx.clone())
+            .with_retry_policy(ctx.retry.clone().for_dependency(Dependency::Jupiter)),
+    ))
+}
+
+fn drift_feed(ctx: &FeedContext) -> Result<Arc<dyn MarketFeed>> {
+    let feed = DriftFeed::new(&ctx.protocols.drift, ctx.event_tx.clone())
+        .with_retry_policy(ctx.retry.clone().for_dependency(Dependency::DriftData));
+    // Synthetic funding replaces Drift's
+    Ok(Arc::new(if ctx.synthetic_funding.is_some() { feed.without_funding() } else { feed }))
+}
+
+fn drift_dlob(ctx: &FeedContext) -> Result<Arc<dyn MarketFeed>> {
+    Ok(Arc::new(DlobFeed::new(&ctx.protocols.drift, ctx.event_tx.clone()).with_retry_policy(ctx.retry.clone())))
+}
+
+fn synthetic_funding(ctx: &FeedContext) -> Result<Arc<dyn MarketFeed>> {
+    let config = ctx.synthetic_funding.as_ref().context("Synthetic funding is not configured")?;
+    Ok(Arc::new(SyntheticFundingFeed::new(config, ctx.event_tx.clone())))
+}
+
+fn jupiter_spot(ctx: &VenueContext) -> Result<Arc<dyn SpotVenue>> {
+    let retry = HttpRetryPolicy::new(&ctx.config.http).for_dependency(Dependency::Jupiter);
+    Ok(Arc::new(JupiterSpot::new(JupiterClient::new(&ctx.config.protocols.jupiter)?.with_retry_policy(retry))))
+}
+
+fn orca(ctx: &VenueContext) -> Result<Arc<dyn SpotVenue>> {
+    Ok(Arc::new(OrcaWhirlpoolVenue::new(
+        &ctx.config.protocols.orca,
+        &ctx.config.protocols.jupiter,
+        ctx.rpc.clone(),
+        TransactionBuilder::new(ctx.config.clone(), ctx.rpc.clone())?,
+    )?))
+}
+
+fn drift_venue(ctx: &VenueContext) -> Result<Arc<dyn PerpVenue>> {
+    Ok(Arc::new(DriftVenue::new(
+        ctx.config.clone(),
+        ctx.state.clone(),
+        ctx.rpc.clone(),
+        TransactionBuilder::new(ctx.config.clone(), ctx.rpc.clone())?.with_nonce(ctx.nonce.clone()),
+        TransactionSubmitter::new(ctx.config.clone(), ctx.rpc.clone()).with_nonce(ctx.nonce.clone()),
+        ctx.payer(),
+    )))
+}
+
+fn hyperliquid(ctx: &VenueContext) -> Result<Arc<dyn PerpVenue>> {
+    Ok(Arc::new(HyperliquidVenue::new(&ctx.config.protocols.hyperliquid)?))
+}
+
+fn jupiter_perps(ctx: &VenueContext) -> Result<Arc<dyn PerpVenue>> {
+    Ok(Arc::new(JupiterPerpsVenue::new(
+        &ctx.config.protocols.jupiter_perps,
+        ctx.state.clone(),
+        ctx.rpc.clone(),
+        TransactionBuilder::new(ctx.config.clone(), ctx.rpc.clone())?,
+        TransactionSubmitter::new(ctx.config.clone(), ctx.rpc.clone()),
+        ctx.payer(),
+    )?))
+}
+
+fn build_enabled<C, T: ?Sized>(registrations: &[Registration<C, T>], ctx: &C) -> Result<Vec<Arc<T>>> {
+    registrations
+        .iter()
+        .filter(|r| (r.enabled)(ctx))
+        .map(|r| (r.build)(ctx))
+        .collect()
+}
+
+fn enabled_names<C, T: ?Sized>(registrations: &[Registration<C, T>], ctx: &C) -> Vec<&'static str> {
+    registrations.iter().filter(|r| (r.enabled)(ctx)).map(|r| r.name).collect()
+}
+
+impl VenueRegistry {
+    pub fn register_oracle(&mut self, registration: FeedRegistration) {
+        self.oracles.push(registration);
+    }
+
+    pub fn register_spot_feed(&mut self, registration: FeedRegistration) {
+        self.spot_feeds.push(registration);
+    }
+
+    pub fn register_spot_venue(&mut self, registration: SpotVenueRegistration) {
+        self.spot_venues.push(registration);
+    }
+
+    pub fn register_perp_feed(&mut self, registration: FeedRegistration) {
+        self.perp_feeds.push(registration);
+    }
+
+    pub fn register_perp_venue(&mut self, registration: PerpVenueRegistration) {
+        self.perp_venues.push(registration);
+    }
+
+    /// Enabled oracles, spot feeds and perp feeds, in that order
+    pub fn feeds(&self, ctx: &FeedContext) -> Result<Vec<Arc<dyn MarketFeed>>> {
+        let mut feeds = build_enabled(&self.oracles, ctx)?;
+        feeds.extend(build_enabled(&self.spot_feeds, ctx)?);
+        feeds.extend(build_enabled(&self.perp_feeds, ctx)?);
+        Ok(feeds)
+    }
+
+    /// Enabled spot venues, Jupiter first
+    pub fn spot_venues(&self, ctx: &VenueContext) -> Result<Vec<Arc<dyn SpotVenue>>> {
+        build_enabled(&self.spot_venues, ctx)
+    }
+
+    /// Enabled perp venues, Drift first
+    pub fn perp_venues(&self, ctx: &VenueContext) -> Result<Vec<Arc<dyn PerpVenue>>> {
+        build_enabled(&self.perp_venues, ctx)
+    }
+
+    /// Names of the enabled oracles
+    pub fn oracle_names(&self, ctx: &FeedContext) -> Vec<&'static str> {
+        enabled_names(&self.oracles, ctx)
+    }
+
+    /// Names of the enabled spot feeds
+    pub fn spot_feed_names(&self, ctx: &FeedContext) -> Vec<&'static str> {
+        enabled_names(&self.spot_feeds, ctx)
+    }
+
+    /// Names of the enabled spot venues
+    pub fn spot_venue_names(&self, ctx: &VenueContext) -> Vec<&'static str> {
+        enabled_names(&self.spot_venues, ctx)
+    }
+
+    /// Names of the enabled perp feeds
+    pub fn perp_feed_names(&self, ctx: &FeedContext) -> Vec<&'static str> {
+        enabled_names(&self.perp_feeds, ctx)
+    }
+
+    /// Names of the enabled perp venues
+    pub fn perp_venue_names(&self, ctx: &VenueContext) -> Vec<&'static str> {
+        enabled_names(&self.perp_venues, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_follows_config() {
+        let config = AppConfig::default_for_test();
+        let (event_tx, _) = broadcast::channel(16);
+        let mut ctx = FeedContext {
+            protocols: config.protocols.clone(),
+            event_tx,
+            retry: HttpRetryPolicy::default(),
+            synthetic_funding: None,
+        };
+        let registry = VenueRegistry::default();
+        assert_eq!(registry.spot_feed_names(&ctx), vec!["jupiter"]);
+        assert_eq!(registry.perp_feed_names(&ctx), vec!["drift", "drift_dlob"]);
+
+        ctx.protocols.switchboard.enabled = true;
+        ctx.synthetic_funding = Some(SyntheticFundingConfig::default());
+        assert_eq!(registry.oracle_names(&ctx), vec!["pyth", "switchboard"]);
+        assert_eq!(registry.perp_feed_names(&ctx), vec!["drift", "drift_dlob", "synthetic_funding"]);
+        assert_eq!(registry.feeds(&ctx).unwrap().len(), 6);
+    }
+}
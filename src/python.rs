@@ -0,0 +1,200 @@
+//! Python Bindings
+//!
+//! With the `python` feature the library is also a Python extension module,
+//! `sol_basis_bot` (`maturin develop --release`, see `pyproject.toml`), for
+//! parameter sweeps and plots in notebooks while execution stays in Rust:
+//! - `run_scenario(scenario, config, overrides)` - play a scenario file or
+//!   canned scenario through a paper agent, as `sol-basis-bot scenario` does;
+//!   `overrides` is merged over the scenario's own config overrides
+//! - `canned_scenarios()` - names of the scenarios shipped with the bot
+//! - `evaluate_signal(spot, perp, funding_rate, config, overrides)` - the
+//!   signal engine's evaluation of one market reading, flat and with no
+//!   engine history
+//! - `PerformanceDb(path)` - metrics, trades, daily equity and the daily
+//!   ledger of a performance database
+//!
+//! Results are plain dicts and lists with the field names of the JSON API.
+//! Calls release the GIL and block on a runtime shared by the module.
+
+use pyo3::prelude::*;
+use pythonize::{depythonize_bound, pythonize};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use tokio::runtime::Runtime;
+
+use crate::agentic::{DailyLedger, PerformanceDb};
+use crate::config::{layers, AppConfig};
+use crate::engines::SignalEngine;
+use crate::feeds::{MarketTick, MockFeed};
+use crate::network::EventBus;
+use crate::scenario::{self, Scenario};
+use crate::state::SharedState;
+
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| Runtime::new().expect("Failed to start the tokio runtime"))
+}
+
+fn to_py<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    Ok(pythonize(py, value)?)
+}
+
+/// YAML value of an optional Python dict
+fn yaml_overrides(overrides: Option<&Bound<'_, PyAny>>) -> PyResult<serde_yaml::Value> {
+    Ok(match overrides {
+        Some(obj) => depythonize_bound(obj.clone())?,
+        None => serde_yaml::Value::Null,
+    })
+}
+
+/// The config at `path` with `overrides` merged in
+fn load_config(path: &str, overrides: serde_yaml::Value) -> anyhow::Result<AppConfig> {
+    let config = AppConfig::load(Path::new(path))?;
+    if overrides.is_null() {
+        return Ok(config);
+    }
+    let mut value = serde_yaml::to_value(&config)?;
+    layers::merge(&mut value, overrides);
+    let config: AppConfig = serde_yaml::from_value(value)
+        .map_err(|e| anyhow::anyhow!("Invalid config overrides: {}", e))?;
+    config.validate()?;
+    Ok(config)
+}
+
+/// Run a scenario (file path or canned name) and return its report
+#[pyfunction]
+#[pyo3(signature = (scenario, config = "config.yaml", overrides = None))]
+fn run_scenario(
+    py: Python<'_>,
+    scenario: &str,
+    config: &str,
+    overrides: Option<&Bound<'_, PyAny>>,
+) -> PyResult<PyObject> {
+    let base = AppConfig::load(Path::new(config))?;
+    let mut scenario = match Scenario::canned_named(scenario) {
+        Some(canned) => canned,
+        None => Scenario::load(Path::new(scenario))?,
+    };
+    layers::merge(&mut scenario.config, yaml_overrides(overrides)?);
+    let report = py.allow_threads(|| runtime().block_on(scenario::run(&scenario, &base)))?;
+    to_py(py, &report)
+}
+
+#[pyfunction]
+fn canned_scenarios() -> Vec<String> {
+    Scenario::canned().into_iter().map(|s| s.name).collect()
+}
+
+/// Evaluate entry conditions for one market reading
+#[pyfunction]
+#[pyo3(signature = (spot, perp, funding_rate, config = "config.yaml", overrides = None))]
+fn evaluate_signal(
+    py: Python<'_>,
+    spot: f64,
+    perp: f64,
+    funding_rate: f64,
+    config: &str,
+    overrides: Option<&Bound<'_, PyAny>>,
+) -> PyResult<PyObject> {
+    let config = Arc::new(load_config(config, yaml_overrides(overrides)?)?);
+    let state = Arc::new(SharedState::with_funding_interval(config.protocols.drift.funding_interval));
+    let tick = MarketTick { spot, perp, funding_rate: Some(funding_rate), spot_stale: false };
+    MockFeed::apply(&state, &EventBus::new(16).sender(), &tick);
+    let evaluation = py.allow_threads(|| runtime().block_on(SignalEngine::evaluate(&config, &state)));
+    to_py(py, &evaluation)
+}
+
+/// Read-only queries on a performance database
+#[pyclass(name = "PerformanceDb")]
+struct PyPerformanceDb {
+    db: Arc<PerformanceDb>,
+}
+
+#[pymethods]
+impl PyPerformanceDb {
+    #[new]
+    fn new(py: Python<'_>, path: &str) -> PyResult<Self> {
+        let db = py.allow_threads(|| runtime().block_on(PerformanceDb::new(path)))?;
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    fn metrics(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let metrics = py.allow_threads(|| runtime().block_on(self.db.get_metrics()));
+        to_py(py, &metrics)
+    }
+
+    /// Closed trades, optionally those closed within `start..=end` (ms)
+    #[pyo3(signature = (start = None, end = None))]
+    fn trades(&self, py: Python<'_>, start: Option<i64>, end: Option<i64>) -> PyResult<PyObject> {
+        let trades = py.allow_threads(|| {
+            runtime().block_on(self.db.get_trades_in_range(start.unwrap_or(0), end.unwrap_or(i64::MAX)))
+        });
+        to_py(py, &trades)
+    }
+
+    /// Equity at the end of each day, for equity curves
+    fn daily_equity(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let equity = py.allow_threads(|| runtime().block_on(self.db.get_daily_equity()));
+        to_py(py, &equity)
+    }
+
+    /// Daily P&L rows; the saved ledger, or one rebuilt in `timezone` (an
+    /// IANA name, UTC without a saved ledger)
+    #[pyo3(signature = (timezone = None))]
+    fn daily_ledger(&self, py: Python<'_>, timezone: Option<&str>) -> PyResult<PyObject> {
+        let tz = timezone
+            .map(|name| name.parse::<chrono_tz::Tz>().map_err(|e| anyhow::anyhow!("Invalid timezone: {}", e)))
+            .transpose()?;
+        let ledger = py.allow_threads(|| {
+            runtime().block_on(async {
+                let saved = match tz {
+                    Some(_) => None,
+                    None => self.db.load_daily_ledger().await?,
+                };
+                let ledger = match saved {
+                    Some(ledger) => ledger,
+                    None => DailyLedger::rebuild(&self.db.get_all_trades().await, tz.unwrap_or(chrono_tz::UTC)),
+                };
+                anyhow::Ok(ledger)
+            })
+        })?;
+        to_py(py, &ledger)
+    }
+}
+
+#[pymodule]
+fn sol_basis_bot(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(run_scenario, m)?)?;
+    m.add_function(wrap_pyfunction!(canned_scenarios, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_signal, m)?)?;
+    m.add_class::<PyPerformanceDb>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    #[test]
+    fn test_evaluate_signal_applies_overrides() {
+        pyo3::prepare_freethreaded_python();
+        let config = concat!(env!("CARGO_MANIFEST_DIR"), "/config.yaml");
+        Python::with_gil(|py| {
+            let evaluate = |overrides: Option<&Bound<'_, PyAny>>| {
+                let evaluation = evaluate_signal(py, 150.0, 151.0, 0.0002, config, overrides).unwrap();
+                let evaluation = evaluation.bind(py).downcast::<PyDict>().unwrap().clone();
+                evaluation.get_item("should_open").unwrap().unwrap().extract::<bool>().unwrap()
+            };
+            assert!(evaluate(None));
+
+            // A basis minimum far above the reading blocks the entry
+            let trading = PyDict::new_bound(py);
+            trading.set_item("min_basis_spread_pct", 50.0).unwrap();
+            let overrides = PyDict::new_bound(py);
+            overrides.set_item("trading", trading).unwrap();
+            assert!(!evaluate(Some(overrides.as_any())));
+        });
+    }
+}
@@ -0,0 +1,387 @@
+//! Stress Scenarios
+//!
+//! A small YAML format describing timed market paths and infrastructure
+//! events, played through the mock feed into a paper-trading agent:
+//! - `start`: spot, basis, funding rate and USDC peg at t=0
+//! - `steps`: at a time, jump (`basis_pct: 1.2`) or ramp
+//!   (`basis_pct: { to: 1.6, over: 10m }`) a market knob; inject an entry
+//!   signal; take RPC down or up; stall or restore the spot oracle
+//! - `expect`: agent states that must be reached (or never entered) within
+//!   a time window, and the state at the end of the run
+//! - `config`: overrides merged into the base config for this scenario
+//! - `replay`: a history CSV (see `state::history_import`), played from its
+//!   first spot sample as the market path in place of `start` and the
+//!   spot, basis and funding knobs; `duration` still bounds the run
+//!
+//! Times are seconds or strings like `90s`, `10m`, `1h`. Scenario time runs
+//! `speed` times faster than the wall clock. Analytics that sample on the
+//! wall clock (funding history, reversal detection) only see real time, so
+//! scenarios relying on them should run at `speed: 1`.
+//!
+//! ```yaml
+//! name: basis_convergence
+//! speed: 60
+//! duration: 10m
+//! start: { spot: 150, basis_pct: 0.6, funding_rate: 0.0002 }
+//! steps:
+//!   - at: 0s
+//!     signal: open
+//!   - at: 3m
+//!     basis_pct: { to: 0.02, over: 3m }
+//! expect:
+//!   - reach: Closing
+//!     by: 8m
+//!   - end: Idle
+//! ```
+
+pub mod replay;
+pub mod runner;
+
+pub use replay::{ReplayReport, Session, SessionRecorder};
+pub use runner::{run, ScenarioReport, TimelineEntry};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::agent::AgentState;
+use crate::state::history_import::{self, HistoricalSeries};
+
+/// Canned scenarios shipped with the bot
+const CANNED: &[(&str, &str)] = &[
+    ("basis_convergence", include_str!("../../scenarios/basis_convergence.yaml")),
+    ("funding_reversal", include_str!("../../scenarios/funding_reversal.yaml")),
+    ("usdc_depeg", include_str!("../../scenarios/usdc_depeg.yaml")),
+    ("oracle_stall", include_str!("../../scenarios/oracle_stall.yaml")),
+    ("rpc_outage", include_str!("../../scenarios/rpc_outage.yaml")),
+];
+
+/// A duration in scenario seconds, written as a number or `90s` / `10m` / `1h`
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Secs(pub f64);
+
+impl<'de> Deserialize<'de> for Secs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(f64),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(secs) => Ok(Secs(secs)),
+            Raw::Text(text) => parse_secs(&text).map(Secs).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+fn parse_secs(text: &str) -> Result<f64> {
+    let text = text.trim();
+    let (number, unit) = match text.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&text[..i], c),
+        _ => (text, 's'),
+    };
+    let value: f64 = number.trim().parse().with_context(|| format!("Invalid duration {:?}", text))?;
+    let scale = match unit {
+        's' => 1.0,
+        'm' => 60.0,
+        'h' => 3600.0,
+        _ => anyhow::bail!("Invalid duration unit in {:?}", text),
+    };
+    Ok(value * scale)
+}
+
+/// A change to a market knob
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(untagged)]
+pub enum Change {
+    /// Jump to a value
+    Set(f64),
+    /// Move linearly to a value
+    Ramp { to: f64, over: Secs },
+}
+
+/// Market at t=0
+#[derive(Debug, Clone, Deserialize)]
+pub struct MarketStart {
+    /// Spot price (USD)
+    pub spot: f64,
+    /// Perp premium over spot (%)
+    #[serde(default)]
+    pub basis_pct: f64,
+    /// Funding rate per interval
+    #[serde(default)]
+    pub funding_rate: f64,
+    /// USD value of one USDC; prices are quoted in USDC
+    #[serde(default = "default_peg")]
+    pub usdc_peg: f64,
+}
+
+fn default_peg() -> f64 { 1.0 }
+
+impl Default for MarketStart {
+    fn default() -> Self {
+        Self { spot: 0.0, basis_pct: 0.0, funding_rate: 0.0, usdc_peg: default_peg() }
+    }
+}
+
+/// Entry signal injected into the agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignalAction {
+    /// OpenBasis at the current market
+    Open,
+}
+
+/// RPC connectivity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkState {
+    Up,
+    Down,
+}
+
+/// Spot oracle behaviour
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OracleState {
+    Live,
+    /// Spot stops updating
+    Stall,
+}
+
+/// What happens at one point in time
+#[derive(Debug, Clone, Deserialize)]
+pub struct Step {
+    pub at: Secs,
+    #[serde(default)]
+    pub spot: Option<Change>,
+    #[serde(default)]
+    pub basis_pct: Option<Change>,
+    #[serde(default)]
+    pub funding_rate: Option<Change>,
+    #[serde(default)]
+    pub usdc_peg: Option<Change>,
+    #[serde(default)]
+    pub signal: Option<SignalAction>,
+    #[serde(default)]
+    pub rpc: Option<LinkState>,
+    #[serde(default)]
+    pub oracle: Option<OracleState>,
+}
+
+/// An assertion on the agent's behaviour
+#[derive(Debug, Clone, Deserialize)]
+pub struct Expectation {
+    /// State entered at some point in the window
+    #[serde(default)]
+    pub reach: Option<AgentState>,
+    /// State never entered in the window
+    #[serde(default)]
+    pub never: Option<AgentState>,
+    /// State when the run ends
+    #[serde(default)]
+    pub end: Option<AgentState>,
+    /// Window start (default t=0)
+    #[serde(default)]
+    pub after: Option<Secs>,
+    /// Window end (default the whole run)
+    #[serde(default)]
+    pub by: Option<Secs>,
+}
+
+/// A scripted stress scenario
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Scenario seconds per wall-clock second
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    /// Market update interval (scenario time)
+    #[serde(default = "default_tick")]
+    pub tick: Secs,
+    pub duration: Secs,
+    /// Market at t=0; not needed when replaying
+    #[serde(default)]
+    pub start: MarketStart,
+    /// History CSV replayed as the market path, relative to the scenario file
+    #[serde(default)]
+    pub replay: Option<PathBuf>,
+    /// Loaded replay history
+    #[serde(skip)]
+    history: Option<Arc<HistoricalSeries>>,
+    /// Config overrides, merged into the base config
+    #[serde(default)]
+    pub config: serde_yaml::Value,
+    #[serde(default)]
+    pub steps: Vec<Step>,
+    #[serde(default)]
+    pub expect: Vec<Expectation>,
+}
+
+fn default_speed() -> f64 { 60.0 }
+fn default_tick() -> Secs { Secs(10.0) }
+
+/// Market quoted to the bot at one instant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScenarioMarket {
+    /// Spot in USDC
+    pub spot: f64,
+    /// Perp mark in USDC
+    pub perp: f64,
+    pub funding_rate: f64,
+}
+
+impl Scenario {
+    /// Parse and validate a scenario
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        Self::parse(content, Path::new(""))
+    }
+
+    /// Load a scenario file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read scenario: {:?}", path))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&content, dir).with_context(|| format!("Invalid scenario: {:?}", path))
+    }
+
+    /// Parse a scenario, loading its replay relative to `dir`
+    fn parse(content: &str, dir: &Path) -> Result<Self> {
+        let mut scenario: Scenario = serde_yaml::from_str(content).context("Invalid scenario")?;
+        scenario.steps.sort_by(|a, b| a.at.0.total_cmp(&b.at.0));
+        if let Some(replay) = &scenario.replay {
+            let history = history_import::load_csv(&dir.join(replay))?;
+            anyhow::ensure!(!history.spot.is_empty(), "{}: replay has no spot prices", scenario.name);
+            scenario.history = Some(Arc::new(history));
+        }
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// The scenarios shipped in `scenarios/`
+    pub fn canned() -> Vec<Self> {
+        CANNED
+            .iter()
+            .map(|(name, content)| Self::from_yaml(content).unwrap_or_else(|e| panic!("canned scenario {}: {:#}", name, e)))
+            .collect()
+    }
+
+    /// A canned scenario by name
+    pub fn canned_named(name: &str) -> Option<Self> {
+        Self::canned().into_iter().find(|s| s.name == name)
+    }
+
+    fn validate(&self) -> Result<()> {
+        anyhow::ensure!(self.speed > 0.0, "{}: speed must be positive", self.name);
+        anyhow::ensure!(self.tick.0 > 0.0, "{}: tick must be positive", self.name);
+        anyhow::ensure!(self.duration.0 > 0.0, "{}: duration must be positive", self.name);
+        anyhow::ensure!(
+            (self.start.spot > 0.0 || self.history.is_some()) && self.start.usdc_peg > 0.0,
+            "{}: start prices must be positive",
+            self.name
+        );
+        if self.history.is_some() {
+            anyhow::ensure!(
+                self.steps.iter().all(|s| s.spot.is_none() && s.basis_pct.is_none() && s.funding_rate.is_none()),
+                "{}: spot, basis and funding steps cannot be combined with a replay",
+                self.name
+            );
+        }
+        for expectation in &self.expect {
+            let kinds = [expectation.reach, expectation.never, expectation.end].iter().filter(|k| k.is_some()).count();
+            anyhow::ensure!(kinds == 1, "{}: each expectation needs exactly one of reach, never, end", self.name);
+        }
+        Ok(())
+    }
+
+    /// Market at scenario time `t` (seconds)
+    pub fn market_at(&self, t: f64) -> ScenarioMarket {
+        let knob = |start: f64, pick: fn(&Step) -> Option<Change>| {
+            let changes: Vec<(f64, Change)> = self.steps.iter()
+                .filter_map(|s| pick(s).map(|c| (s.at.0, c)))
+                .collect();
+            value_at(start, &changes, t)
+        };
+        let peg = knob(self.start.usdc_peg, |s| s.usdc_peg).max(f64::EPSILON);
+        if let Some(history) = &self.history {
+            let first = history.spot[0].timestamp;
+            let (spot, perp, funding_rate) = history
+                .market_at(first + (t * 1000.0) as i64)
+                .unwrap_or((history.spot[0].value, history.spot[0].value, 0.0));
+            return ScenarioMarket { spot: spot / peg, perp: perp / peg, funding_rate };
+        }
+
+        let spot_usd = knob(self.start.spot, |s| s.spot);
+        let basis_pct = knob(self.start.basis_pct, |s| s.basis_pct);
+        let funding_rate = knob(self.start.funding_rate, |s| s.funding_rate);
+
+        let spot = spot_usd / peg;
+        ScenarioMarket { spot, perp: spot * (1.0 + basis_pct / 100.0), funding_rate }
+    }
+}
+
+/// Value of a knob at `t`; a ramp starts from wherever earlier changes left it
+fn value_at(start: f64, changes: &[(f64, Change)], t: f64) -> f64 {
+    let Some(i) = changes.iter().rposition(|(at, _)| *at <= t) else {
+        return start;
+    };
+    let (at, change) = changes[i];
+    match change {
+        Change::Set(value) => value,
+        Change::Ramp { to, over } => {
+            let from = value_at(start, &changes[..i], at);
+            let progress = if over.0 > 0.0 { ((t - at) / over.0).min(1.0) } else { 1.0 };
+            from + (to - from) * progress
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_market_paths() {
+        let scenario = Scenario::from_yaml(
+            "name: t\nduration: 1h\nstart: { spot: 100, basis_pct: 0.5, funding_rate: 0.0001 }\n\
+             steps:\n  - at: 10m\n    basis_pct: { to: 1.5, over: 10m }\n  - at: 30m\n    funding_rate: -0.0002\n\
+             \x20 - at: 40m\n    usdc_peg: 0.5\nexpect:\n  - reach: Closing\n    by: 45m\n",
+        ).unwrap();
+
+        assert!((scenario.market_at(0.0).perp - 100.5).abs() < 1e-9);
+        assert!((scenario.market_at(900.0).perp - 101.0).abs() < 1e-9);
+        assert!((scenario.market_at(1500.0).perp - 101.5).abs() < 1e-9);
+        assert_eq!(scenario.market_at(1799.0).funding_rate, 0.0001);
+        assert_eq!(scenario.market_at(1800.0).funding_rate, -0.0002);
+        assert!((scenario.market_at(2400.0).spot - 200.0).abs() < 1e-9);
+        assert_eq!(scenario.expect[0].by, Some(Secs(2700.0)));
+    }
+
+    #[test]
+    fn test_replay_drives_market() {
+        let dir = std::env::temp_dir().join(format!("replay-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("history.csv"),
+            "timestamp,spot_price,basis_pct,funding_rate\n1700000000000,100,0.5,0.0001\n1700000060000,110,1.0,0.0002\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("replay.yaml"), "name: r\nduration: 2m\nreplay: history.csv\n").unwrap();
+
+        let scenario = Scenario::load(&dir.join("replay.yaml")).unwrap();
+        assert!((scenario.market_at(30.0).perp - 100.5).abs() < 1e-9);
+        let market = scenario.market_at(60.0);
+        assert!((market.perp - 111.1).abs() < 1e-9);
+        assert_eq!(market.funding_rate, 0.0002);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_canned_scenarios_parse() {
+        assert_eq!(Scenario::canned().len(), CANNED.len());
+    }
+}
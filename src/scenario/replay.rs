@@ -0,0 +1,381 @@
+//! Session Replay
+//!
+//! Records what a live session fed the agent and plays it back, to answer
+//! "why did it close there?" after an incident:
+//! - With `agentic.record_session`, every agent tick where the market, the
+//!   RPC link or the open signal changed is appended to
+//!   `sessions/session-<start>.jsonl` next to the performance database
+//! - `replay --journal <file> --speed 10x` plays those inputs at their
+//!   recorded offsets into a paper agent, as the scenario runner does, and
+//!   compares its state transitions with the session's audit log
+//!
+//! Replays start flat from `Idle` with empty engine histories and fill
+//! through the mock executor, so sessions that began with a restored
+//! position, or decisions that depend on fills or on wall-clock sampling
+//! (funding history, reversal detection), can diverge at speeds above 1x.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::agent::StateTransition;
+use crate::config::AppConfig;
+use crate::engines::signal_engine::FullTradeSignal;
+use crate::execution::{ExecutionJournal, JournalEntry, JournalKind};
+use crate::feeds::{MarketTick, MockFeed};
+use crate::network::EventBus;
+use crate::position::PositionManager;
+use crate::state::{MarketSnapshot, SharedState};
+use crate::utils::types::{SignalType, TradeSignal};
+
+use super::runner::{fmt_time, full_signal, paper_config, paper_state, start_agent, TimelineEntry, FLUSH_DELAY};
+
+/// One input the agent saw
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "input", rename_all = "snake_case")]
+pub enum SessionInput {
+    /// Market after a price or funding update
+    Market {
+        spot: f64,
+        perp: f64,
+        /// Set when the funding rate was updated since the last input
+        funding_rate: Option<f64>,
+        /// Spot was not republished since the last input
+        spot_stale: bool,
+    },
+    /// RPC link went up or down
+    Rpc { connected: bool },
+    /// A new open signal from the signal engine
+    Signal {
+        signal: TradeSignal,
+        recommended_size: f64,
+        reasons: Vec<String>,
+    },
+}
+
+/// Recorded inputs of one session, oldest first
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: String,
+    /// Input timestamps and inputs (ms)
+    pub inputs: Vec<(i64, SessionInput)>,
+}
+
+impl Session {
+    /// Load a session journal; lines of other kinds are ignored
+    pub async fn load(path: &Path) -> Result<Self> {
+        anyhow::ensure!(path.exists(), "Session journal {:?} not found", path);
+        let entries = ExecutionJournal::new(&path.to_string_lossy()).entries().await?;
+        let mut id = String::new();
+        let mut inputs = Vec::new();
+        for entry in entries.into_iter().filter(|e| e.kind == JournalKind::SessionInput) {
+            let input = serde_json::from_value(entry.details)
+                .with_context(|| format!("Invalid session input at {}", entry.timestamp))?;
+            id = entry.trade_id;
+            inputs.push((entry.timestamp, input));
+        }
+        anyhow::ensure!(!inputs.is_empty(), "Session journal {:?} has no inputs", path);
+        inputs.sort_by_key(|(timestamp, _)| *timestamp);
+        Ok(Self { id, inputs })
+    }
+
+    /// First and last input timestamp (ms)
+    pub fn span(&self) -> (i64, i64) {
+        (self.inputs[0].0, self.inputs[self.inputs.len() - 1].0)
+    }
+}
+
+/// Appends the agent's inputs to a session journal on every agent tick
+pub struct SessionRecorder {
+    state: Arc<SharedState>,
+    signals: Arc<RwLock<Option<FullTradeSignal>>>,
+    journal: ExecutionJournal,
+    path: PathBuf,
+    id: String,
+    tick: Duration,
+}
+
+impl SessionRecorder {
+    /// Recorder writing to `sessions/` next to the performance database
+    pub fn new(
+        config: &AppConfig,
+        state: Arc<SharedState>,
+        signals: Arc<RwLock<Option<FullTradeSignal>>>,
+    ) -> Result<Self> {
+        let dir = Path::new(&config.agentic.performance_db_path).with_file_name("sessions");
+        std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {:?}", dir))?;
+        let id = format!("session-{}", chrono::Utc::now().format("%Y%m%dT%H%M%S"));
+        let path = dir.join(format!("{}.jsonl", id));
+        Ok(Self {
+            state,
+            signals,
+            journal: ExecutionJournal::new(&path.to_string_lossy()),
+            path,
+            id,
+            tick: config.engines.agent_tick(),
+        })
+    }
+
+    /// Record until the task is aborted
+    pub fn spawn(self) -> JoinHandle<()> {
+        info!("Recording session inputs to {}", self.path.display());
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.tick);
+            let mut last_market: Option<MarketSnapshot> = None;
+            let mut last_rpc = None;
+            let mut last_signal = 0;
+            loop {
+                interval.tick().await;
+                let mut inputs = Vec::new();
+
+                let connected = *self.state.rpc_connected.read();
+                if last_rpc != Some(connected) {
+                    inputs.push(SessionInput::Rpc { connected });
+                    last_rpc = Some(connected);
+                }
+                let market = self.state.snapshot();
+                if market.spot_price > 0.0 && last_market.map_or(true, |m| m.timestamp != market.timestamp) {
+                    inputs.push(market_input(&market, last_market.as_ref()));
+                    last_market = Some(market);
+                }
+                if let Some(signal) = self.signals.read().await.as_ref() {
+                    if signal.signal.signal_type == SignalType::OpenBasis && signal.signal.timestamp > last_signal {
+                        last_signal = signal.signal.timestamp;
+                        inputs.push(SessionInput::Signal {
+                            signal: signal.signal.clone(),
+                            recommended_size: signal.evaluation.recommended_size,
+                            reasons: signal.evaluation.reasons.clone(),
+                        });
+                    }
+                }
+
+                for input in inputs {
+                    let details = serde_json::to_value(&input).unwrap_or_default();
+                    let entry = JournalEntry::new(JournalKind::SessionInput, self.id.clone(), details);
+                    if let Err(e) = self.journal.append(&entry).await {
+                        warn!("Failed to record session input: {}", e);
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Market input for a snapshot, relative to the last one recorded
+fn market_input(market: &MarketSnapshot, last: Option<&MarketSnapshot>) -> SessionInput {
+    SessionInput::Market {
+        spot: market.spot_price,
+        perp: market.perp_mark_price,
+        funding_rate: last
+            .map_or(true, |l| l.funding_updated_at != market.funding_updated_at)
+            .then_some(market.funding_rate),
+        spot_stale: last.is_some_and(|l| l.spot_updated_at == market.spot_updated_at),
+    }
+}
+
+/// Replay speed from `10x` or `10`
+pub fn parse_speed(s: &str) -> Result<f64> {
+    let speed: f64 = s.trim().trim_end_matches(['x', 'X']).parse().with_context(|| format!("Invalid speed {:?}", s))?;
+    anyhow::ensure!(speed > 0.0 && speed.is_finite(), "Speed must be positive");
+    Ok(speed)
+}
+
+/// First point where the replayed decisions differ from the recorded ones
+#[derive(Debug, Clone, Serialize)]
+pub struct Divergence {
+    /// Position in the timelines
+    pub index: usize,
+    pub recorded: Option<TimelineEntry>,
+    pub replayed: Option<TimelineEntry>,
+}
+
+/// Outcome of a replay
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    pub session: String,
+    pub inputs: usize,
+    pub speed: f64,
+    /// Transitions from the session's audit log, at session time
+    pub recorded: Vec<TimelineEntry>,
+    /// Transitions of the replayed agent, at session time
+    pub replayed: Vec<TimelineEntry>,
+    pub divergence: Option<Divergence>,
+}
+
+impl ReplayReport {
+    pub fn matched(&self) -> bool {
+        self.divergence.is_none()
+    }
+
+    /// Side-by-side timelines and verdict
+    pub fn summary(&self) -> String {
+        let verdict = if self.matched() { "MATCH" } else { "DIVERGED" };
+        let mut out = format!(
+            "{} {} ({} inputs at {}x, {} recorded / {} replayed transitions)\n",
+            verdict,
+            self.session,
+            self.inputs,
+            self.speed,
+            self.recorded.len(),
+            self.replayed.len()
+        );
+        let line = |entry: &TimelineEntry| format!("{:>7}  {} -> {}", fmt_time(entry.at), entry.from, entry.to);
+        for i in 0..self.recorded.len().max(self.replayed.len()) {
+            let recorded = self.recorded.get(i).map_or_else(|| "-".to_string(), line);
+            let replayed = self.replayed.get(i).map_or_else(|| "-".to_string(), line);
+            let marker = if self.divergence.as_ref().is_some_and(|d| d.index == i) { "✗" } else { " " };
+            out.push_str(&format!("{} {:<40} {}\n", marker, recorded, replayed));
+        }
+        if let Some(entry) = self.divergence.as_ref().and_then(|d| d.recorded.as_ref()) {
+            if let Some(reason) = &entry.reason {
+                out.push_str(&format!("  recorded reason: {}\n", reason));
+            }
+        }
+        out
+    }
+}
+
+/// Transitions relative to `start` (ms), in session seconds
+fn timeline(transitions: Vec<StateTransition>, start: i64, speed: f64) -> Vec<TimelineEntry> {
+    transitions
+        .into_iter()
+        .map(|tr| TimelineEntry {
+            at: (tr.timestamp - start) as f64 / 1000.0 * speed,
+            from: tr.from,
+            to: tr.to,
+            reason: tr.reason,
+        })
+        .collect()
+}
+
+/// First index where the state changes differ
+fn first_divergence(recorded: &[TimelineEntry], replayed: &[TimelineEntry]) -> Option<Divergence> {
+    let index = (0..recorded.len().max(replayed.len())).find(|&i| {
+        match (recorded.get(i), replayed.get(i)) {
+            (Some(a), Some(b)) => (a.from, a.to) != (b.from, b.to),
+            _ => true,
+        }
+    })?;
+    Some(Divergence { index, recorded: recorded.get(index).cloned(), replayed: replayed.get(index).cloned() })
+}
+
+/// Replay a session into a paper agent built from `base` and compare its
+/// transitions with the `recorded` ones
+pub async fn replay(
+    session: &Session,
+    recorded: Vec<StateTransition>,
+    base: &AppConfig,
+    speed: f64,
+) -> Result<ReplayReport> {
+    let dir = std::env::temp_dir().join(format!("replay-{}-{}", session.id, uuid::Uuid::new_v4()));
+    let config = Arc::new(paper_config(base, &serde_yaml::Value::Null, &dir)?);
+    let (session_start, _) = session.span();
+    info!("Replaying {} ({} inputs, {}x speed)", session.id, session.inputs.len(), speed);
+
+    let state = paper_state(&config);
+    let bus = EventBus::new(1024);
+    let position_manager = Arc::new(PositionManager::new(state.clone()));
+    let signals = Arc::new(RwLock::new(None));
+    let mut inputs = session.inputs.iter().peekable();
+
+    // Inputs up to the first market tick set the scene before the agent starts
+    while let Some((_, input)) = inputs.next_if(|(ts, _)| *ts == session_start) {
+        apply(input, &state, &bus, &signals, &position_manager).await;
+    }
+    let agent = start_agent(&config, &state, &position_manager, &bus, &signals).await?;
+
+    let started = Instant::now();
+    let started_ms = chrono::Utc::now().timestamp_millis();
+    for (timestamp, input) in inputs {
+        let due = Duration::from_secs_f64((timestamp - session_start) as f64 / 1000.0 / speed);
+        tokio::time::sleep_until((started + due).into()).await;
+        apply(input, &state, &bus, &signals, &position_manager).await;
+    }
+
+    tokio::time::sleep(FLUSH_DELAY).await;
+    agent.stop().await;
+    let replayed = agent
+        .get_transitions(started_ms, i64::MAX)
+        .await
+        .context("Failed to read the replayed transition log")?;
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let recorded = timeline(recorded, session_start, 1.0);
+    let replayed = timeline(replayed, started_ms, speed);
+    Ok(ReplayReport {
+        session: session.id.clone(),
+        inputs: session.inputs.len(),
+        speed,
+        divergence: first_divergence(&recorded, &replayed),
+        recorded,
+        replayed,
+    })
+}
+
+/// Feed one input to the replayed agent; signals are restamped to now so
+/// they pass the agent's freshness check
+async fn apply(
+    input: &SessionInput,
+    state: &SharedState,
+    bus: &EventBus,
+    signals: &RwLock<Option<FullTradeSignal>>,
+    position_manager: &PositionManager,
+) {
+    match input {
+        SessionInput::Market { spot, perp, funding_rate, spot_stale } => {
+            let tick = MarketTick { spot: *spot, perp: *perp, funding_rate: *funding_rate, spot_stale: *spot_stale };
+            MockFeed::apply(state, &bus.sender(), &tick);
+            position_manager.update_pnl().await;
+        }
+        SessionInput::Rpc { connected } => *state.rpc_connected.write() = *connected,
+        SessionInput::Signal { signal, recommended_size, reasons } => {
+            let now = chrono::Utc::now().timestamp_millis();
+            let signal = TradeSignal { timestamp: now, market_timestamp: now, ..signal.clone() };
+            let mut full = full_signal(signal, reasons.clone());
+            full.evaluation.recommended_size = *recommended_size;
+            *signals.write().await = Some(full);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentState;
+
+    #[test]
+    fn test_speed_and_divergence() {
+        assert_eq!(parse_speed("10x").unwrap(), 10.0);
+        assert_eq!(parse_speed("2.5").unwrap(), 2.5);
+        assert!(parse_speed("0x").is_err());
+
+        let entry = |at, from, to| TimelineEntry { at, from, to, reason: None };
+        let recorded = vec![
+            entry(1.0, AgentState::Idle, AgentState::Monitoring),
+            entry(60.0, AgentState::Monitoring, AgentState::Closing),
+        ];
+        // Timing differences alone are not divergences
+        let replayed = vec![entry(1.4, AgentState::Idle, AgentState::Monitoring), recorded[1].clone()];
+        assert!(first_divergence(&recorded, &replayed).is_none());
+
+        let divergence = first_divergence(&recorded, &replayed[..1]).unwrap();
+        assert_eq!(divergence.index, 1);
+        assert!(divergence.replayed.is_none());
+    }
+
+    #[test]
+    fn test_market_input_marks_stale_spot_and_funding() {
+        let first = MarketSnapshot { spot_price: 100.0, spot_updated_at: 1, funding_updated_at: 1, ..MarketSnapshot::default() };
+        let next = MarketSnapshot { perp_mark_price: 100.2, timestamp: 2, ..first };
+        assert!(matches!(
+            market_input(&next, Some(&first)),
+            SessionInput::Market { perp, funding_rate: None, spot_stale: true, .. } if perp == 100.2
+        ));
+        assert!(matches!(market_input(&first, None), SessionInput::Market { funding_rate: Some(_), spot_stale: false, .. }));
+    }
+}
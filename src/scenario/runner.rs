@@ -0,0 +1,316 @@
+//! Scenario Runner
+//!
+//! Plays a scenario into a paper-trading agent, with no network:
+//! - Market ticks go through `MockFeed`; P&L is updated on each perp tick
+//!   as the bot's event processor does
+//! - Entry signals, RPC outages and oracle stalls are applied at their times
+//! - The agent's state transitions (from the audit log) are checked
+//!   against the scenario's expectations
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::agent::{AgentState, TradingAgent};
+use crate::config::{layers, AppConfig};
+use crate::engines::signal_engine::{FullTradeSignal, SignalEvaluation};
+use crate::execution::MockExecution;
+use crate::feeds::{MarketTick, MockFeed};
+use crate::network::EventBus;
+use crate::position::PositionManager;
+use crate::state::SharedState;
+use crate::utils::types::{SignalType, TradeSignal};
+
+use super::{LinkState, OracleState, Scenario, SignalAction};
+
+/// Shortest wall-clock interval between market ticks
+const MIN_TICK: Duration = Duration::from_millis(10);
+/// Time for the audit log writer to catch up after the run
+pub(super) const FLUSH_DELAY: Duration = Duration::from_millis(250);
+
+/// One state change, at scenario time
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    /// Scenario time (seconds)
+    pub at: f64,
+    pub from: AgentState,
+    pub to: AgentState,
+    pub reason: Option<String>,
+}
+
+/// Outcome of a scenario run
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub timeline: Vec<TimelineEntry>,
+    pub final_state: AgentState,
+    /// Trades recorded during the run
+    pub trades: usize,
+    /// Realized P&L of those trades (USD)
+    pub pnl: f64,
+    /// Unmet expectations
+    pub failures: Vec<String>,
+}
+
+impl ScenarioReport {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Human-readable timeline and verdict
+    pub fn summary(&self) -> String {
+        let verdict = if self.passed() { "PASS" } else { "FAIL" };
+        let mut out = format!("{} {} ({} trades, ended {})\n", verdict, self.name, self.trades, self.final_state);
+        for entry in &self.timeline {
+            out.push_str(&format!("  {:>7}  {} -> {}", fmt_time(entry.at), entry.from, entry.to));
+            if let Some(reason) = &entry.reason {
+                out.push_str(&format!(" ({})", reason));
+            }
+            out.push('\n');
+        }
+        for failure in &self.failures {
+            out.push_str(&format!("  ✗ {}\n", failure));
+        }
+        out
+    }
+}
+
+pub(super) fn fmt_time(secs: f64) -> String {
+    if secs >= 60.0 {
+        format!("{:.1}m", secs / 60.0)
+    } else {
+        format!("{:.0}s", secs)
+    }
+}
+
+/// Run a scenario against a copy of `base` in paper mode
+pub async fn run(scenario: &Scenario, base: &AppConfig) -> Result<ScenarioReport> {
+    let dir = std::env::temp_dir().join(format!("scenario-{}-{}", scenario.name, uuid::Uuid::new_v4()));
+    let config = Arc::new(paper_config(base, &scenario.config, &dir)?);
+    info!("Running scenario {} ({}x speed)", scenario.name, scenario.speed);
+
+    let state = paper_state(&config);
+    let bus = EventBus::new(1024);
+    let position_manager = Arc::new(PositionManager::new(state.clone()));
+    let signals = Arc::new(RwLock::new(None));
+
+    let mut oracle = OracleState::Live;
+    let tick_at = |t: f64, oracle: OracleState| {
+        let market = scenario.market_at(t);
+        let tick = MarketTick { spot: market.spot, perp: market.perp, funding_rate: Some(market.funding_rate), spot_stale: false };
+        if oracle == OracleState::Stall { tick.stale_spot() } else { tick }
+    };
+    MockFeed::apply(&state, &bus.sender(), &tick_at(0.0, oracle));
+
+    let agent = start_agent(&config, &state, &position_manager, &bus, &signals).await?;
+
+    let started = Instant::now();
+    let started_ms = chrono::Utc::now().timestamp_millis();
+    let mut interval = tokio::time::interval(
+        Duration::from_secs_f64(scenario.tick.0 / scenario.speed).max(MIN_TICK),
+    );
+    let mut next_step = 0;
+
+    loop {
+        interval.tick().await;
+        let t = started.elapsed().as_secs_f64() * scenario.speed;
+        if t > scenario.duration.0 {
+            break;
+        }
+
+        while let Some(step) = scenario.steps.get(next_step).filter(|s| s.at.0 <= t) {
+            if let Some(link) = step.rpc {
+                *state.rpc_connected.write() = link == LinkState::Up;
+            }
+            if let Some(mode) = step.oracle {
+                oracle = mode;
+            }
+            if step.signal == Some(SignalAction::Open) {
+                *signals.write().await = Some(open_signal(&state));
+            }
+            next_step += 1;
+        }
+
+        MockFeed::apply(&state, &bus.sender(), &tick_at(t, oracle));
+        position_manager.update_pnl().await;
+    }
+
+    tokio::time::sleep(FLUSH_DELAY).await;
+    let final_state = agent.current_state().await;
+    agent.stop().await;
+
+    let timeline = agent
+        .get_transitions(started_ms, i64::MAX)
+        .await
+        .context("Failed to read the transition log")?
+        .into_iter()
+        .map(|tr| TimelineEntry {
+            at: (tr.timestamp - started_ms) as f64 / 1000.0 * scenario.speed,
+            from: tr.from,
+            to: tr.to,
+            reason: tr.reason,
+        })
+        .collect::<Vec<_>>();
+    let closed = agent.performance_db().get_all_trades().await;
+    let (trades, pnl) = (closed.len(), closed.iter().map(|t| t.total_pnl).sum());
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let failures = check(scenario, &timeline, final_state);
+    Ok(ScenarioReport {
+        name: scenario.name.clone(),
+        timeline,
+        final_state,
+        trades,
+        pnl,
+        failures,
+    })
+}
+
+/// Base config plus overrides, in paper mode with scratch storage in `dir`
+pub(super) fn paper_config(base: &AppConfig, overrides: &serde_yaml::Value, dir: &std::path::Path) -> Result<AppConfig> {
+    let mut value = serde_yaml::to_value(base)?;
+    if !overrides.is_null() {
+        layers::merge(&mut value, overrides.clone());
+    }
+    let mut config: AppConfig = serde_yaml::from_value(value).context("Invalid scenario config overrides")?;
+
+    config.paper_trading = true;
+    config.dry_run = false;
+    config.trading_windows.enabled = false;
+    config.execution.passive_entry.enabled = false;
+    config.agentic.performance_db_path = dir.join("performance.json").to_string_lossy().into_owned();
+    config.agentic.review_report_path = dir.join("trade_review.md").to_string_lossy().into_owned();
+    config.agentic.record_session = false;
+    config.validate()?;
+    Ok(config)
+}
+
+/// Shared state sampled at the config's intervals, with RPC up
+pub(super) fn paper_state(config: &AppConfig) -> Arc<SharedState> {
+    let state = Arc::new(SharedState::with_sampling(
+        config.protocols.drift.funding_interval,
+        config.engines.basis_interval(),
+        config.engines.funding_interval(),
+    ));
+    *state.rpc_connected.write() = true;
+    state
+}
+
+/// Start a paper agent filling through the mock executor
+pub(super) async fn start_agent(
+    config: &Arc<AppConfig>,
+    state: &Arc<SharedState>,
+    position_manager: &Arc<PositionManager>,
+    bus: &EventBus,
+    signals: &Arc<RwLock<Option<FullTradeSignal>>>,
+) -> Result<TradingAgent> {
+    let agent = TradingAgent::new(
+        config.clone(),
+        state.clone(),
+        position_manager.clone(),
+        bus.sender(),
+        signals.clone(),
+        Arc::new(RwLock::new(None)),
+    )
+    .await?
+    .with_execution(Arc::new(MockExecution::new(state.clone())));
+    agent.start().await?;
+    Ok(agent)
+}
+
+/// OpenBasis signal at the current market
+fn open_signal(state: &SharedState) -> FullTradeSignal {
+    let market = state.snapshot();
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    let reason = format!("Scenario entry at {:.3}% basis", market.basis_spread);
+    full_signal(
+        TradeSignal {
+            signal_type: SignalType::OpenBasis,
+            size: 0.0,
+            basis_spread: market.basis_spread,
+            funding_apr: market.funding_apr,
+            expected_profit: 0.0,
+            confidence: 0.8,
+            timestamp,
+            market_timestamp: timestamp,
+            reason: reason.clone(),
+            expected_value: None,
+        },
+        vec![reason],
+    )
+}
+
+/// A signal the agent will act on, without engine analyses attached
+pub(super) fn full_signal(signal: TradeSignal, reasons: Vec<String>) -> FullTradeSignal {
+    FullTradeSignal {
+        evaluation: SignalEvaluation {
+            should_open: signal.signal_type == SignalType::OpenBasis,
+            should_close: signal.signal_type == SignalType::CloseBasis,
+            should_rebalance: false,
+            recommended_size: signal.size,
+            confidence: signal.confidence,
+            factors: Vec::new(),
+            expected_profit: signal.expected_profit,
+            perp_execution_cost_pct: None,
+            expected_value: signal.expected_value,
+            reasons,
+            blocked: None,
+            mark_index_divergence_pct: None,
+            timestamp: signal.timestamp,
+        },
+        signal,
+        funding: None,
+        basis: None,
+    }
+}
+
+/// Unmet expectations
+fn check(scenario: &Scenario, timeline: &[TimelineEntry], final_state: AgentState) -> Vec<String> {
+    let mut failures = Vec::new();
+    for expectation in &scenario.expect {
+        let after = expectation.after.map_or(0.0, |s| s.0);
+        let by = expectation.by.map_or(scenario.duration.0, |s| s.0);
+        let entered = |target: AgentState| {
+            timeline.iter().find(|e| e.to == target && e.at >= after && e.at <= by)
+        };
+
+        if let Some(target) = expectation.reach {
+            if entered(target).is_none() {
+                failures.push(format!("expected {} between {} and {}", target, fmt_time(after), fmt_time(by)));
+            }
+        }
+        if let Some(target) = expectation.never {
+            if let Some(entry) = entered(target) {
+                failures.push(format!("entered {} at {}, expected never", target, fmt_time(entry.at)));
+            }
+        }
+        if let Some(target) = expectation.end {
+            if final_state != target {
+                failures.push(format!("ended in {}, expected {}", final_state, target));
+            }
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expectations() {
+        let scenario = Scenario::from_yaml(
+            "name: t\nduration: 10m\nstart: { spot: 100 }\nexpect:\n  - reach: Paused\n    after: 2m\n    by: 5m\n  - never: Closing\n  - end: Idle\n",
+        ).unwrap();
+        let entry = |at, to| TimelineEntry { at, from: AgentState::Idle, to, reason: None };
+
+        let timeline = vec![entry(60.0, AgentState::Monitoring), entry(200.0, AgentState::Paused)];
+        assert!(check(&scenario, &timeline, AgentState::Idle).is_empty());
+
+        let timeline = vec![entry(400.0, AgentState::Paused), entry(500.0, AgentState::Closing)];
+        assert_eq!(check(&scenario, &timeline, AgentState::Paused).len(), 3);
+    }
+}
@@ -0,0 +1,23 @@
+//! Task Heartbeats
+//!
+//! Long-running loops beat a `Heartbeat` on every iteration so a supervisor
+//! can tell a live task from one that has panicked or wedged.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+/// Time of a task's last loop iteration, shared with its supervisor
+#[derive(Debug, Clone, Default)]
+pub struct Heartbeat(Arc<AtomicI64>);
+
+impl Heartbeat {
+    /// Record an iteration now
+    pub fn beat(&self) {
+        self.0.store(chrono::Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    /// Last iteration (ms, 0 before the first)
+    pub fn last(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
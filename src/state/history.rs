@@ -0,0 +1,192 @@
+//! Bounded time-series history
+//!
+//! Fixed-size ring buffer of time buckets. Each bucket keeps the latest
+//! sample recorded in its interval, so memory is bounded no matter how
+//! often updates arrive, and samples older than the window are overwritten.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A single timestamped sample
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    /// Timestamp (ms)
+    pub timestamp: i64,
+    /// Sampled value
+    pub value: f64,
+}
+
+/// Time-bucketed ring buffer
+pub struct TimeSeries {
+    /// Bucket width (ms)
+    bucket_ms: i64,
+    /// Retention window (ms)
+    window_ms: i64,
+    /// Bucket slots, indexed by (timestamp / bucket_ms) % len
+    slots: RwLock<Vec<Option<Sample>>>,
+}
+
+impl TimeSeries {
+    /// Create a series keeping one sample per `bucket` for `window`
+    pub fn new(bucket: Duration, window: Duration) -> Self {
+        let bucket_ms = (bucket.as_millis() as i64).max(1);
+        let window_ms = (window.as_millis() as i64).max(bucket_ms);
+        let capacity = (window_ms / bucket_ms) as usize;
+
+        Self {
+            bucket_ms,
+            window_ms,
+            slots: RwLock::new(vec![None; capacity.max(1)]),
+        }
+    }
+
+    /// Record a sample, replacing any older sample in the same slot
+    pub fn record(&self, timestamp: i64, value: f64) {
+        let mut slots = self.slots.write();
+        let index = (timestamp.div_euclid(self.bucket_ms) as usize) % slots.len();
+        slots[index] = Some(Sample { timestamp, value });
+    }
+
+    /// Most recent sample
+    pub fn latest(&self) -> Option<Sample> {
+        self.slots
+            .read()
+            .iter()
+            .flatten()
+            .max_by_key(|s| s.timestamp)
+            .copied()
+    }
+
+    /// Samples with `start <= timestamp <= end`, oldest first.
+    ///
+    /// Samples that have aged out of the retention window (relative to
+    /// the newest sample) are never returned.
+    pub fn range(&self, start: i64, end: i64) -> Vec<Sample> {
+        let slots = self.slots.read();
+        let newest = slots.iter().flatten().map(|s| s.timestamp).max().unwrap_or(0);
+        let oldest_allowed = newest - self.window_ms;
+
+        let mut samples: Vec<Sample> = slots
+            .iter()
+            .flatten()
+            .filter(|s| s.timestamp > oldest_allowed)
+            .filter(|s| s.timestamp >= start && s.timestamp <= end)
+            .copied()
+            .collect();
+        samples.sort_by_key(|s| s.timestamp);
+        samples
+    }
+
+    /// The `n` most recent samples, oldest first
+    pub fn last_n(&self, n: usize) -> Vec<Sample> {
+        let mut samples = self.all();
+        samples.drain(..samples.len().saturating_sub(n));
+        samples
+    }
+
+    /// Samples from `start` onwards
+    pub fn since(&self, start: i64) -> Vec<Sample> {
+        self.range(start, i64::MAX)
+    }
+
+    /// All retained samples
+    pub fn all(&self) -> Vec<Sample> {
+        self.range(i64::MIN, i64::MAX)
+    }
+
+    /// Number of retained samples
+    pub fn len(&self) -> usize {
+        self.all().len()
+    }
+
+    /// Whether no samples are retained
+    pub fn is_empty(&self) -> bool {
+        self.latest().is_none()
+    }
+
+    /// Mean value over a time range
+    pub fn mean(&self, start: i64, end: i64) -> Option<f64> {
+        mean_of(&self.range(start, end))
+    }
+
+    /// Population standard deviation over a time range
+    pub fn std_dev(&self, start: i64, end: i64) -> Option<f64> {
+        std_dev_of(&self.range(start, end))
+    }
+
+    /// Value at the given percentile (0-100) over a time range
+    pub fn percentile(&self, start: i64, end: i64, pct: f64) -> Option<f64> {
+        let mut values: Vec<f64> = self.range(start, end).iter().map(|s| s.value).collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let rank = (pct.clamp(0.0, 100.0) / 100.0) * (values.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let weight = rank - lower as f64;
+        Some(values[lower] + (values[upper] - values[lower]) * weight)
+    }
+
+    /// Percentile rank (0-100) of `value` among samples in a time range
+    pub fn percentile_rank(&self, start: i64, end: i64, value: f64) -> Option<f64> {
+        let samples = self.range(start, end);
+        if samples.is_empty() {
+            return None;
+        }
+        let below = samples.iter().filter(|s| s.value < value).count();
+        Some(below as f64 / samples.len() as f64 * 100.0)
+    }
+}
+
+/// Mean of sample values
+pub fn mean_of(samples: &[Sample]) -> Option<f64> {
+    if samples.is_empty() {
+        return None;
+    }
+    Some(samples.iter().map(|s| s.value).sum::<f64>() / samples.len() as f64)
+}
+
+/// Population standard deviation of sample values
+pub fn std_dev_of(samples: &[Sample]) -> Option<f64> {
+    let mean = mean_of(samples)?;
+    let variance = samples.iter().map(|s| (s.value - mean).powi(2)).sum::<f64>()
+        / samples.len() as f64;
+    Some(variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_is_bounded() {
+        let series = TimeSeries::new(Duration::from_secs(10), Duration::from_secs(60));
+
+        // Two minutes of 1s updates into a one minute window
+        for i in 0..120 {
+            series.record(i * 1000, i as f64);
+        }
+
+        let samples = series.all();
+        assert_eq!(samples.len(), 6);
+        assert_eq!(samples.last().unwrap().value, 119.0);
+        assert!(samples.first().unwrap().timestamp > 59_000);
+    }
+
+    #[test]
+    fn test_range_statistics() {
+        let series = TimeSeries::new(Duration::from_secs(1), Duration::from_secs(100));
+        for i in 1..=5 {
+            series.record(i * 1000, i as f64);
+        }
+
+        assert_eq!(series.mean(0, i64::MAX), Some(3.0));
+        assert_eq!(series.mean(4000, 5000), Some(4.5));
+        assert_eq!(series.percentile(0, i64::MAX, 50.0), Some(3.0));
+        assert_eq!(series.percentile_rank(0, i64::MAX, 3.0), Some(40.0));
+        assert_eq!(series.last_n(2).iter().map(|s| s.value).collect::<Vec<_>>(), vec![4.0, 5.0]);
+    }
+}
@@ -0,0 +1,286 @@
+//! Historical Data Import
+//!
+//! Loads market history from outside the bot, for engine warm-up and for
+//! scenario replays:
+//! - A CSV with `timestamp` (ms or seconds) and any of `spot_price`,
+//!   `perp_mark_price`, `basis_pct` and `funding_rate`; market export files
+//!   load as-is
+//! - Drift funding records, with the mark and oracle TWAPs of each period
+//! - Pyth Benchmarks one-minute SOL/USD candles
+//!
+//! Sources are merged by timestamp. Basis is taken from the data where
+//! given, otherwise derived from perp samples and the spot sample just
+//! before them.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::path::Path;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::config::{DriftConfig, HistoryImportConfig};
+use crate::utils::clock::to_millis;
+
+use super::{Sample, SharedState};
+
+/// Pyth Benchmarks TradingView history endpoint
+const PYTH_BENCHMARKS_URL: &str = "https://benchmarks.pyth.network/v1/shims/tradingview/history";
+/// Pyth Benchmarks symbol for SOL/USD
+const PYTH_SOL_SYMBOL: &str = "Crypto.SOL/USD";
+/// Drift funding rate precision
+const FUNDING_RATE_PRECISION: f64 = 1e9;
+/// Drift price precision
+const PRICE_PRECISION: f64 = 1e6;
+/// Spot samples further than this before a perp sample are not paired with it
+const MAX_PAIRING_GAP_MS: i64 = 2 * 60 * 1000;
+
+/// Market history from external sources, each series oldest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoricalSeries {
+    pub spot: Vec<Sample>,
+    pub perp: Vec<Sample>,
+    /// Perp premium over spot (%)
+    pub basis: Vec<Sample>,
+    /// Funding rate per interval
+    pub funding: Vec<Sample>,
+}
+
+impl HistoricalSeries {
+    pub fn is_empty(&self) -> bool {
+        self.spot.is_empty() && self.perp.is_empty() && self.basis.is_empty() && self.funding.is_empty()
+    }
+
+    /// Add another source's samples
+    pub fn merge(&mut self, other: HistoricalSeries) {
+        self.spot.extend(other.spot);
+        self.perp.extend(other.perp);
+        self.basis.extend(other.basis);
+        self.funding.extend(other.funding);
+        self.finish();
+    }
+
+    /// Sort and deduplicate each series, then derive missing basis samples
+    fn finish(&mut self) {
+        for series in [&mut self.spot, &mut self.perp, &mut self.basis, &mut self.funding] {
+            series.sort_by_key(|s| s.timestamp);
+            series.dedup_by_key(|s| s.timestamp);
+        }
+        let derived: Vec<Sample> = self
+            .perp
+            .iter()
+            .filter(|p| self.basis.binary_search_by_key(&p.timestamp, |b| b.timestamp).is_err())
+            .filter_map(|p| {
+                let spot = latest_at(&self.spot, p.timestamp).filter(|s| p.timestamp - s.timestamp <= MAX_PAIRING_GAP_MS)?;
+                (spot.value > 0.0).then(|| Sample { timestamp: p.timestamp, value: (p.value / spot.value - 1.0) * 100.0 })
+            })
+            .collect();
+        if !derived.is_empty() {
+            self.basis.extend(derived);
+            self.basis.sort_by_key(|s| s.timestamp);
+        }
+    }
+
+    /// First and last timestamp across the series (ms)
+    pub fn span(&self) -> Option<(i64, i64)> {
+        let all = || [&self.spot, &self.perp, &self.basis, &self.funding].into_iter().flatten();
+        Some((all().map(|s| s.timestamp).min()?, all().map(|s| s.timestamp).max()?))
+    }
+
+    /// Spot, perp and funding rate in effect at `timestamp`
+    pub fn market_at(&self, timestamp: i64) -> Option<(f64, f64, f64)> {
+        let spot = latest_at(&self.spot, timestamp)?.value;
+        let perp = match (latest_at(&self.perp, timestamp), latest_at(&self.basis, timestamp)) {
+            (Some(perp), _) => perp.value,
+            (None, Some(basis)) => spot * (1.0 + basis.value / 100.0),
+            (None, None) => spot,
+        };
+        let funding = latest_at(&self.funding, timestamp).map_or(0.0, |s| s.value);
+        Some((spot, perp, funding))
+    }
+
+    /// Record basis and funding into the engine histories; returns the number of samples
+    pub fn warm(&self, state: &SharedState) -> usize {
+        for sample in &self.basis {
+            state.basis_history.record(sample.timestamp, sample.value);
+        }
+        for sample in &self.funding {
+            state.funding_history.record(sample.timestamp, sample.value);
+        }
+        self.basis.len() + self.funding.len()
+    }
+}
+
+/// Latest sample at or before `timestamp` in a sorted series
+fn latest_at(series: &[Sample], timestamp: i64) -> Option<Sample> {
+    let end = series.partition_point(|s| s.timestamp <= timestamp);
+    end.checked_sub(1).map(|i| series[i])
+}
+
+/// Parse a history CSV; unknown columns and unparsable cells are skipped
+pub fn parse_csv(content: &str) -> Result<HistoricalSeries> {
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+    let header: Vec<&str> = lines.next().context("History CSV is empty")?.split(',').map(str::trim).collect();
+    let column = |name: &str| header.iter().position(|h| *h == name);
+    let time = column("timestamp").context("History CSV has no timestamp column")?;
+    let (spot, perp, basis, funding) =
+        (column("spot_price"), column("perp_mark_price"), column("basis_pct"), column("funding_rate"));
+
+    let mut series = HistoricalSeries::default();
+    for line in lines {
+        let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+        let Some(timestamp) = cells.get(time).and_then(|c| c.parse::<i64>().ok()).map(to_millis) else {
+            continue;
+        };
+        let push = |index: Option<usize>, target: &mut Vec<Sample>| {
+            if let Some(value) = index.and_then(|i| cells.get(i)).and_then(|c| c.parse::<f64>().ok()) {
+                target.push(Sample { timestamp, value });
+            }
+        };
+        push(spot, &mut series.spot);
+        push(perp, &mut series.perp);
+        push(basis, &mut series.basis);
+        push(funding, &mut series.funding);
+    }
+    series.finish();
+    Ok(series)
+}
+
+/// Load a history CSV
+pub fn load_csv(path: &Path) -> Result<HistoricalSeries> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read history CSV: {:?}", path))?;
+    parse_csv(&content).with_context(|| format!("Invalid history CSV: {:?}", path))
+}
+
+/// A number sent as either a JSON number or a string
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        value => value.as_f64(),
+    }
+}
+
+/// Drift funding records since `since` (ms): funding rate, mark TWAP as
+/// perp, oracle TWAP as spot
+pub async fn fetch_drift(client: &reqwest::Client, drift: &DriftConfig, since: i64) -> Result<HistoricalSeries> {
+    let url = format!("{}/fundingRates?marketIndex={}", drift.api_url.trim_end_matches('/'), drift.market_index);
+    let response: Value = client.get(&url).send().await?.error_for_status()?.json().await?;
+    let records = response
+        .get("fundingRates")
+        .and_then(Value::as_array)
+        .context("Drift response has no fundingRates")?;
+
+    let mut series = HistoricalSeries::default();
+    for record in records {
+        let field = |name: &str| record.get(name).and_then(number);
+        let Some(timestamp) = field("ts").map(|ts| to_millis(ts as i64)).filter(|ts| *ts >= since) else {
+            continue;
+        };
+        let (Some(rate), Some(oracle), Some(mark)) = (field("fundingRate"), field("oraclePriceTwap"), field("markPriceTwap")) else {
+            continue;
+        };
+        let (oracle, mark) = (oracle / PRICE_PRECISION, mark / PRICE_PRECISION);
+        if oracle <= 0.0 {
+            continue;
+        }
+        series.funding.push(Sample { timestamp, value: rate / FUNDING_RATE_PRECISION / oracle });
+        series.spot.push(Sample { timestamp, value: oracle });
+        series.perp.push(Sample { timestamp, value: mark });
+    }
+    series.finish();
+    Ok(series)
+}
+
+/// Pyth one-minute SOL/USD closes between `since` and `until` (ms)
+pub async fn fetch_pyth(client: &reqwest::Client, since: i64, until: i64) -> Result<HistoricalSeries> {
+    let response: Value = client
+        .get(PYTH_BENCHMARKS_URL)
+        .query(&[
+            ("symbol", PYTH_SOL_SYMBOL.to_string()),
+            ("resolution", "1".to_string()),
+            ("from", (since / 1000).to_string()),
+            ("to", (until / 1000).to_string()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    anyhow::ensure!(
+        response.get("s").and_then(Value::as_str) == Some("ok"),
+        "Pyth Benchmarks returned no data"
+    );
+    let times = response.get("t").and_then(Value::as_array).context("Pyth response has no times")?;
+    let closes = response.get("c").and_then(Value::as_array).context("Pyth response has no closes")?;
+
+    let mut series = HistoricalSeries {
+        spot: times
+            .iter()
+            .zip(closes)
+            .filter_map(|(t, c)| Some(Sample { timestamp: to_millis(t.as_i64()?), value: number(c)? }))
+            .collect(),
+        ..HistoricalSeries::default()
+    };
+    series.finish();
+    Ok(series)
+}
+
+/// Merge every configured source; a failing source is logged and skipped
+pub async fn import(config: &HistoryImportConfig, drift: &DriftConfig) -> HistoricalSeries {
+    let mut series = HistoricalSeries::default();
+    if !config.csv_path.is_empty() {
+        match load_csv(Path::new(&config.csv_path)) {
+            Ok(csv) => series.merge(csv),
+            Err(e) => warn!("History CSV import failed: {:#}", e),
+        }
+    }
+
+    let until = chrono::Utc::now().timestamp_millis();
+    let since = until - Duration::from_secs(config.lookback_hours * 3600).as_millis() as i64;
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(30)).build().unwrap_or_default();
+    if config.drift_funding {
+        match fetch_drift(&client, drift, since).await {
+            Ok(drift) => series.merge(drift),
+            Err(e) => warn!("Drift funding history import failed: {:#}", e),
+        }
+    }
+    if config.pyth_prices {
+        match fetch_pyth(&client, since, until).await {
+            Ok(pyth) => series.merge(pyth),
+            Err(e) => warn!("Pyth price history import failed: {:#}", e),
+        }
+    }
+
+    info!(
+        "Imported history: {} spot, {} perp, {} basis, {} funding samples",
+        series.spot.len(),
+        series.perp.len(),
+        series.basis.len(),
+        series.funding.len()
+    );
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_import_derives_basis() {
+        let csv = "timestamp,spot_price,perp_mark_price,funding_rate,notes\n\
+                   1700000000,100,100.5,0.0001,x\n\
+                   1700000060000,101,,,\n\
+                   1700000120000,,101.202,0.0002,\n";
+        let series = parse_csv(csv).unwrap();
+        assert_eq!(series.spot.len(), 2);
+        assert_eq!(series.basis.len(), 2);
+        assert_eq!(series.basis[0].timestamp, 1_700_000_000_000);
+        assert!((series.basis[1].value - 0.2).abs() < 1e-9);
+
+        let (spot, perp, funding) = series.market_at(1_700_000_090_000).unwrap();
+        assert_eq!((spot, perp, funding), (101.0, 100.5, 0.0001));
+
+        let state = SharedState::new();
+        assert_eq!(series.warm(&state), 4);
+        assert_eq!(state.funding_history.latest().map(|s| s.value), Some(0.0002));
+    }
+}
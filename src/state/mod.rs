@@ -2,14 +2,41 @@
 //!
 //! Thread-safe state management using lock-free structures where possible.
 
+pub mod heartbeat;
+pub mod history;
+pub mod snapshot;
+pub mod warm_start;
+pub mod history_import;
+
+pub use heartbeat::Heartbeat;
+pub use history::{Sample, TimeSeries};
+pub use snapshot::MarketSnapshot;
+
+use arc_swap::ArcSwap;
 use dashmap::DashMap;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::network::Event;
+use crate::utils::types::{
+    AgentState, EntryThresholds, FundingInterval, FundingSnapshot, GasStatus, GovernorStatus, OrderBook, Position,
+    VolatilityStatus,
+};
 
-use crate::utils::types::{AgentState, FundingSnapshot, Position};
+/// Retention for basis and funding history
+const HISTORY_WINDOW: Duration = Duration::from_secs(8 * 60 * 60);
+/// Default basis history resolution
+const BASIS_BUCKET: Duration = Duration::from_secs(10);
+/// Default funding history resolution
+const FUNDING_BUCKET: Duration = Duration::from_secs(30);
 
-/// Atomic floating point wrapper using u64 bit representation
+/// Atomic floating point wrapper using u64 bit representation.
+///
+/// Stores publish with Release and loads observe with Acquire: each value is
+/// independent, so no global (SeqCst) order is needed. Readers that need
+/// several fields consistent with each other use `SharedState::snapshot`.
 #[derive(Debug, Default)]
 pub struct AtomicF64 {
     inner: AtomicU64,
@@ -23,11 +50,20 @@ impl AtomicF64 {
     }
 
     pub fn load(&self) -> f64 {
-        f64::from_bits(self.inner.load(Ordering::SeqCst))
+        f64::from_bits(self.inner.load(Ordering::Acquire))
     }
 
     pub fn store(&self, val: f64) {
-        self.inner.store(val.to_bits(), Ordering::SeqCst);
+        self.inner.store(val.to_bits(), Ordering::Release);
+    }
+
+    /// Atomically add to the value, returning the previous value
+    pub fn fetch_add(&self, delta: f64) -> f64 {
+        let prev = self.inner.fetch_update(Ordering::AcqRel, Ordering::Acquire, |bits| {
+            Some((f64::from_bits(bits) + delta).to_bits())
+        });
+        // The closure always returns Some
+        f64::from_bits(prev.unwrap_or_else(|bits| bits))
     }
 }
 
@@ -38,17 +74,39 @@ pub struct SharedState {
     pub perp_mark_price: AtomicF64,
     pub perp_index_price: AtomicF64,
     pub last_price_update: AtomicI64,
+    /// Spread between the spot sources in the composite price (%)
+    pub oracle_divergence_pct: AtomicF64,
+    /// Pyth is stale and spot is priced from the secondary sources
+    pub oracle_contingency: RwLock<bool>,
+    /// USDC/USD (1.0 until the peg monitor reports)
+    pub usdc_usd: AtomicF64,
+    /// USDC is trading outside the peg band
+    pub usdc_depeg: RwLock<bool>,
+    /// Last funding reversal alert (ms, 0 if none)
+    pub funding_reversal_at: AtomicI64,
     
     // Funding
+    pub funding_interval: FundingInterval,
     pub current_funding_rate: AtomicF64,
     pub funding_apr: AtomicF64,
     pub predicted_funding: AtomicF64,
-    pub funding_history: DashMap<i64, FundingSnapshot>,
+    /// Funding rate history (per-period rate, 30s buckets, 8h window);
+    /// read it through `get_funding_series`, `funding_avg_over` and `funding_last_n`
+    pub funding_history: TimeSeries,
     
     // Basis
     pub basis_spread: AtomicF64,
-    pub basis_history: DashMap<i64, f64>,
+    /// Basis spread history (%, 10s buckets, 8h window)
+    pub basis_history: TimeSeries,
     pub hedge_drift: AtomicF64,
+    /// Share of the perp short bought back by a reversal tilt (%); hedge
+    /// drift is measured from this ratio
+    pub hedge_tilt_pct: AtomicF64,
+    /// Spot price history (basis buckets, 8h window), for realized volatility
+    pub spot_history: TimeSeries,
+    
+    // Perp order book
+    perp_book: RwLock<Option<OrderBook>>,
     
     // Positions
     pub spot_position: RwLock<Option<Position>>,
@@ -59,6 +117,8 @@ pub struct SharedState {
     pub realized_pnl: AtomicF64,
     pub unrealized_pnl: AtomicF64,
     pub total_funding_received: AtomicF64,
+    // Drawdown from the equity high-water mark (%), set by the risk manager
+    pub drawdown_pct: AtomicF64,
     
     // System
     pub agent_state: RwLock<AgentState>,
@@ -68,33 +128,71 @@ pub struct SharedState {
     pub trade_count: AtomicU64,
     pub is_paused: RwLock<bool>,
     pub pause_reason: RwLock<Option<String>>,
+    /// Last agent loop tick (ms, 0 before the agent starts)
+    pub agent_heartbeat: AtomicI64,
     
     // Connection
     pub rpc_connected: RwLock<bool>,
     pub ws_connected: RwLock<bool>,
     pub rpc_latency_us: AtomicU64,
+    
+    // Wallet SOL for fees and tips; None until the gas watchdog has run
+    pub gas: RwLock<Option<GasStatus>>,
+    
+    // Realized spot volatility; None until the volatility monitor has measured
+    pub volatility: RwLock<Option<VolatilityStatus>>,
+    
+    // Learned entry thresholds; None trades on the configured ones
+    pub entry_thresholds: RwLock<Option<EntryThresholds>>,
+    
+    // Trade-frequency governor; None while it is disabled
+    pub frequency_governor: RwLock<Option<GovernorStatus>>,
+    
+    // Consistent market view, swapped as a whole on every update
+    market: ArcSwap<MarketSnapshot>,
+    market_write: Mutex<()>,
 }
 
 impl SharedState {
     pub fn new() -> Self {
+        Self::with_funding_interval(FundingInterval::default())
+    }
+    
+    /// Create state for a perp venue with the given funding interval
+    pub fn with_funding_interval(funding_interval: FundingInterval) -> Self {
+        Self::with_sampling(funding_interval, BASIS_BUCKET, FUNDING_BUCKET)
+    }
+    
+    /// Create state whose basis and funding history match the engines' sampling
+    pub fn with_sampling(funding_interval: FundingInterval, basis_bucket: Duration, funding_bucket: Duration) -> Self {
         Self {
             spot_price: AtomicF64::new(0.0),
             perp_mark_price: AtomicF64::new(0.0),
             perp_index_price: AtomicF64::new(0.0),
             last_price_update: AtomicI64::new(0),
+            oracle_divergence_pct: AtomicF64::new(0.0),
+            oracle_contingency: RwLock::new(false),
+            usdc_usd: AtomicF64::new(1.0),
+            usdc_depeg: RwLock::new(false),
+            funding_reversal_at: AtomicI64::new(0),
+            funding_interval,
             current_funding_rate: AtomicF64::new(0.0),
             funding_apr: AtomicF64::new(0.0),
             predicted_funding: AtomicF64::new(0.0),
-            funding_history: DashMap::new(),
+            funding_history: TimeSeries::new(funding_bucket, HISTORY_WINDOW),
             basis_spread: AtomicF64::new(0.0),
-            basis_history: DashMap::new(),
+            basis_history: TimeSeries::new(basis_bucket, HISTORY_WINDOW),
             hedge_drift: AtomicF64::new(0.0),
+            hedge_tilt_pct: AtomicF64::new(0.0),
+            spot_history: TimeSeries::new(basis_bucket, HISTORY_WINDOW),
+            perp_book: RwLock::new(None),
             spot_position: RwLock::new(None),
             perp_position: RwLock::new(None),
             open_positions: DashMap::new(),
             realized_pnl: AtomicF64::new(0.0),
             unrealized_pnl: AtomicF64::new(0.0),
             total_funding_received: AtomicF64::new(0.0),
+            drawdown_pct: AtomicF64::new(0.0),
             agent_state: RwLock::new(AgentState::Initializing),
             last_rebalance: AtomicI64::new(0),
             last_trade: AtomicI64::new(0),
@@ -102,63 +200,201 @@ impl SharedState {
             trade_count: AtomicU64::new(0),
             is_paused: RwLock::new(false),
             pause_reason: RwLock::new(None),
+            agent_heartbeat: AtomicI64::new(0),
             rpc_connected: RwLock::new(false),
             ws_connected: RwLock::new(false),
             rpc_latency_us: AtomicU64::new(0),
+            gas: RwLock::new(None),
+            volatility: RwLock::new(None),
+            entry_thresholds: RwLock::new(None),
+            frequency_governor: RwLock::new(None),
+            market: ArcSwap::from_pointee(MarketSnapshot::default()),
+            market_write: Mutex::new(()),
         }
     }
     
+    /// Apply a market data event from the bus, as the event processor
+    /// does; false for events that carry no market data
+    pub fn apply_market_event(&self, event: &Event) -> bool {
+        match event {
+            Event::SpotPriceUpdate(update) => self.update_spot_price(update.price),
+            Event::PerpMarkPriceUpdate(update) => self.update_perp_mark_price(update.price),
+            Event::PerpIndexPriceUpdate(update) => self.update_perp_index_price(update.price),
+            Event::SpotDepthUpdate(quote) => self.update_spot_depth(quote.buy_price, quote.sell_price),
+            Event::PerpBookUpdate(book) => self.update_perp_book(book.clone()),
+            Event::FundingRateUpdate { rate, .. } => self.update_funding_rate(*rate),
+            _ => return false,
+        }
+        true
+    }
+    
     pub fn update_spot_price(&self, price: f64) {
-        self.spot_price.store(price);
-        self.update_price_timestamp();
-        self.recalculate_basis();
+        let now = current_timestamp_millis();
+        self.update_market(now, |m| {
+            m.spot_price = price;
+            m.spot_updated_at = now;
+        });
+        self.last_price_update.store(now, Ordering::Release);
     }
     
     pub fn update_perp_mark_price(&self, price: f64) {
-        self.perp_mark_price.store(price);
-        self.update_price_timestamp();
-        self.recalculate_basis();
+        let now = current_timestamp_millis();
+        self.update_market(now, |m| {
+            m.perp_mark_price = price;
+            m.perp_updated_at = now;
+        });
+        self.last_price_update.store(now, Ordering::Release);
+    }
+    
+    pub fn update_perp_index_price(&self, price: f64) {
+        let now = current_timestamp_millis();
+        self.update_market(now, |m| m.perp_index_price = price);
+    }
+    
+    /// Record effective spot prices at the typical trade size
+    pub fn update_spot_depth(&self, buy_price: f64, sell_price: f64) {
+        let now = current_timestamp_millis();
+        self.update_market(now, |m| {
+            m.spot_buy_price = buy_price;
+            m.spot_sell_price = sell_price;
+            m.depth_updated_at = now;
+        });
+    }
+    
+    /// Record the latest perp order book
+    pub fn update_perp_book(&self, book: OrderBook) {
+        let now = current_timestamp_millis();
+        let (bid, ask) = (book.best_bid().unwrap_or(0.0), book.best_ask().unwrap_or(0.0));
+        *self.perp_book.write() = Some(book);
+        self.update_market(now, |m| {
+            m.perp_best_bid = bid;
+            m.perp_best_ask = ask;
+            m.book_updated_at = now;
+        });
+    }
+    
+    /// Latest perp order book
+    pub fn perp_book(&self) -> Option<OrderBook> {
+        self.perp_book.read().clone()
     }
     
     pub fn update_funding_rate(&self, rate: f64) {
-        self.current_funding_rate.store(rate);
-        let apr = rate * 24.0 * 365.0 * 100.0;
-        self.funding_apr.store(apr);
-        
-        let timestamp = current_timestamp_millis();
-        self.funding_history.insert(timestamp, FundingSnapshot {
-            timestamp,
-            rate,
-            apr,
+        let now = current_timestamp_millis();
+        let apr = self.funding_interval.annualize(rate);
+        self.update_market(now, |m| {
+            m.funding_rate = rate;
+            m.funding_apr = apr;
+            m.funding_updated_at = now;
         });
-        self.cleanup_funding_history();
+        
+        self.funding_history.record(now, rate);
+    }
+    
+    /// Funding snapshots with `start <= timestamp <= end`, oldest first
+    pub fn get_funding_series(&self, start: i64, end: i64) -> Vec<FundingSnapshot> {
+        self.funding_history.range(start, end).into_iter().map(|s| self.funding_snapshot(s)).collect()
+    }
+    
+    /// Mean funding over the `window` ending at `end`
+    pub fn funding_avg_over(&self, window: Duration, end: i64) -> Option<FundingSnapshot> {
+        let rate = self.funding_history.mean(end - window.as_millis() as i64, end)?;
+        Some(FundingSnapshot { timestamp: end, rate, apr: self.funding_interval.annualize(rate) })
+    }
+    
+    /// The `n` most recent funding snapshots, oldest first
+    pub fn funding_last_n(&self, n: usize) -> Vec<FundingSnapshot> {
+        self.funding_history.last_n(n).into_iter().map(|s| self.funding_snapshot(s)).collect()
+    }
+    
+    fn funding_snapshot(&self, sample: Sample) -> FundingSnapshot {
+        FundingSnapshot {
+            timestamp: sample.timestamp,
+            rate: sample.value,
+            apr: self.funding_interval.annualize(sample.value),
+        }
     }
     
     pub fn get_basis_spread(&self) -> f64 {
         self.basis_spread.load()
     }
     
-    fn recalculate_basis(&self) {
-        let spot = self.spot_price.load();
-        let perp = self.perp_mark_price.load();
-        
-        if spot > 0.0 {
-            let basis = ((perp - spot) / spot) * 100.0;
-            self.basis_spread.store(basis);
-            
-            let timestamp = current_timestamp_millis();
-            self.basis_history.insert(timestamp, basis);
-        }
+    /// Consistent view of prices, basis and funding at one instant
+    pub fn snapshot(&self) -> MarketSnapshot {
+        **self.market.load()
     }
     
-    fn update_price_timestamp(&self) {
-        let now = current_timestamp_millis();
-        self.last_price_update.store(now, Ordering::SeqCst);
+    /// Apply a change to the market snapshot, recompute basis, and mirror
+    /// the result into the individual atomics.
+    ///
+    /// This runs on every price tick. The snapshot is copied on the stack;
+    /// the only allocation is the `Arc` published to readers.
+    fn update_market(&self, now: i64, apply: impl FnOnce(&mut MarketSnapshot)) {
+        let _guard = self.market_write.lock();
+        
+        let mut market = **self.market.load();
+        apply(&mut market);
+        market.timestamp = now;
+        
+        if market.spot_price > 0.0 {
+            market.basis_spread =
+                ((market.perp_mark_price - market.spot_price) / market.spot_price) * 100.0;
+        }
+        
+        self.spot_price.store(market.spot_price);
+        self.perp_mark_price.store(market.perp_mark_price);
+        self.perp_index_price.store(market.perp_index_price);
+        self.basis_spread.store(market.basis_spread);
+        self.current_funding_rate.store(market.funding_rate);
+        self.funding_apr.store(market.funding_apr);
+        self.market.store(Arc::new(market));
+        
+        if market.spot_price > 0.0 {
+            self.basis_history.record(now, market.basis_spread);
+            self.spot_history.record(now, market.spot_price);
+        }
     }
     
-    fn cleanup_funding_history(&self) {
-        let cutoff = current_timestamp_millis() - (8 * 60 * 60 * 1000);
-        self.funding_history.retain(|&ts, _| ts > cutoff);
+    /// Copy market readings (prices, book, funding, basis, oracle, peg,
+    /// connection, gas and volatility status) from the primary state, and
+    /// history samples newer than `since`. Positions, P&L and agent state
+    /// are left alone. Returns the newest timestamp copied, for the next call.
+    pub fn mirror_market(&self, from: &SharedState, since: i64) -> i64 {
+        let _guard = self.market_write.lock();
+        let market = from.market.load_full();
+        self.spot_price.store(market.spot_price);
+        self.perp_mark_price.store(market.perp_mark_price);
+        self.perp_index_price.store(market.perp_index_price);
+        self.basis_spread.store(market.basis_spread);
+        self.current_funding_rate.store(market.funding_rate);
+        self.funding_apr.store(market.funding_apr);
+        self.market.store(market);
+        *self.perp_book.write() = from.perp_book();
+        
+        self.last_price_update.store(from.last_price_update.load(Ordering::Acquire), Ordering::Release);
+        self.oracle_divergence_pct.store(from.oracle_divergence_pct.load());
+        *self.oracle_contingency.write() = *from.oracle_contingency.read();
+        self.usdc_usd.store(from.usdc_usd.load());
+        *self.usdc_depeg.write() = *from.usdc_depeg.read();
+        self.funding_reversal_at.store(from.funding_reversal_at.load(Ordering::Acquire), Ordering::Release);
+        self.predicted_funding.store(from.predicted_funding.load());
+        *self.rpc_connected.write() = *from.rpc_connected.read();
+        *self.ws_connected.write() = *from.ws_connected.read();
+        self.rpc_latency_us.store(from.rpc_latency_us.load(Ordering::Relaxed), Ordering::Relaxed);
+        *self.gas.write() = *from.gas.read();
+        *self.volatility.write() = *from.volatility.read();
+        
+        let mut newest = since;
+        for (source, target) in [
+            (&from.funding_history, &self.funding_history),
+            (&from.basis_history, &self.basis_history),
+            (&from.spot_history, &self.spot_history),
+        ] {
+            for sample in source.since(since + 1) {
+                target.record(sample.timestamp, sample.value);
+                newest = newest.max(sample.timestamp);
+            }
+        }
+        newest
     }
     
     pub fn pause(&self, reason: &str) {
@@ -173,12 +409,13 @@ impl SharedState {
         *self.agent_state.write() = AgentState::Scanning;
     }
     
+    // Counters don't order other memory, so Relaxed is enough
     pub fn increment_error_count(&self) {
-        self.error_count.fetch_add(1, Ordering::SeqCst);
+        self.error_count.fetch_add(1, Ordering::Relaxed);
     }
     
     pub fn increment_trade_count(&self) {
-        self.trade_count.fetch_add(1, Ordering::SeqCst);
+        self.trade_count.fetch_add(1, Ordering::Relaxed);
     }
 }
 
@@ -194,3 +431,37 @@ fn current_timestamp_millis() -> i64 {
         .unwrap()
         .as_millis() as i64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_concurrent_fetch_add() {
+        let value = Arc::new(AtomicF64::new(0.0));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let value = value.clone();
+                std::thread::spawn(move || (0..1000).for_each(|_| { value.fetch_add(0.5); }))
+            })
+            .collect();
+        handles.into_iter().for_each(|h| h.join().unwrap());
+        assert_eq!(value.load(), 2000.0);
+    }
+    
+    #[test]
+    fn test_funding_queries() {
+        let state = SharedState::with_funding_interval(FundingInterval::Hourly);
+        for i in 0..4 {
+            state.funding_history.record(i * 60_000, 0.0001 * (i + 1) as f64);
+        }
+        
+        let series = state.get_funding_series(60_000, 120_000);
+        assert_eq!(series.len(), 2);
+        assert!((series[0].apr - FundingInterval::Hourly.annualize(0.0002)).abs() < 1e-9);
+        
+        let avg = state.funding_avg_over(Duration::from_secs(120), 180_000).unwrap();
+        assert!((avg.rate - 0.0003).abs() < 1e-12);
+        assert_eq!(state.funding_last_n(1)[0].timestamp, 180_000);
+    }
+}
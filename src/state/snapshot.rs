@@ -0,0 +1,82 @@
+//! Market data snapshot
+//!
+//! A consistent view of prices, basis and funding captured at one instant.
+//! Readers should take a snapshot once per evaluation instead of loading
+//! individual atomics, which may be updated between loads.
+
+use serde::{Deserialize, Serialize};
+
+/// Consistent view of market data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketSnapshot {
+    /// Spot price
+    pub spot_price: f64,
+    /// Perp mark price
+    pub perp_mark_price: f64,
+    /// Perp index (oracle) price
+    pub perp_index_price: f64,
+    /// Basis spread (%): (perp - spot) / spot * 100
+    pub basis_spread: f64,
+    /// Current funding rate (per venue funding period)
+    pub funding_rate: f64,
+    /// Annualized funding APR (%)
+    pub funding_apr: f64,
+    /// Effective spot buy price at the typical trade size (0 = unknown)
+    pub spot_buy_price: f64,
+    /// Effective spot sell price at the typical trade size (0 = unknown)
+    pub spot_sell_price: f64,
+    /// Best perp bid from the order book (0 = unknown)
+    pub perp_best_bid: f64,
+    /// Best perp ask from the order book (0 = unknown)
+    pub perp_best_ask: f64,
+    /// Last spot update (ms)
+    pub spot_updated_at: i64,
+    /// Last perp mark update (ms)
+    pub perp_updated_at: i64,
+    /// Last funding update (ms)
+    pub funding_updated_at: i64,
+    /// Last depth quote update (ms)
+    pub depth_updated_at: i64,
+    /// Last perp order book update (ms)
+    pub book_updated_at: i64,
+    /// Time the snapshot was last modified (ms)
+    pub timestamp: i64,
+}
+
+impl MarketSnapshot {
+    /// Whether both legs have a price
+    pub fn has_prices(&self) -> bool {
+        self.spot_price > 0.0 && self.perp_mark_price > 0.0
+    }
+
+    /// Age of the oldest price leg (ms)
+    pub fn price_age_ms(&self, now: i64) -> i64 {
+        now - self.spot_updated_at.min(self.perp_updated_at)
+    }
+
+    /// Basis (%) achievable when entering at the typical trade size.
+    ///
+    /// A positive basis is entered by buying spot, so the effective buy
+    /// price is used; a negative basis by selling spot, using the sell price.
+    pub fn executable_basis(&self) -> Option<f64> {
+        let spot = if self.basis_spread >= 0.0 { self.spot_buy_price } else { self.spot_sell_price };
+        if spot <= 0.0 || self.perp_mark_price <= 0.0 {
+            return None;
+        }
+        Some((self.perp_mark_price - spot) / spot * 100.0)
+    }
+
+    /// Perp mark deviation from the index (%), once both are known
+    pub fn mark_index_divergence_pct(&self) -> Option<f64> {
+        if self.perp_index_price <= 0.0 || self.perp_mark_price <= 0.0 {
+            return None;
+        }
+        Some((self.perp_mark_price - self.perp_index_price) / self.perp_index_price * 100.0)
+    }
+
+    /// Whether basis and funding point the same way (positive carry for the trade)
+    pub fn is_aligned(&self) -> bool {
+        (self.basis_spread > 0.0 && self.funding_apr > 0.0)
+            || (self.basis_spread < 0.0 && self.funding_apr < 0.0)
+    }
+}
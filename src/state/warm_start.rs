@@ -0,0 +1,117 @@
+//! Warm Start
+//!
+//! The basis and funding analyses need minutes to hours of history, which
+//! leaves the bot blind after a restart. The rolling histories are saved
+//! periodically and reloaded on startup so analyses are available at once:
+//! - Saved every `engines.history_save_secs` and once more on shutdown
+//! - Ignored on load when older than `engines.history_max_age_mins`
+//! - Samples past the retention window age out as usual
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use super::{Sample, SharedState};
+
+/// Basis and funding history at one moment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistorySnapshot {
+    /// When the snapshot was taken (ms)
+    pub saved_at: i64,
+    pub basis: Vec<Sample>,
+    pub funding: Vec<Sample>,
+}
+
+impl HistorySnapshot {
+    pub fn capture(state: &SharedState, now: i64) -> Self {
+        Self {
+            saved_at: now,
+            basis: state.basis_history.all(),
+            funding: state.funding_history.all(),
+        }
+    }
+
+    /// Record the saved samples into `state`; returns the number restored
+    pub fn restore(&self, state: &SharedState) -> usize {
+        for sample in &self.basis {
+            state.basis_history.record(sample.timestamp, sample.value);
+        }
+        for sample in &self.funding {
+            state.funding_history.record(sample.timestamp, sample.value);
+        }
+        self.basis.len() + self.funding.len()
+    }
+}
+
+/// Write the current histories to `path`
+pub async fn save(state: &SharedState, path: &Path) -> Result<()> {
+    let snapshot = HistorySnapshot::capture(state, chrono::Utc::now().timestamp_millis());
+    let content = serde_json::to_string(&snapshot).context("Failed to serialize history")?;
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await.ok();
+    }
+    let tmp = path.with_extension("tmp");
+    tokio::fs::write(&tmp, content).await
+        .context("Failed to write history")?;
+    tokio::fs::rename(&tmp, path).await
+        .context("Failed to replace history")?;
+    Ok(())
+}
+
+/// Reload histories saved within `max_age`; returns the number of samples restored
+pub async fn load(state: &SharedState, path: &Path, max_age: Duration) -> Result<usize> {
+    if !path.exists() {
+        return Ok(0);
+    }
+    let content = tokio::fs::read_to_string(path).await
+        .with_context(|| format!("Failed to read history: {:?}", path))?;
+    let snapshot: HistorySnapshot = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse history: {:?}", path))?;
+
+    let age_ms = chrono::Utc::now().timestamp_millis() - snapshot.saved_at;
+    if age_ms > max_age.as_millis() as i64 {
+        info!("Saved history is {} minutes old, starting cold", age_ms / 60_000);
+        return Ok(0);
+    }
+    Ok(snapshot.restore(state))
+}
+
+/// Save the histories every `period` until the task is aborted
+pub fn spawn_saver(state: Arc<SharedState>, path: PathBuf, period: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            match save(&state, &path).await {
+                Ok(()) => debug!("Saved engine history to {:?}", path),
+                Err(e) => warn!("Failed to save engine history: {}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let state = SharedState::new();
+        for i in 0..20 {
+            state.basis_history.record(i * 10_000, 0.1 + i as f64 * 0.01);
+            state.funding_history.record(i * 30_000, 0.0001);
+        }
+
+        let json = serde_json::to_string(&HistorySnapshot::capture(&state, 600_000)).unwrap();
+        let snapshot: HistorySnapshot = serde_json::from_str(&json).unwrap();
+        let restarted = SharedState::new();
+        assert_eq!(snapshot.restore(&restarted), 40);
+        assert_eq!(restarted.basis_history.all(), state.basis_history.all());
+        assert_eq!(restarted.funding_history.latest(), state.funding_history.latest());
+    }
+}
@@ -1,15 +1,26 @@
 //! Alert management for notifications
+//!
+//! Alerts flow through a small pipeline:
+//! - Severity routing: each channel (log, webhook, Telegram) has a minimum level
+//! - Deduplication: identical alerts are suppressed within a configurable window
+//! - Escalation: critical alerts are re-sent until acknowledged via the control API
 
 use anyhow::Result;
+use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
 use tracing::{info, warn, error};
 
-use crate::config::TelemetryConfig;
+use crate::config::{AlertingConfig, TelemetryConfig};
+use crate::network::Event;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AlertLevel {
     Info,
+    #[serde(alias = "warn")]
     Warning,
     Error,
     Critical,
@@ -26,8 +37,17 @@ impl AlertLevel {
     }
 }
 
+/// Delivery channel for alerts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertChannel {
+    Log,
+    Webhook,
+    Telegram,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
+    pub id: String,
     pub level: AlertLevel,
     pub title: String,
     pub message: String,
@@ -39,6 +59,7 @@ pub struct Alert {
 impl Alert {
     pub fn new(level: AlertLevel, title: impl Into<String>, message: impl Into<String>) -> Self {
         Self {
+            id: uuid::Uuid::new_v4().to_string(),
             level,
             title: title.into(),
             message: message.into(),
@@ -67,51 +88,225 @@ impl Alert {
     pub fn critical(title: impl Into<String>, message: impl Into<String>) -> Self {
         Self::new(AlertLevel::Critical, title, message)
     }
+
+    /// Key identifying repeats of the same alert
+    pub fn dedup_key(&self) -> String {
+        format!("{:?}:{}:{}", self.level, self.title, self.message)
+    }
+}
+
+/// Critical alert awaiting acknowledgement
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingAlert {
+    pub alert: Alert,
+    /// Number of times the alert has been dispatched
+    pub attempts: u32,
+    /// Last dispatch time (unix seconds)
+    pub last_sent: i64,
 }
 
 pub struct AlertManager {
     enabled: bool,
+    routing: AlertingConfig,
     webhook_url: Option<String>,
     telegram_bot_token: Option<String>,
     telegram_chat_id: Option<String>,
     http_client: reqwest::Client,
+    /// Last dispatch time per dedup key
+    recent: DashMap<String, i64>,
+    /// Critical alerts awaiting acknowledgement, by alert id
+    pending: DashMap<String, PendingAlert>,
 }
 
 impl AlertManager {
     pub fn new(config: &TelemetryConfig) -> Self {
         Self {
             enabled: config.enable_alerts,
+            routing: config.alerting.clone(),
             webhook_url: config.alert_webhook.clone(),
             telegram_bot_token: config.telegram.bot_token.clone(),
             telegram_chat_id: config.telegram.chat_id.clone(),
             http_client: reqwest::Client::new(),
+            recent: DashMap::new(),
+            pending: DashMap::new(),
         }
     }
     
-    pub async fn send(&self, alert: Alert) {
+    /// Send an alert through the pipeline.
+    ///
+    /// Returns false if the alert was suppressed (disabled or duplicate).
+    pub async fn send(&self, alert: Alert) -> bool {
         if !self.enabled {
-            return;
+            return false;
         }
         
-        match alert.level {
-            AlertLevel::Info => info!("[ALERT] {}: {}", alert.title, alert.message),
-            AlertLevel::Warning => warn!("[ALERT] {}: {}", alert.title, alert.message),
-            AlertLevel::Error => error!("[ALERT] {}: {}", alert.title, alert.message),
-            AlertLevel::Critical => error!("[CRITICAL] {}: {}", alert.title, alert.message),
+        let now = chrono::Utc::now().timestamp();
+        if self.is_duplicate(&alert, now) {
+            return false;
+        }
+
+        if alert.level == AlertLevel::Critical {
+            self.pending.insert(alert.id.clone(), PendingAlert {
+                alert: alert.clone(),
+                attempts: 1,
+                last_sent: now,
+            });
+        }
+
+        self.dispatch(&alert).await;
+        true
+    }
+
+    /// Check and record the dedup window for an alert
+    fn is_duplicate(&self, alert: &Alert, now: i64) -> bool {
+        let key = alert.dedup_key();
+        if let Some(last) = self.recent.get(&key) {
+            if now - *last < self.routing.dedup_window_secs as i64 {
+                return true;
+            }
+        }
+        self.recent.insert(key, now);
+
+        // Forget keys that have aged out of the window
+        let window = self.routing.dedup_window_secs as i64;
+        self.recent.retain(|_, last| now - *last < window);
+        false
+    }
+
+    /// Whether an alert of this level should go to a channel
+    pub fn routes_to(&self, level: AlertLevel, channel: AlertChannel) -> bool {
+        let min_level = match channel {
+            AlertChannel::Log => self.routing.log_min_level,
+            AlertChannel::Webhook => self.routing.webhook_min_level,
+            AlertChannel::Telegram => self.routing.telegram_min_level,
+        };
+        level >= min_level
+    }
+
+    /// Deliver an alert to every channel it routes to
+    async fn dispatch(&self, alert: &Alert) {
+        if self.routes_to(alert.level, AlertChannel::Log) {
+            match alert.level {
+                AlertLevel::Info => info!("[ALERT] {}: {}", alert.title, alert.message),
+                AlertLevel::Warning => warn!("[ALERT] {}: {}", alert.title, alert.message),
+                AlertLevel::Error => error!("[ALERT] {}: {}", alert.title, alert.message),
+                AlertLevel::Critical => error!("[CRITICAL] {}: {} (id {})", alert.title, alert.message, alert.id),
+            }
         }
         
         if let Some(url) = &self.webhook_url {
-            if let Err(e) = self.send_webhook(url, &alert).await {
-                warn!("Failed to send webhook alert: {}", e);
+            if self.routes_to(alert.level, AlertChannel::Webhook) {
+                if let Err(e) = self.send_webhook(url, alert).await {
+                    warn!("Failed to send webhook alert: {}", e);
+                }
             }
         }
         
-        if self.telegram_bot_token.is_some() && self.telegram_chat_id.is_some() {
-            if let Err(e) = self.send_telegram(&alert).await {
+        if self.telegram_bot_token.is_some()
+            && self.telegram_chat_id.is_some()
+            && self.routes_to(alert.level, AlertChannel::Telegram)
+        {
+            if let Err(e) = self.send_telegram(alert).await {
                 warn!("Failed to send Telegram alert: {}", e);
             }
         }
     }
+
+    /// Acknowledge a critical alert, stopping its escalation
+    pub fn acknowledge(&self, id: &str) -> bool {
+        match self.pending.remove(id) {
+            Some((_, pending)) => {
+                info!("Alert acknowledged: {} ({})", pending.alert.title, id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Critical alerts that have not been acknowledged
+    pub fn pending_alerts(&self) -> Vec<PendingAlert> {
+        let mut pending: Vec<PendingAlert> = self.pending.iter().map(|p| p.value().clone()).collect();
+        pending.sort_by_key(|p| p.alert.timestamp);
+        pending
+    }
+
+    /// Re-send unacknowledged critical alerts whose escalation interval elapsed
+    async fn escalate(&self) {
+        let now = chrono::Utc::now().timestamp();
+        let interval = self.routing.escalation_interval_secs as i64;
+        let max = self.routing.max_escalations;
+
+        let due: Vec<String> = self.pending
+            .iter()
+            .filter(|p| now - p.last_sent >= interval)
+            .filter(|p| max == 0 || p.attempts <= max)
+            .map(|p| p.key().clone())
+            .collect();
+
+        for id in due {
+            let escalated = match self.pending.get_mut(&id) {
+                Some(mut p) => {
+                    p.attempts += 1;
+                    p.last_sent = now;
+                    let mut alert = p.alert.clone();
+                    alert.title = format!("[ESCALATION #{}] {}", p.attempts - 1, alert.title);
+                    alert
+                }
+                None => continue,
+            };
+            self.dispatch(&escalated).await;
+        }
+    }
+
+    /// Spawn the pipeline task: routes alerting events from the bus and
+    /// escalates unacknowledged critical alerts
+    pub fn spawn(self: Arc<Self>, mut event_rx: broadcast::Receiver<Event>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(
+                self.routing.escalation_interval_secs.max(1),
+            ));
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        self.escalate().await;
+                    }
+                    event = event_rx.recv() => {
+                        let alert = match event {
+                            Ok(Event::Alert(alert)) => alert,
+                            Ok(Event::Error { source, message }) => Alert::error(source, message),
+                            Ok(Event::SystemPause { reason }) => Alert::warning("Trading paused", reason),
+                            Ok(Event::DependencyDegraded { dependency, failures, cool_off_secs }) => Alert::warning(
+                                "Execution venue degraded",
+                                format!("{} failed {} times in a row, cooling off for {}s", dependency, failures, cool_off_secs),
+                            ),
+                            Ok(Event::DependencyRecovered { dependency, down_secs }) => {
+                                Alert::info("Execution venue recovered", format!("{} is back after {}s", dependency, down_secs))
+                            }
+                            Ok(Event::LegImbalance { trade_id, phase, spot_filled, perp_filled, resolution }) => {
+                                let message = format!(
+                                    "{} of {}: spot {:.4} / perp {:.4}, {}",
+                                    phase, trade_id, spot_filled, perp_filled, resolution
+                                );
+                                if resolution == "failed" {
+                                    Alert::critical("Unhedged leg", message)
+                                } else {
+                                    Alert::warning("Leg imbalance", message)
+                                }
+                            }
+                            Ok(_) => continue,
+                            Err(broadcast::error::RecvError::Lagged(n)) => {
+                                warn!("Alert pipeline lagged by {} events", n);
+                                continue;
+                            }
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        };
+                        self.send(alert).await;
+                    }
+                }
+            }
+        })
+    }
     
     async fn send_webhook(&self, url: &str, alert: &Alert) -> Result<()> {
         let payload = serde_json::json!({
@@ -127,7 +322,10 @@ impl AlertManager {
         let chat_id = self.telegram_chat_id.as_ref().unwrap();
         
         let url = format!("https://api.telegram.org/bot{}/sendMessage", bot_token);
-        let text = format!("{} *{}*\n\n{}", alert.level.emoji(), alert.title, alert.message);
+        let mut text = format!("{} *{}*\n\n{}", alert.level.emoji(), alert.title, alert.message);
+        if alert.level == AlertLevel::Critical {
+            text.push_str(&format!("\n\nAck id: `{}`", alert.id));
+        }
         
         let payload = serde_json::json!({
             "chat_id": chat_id,
@@ -139,3 +337,38 @@ impl AlertManager {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    fn manager() -> AlertManager {
+        let mut config = AppConfig::default_for_test().telemetry;
+        config.enable_alerts = true;
+        AlertManager::new(&config)
+    }
+
+    #[tokio::test]
+    async fn test_dedup_and_acknowledge() {
+        let alerts = manager();
+
+        assert!(alerts.send(Alert::warning("RPC", "failover")).await);
+        assert!(!alerts.send(Alert::warning("RPC", "failover")).await);
+
+        let critical = Alert::critical("Drawdown", "limit breached");
+        let id = critical.id.clone();
+        assert!(alerts.send(critical).await);
+        assert_eq!(alerts.pending_alerts().len(), 1);
+        assert!(alerts.acknowledge(&id));
+        assert!(alerts.pending_alerts().is_empty());
+    }
+
+    #[test]
+    fn test_routing() {
+        let alerts = manager();
+        assert!(alerts.routes_to(AlertLevel::Info, AlertChannel::Log));
+        assert!(!alerts.routes_to(AlertLevel::Info, AlertChannel::Telegram));
+        assert!(alerts.routes_to(AlertLevel::Critical, AlertChannel::Telegram));
+    }
+}
@@ -0,0 +1,227 @@
+//! Latency budget instrumentation
+//!
+//! Per-stage latency histograms for the execution path (quote, blockhash,
+//! simulate, submit, confirm) plus a per-trade tracker for the open path
+//! (market data → signal → sizing → risk check → execute → reconcile). The
+//! path starts when the market data behind the signal arrived, so time the
+//! signal waits for the agent counts. An open path that exceeds the
+//! configured budget raises a slow-path alert.
+//!
+//! Work run under `observe_stages` also reports each execution stage it
+//! enters, so the agent can show where a trade is. Recent samples of each
+//! stage are also kept in-process for `stage_stats` (the session report).
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::alerts::Alert;
+use super::metrics::{record_budget_exceeded, record_stage_latency};
+
+/// Measured stage of the trade path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatencyStage {
+    Quote,
+    Blockhash,
+    Simulate,
+    Submit,
+    Confirm,
+    /// Market data arrival to the agent picking up the signal
+    Signal,
+    Sizing,
+    RiskCheck,
+    Execute,
+    Reconcile,
+}
+
+impl LatencyStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Quote => "quote",
+            Self::Blockhash => "blockhash",
+            Self::Simulate => "simulate",
+            Self::Submit => "submit",
+            Self::Confirm => "confirm",
+            Self::Signal => "signal",
+            Self::Sizing => "sizing",
+            Self::RiskCheck => "risk_check",
+            Self::Execute => "execute",
+            Self::Reconcile => "reconcile",
+        }
+    }
+}
+
+/// Samples kept per stage for `stage_stats`
+const MAX_SAMPLES: usize = 1000;
+
+/// Latency summary of one stage
+#[derive(Debug, Clone, Serialize)]
+pub struct StageStats {
+    pub stage: &'static str,
+    /// Samples since startup
+    pub count: u64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Samples since startup and the most recent ones, per stage
+fn samples() -> &'static Mutex<BTreeMap<&'static str, (u64, VecDeque<f64>)>> {
+    static SAMPLES: OnceLock<Mutex<BTreeMap<&'static str, (u64, VecDeque<f64>)>>> = OnceLock::new();
+    SAMPLES.get_or_init(Default::default)
+}
+
+fn observe(stage: LatencyStage, ms: f64) {
+    record_stage_latency(stage.as_str(), ms);
+    let mut samples = samples().lock();
+    let (count, recent) = samples.entry(stage.as_str()).or_default();
+    *count += 1;
+    if recent.len() == MAX_SAMPLES {
+        recent.pop_front();
+    }
+    recent.push_back(ms);
+}
+
+/// Latency of each stage seen so far, over its recent samples
+pub fn stage_stats() -> Vec<StageStats> {
+    samples()
+        .lock()
+        .iter()
+        .filter(|(_, (_, recent))| !recent.is_empty())
+        .map(|(stage, (count, recent))| {
+            let mut sorted: Vec<f64> = recent.iter().copied().collect();
+            sorted.sort_by(f64::total_cmp);
+            let at = |q: f64| sorted[((sorted.len() - 1) as f64 * q).round() as usize];
+            StageStats {
+                stage: *stage,
+                count: *count,
+                mean_ms: sorted.iter().sum::<f64>() / sorted.len() as f64,
+                p50_ms: at(0.5),
+                p95_ms: at(0.95),
+                max_ms: sorted[sorted.len() - 1],
+            }
+        })
+        .collect()
+}
+
+type StageObserver = Arc<dyn Fn(LatencyStage) + Send + Sync>;
+
+tokio::task_local! {
+    /// Told of each stage entered by the work being observed
+    static STAGE_OBSERVER: StageObserver;
+}
+
+/// Await `fut`, telling `observer` of every stage `timed` enters within it
+pub async fn observe_stages<F: Future>(observer: impl Fn(LatencyStage) + Send + Sync + 'static, fut: F) -> F::Output {
+    STAGE_OBSERVER.scope(Arc::new(observer), fut).await
+}
+
+/// Await `fut`, recording its latency under `stage`
+pub async fn timed<T>(stage: LatencyStage, fut: impl Future<Output = T>) -> T {
+    let _ = STAGE_OBSERVER.try_with(|observe| observe(stage));
+    let start = Instant::now();
+    let out = fut.await;
+    observe(stage, start.elapsed().as_secs_f64() * 1000.0);
+    out
+}
+
+/// Stage timings for one trade's open path
+#[derive(Debug, Clone)]
+pub struct LatencyTracker {
+    /// Start of the path (market data arrival)
+    start: Instant,
+    /// Recorded stages, in order (ms)
+    stages: Vec<(LatencyStage, f64)>,
+}
+
+impl LatencyTracker {
+    pub fn start() -> Self {
+        Self {
+            start: Instant::now(),
+            stages: Vec::new(),
+        }
+    }
+
+    /// Tracker for a path that started at `started_at` (wall clock, ms):
+    /// the time since is recorded as the `Signal` stage
+    pub fn since(started_at: i64) -> Self {
+        let waited_ms = (chrono::Utc::now().timestamp_millis() - started_at).max(0) as u64;
+        let now = Instant::now();
+        let mut tracker = Self {
+            start: now.checked_sub(Duration::from_millis(waited_ms)).unwrap_or(now),
+            stages: Vec::new(),
+        };
+        tracker.record(LatencyStage::Signal, waited_ms as f64);
+        tracker
+    }
+
+    /// Await `fut` as one stage of the path
+    pub async fn time<T>(&mut self, stage: LatencyStage, fut: impl Future<Output = T>) -> T {
+        let start = Instant::now();
+        let out = fut.await;
+        self.record(stage, start.elapsed().as_secs_f64() * 1000.0);
+        out
+    }
+
+    /// Record a stage measured elsewhere
+    pub fn record(&mut self, stage: LatencyStage, ms: f64) {
+        observe(stage, ms);
+        self.stages.push((stage, ms));
+    }
+
+    /// Wall time since the path started (ms)
+    pub fn elapsed_ms(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() * 1000.0
+    }
+
+    /// "sizing 3ms, execute 420ms, ..." for alerts and logs
+    pub fn breakdown(&self) -> String {
+        self.stages
+            .iter()
+            .map(|(stage, ms)| format!("{} {:.0}ms", stage.as_str(), ms))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Slow-path alert if the path took longer than `budget_ms`
+    pub fn check_budget(&self, budget_ms: u64, trade_id: &str) -> Option<Alert> {
+        let elapsed = self.elapsed_ms();
+        if budget_ms == 0 || elapsed <= budget_ms as f64 {
+            return None;
+        }
+        record_budget_exceeded();
+        Some(Alert::warning(
+            "Slow open path",
+            format!(
+                "Trade {} opened in {:.0}ms, budget {}ms ({})",
+                trade_id, elapsed, budget_ms, self.breakdown()
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_alarm() {
+        let mut tracker = LatencyTracker::start();
+        tracker.record(LatencyStage::Execute, 12.0);
+        assert!(tracker.breakdown().contains("execute 12ms"));
+        assert!(tracker.check_budget(60_000, "t1").is_none());
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let alert = tracker.check_budget(1, "t1").unwrap();
+        assert!(alert.message.contains("budget 1ms"));
+
+        // Started by market data that arrived 1.5s before the agent saw it
+        let waited = LatencyTracker::since(chrono::Utc::now().timestamp_millis() - 1_500);
+        assert!(waited.elapsed_ms() >= 1_500.0);
+        assert!(waited.breakdown().starts_with("signal 15"));
+    }
+}
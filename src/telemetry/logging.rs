@@ -6,6 +6,8 @@ use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, Env
 
 use crate::config::TelemetryConfig;
 
+use super::otel;
+
 pub fn init_logging(config: &TelemetryConfig) -> Result<()> {
     let log_level = parse_log_level(&config.log_level);
     
@@ -17,6 +19,15 @@ pub fn init_logging(config: &TelemetryConfig) -> Result<()> {
         .add_directive("tungstenite=warn".parse()?)
         .add_directive("tokio_tungstenite=warn".parse()?);
     
+    // Trade lifecycle spans go to the OTLP collector when one is configured
+    let otel_layer = match &config.otlp_endpoint {
+        Some(endpoint) => Some(
+            tracing_opentelemetry::layer()
+                .with_tracer(otel::init_tracer(endpoint, &config.otlp_service_name)?),
+        ),
+        None => None,
+    };
+    
     if config.json_logs {
         let fmt_layer = fmt::layer()
             .json()
@@ -27,6 +38,7 @@ pub fn init_logging(config: &TelemetryConfig) -> Result<()> {
         
         tracing_subscriber::registry()
             .with(env_filter)
+            .with(otel_layer)
             .with(fmt_layer)
             .init();
     } else {
@@ -39,6 +51,7 @@ pub fn init_logging(config: &TelemetryConfig) -> Result<()> {
         
         tracing_subscriber::registry()
             .with(env_filter)
+            .with(otel_layer)
             .with(fmt_layer)
             .init();
     }
@@ -0,0 +1,261 @@
+//! Market Data Export
+//!
+//! Samples the market for offline research, beyond the trade CSV:
+//! - Spot, perp mark and index, basis, funding rate and APR, and hedge
+//!   drift, every `sample_interval_ms`
+//! - Appended to `market-YYYY-MM-DD.csv` (`market-YYYY-MM-DDTHH.csv` when
+//!   rotating hourly) in `directory`
+//! - With the parquet format, each file is compacted to `.parquet` once it
+//!   has rotated, so a crash never leaves a Parquet file without its footer
+//! - Files older than `retention_days` are deleted at each rotation
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::io::AsyncWriteExt;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::config::{ExportFormat, ExportRotation, MarketExportConfig};
+use crate::state::SharedState;
+
+/// Prefix of every export file
+const FILE_PREFIX: &str = "market-";
+
+/// Columns, in file order
+const COLUMNS: [&str; 8] = [
+    "timestamp",
+    "spot_price",
+    "perp_mark_price",
+    "perp_index_price",
+    "basis_pct",
+    "funding_rate",
+    "funding_apr_pct",
+    "hedge_drift_pct",
+];
+
+/// One market sample
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MarketSample {
+    /// Sample time (ms)
+    pub timestamp: i64,
+    pub spot_price: f64,
+    pub perp_mark_price: f64,
+    pub perp_index_price: f64,
+    pub basis_pct: f64,
+    pub funding_rate: f64,
+    pub funding_apr_pct: f64,
+    pub hedge_drift_pct: f64,
+}
+
+impl MarketSample {
+    /// Sample the shared state
+    pub fn from_state(state: &SharedState, timestamp: i64) -> Self {
+        let market = state.snapshot();
+        Self::from_values(timestamp, [
+            market.spot_price,
+            market.perp_mark_price,
+            market.perp_index_price,
+            market.basis_spread,
+            market.funding_rate,
+            market.funding_apr,
+            state.hedge_drift.load(),
+        ])
+    }
+
+    /// Price columns, in file order after the timestamp
+    fn values(&self) -> [f64; 7] {
+        [
+            self.spot_price,
+            self.perp_mark_price,
+            self.perp_index_price,
+            self.basis_pct,
+            self.funding_rate,
+            self.funding_apr_pct,
+            self.hedge_drift_pct,
+        ]
+    }
+
+    fn from_values(timestamp: i64, v: [f64; 7]) -> Self {
+        Self {
+            timestamp,
+            spot_price: v[0],
+            perp_mark_price: v[1],
+            perp_index_price: v[2],
+            basis_pct: v[3],
+            funding_rate: v[4],
+            funding_apr_pct: v[5],
+            hedge_drift_pct: v[6],
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        let values: Vec<String> = self.values().iter().map(|v| v.to_string()).collect();
+        format!("{},{}\n", self.timestamp, values.join(","))
+    }
+
+    fn parse_csv_row(line: &str) -> Option<Self> {
+        let mut fields = line.trim().split(',');
+        let timestamp = fields.next()?.parse().ok()?;
+        let values: Vec<f64> = fields.map(|f| f.parse().ok()).collect::<Option<_>>()?;
+        Some(Self::from_values(timestamp, values.try_into().ok()?))
+    }
+}
+
+/// File name without extension for the file holding `timestamp` (ms)
+pub fn file_stem(timestamp: i64, rotation: ExportRotation) -> String {
+    let time = chrono::DateTime::from_timestamp_millis(timestamp).unwrap_or_default();
+    match rotation {
+        ExportRotation::Daily => format!("{}{}", FILE_PREFIX, time.format("%Y-%m-%d")),
+        ExportRotation::Hourly => format!("{}{}", FILE_PREFIX, time.format("%Y-%m-%dT%H")),
+    }
+}
+
+/// Appends market samples to rotating files
+pub struct MarketDataExporter {
+    config: MarketExportConfig,
+    state: Arc<SharedState>,
+}
+
+impl MarketDataExporter {
+    pub fn new(config: &MarketExportConfig, state: Arc<SharedState>) -> Self {
+        Self { config: config.clone(), state }
+    }
+
+    /// Sample on `sample_interval_ms` until the task is aborted
+    pub fn spawn(self) -> JoinHandle<()> {
+        let period = Duration::from_millis(self.config.sample_interval_ms.max(1));
+        info!("Exporting market data to {} every {:?}", self.config.directory, period);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            let mut current: Option<String> = None;
+            loop {
+                interval.tick().await;
+                let now = chrono::Utc::now().timestamp_millis();
+                let sample = MarketSample::from_state(&self.state, now);
+                if sample.spot_price <= 0.0 {
+                    continue;
+                }
+
+                let stem = file_stem(now, self.config.rotation);
+                if current.as_ref() != Some(&stem) {
+                    if let Err(e) = self.rotate(&stem).await {
+                        warn!("Market export rotation failed: {:#}", e);
+                    }
+                    current = Some(stem.clone());
+                }
+                if let Err(e) = self.append(&stem, &sample).await {
+                    warn!("Market export write failed: {:#}", e);
+                }
+            }
+        })
+    }
+
+    /// Append a sample to the CSV for `stem`, writing the header first on a new file
+    async fn append(&self, stem: &str, sample: &MarketSample) -> Result<()> {
+        let path = Path::new(&self.config.directory).join(format!("{}.csv", stem));
+        let new_file = !tokio::fs::try_exists(&path).await.unwrap_or(false);
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        if new_file {
+            file.write_all(format!("{}\n", COLUMNS.join(",")).as_bytes()).await?;
+        }
+        file.write_all(sample.csv_row().as_bytes()).await?;
+        Ok(())
+    }
+
+    /// Compact finished CSVs (parquet format) and delete expired files
+    async fn rotate(&self, current_stem: &str) -> Result<()> {
+        let dir = PathBuf::from(&self.config.directory);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let config = self.config.clone();
+        let current_stem = current_stem.to_string();
+        tokio::task::spawn_blocking(move || maintain(&dir, &current_stem, &config)).await?
+    }
+}
+
+fn maintain(dir: &Path, current_stem: &str, config: &MarketExportConfig) -> Result<()> {
+    let expiry = (config.retention_days > 0)
+        .then(|| SystemTime::now() - Duration::from_secs(config.retention_days as u64 * 86_400));
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if !stem.starts_with(FILE_PREFIX) || stem == current_stem {
+            continue;
+        }
+
+        let modified = std::fs::metadata(&path)?.modified()?;
+        if expiry.is_some_and(|expiry| modified < expiry) {
+            std::fs::remove_file(&path)?;
+            debug!("Deleted expired market export {}", path.display());
+        } else if config.format == ExportFormat::Parquet && path.extension().is_some_and(|e| e == "csv") {
+            compact(&path)?;
+            info!("Compacted {} to Parquet", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Rewrite a finished CSV as Parquet next to it, then remove the CSV
+fn compact(csv: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(csv)?;
+    let samples: Vec<MarketSample> = content.lines().skip(1).filter_map(MarketSample::parse_csv_row).collect();
+    let tmp = csv.with_extension("parquet.tmp");
+    write_parquet(&tmp, &samples)?;
+    std::fs::rename(&tmp, csv.with_extension("parquet"))?;
+    std::fs::remove_file(csv)?;
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet(path: &Path, samples: &[MarketSample]) -> Result<()> {
+    use arrow::array::{ArrayRef, Float64Array, Int64Array};
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+
+    let timestamps: ArrayRef = Arc::new(Int64Array::from_iter_values(samples.iter().map(|s| s.timestamp)));
+    let mut columns = vec![(COLUMNS[0], timestamps)];
+    for (i, name) in COLUMNS[1..].iter().enumerate() {
+        let values: ArrayRef = Arc::new(Float64Array::from_iter_values(samples.iter().map(|s| s.values()[i])));
+        columns.push((*name, values));
+    }
+    let batch = RecordBatch::try_from_iter(columns)?;
+    let mut writer = ArrowWriter::try_new(std::fs::File::create(path)?, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(not(feature = "parquet"))]
+fn write_parquet(_path: &Path, _samples: &[MarketSample]) -> Result<()> {
+    anyhow::bail!("Parquet export needs a build with --features parquet")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_stem_and_csv_roundtrip() {
+        // 2026-03-14 15:09:26 UTC
+        let ts = 1_773_500_966_000;
+        assert_eq!(file_stem(ts, ExportRotation::Daily), "market-2026-03-14");
+        assert_eq!(file_stem(ts, ExportRotation::Hourly), "market-2026-03-14T15");
+
+        let sample = MarketSample::from_values(ts, [142.5, 142.9, 142.8, 0.2807, 0.0001, 10.95, -0.4]);
+        let row = sample.csv_row();
+        assert_eq!(row.trim().split(',').count(), COLUMNS.len());
+        assert_eq!(MarketSample::parse_csv_row(&row), Some(sample));
+        assert_eq!(MarketSample::parse_csv_row("timestamp,spot_price"), None);
+    }
+}
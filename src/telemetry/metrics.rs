@@ -6,6 +6,8 @@ use metrics_exporter_prometheus::PrometheusBuilder;
 use std::net::SocketAddr;
 use tracing::info;
 
+use crate::utils::types::GasLevel;
+
 pub fn init_metrics(port: u16) -> Result<()> {
     let addr: SocketAddr = ([0, 0, 0, 0], port).into();
     
@@ -23,6 +25,17 @@ fn register_metrics() {
     describe_gauge!("sol_basis_bot_spot_price", "Current SOL spot price in USD");
     describe_gauge!("sol_basis_bot_perp_mark_price", "Current SOL perp mark price");
     describe_gauge!("sol_basis_bot_perp_index_price", "Current SOL perp index price");
+    describe_gauge!("sol_basis_bot_oracle_divergence_pct", "Spread between the accepted spot oracles (%)");
+    describe_gauge!("sol_basis_bot_oracle_contingency", "1 while Pyth is stale and spot is priced from the secondary sources");
+    describe_gauge!("sol_basis_bot_usdc_usd", "USDC/USD from Pyth");
+    describe_gauge!("sol_basis_bot_usdc_depeg", "1 while USDC trades outside the peg band");
+    describe_gauge!("sol_basis_bot_realized_vol_pct", "Annualized realized volatility of spot returns");
+    describe_gauge!("sol_basis_bot_volatility_elevated", "1 while realized volatility is above its trigger percentile");
+    describe_gauge!("sol_basis_bot_drawdown_throttle", "Target size multiplier from the drawdown throttle (0-1)");
+    describe_gauge!("sol_basis_bot_governor_level", "Trade-frequency governor level");
+    describe_gauge!("sol_basis_bot_governor_multiplier", "Entry threshold multiplier from the trade-frequency governor");
+    describe_gauge!("sol_basis_bot_entry_min_basis_pct", "Basis entry threshold in use (%)");
+    describe_gauge!("sol_basis_bot_entry_min_funding_apr_pct", "Funding APR entry threshold in use (%)");
     
     // Basis metrics
     describe_gauge!("sol_basis_bot_basis_spread", "Current basis spread percentage");
@@ -37,28 +50,84 @@ fn register_metrics() {
     
     // P&L metrics
     describe_gauge!("sol_basis_bot_realized_pnl", "Total realized P&L in USD");
+    describe_gauge!("sol_basis_bot_idle_capital_usd", "Idle USDC and SOL not in a position (USD)");
+    describe_gauge!("sol_basis_bot_capital_utilization_pct", "Share of capital deployed in the spot leg");
+    describe_gauge!("sol_basis_bot_return_on_capital_pct", "Net P&L over the capital it was earned on");
     describe_gauge!("sol_basis_bot_unrealized_pnl", "Current unrealized P&L in USD");
     
     // Trade metrics
     describe_counter!("sol_basis_bot_trades_total", "Total number of trades executed");
     describe_counter!("sol_basis_bot_trades_success", "Number of successful trades");
     describe_counter!("sol_basis_bot_trades_failed", "Number of failed trades");
+    describe_counter!("sol_basis_bot_pre_trade_failures_total", "Live orders stopped by the pre-trade checklist (label: check)");
+    describe_histogram!("sol_basis_bot_slippage_bps", "Realized slippage against the quoted price (labels: leg, bucket)");
+    describe_gauge!("sol_basis_bot_slippage_tolerance_pct", "Spot swap slippage tolerance (label: bucket)");
     
     // Latency metrics
     describe_histogram!("sol_basis_bot_execution_latency_ms", "Trade execution latency");
     describe_histogram!("sol_basis_bot_rpc_latency_us", "RPC request latency");
+    describe_histogram!("sol_basis_bot_stage_latency_ms", "Latency per trade path stage (label: stage)");
+    describe_counter!("sol_basis_bot_latency_budget_exceeded_total", "Open paths slower than the latency budget");
     
     // System metrics
     describe_counter!("sol_basis_bot_errors_total", "Total number of errors");
+    describe_counter!("sol_basis_bot_task_restarts_total", "Stalled tasks restarted by the supervisor (label: task)");
+    describe_counter!("sol_basis_bot_http_requests_total", "Outbound HTTP calls (labels: host, outcome ok/retry/failure/rejected)");
+    describe_gauge!("sol_basis_bot_clock_skew_ms", "Local clock minus RPC block time (ms)");
+    describe_gauge!("sol_basis_bot_http_cooling_off", "Whether a circuit breaker is open (label: breaker, a dependency or host)");
     describe_gauge!("sol_basis_bot_agent_state", "Current agent state");
+    describe_gauge!("sol_basis_bot_strategy_position_sol", "Spot leg of a named strategy (label: strategy)");
+    describe_gauge!("sol_basis_bot_strategy_realized_pnl", "Realized P&L of a named strategy (label: strategy)");
+    describe_gauge!("sol_basis_bot_strategy_unrealized_pnl", "Unrealized P&L of a named strategy (label: strategy)");
+    describe_gauge!("sol_basis_bot_trading_window_open", "Whether the trading window allows entries");
     describe_gauge!("sol_basis_bot_rpc_connected", "RPC connection status");
     describe_gauge!("sol_basis_bot_ws_connected", "WebSocket connection status");
+    describe_gauge!("sol_basis_bot_wallet_sol_balance", "Wallet SOL available for fees and tips");
+    describe_gauge!("sol_basis_bot_wallet_gas_level", "Wallet gas level (0 = ok, 1 = low, 2 = critical)");
 }
 
 pub fn record_spot_price(price: f64) {
     gauge!("sol_basis_bot_spot_price").set(price);
 }
 
+pub fn record_oracle_divergence(divergence_pct: f64) {
+    gauge!("sol_basis_bot_oracle_divergence_pct").set(divergence_pct);
+}
+
+pub fn record_oracle_contingency(active: bool) {
+    gauge!("sol_basis_bot_oracle_contingency").set(if active { 1.0 } else { 0.0 });
+}
+
+pub fn record_usdc_peg(usdc_usd: f64, depegged: bool) {
+    gauge!("sol_basis_bot_usdc_usd").set(usdc_usd);
+    gauge!("sol_basis_bot_usdc_depeg").set(if depegged { 1.0 } else { 0.0 });
+}
+
+pub fn record_realized_volatility(vol_pct: f64, elevated: bool) {
+    gauge!("sol_basis_bot_realized_vol_pct").set(vol_pct);
+    gauge!("sol_basis_bot_volatility_elevated").set(if elevated { 1.0 } else { 0.0 });
+}
+
+pub fn record_drawdown_throttle(multiplier: f64) {
+    gauge!("sol_basis_bot_drawdown_throttle").set(multiplier);
+}
+
+pub fn record_frequency_governor(level: u32, multiplier: f64) {
+    gauge!("sol_basis_bot_governor_level").set(level as f64);
+    gauge!("sol_basis_bot_governor_multiplier").set(multiplier);
+}
+
+pub fn record_entry_thresholds(min_basis_pct: f64, min_funding_apr_pct: f64) {
+    gauge!("sol_basis_bot_entry_min_basis_pct").set(min_basis_pct);
+    gauge!("sol_basis_bot_entry_min_funding_apr_pct").set(min_funding_apr_pct);
+}
+
+pub fn record_strategy(strategy: &str, position_sol: f64, realized_pnl: f64, unrealized_pnl: f64) {
+    gauge!("sol_basis_bot_strategy_position_sol", "strategy" => strategy.to_string()).set(position_sol);
+    gauge!("sol_basis_bot_strategy_realized_pnl", "strategy" => strategy.to_string()).set(realized_pnl);
+    gauge!("sol_basis_bot_strategy_unrealized_pnl", "strategy" => strategy.to_string()).set(unrealized_pnl);
+}
+
 pub fn record_perp_mark_price(price: f64) {
     gauge!("sol_basis_bot_perp_mark_price").set(price);
 }
@@ -81,6 +150,18 @@ pub fn record_trade_failure() {
     counter!("sol_basis_bot_trades_failed").increment(1);
 }
 
+pub fn record_pre_trade_failure(check: &'static str) {
+    counter!("sol_basis_bot_pre_trade_failures_total", "check" => check).increment(1);
+}
+
+pub fn record_slippage(leg: &'static str, bucket: String, bps: f64) {
+    histogram!("sol_basis_bot_slippage_bps", "leg" => leg, "bucket" => bucket).record(bps);
+}
+
+pub fn record_slippage_tolerance(bucket: String, tolerance_pct: f64) {
+    gauge!("sol_basis_bot_slippage_tolerance_pct", "bucket" => bucket).set(tolerance_pct);
+}
+
 pub fn record_execution_latency(latency_ms: f64) {
     histogram!("sol_basis_bot_execution_latency_ms").record(latency_ms);
 }
@@ -89,14 +170,53 @@ pub fn record_rpc_latency(latency_us: f64) {
     histogram!("sol_basis_bot_rpc_latency_us").record(latency_us);
 }
 
+pub fn record_stage_latency(stage: &'static str, latency_ms: f64) {
+    histogram!("sol_basis_bot_stage_latency_ms", "stage" => stage).record(latency_ms);
+}
+
+pub fn record_budget_exceeded() {
+    counter!("sol_basis_bot_latency_budget_exceeded_total").increment(1);
+}
+
 pub fn record_error() {
     counter!("sol_basis_bot_errors_total").increment(1);
 }
 
+pub fn record_task_restart(task: &'static str) {
+    counter!("sol_basis_bot_task_restarts_total", "task" => task).increment(1);
+}
+
+pub fn record_http_request(host: &str, outcome: &'static str) {
+    counter!("sol_basis_bot_http_requests_total", "host" => host.to_string(), "outcome" => outcome).increment(1);
+}
+
+pub fn record_clock_skew(skew_ms: i64) {
+    gauge!("sol_basis_bot_clock_skew_ms").set(skew_ms as f64);
+}
+
+pub fn record_http_cool_off(breaker: &str, cooling_off: bool) {
+    gauge!("sol_basis_bot_http_cooling_off", "breaker" => breaker.to_string()).set(if cooling_off { 1.0 } else { 0.0 });
+}
+
 pub fn record_agent_state(state: u8) {
     gauge!("sol_basis_bot_agent_state").set(state as f64);
 }
 
+pub fn record_trading_window(open: bool) {
+    gauge!("sol_basis_bot_trading_window_open").set(if open { 1.0 } else { 0.0 });
+}
+
+pub fn record_capital(idle_usd: f64, utilization_pct: f64, return_on_capital_pct: f64) {
+    gauge!("sol_basis_bot_idle_capital_usd").set(idle_usd);
+    gauge!("sol_basis_bot_capital_utilization_pct").set(utilization_pct);
+    gauge!("sol_basis_bot_return_on_capital_pct").set(return_on_capital_pct);
+}
+
+pub fn record_wallet_gas(sol_balance: f64, level: GasLevel) {
+    gauge!("sol_basis_bot_wallet_sol_balance").set(sol_balance);
+    gauge!("sol_basis_bot_wallet_gas_level").set(level as u8 as f64);
+}
+
 pub fn record_connection_status(rpc: bool, ws: bool) {
     gauge!("sol_basis_bot_rpc_connected").set(if rpc { 1.0 } else { 0.0 });
     gauge!("sol_basis_bot_ws_connected").set(if ws { 1.0 } else { 0.0 });
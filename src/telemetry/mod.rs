@@ -3,7 +3,13 @@
 mod logging;
 mod metrics;
 mod alerts;
+mod otel;
+mod latency;
+mod market_export;
 
 pub use logging::init_logging;
-pub use metrics::init_metrics;
-pub use alerts::{AlertManager, Alert, AlertLevel};
+pub use otel::{shutdown_tracing, trade_span};
+pub use latency::{observe_stages, stage_stats, timed, LatencyStage, LatencyTracker, StageStats};
+pub use metrics::{init_metrics, record_capital, record_clock_skew, record_drawdown_throttle, record_entry_thresholds, record_frequency_governor, record_http_cool_off, record_http_request, record_oracle_contingency, record_oracle_divergence, record_pre_trade_failure, record_realized_volatility, record_slippage, record_slippage_tolerance, record_strategy, record_task_restart, record_trading_window, record_usdc_peg, record_wallet_gas};
+pub use alerts::{AlertManager, Alert, AlertLevel, AlertChannel, PendingAlert};
+pub use market_export::{MarketDataExporter, MarketSample};
@@ -0,0 +1,40 @@
+//! OpenTelemetry trace export
+//!
+//! Exports tracing spans over OTLP (gRPC) so each stage of a trade can be
+//! inspected in Jaeger/Tempo. Every trade gets a root `trade` span carrying
+//! its id; stage spans (signal, sizing, risk_check, execute, tx_build,
+//! submit, confirm, record) are created as its children.
+
+use anyhow::{Context, Result};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace, Resource};
+use tracing::Span;
+
+/// Build an OTLP batch tracer for `endpoint`
+pub fn init_tracer(endpoint: &str, service_name: &str) -> Result<trace::Tracer> {
+    opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![
+                KeyValue::new("service.name", service_name.to_string()),
+            ])),
+        )
+        .install_batch(runtime::Tokio)
+        .with_context(|| format!("Failed to install OTLP exporter for {}", endpoint))
+}
+
+/// Flush buffered spans; call once on shutdown
+pub fn shutdown_tracing() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/// Root span correlating every stage of one trade
+pub fn trade_span(trade_id: &str) -> Span {
+    tracing::info_span!(parent: None, "trade", trade_id = %trade_id)
+}
@@ -0,0 +1,146 @@
+//! Token Amounts
+//!
+//! On-chain instructions take integer base units (lamports, USDC units,
+//! Drift base and price precision); strategy code sizes in UI units as f64.
+//! Every conversion between the two goes through `Amount`, a decimal value
+//! tagged with its asset's decimals:
+//! - `Amount::from_ui` rejects negative, non-finite and overflowing values
+//!   instead of the silent saturation of an `as u64` cast (NaN became 0)
+//! - `Amount::base_units` rounds half away from zero, once, in decimal
+//!   arithmetic, so 0.1 SOL is exactly 100,000,000 lamports
+//! - `Amount::from_base_units` is exact
+
+use anyhow::{Context, Result};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::fmt;
+
+/// SOL (lamports)
+pub const SOL_DECIMALS: u8 = 9;
+/// USDC
+pub const USDC_DECIMALS: u8 = 6;
+/// Drift perp base asset amounts (every market)
+pub const DRIFT_BASE_DECIMALS: u8 = 9;
+/// Drift prices
+pub const DRIFT_PRICE_DECIMALS: u8 = 6;
+/// Jupiter Perps USD amounts
+pub const JUPITER_USD_DECIMALS: u8 = 6;
+
+/// A non-negative amount of an asset with `decimals` decimals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Amount {
+    value: Decimal,
+    decimals: u8,
+}
+
+impl Amount {
+    /// Amount of `ui` whole tokens
+    pub fn from_ui(ui: f64, decimals: u8) -> Result<Self> {
+        anyhow::ensure!(ui.is_finite() && ui >= 0.0, "Invalid amount {}", ui);
+        let value = Decimal::from_f64(ui).with_context(|| format!("Amount {} out of range", ui))?;
+        Ok(Self { value, decimals })
+    }
+
+    /// Amount of `units` base units
+    pub fn from_base_units(units: u64, decimals: u8) -> Self {
+        Self {
+            value: Decimal::from_i128_with_scale(units as i128, decimals as u32),
+            decimals,
+        }
+    }
+
+    pub fn sol(ui: f64) -> Result<Self> {
+        Self::from_ui(ui, SOL_DECIMALS)
+    }
+
+    pub fn lamports(lamports: u64) -> Self {
+        Self::from_base_units(lamports, SOL_DECIMALS)
+    }
+
+    pub fn usdc(ui: f64) -> Result<Self> {
+        Self::from_ui(ui, USDC_DECIMALS)
+    }
+
+    pub fn usdc_units(units: u64) -> Self {
+        Self::from_base_units(units, USDC_DECIMALS)
+    }
+
+    /// Drift perp base asset amount of a signed `size` (its magnitude)
+    pub fn drift_base(size: f64) -> Result<Self> {
+        Self::from_ui(size.abs(), DRIFT_BASE_DECIMALS)
+    }
+
+    /// Drift price of `price`
+    pub fn drift_price(price: f64) -> Result<Self> {
+        Self::from_ui(price, DRIFT_PRICE_DECIMALS)
+    }
+
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// Base units, rounded to the nearest
+    pub fn base_units(&self) -> Result<u64> {
+        self.value
+            .checked_mul(Decimal::from(10u64.pow(self.decimals as u32)))
+            .map(|units| units.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero))
+            .and_then(|units| units.to_u64())
+            .with_context(|| format!("Amount {} overflows base units", self.value))
+    }
+
+    /// Base units, rounded down: for bounds that must not be exceeded
+    pub fn base_units_floor(&self) -> Result<u64> {
+        self.value
+            .checked_mul(Decimal::from(10u64.pow(self.decimals as u32)))
+            .map(|units| units.floor())
+            .and_then(|units| units.to_u64())
+            .with_context(|| format!("Amount {} overflows base units", self.value))
+    }
+
+    /// Whole tokens
+    pub fn ui(&self) -> f64 {
+        self.value.to_f64().unwrap_or(0.0)
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// Base units of `ui` whole tokens with `decimals` decimals
+pub fn to_base_units(ui: f64, decimals: u8) -> Result<u64> {
+    Amount::from_ui(ui, decimals)?.base_units()
+}
+
+/// Whole tokens of `units` base units with `decimals` decimals
+pub fn from_base_units(units: u64, decimals: u8) -> f64 {
+    Amount::from_base_units(units, decimals).ui()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions_are_exact() {
+        // Scaled in decimal, not as an f64 product
+        assert_eq!(Amount::sol(0.1).unwrap().base_units().unwrap(), 100_000_000);
+        assert_eq!(Amount::drift_base(-2.5).unwrap().base_units().unwrap(), 2_500_000_000);
+        assert_eq!(Amount::drift_price(151.23455).unwrap().base_units().unwrap(), 151_234_550);
+        assert_eq!(Amount::usdc(1.0000004).unwrap().base_units_floor().unwrap(), 1_000_000);
+        assert_eq!(Amount::lamports(1_500_000_001).ui(), 1.500000001);
+        assert_eq!(from_base_units(2_000_000, USDC_DECIMALS), 2.0);
+        assert_eq!(to_base_units(3.0, 8).unwrap(), 300_000_000);
+    }
+
+    #[test]
+    fn test_invalid_amounts_are_rejected() {
+        assert!(Amount::sol(f64::NAN).is_err());
+        assert!(Amount::sol(f64::INFINITY).is_err());
+        assert!(Amount::usdc(-1.0).is_err());
+        // Beyond u64 lamports
+        assert!(Amount::sol(1e11).unwrap().base_units().is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Clock
+//!
+//! Events are stamped by the local clock, but some feeds carry the venue's
+//! own time (Pyth publish time). A local clock running off the chain's makes
+//! those look older or newer than they are. The RPC clock monitor measures
+//! the skew against block time, and venue timestamps are normalized onto the
+//! local clock with it:
+//! - `to_millis` takes seconds, milliseconds or microseconds to milliseconds
+//! - `normalize` also shifts by the measured skew and caps at the local now,
+//!   so a venue timestamp never lies in the future
+//! - `estimate_skew` takes the median of recent samples, as block times are
+//!   whole seconds
+//!
+//! Local timestamps (and everything compared against them) are left as they
+//! are, so latencies and history windows stay on one clock.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+
+/// Timestamps below this are taken to be in seconds
+const SECONDS_CUTOFF: i64 = 100_000_000_000;
+/// Timestamps above this are taken to be in microseconds
+const MICROS_CUTOFF: i64 = 100_000_000_000_000;
+
+/// Local clock minus chain clock (ms), as last measured
+static SKEW_MS: AtomicI64 = AtomicI64::new(0);
+
+/// Seconds, milliseconds or microseconds since the epoch, in milliseconds
+pub fn to_millis(timestamp: i64) -> i64 {
+    if timestamp < SECONDS_CUTOFF {
+        timestamp * 1000
+    } else if timestamp > MICROS_CUTOFF {
+        timestamp / 1000
+    } else {
+        timestamp
+    }
+}
+
+/// Measured skew of the local clock (ms, positive when ahead of the chain)
+pub fn skew_ms() -> i64 {
+    SKEW_MS.load(Ordering::Relaxed)
+}
+
+/// Apply a new skew measurement
+pub fn set_skew_ms(skew_ms: i64) {
+    SKEW_MS.store(skew_ms, Ordering::Relaxed);
+}
+
+/// A venue timestamp on the local clock (ms)
+pub fn normalize(timestamp: i64) -> i64 {
+    normalize_at(timestamp, skew_ms(), chrono::Utc::now().timestamp_millis())
+}
+
+fn normalize_at(timestamp: i64, skew_ms: i64, now: i64) -> i64 {
+    (to_millis(timestamp) + skew_ms).min(now)
+}
+
+/// Median of skew samples (ms)
+pub fn estimate_skew(samples: &[i64]) -> Option<i64> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    Some(if sorted.len() % 2 == 0 { (sorted[mid - 1] + sorted[mid]) / 2 } else { sorted[mid] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_venue_timestamps() {
+        let now = 1_700_000_010_000;
+        assert_eq!(to_millis(1_700_000_000), 1_700_000_000_000);
+        assert_eq!(to_millis(1_700_000_000_000), 1_700_000_000_000);
+        assert_eq!(to_millis(1_700_000_000_000_000), 1_700_000_000_000);
+
+        // A clock 3s ahead sees venue times 3s later
+        assert_eq!(normalize_at(1_700_000_000, 3_000, now), 1_700_000_003_000);
+        // Never in the future
+        assert_eq!(normalize_at(1_700_000_020, 0, now), now);
+
+        // One outlier does not move the estimate
+        assert_eq!(estimate_skew(&[800, 900, 15_000, 700, 1_000]), Some(900));
+        assert_eq!(estimate_skew(&[]), None);
+    }
+}
@@ -1,6 +1,7 @@
 //! Helper functions
 
 use std::time::{SystemTime, UNIX_EPOCH, Instant};
+use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Keypair;
 use anyhow::{Result, Context};
 use std::path::Path;
@@ -52,6 +53,30 @@ pub fn load_keypair_from_env_or_file(env_var: &str, file_path: &Path) -> Result<
     load_keypair(file_path)
 }
 
+/// SPL Token program ID
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// Associated Token Account program ID
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Derive the associated token account for an owner and mint
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let token_program: Pubkey = TOKEN_PROGRAM_ID.parse().unwrap();
+    let ata_program: Pubkey = ASSOCIATED_TOKEN_PROGRAM_ID.parse().unwrap();
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ata_program,
+    ).0
+}
+
+/// Derive the Drift user account of `authority`'s sub-account
+pub fn drift_user_address(program_id: &Pubkey, authority: &Pubkey, sub_account: u16) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"user", authority.as_ref(), &sub_account.to_le_bytes()],
+        program_id,
+    ).0
+}
+
 pub fn format_price(price: f64) -> String {
     if price >= 1000.0 {
         format!("{:.2}", price)
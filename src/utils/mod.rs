@@ -2,6 +2,8 @@
 
 pub mod types;
 pub mod helpers;
+pub mod clock;
+pub mod amount;
 
 pub use types::*;
 pub use helpers::*;
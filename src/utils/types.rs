@@ -118,13 +118,53 @@ impl Position {
 }
 
 /// Funding rate snapshot
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct FundingSnapshot {
     pub timestamp: i64,
     pub rate: f64,
     pub apr: f64,
 }
 
+/// How often a perp venue settles funding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FundingInterval {
+    /// Settled every hour (Drift, Hyperliquid)
+    #[default]
+    #[serde(rename = "1h")]
+    Hourly,
+    /// Settled every 8 hours (most CEXs)
+    #[serde(rename = "8h")]
+    EightHourly,
+    /// Accrued continuously; the venue quotes an hourly-equivalent rate
+    #[serde(rename = "continuous")]
+    Continuous,
+}
+
+impl FundingInterval {
+    /// Hours covered by one quoted funding rate
+    pub fn period_hours(&self) -> f64 {
+        match self {
+            FundingInterval::Hourly | FundingInterval::Continuous => 1.0,
+            FundingInterval::EightHourly => 8.0,
+        }
+    }
+
+    /// Funding periods per year
+    pub fn periods_per_year(&self) -> f64 {
+        24.0 * 365.0 / self.period_hours()
+    }
+
+    /// Annualize a per-period rate to an APR (%)
+    pub fn annualize(&self, rate: f64) -> f64 {
+        rate * self.periods_per_year() * 100.0
+    }
+
+    /// Normalize a per-period rate to a per-hour rate
+    pub fn hourly_rate(&self, rate: f64) -> f64 {
+        rate / self.period_hours()
+    }
+}
+
 /// Trade signal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeSignal {
@@ -135,11 +175,42 @@ pub struct TradeSignal {
     pub expected_profit: f64,
     pub confidence: f64,
     pub timestamp: i64,
+    /// Arrival of the newest price the signal was evaluated on (ms)
+    #[serde(default)]
+    pub market_timestamp: i64,
     pub reason: String,
+    /// Expected value of an open and its inputs
+    #[serde(default)]
+    pub expected_value: Option<ExpectedValue>,
+}
+
+/// Expected P&L of a basis trade at entry, with its inputs (USD unless noted)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExpectedValue {
+    pub notional_usd: f64,
+    /// Expected hold, cut short by a predicted funding zero crossing
+    pub hold_hours: f64,
+    /// Basis half-life the hold and exit basis were estimated from (hours)
+    #[serde(default)]
+    pub half_life_hours: Option<f64>,
+    /// Average funding APR expected over the hold (%, in the position's favour)
+    pub funding_apr_pct: f64,
+    pub expected_funding: f64,
+    pub entry_basis_pct: f64,
+    /// Basis expected at exit (%)
+    pub exit_basis_pct: f64,
+    pub expected_convergence: f64,
+    /// Convergence profit per hour of the hold
+    #[serde(default)]
+    pub convergence_per_hour: f64,
+    /// Fees, perp book cost and spot swap cost, entry and exit
+    pub expected_costs: f64,
+    /// Funding plus convergence, less costs
+    pub ev: f64,
 }
 
 /// Signal types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SignalType {
     OpenBasis,
@@ -148,6 +219,139 @@ pub enum SignalType {
     Hold,
 }
 
+/// Filter that blocked an open signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Risk rules paused trading
+    RiskPause,
+    /// Operator paused trading
+    OperatorPause,
+    /// Signal cooldown or minimum interval since the last trade
+    Cooldown,
+    /// Outside the configured trading windows
+    TradingWindow,
+    /// Funding reversing
+    Reversal,
+    /// Basis regime trending
+    Regime,
+    /// Pre-trade risk check failed
+    RiskCheck,
+    /// No capital left to size the trade
+    Capital,
+    /// Basis below the first entry ladder level
+    Ladder,
+    /// Pyth stale, spot priced from the secondary oracles
+    OracleOutage,
+    /// An API the trade depends on is cooling off after repeated failures
+    VenueDegraded,
+    /// Same setup as the last failed or closed entry, without a better edge
+    Reentry,
+    /// USDC trading outside its peg band
+    UsdcDepeg,
+    /// A live order failed the pre-trade checklist
+    PreTradeCheck,
+}
+
+/// External API behind a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dependency {
+    /// Price and quote/swap API (spot leg)
+    Jupiter,
+    /// Drift market data API (perp prices and funding)
+    DriftData,
+    /// Jito block engine (bundles)
+    Jito,
+}
+
+impl Dependency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Jupiter => "jupiter",
+            Self::DriftData => "drift_data",
+            Self::Jito => "jito",
+        }
+    }
+
+    /// Whether new trades cannot be placed without it; Jito bundles fall
+    /// back to regular sends
+    pub fn blocks_entries(&self) -> bool {
+        !matches!(self, Self::Jito)
+    }
+}
+
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Why a basis trade was closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", from = "String")]
+pub enum CloseReason {
+    /// Basis fell inside `basis_close_threshold_pct`
+    BasisConverged,
+    /// Position loss reached the stop
+    StopLoss,
+    /// Held for `max_hold_time_hours`
+    MaxHold,
+    /// Critical funding reversal
+    ReversalCritical,
+    /// Operator close-all or manual close
+    ManualClose,
+    /// A risk limit other than the stop (drawdown) forced the exit
+    RiskPause,
+    /// Unwound after a rebalance got stuck
+    Rebalance,
+    /// Unwound after an open or close got stuck
+    Timeout,
+    /// Recorded before reasons were tracked
+    Unknown,
+}
+
+impl CloseReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BasisConverged => "basis_converged",
+            Self::StopLoss => "stop_loss",
+            Self::MaxHold => "max_hold",
+            Self::ReversalCritical => "reversal_critical",
+            Self::ManualClose => "manual_close",
+            Self::RiskPause => "risk_pause",
+            Self::Rebalance => "rebalance",
+            Self::Timeout => "timeout",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for CloseReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Older trade stores hold free-form reasons
+impl From<String> for CloseReason {
+    fn from(reason: String) -> Self {
+        [
+            Self::BasisConverged,
+            Self::StopLoss,
+            Self::MaxHold,
+            Self::ReversalCritical,
+            Self::ManualClose,
+            Self::RiskPause,
+            Self::Rebalance,
+            Self::Timeout,
+        ]
+        .into_iter()
+        .find(|r| r.as_str() == reason)
+        .unwrap_or(Self::Unknown)
+    }
+}
+
 /// Trade record for history
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeRecord {
@@ -176,7 +380,7 @@ pub enum TradeType {
 }
 
 /// Price update from feeds
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceUpdate {
     pub source: PriceSource,
     pub price: f64,
@@ -184,13 +388,99 @@ pub struct PriceUpdate {
     pub timestamp: i64,
 }
 
+/// Effective spot prices for a fixed trade size, from aggregator quotes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DepthQuote {
+    /// Quoted trade size (SOL)
+    pub size_sol: f64,
+    /// Average price paid to buy `size_sol`
+    pub buy_price: f64,
+    /// Average price received selling `size_sol`
+    pub sell_price: f64,
+    pub timestamp: i64,
+}
+
+impl DepthQuote {
+    /// Buy/sell spread relative to the midpoint (%)
+    pub fn spread_pct(&self) -> f64 {
+        let mid = (self.buy_price + self.sell_price) / 2.0;
+        if mid > 0.0 { (self.buy_price - self.sell_price) / mid * 100.0 } else { 0.0 }
+    }
+}
+
+/// Single order book price level
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub price: f64,
+    /// Size at this level (base asset)
+    pub size: f64,
+}
+
+/// L2 order book snapshot, best levels first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderBook {
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+impl OrderBook {
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|l| l.price)
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|l| l.price)
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_bid()? + self.best_ask()?) / 2.0)
+    }
+
+    /// Bid/ask spread relative to the mid (%)
+    pub fn spread_pct(&self) -> Option<f64> {
+        let mid = self.mid()?;
+        Some((self.best_ask()? - self.best_bid()?) / mid * 100.0)
+    }
+
+    /// Average fill price for a market order of `size` walking the book.
+    ///
+    /// Returns None if the visible depth cannot fill the whole size.
+    pub fn fill_price(&self, size: f64, is_buy: bool) -> Option<f64> {
+        let levels = if is_buy { &self.asks } else { &self.bids };
+        let mut remaining = size;
+        let mut cost = 0.0;
+
+        for level in levels {
+            let take = remaining.min(level.size);
+            cost += take * level.price;
+            remaining -= take;
+            if remaining <= 0.0 {
+                return Some(cost / size);
+            }
+        }
+        None
+    }
+
+    /// Cost of a market order of `size` relative to the mid (%, always >= 0)
+    pub fn taker_cost_pct(&self, size: f64, is_buy: bool) -> Option<f64> {
+        let mid = self.mid()?;
+        let fill = self.fill_price(size, is_buy)?;
+        Some((fill - mid).abs() / mid * 100.0)
+    }
+}
+
 /// Price sources
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PriceSource {
     Pyth,
     Jupiter,
+    Switchboard,
     DriftMark,
     DriftIndex,
+    /// Aggregate of the spot sources
+    Composite,
 }
 
 impl fmt::Display for PriceSource {
@@ -198,10 +488,129 @@ impl fmt::Display for PriceSource {
         match self {
             PriceSource::Pyth => write!(f, "Pyth"),
             PriceSource::Jupiter => write!(f, "Jupiter"),
+            PriceSource::Switchboard => write!(f, "Switchboard"),
             PriceSource::DriftMark => write!(f, "Drift Mark"),
             PriceSource::DriftIndex => write!(f, "Drift Index"),
+            PriceSource::Composite => write!(f, "Composite"),
+        }
+    }
+}
+
+/// Whether the wallet can pay for transactions and tips
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GasLevel {
+    Ok,
+    /// Below the warning threshold; top up soon
+    Low,
+    /// Below the critical threshold; no new entries
+    Critical,
+}
+
+impl GasLevel {
+    /// Level for a SOL balance given the warning and critical thresholds
+    pub fn classify(sol_balance: f64, warn_sol: f64, critical_sol: f64) -> Self {
+        if sol_balance < critical_sol {
+            Self::Critical
+        } else if sol_balance < warn_sol {
+            Self::Low
+        } else {
+            Self::Ok
+        }
+    }
+}
+
+impl fmt::Display for GasLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GasLevel::Ok => write!(f, "ok"),
+            GasLevel::Low => write!(f, "low"),
+            GasLevel::Critical => write!(f, "critical"),
         }
     }
 }
 
+/// Latest wallet SOL balance check
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GasStatus {
+    pub sol_balance: f64,
+    pub level: GasLevel,
+    /// Check timestamp (ms)
+    pub timestamp: i64,
+}
+
+/// Latest realized volatility reading
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VolatilityStatus {
+    /// Annualized realized volatility of spot returns (%)
+    pub realized_vol_pct: f64,
+    /// Trigger percentile of past readings (%); None until enough history
+    pub threshold_pct: Option<f64>,
+    /// Percentile rank of this reading among past readings (0-100)
+    pub percentile: Option<f64>,
+    /// Above the threshold: de-risk
+    pub elevated: bool,
+    /// Reading timestamp (ms)
+    pub timestamp: i64,
+}
+
+/// Entry thresholds the signal engine opens on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EntryThresholds {
+    pub min_basis_spread_pct: f64,
+    pub min_funding_apr_pct: f64,
+}
+
+/// Trade-frequency governor reading
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GovernorStatus {
+    /// Steps the entry thresholds are raised by
+    pub level: u32,
+    /// Multiplier on the entry thresholds
+    pub multiplier: f64,
+    /// Mean net ROI of the recent trades (%)
+    pub recent_edge_pct: Option<f64>,
+    /// Mean net ROI of the baseline trades (%)
+    pub baseline_edge_pct: Option<f64>,
+}
+
+impl Default for GovernorStatus {
+    fn default() -> Self {
+        Self { level: 0, multiplier: 1.0, recent_edge_pct: None, baseline_edge_pct: None }
+    }
+}
+
 pub type AppResult<T> = anyhow::Result<T>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_book_fill_price() {
+        let book = OrderBook {
+            bids: vec![
+                BookLevel { price: 99.9, size: 10.0 },
+                BookLevel { price: 99.5, size: 10.0 },
+            ],
+            asks: vec![
+                BookLevel { price: 100.1, size: 5.0 },
+                BookLevel { price: 100.5, size: 20.0 },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(book.mid(), Some(100.0));
+        assert!((book.fill_price(15.0, false).unwrap() - 99.7666).abs() < 1e-3);
+        assert!((book.fill_price(10.0, true).unwrap() - 100.3).abs() < 1e-9);
+        assert!((book.taker_cost_pct(10.0, true).unwrap() - 0.3).abs() < 1e-9);
+        assert_eq!(book.fill_price(100.0, true), None);
+    }
+
+    #[test]
+    fn test_gas_level() {
+        assert_eq!(GasLevel::classify(0.5, 0.1, 0.02), GasLevel::Ok);
+        assert_eq!(GasLevel::classify(0.05, 0.1, 0.02), GasLevel::Low);
+        assert_eq!(GasLevel::classify(0.01, 0.1, 0.02), GasLevel::Critical);
+    }
+}
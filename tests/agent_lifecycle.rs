@@ -0,0 +1,220 @@
+//! Agent Lifecycle Test
+//!
+//! Drives the full agent through signal → open → monitor → close with a
+//! scripted market from `feeds::mock` and no network access.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+use sol_basis_bot::engines::signal_engine::{FullTradeSignal, SignalEvaluation};
+use sol_basis_bot::execution::{MockExecution, MockLeg};
+use sol_basis_bot::feeds::{MarketTick, MockFeed};
+use sol_basis_bot::utils::types::{SignalType, TradeSignal};
+use sol_basis_bot::{AgentState, AppConfig, EventBus, PnlAttribution, PositionManager, SharedState, TradingAgent};
+
+fn open_signal(basis: f64, funding_apr: f64) -> FullTradeSignal {
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    FullTradeSignal {
+        signal: TradeSignal {
+            signal_type: SignalType::OpenBasis,
+            size: 1.0,
+            basis_spread: basis,
+            funding_apr,
+            expected_profit: 10.0,
+            confidence: 0.9,
+            timestamp,
+            market_timestamp: timestamp,
+            reason: "scripted".to_string(),
+            expected_value: None,
+        },
+        funding: None,
+        basis: None,
+        evaluation: SignalEvaluation {
+            should_open: true,
+            should_close: false,
+            should_rebalance: false,
+            recommended_size: 1.0,
+            confidence: 0.9,
+            factors: vec![],
+            expected_profit: 10.0,
+            perp_execution_cost_pct: None,
+            expected_value: None,
+            reasons: vec!["scripted".to_string()],
+            blocked: None,
+            mark_index_divergence_pct: None,
+            timestamp,
+        },
+    }
+}
+
+async fn wait_for(agent: &TradingAgent, target: AgentState) {
+    for _ in 0..200 {
+        if agent.current_state().await == target {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("agent never reached {:?}, stuck in {:?}", target, agent.current_state().await);
+}
+
+fn lifecycle_config(dir: &Path, paper_trading: bool) -> AppConfig {
+    let mut config = AppConfig::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("config.yaml")).unwrap();
+    config.paper_trading = paper_trading;
+    config.trading_windows.enabled = false;
+    config.execution.passive_entry.enabled = false;
+    config.agentic.performance_db_path = dir.join("performance.json").to_string_lossy().into_owned();
+    config
+}
+
+#[tokio::test]
+async fn test_signal_open_monitor_close() {
+    let dir = std::env::temp_dir().join(format!("lifecycle-{}", uuid::Uuid::new_v4()));
+    let config = lifecycle_config(&dir, true);
+    let close_threshold = config.trading.basis_close_threshold_pct;
+    let config = Arc::new(config);
+
+    let state = Arc::new(SharedState::new());
+    *state.rpc_connected.write() = true;
+    let bus = EventBus::new(256);
+    let feed = MockFeed::new(state.clone(), bus.sender());
+    let execution = Arc::new(MockExecution::new(state.clone()));
+
+    // Wide basis with rich funding
+    feed.push([MarketTick::with_basis(150.0, 0.6).funding(0.0002)]);
+    assert!(feed.advance());
+
+    let signals = Arc::new(RwLock::new(None));
+    let agent = TradingAgent::new(
+        config.clone(),
+        state.clone(),
+        Arc::new(PositionManager::new(state.clone())),
+        bus.sender(),
+        signals.clone(),
+        Arc::new(RwLock::new(None)),
+    )
+    .await
+    .unwrap()
+    .with_execution(execution.clone());
+    agent.start().await.unwrap();
+
+    *signals.write().await = Some(open_signal(0.6, 17.5));
+    wait_for(&agent, AgentState::Monitoring).await;
+    assert!(state.spot_position.read().is_some());
+
+    // Basis converges inside the close threshold
+    feed.push([MarketTick::with_basis(151.0, close_threshold / 2.0).funding(0.0002)]);
+    assert!(feed.advance());
+    wait_for(&agent, AgentState::Idle).await;
+
+    let trades = agent.performance_db().get_all_trades().await;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].close_reason, "basis_converged");
+    assert!(trades[0].entry_basis > close_threshold);
+
+    // Paper mode leaves the live executor untouched
+    assert!(execution.fills().is_empty());
+
+    agent.stop().await;
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_live_legs_charge_measured_slippage() {
+    let dir = std::env::temp_dir().join(format!("lifecycle-{}", uuid::Uuid::new_v4()));
+    let config = lifecycle_config(&dir, false);
+    let close_threshold = config.trading.basis_close_threshold_pct;
+    let config = Arc::new(config);
+
+    let state = Arc::new(SharedState::new());
+    *state.rpc_connected.write() = true;
+    let bus = EventBus::new(256);
+    let feed = MockFeed::new(state.clone(), bus.sender());
+    let execution = Arc::new(MockExecution::new(state.clone()).with_slippage_bps(10.0).with_fee_bps(5.0));
+
+    feed.push([MarketTick::with_basis(150.0, 0.6).funding(0.0002)]);
+    assert!(feed.advance());
+
+    let signals = Arc::new(RwLock::new(None));
+    let agent = TradingAgent::new(
+        config.clone(),
+        state.clone(),
+        Arc::new(PositionManager::new(state.clone())),
+        bus.sender(),
+        signals.clone(),
+        Arc::new(RwLock::new(None)),
+    )
+    .await
+    .unwrap()
+    .with_execution(execution.clone());
+    agent.start().await.unwrap();
+
+    // Live, both legs are filled by the executor
+    *signals.write().await = Some(open_signal(0.6, 17.5));
+    wait_for(&agent, AgentState::Monitoring).await;
+    assert!(execution.net_filled(MockLeg::Spot) > 0.0);
+    assert!(execution.net_filled(MockLeg::Perp) < 0.0);
+
+    feed.push([MarketTick::with_basis(151.0, close_threshold / 2.0).funding(0.0002)]);
+    assert!(feed.advance());
+    wait_for(&agent, AgentState::Idle).await;
+
+    // 10 bps on each of the four fills lands in the slippage bucket
+    let trades = agent.performance_db().get_all_trades().await;
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert!(trade.slippage_cost > 0.0);
+    // Priced from the fills, net of their slippage, with fees on all four
+    assert!((trade.entry_spot - 150.0).abs() < 1e-9);
+    let notional = (trade.entry_spot + trade.entry_perp + trade.exit_spot + trade.exit_perp) * trade.size;
+    assert!((trade.fees_paid - notional * 5.0 / 10_000.0).abs() < 0.01);
+    let attribution = PnlAttribution::from_trade(trade);
+    assert!((attribution.slippage + trade.slippage_cost).abs() < 1e-9);
+    let identity = attribution.convergence + attribution.funding + attribution.directional
+        - trade.fees_paid - trade.slippage_cost;
+    assert!((identity - trade.total_pnl).abs() < 1e-6);
+
+    agent.stop().await;
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_resume_returns_to_the_open_position() {
+    let dir = std::env::temp_dir().join(format!("lifecycle-{}", uuid::Uuid::new_v4()));
+    let config = Arc::new(lifecycle_config(&dir, true));
+
+    let state = Arc::new(SharedState::new());
+    *state.rpc_connected.write() = true;
+    let bus = EventBus::new(256);
+    let feed = MockFeed::new(state.clone(), bus.sender());
+    feed.push([MarketTick::with_basis(150.0, 0.6).funding(0.0002)]);
+    assert!(feed.advance());
+
+    let signals = Arc::new(RwLock::new(None));
+    let agent = TradingAgent::new(
+        config.clone(),
+        state.clone(),
+        Arc::new(PositionManager::new(state.clone())),
+        bus.sender(),
+        signals.clone(),
+        Arc::new(RwLock::new(None)),
+    )
+    .await
+    .unwrap();
+    agent.start().await.unwrap();
+
+    *signals.write().await = Some(open_signal(0.6, 17.5));
+    wait_for(&agent, AgentState::Monitoring).await;
+
+    agent.control().pause("operator check");
+    wait_for(&agent, AgentState::Paused).await;
+
+    // The position is still open, so the agent goes back to monitoring it
+    agent.control().resume();
+    wait_for(&agent, AgentState::Monitoring).await;
+    assert!(state.spot_position.read().is_some());
+
+    agent.stop().await;
+    let _ = std::fs::remove_dir_all(dir);
+}
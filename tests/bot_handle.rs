@@ -0,0 +1,61 @@
+//! Bot Handle Test
+//!
+//! Reads a paper agent through the library handle, as an embedding crate
+//! would.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+use sol_basis_bot::feeds::{MarketTick, MockFeed};
+use sol_basis_bot::{AgentState, AppConfig, BotHandle, Event, EventBus, PositionManager, SharedState, TradingAgent};
+
+#[tokio::test]
+async fn test_handle_reads_agent() {
+    let dir = std::env::temp_dir().join(format!("bot-handle-{}", uuid::Uuid::new_v4()));
+    let mut config = AppConfig::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("config.yaml")).unwrap();
+    config.paper_trading = true;
+    config.agentic.performance_db_path = dir.join("performance.json").to_string_lossy().into_owned();
+    let config = Arc::new(config);
+
+    let state = Arc::new(SharedState::new());
+    let bus = EventBus::new(256);
+    let feed = MockFeed::new(state.clone(), bus.sender());
+    feed.push([MarketTick::with_basis(150.0, 0.4)]);
+    assert!(feed.advance());
+
+    let agent = TradingAgent::new(
+        config,
+        state.clone(),
+        Arc::new(PositionManager::new(state.clone())),
+        bus.sender(),
+        Arc::new(RwLock::new(None)),
+        Arc::new(RwLock::new(None)),
+    )
+    .await
+    .unwrap();
+    let handle = BotHandle::new(Arc::new(agent)).with_events(bus.subscribe());
+
+    // Clones read the same bot
+    let reader = handle.clone();
+    assert_eq!(reader.snapshot(), state.snapshot());
+    assert_eq!(reader.snapshot().spot_price, 150.0);
+    assert_eq!(reader.status().await.state, AgentState::Idle);
+    assert_eq!(reader.metrics().await.total_trades, 0);
+    assert!(reader.recent_trades(10).await.is_empty());
+    assert!(reader.latest_signal().await.is_none());
+    assert_eq!(reader.positions().await.spot_size, 0.0);
+
+    let _ = bus.sender().send(Event::HedgeDriftAlert { drift_pct: 2.5, threshold_pct: 2.0 });
+    for _ in 0..50 {
+        if !reader.recent_signals().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(handle.recent_signals().len(), 1);
+    assert!(handle.recent_alerts().is_empty());
+
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,41 @@
+//! Canned Stress Scenarios
+//!
+//! Runs the scenarios in `scenarios/` against the default config.
+
+use std::path::Path;
+
+use sol_basis_bot::scenario::{self, Scenario};
+use sol_basis_bot::AppConfig;
+
+async fn assert_scenario(name: &str) {
+    let config = AppConfig::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("config.yaml")).unwrap();
+    let scenario = Scenario::canned_named(name).unwrap();
+    let report = scenario::run(&scenario, &config).await.unwrap();
+    assert!(report.passed(), "{}", report.summary());
+}
+
+#[tokio::test]
+async fn test_basis_convergence() {
+    assert_scenario("basis_convergence").await;
+}
+
+#[tokio::test]
+async fn test_usdc_depeg() {
+    assert_scenario("usdc_depeg").await;
+}
+
+#[tokio::test]
+async fn test_oracle_stall() {
+    assert_scenario("oracle_stall").await;
+}
+
+#[tokio::test]
+async fn test_rpc_outage() {
+    assert_scenario("rpc_outage").await;
+}
+
+#[tokio::test]
+#[ignore = "runs in real time (12 minutes): the reversal detector samples on the wall clock"]
+async fn test_funding_reversal() {
+    assert_scenario("funding_reversal").await;
+}